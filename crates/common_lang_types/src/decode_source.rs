@@ -0,0 +1,68 @@
+/// The byte order mark that some editors (e.g. on Windows) add to the start of
+/// UTF-8 files.
+const BYTE_ORDER_MARK: &[u8] = "\u{feff}".as_bytes();
+
+/// The content of a schema or source file, decoded as UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedSource {
+    pub content: String,
+    /// The byte offset (into the content) of the first invalid UTF-8 sequence, if any.
+    pub first_invalid_byte: Option<usize>,
+}
+
+/// Decode the content of a schema or source file.
+///
+/// A leading byte order mark is stripped, since it is not part of the content, and
+/// spans are relative to the content without it. Invalid UTF-8 sequences are
+/// replaced with U+FFFD, so that e.g. a comment saved in another encoding does not
+/// prevent the rest of the file from being compiled.
+///
+/// Files are re-read with this function when printing diagnostics, so that spans
+/// point at the same text.
+pub fn decode_source(bytes: &[u8]) -> DecodedSource {
+    let bytes = bytes.strip_prefix(BYTE_ORDER_MARK).unwrap_or(bytes);
+    DecodedSource {
+        content: String::from_utf8_lossy(bytes).into_owned(),
+        first_invalid_byte: std::str::from_utf8(bytes)
+            .err()
+            .map(|error| error.valid_up_to()),
+    }
+}
+
+/// The (1-based) line and column of a byte offset into the content.
+pub fn line_and_column(content: &str, byte_offset: usize) -> (usize, usize) {
+    let before = &content[..byte_offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_byte_order_mark() {
+        assert_eq!(
+            decode_source(b"\xEF\xBB\xBFtype Query { id: ID! }"),
+            DecodedSource {
+                content: "type Query { id: ID! }".to_string(),
+                first_invalid_byte: None,
+            }
+        );
+        // Only a leading byte order mark is stripped
+        assert_eq!(
+            decode_source("a\u{feff}".as_bytes()).content,
+            "a\u{feff}".to_string()
+        );
+    }
+
+    #[test]
+    fn replaces_invalid_utf8() {
+        let decoded = decode_source(b"\xEF\xBB\xBF// caf\xE9\nexport const a = 1;");
+        assert_eq!(decoded.content, "// caf\u{fffd}\nexport const a = 1;");
+        assert_eq!(decoded.first_invalid_byte, Some(6));
+        assert_eq!(line_and_column(&decoded.content, 6), (1, 7));
+        assert_eq!(line_and_column(&decoded.content, 10), (2, 1));
+    }
+}
@@ -0,0 +1,499 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::{Location, WithLocation};
+
+/// A stable identifier for a kind of error, displayed as e.g. IS0104.
+///
+/// Codes are grouped by hundreds according to the phase in which the error
+/// occurs. Once assigned, a code must never be reused for a different error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorCode(pub u16);
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IS{:04}", self.0)
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .strip_prefix("IS")
+            .or_else(|| s.strip_prefix("is"))
+            .unwrap_or(s);
+        digits.parse::<u16>().map(ErrorCode).map_err(|_| ())
+    }
+}
+
+pub trait HasErrorCode {
+    fn error_code(&self) -> ErrorCode;
+}
+
+/// An error that is reported as a warning, e.g. because a validation is configured
+/// to warn. Its code and location are reported along with it, so that tools (e.g.
+/// with --output-format=json) receive them as they do for errors.
+pub trait Warning: fmt::Display {
+    fn warning_code(&self) -> ErrorCode;
+
+    /// The message, without the location.
+    fn warning_message(&self) -> String;
+
+    fn warning_location(&self) -> Location;
+}
+
+impl<T: HasErrorCode + fmt::Display> Warning for T {
+    fn warning_code(&self) -> ErrorCode {
+        self.error_code()
+    }
+
+    fn warning_message(&self) -> String {
+        self.to_string()
+    }
+
+    fn warning_location(&self) -> Location {
+        Location::generated()
+    }
+}
+
+impl<T: HasErrorCode + fmt::Display> Warning for WithLocation<T> {
+    fn warning_code(&self) -> ErrorCode {
+        self.item.error_code()
+    }
+
+    fn warning_message(&self) -> String {
+        self.item.to_string()
+    }
+
+    fn warning_location(&self) -> Location {
+        self.location
+    }
+}
+
+/// An error whose type has been erased, but whose code has been preserved.
+///
+/// This is useful when passing errors across an API boundary that only knows
+/// about Box<dyn Error>, e.g. between a network protocol and the compiler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodedError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for CodedError {}
+
+impl CodedError {
+    pub fn new<T: HasErrorCode + fmt::Display>(error: T) -> Self {
+        CodedError {
+            code: error.error_code(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl HasErrorCode for CodedError {
+    fn error_code(&self) -> ErrorCode {
+        self.code
+    }
+}
+
+pub struct ErrorCodeInfo {
+    pub code: ErrorCode,
+    pub name: &'static str,
+    pub explanation: &'static str,
+}
+
+pub fn explain_error_code(code: ErrorCode) -> Option<&'static ErrorCodeInfo> {
+    error_codes::ERROR_CODES
+        .iter()
+        .find(|info| info.code == code)
+}
+
+macro_rules! define_error_codes {
+    ($($code:ident = $number:literal, $name:literal, $explanation:literal;)*) => {
+        $(
+            pub const $code: ErrorCode = ErrorCode($number);
+        )*
+
+        pub static ERROR_CODES: &[ErrorCodeInfo] = &[
+            $(
+                ErrorCodeInfo {
+                    code: $code,
+                    name: $name,
+                    explanation: $explanation,
+                },
+            )*
+        ];
+    };
+}
+
+pub mod error_codes {
+    use super::{ErrorCode, ErrorCodeInfo};
+
+    define_error_codes! {
+        // IS00xx: parsing iso literals
+        IS0001 = 1, "ParseError",
+            "The iso literal could not be tokenized or parsed. The message describes \
+            which token was expected.";
+        IS0002 = 2, "ExpectedTypeAnnotation",
+            "A type annotation, such as String, [String] or String!, was expected. \
+            Type annotations are required for variable definitions.";
+        IS0003 = 3, "LeftoverTokens",
+            "The iso literal was parsed successfully, but text remained after the end \
+            of the declaration. Each iso literal may contain exactly one declaration.";
+        IS0004 = 4, "ExpectedAssociatedJsFunction",
+            "Client field iso literals must be immediately called with the function \
+            that receives the selected data, e.g. iso(`field Query.Foo { id }`)(function Foo() {}).";
+        IS0005 = 5, "ExpectedFieldOrPointerOrEntrypoint",
+            "Iso literals must start with one of the keywords field, pointer or entrypoint.";
+        IS0006 = 6, "ExpectedTo",
+            "Client pointer declarations must specify their target type with the to \
+            keyword, e.g. pointer Query.BestFriend to User { ... }.";
+        IS0007 = 7, "ExpectedLiteralToBeExported",
+            "Client field and client pointer iso literals must be exported as a named \
+            export whose name matches the field name, so that generated artifacts can \
+            import them.";
+        IS0008 = 8, "ExpectedNonConstantValue",
+            "An argument value was expected, such as a variable ($foo), a number, a \
+            string, a boolean, null, an enum value, a list or an object.";
+        IS0009 = 9, "UnexpectedVariable",
+            "A variable was used where only constant values are allowed, such as in \
+            the default value of a variable definition.";
+        IS0010 = 10, "DescriptionsAreDisallowed",
+            "Descriptions are not allowed in this position, e.g. on entrypoints.";
+        IS0011 = 11, "ExpectedCommaOrLineBreak",
+            "Selections must be separated by a comma or a line break.";
+        IS0012 = 12, "ExpectedSelectionSet",
+            "Client fields and linked fields require a selection set. If no fields \
+            should be selected, write an empty selection set: {}.";
+        IS0013 = 13, "ExpectedParenthesesAroundIsoLiteral",
+            "The iso function must be called with parentheses, i.e. iso(`...`). The \
+            tagged template syntax iso`...` is not supported.";
+        IS0014 = 14, "DuplicateNameOrAlias",
+            "Two selections in the same selection set have the same name or alias. \
+            Alias one of them using `alias: field`.";
+        IS0016 = 16, "ExpectedDelimiterOrClosingToken",
+            "Items in a list must be separated by the delimiter, and the list must be \
+            closed with the closing token.";
+        IS0017 = 17, "UnableToDeserializeDirectives",
+            "The directives in this iso literal could not be processed. Check that \
+            each directive is supported in this position and has valid arguments.";
+        IS0018 = 18, "MultipleDescriptions",
+            "A client field or client pointer has a description both before the `field` \
+            or `pointer` keyword and before its selection set. Remove one of them.";
+        IS0019 = 19, "UnknownDirective",
+            "A directive is not supported in this position. Selections of scalar fields \
+            support @loadable, @updatable, @include and @skip, and selections of linked \
+            fields support @updatable, @include and @skip.";
+        IS0020 = 20, "InvalidSelectionCondition",
+            "@include and @skip must be passed exactly one argument, if, whose value is \
+            a variable or a boolean, e.g. @include(if: $showDetails).";
+
+        // IS01xx: processing the schema
+        IS0100 = 100, "DuplicateSchemaDefinition",
+            "The schema contains more than one schema definition (schema { ... }).";
+        IS0101 = 101, "AttemptedToExtendUndefinedType",
+            "A type extension (extend type Foo) refers to a type that is not defined \
+            in the schema.";
+        IS0102 = 102, "AttemptedToImplementNonExistentType",
+            "A type claims to implement an interface that is not defined in the schema.";
+        IS0103 = 103, "CompilerCreatedFieldExistsOnType",
+            "Isograph automatically creates some fields (such as __typename, __refetch \
+            and fields created by @exposeField). A field with the same name is already \
+            defined on the type.";
+        IS0104 = 104, "DuplicateField",
+            "The same field is defined twice on a type.";
+        IS0105 = 105, "InvalidField",
+            "The field argument of an @exposeField directive is not a valid path to a \
+            field.";
+        IS0106 = 106, "InvalidMutationField",
+            "The field exposed by an @exposeField directive is not a valid mutation field.";
+        IS0107 = 107, "PrimaryDirectiveArgumentDoesNotExistOnField",
+            "An item in the field_map of an @exposeField directive refers to an \
+            argument that does not exist on the exposed field, or that was already \
+            used by another field_map item.";
+        IS0108 = 108, "PrimaryDirectiveCannotRemapObject",
+            "An item in the field_map of an @exposeField directive refers to an object. \
+            Only scalars can be remapped.";
+        IS0109 = 109, "PrimaryDirectiveFieldNotFound",
+            "An item in the field_map of an @exposeField directive refers to a field \
+            that does not exist.";
+        IS0110 = 110, "FailedToDeserialize",
+            "A directive in the schema could not be deserialized. Check that its \
+            arguments are valid.";
+        IS0111 = 111, "IdFieldMustBeNonNullIdType",
+            "Fields named id must have type ID!. This validation can be configured with \
+            the on_invalid_id_type config option.";
+        IS0112 = 112, "FieldArgumentTypeDoesNotExist",
+            "The type of a field argument is not defined in the schema.";
+        IS0113 = 113, "FieldTypenameDoesNotExist",
+            "The type of a field is not defined in the schema.";
+        IS0114 = 114, "DuplicateTypeDefinition",
+            "Two types with the same name are defined in the schema.";
+        IS0115 = 115, "InvalidOpenApiDocument",
+            "With the rest network protocol, the schema and schema extensions must be \
+            OpenAPI 3 documents, in JSON. This file could not be parsed as one.";
+        IS0116 = 116, "UnsupportedOpenApiSchema",
+            "An OpenAPI schema uses a feature that Isograph does not support, such as \
+            oneOf, or a $ref to something other than #/components/schemas.";
+        IS0117 = 117, "InvalidProtoFile",
+            "With the grpc network protocol, the schema and schema extensions must be \
+            proto3 files. This file could not be parsed as one.";
+        IS0118 = 118, "ProtoRequestTypeIsNotAMessage",
+            "The request type of an RPC method must be a message defined in the schema \
+            or schema extensions.";
+        IS0119 = 119, "PrimaryFieldPathArgumentConflict",
+            "An argument of a field in the path of an @exposeField directive (e.g. of \
+            user in updateUser.user.profile) has the same name as another argument of \
+            the exposed field.";
+        IS0120 = 120, "NodeTypeMissingIdField",
+            "A type implements the Node interface, but has no id field. This is only \
+            reported if the on_missing_node_id_field config option is set to warn or error.";
+        IS0121 = 121, "ImplementedTypeIsNotAnInterface",
+            "A type or interface claims to implement a type (e.g. an object or union) \
+            that is not an interface.";
+        IS0122 = 122, "InterfaceImplementationCycle",
+            "Interfaces implement each other in a cycle, e.g. interface A implements B \
+            and interface B implements A.";
+        IS0123 = 123, "UnknownSchemaDirective",
+            "With the strict_schema option, every directive used in the schema must be \
+            defined in the schema, unless it is a built-in directive (such as \
+            @deprecated) or a directive that Isograph processes (such as @exposeField).";
+        IS0124 = 124, "DuplicateDirectiveDefinition",
+            "With the strict_schema option, each directive can only be defined once.";
+        IS0125 = 125, "DirectiveNotAllowedAtLocation",
+            "With the strict_schema option, a directive that is defined in the schema \
+            can only be used in the locations listed in its definition \
+            (e.g. directive @foo on FIELD_DEFINITION).";
+        IS0126 = 126, "UnionWithoutMembers",
+            "With the strict_schema option, every union must have at least one member.";
+        IS0127 = 127, "MissingQueryType",
+            "The schema does not define a query type. The query type is the type named \
+            in the schema definition (e.g. schema { query: QueryRoot }), or, if there \
+            is no schema definition, the type named by the query_type_name option \
+            (Query by default).";
+
+        // IS02xx: processing client field and client pointer declarations
+        IS0200 = 200, "ParentTypeNotDefined",
+            "The parent type of a client field or client pointer (i.e. Foo in \
+            field Foo.Bar) is not defined in the schema.";
+        IS0201 = 201, "DirectiveNotSupportedOnClientPointer",
+            "This directive can be used on client fields, but not on client pointers.";
+        IS0202 = 202, "InvalidParentType",
+            "Client fields and client pointers can only be defined on objects, \
+            interfaces and unions, not on scalars.";
+        IS0203 = 203, "ClientPointerInvalidTargetType",
+            "Client pointers must point to an object, interface or union, not to a scalar.";
+        IS0204 = 204, "ClientPointerTargetTypeHasNoId",
+            "Client pointers must point to a type with an id field, i.e. a type that \
+            implements the Node interface.";
+        IS0205 = 205, "ParentAlreadyHasField",
+            "A client field or client pointer has the same name as an existing field \
+            on the parent type.";
+        IS0206 = 206, "UnableToDeserializeClientFieldDirectives",
+            "The directives on this client field or client pointer could not be \
+            processed. Check that each directive is supported and has valid arguments.";
+        IS0207 = 207, "ClientPointerTargetTypeNotDefined",
+            "The target type of a client pointer (i.e. Bar in pointer Foo.baz to Bar) \
+            is not defined in the schema.";
+        IS0208 = 208, "OverrideHasDifferentVariables",
+            "A client field defined on a concrete type overrides a client field with the \
+            same name defined on an abstract type (e.g. an interface) that it belongs to, \
+            but declares different variables.";
+        IS0209 = 209, "OverrideHasDifferentKind",
+            "A client field defined on a concrete type overrides a client field with the \
+            same name defined on an abstract type that it belongs to, but only one of them \
+            is a component.";
+        IS0210 = 210, "DuplicateClientTypeDeclaration",
+            "The same client field or client pointer (e.g. field Query.Foo) is declared \
+            more than once, possibly in different files. Rename or remove one of the \
+            declarations.";
+        IS0211 = 211, "PluralPathNotSelected",
+            "A client field declared with @plural(path: ...) does not select the linked \
+            field at that path.";
+        IS0212 = 212, "PluralPathNotPlural",
+            "The path of a client field declared with @plural(path: ...) must be a \
+            linked field that returns a list, so that the resolver can return an item \
+            for each of its items.";
+        IS0213 = 213, "SelectionOutsidePluralPath",
+            "A client field declared with @plural(path: ...) must select everything \
+            within that path. Other top-level selections are not allowed.";
+        IS0214 = 214, "NodePointerParentIsNotQuery",
+            "A client pointer without a selection set (e.g. pointer Query.currentUser \
+            to User) is a node pointer, which can only be defined on Query.";
+        IS0215 = 215, "NodePointerHasVariables",
+            "A node pointer is passed the id of the record that it points to, and \
+            cannot declare other variables.";
+        IS0216 = 216, "NodePointerTargetIsNotFetchableByNode",
+            "A node pointer is fetched with Query.node, so Query must have a node \
+            field, and the target of the pointer must be a concrete type that \
+            implements Node.";
+
+        // IS03xx: validating entrypoints
+        IS0300 = 300, "EntrypointParentTypeNotDefined",
+            "The parent type of an entrypoint (i.e. Foo in entrypoint Foo.Bar) is not \
+            defined in the schema.";
+        IS0301 = 301, "EntrypointInvalidParentType",
+            "Entrypoints can only be defined on objects and interfaces.";
+        IS0302 = 302, "NonFetchableParentType",
+            "Entrypoints must be defined on a type that can be fetched, such as Query \
+            or Mutation.";
+        IS0303 = 303, "ClientFieldMustExist",
+            "An entrypoint refers to a client field that is not defined.";
+        IS0304 = 304, "FieldMustBeClientField",
+            "Entrypoints must refer to client fields, not to server fields.";
+        IS0305 = 305, "LazyLoadInconsistentEntrypoint",
+            "The same entrypoint is declared with @lazyLoad in one location, and \
+            without it in another. It must be declared consistently.";
+        IS0306 = 306, "DuplicateConfigEntrypoint",
+            "The same entrypoint is listed more than once in the entrypoints of the config.";
+
+        // IS04xx: validating selection sets
+        IS0400 = 400, "SelectionFieldDoesNotExist",
+            "A selection refers to a field that does not exist on the parent type.";
+        IS0401 = 401, "SelectionFieldIsNotScalar",
+            "A field whose type is an object is selected without a selection set. \
+            Add a selection set, or select a scalar field instead.";
+        IS0402 = 402, "SelectionFieldIsScalar",
+            "A field whose type is a scalar is selected with a selection set. Remove \
+            the selection set.";
+        IS0403 = 403, "ClientPointerSelectedAsScalar",
+            "Client pointers can only be selected as linked fields, i.e. with a \
+            selection set.";
+        IS0404 = 404, "ServerFieldCannotBeSelectedLoadably",
+            "Only client fields can be selected with @loadable.";
+        IS0405 = 405, "InvalidTypeRefinement",
+            "An abstract type is refined (with asConcreteType or ... on ConcreteType) \
+            to a type that is not one of its subtypes.";
+        IS0406 = 406, "ClientFieldSelectedAsLinked",
+            "Client fields can only be selected as scalars, i.e. without a selection set.";
+        IS0407 = 407, "EmptySelectionSet",
+            "A field is selected with an empty selection set. Select at least one field.";
+        IS0408 = 408, "InputObjectSelected",
+            "An input object is used as the parent of a client field or client pointer, \
+            as the target of a client pointer, or as the type of a selected field. Input \
+            objects can only be the types of arguments.";
+        IS0409 = 409, "RestrictedServerFieldSelected",
+            "A server field is selected, but the disallowed_server_fields or \
+            allowed_server_fields config option does not allow it to be selected.";
+
+        // IS05xx: validating arguments and variables
+        IS0500 = 500, "MissingArguments",
+            "A field is selected without passing one of its required (non-null, and \
+            without a default value) arguments.";
+        IS0501 = 501, "ExtraneousArgument",
+            "A field is selected with an argument that is not defined on that field.";
+        IS0502 = 502, "UnusedVariable",
+            "A client field defines a variable that is never used. Remove it.";
+        IS0503 = 503, "ExpectedTypeFoundVariable",
+            "A variable is passed to an argument, but the variable's type is not \
+            compatible with the argument's type.";
+        IS0504 = 504, "ExpectedTypeFoundScalar",
+            "A scalar literal is passed to an argument of an incompatible type.";
+        IS0505 = 505, "ExpectedTypeFoundObject",
+            "An object literal is passed to an argument whose type is not an input object.";
+        IS0506 = 506, "ExpectedTypeFoundList",
+            "A list literal is passed to an argument whose type is not a list.";
+        IS0507 = 507, "ExpectedNonNullTypeFoundNull",
+            "null is passed to an argument whose type is non-null.";
+        IS0508 = 508, "ExpectedTypeFoundEnum",
+            "An enum literal is passed to an argument of an incompatible type.";
+        IS0509 = 509, "UsedUndefinedVariable",
+            "A variable is used, but it is not defined by the client field.";
+        IS0510 = 510, "MissingFields",
+            "An object literal is missing required fields of the input object type.";
+        IS0511 = 511, "ExtraneousField",
+            "An object literal contains a field that is not defined on the input \
+            object type.";
+        IS0512 = 512, "DuplicateArgument",
+            "A field is selected with the same argument more than once.";
+        IS0513 = 513, "ConflictingNormalizationAlias",
+            "Two selections of the same field with different arguments produce the same \
+            alias in the generated query, e.g. because the argument values only differ in \
+            non-word characters. Their values would overwrite each other.";
+
+        // IS06xx: analyzing the validated schema
+        IS0600 = 600, "UnusedClientField",
+            "A client field or client pointer is not reachable from any entrypoint, i.e. \
+            it is never read. This is only reported if the on_unused_client_field config \
+            option is set to warn or error.";
+        IS0601 = 601, "MaxQueryDepthExceeded",
+            "The query of an entrypoint has more nested linked fields than the \
+            max_query_depth config option allows.";
+        IS0602 = 602, "MaxQueryFieldCountExceeded",
+            "The query of an entrypoint selects more fields (after merging) than the \
+            max_query_field_count config option allows.";
+        IS0603 = 603, "SelectedFieldRemoved",
+            "Reported by the diff command. A field that is selected by a client field or \
+            client pointer does not exist in the new schema.";
+        IS0604 = 604, "SelectedFieldTypeChanged",
+            "Reported by the diff command. The type of a selected field changed in an \
+            incompatible way in the new schema, e.g. it became nullable, a list, or a \
+            different type.";
+        IS0605 = 605, "NewRequiredArgument",
+            "Reported by the diff command. A selected field has a new required argument \
+            in the new schema, which the selection does not pass.";
+        IS0606 = 606, "PassedArgumentRemoved",
+            "Reported by the diff command. An argument that a selection passes to a field \
+            does not exist in the new schema.";
+        IS0607 = 607, "PassedArgumentTypeChanged",
+            "Reported by the diff command. The type of an argument that a selection passes \
+            to a field changed in an incompatible way in the new schema, e.g. it became \
+            non-null or a different type.";
+        IS0608 = 608, "ValidationRuleViolated",
+            "A selection violates one of the rules enabled with the validation_rules \
+            config option, or a custom validation rule run by a tool that embeds the \
+            compiler.";
+        IS0609 = 609, "DuplicateOperationName",
+            "Two different generated queries or mutations have the same name, e.g. the \
+            refetch queries of two entrypoints. Servers identify operations by name, so \
+            names must be unique. This can happen if the refetch_operation_name_template \
+            config option does not include the entrypoint.";
+        IS0610 = 610, "SelectionBudgetExceeded",
+            "A client field reads more scalar fields (including the fields read by the \
+            client fields it selects, after merging) than the selection_budget config \
+            option allows. This is only an error if on_selection_budget_exceeded is set \
+            to error.";
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{error_codes::ERROR_CODES, ErrorCode};
+
+    #[test]
+    fn error_codes_are_unique() {
+        let mut seen = HashSet::new();
+        for info in ERROR_CODES {
+            assert!(seen.insert(info.code), "{} is defined twice", info.code);
+        }
+    }
+
+    #[test]
+    fn error_code_names_are_unique() {
+        let mut seen = HashSet::new();
+        for info in ERROR_CODES {
+            assert!(
+                seen.insert(info.name),
+                "{} is used by more than one error code",
+                info.name
+            );
+        }
+    }
+
+    #[test]
+    fn error_code_round_trips() {
+        let code: ErrorCode = "IS0104".parse().unwrap();
+        assert_eq!(code, ErrorCode(104));
+        assert_eq!(code.to_string(), "IS0104");
+    }
+}
@@ -3,8 +3,8 @@ use std::{error::Error, fmt, path::PathBuf};
 use intern::string_key::{Intern, Lookup};
 
 use crate::{
-    text_with_carats::text_with_carats, CurrentWorkingDirectory, RelativePathToSourceFile, Span,
-    WithSpan,
+    decode_source, text_with_carats::text_with_carats, CurrentWorkingDirectory,
+    RelativePathToSourceFile, Span, WithSpan,
 };
 
 /// A source, which consists of a filename, and an optional span
@@ -53,8 +53,10 @@ impl TextSource {
             relative_path.to_string()
         };
 
-        let file_contents =
-            std::fs::read_to_string(&absolute_or_relative_file_path).expect("file should exist");
+        let file_contents = decode_source(
+            &std::fs::read(&absolute_or_relative_file_path).expect("file should exist"),
+        )
+        .content;
         if let Some(span) = self.span {
             // TODO we're cloning here unnecessarily, I think!
             (
@@ -115,6 +117,12 @@ impl EmbeddedLocation {
     pub fn new(text_source: TextSource, span: Span) -> Self {
         EmbeddedLocation { text_source, span }
     }
+
+    /// The span, relative to the entire source file instead of to the text source.
+    pub fn span_in_file(&self) -> Span {
+        let offset = self.text_source.span.map(|span| span.start).unwrap_or(0);
+        Span::new(self.span.start + offset, self.span.end + offset)
+    }
 }
 
 impl fmt::Display for Location {
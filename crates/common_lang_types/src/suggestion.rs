@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+/// Find the candidate that is closest to `name`, for use in "did you mean" messages.
+/// Candidates that differ from `name` by more than a third of its length (and at
+/// least one character) are not considered.
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Format a suggestion for the end of an error message, e.g. " Did you mean `@loadable`?".
+/// The prefix (e.g. `@` for directives or `$` for variables) is prepended to the suggestion.
+pub fn did_you_mean(prefix: &str, suggestion: Option<impl Display>) -> String {
+    match suggestion {
+        Some(suggestion) => format!(" Did you mean `{prefix}{suggestion}`?"),
+        None => String::new(),
+    }
+}
+
+/// The edit distance between two strings, where insertions, deletions, substitutions
+/// and transpositions of adjacent characters each count as one edit. Case differences
+/// are not counted.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_lowercase().chars().collect::<Vec<_>>();
+    let b = b.to_lowercase().chars().collect::<Vec<_>>();
+
+    // distances[i][j] is the distance between the first i chars of a and the
+    // first j chars of b.
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution_cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j - 1] + substitution_cost)
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_closest_match() {
+        let candidates = ["loadable", "updatable"];
+        assert_eq!(closest_match("loadabel", candidates), Some("loadable"));
+        assert_eq!(closest_match("Updatable", candidates), Some("updatable"));
+        assert_eq!(closest_match("skip", candidates), None);
+        assert_eq!(closest_match("nmae", ["name", "id"]), Some("name"));
+    }
+
+    #[test]
+    fn formats_suggestion() {
+        assert_eq!(
+            did_you_mean("@", Some("loadable")),
+            " Did you mean `@loadable`?"
+        );
+        assert_eq!(did_you_mean("$", None::<&str>), "");
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use common_lang_types::{ArtifactPathAndContent, ObjectTypeAndFieldName, RelativePathToSourceFile};
+use intern::Lookup;
+use isograph_config::CompilerConfig;
+use isograph_schema::{ClientScalarOrObjectSelectable, NetworkProtocol, Schema};
+use md5::{Digest, Md5};
+
+use crate::schema_metadata::path_relative_to_project_root;
+
+/// Start each artifact with a machine-readable comment containing the version of
+/// the compiler, and, if the artifact was generated from an iso literal, the file
+/// and line of that iso literal and the md5 hash of that file. e.g.
+///
+/// // @generated-by isograph 0.3.1 from components/PetDetail.tsx:12
+/// // @source-hash 5d41402abc4b2a76b9719d911017c592
+///
+/// Tooling can compare the hash to that of the file to detect stale artifacts.
+pub(crate) fn add_artifact_provenance<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    config: &CompilerConfig,
+    iso_literal_sources: &HashMap<RelativePathToSourceFile, &str>,
+    artifacts: &mut [ArtifactPathAndContent],
+) {
+    let iso_literal_locations = schema
+        .user_written_client_types()
+        .filter_map(|(_, client_type, _)| {
+            let text_source = client_type.text_source()?;
+            Some((
+                client_type.type_and_field(),
+                (
+                    text_source.relative_path_to_source_file,
+                    text_source.span.map(|span| span.start).unwrap_or(0),
+                ),
+            ))
+        })
+        .collect::<HashMap<ObjectTypeAndFieldName, _>>();
+
+    let version = env!("CARGO_PKG_VERSION");
+    for artifact in artifacts {
+        let source = artifact
+            .type_and_field
+            .and_then(|type_and_field| iso_literal_locations.get(&type_and_field))
+            .and_then(|(relative_path, literal_start)| {
+                let content = iso_literal_sources.get(relative_path)?;
+                Some((*relative_path, *literal_start, *content))
+            });
+        let banner = match source {
+            Some((relative_path, literal_start, content)) => {
+                let line = content[..literal_start as usize].matches('\n').count() + 1;
+                format!(
+                    "// @generated-by isograph {version} from {}:{line}\n\
+                    // @source-hash {}\n",
+                    path_relative_to_project_root(config, relative_path.lookup()),
+                    md5_hex(content)
+                )
+            }
+            None => format!("// @generated-by isograph {version}\n"),
+        };
+        artifact.file_content.insert_str(0, &banner);
+    }
+}
+
+fn md5_hex(content: &str) -> String {
+    Md5::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
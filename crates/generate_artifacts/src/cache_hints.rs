@@ -0,0 +1,143 @@
+use isograph_lang_types::{DefinitionLocation, SelectionType, ServerObjectEntityId};
+use isograph_schema::{
+    CacheControl, MergedSelectionMap, MergedServerSelection, NetworkProtocol, Schema,
+};
+
+use crate::javascript_value::JavaScriptValue;
+
+/// The cache hints of an entrypoint, aggregated over the server fields that its
+/// query selects.
+#[derive(Debug, Default)]
+struct CacheHints {
+    /// The smallest maxAge of the selected fields that have one.
+    max_age: Option<u32>,
+    private: bool,
+}
+
+impl CacheHints {
+    fn add(&mut self, cache_control: CacheControl) {
+        if let Some(max_age) = cache_control.max_age {
+            self.max_age = Some(self.max_age.map_or(max_age, |min| min.min(max_age)));
+        }
+        self.private |= cache_control.private;
+    }
+}
+
+/// The cache hints of an entrypoint, e.g. { maxAge: 60, uncacheable: false,
+/// private: false }, so that the network layer can choose a fetch policy, or None
+/// if none of the fields that its query selects have a cache hint.
+///
+/// The hint of a field is its own (e.g. from @cacheControl on the field in GraphQL),
+/// or, for linked fields without one, the hint of the type that it returns. maxAge
+/// is the smallest maxAge of the selected fields, the query is uncacheable if any
+/// of them has a maxAge of zero, and private if any of them is private. Fields
+/// without a hint are ignored.
+pub(crate) fn generate_cache_hints<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    root_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+) -> Option<JavaScriptValue> {
+    let mut cache_hints = None;
+    collect_cache_hints(
+        schema,
+        root_object_entity_id,
+        selection_map,
+        &mut cache_hints,
+    );
+
+    cache_hints.map(|cache_hints: CacheHints| {
+        JavaScriptValue::Object(vec![
+            (
+                "maxAge".to_string(),
+                cache_hints
+                    .max_age
+                    .map(|max_age| JavaScriptValue::Number(max_age.to_string()))
+                    .unwrap_or(JavaScriptValue::Null),
+            ),
+            (
+                "uncacheable".to_string(),
+                JavaScriptValue::Boolean(cache_hints.max_age == Some(0)),
+            ),
+            (
+                "private".to_string(),
+                JavaScriptValue::Boolean(cache_hints.private),
+            ),
+        ])
+    })
+}
+
+fn collect_cache_hints<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+    cache_hints: &mut Option<CacheHints>,
+) {
+    let selectables = &schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&parent_object_entity_id)
+        .expect(
+            "Expected parent_object_entity_id to exist \
+            in server_object_entity_extra_info",
+        )
+        .selectables;
+
+    for selection in selection_map.values() {
+        match selection {
+            MergedServerSelection::ScalarField(scalar_field) => {
+                let Some(DefinitionLocation::Server(SelectionType::Scalar(
+                    server_scalar_selectable_id,
+                ))) = selectables.get(&scalar_field.name.into())
+                else {
+                    continue;
+                };
+                if let Some(cache_control) = schema
+                    .server_scalar_selectable(*server_scalar_selectable_id)
+                    .cache_control
+                {
+                    cache_hints.get_or_insert_default().add(cache_control);
+                }
+            }
+            MergedServerSelection::LinkedField(linked_field) => {
+                let Some(DefinitionLocation::Server(SelectionType::Object(
+                    server_object_selectable_id,
+                ))) = selectables.get(&linked_field.name.into())
+                else {
+                    continue;
+                };
+                let selectable = schema.server_object_selectable(*server_object_selectable_id);
+                let target_object_entity_id = *selectable.target_object_entity.inner();
+                if let Some(cache_control) = selectable.cache_control.or_else(|| {
+                    CacheControl::from_directives(
+                        &schema
+                            .server_entity_data
+                            .server_object_entity(target_object_entity_id)
+                            .directives,
+                    )
+                }) {
+                    cache_hints.get_or_insert_default().add(cache_control);
+                }
+                collect_cache_hints(
+                    schema,
+                    target_object_entity_id,
+                    &linked_field.selection_map,
+                    cache_hints,
+                );
+            }
+            MergedServerSelection::InlineFragment(inline_fragment) => {
+                if let Some(SelectionType::Object(refined_object_entity_id)) = schema
+                    .server_entity_data
+                    .defined_entities
+                    .get(&inline_fragment.type_to_refine_to.into())
+                {
+                    collect_cache_hints(
+                        schema,
+                        *refined_object_entity_id,
+                        &inline_fragment.selection_map,
+                        cache_hints,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use common_lang_types::{
+    ArtifactFileName, ArtifactPathAndContent, Location, ObjectTypeAndFieldName,
+    RelativePathToSourceFile, Span,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_config::CompilerConfig;
+use isograph_lang_types::SelectionType;
+use isograph_schema::{
+    ClientFieldVariant, ClientPointerVariant, ClientScalarOrObjectSelectable, NetworkProtocol,
+    Schema,
+};
+use lazy_static::lazy_static;
+use serde_json::{json, Value};
+
+use crate::{
+    generate_artifacts::ENTRYPOINT_FILE_NAME, schema_metadata::path_relative_to_project_root,
+};
+
+lazy_static! {
+    pub static ref COMPILE_MANIFEST_FILE_NAME: ArtifactFileName =
+        "compile-manifest.json".intern().into();
+}
+
+/// An iso literal, and the artifacts that were generated for it.
+struct ManifestLiteral {
+    /// The offset of the literal within its file.
+    start: u32,
+    end: u32,
+    /// `field`, `pointer` or `entrypoint`
+    keyword: &'static str,
+    type_and_field: ObjectTypeAndFieldName,
+    const_export_name: Option<String>,
+}
+
+/// Generate a JSON file that maps each source file to the iso literals in it, and
+/// each iso literal to the artifacts that were generated for it. The babel plugin
+/// (or any other transform) can use this to rewrite `iso` calls without parsing
+/// the literals itself.
+///
+/// The id of a literal is its keyword and field, e.g. `entrypoint Query.HomeRoute`,
+/// which is what the transform reads from the start of the literal. The `import`
+/// of an entrypoint literal is the artifact that the `iso` call is replaced with.
+///
+/// Artifact paths are relative to the artifact directory, and source paths are
+/// relative to the project root. Everything is sorted, so that the artifact is
+/// stable across runs.
+pub(crate) fn generate_compile_manifest_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    config: &CompilerConfig,
+    artifacts: &[ArtifactPathAndContent],
+) -> ArtifactPathAndContent {
+    let mut artifact_paths = BTreeMap::<ObjectTypeAndFieldName, Vec<String>>::new();
+    for artifact in artifacts {
+        if let Some(type_and_field) = artifact.type_and_field {
+            artifact_paths
+                .entry(type_and_field)
+                .or_default()
+                .push(artifact_path(
+                    config,
+                    Some(type_and_field),
+                    artifact.file_name,
+                ));
+        }
+    }
+    for paths in artifact_paths.values_mut() {
+        paths.sort();
+    }
+
+    let mut literals_by_file = BTreeMap::<String, Vec<ManifestLiteral>>::new();
+    let mut add_literal = |relative_path: RelativePathToSourceFile, literal: ManifestLiteral| {
+        literals_by_file
+            .entry(path_relative_to_project_root(
+                config,
+                relative_path.lookup(),
+            ))
+            .or_default()
+            .push(literal);
+    };
+
+    for (_, client_type, _) in schema.user_written_client_types() {
+        let Some(text_source) = client_type.text_source() else {
+            continue;
+        };
+        let (keyword, const_export_name) = match client_type {
+            SelectionType::Scalar(client_field) => (
+                "field",
+                match client_field.variant {
+                    ClientFieldVariant::UserWritten(info) => Some(info.const_export_name),
+                    _ => None,
+                },
+            ),
+            SelectionType::Object(client_pointer) => (
+                "pointer",
+                match client_pointer.variant {
+                    ClientPointerVariant::UserWritten => {
+                        Some(client_pointer.info.const_export_name)
+                    }
+                    ClientPointerVariant::Node => None,
+                },
+            ),
+        };
+        let span = text_source.span.unwrap_or(Span::new(0, 0));
+        add_literal(
+            text_source.relative_path_to_source_file,
+            ManifestLiteral {
+                start: span.start,
+                end: span.end,
+                keyword,
+                type_and_field: client_type.type_and_field(),
+                const_export_name: const_export_name.map(|name| name.to_string()),
+            },
+        );
+    }
+
+    for (entrypoint_id, entrypoint_declaration_info) in &schema.entrypoints {
+        let type_and_field = schema.client_field(*entrypoint_id).type_and_field;
+        for location in &entrypoint_declaration_info.client_field_name_locations {
+            let Location::Embedded(location) = location else {
+                continue;
+            };
+            let span = location.text_source.span.unwrap_or(Span::new(0, 0));
+            add_literal(
+                location.text_source.relative_path_to_source_file,
+                ManifestLiteral {
+                    start: span.start,
+                    end: span.end,
+                    keyword: "entrypoint",
+                    type_and_field,
+                    const_export_name: None,
+                },
+            );
+        }
+    }
+
+    let files = literals_by_file
+        .into_iter()
+        .map(|(path, mut literals)| {
+            literals.sort_by_key(|literal| (literal.start, literal.keyword));
+            json!({
+                "path": path,
+                "literals": literals
+                    .iter()
+                    .map(|literal| literal_manifest(config, &artifact_paths, literal))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    ArtifactPathAndContent {
+        file_content: format!(
+            "{}\n",
+            serde_json::to_string_pretty(&json!({ "files": files }))
+                .expect("Expected compile manifest to be serializable")
+        ),
+        file_name: *COMPILE_MANIFEST_FILE_NAME,
+        type_and_field: None,
+    }
+}
+
+fn literal_manifest(
+    config: &CompilerConfig,
+    artifact_paths: &BTreeMap<ObjectTypeAndFieldName, Vec<String>>,
+    literal: &ManifestLiteral,
+) -> Value {
+    let type_and_field = literal.type_and_field;
+    json!({
+        "id": format!(
+            "{} {}.{}",
+            literal.keyword, type_and_field.type_name, type_and_field.field_name
+        ),
+        "kind": literal.keyword,
+        "type": type_and_field.type_name.to_string(),
+        "field": type_and_field.field_name.to_string(),
+        "start": literal.start,
+        "end": literal.end,
+        "const_export_name": literal.const_export_name,
+        "import": (literal.keyword == "entrypoint")
+            .then(|| artifact_path(config, Some(type_and_field), *ENTRYPOINT_FILE_NAME)),
+        "artifacts": artifact_paths.get(&type_and_field).cloned().unwrap_or_default(),
+    })
+}
+
+fn artifact_path(
+    config: &CompilerConfig,
+    type_and_field: Option<ObjectTypeAndFieldName>,
+    file_name: ArtifactFileName,
+) -> String {
+    config
+        .options
+        .artifact_layout
+        .generate_path(type_and_field, file_name)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
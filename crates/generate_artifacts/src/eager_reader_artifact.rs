@@ -1,24 +1,32 @@
 use common_lang_types::{ArtifactPathAndContent, ObjectTypeAndFieldName, WithSpan};
 use intern::Lookup;
 
-use isograph_config::{CompilerConfig, GenerateFileExtensionsOption};
+use isograph_config::{
+    ArtifactLayout, CompilerConfig, GenerateFileExtensionsOption, NullableFieldOptions,
+};
 
-use isograph_lang_types::{ClientFieldDirectiveSet, SelectionType};
+use isograph_lang_types::{ClientFieldDirectiveSet, EmptyDirectiveSet, SelectionType};
 use isograph_schema::{
-    initial_variable_context, ClientScalarOrObjectSelectable, ClientSelectable, NetworkProtocol,
-    Schema, ServerObjectSelectable, ValidatedSelection,
+    initial_variable_context, ClientObjectSelectable, ClientScalarOrObjectSelectable,
+    ClientScalarSelectable, ClientSelectable, NetworkProtocol, PaginatedFieldVariant, Schema,
+    SchemaServerObjectSelectableVariant, ServerObjectSelectable, ValidatedSelection,
 };
 use isograph_schema::{RefetchedPathsMap, UserWrittenClientTypeInfo};
 
-use std::{borrow::Cow, collections::BTreeSet, path::PathBuf};
+use std::{
+    borrow::Cow,
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
 
 use crate::generate_artifacts::ClientFieldOutputType;
 use crate::{
     generate_artifacts::{
         generate_client_field_parameter_type, generate_client_field_updatable_data_type,
-        generate_output_type, generate_parameters, ClientFieldFunctionImportStatement,
-        RESOLVER_OUTPUT_TYPE, RESOLVER_OUTPUT_TYPE_FILE_NAME, RESOLVER_PARAMETERS_TYPE_FILE_NAME,
-        RESOLVER_PARAM_TYPE, RESOLVER_PARAM_TYPE_FILE_NAME, RESOLVER_READER_FILE_NAME,
+        generate_output_type, generate_parameters, link_output_type, write_optional_description,
+        ClientFieldFunctionImportStatement, RESOLVER_OUTPUT_TYPE, RESOLVER_OUTPUT_TYPE_FILE_NAME,
+        RESOLVER_PARAMETERS_TYPE, RESOLVER_PARAMETERS_TYPE_FILE_NAME, RESOLVER_PARAM_TYPE,
+        RESOLVER_PARAM_TYPE_FILE_NAME, RESOLVER_READER_FILE_NAME,
     },
     import_statements::{
         param_type_imports_to_import_param_statement, param_type_imports_to_import_statement,
@@ -35,26 +43,122 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
     refetched_paths: &RefetchedPathsMap,
     file_extensions: GenerateFileExtensionsOption,
     has_updatable: bool,
+) -> Vec<ArtifactPathAndContent> {
+    generate_eager_reader_artifacts_with_resolver(
+        schema,
+        client_selectable,
+        config,
+        generate_function_import_statement(config, info, file_extensions),
+        info.client_field_directive_set,
+        refetched_paths,
+        file_extensions,
+        has_updatable,
+    )
+}
+
+/// The reader artifact and param type of a generated pagination field, whose resolver
+/// returns the connection that it selects.
+pub(crate) fn generate_paginated_field_reader_artifacts<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+    config: &CompilerConfig,
+    paginated_field_variant: PaginatedFieldVariant,
+    refetched_paths: &RefetchedPathsMap,
+    file_extensions: GenerateFileExtensionsOption,
+    has_updatable: bool,
+) -> Vec<ArtifactPathAndContent> {
+    let connection_field_name = paginated_field_variant.connection_field_name;
+    // The reader artifact imports the param type, so the resolver can use it.
+    let function_import_statement = ClientFieldFunctionImportStatement(format!(
+        "const resolver = ({{ data }}: {}__{}__param) => data.{connection_field_name};",
+        client_field.type_and_field.type_name, client_field.name
+    ));
+    let mut path_and_contents = generate_eager_reader_artifacts_with_resolver(
+        schema,
+        &SelectionType::Scalar(client_field),
+        config,
+        function_import_statement,
+        ClientFieldDirectiveSet::None(EmptyDirectiveSet {}),
+        refetched_paths,
+        file_extensions,
+        has_updatable,
+    );
+    // Param types are generated for every user-written client field, but only for
+    // the pagination fields that are read.
+    path_and_contents.push(generate_eager_reader_param_type_artifact(
+        schema,
+        &SelectionType::Scalar(client_field),
+        file_extensions,
+        config.options.artifact_layout,
+        config.options.nullable_fields,
+    ));
+    path_and_contents
+}
+
+/// A node pointer (e.g. `pointer Query.currentUser to User`) has no resolver. Its
+/// reader returns a link to the record with the id that is passed to it, so the
+/// record is read from the store, wherever it was fetched.
+pub(crate) fn generate_node_pointer_reader_artifacts<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_pointer: &ClientObjectSelectable<TNetworkProtocol>,
+    config: &CompilerConfig,
+    refetched_paths: &RefetchedPathsMap,
+    file_extensions: GenerateFileExtensionsOption,
+) -> Vec<ArtifactPathAndContent> {
+    let target_type_name = schema
+        .server_entity_data
+        .server_object_entity(*client_pointer.target_object_entity.inner())
+        .name;
+    let function_import_statement = ClientFieldFunctionImportStatement(format!(
+        "const resolver = ({{ parameters }}: {}__{}__param) => \
+        ({{ __link: parameters.id, __typename: \"{target_type_name}\" }});",
+        client_pointer.type_and_field.type_name, client_pointer.name
+    ));
+    generate_eager_reader_artifacts_with_resolver(
+        schema,
+        &SelectionType::Object(client_pointer),
+        config,
+        function_import_statement,
+        ClientFieldDirectiveSet::None(EmptyDirectiveSet {}),
+        refetched_paths,
+        file_extensions,
+        false,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_eager_reader_artifacts_with_resolver<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_selectable: &ClientSelectable<TNetworkProtocol>,
+    config: &CompilerConfig,
+    function_import_statement: ClientFieldFunctionImportStatement,
+    user_written_component_variant: ClientFieldDirectiveSet,
+    refetched_paths: &RefetchedPathsMap,
+    file_extensions: GenerateFileExtensionsOption,
+    has_updatable: bool,
 ) -> Vec<ArtifactPathAndContent> {
     let ts_file_extension = file_extensions.ts();
-    let user_written_component_variant = info.client_field_directive_set;
+    let artifact_layout = config.options.artifact_layout;
     let parent_object_entity = schema
         .server_entity_data
         .server_object_entity(client_selectable.parent_object_entity_id());
+    let type_and_field = ObjectTypeAndFieldName {
+        type_name: parent_object_entity.name,
+        field_name: client_selectable.name().into(),
+    };
 
     let (reader_ast, reader_imports) = generate_reader_ast(
         schema,
         client_selectable.selection_set_for_parent_query(),
+        client_selectable.parent_object_entity_id(),
         0,
         refetched_paths,
         &initial_variable_context(client_selectable),
+        artifact_layout,
     );
 
-    let function_import_statement =
-        generate_function_import_statement(config, info, file_extensions);
-
     let reader_import_statement =
-        reader_imports_to_import_statement(&reader_imports, file_extensions);
+        reader_imports_to_import_statement(&reader_imports, file_extensions, artifact_layout);
 
     let reader_param_type = format!(
         "{}__{}__param",
@@ -62,7 +166,9 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
         client_selectable.name()
     );
 
-    let reader_content = if let ClientFieldDirectiveSet::None(_) = user_written_component_variant {
+    let reader_content = if let ClientFieldDirectiveSet::None(_)
+    | ClientFieldDirectiveSet::Plural(_) = user_written_component_variant
+    {
         let eager_reader_name =
             format!("{}.{}", parent_object_entity.name, client_selectable.name());
         let reader_output_type = format!(
@@ -70,12 +176,14 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
             parent_object_entity.name,
             client_selectable.name()
         );
-        let param_type_file_name = *RESOLVER_PARAM_TYPE;
-        let output_type_file_name = *RESOLVER_OUTPUT_TYPE;
+        let param_type_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *RESOLVER_PARAM_TYPE);
+        let output_type_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *RESOLVER_OUTPUT_TYPE);
         format!(
             "import type {{ EagerReaderArtifact, ReaderAst }} from '@isograph/react';\n\
-            import {{ {reader_param_type} }} from './{param_type_file_name}{ts_file_extension}';\n\
-            import {{ {reader_output_type} }} from './{output_type_file_name}{ts_file_extension}';\n\
+            import {{ {reader_param_type} }} from '{param_type_file_name}{ts_file_extension}';\n\
+            import {{ {reader_output_type} }} from '{output_type_file_name}{ts_file_extension}';\n\
             {function_import_statement}\n\
             {reader_import_statement}\n\
             const readerAst: ReaderAst<{reader_param_type}> = {reader_ast};\n\n\
@@ -94,11 +202,21 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
         )
     } else {
         let component_name = format!("{}.{}", parent_object_entity.name, client_selectable.name());
-        let param_type_file_name = *RESOLVER_PARAM_TYPE;
+        let param_type_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *RESOLVER_PARAM_TYPE);
+        // The directive must precede the imports. Comments (e.g. the generated file
+        // header) may precede it.
+        let use_client_directive = if config.options.react_server_components
+            && user_written_component_variant.is_client_component()
+        {
+            "'use client';\n\n"
+        } else {
+            ""
+        };
         format!(
-            "import type {{ComponentReaderArtifact, ExtractSecondParam, \
+            "{use_client_directive}import type {{ComponentReaderArtifact, ExtractSecondParam, \
             ReaderAst }} from '@isograph/react';\n\
-            import {{ {reader_param_type} }} from './{param_type_file_name}{ts_file_extension}';\n\
+            import {{ {reader_param_type} }} from '{param_type_file_name}{ts_file_extension}';\n\
             {function_import_statement}\n\
             {reader_import_statement}\n\
             const readerAst: ReaderAst<{reader_param_type}> = {reader_ast};\n\n\
@@ -120,10 +238,7 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
     let mut path_and_contents = vec![ArtifactPathAndContent {
         file_name: *RESOLVER_READER_FILE_NAME,
         file_content: reader_content,
-        type_and_field: Some(ObjectTypeAndFieldName {
-            type_name: parent_object_entity.name,
-            field_name: client_selectable.name().into(),
-        }),
+        type_and_field: Some(type_and_field),
     }];
 
     if !client_selectable.variable_definitions().is_empty() {
@@ -142,10 +257,7 @@ pub(crate) fn generate_eager_reader_artifacts<TNetworkProtocol: NetworkProtocol>
         path_and_contents.push(ArtifactPathAndContent {
             file_name: *RESOLVER_PARAMETERS_TYPE_FILE_NAME,
             file_content: parameters_content,
-            type_and_field: Some(ObjectTypeAndFieldName {
-                type_name: parent_object_entity.name,
-                field_name: client_selectable.name().into(),
-            }),
+            type_and_field: Some(type_and_field),
         });
     }
 
@@ -158,6 +270,7 @@ pub(crate) fn generate_eager_reader_condition_artifact<TNetworkProtocol: Network
     inline_fragment_reader_selections: &[WithSpan<ValidatedSelection>],
     refetch_paths: &RefetchedPathsMap,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
 ) -> ArtifactPathAndContent {
     let server_object_selectable_name = server_object_selectable.name.item;
 
@@ -173,16 +286,27 @@ pub(crate) fn generate_eager_reader_condition_artifact<TNetworkProtocol: Network
     let (reader_ast, reader_imports) = generate_reader_ast(
         schema,
         inline_fragment_reader_selections,
+        server_object_selectable.parent_object_entity_id,
         0,
         refetch_paths,
         &server_object_selectable.initial_variable_context(),
+        artifact_layout,
     );
 
     let reader_import_statement =
-        reader_imports_to_import_statement(&reader_imports, file_extensions);
+        reader_imports_to_import_statement(&reader_imports, file_extensions, artifact_layout);
 
     let reader_param_type = "{ data: any, parameters: Record<PropertyKey, never> }";
-    let reader_output_type = "Link | null";
+    // An upcast (e.g. Pet.asNode) always succeeds, whereas a refinement to a subtype
+    // (e.g. Node.asPet) only succeeds if the record has that type.
+    let (reader_output_type, resolver) = match server_object_selectable.object_selectable_variant {
+        SchemaServerObjectSelectableVariant::Upcast => ("Link", "data.link".to_string()),
+        SchemaServerObjectSelectableVariant::InlineFragment
+        | SchemaServerObjectSelectableVariant::LinkedField => (
+            "Link | null",
+            format!("data.__typename === \"{concrete_type}\" ? data.link : null"),
+        ),
+    };
 
     let eager_reader_name = format!(
         "{}.{}",
@@ -199,7 +323,7 @@ pub(crate) fn generate_eager_reader_condition_artifact<TNetworkProtocol: Network
         > = {{\n\
         {}kind: \"EagerReaderArtifact\",\n\
         {}fieldName: \"{eager_reader_name}\",\n\
-        {}resolver: ({{ data }}) => data.__typename === \"{concrete_type}\" ? data.link : null,\n\
+        {}resolver: ({{ data }}) => {resolver},\n\
         {}readerAst,\n\
         {}hasUpdatable: false,\n\
         }};\n\n\
@@ -221,11 +345,17 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
     schema: &Schema<TNetworkProtocol>,
     client_scalar_selectable: &ClientSelectable<TNetworkProtocol>,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
+    nullable_fields: NullableFieldOptions,
 ) -> ArtifactPathAndContent {
     let ts_file_extension = file_extensions.ts();
     let parent_type = schema
         .server_entity_data
         .server_object_entity(client_scalar_selectable.parent_object_entity_id());
+    let type_and_field = ObjectTypeAndFieldName {
+        type_name: parent_type.name,
+        field_name: client_scalar_selectable.name().into(),
+    };
 
     let mut param_type_imports = BTreeSet::new();
     let mut loadable_fields = BTreeSet::new();
@@ -234,23 +364,30 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
     let client_field_parameter_type = generate_client_field_parameter_type(
         schema,
         client_scalar_selectable.selection_set_for_parent_query(),
+        client_scalar_selectable.parent_object_entity_id(),
         &mut param_type_imports,
         &mut loadable_fields,
         1,
         &mut link_fields,
+        nullable_fields,
     );
     let updatable_data_type = generate_client_field_updatable_data_type(
         schema,
         client_scalar_selectable.selection_set_for_parent_query(),
+        client_scalar_selectable.parent_object_entity_id(),
         &mut param_type_imports,
         &mut loadable_fields,
         1,
         &mut link_fields,
         &mut updatable_fields,
+        nullable_fields,
     );
 
-    let param_type_import_statement =
-        param_type_imports_to_import_statement(&param_type_imports, file_extensions);
+    let param_type_import_statement = param_type_imports_to_import_statement(
+        &param_type_imports,
+        file_extensions,
+        artifact_layout,
+    );
     let reader_param_type = format!(
         "{}__{}__param",
         parent_type.name,
@@ -270,8 +407,11 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
     };
 
     let loadable_field_imports = if !loadable_fields.is_empty() {
-        let param_imports =
-            param_type_imports_to_import_param_statement(&loadable_fields, file_extensions);
+        let param_imports = param_type_imports_to_import_param_statement(
+            &loadable_fields,
+            file_extensions,
+            artifact_layout,
+        );
         format!(
             "import {{ type LoadableField, type ExtractParameters }} from '@isograph/react';\n\
             {param_imports}"
@@ -289,8 +429,10 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
             parent_type.name,
             client_scalar_selectable.name()
         );
+        let parameters_type_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *RESOLVER_PARAMETERS_TYPE);
         (
-            format!("import type {{ {reader_parameters_type} }} from './parameters_type{ts_file_extension}';\n"),
+            format!("import type {{ {reader_parameters_type} }} from '{parameters_type_file_name}{ts_file_extension}';\n"),
             reader_parameters_type,
         )
     } else {
@@ -307,12 +449,16 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
         "".to_string()
     };
 
+    let mut description = String::new();
+    write_optional_description(client_scalar_selectable.description(), &mut description, 0);
+
     let param_type_content = format!(
         "{param_type_import_statement}\
         {link_field_imports}\
         {start_update_imports}\
         {loadable_field_imports}\
         {parameters_import}\n\
+        {description}\
         export type {reader_param_type} = {{\n\
         {indent}readonly data: {client_field_parameter_type},\n\
         {indent}readonly parameters: {parameters_type},\n\
@@ -322,10 +468,7 @@ pub(crate) fn generate_eager_reader_param_type_artifact<TNetworkProtocol: Networ
     ArtifactPathAndContent {
         file_name: *RESOLVER_PARAM_TYPE_FILE_NAME,
         file_content: param_type_content,
-        type_and_field: Some(ObjectTypeAndFieldName {
-            type_name: parent_type.name,
-            field_name: client_scalar_selectable.name().into(),
-        }),
+        type_and_field: Some(type_and_field),
     }
 }
 
@@ -345,7 +488,7 @@ pub(crate) fn generate_eager_reader_output_type_artifact<TNetworkProtocol: Netwo
 
     let client_field_output_type = match client_field {
         SelectionType::Object(_) => ClientFieldOutputType("Link".to_string()),
-        SelectionType::Scalar(client_field) => generate_output_type(client_field),
+        SelectionType::Scalar(client_field) => generate_output_type(schema, client_field),
     };
 
     let output_type_text = format!(
@@ -363,7 +506,9 @@ pub(crate) fn generate_eager_reader_output_type_artifact<TNetworkProtocol: Netwo
                 from '@isograph/react';\n\
                 {output_type_text}\n",
         )
-    } else if let ClientFieldDirectiveSet::None(_) = info.client_field_directive_set {
+    } else if let ClientFieldDirectiveSet::None(_) | ClientFieldDirectiveSet::Plural(_) =
+        info.client_field_directive_set
+    {
         output_type_text
     } else {
         format!(
@@ -383,46 +528,205 @@ pub(crate) fn generate_eager_reader_output_type_artifact<TNetworkProtocol: Netwo
     }
 }
 
+/// The output type of a generated pagination field is the type of the connection
+/// that it selects.
+pub(crate) fn generate_paginated_field_output_type_artifact<TNetworkProtocol: NetworkProtocol>(
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+    paginated_field_variant: PaginatedFieldVariant,
+    file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
+) -> ArtifactPathAndContent {
+    let ts_file_extension = file_extensions.ts();
+    let type_and_field = client_field.type_and_field;
+    let reader_param_type = format!(
+        "{}__{}__param",
+        type_and_field.type_name, type_and_field.field_name
+    );
+    let param_type_file_name =
+        artifact_layout.sibling_import_path(type_and_field, *RESOLVER_PARAM_TYPE);
+    let connection_field_name = paginated_field_variant.connection_field_name;
+
+    ArtifactPathAndContent {
+        file_name: *RESOLVER_OUTPUT_TYPE_FILE_NAME,
+        file_content: format!(
+            "import type {{ {reader_param_type} }} from '{param_type_file_name}{ts_file_extension}';\n\
+            export type {}__{}__output_type = {reader_param_type}['data']['{connection_field_name}'];\n",
+            type_and_field.type_name, type_and_field.field_name
+        ),
+        type_and_field: Some(type_and_field),
+    }
+}
+
+/// The output type of a node pointer is a link. Unlike other client pointers, there is
+/// no resolver to import.
+pub(crate) fn generate_node_pointer_output_type_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_pointer: &ClientObjectSelectable<TNetworkProtocol>,
+) -> ArtifactPathAndContent {
+    let parent_type = schema
+        .server_entity_data
+        .server_object_entity(client_pointer.parent_object_entity_id);
+
+    ArtifactPathAndContent {
+        file_name: *RESOLVER_OUTPUT_TYPE_FILE_NAME,
+        file_content: format!(
+            "import type {{ Link }} from '@isograph/react';\n\
+            export type {}__{}__output_type = Link;\n",
+            parent_type.name, client_pointer.name
+        ),
+        type_and_field: Some(client_pointer.type_and_field),
+    }
+}
+
+/// The reader artifact of the link field of an object, which reads a Link to the
+/// object itself, i.e. a pointer to its record in the store.
+pub(crate) fn generate_link_reader_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> ArtifactPathAndContent {
+    let reader_param_type = "{ data: any, parameters: Record<PropertyKey, never> }";
+    let reader_output_type = link_output_type(schema, client_field);
+    let type_and_field = client_field.type_and_field;
+    let field_name = type_and_field.field_name;
+
+    let reader_content = format!(
+        "import type {{ EagerReaderArtifact, ReaderAst, Link }} from '@isograph/react';\n\n\
+        const readerAst: ReaderAst<{reader_param_type}> = [\n\
+        {}{{\n\
+        {}kind: \"Link\",\n\
+        {}alias: \"{field_name}\",\n\
+        {}}},\n\
+        ];\n\n\
+        const artifact: EagerReaderArtifact<\n\
+        {}{reader_param_type},\n\
+        {}{reader_output_type}\n\
+        > = {{\n\
+        {}kind: \"EagerReaderArtifact\",\n\
+        {}fieldName: \"{}.{field_name}\",\n\
+        {}resolver: ({{ data }}) => data.{field_name},\n\
+        {}readerAst,\n\
+        {}hasUpdatable: false,\n\
+        }};\n\n\
+        export default artifact;\n",
+        "  ",
+        "    ",
+        "    ",
+        "  ",
+        "  ",
+        "  ",
+        "  ",
+        "  ",
+        type_and_field.type_name,
+        "  ",
+        "  ",
+        "  "
+    );
+
+    ArtifactPathAndContent {
+        file_name: *RESOLVER_READER_FILE_NAME,
+        file_content: reader_content,
+        type_and_field: Some(type_and_field),
+    }
+}
+
+pub(crate) fn generate_link_output_type_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> ArtifactPathAndContent {
+    ArtifactPathAndContent {
+        file_name: *RESOLVER_OUTPUT_TYPE_FILE_NAME,
+        file_content: format!(
+            "import type {{ Link }} from '@isograph/react';\n\
+            export type {}__output_type = {};\n",
+            client_field.type_and_field.underscore_separated(),
+            link_output_type(schema, client_field)
+        ),
+        type_and_field: Some(client_field.type_and_field),
+    }
+}
+
+/// If the client field is in the directory of an import alias (e.g. `@src`), the path to
+/// the client field using that alias (e.g. `@src/components/PetUpdater.tsx`).
+fn aliased_import_path(
+    config: &CompilerConfig,
+    relative_path_to_client_field: &Path,
+) -> Option<String> {
+    config
+        .options
+        .import_aliases
+        .iter()
+        .find_map(|import_alias| {
+            let path_within_directory = relative_path_to_client_field
+                .strip_prefix(import_alias.directory.relative_path.lookup())
+                .ok()?;
+            Some(format!(
+                "{}/{}",
+                import_alias.prefix,
+                path_within_directory.to_str()?
+            ))
+        })
+}
+
 /// Example: import { PetUpdater as resolver } from '../../../PetUpdater';
+/// or, if an import alias matches, from '@src/PetUpdater'.
 fn generate_function_import_statement(
     config: &CompilerConfig,
     target_field_info: UserWrittenClientTypeInfo,
     file_extensions: GenerateFileExtensionsOption,
 ) -> ClientFieldFunctionImportStatement {
-    // artifact directory includes __isograph, so artifact_directory.join("Type/Field")
-    // is a directory "two levels deep" within the artifact_directory.
-    //
-    // So diff_paths(path_to_client_field, artifact_directory.join("Type/Field"))
-    // is a lazy way of saying "make a relative path from two levels deep in the artifact
-    // dir to the client field".
-    //
-    // Since we will always go ../../../ the Type/Field part will never show up
-    // in the output.
-    //
-    // Anyway, TODO do better.
-    let relative_path_to_current_artifact =
-        PathBuf::from(config.artifact_directory.relative_path.lookup()).join("Type/Field");
-    let relative_path_to_client_field = target_field_info.file_path.lookup();
-
-    let relative_path = pathdiff::diff_paths(
-        relative_path_to_client_field,
-        relative_path_to_current_artifact,
-    )
-    .expect("Relative path should work");
-    let complete_file_name = relative_path.to_str().expect(
-        "This path should be stringifiable. This probably is indicative of a bug in Isograph.",
-    );
+    let relative_path_to_client_field = Path::new(target_field_info.file_path.lookup());
+
+    let import_path = match aliased_import_path(config, relative_path_to_client_field) {
+        Some(aliased_import_path) => aliased_import_path,
+        None => {
+            // artifact directory includes __isograph, so (with the nested layout)
+            // artifact_directory.join("Type/Field") is a directory "two levels deep" within
+            // the artifact_directory.
+            //
+            // So diff_paths(path_to_client_field, artifact_directory.join("Type/Field"))
+            // is a lazy way of saying "make a relative path from two levels deep in the artifact
+            // dir to the client field".
+            //
+            // Since we will always go ../../../ the Type/Field part will never show up
+            // in the output.
+            //
+            // Anyway, TODO do better.
+            let relative_path_to_current_artifact =
+                PathBuf::from(config.artifact_directory.relative_path.lookup()).join(match config
+                    .options
+                    .artifact_layout
+                {
+                    ArtifactLayout::Nested => "Type/Field",
+                    ArtifactLayout::Flat => "",
+                    ArtifactLayout::FolderPerType => "Type",
+                });
+
+            let relative_path = pathdiff::diff_paths(
+                relative_path_to_client_field,
+                relative_path_to_current_artifact,
+            )
+            .expect("Relative path should work");
+            relative_path
+                .to_str()
+                .expect(
+                    "This path should be stringifiable. This probably is indicative of a bug in Isograph.",
+                )
+                .to_string()
+        }
+    };
 
     let normalized_file_name = if cfg!(windows) {
-        Cow::Owned(complete_file_name.replace("\\", "/"))
+        Cow::Owned(import_path.replace("\\", "/"))
     } else {
-        Cow::Borrowed(complete_file_name)
+        Cow::Borrowed(import_path.as_str())
     };
 
     let file_name = match file_extensions {
         GenerateFileExtensionsOption::ExcludeExtensionsInFileImports => {
-            let extension_char_count_including_dot =
-                relative_path.extension().map(|x| x.len() + 1).unwrap_or(0);
+            let extension_char_count_including_dot = relative_path_to_client_field
+                .extension()
+                .map(|x| x.len() + 1)
+                .unwrap_or(0);
             &normalized_file_name
                 [0..(normalized_file_name.len() - extension_char_count_including_dot)]
         }
@@ -1,10 +1,12 @@
 use std::collections::BTreeSet;
 
 use common_lang_types::{
-    ArtifactPathAndContent, IsographObjectTypeName, ObjectTypeAndFieldName, QueryOperationName,
-    QueryText, VariableName,
+    ArtifactPathAndContent, DirectiveName, IsographObjectTypeName, Location,
+    ObjectTypeAndFieldName, QueryOperationName, QueryText, VariableName,
 };
-use isograph_config::GenerateFileExtensionsOption;
+use graphql_lang_types::GraphQLTypeAnnotation;
+use intern::string_key::Intern;
+use isograph_config::{ArtifactLayout, GenerateFileExtensionsOption, OperationNameOptions};
 use isograph_lang_types::{
     ClientScalarSelectableId, DefinitionLocation, ScalarSelectionDirectiveSet, SelectionType,
     ServerObjectEntityId,
@@ -16,17 +18,21 @@ use isograph_schema::{
     ClientScalarSelectable, FieldToCompletedMergeTraversalStateMap, FieldTraversalResult,
     MergedSelectionMap, NetworkProtocol, RootOperationName, RootRefetchedPath,
     ScalarClientFieldTraversalState, Schema, ServerObjectEntity, ValidatedVariableDefinition,
-    WrappedSelectionMapSelection,
+    WrappedSelectionMapSelection, REFETCH_FIELD_NAME,
 };
 
 use crate::{
+    cache_hints::generate_cache_hints,
     generate_artifacts::{
         NormalizationAstText, RefetchQueryArtifactImport, ENTRYPOINT_FILE_NAME, NORMALIZATION_AST,
         NORMALIZATION_AST_FILE_NAME, QUERY_TEXT, QUERY_TEXT_FILE_NAME, RESOLVER_OUTPUT_TYPE,
         RESOLVER_PARAM_TYPE, RESOLVER_READER,
     },
     imperatively_loaded_fields::get_artifact_for_imperatively_loaded_field,
-    normalization_ast_text::generate_normalization_ast_text,
+    javascript_value::JavaScriptValue,
+    normalization_ast_text::{generate_normalization_ast_text, generate_type_metadata},
+    operation_names::GeneratedOperations,
+    refetch_query_index::{generate_refetch_query_index_artifact, RefetchQueryIndexEntry},
 };
 
 #[derive(Debug)]
@@ -35,15 +41,24 @@ struct EntrypointArtifactInfo<'schema, TNetworkProtocol: NetworkProtocol> {
     parent_type: &'schema ServerObjectEntity<TNetworkProtocol>,
     query_text: QueryText,
     normalization_ast_text: NormalizationAstText,
+    /// The propagated directives of the types that the query can fetch, if any.
+    type_metadata: Option<JavaScriptValue>,
+    /// The aggregated cache hints of the fields that the query selects, if any.
+    cache_hints: Option<JavaScriptValue>,
     refetch_query_artifact_import: RefetchQueryArtifactImport,
     concrete_type: IsographObjectTypeName,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn generate_entrypoint_artifacts<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     entrypoint_id: ClientScalarSelectableId,
     encountered_client_type_map: &mut FieldToCompletedMergeTraversalStateMap,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
+    operation_names: &OperationNameOptions,
+    propagated_object_directives: &[DirectiveName],
+    generated_operations: &mut GeneratedOperations,
 ) -> Vec<ArtifactPathAndContent> {
     let entrypoint = schema.client_field(entrypoint_id);
 
@@ -75,6 +90,19 @@ pub(crate) fn generate_entrypoint_artifacts<TNetworkProtocol: NetworkProtocol>(
             .map(|variable_definition| &variable_definition.item),
         &schema.find_mutation(),
         file_extensions,
+        artifact_layout,
+        operation_names,
+        propagated_object_directives,
+        generated_operations,
+        format!(
+            "the entrypoint `{}.{}`",
+            entrypoint.type_and_field.type_name, entrypoint.type_and_field.field_name
+        ),
+        schema
+            .entrypoints
+            .get(&entrypoint_id)
+            .and_then(|info| info.client_field_name_locations.first().copied())
+            .unwrap_or_else(Location::generated),
     )
 }
 
@@ -91,8 +119,19 @@ pub(crate) fn generate_entrypoint_artifacts_with_client_field_traversal_result<
     variable_definitions: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
     default_root_operation: &Option<(&ServerObjectEntityId, &RootOperationName)>,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
+    operation_names: &OperationNameOptions,
+    propagated_object_directives: &[DirectiveName],
+    generated_operations: &mut GeneratedOperations,
+    // e.g. "the entrypoint `Query.HomeRoute`", for error messages
+    description: String,
+    location: Location,
 ) -> Vec<ArtifactPathAndContent> {
     let query_name = entrypoint.name.into();
+    let operation_name = operation_names
+        .entrypoint_operation_name(entrypoint.name)
+        .intern()
+        .into();
     // TODO when we do not call generate_entrypoint_artifact extraneously,
     // we can panic instead of using a default entrypoint type
     // TODO model this better so that the RootOperationName is somehow a
@@ -116,13 +155,20 @@ pub(crate) fn generate_entrypoint_artifacts_with_client_field_traversal_result<
     let parent_object = schema
         .server_entity_data
         .server_object_entity(entrypoint.parent_object_entity_id);
+    // Only declare the variables that are used by the merged selection set. Refinement
+    // pruning can remove every use of a variable, and servers reject operations that
+    // declare unused variables.
+    let reachable_variables = get_reachable_variables(merged_selection_map);
     let query_text = TNetworkProtocol::generate_query_text(
-        query_name,
+        operation_name,
         schema,
         merged_selection_map,
-        variable_definitions,
+        variable_definitions.filter(move |variable_definition| {
+            reachable_variables.contains(&variable_definition.name.item)
+        }),
         root_operation_name,
     );
+    generated_operations.insert(operation_name, &query_text, description.clone(), location);
     let refetch_paths_with_variables = traversal_state
         .refetch_paths
         .iter()
@@ -156,30 +202,48 @@ pub(crate) fn generate_entrypoint_artifacts_with_client_field_traversal_result<
         })
         .collect::<Vec<_>>();
 
-    let refetch_query_artifact_import =
-        generate_refetch_query_artifact_import(&refetch_paths_with_variables, file_extensions);
+    let refetch_query_artifact_import = generate_refetch_query_artifact_import(
+        entrypoint.type_and_field,
+        &refetch_paths_with_variables,
+        file_extensions,
+        artifact_layout,
+    );
 
     let normalization_ast_text =
         generate_normalization_ast_text(schema, merged_selection_map.values(), 1);
 
-    let concrete_type = schema.server_entity_data.server_object_entity(
-        if schema
-            .fetchable_types
-            .contains_key(&entrypoint.parent_object_entity_id)
-        {
-            entrypoint.parent_object_entity_id
-        } else {
-            *default_root_operation
-                .map(|(operation_id, _)| operation_id)
-                .unwrap_or_else(|| {
-                    schema
-                        .fetchable_types
-                        .iter()
-                        .next()
-                        .expect("Expected at least one fetchable type to exist")
-                        .0
-                })
-        },
+    let concrete_type_id = if schema
+        .fetchable_types
+        .contains_key(&entrypoint.parent_object_entity_id)
+    {
+        entrypoint.parent_object_entity_id
+    } else {
+        *default_root_operation
+            .map(|(operation_id, _)| operation_id)
+            .unwrap_or_else(|| {
+                schema
+                    .fetchable_types
+                    .iter()
+                    .next()
+                    .expect("Expected at least one fetchable type to exist")
+                    .0
+            })
+    };
+    let concrete_type = schema
+        .server_entity_data
+        .server_object_entity(concrete_type_id);
+
+    let type_metadata = generate_type_metadata(
+        schema,
+        concrete_type_id,
+        merged_selection_map,
+        propagated_object_directives,
+    );
+
+    let cache_hints = generate_cache_hints(
+        schema,
+        entrypoint.parent_object_entity_id,
+        merged_selection_map,
     );
 
     let mut paths_and_contents = EntrypointArtifactInfo {
@@ -187,14 +251,34 @@ pub(crate) fn generate_entrypoint_artifacts_with_client_field_traversal_result<
         query_name,
         parent_type: parent_object,
         normalization_ast_text,
+        type_metadata,
+        cache_hints,
         refetch_query_artifact_import,
         concrete_type: concrete_type.name,
     }
-    .path_and_content(file_extensions);
+    .path_and_content(file_extensions, artifact_layout);
 
-    for (index, (root_refetch_path, nested_selection_map, reachable_variables)) in
-        refetch_paths_with_variables.into_iter().enumerate()
+    let mut refetch_query_index_entries = vec![];
+    for (index, ((path, _), (root_refetch_path, nested_selection_map, reachable_variables))) in
+        traversal_state
+            .refetch_paths
+            .keys()
+            .zip(refetch_paths_with_variables)
+            .enumerate()
     {
+        let client_field =
+            schema.client_field(root_refetch_path.path_to_refetch_field_info.client_field_id);
+        let allowed_variables = variable_names_to_string(
+            &reachable_variables,
+            get_used_variables(
+                &root_refetch_path
+                    .path_to_refetch_field_info
+                    .imperatively_loaded_field_variant
+                    .subfields_or_inline_fragments,
+            )
+            .into_iter(),
+        );
+
         let artifact_info = get_imperatively_loaded_artifact_info(
             schema,
             entrypoint,
@@ -202,25 +286,61 @@ pub(crate) fn generate_entrypoint_artifacts_with_client_field_traversal_result<
             nested_selection_map,
             &reachable_variables,
             index,
+            operation_names,
+        );
+
+        let refetch_description = format!(
+            "the refetch {} for `{}.{}` of {description}",
+            artifact_info.root_operation_name.0,
+            client_field.type_and_field.type_name,
+            client_field.type_and_field.field_name
         );
+        refetch_query_index_entries.push(RefetchQueryIndexEntry {
+            client_field: client_field.type_and_field,
+            operation_kind: artifact_info.root_operation_name.0.clone(),
+            path: &path.linked_fields,
+            allowed_variables,
+            required_variables: artifact_info
+                .variable_definitions
+                .iter()
+                .filter(|variable_definition| {
+                    matches!(variable_definition.type_, GraphQLTypeAnnotation::NonNull(_))
+                        && variable_definition.default_value.is_none()
+                })
+                .map(|variable_definition| variable_definition.name.item)
+                .collect(),
+        });
 
         paths_and_contents.extend(get_artifact_for_imperatively_loaded_field(
             schema,
             artifact_info,
             file_extensions,
+            artifact_layout,
+            generated_operations,
+            refetch_description,
+            location,
         ))
     }
 
+    paths_and_contents.push(generate_refetch_query_index_artifact(
+        entrypoint.type_and_field,
+        &refetch_query_index_entries,
+        file_extensions,
+        artifact_layout,
+    ));
+
     paths_and_contents
 }
 
 fn generate_refetch_query_artifact_import(
+    entrypoint_type_and_field: ObjectTypeAndFieldName,
     root_refetched_paths: &[(
         RootRefetchedPath,
         &MergedSelectionMap,
         BTreeSet<VariableName>,
     )],
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
 ) -> RefetchQueryArtifactImport {
     // TODO name the refetch queries with the path, or something, instead of
     // with indexes.
@@ -232,9 +352,12 @@ fn generate_refetch_query_artifact_import(
             ..
         } = &item.0;
         output.push_str(&format!(
-            "import refetchQuery{} from './__refetch__{}{}';\n",
-            query_index,
+            "import refetchQuery{} from '{}{}';\n",
             query_index,
+            artifact_layout.sibling_import_path(
+                entrypoint_type_and_field,
+                format!("{}__{}", *REFETCH_FIELD_NAME, query_index)
+            ),
             file_extensions.ts()
         ));
 
@@ -268,12 +391,14 @@ impl<TNetworkProtocol: NetworkProtocol> EntrypointArtifactInfo<'_, TNetworkProto
     fn path_and_content(
         self,
         file_extensions: GenerateFileExtensionsOption,
+        artifact_layout: ArtifactLayout,
     ) -> Vec<ArtifactPathAndContent> {
         let EntrypointArtifactInfo {
             query_name,
             parent_type,
             query_text,
             normalization_ast_text,
+            type_metadata,
             ..
         } = &self;
         let field_name = (*query_name).into();
@@ -294,9 +419,18 @@ impl<TNetworkProtocol: NetworkProtocol> EntrypointArtifactInfo<'_, TNetworkProto
                     const normalizationAst: NormalizationAst = {{\n\
                     {}kind: \"NormalizationAst\",\n\
                     {}selections: {normalization_ast_text},\n\
+                    {type_metadata}\
                     }};\n\
                     export default normalizationAst;\n",
-                    "  ", "  "
+                    "  ",
+                    "  ",
+                    type_metadata = type_metadata
+                        .as_ref()
+                        .map(|type_metadata| format!(
+                            "  typeMetadata: {},\n",
+                            type_metadata.print(1)
+                        ))
+                        .unwrap_or_default(),
                 ),
                 file_name: *NORMALIZATION_AST_FILE_NAME,
                 type_and_field: Some(ObjectTypeAndFieldName {
@@ -305,7 +439,7 @@ impl<TNetworkProtocol: NetworkProtocol> EntrypointArtifactInfo<'_, TNetworkProto
                 }),
             },
             ArtifactPathAndContent {
-                file_content: self.file_contents(file_extensions),
+                file_content: self.file_contents(file_extensions, artifact_layout),
                 file_name: *ENTRYPOINT_FILE_NAME,
                 type_and_field: Some(ObjectTypeAndFieldName {
                     type_name,
@@ -315,12 +449,17 @@ impl<TNetworkProtocol: NetworkProtocol> EntrypointArtifactInfo<'_, TNetworkProto
         ]
     }
 
-    fn file_contents(self, file_extensions: GenerateFileExtensionsOption) -> String {
+    fn file_contents(
+        self,
+        file_extensions: GenerateFileExtensionsOption,
+        artifact_layout: ArtifactLayout,
+    ) -> String {
         let EntrypointArtifactInfo {
             refetch_query_artifact_import,
             query_name,
             parent_type,
             concrete_type,
+            cache_hints,
             ..
         } = self;
         let ts_file_extension = file_extensions.ts();
@@ -328,19 +467,27 @@ impl<TNetworkProtocol: NetworkProtocol> EntrypointArtifactInfo<'_, TNetworkProto
         let entrypoint_output_type_name =
             format!("{}__{}__output_type", parent_type.name, query_name);
 
-        let resolver_reader_file_name = *RESOLVER_READER;
-        let param_type_file_name = *RESOLVER_PARAM_TYPE;
-        let output_type_file_name = *RESOLVER_OUTPUT_TYPE;
-        let query_text_file_name = *QUERY_TEXT;
-        let normalization_text_file_name = *NORMALIZATION_AST;
+        let type_and_field = ObjectTypeAndFieldName {
+            type_name: parent_type.name,
+            field_name: query_name.into(),
+        };
+        let resolver_reader_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *RESOLVER_READER);
+        let param_type_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *RESOLVER_PARAM_TYPE);
+        let output_type_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *RESOLVER_OUTPUT_TYPE);
+        let query_text_file_name = artifact_layout.sibling_import_path(type_and_field, *QUERY_TEXT);
+        let normalization_text_file_name =
+            artifact_layout.sibling_import_path(type_and_field, *NORMALIZATION_AST);
         format!(
             "import type {{IsographEntrypoint, \
             NormalizationAst, RefetchQueryNormalizationArtifactWrapper}} from '@isograph/react';\n\
-            import {{{entrypoint_params_typename}}} from './{param_type_file_name}{ts_file_extension}';\n\
-            import {{{entrypoint_output_type_name}}} from './{output_type_file_name}{ts_file_extension}';\n\
-            import readerResolver from './{resolver_reader_file_name}{ts_file_extension}';\n\
-            import queryText from './{query_text_file_name}{ts_file_extension}';\n\
-            import normalizationAst from './{normalization_text_file_name}{ts_file_extension}';\n\
+            import {{{entrypoint_params_typename}}} from '{param_type_file_name}{ts_file_extension}';\n\
+            import {{{entrypoint_output_type_name}}} from '{output_type_file_name}{ts_file_extension}';\n\
+            import readerResolver from '{resolver_reader_file_name}{ts_file_extension}';\n\
+            import queryText from '{query_text_file_name}{ts_file_extension}';\n\
+            import normalizationAst from '{normalization_text_file_name}{ts_file_extension}';\n\
             {refetch_query_artifact_import}\n\n\
             const artifact: IsographEntrypoint<\n\
             {}{entrypoint_params_typename},\n\
@@ -359,14 +506,18 @@ impl<TNetworkProtocol: NetworkProtocol> EntrypointArtifactInfo<'_, TNetworkProto
             {}  nestedRefetchQueries,\n\
             {}  readerArtifact: readerResolver,\n\
             {}}},\n\
+            {cache_hints}\
             }};\n\n\
             export default artifact;\n",
             "  ", "  ", "  ","  ", "  ", "  ", "  ", "  ", "  ", "  ", "  ", "  ", "  ", "  ", "  ",
+            cache_hints = cache_hints
+                .map(|cache_hints| format!("  cacheHints: {},\n", cache_hints.print(1)))
+                .unwrap_or_default(),
         )
     }
 }
 
-fn variable_names_to_string(
+pub(crate) fn variable_names_to_string(
     variable_names: &BTreeSet<VariableName>,
     field_variables: impl Iterator<Item = VariableName>,
 ) -> String {
@@ -384,7 +535,7 @@ fn variable_names_to_string(
     s
 }
 
-fn get_used_variables(
+pub(crate) fn get_used_variables(
     inline_fragments_or_linked_fields: &[WrappedSelectionMapSelection],
 ) -> BTreeSet<VariableName> {
     // TODO return impl iterator
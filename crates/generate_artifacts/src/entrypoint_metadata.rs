@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use common_lang_types::{ArtifactFileName, ArtifactPathAndContent, StringLiteralValue};
+use intern::string_key::Intern;
+use isograph_lang_types::{
+    ClientScalarSelectableId, DefinitionLocation, SelectionType, ServerObjectEntityId,
+};
+use isograph_schema::{
+    create_merged_selection_map_for_field_and_insert_into_global_map, initial_variable_context,
+    ClientScalarOrObjectSelectable, FieldToCompletedMergeTraversalStateMap, FieldTraversalResult,
+    MergedSelectionMap, MergedServerSelection, NetworkProtocol, Schema,
+};
+use lazy_static::lazy_static;
+use serde_json::json;
+
+lazy_static! {
+    pub static ref ENTRYPOINT_METADATA_FILE_NAME: ArtifactFileName =
+        "metadata.json".intern().into();
+}
+
+/// A selected server field that has a cost or an owner.
+struct AnnotatedField {
+    /// The path from the root of the query, e.g. `me.pets.name`.
+    path: String,
+    /// The field, e.g. `Pet.name`.
+    field: String,
+    cost: Option<u32>,
+    owner: Option<StringLiteralValue>,
+}
+
+/// Generate a JSON file for each entrypoint, describing the aggregate cost of
+/// the server fields that its query selects, and the teams that own them. These
+/// come from the `cost` and `owner` of each server field (e.g. from the `@cost` and
+/// `@owner` directives in a GraphQL schema), and are meant for governance tooling.
+///
+/// The cost of a query is the sum of the costs of its selections. A field that is
+/// selected in several places (or with different arguments) counts once per
+/// selection, and fields without a cost count as zero.
+pub(crate) fn generate_entrypoint_metadata_artifacts<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> Vec<ArtifactPathAndContent> {
+    let mut encountered_client_type_map = BTreeMap::new();
+    schema
+        .entrypoints
+        .keys()
+        .map(|entrypoint_id| {
+            generate_entrypoint_metadata_artifact(
+                schema,
+                *entrypoint_id,
+                &mut encountered_client_type_map,
+            )
+        })
+        .collect()
+}
+
+fn generate_entrypoint_metadata_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    entrypoint_id: ClientScalarSelectableId,
+    encountered_client_type_map: &mut FieldToCompletedMergeTraversalStateMap,
+) -> ArtifactPathAndContent {
+    let entrypoint = schema.client_field(entrypoint_id);
+
+    let FieldTraversalResult {
+        merged_selection_map,
+        ..
+    } = create_merged_selection_map_for_field_and_insert_into_global_map(
+        schema,
+        entrypoint.parent_object_entity_id,
+        schema
+            .server_entity_data
+            .server_object_entity(entrypoint.parent_object_entity_id),
+        entrypoint.selection_set_for_parent_query(),
+        encountered_client_type_map,
+        DefinitionLocation::Client(SelectionType::Scalar(entrypoint_id)),
+        &initial_variable_context(&SelectionType::Scalar(entrypoint)),
+    );
+
+    let mut annotated_fields = vec![];
+    collect_annotated_fields(
+        schema,
+        entrypoint.parent_object_entity_id,
+        &merged_selection_map,
+        "",
+        &mut annotated_fields,
+    );
+    annotated_fields.sort_by(|a, b| (&a.path, &a.field).cmp(&(&b.path, &b.field)));
+
+    let cost = annotated_fields
+        .iter()
+        .filter_map(|annotated_field| annotated_field.cost)
+        .map(u64::from)
+        .sum::<u64>();
+    let owners = annotated_fields
+        .iter()
+        .filter_map(|annotated_field| annotated_field.owner.map(|owner| owner.to_string()))
+        .collect::<BTreeSet<_>>();
+
+    let metadata = json!({
+        "entrypoint": format!(
+            "{}.{}",
+            entrypoint.type_and_field.type_name, entrypoint.type_and_field.field_name
+        ),
+        "cost": cost,
+        "owners": owners,
+        "fields": annotated_fields
+            .iter()
+            .map(|annotated_field| {
+                json!({
+                    "path": annotated_field.path,
+                    "field": annotated_field.field,
+                    "cost": annotated_field.cost,
+                    "owner": annotated_field.owner.map(|owner| owner.to_string()),
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+
+    ArtifactPathAndContent {
+        file_content: format!(
+            "{}\n",
+            serde_json::to_string_pretty(&metadata)
+                .expect("Expected entrypoint metadata to be serializable")
+        ),
+        file_name: *ENTRYPOINT_METADATA_FILE_NAME,
+        type_and_field: Some(entrypoint.type_and_field),
+    }
+}
+
+fn collect_annotated_fields<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+    path: &str,
+    annotated_fields: &mut Vec<AnnotatedField>,
+) {
+    let parent_object_name = schema
+        .server_entity_data
+        .server_object_entity(parent_object_entity_id)
+        .name;
+    let selectables = &schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&parent_object_entity_id)
+        .expect(
+            "Expected parent_object_entity_id to exist \
+            in server_object_entity_extra_info",
+        )
+        .selectables;
+    let path_to = |name: &dyn std::fmt::Display| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{path}.{name}")
+        }
+    };
+
+    for selection in selection_map.values() {
+        match selection {
+            MergedServerSelection::ScalarField(scalar_field) => {
+                let Some(DefinitionLocation::Server(SelectionType::Scalar(
+                    server_scalar_selectable_id,
+                ))) = selectables.get(&scalar_field.name.into())
+                else {
+                    continue;
+                };
+                let selectable = schema.server_scalar_selectable(*server_scalar_selectable_id);
+                if selectable.cost.is_some() || selectable.owner.is_some() {
+                    annotated_fields.push(AnnotatedField {
+                        path: path_to(&scalar_field.name),
+                        field: format!("{parent_object_name}.{}", scalar_field.name),
+                        cost: selectable.cost,
+                        owner: selectable.owner,
+                    });
+                }
+            }
+            MergedServerSelection::LinkedField(linked_field) => {
+                let Some(DefinitionLocation::Server(SelectionType::Object(
+                    server_object_selectable_id,
+                ))) = selectables.get(&linked_field.name.into())
+                else {
+                    continue;
+                };
+                let selectable = schema.server_object_selectable(*server_object_selectable_id);
+                let linked_field_path = path_to(&linked_field.name);
+                if selectable.cost.is_some() || selectable.owner.is_some() {
+                    annotated_fields.push(AnnotatedField {
+                        path: linked_field_path.clone(),
+                        field: format!("{parent_object_name}.{}", linked_field.name),
+                        cost: selectable.cost,
+                        owner: selectable.owner,
+                    });
+                }
+                collect_annotated_fields(
+                    schema,
+                    *selectable.target_object_entity.inner(),
+                    &linked_field.selection_map,
+                    &linked_field_path,
+                    annotated_fields,
+                );
+            }
+            MergedServerSelection::InlineFragment(inline_fragment) => {
+                // Fields within a type refinement are at the same path as the
+                // fields of the parent.
+                if let Some(SelectionType::Object(refined_object_entity_id)) = schema
+                    .server_entity_data
+                    .defined_entities
+                    .get(&inline_fragment.type_to_refine_to.into())
+                {
+                    collect_annotated_fields(
+                        schema,
+                        *refined_object_entity_id,
+                        &inline_fragment.selection_map,
+                        path,
+                        annotated_fields,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -3,10 +3,14 @@ use std::fmt::Debug;
 use common_lang_types::SelectableName;
 use graphql_lang_types::{GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation};
 
+use intern::Lookup;
 use isograph_lang_types::{
-    DefinitionLocation, SelectionType, ServerEntityId, TypeAnnotation, UnionVariant,
+    DefinitionLocation, SelectionType, ServerEntityId, ServerObjectEntityId, TypeAnnotation,
+    UnionVariant,
+};
+use isograph_schema::{
+    ImperativelyLoadedFieldVariant, NetworkProtocol, Schema, ServerSelectableId,
 };
-use isograph_schema::{NetworkProtocol, Schema, ServerSelectableId};
 
 pub(crate) fn format_parameter_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
@@ -40,6 +44,188 @@ pub(crate) fn format_parameter_type<TNetworkProtocol: NetworkProtocol>(
     }
 }
 
+/// The params of an imperatively loaded field (e.g. of a mutation field exposed
+/// with @exposeField), e.g. `params: { readonly tagline: string }`.
+///
+/// These are the arguments of the exposed field and of the linked fields in its
+/// path, except for those that are read from the parent object, per the field map.
+/// If the field map only sets a field of an input object argument (e.g. to:
+/// "input.id"), the argument is typed as the input object without that field. The
+/// params can be omitted if none of them are required.
+pub(crate) fn format_imperatively_loaded_field_params<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    variant: &ImperativelyLoadedFieldVariant,
+) -> String {
+    let field_map_paths = variant
+        .field_map
+        .iter()
+        .map(|field_map_item| {
+            let split_to_arg = field_map_item.split_to_arg();
+            std::iter::once(split_to_arg.to_argument_name)
+                .chain(split_to_arg.to_field_names)
+                .map(|name| name.lookup())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let arguments = variant
+        .top_level_schema_field_arguments
+        .iter()
+        .chain(variant.primary_field_path_arguments.iter())
+        .map(|argument| ParamField {
+            name: argument.name.item.lookup(),
+            type_: format_parameter_type(schema, argument.type_.clone(), 1),
+            is_optional: argument.type_.is_nullable() || argument.default_value.is_some(),
+            input_object_entity_id: match &argument.type_ {
+                GraphQLTypeAnnotation::Named(named) => input_object_entity_id(named.0.item),
+                GraphQLTypeAnnotation::NonNull(non_null) => match non_null.as_ref() {
+                    GraphQLNonNullTypeAnnotation::Named(named) => {
+                        input_object_entity_id(named.0.item)
+                    }
+                    GraphQLNonNullTypeAnnotation::List(_) => None,
+                },
+                GraphQLTypeAnnotation::List(_) => None,
+            },
+        })
+        .collect();
+
+    let (params_type, is_optional) = format_params_object(schema, arguments, &field_map_paths, 0);
+    format!(
+        "params{}: {params_type}",
+        if is_optional { "?" } else { "" }
+    )
+}
+
+/// An argument, or a field of an input object, that may be passed as a param.
+struct ParamField {
+    name: &'static str,
+    type_: String,
+    is_optional: bool,
+    /// Set if the param is a (non-list) input object, whose fields may be read from
+    /// the parent object.
+    input_object_entity_id: Option<ServerObjectEntityId>,
+}
+
+/// Print the params as an object type, omitting the fields that are read from the
+/// parent object. Returns the type, and whether every field in it is optional.
+fn format_params_object<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    fields: Vec<ParamField>,
+    field_map_paths: &[Vec<&str>],
+    indentation_level: u8,
+) -> (String, bool) {
+    let mut s = "{\n".to_string();
+    let mut is_optional = true;
+    for field in fields {
+        let nested_field_map_paths = field_map_paths
+            .iter()
+            .filter(|path| path.first() == Some(&field.name))
+            .map(|path| path[1..].to_vec())
+            .collect::<Vec<_>>();
+        if nested_field_map_paths.iter().any(|path| path.is_empty()) {
+            // The entire param is read from the parent object.
+            continue;
+        }
+
+        let (type_, field_is_optional) = match field.input_object_entity_id {
+            // The runtime creates the input object if it is not passed, so it is
+            // optional if its remaining fields are.
+            Some(input_object_entity_id) if !nested_field_map_paths.is_empty() => {
+                format_params_object(
+                    schema,
+                    input_object_fields(schema, input_object_entity_id, indentation_level + 1),
+                    &nested_field_map_paths,
+                    indentation_level + 1,
+                )
+            }
+            _ => (field.type_, field.is_optional),
+        };
+        is_optional &= field_is_optional;
+        s.push_str(&format!(
+            "{}readonly {}{}: {type_},\n",
+            "  ".repeat((indentation_level + 1) as usize),
+            field.name,
+            if field_is_optional { "?" } else { "" },
+        ));
+    }
+    if s == "{\n" {
+        return ("Record<PropertyKey, never>".to_string(), is_optional);
+    }
+    s.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
+    (s, is_optional)
+}
+
+/// The fields of an input object that is printed at indentation_level.
+fn input_object_fields<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    input_object_entity_id: ServerObjectEntityId,
+    indentation_level: u8,
+) -> Vec<ParamField> {
+    schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&input_object_entity_id)
+        .expect("Expected input_object_entity_id to exist in server_object_entity_extra_info")
+        .selectables
+        .iter()
+        .filter_map(
+            |(name, field_definition_location)| match field_definition_location {
+                DefinitionLocation::Server(server_selectable_id) => {
+                    Some((name, *server_selectable_id))
+                }
+                DefinitionLocation::Client(_) => None,
+            },
+        )
+        .map(|(name, server_selectable_id)| {
+            let (is_optional, selection_type, input_object_entity_id) =
+                match schema.server_selectable(server_selectable_id) {
+                    SelectionType::Scalar(scalar_selectable) => (
+                        is_nullable(&scalar_selectable.target_scalar_entity),
+                        scalar_selectable
+                            .target_scalar_entity
+                            .clone()
+                            .map(&mut SelectionType::Scalar),
+                        None,
+                    ),
+                    SelectionType::Object(object_selectable) => (
+                        is_nullable(&object_selectable.target_object_entity),
+                        object_selectable
+                            .target_object_entity
+                            .clone()
+                            .map(&mut SelectionType::Object),
+                        non_list_object_entity_id(&object_selectable.target_object_entity),
+                    ),
+                };
+            ParamField {
+                name: name.lookup(),
+                type_: format_type_annotation(schema, &selection_type, indentation_level),
+                is_optional,
+                input_object_entity_id,
+            }
+        })
+        .collect()
+}
+
+fn non_list_object_entity_id(
+    type_annotation: &TypeAnnotation<ServerObjectEntityId>,
+) -> Option<ServerObjectEntityId> {
+    match type_annotation {
+        TypeAnnotation::Scalar(object_entity_id) => Some(*object_entity_id),
+        TypeAnnotation::Union(union) if union.variants.len() == 1 => match union.variants.first() {
+            Some(UnionVariant::Scalar(object_entity_id)) => Some(*object_entity_id),
+            _ => None,
+        },
+        TypeAnnotation::Union(_) | TypeAnnotation::Plural(_) => None,
+    }
+}
+
+fn input_object_entity_id(server_entity_id: ServerEntityId) -> Option<ServerObjectEntityId> {
+    match server_entity_id {
+        ServerEntityId::Object(object_entity_id) => Some(object_entity_id),
+        ServerEntityId::Scalar(_) => None,
+    }
+}
+
 fn format_server_field_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     field: ServerEntityId,
@@ -116,7 +302,7 @@ fn format_field_definition<TNetworkProtocol: NetworkProtocol>(
     )
 }
 
-fn is_nullable<T: Ord + Debug>(type_annotation: &TypeAnnotation<T>) -> bool {
+pub(crate) fn is_nullable<T: Ord + Debug>(type_annotation: &TypeAnnotation<T>) -> bool {
     match type_annotation {
         TypeAnnotation::Union(union) => union.nullable,
         TypeAnnotation::Plural(_) => false,
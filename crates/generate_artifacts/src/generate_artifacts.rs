@@ -1,6 +1,7 @@
 use common_lang_types::{
     derive_display, ArtifactFileName, ArtifactFilePrefix, ArtifactPathAndContent, DescriptionValue,
-    Location, ObjectTypeAndFieldName, SelectableNameOrAlias, Span, WithLocation, WithSpan,
+    IsographObjectTypeName, Location, ObjectTypeAndFieldName, RelativePathToSourceFile,
+    SelectableNameOrAlias, Span, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
     GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation,
@@ -8,7 +9,7 @@ use graphql_lang_types::{
 use intern::{string_key::Intern, Lookup};
 
 use core::panic;
-use isograph_config::CompilerConfig;
+use isograph_config::{CompilerConfig, NullableFieldOptions};
 use isograph_lang_types::{
     ArgumentKeyAndValue, ClientFieldDirectiveSet, ClientScalarSelectableId, DefinitionLocation,
     EmptyDirectiveSet, NonConstantValue, ObjectSelectionDirectiveSet, ScalarSelection,
@@ -17,34 +18,52 @@ use isograph_lang_types::{
     UnionVariant, VariableDefinition,
 };
 use isograph_schema::{
-    accessible_client_fields, description, inline_fragment_reader_selection_set,
-    output_type_annotation, selection_map_wrapped, ClientFieldVariant, ClientScalarSelectable,
-    ClientSelectableId, FieldMapItem, FieldTraversalResult, NameAndArguments, NetworkProtocol,
-    NormalizationKey, ScalarSelectableId, Schema, SchemaServerObjectSelectableVariant,
-    UserWrittenClientTypeInfo, ValidatedSelection, ValidatedVariableDefinition,
-    WrappedSelectionMapSelection,
+    accessible_client_fields, description, implicit_concrete_type_refinements,
+    inline_fragment_reader_selection_set, output_type_annotation, selection_map_wrapped,
+    unused_client_types, ClientFieldVariant, ClientOrServerObjectSelectable, ClientPointerVariant,
+    ClientScalarSelectable, ClientSelectableId, FieldMapItem, FieldTraversalResult,
+    NameAndArguments, NetworkProtocol, NormalizationKey, ObjectSelectable, ScalarSelectableId,
+    Schema, SchemaServerObjectSelectableVariant, ServerScalarSelectable, UserWrittenClientTypeInfo,
+    ValidatedSelection, ValidatedVariableDefinition, WrappedSelectionMapSelection,
+    TYPENAME_FIELD_NAME,
 };
 use lazy_static::lazy_static;
 use std::{
-    collections::{BTreeMap, BTreeSet, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{Debug, Display},
 };
 
 use crate::{
+    artifact_provenance::add_artifact_provenance,
+    compile_manifest::generate_compile_manifest_artifact,
     eager_reader_artifact::{
         generate_eager_reader_artifacts, generate_eager_reader_condition_artifact,
         generate_eager_reader_output_type_artifact, generate_eager_reader_param_type_artifact,
+        generate_link_output_type_artifact, generate_link_reader_artifact,
+        generate_node_pointer_output_type_artifact, generate_node_pointer_reader_artifacts,
+        generate_paginated_field_output_type_artifact, generate_paginated_field_reader_artifacts,
     },
     entrypoint_artifact::{
         generate_entrypoint_artifacts,
         generate_entrypoint_artifacts_with_client_field_traversal_result,
     },
-    format_parameter_type::format_parameter_type,
+    entrypoint_metadata::generate_entrypoint_metadata_artifacts,
+    format_parameter_type::{
+        format_imperatively_loaded_field_params, format_parameter_type, is_nullable,
+    },
     import_statements::{LinkImports, ParamTypeImports, UpdatableImports},
     iso_overload_file::build_iso_overload_artifact,
+    javascript_value::{argument_value_to_javascript_value, arguments_to_javascript_value},
+    operation_names::{GeneratedOperations, OperationNameError},
+    optimistic_response_type::{
+        generate_optimistic_response_type_artifact, get_mutation_field_selection_maps,
+    },
+    reader_ast_types::generate_reader_ast_types_artifact,
     refetch_reader_artifact::{
         generate_refetch_output_type_artifact, generate_refetch_reader_artifact,
     },
+    schema_metadata::generate_schema_metadata_artifact,
+    schema_search_index::generate_schema_search_index_artifact,
 };
 
 lazy_static! {
@@ -55,8 +74,12 @@ lazy_static! {
     pub static ref NORMALIZATION_AST_FILE_NAME: ArtifactFileName =
         "normalization_ast.ts".intern().into();
     pub static ref NORMALIZATION_AST: ArtifactFilePrefix = "normalization_ast".intern().into();
+    pub static ref OPTIMISTIC_RESPONSE_TYPE_FILE_NAME: ArtifactFileName =
+        "optimistic_response_type.ts".intern().into();
     pub static ref QUERY_TEXT_FILE_NAME: ArtifactFileName = "query_text.ts".intern().into();
     pub static ref QUERY_TEXT: ArtifactFilePrefix = "query_text".intern().into();
+    pub static ref REFETCH_QUERY_INDEX_FILE_NAME: ArtifactFileName =
+        "refetch_query_index.ts".intern().into();
     pub static ref REFETCH_READER_FILE_NAME: ArtifactFileName = "refetch_reader.ts".intern().into();
     pub static ref REFETCH_READER: ArtifactFilePrefix = "refetch_reader".intern().into();
     pub static ref RESOLVER_OUTPUT_TYPE_FILE_NAME: ArtifactFileName =
@@ -91,28 +114,71 @@ lazy_static! {
 /// each field will have only one or the other; but once we have loadable fields,
 /// the loadable field will have both a refetch and resolver reader artifact.)
 ///
+/// Reader artifacts are therefore only generated for fields that are reachable
+/// from an entrypoint. Fields that are not reachable are never read, so they do
+/// not need readers.
+///
 /// Also, for each user-written resolver, we must generate a param_type artifact.
 /// For each resolver that is reachable from a reader, we must also generate an
-/// output_type artifact.
+/// output_type artifact. (Param types import the output types of the client fields
+/// they select, so unreachable resolvers also get output types.) With the
+/// prune_unused_client_fields option, neither is generated for client fields that
+/// are not reachable from an entrypoint.
 ///
 /// TODO this should go through OutputFormat
+///
+/// iso_literal_sources contains the contents of the files that contain iso
+/// literals, which are used by the generate_artifact_provenance option.
+///
+/// This fails if two different generated queries or mutations have the same name.
 pub fn get_artifact_path_and_content<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     config: &CompilerConfig,
-) -> Vec<ArtifactPathAndContent> {
-    let mut artifact_path_and_content = get_artifact_path_and_content_impl(schema, config);
+    iso_literal_sources: &HashMap<RelativePathToSourceFile, &str>,
+) -> Result<Vec<ArtifactPathAndContent>, Vec<WithLocation<OperationNameError>>> {
+    let mut generated_operations = GeneratedOperations::default();
+    let mut artifact_path_and_content =
+        get_artifact_path_and_content_impl(schema, config, &mut generated_operations);
+    generated_operations.validate()?;
+    if config.options.generate_reader_ast_types {
+        artifact_path_and_content.push(generate_reader_ast_types_artifact());
+    }
+    if config.options.generate_artifact_provenance {
+        add_artifact_provenance(
+            schema,
+            config,
+            iso_literal_sources,
+            &mut artifact_path_and_content,
+        );
+    }
     if let Some(header) = config.options.generated_file_header {
         for artifact_path_and_content in artifact_path_and_content.iter_mut() {
             artifact_path_and_content.file_content =
                 format!("// {header}\n{}", artifact_path_and_content.file_content);
         }
     }
-    artifact_path_and_content
+    // These are JSON, so they cannot contain the header comment.
+    if config.options.generate_schema_metadata {
+        artifact_path_and_content.push(generate_schema_metadata_artifact(schema, config));
+    }
+    if config.options.generate_schema_search_index {
+        artifact_path_and_content.push(generate_schema_search_index_artifact(schema));
+    }
+    if config.options.generate_entrypoint_metadata {
+        artifact_path_and_content.extend(generate_entrypoint_metadata_artifacts(schema));
+    }
+    if config.options.generate_compile_manifest {
+        let compile_manifest =
+            generate_compile_manifest_artifact(schema, config, &artifact_path_and_content);
+        artifact_path_and_content.push(compile_manifest);
+    }
+    Ok(artifact_path_and_content)
 }
 
 fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     config: &CompilerConfig,
+    generated_operations: &mut GeneratedOperations,
 ) -> Vec<ArtifactPathAndContent> {
     let mut encountered_client_type_map = BTreeMap::new();
     let mut path_and_contents = vec![];
@@ -125,6 +191,10 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
             *entrypoint_id,
             &mut encountered_client_type_map,
             config.options.include_file_extensions_in_import_statements,
+            config.options.artifact_layout,
+            &config.options.operation_names,
+            &config.options.propagated_object_directives,
+            generated_operations,
         );
         path_and_contents.extend(entrypoint_path_and_content);
 
@@ -148,13 +218,15 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                     schema.server_object_selectable(*server_object_selectable_id);
                 match &server_object_selectable.object_selectable_variant {
                     SchemaServerObjectSelectableVariant::LinkedField => {}
-                    SchemaServerObjectSelectableVariant::InlineFragment => {
+                    SchemaServerObjectSelectableVariant::InlineFragment
+                    | SchemaServerObjectSelectableVariant::Upcast => {
                         path_and_contents.push(generate_eager_reader_condition_artifact(
                             schema,
                             server_object_selectable,
                             &inline_fragment_reader_selection_set(schema, server_object_selectable),
                             &traversal_state.refetch_paths,
                             config.options.include_file_extensions_in_import_statements,
+                            config.options.artifact_layout,
                         ));
                     }
                 }
@@ -162,37 +234,67 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
 
             DefinitionLocation::Client(SelectionType::Object(client_object_selectable_id)) => {
                 let client_object_selectable = schema.client_pointer(*client_object_selectable_id);
-                path_and_contents.extend(generate_eager_reader_artifacts(
-                    schema,
-                    &SelectionType::Object(client_object_selectable),
-                    config,
-                    UserWrittenClientTypeInfo {
-                        const_export_name: client_object_selectable.info.const_export_name,
-                        file_path: client_object_selectable.info.file_path,
-                        client_field_directive_set: ClientFieldDirectiveSet::None(
-                            EmptyDirectiveSet {},
-                        ),
-                    },
-                    &traversal_state.refetch_paths,
-                    config.options.include_file_extensions_in_import_statements,
-                    traversal_state.has_updatable,
-                ));
+                path_and_contents.extend(match client_object_selectable.variant {
+                    ClientPointerVariant::UserWritten => generate_eager_reader_artifacts(
+                        schema,
+                        &SelectionType::Object(client_object_selectable),
+                        config,
+                        UserWrittenClientTypeInfo {
+                            const_export_name: client_object_selectable.info.const_export_name,
+                            file_path: client_object_selectable.info.file_path,
+                            text_source: client_object_selectable.info.text_source,
+                            client_field_directive_set: ClientFieldDirectiveSet::None(
+                                EmptyDirectiveSet {},
+                            ),
+                        },
+                        &traversal_state.refetch_paths,
+                        config.options.include_file_extensions_in_import_statements,
+                        traversal_state.has_updatable,
+                    ),
+                    ClientPointerVariant::Node => generate_node_pointer_reader_artifacts(
+                        schema,
+                        client_object_selectable,
+                        config,
+                        &traversal_state.refetch_paths,
+                        config.options.include_file_extensions_in_import_statements,
+                    ),
+                });
             }
             DefinitionLocation::Client(SelectionType::Scalar(client_scalar_selectable_id)) => {
                 let client_scalar_selectable = schema.client_field(*client_scalar_selectable_id);
 
                 match &client_scalar_selectable.variant {
                     ClientFieldVariant::Link => (),
-                    ClientFieldVariant::UserWritten(info) => {
-                        path_and_contents.extend(generate_eager_reader_artifacts(
-                            schema,
-                            &SelectionType::Scalar(client_scalar_selectable),
-                            config,
-                            *info,
-                            &traversal_state.refetch_paths,
-                            config.options.include_file_extensions_in_import_statements,
-                            traversal_state.has_updatable,
-                        ));
+                    ClientFieldVariant::UserWritten(_) | ClientFieldVariant::Paginated(_) => {
+                        path_and_contents.extend(match &client_scalar_selectable.variant {
+                            ClientFieldVariant::Paginated(paginated_field_variant) => {
+                                generate_paginated_field_reader_artifacts(
+                                    schema,
+                                    client_scalar_selectable,
+                                    config,
+                                    *paginated_field_variant,
+                                    &traversal_state.refetch_paths,
+                                    config.options.include_file_extensions_in_import_statements,
+                                    traversal_state.has_updatable,
+                                )
+                            }
+                            ClientFieldVariant::UserWritten(info) => {
+                                generate_eager_reader_artifacts(
+                                    schema,
+                                    &SelectionType::Scalar(client_scalar_selectable),
+                                    config,
+                                    *info,
+                                    &traversal_state.refetch_paths,
+                                    config.options.include_file_extensions_in_import_statements,
+                                    traversal_state.has_updatable,
+                                )
+                            }
+                            ClientFieldVariant::Link
+                            | ClientFieldVariant::ImperativelyLoadedField(_) => panic!(
+                                "Expected client field to be user-written or paginated. \
+                                This is indicative of a bug in Isograph."
+                            ),
+                        });
 
                         if *was_ever_selected_loadably {
                             path_and_contents.push(generate_refetch_reader_artifact(
@@ -201,6 +303,7 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                                 &traversal_state.refetch_paths,
                                 true,
                                 config.options.include_file_extensions_in_import_statements,
+                                config.options.artifact_layout,
                                 &[FieldMapItem {
                                     from: "id".intern().into(),
                                     to: "id".intern().into(),
@@ -286,6 +389,21 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                                     variable_definitions_iter,
                                     &schema.find_query(),
                                     config.options.include_file_extensions_in_import_statements,
+                                    config.options.artifact_layout,
+                                    &config.options.operation_names,
+                                    &config.options.propagated_object_directives,
+                                    generated_operations,
+                                    format!(
+                                        "the loadable field `{}.{}`",
+                                        client_scalar_selectable.type_and_field.type_name,
+                                        client_scalar_selectable.type_and_field.field_name
+                                    ),
+                                    match &client_scalar_selectable.variant {
+                                        ClientFieldVariant::UserWritten(info) => {
+                                            Location::new(info.text_source, Span::new(0, 0))
+                                        }
+                                        _ => Location::generated(),
+                                    },
                                 ),
                             );
                         }
@@ -297,6 +415,7 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                             &traversal_state.refetch_paths,
                             false,
                             config.options.include_file_extensions_in_import_statements,
+                            config.options.artifact_layout,
                             &s.field_map,
                         ));
                     }
@@ -305,12 +424,38 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
         }
     }
 
+    // For each exposed mutation field, generate a type describing the fields that it
+    // refetches, which can be used for optimistic updates.
+    for (client_field_id, selection_map) in
+        get_mutation_field_selection_maps(schema, &encountered_client_type_map)
+    {
+        path_and_contents.push(generate_optimistic_response_type_artifact(
+            schema,
+            client_field_id,
+            &selection_map,
+        ));
+    }
+
+    // Client types that are not reachable from an entrypoint are never read, so if
+    // the user opts in, we do not generate their param types and output types.
+    let pruned_client_types = if config.options.prune_unused_client_fields {
+        unused_client_types(schema)
+    } else {
+        BTreeSet::new()
+    };
+
     for (client_type_id, user_written_client_type, _) in schema.user_written_client_types() {
+        if pruned_client_types.contains(&client_type_id) {
+            continue;
+        }
+
         // For each user-written client types, generate a param type artifact
         path_and_contents.push(generate_eager_reader_param_type_artifact(
             schema,
             &user_written_client_type,
             config.options.include_file_extensions_in_import_statements,
+            config.options.artifact_layout,
+            config.options.nullable_fields,
         ));
 
         match encountered_client_type_map.get(&DefinitionLocation::Client(client_type_id)) {
@@ -337,23 +482,33 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
         let client_type = schema.client_type(output_type_id);
 
         let artifact_path_and_content = match client_type {
-            SelectionType::Object(client_pointer) => {
-                Some(generate_eager_reader_output_type_artifact(
+            SelectionType::Object(client_pointer) => Some(match client_pointer.variant {
+                ClientPointerVariant::UserWritten => generate_eager_reader_output_type_artifact(
                     schema,
                     &SelectionType::Object(client_pointer),
                     config,
                     UserWrittenClientTypeInfo {
                         const_export_name: client_pointer.info.const_export_name,
                         file_path: client_pointer.info.file_path,
+                        text_source: client_pointer.info.text_source,
                         client_field_directive_set: ClientFieldDirectiveSet::None(
                             EmptyDirectiveSet {},
                         ),
                     },
                     config.options.include_file_extensions_in_import_statements,
-                ))
-            }
+                ),
+                ClientPointerVariant::Node => {
+                    generate_node_pointer_output_type_artifact(schema, client_pointer)
+                }
+            }),
             SelectionType::Scalar(client_field) => match client_field.variant {
-                ClientFieldVariant::Link => None,
+                ClientFieldVariant::Link => {
+                    // Link fields have no selections, so they are not in the
+                    // encountered_client_type_map, and their reader artifact is
+                    // generated along with their output type.
+                    path_and_contents.push(generate_link_reader_artifact(schema, client_field));
+                    Some(generate_link_output_type_artifact(schema, client_field))
+                }
                 ClientFieldVariant::UserWritten(info) => {
                     Some(generate_eager_reader_output_type_artifact(
                         schema,
@@ -366,6 +521,14 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
                 ClientFieldVariant::ImperativelyLoadedField(_) => {
                     Some(generate_refetch_output_type_artifact(schema, client_field))
                 }
+                ClientFieldVariant::Paginated(paginated_field_variant) => {
+                    Some(generate_paginated_field_output_type_artifact(
+                        client_field,
+                        paginated_field_variant,
+                        config.options.include_file_extensions_in_import_statements,
+                        config.options.artifact_layout,
+                    ))
+                }
             },
         };
 
@@ -377,142 +540,36 @@ fn get_artifact_path_and_content_impl<TNetworkProtocol: NetworkProtocol>(
     path_and_contents.push(build_iso_overload_artifact(
         schema,
         config.options.include_file_extensions_in_import_statements,
+        config.options.artifact_layout,
         config.options.no_babel_transform,
+        &pruned_client_types,
     ));
 
     path_and_contents
 }
 
 pub(crate) fn get_serialized_field_arguments(
-    // TODO make this an iterator
     arguments: &[ArgumentKeyAndValue],
     indentation_level: u8,
 ) -> String {
-    if arguments.is_empty() {
-        return "null".to_string();
-    }
-
-    let mut s = "[".to_string();
-
-    for argument in arguments {
-        s.push_str(&get_serialized_field_argument(argument, indentation_level));
-    }
-
-    s.push_str(&format!("{}]", "  ".repeat(indentation_level as usize)));
-    s
+    arguments_to_javascript_value(arguments).print(indentation_level)
 }
 
-fn get_serialized_field_argument(
-    // TODO make this an iterator
-    argument: &ArgumentKeyAndValue,
+/// Serialize an argument value, which starts on a line indented at indentation_level.
+pub(crate) fn get_serialized_argument_value(
+    value: &NonConstantValue,
     indentation_level: u8,
 ) -> String {
-    let indent_1 = "  ".repeat((indentation_level + 1) as usize);
-    let indent_2 = "  ".repeat((indentation_level + 2) as usize);
-    let indent_3 = "  ".repeat((indentation_level + 3) as usize);
-
-    let argument_name = argument.key;
-
-    match &argument.value {
-        NonConstantValue::Variable(variable_name) => {
-            format!(
-                "\n\
-                {indent_1}[\n\
-                {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"Variable\", name: \"{variable_name}\" }},\n\
-                {indent_1}],\n",
-            )
-        }
-        NonConstantValue::Integer(int_value) => {
-            format!(
-                "\n\
-                {indent_1}[\n\
-                {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"Literal\", value: {int_value} }},\n\
-                {indent_1}],\n"
-            )
-        }
-        NonConstantValue::Boolean(bool) => {
-            let bool_string = bool.to_string();
-            format!(
-                "\n\
-                {indent_1}[\n\
-                {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"Literal\", value: {bool_string} }},\n\
-                {indent_1}],\n"
-            )
-        }
-        NonConstantValue::String(s) => {
-            format!(
-                "\n\
-                {indent_1}[\n\
-                {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"String\", value: \"{s}\" }},\n\
-                {indent_1}],\n"
-            )
-        }
-        NonConstantValue::Float(f) => {
-            let float = f.as_float();
-            format!(
-                "\n\
-                {indent_1}[\n\
-                {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"Literal\", value: {float} }},\n\
-                {indent_1}],\n"
-            )
-        }
-        NonConstantValue::Null => {
-            format!(
-                "\n\
-                {indent_1}[\n\
-                {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"Literal\", value: null }},\n\
-                {indent_1}],\n"
-            )
-        }
-        NonConstantValue::Enum(e) => {
-            format!(
-                "\n\
-                {indent_1}[\n\
-                {indent_2}\"{argument_name}\",\n\
-                {indent_2}{{ kind: \"Enum\", value: \"{e}\" }},\n\
-                {indent_1}],\n"
-            )
-        }
-        NonConstantValue::List(_) => panic!("Lists are not supported here"),
-        NonConstantValue::Object(object) => format!(
-            "\n\
-            {indent_1}[\n\
-            {indent_2}\"{argument_name}\",\n\
-            {indent_2}{{\n\
-            {indent_3}kind: \"Object\",\n\
-            {indent_3}value: [{}\n\
-            {indent_3}]\n\
-            {indent_2}}},\n\
-            {indent_1}],\n",
-            object
-                .iter()
-                .map(|entry| {
-                    get_serialized_field_argument(
-                        &ArgumentKeyAndValue {
-                            key: entry.name.item.unchecked_conversion(),
-                            value: entry.value.item.clone(),
-                        },
-                        indentation_level + 3,
-                    )
-                })
-                .collect::<Vec<_>>()
-                .join("")
-        ),
-    }
+    argument_value_to_javascript_value(value).print(indentation_level)
 }
 
-pub(crate) fn generate_output_type<TNetworkProtocol: NetworkProtocol>(
+pub fn generate_output_type<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
     client_field: &ClientScalarSelectable<TNetworkProtocol>,
 ) -> ClientFieldOutputType {
     let variant = &client_field.variant;
     match variant {
-        ClientFieldVariant::Link => ClientFieldOutputType("Link".to_string()),
+        ClientFieldVariant::Link => ClientFieldOutputType(link_output_type(schema, client_field)),
         ClientFieldVariant::UserWritten(info) => match info.client_field_directive_set {
             ClientFieldDirectiveSet::None(_) => {
                 ClientFieldOutputType("ReturnType<typeof resolver>".to_string())
@@ -521,72 +578,189 @@ pub(crate) fn generate_output_type<TNetworkProtocol: NetworkProtocol>(
                 "(React.FC<CombineWithIntrinsicAttributes<ExtractSecondParam<typeof resolver>>>)"
                     .to_string(),
             ),
+            // Indexing with number fails to type check if the resolver does not return an array.
+            ClientFieldDirectiveSet::Plural(_) => ClientFieldOutputType(
+                "ReadonlyArray<ReturnType<typeof resolver>[number]>".to_string(),
+            ),
         },
-        ClientFieldVariant::ImperativelyLoadedField(_) => {
-            // TODO - we should not type params as any, but instead use some generated type
+        ClientFieldVariant::Paginated(paginated_field_variant) => ClientFieldOutputType(format!(
+            "{}__{}__param['data']['{}']",
+            client_field.type_and_field.type_name,
+            client_field.type_and_field.field_name,
+            paginated_field_variant.connection_field_name
+        )),
+        ClientFieldVariant::ImperativelyLoadedField(variant) => {
             // N.B. the string is a stable id for deduplicating
-            ClientFieldOutputType("(params?: any) => [string, () => void]".to_string())
+            ClientFieldOutputType(format!(
+                "({}) => [string, () => void]",
+                format_imperatively_loaded_field_params(schema, variant)
+            ))
         }
     }
 }
 
+/// The type of a link field, e.g. `Link<"Pet">`. A link on an abstract type (e.g.
+/// Node) points to a record of one of its concrete types, so it is typed as `Link`.
+pub(crate) fn link_output_type<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> String {
+    match schema
+        .server_entity_data
+        .server_object_entity(client_field.parent_object_entity_id)
+        .concrete_type
+    {
+        Some(concrete_type) => format!("Link<\"{concrete_type}\">"),
+        None => "Link".to_string(),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn generate_client_field_parameter_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     selection_map: &[WithSpan<ValidatedSelection>],
+    parent_object_entity_id: ServerObjectEntityId,
     nested_client_field_imports: &mut ParamTypeImports,
     loadable_fields: &mut ParamTypeImports,
     indentation_level: u8,
     link_fields: &mut LinkImports,
+    nullable_fields: NullableFieldOptions,
 ) -> ClientFieldParameterType {
     // TODO use unwraps
-    let mut client_field_parameter_type = "{\n".to_string();
+    let client_field_parameter_type = typename_variants(schema, selection_map)
+        .into_iter()
+        .map(|typename| {
+            let mut variant_type = "{\n".to_string();
+            for selection in selection_map.iter() {
+                write_param_type_from_selection(
+                    schema,
+                    &mut variant_type,
+                    selection,
+                    parent_object_entity_id,
+                    nested_client_field_imports,
+                    loadable_fields,
+                    indentation_level + 1,
+                    link_fields,
+                    typename,
+                    nullable_fields,
+                );
+            }
+            variant_type.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
+            variant_type
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
 
-    for selection in selection_map.iter() {
-        write_param_type_from_selection(
-            schema,
-            &mut client_field_parameter_type,
-            selection,
-            nested_client_field_imports,
-            loadable_fields,
-            indentation_level + 1,
-            link_fields,
-        );
+    ClientFieldParameterType(client_field_parameter_type)
+}
+
+/// If __typename is selected, the generated type is a discriminated union with one
+/// variant per concrete type that the parent type can be, in which __typename is
+/// typed as the literal name of that concrete type. Otherwise, there is a single
+/// variant in which __typename (if selected) is typed as a string.
+fn typename_variants<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selection_map: &[WithSpan<ValidatedSelection>],
+) -> Vec<Option<IsographObjectTypeName>> {
+    let parent_object_entity_id = selection_map.iter().find_map(|selection| {
+        let SelectionTypeContainingSelections::Scalar(scalar_field_selection) = &selection.item
+        else {
+            return None;
+        };
+        let DefinitionLocation::Server(server_scalar_selectable_id) =
+            scalar_field_selection.associated_data
+        else {
+            return None;
+        };
+        let field = schema.server_scalar_selectable(server_scalar_selectable_id);
+        (field.name.item == *TYPENAME_FIELD_NAME).then_some(field.parent_object_entity_id)
+    });
+    let Some(parent_object_entity_id) = parent_object_entity_id else {
+        return vec![None];
+    };
+
+    if let Some(concrete_type) = schema
+        .server_entity_data
+        .server_object_entity(parent_object_entity_id)
+        .concrete_type
+    {
+        return vec![Some(concrete_type)];
     }
-    client_field_parameter_type.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
 
-    ClientFieldParameterType(client_field_parameter_type)
+    // The concrete types of an abstract type are the targets of its inline fragments
+    // (i.e. the asConcreteType fields.)
+    let mut concrete_types = schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&parent_object_entity_id)
+        .into_iter()
+        .flat_map(|extra_info| extra_info.selectables.values())
+        .filter_map(|selectable_id| match selectable_id {
+            DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => {
+                let server_object_selectable =
+                    schema.server_object_selectable(*server_object_selectable_id);
+                match server_object_selectable.object_selectable_variant {
+                    SchemaServerObjectSelectableVariant::InlineFragment => Some(
+                        schema
+                            .server_entity_data
+                            .server_object_entity(
+                                *server_object_selectable.target_object_entity.inner(),
+                            )
+                            .name,
+                    ),
+                    SchemaServerObjectSelectableVariant::LinkedField
+                    | SchemaServerObjectSelectableVariant::Upcast => None,
+                }
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if concrete_types.is_empty() {
+        return vec![None];
+    }
+    concrete_types.sort_by_key(|concrete_type| concrete_type.to_string());
+    concrete_types.dedup();
+    concrete_types.into_iter().map(Some).collect()
 }
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn generate_client_field_updatable_data_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     selection_map: &[WithSpan<ValidatedSelection>],
+    parent_object_entity_id: ServerObjectEntityId,
     nested_client_field_imports: &mut ParamTypeImports,
     loadable_fields: &mut ParamTypeImports,
     indentation_level: u8,
     link_fields: &mut LinkImports,
     updatable_fields: &mut UpdatableImports,
+    nullable_fields: NullableFieldOptions,
 ) -> ClientFieldUpdatableDataType {
     // TODO use unwraps
 
-    let mut client_field_updatable_data_type = "{\n".to_string();
-
-    for selection in selection_map.iter() {
-        write_updatable_data_type_from_selection(
-            schema,
-            &mut client_field_updatable_data_type,
-            selection,
-            nested_client_field_imports,
-            loadable_fields,
-            indentation_level + 1,
-            link_fields,
-            updatable_fields,
-        );
-    }
-
-    client_field_updatable_data_type
-        .push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
+    let client_field_updatable_data_type = typename_variants(schema, selection_map)
+        .into_iter()
+        .map(|typename| {
+            let mut variant_type = "{\n".to_string();
+            for selection in selection_map.iter() {
+                write_updatable_data_type_from_selection(
+                    schema,
+                    &mut variant_type,
+                    selection,
+                    parent_object_entity_id,
+                    nested_client_field_imports,
+                    loadable_fields,
+                    indentation_level + 1,
+                    link_fields,
+                    updatable_fields,
+                    typename,
+                    nullable_fields,
+                );
+            }
+            variant_type.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
+            variant_type
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
 
     ClientFieldUpdatableDataType(client_field_updatable_data_type)
 }
@@ -596,11 +770,19 @@ fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     query_type_declaration: &mut String,
     selection: &WithSpan<ValidatedSelection>,
+    parent_object_entity_id: ServerObjectEntityId,
     nested_client_field_imports: &mut ParamTypeImports,
     loadable_fields: &mut ParamTypeImports,
     indentation_level: u8,
     link_fields: &mut LinkImports,
+    typename: Option<IsographObjectTypeName>,
+    nullable_fields: NullableFieldOptions,
 ) {
+    // Fields that are only defined on some concrete types of the parent are null
+    // if the record is of another type.
+    let is_refined =
+        implicit_concrete_type_refinements(schema, parent_object_entity_id, &selection.item)
+            .is_some();
     match &selection.item {
         SelectionTypeContainingSelections::Scalar(scalar_field_selection) => {
             match scalar_field_selection.associated_data {
@@ -608,29 +790,48 @@ fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                     let field = schema.server_scalar_selectable(server_scalar_selectable_id);
 
                     write_optional_description(
-                        field.description,
+                        server_scalar_selectable_description(schema, field),
                         query_type_declaration,
                         indentation_level,
                     );
 
                     let name_or_alias = scalar_field_selection.name_or_alias().item;
+                    let target_scalar_entity = reader_type_annotation(
+                        field.target_scalar_entity.clone(),
+                        &field.semantic_non_null_levels,
+                        nullable_fields,
+                    );
 
-                    let output_type =
-                        field
-                            .target_scalar_entity
-                            .clone()
-                            .map(&mut |scalar_entity_id| {
+                    let output_type = match typename {
+                        Some(typename) if field.name.item == *TYPENAME_FIELD_NAME => {
+                            format!("\"{typename}\"")
+                        }
+                        _ => print_param_type_declaration(
+                            &target_scalar_entity.clone().map(&mut |scalar_entity_id| {
                                 schema
                                     .server_entity_data
                                     .server_scalar_entity(scalar_entity_id)
                                     .javascript_name
-                            });
+                            }),
+                            nullable_fields,
+                        ),
+                    };
 
                     query_type_declaration.push_str(&format!(
-                        "{}readonly {}: {},\n",
+                        "{}readonly {}{}: {}{},\n",
                         "  ".repeat(indentation_level as usize),
                         name_or_alias,
-                        print_javascript_type_declaration(&output_type)
+                        optional_marker(
+                            is_nullable(&target_scalar_entity)
+                                || !scalar_field_selection.conditions.is_empty()
+                                || is_refined,
+                            nullable_fields
+                        ),
+                        output_type,
+                        conditional_type_suffix(
+                            !scalar_field_selection.conditions.is_empty() || is_refined,
+                            nullable_fields
+                        )
                     ));
                 }
                 DefinitionLocation::Client(client_field_id) => write_param_type_from_client_field(
@@ -642,6 +843,7 @@ fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                     link_fields,
                     scalar_field_selection,
                     client_field_id,
+                    nullable_fields,
                 ),
             }
         }
@@ -665,26 +867,91 @@ fn write_param_type_from_selection<TNetworkProtocol: NetworkProtocol>(
             query_type_declaration.push_str(&"  ".repeat(indentation_level as usize).to_string());
             let name_or_alias = linked_field.name_or_alias().item;
 
-            let type_annotation = output_type_annotation(&field).clone().map(&mut |_| {
+            let type_annotation = reader_type_annotation(
+                output_type_annotation(&field).clone(),
+                semantic_non_null_levels(&field),
+                nullable_fields,
+            )
+            .map(&mut |_| {
                 generate_client_field_parameter_type(
                     schema,
                     &linked_field.selection_set,
+                    *schema
+                        .object_selectable(linked_field.associated_data)
+                        .target_object_entity_id()
+                        .inner(),
                     nested_client_field_imports,
                     loadable_fields,
                     indentation_level,
                     link_fields,
+                    nullable_fields,
                 )
             });
 
             query_type_declaration.push_str(&format!(
-                "readonly {}: {},\n",
+                "readonly {}{}: {}{},\n",
                 name_or_alias,
-                print_javascript_type_declaration(&type_annotation),
+                optional_marker(
+                    is_nullable(&type_annotation)
+                        || !linked_field.conditions.is_empty()
+                        || is_refined,
+                    nullable_fields
+                ),
+                print_param_type_declaration(&type_annotation, nullable_fields),
+                conditional_type_suffix(
+                    !linked_field.conditions.is_empty() || is_refined,
+                    nullable_fields,
+                ),
             ));
         }
     }
 }
 
+/// Fields selected with @include or @skip (or that are only defined on some concrete
+/// types of the parent) may not have been fetched, in which case the reader will
+/// provide null.
+fn conditional_type_suffix(is_conditional: bool, nullable_fields: NullableFieldOptions) -> String {
+    if !is_conditional {
+        "".to_string()
+    } else {
+        format!(" | {}", nullable_fields.null_type())
+    }
+}
+
+/// The marker (if any) that makes a field an optional property, if it is nullable.
+/// With the semantic_non_null option, fields that are only null if there is an error
+/// are typed as non-null, since the reader throws instead of providing null.
+fn reader_type_annotation<T: Ord>(
+    type_annotation: TypeAnnotation<T>,
+    semantic_non_null_levels: &[u32],
+    nullable_fields: NullableFieldOptions,
+) -> TypeAnnotation<T> {
+    if nullable_fields.semantic_non_null {
+        type_annotation.with_non_null_levels(semantic_non_null_levels)
+    } else {
+        type_annotation
+    }
+}
+
+fn semantic_non_null_levels<'a, TNetworkProtocol: NetworkProtocol>(
+    field: &'a ObjectSelectable<'a, TNetworkProtocol>,
+) -> &'a [u32] {
+    match field {
+        DefinitionLocation::Server(server_object_selectable) => {
+            &server_object_selectable.semantic_non_null_levels
+        }
+        DefinitionLocation::Client(_) => &[],
+    }
+}
+
+fn optional_marker(is_nullable: bool, nullable_fields: NullableFieldOptions) -> &'static str {
+    if is_nullable {
+        nullable_fields.optional_marker()
+    } else {
+        ""
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
@@ -695,6 +962,7 @@ fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
     link_fields: &mut bool,
     scalar_field_selection: &ScalarSelection<ScalarSelectableId>,
     client_field_id: ClientScalarSelectableId,
+    nullable_fields: NullableFieldOptions,
 ) {
     let client_field = schema.client_field(client_field_id);
     write_optional_description(
@@ -706,16 +974,26 @@ fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
     match client_field.variant {
         ClientFieldVariant::Link => {
             *link_fields = true;
-            let output_type = "Link";
+            let output_type = link_output_type(schema, client_field);
             query_type_declaration.push_str(
                 &(format!(
-                    "readonly {}: {},\n",
+                    "readonly {}{}: {}{},\n",
                     scalar_field_selection.name_or_alias().item,
-                    output_type
+                    optional_marker(
+                        !scalar_field_selection.conditions.is_empty(),
+                        nullable_fields
+                    ),
+                    output_type,
+                    conditional_type_suffix(
+                        !scalar_field_selection.conditions.is_empty(),
+                        nullable_fields
+                    )
                 )),
             );
         }
-        ClientFieldVariant::UserWritten(_) | ClientFieldVariant::ImperativelyLoadedField(_) => {
+        ClientFieldVariant::UserWritten(_)
+        | ClientFieldVariant::ImperativelyLoadedField(_)
+        | ClientFieldVariant::Paginated(_) => {
             nested_client_field_imports.insert(client_field.type_and_field);
             let inner_output_type = format!(
                 "{}__output_type",
@@ -755,9 +1033,17 @@ fn write_param_type_from_client_field<TNetworkProtocol: NetworkProtocol>(
             };
             query_type_declaration.push_str(
                 &(format!(
-                    "readonly {}: {},\n",
+                    "readonly {}{}: {}{},\n",
                     scalar_field_selection.name_or_alias().item,
-                    output_type
+                    optional_marker(
+                        !scalar_field_selection.conditions.is_empty(),
+                        nullable_fields
+                    ),
+                    output_type,
+                    conditional_type_suffix(
+                        !scalar_field_selection.conditions.is_empty(),
+                        nullable_fields
+                    )
                 )),
             );
         }
@@ -769,12 +1055,20 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     query_type_declaration: &mut String,
     selection: &WithSpan<ValidatedSelection>,
+    parent_object_entity_id: ServerObjectEntityId,
     nested_client_field_imports: &mut ParamTypeImports,
     loadable_fields: &mut ParamTypeImports,
     indentation_level: u8,
     link_fields: &mut LinkImports,
     updatable_fields: &mut UpdatableImports,
+    typename: Option<IsographObjectTypeName>,
+    nullable_fields: NullableFieldOptions,
 ) {
+    // Fields that are only defined on some concrete types of the parent are null
+    // if the record is of another type.
+    let is_refined =
+        implicit_concrete_type_refinements(schema, parent_object_entity_id, &selection.item)
+            .is_some();
     match &selection.item {
         SelectionTypeContainingSelections::Scalar(scalar_field_selection) => {
             match scalar_field_selection.associated_data {
@@ -782,7 +1076,7 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                     let field = schema.server_scalar_selectable(server_scalar_selectable_id);
 
                     write_optional_description(
-                        field.description,
+                        server_scalar_selectable_description(schema, field),
                         query_type_declaration,
                         indentation_level,
                     );
@@ -815,11 +1109,32 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                             panic!("@loadable server fields are not supported")
                         }
                         ScalarSelectionDirectiveSet::None(_) => {
+                            let output_type = reader_type_annotation(
+                                output_type,
+                                &field.semantic_non_null_levels,
+                                nullable_fields,
+                            );
                             query_type_declaration.push_str(&format!(
-                                "{}readonly {}: {},\n",
+                                "{}readonly {}{}: {}{},\n",
                                 "  ".repeat(indentation_level as usize),
                                 name_or_alias,
-                                print_javascript_type_declaration(&output_type)
+                                optional_marker(
+                                    is_nullable(&output_type)
+                                        || !scalar_field_selection.conditions.is_empty()
+                                        || is_refined,
+                                    nullable_fields
+                                ),
+                                match typename {
+                                    Some(typename) if field.name.item == *TYPENAME_FIELD_NAME => {
+                                        format!("\"{typename}\"")
+                                    }
+                                    _ =>
+                                        print_param_type_declaration(&output_type, nullable_fields),
+                                },
+                                conditional_type_suffix(
+                                    !scalar_field_selection.conditions.is_empty() || is_refined,
+                                    nullable_fields
+                                )
                             ));
                         }
                     }
@@ -834,6 +1149,7 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                         link_fields,
                         scalar_field_selection,
                         client_field_id,
+                        nullable_fields,
                     );
                 }
             }
@@ -849,15 +1165,28 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
             query_type_declaration.push_str(&"  ".repeat(indentation_level as usize).to_string());
             let name_or_alias = linked_field.name_or_alias().item;
 
-            let type_annotation = output_type_annotation(&field).clone().map(&mut |_| {
+            let target_object_entity = match linked_field.object_selection_directive_set {
+                ObjectSelectionDirectiveSet::Updatable(_) => output_type_annotation(&field).clone(),
+                ObjectSelectionDirectiveSet::None(_) => reader_type_annotation(
+                    output_type_annotation(&field).clone(),
+                    semantic_non_null_levels(&field),
+                    nullable_fields,
+                ),
+            };
+            let type_annotation = target_object_entity.map(&mut |_| {
                 generate_client_field_updatable_data_type(
                     schema,
                     &linked_field.selection_set,
+                    *schema
+                        .object_selectable(linked_field.associated_data)
+                        .target_object_entity_id()
+                        .inner(),
                     nested_client_field_imports,
                     loadable_fields,
                     indentation_level,
                     link_fields,
                     updatable_fields,
+                    nullable_fields,
                 )
             });
 
@@ -874,9 +1203,19 @@ fn write_updatable_data_type_from_selection<TNetworkProtocol: NetworkProtocol>(
                 }
                 ObjectSelectionDirectiveSet::None(_) => {
                     query_type_declaration.push_str(&format!(
-                        "readonly {}: {},\n",
+                        "readonly {}{}: {}{},\n",
                         name_or_alias,
-                        print_javascript_type_declaration(&type_annotation),
+                        optional_marker(
+                            is_nullable(&type_annotation)
+                                || !linked_field.conditions.is_empty()
+                                || is_refined,
+                            nullable_fields
+                        ),
+                        print_param_type_declaration(&type_annotation, nullable_fields),
+                        conditional_type_suffix(
+                            !linked_field.conditions.is_empty() || is_refined,
+                            nullable_fields,
+                        ),
                     ));
                 }
             }
@@ -918,7 +1257,7 @@ fn get_loadable_field_type_from_arguments<TNetworkProtocol: NetworkProtocol>(
             loadable_field_type.push_str(", ");
         }
         is_first = false;
-        let is_optional = !matches!(arg.type_, GraphQLTypeAnnotation::NonNull(_));
+        let is_optional = argument_is_optional(arg);
         loadable_field_type.push_str(&format!(
             "readonly {}{}: {}",
             arg.name.item,
@@ -975,6 +1314,11 @@ fn format_type_for_js_inner(
     }
 }
 
+/// Arguments with a default value can be omitted, even if they are non-null.
+fn argument_is_optional(argument: &VariableDefinition<ServerEntityId>) -> bool {
+    !matches!(argument.type_, GraphQLTypeAnnotation::NonNull(_)) || argument.default_value.is_some()
+}
+
 pub(crate) fn generate_parameters<'a, TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     argument_definitions: impl Iterator<Item = &'a VariableDefinition<ServerEntityId>>,
@@ -982,7 +1326,7 @@ pub(crate) fn generate_parameters<'a, TNetworkProtocol: NetworkProtocol>(
     let mut s = "{\n".to_string();
     let indent = "  ";
     for arg in argument_definitions {
-        let is_optional = !matches!(arg.type_, GraphQLTypeAnnotation::NonNull(_));
+        let is_optional = argument_is_optional(arg);
         s.push_str(&format!(
             "{indent}readonly {}{}: {},\n",
             arg.name.item,
@@ -994,7 +1338,26 @@ pub(crate) fn generate_parameters<'a, TNetworkProtocol: NetworkProtocol>(
     s
 }
 
-fn write_optional_description(
+/// The description of a server scalar field, followed by a link to the
+/// specification of its scalar type, if the scalar has `@specifiedBy`.
+fn server_scalar_selectable_description<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    field: &ServerScalarSelectable<TNetworkProtocol>,
+) -> Option<DescriptionValue> {
+    let specified_by_url = schema
+        .server_entity_data
+        .server_scalar_entity(*field.target_scalar_entity.inner())
+        .specified_by_url;
+    match (field.description, specified_by_url) {
+        (description, None) => description,
+        (None, Some(url)) => Some(format!("@see {url}").intern().into()),
+        (Some(description), Some(url)) => {
+            Some(format!("{description}\n@see {url}").intern().into())
+        }
+    }
+}
+
+pub(crate) fn write_optional_description(
     description: Option<DescriptionValue>,
     query_type_declaration: &mut String,
     indentation_level: u8,
@@ -1009,17 +1372,29 @@ fn write_optional_description(
     }
 }
 
-fn print_javascript_type_declaration<T: Display + Ord + Debug>(
+pub(crate) fn print_javascript_type_declaration<T: Display + Ord + Debug>(
+    type_annotation: &TypeAnnotation<T>,
+) -> String {
+    let mut s = String::new();
+    print_javascript_type_declaration_impl(type_annotation, &mut s, "null");
+    s
+}
+
+/// Print the type of a field in a param type, in which nullable types are printed
+/// according to the nullable field options.
+fn print_param_type_declaration<T: Display + Ord + Debug>(
     type_annotation: &TypeAnnotation<T>,
+    nullable_fields: NullableFieldOptions,
 ) -> String {
     let mut s = String::new();
-    print_javascript_type_declaration_impl(type_annotation, &mut s);
+    print_javascript_type_declaration_impl(type_annotation, &mut s, nullable_fields.null_type());
     s
 }
 
 fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
     type_annotation: &TypeAnnotation<T>,
     s: &mut String,
+    null_type: &str,
 ) {
     match &type_annotation {
         TypeAnnotation::Scalar(scalar) => {
@@ -1043,13 +1418,14 @@ fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
                         }
                         UnionVariant::Plural(type_annotation) => {
                             s.push_str("ReadonlyArray<");
-                            print_javascript_type_declaration_impl(type_annotation, s);
+                            print_javascript_type_declaration_impl(type_annotation, s, null_type);
                             s.push('>');
                         }
                     }
                 }
                 if union_type_annotation.nullable {
-                    s.push_str(" | null");
+                    s.push_str(" | ");
+                    s.push_str(null_type);
                 }
                 s.push(')');
             } else {
@@ -1063,7 +1439,7 @@ fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
                     }
                     UnionVariant::Plural(type_annotation) => {
                         s.push_str("ReadonlyArray<");
-                        print_javascript_type_declaration_impl(type_annotation, s);
+                        print_javascript_type_declaration_impl(type_annotation, s, null_type);
                         s.push('>');
                     }
                 }
@@ -1071,7 +1447,7 @@ fn print_javascript_type_declaration_impl<T: Display + Ord + Debug>(
         }
         TypeAnnotation::Plural(type_annotation) => {
             s.push_str("ReadonlyArray<");
-            print_javascript_type_declaration_impl(type_annotation, s);
+            print_javascript_type_declaration_impl(type_annotation, s, null_type);
             s.push('>');
         }
     }
@@ -1090,7 +1466,7 @@ pub(crate) struct ClientFieldFunctionImportStatement(pub String);
 derive_display!(ClientFieldFunctionImportStatement);
 
 #[derive(Debug)]
-pub(crate) struct ClientFieldOutputType(pub String);
+pub struct ClientFieldOutputType(pub String);
 derive_display!(ClientFieldOutputType);
 
 #[derive(Debug)]
@@ -1,9 +1,9 @@
 use common_lang_types::{
-    ArtifactPathAndContent, ClientScalarSelectableName, IsographObjectTypeName,
+    ArtifactPathAndContent, ClientScalarSelectableName, IsographObjectTypeName, Location,
     ObjectTypeAndFieldName, QueryText,
 };
 use intern::string_key::Intern;
-use isograph_config::GenerateFileExtensionsOption;
+use isograph_config::{ArtifactLayout, GenerateFileExtensionsOption};
 use isograph_lang_types::RefetchQueryIndex;
 use isograph_schema::{
     ImperativelyLoadedFieldArtifactInfo, NetworkProtocol, Schema, REFETCH_FIELD_NAME,
@@ -12,6 +12,7 @@ use isograph_schema::{
 use crate::{
     generate_artifacts::{NormalizationAstText, QUERY_TEXT},
     normalization_ast_text::generate_normalization_ast_text,
+    operation_names::GeneratedOperations,
 };
 
 #[derive(Debug)]
@@ -28,6 +29,7 @@ impl ImperativelyLoadedEntrypointArtifactInfo {
     pub fn path_and_content(
         self,
         file_extensions: GenerateFileExtensionsOption,
+        artifact_layout: ArtifactLayout,
     ) -> Vec<ArtifactPathAndContent> {
         let ImperativelyLoadedEntrypointArtifactInfo {
             root_fetchable_field,
@@ -61,7 +63,7 @@ impl ImperativelyLoadedEntrypointArtifactInfo {
                 }),
             },
             ArtifactPathAndContent {
-                file_content: self.file_contents(file_extensions),
+                file_content: self.file_contents(file_extensions, artifact_layout),
                 file_name: file_name_prefix,
                 type_and_field: Some(ObjectTypeAndFieldName {
                     type_name,
@@ -73,22 +75,34 @@ impl ImperativelyLoadedEntrypointArtifactInfo {
 }
 
 impl ImperativelyLoadedEntrypointArtifactInfo {
-    pub(crate) fn file_contents(self, file_extensions: GenerateFileExtensionsOption) -> String {
+    pub(crate) fn file_contents(
+        self,
+        file_extensions: GenerateFileExtensionsOption,
+        artifact_layout: ArtifactLayout,
+    ) -> String {
         let ImperativelyLoadedEntrypointArtifactInfo {
             normalization_ast_text: normalization_ast,
             concrete_type,
             refetch_query_index,
+            root_fetchable_field,
+            root_fetchable_field_parent_object,
             ..
         } = self;
         let ts_file_extension = file_extensions.ts();
-        let query_text_file_name = format!(
-            "{}__{}__{}",
-            *REFETCH_FIELD_NAME, *QUERY_TEXT, refetch_query_index.0,
+        let query_text_file_name = artifact_layout.sibling_import_path(
+            ObjectTypeAndFieldName {
+                type_name: root_fetchable_field_parent_object,
+                field_name: root_fetchable_field.into(),
+            },
+            format!(
+                "{}__{}__{}",
+                *REFETCH_FIELD_NAME, *QUERY_TEXT, refetch_query_index.0,
+            ),
         );
 
         format!(
             "import type {{ IsographEntrypoint, ReaderAst, FragmentReference, NormalizationAst, RefetchQueryNormalizationArtifact }} from '@isograph/react';\n\
-            import queryText from './{query_text_file_name}{ts_file_extension}';\n\n\
+            import queryText from '{query_text_file_name}{ts_file_extension}';\n\n\
             const normalizationAst: NormalizationAst = {{\n\
             {}kind: \"NormalizationAst\",\n\
             {}selections: {normalization_ast},\n\
@@ -121,6 +135,10 @@ pub(crate) fn get_artifact_for_imperatively_loaded_field<TNetworkProtocol: Netwo
     schema: &Schema<TNetworkProtocol>,
     imperatively_loaded_field_artifact_info: ImperativelyLoadedFieldArtifactInfo,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
+    generated_operations: &mut GeneratedOperations,
+    description: String,
+    location: Location,
 ) -> Vec<ArtifactPathAndContent> {
     let ImperativelyLoadedFieldArtifactInfo {
         merged_selection_set,
@@ -140,6 +158,7 @@ pub(crate) fn get_artifact_for_imperatively_loaded_field<TNetworkProtocol: Netwo
         variable_definitions.iter(),
         &root_operation_name,
     );
+    generated_operations.insert(query_name, &query_text, description, location);
 
     let normalization_ast_text =
         generate_normalization_ast_text(schema, merged_selection_set.values(), 1);
@@ -152,5 +171,5 @@ pub(crate) fn get_artifact_for_imperatively_loaded_field<TNetworkProtocol: Netwo
         refetch_query_index,
         concrete_type,
     }
-    .path_and_content(file_extensions)
+    .path_and_content(file_extensions, artifact_layout)
 }
@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
 
 use common_lang_types::ObjectTypeAndFieldName;
-use isograph_config::GenerateFileExtensionsOption;
+use isograph_config::{ArtifactLayout, GenerateFileExtensionsOption};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum ImportedFileCategory {
@@ -28,16 +28,15 @@ pub(crate) type UpdatableImports = bool;
 pub(crate) fn reader_imports_to_import_statement(
     reader_imports: &ReaderImports,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
 ) -> String {
     let mut output = String::new();
     for (type_and_field, artifact_type) in reader_imports.iter() {
         output.push_str(&format!(
-            "import {}__{} from '../../{}/{}/{}{}';\n",
+            "import {}__{} from '{}{}';\n",
             type_and_field.underscore_separated(),
             artifact_type.filename(),
-            type_and_field.type_name,
-            type_and_field.field_name,
-            artifact_type.filename(),
+            artifact_layout.field_import_path(*type_and_field, artifact_type.filename()),
             file_extensions.ts()
         ));
     }
@@ -47,14 +46,14 @@ pub(crate) fn reader_imports_to_import_statement(
 pub(crate) fn param_type_imports_to_import_statement(
     param_type_imports: &ParamTypeImports,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
 ) -> String {
     let mut output = String::new();
     for type_and_field in param_type_imports.iter() {
         output.push_str(&format!(
-            "import {{ type {}__output_type }} from '../../{}/{}/output_type{}';\n",
+            "import {{ type {}__output_type }} from '{}{}';\n",
             type_and_field.underscore_separated(),
-            type_and_field.type_name,
-            type_and_field.field_name,
+            artifact_layout.field_import_path(*type_and_field, "output_type"),
             file_extensions.ts(),
         ));
     }
@@ -64,14 +63,14 @@ pub(crate) fn param_type_imports_to_import_statement(
 pub(crate) fn param_type_imports_to_import_param_statement(
     param_type_imports: &ParamTypeImports,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
 ) -> String {
     let mut output = String::new();
     for type_and_field in param_type_imports.iter() {
         output.push_str(&format!(
-            "import {{ type {}__param }} from '../../{}/{}/param_type{}';\n",
+            "import {{ type {}__param }} from '{}{}';\n",
             type_and_field.underscore_separated(),
-            type_and_field.type_name,
-            type_and_field.field_name,
+            artifact_layout.field_import_path(*type_and_field, "param_type"),
             file_extensions.ts()
         ));
     }
@@ -1,19 +1,20 @@
 use intern::Lookup;
-use isograph_config::GenerateFileExtensionsOption;
+use isograph_config::{ArtifactLayout, GenerateFileExtensionsOption};
 use isograph_lang_types::{ClientFieldDirectiveSet, SelectionType};
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::BTreeSet};
 
 use common_lang_types::{ArtifactPathAndContent, SelectableName};
 use isograph_schema::{
-    ClientScalarOrObjectSelectable, ClientScalarSelectable, ClientSelectable,
+    ClientScalarOrObjectSelectable, ClientScalarSelectable, ClientSelectable, ClientSelectableId,
     EntrypointDeclarationInfo, NetworkProtocol, Schema,
 };
 
-use crate::generate_artifacts::ISO_TS_FILE_NAME;
+use crate::generate_artifacts::{write_optional_description, ISO_TS_FILE_NAME};
 
 fn build_iso_overload_for_entrypoint<TNetworkProtocol: NetworkProtocol>(
     validated_client_field: &ClientScalarSelectable<TNetworkProtocol>,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
 ) -> (String, String) {
     let formatted_field = format!(
         "entrypoint {}.{}",
@@ -22,10 +23,10 @@ fn build_iso_overload_for_entrypoint<TNetworkProtocol: NetworkProtocol>(
     );
     let mut s: String = "".to_string();
     let import = format!(
-        "import entrypoint_{} from '../__isograph/{}/{}/entrypoint{}';\n",
+        "import entrypoint_{} from '../__isograph/{}{}';\n",
         validated_client_field.type_and_field.underscore_separated(),
-        validated_client_field.type_and_field.type_name,
-        validated_client_field.type_and_field.field_name,
+        artifact_layout
+            .path_within_artifact_directory(validated_client_field.type_and_field, "entrypoint"),
         file_extensions.ts()
     );
 
@@ -43,14 +44,14 @@ export function iso<T>(
 fn build_iso_overload_for_client_defined_type<TNetworkProtocol: NetworkProtocol>(
     client_type_and_variant: (ClientSelectable<TNetworkProtocol>, ClientFieldDirectiveSet),
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
 ) -> (String, String) {
     let (client_type, variant) = client_type_and_variant;
     let mut s: String = "".to_string();
     let import = format!(
-        "import {{ type {}__param }} from './{}/{}/param_type{}';\n",
+        "import {{ type {}__param }} from './{}{}';\n",
         client_type.type_and_field().underscore_separated(),
-        client_type.type_and_field().type_name,
-        client_type.type_and_field().field_name,
+        artifact_layout.path_within_artifact_directory(client_type.type_and_field(), "param_type"),
         file_extensions.ts()
     );
     let formatted_field = format!(
@@ -62,10 +63,12 @@ fn build_iso_overload_for_client_defined_type<TNetworkProtocol: NetworkProtocol>
         client_type.type_and_field().type_name,
         client_type.type_and_field().field_name
     );
+    // The description is shown when hovering over the call to iso.
+    s.push('\n');
+    write_optional_description(client_type.description(), &mut s, 0);
     if matches!(variant, ClientFieldDirectiveSet::Component(_)) {
         s.push_str(&format!(
-            "
-export function iso<T>(
+            "export function iso<T>(
   param: T & MatchesWhitespaceAndString<'{}', T>
 ): IdentityWithParamComponent<{}__param>;\n",
             formatted_field,
@@ -73,8 +76,7 @@ export function iso<T>(
         ));
     } else {
         s.push_str(&format!(
-            "
-export function iso<T>(
+            "export function iso<T>(
   param: T & MatchesWhitespaceAndString<'{}', T>
 ): IdentityWithParam<{}__param>;\n",
             formatted_field,
@@ -87,10 +89,16 @@ export function iso<T>(
 pub(crate) fn build_iso_overload_artifact<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
     no_babel_transform: bool,
+    pruned_client_types: &BTreeSet<ClientSelectableId>,
 ) -> ArtifactPathAndContent {
-    let mut imports = "import type { IsographEntrypoint } from '@isograph/react';\n".to_string();
-    let mut content = String::from(
+    let mut imports = artifact_layout_migration_note(artifact_layout);
+    imports.push_str(
+        "import type { IsographEntrypoint, IsographRuntimeVersion } from '@isograph/react';\n",
+    );
+    let mut content = runtime_version_check();
+    content.push_str(
         "
 // This is the type given to regular client fields.
 // This means that the type of the exported iso literal is exactly
@@ -119,8 +127,16 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
   ? Whitespace<In>
   : In;
 
+// Client fields and pointers can be preceded by a description.
+type WithoutDescription<In> = Whitespace<In> extends `\"\"\"${string}\"\"\"${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In> extends `\"${string}\"${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In>;
+
 // This is a recursive TypeScript type that matches strings that
-// start with whitespace, followed by TString. So e.g. if we have
+// start with whitespace and an optional description, followed by
+// TString. So e.g. if we have
 // ```
 // export function iso<T>(
 //   isographLiteralText: T & MatchesWhitespaceAndString<'field Query.foo', T>
@@ -138,23 +154,26 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
 type MatchesWhitespaceAndString<
   TString extends string,
   T
-> = Whitespace<T> extends `${TString}${string}` ? T : never;\n",
+> = WithoutDescription<T> extends `${TString}${string}` ? T : never;\n",
     );
 
-    let client_defined_type_overloads =
-        sorted_user_written_types(schema)
-            .into_iter()
-            .map(|client_type| {
-                build_iso_overload_for_client_defined_type(client_type, file_extensions)
-            });
+    let client_defined_type_overloads = sorted_user_written_types(schema, pruned_client_types)
+        .into_iter()
+        .map(|client_type| {
+            build_iso_overload_for_client_defined_type(
+                client_type,
+                file_extensions,
+                artifact_layout,
+            )
+        });
     for (import, client_type_overload) in client_defined_type_overloads {
         imports.push_str(&import);
         content.push_str(&client_type_overload);
     }
 
-    let entrypoint_overloads = sorted_entrypoints(schema)
-        .into_iter()
-        .map(|(field, _)| build_iso_overload_for_entrypoint(field, file_extensions));
+    let entrypoint_overloads = sorted_entrypoints(schema).into_iter().map(|(field, _)| {
+        build_iso_overload_for_entrypoint(field, file_extensions, artifact_layout)
+    });
     for (import, entrypoint_overload) in entrypoint_overloads {
         imports.push_str(&import);
         content.push_str(&entrypoint_overload);
@@ -176,14 +195,15 @@ export function iso(_isographLiteralText: string):
       'set options.no_babel_transform to true in your Isograph config. ');\n}")
         }
         true => {
-            let switch_cases = sorted_entrypoints(schema).into_iter().map(
+            // Entrypoints that are only declared in the config have no iso literal.
+            let switch_cases = sorted_entrypoints(schema).into_iter().filter_map(
                 |(field, entrypoint_declaration_info)| {
-                    format!(
+                    Some(format!(
                         "    case '{}':
       return entrypoint_{};\n",
-                        entrypoint_declaration_info.iso_literal_text,
+                        entrypoint_declaration_info.iso_literal_text?,
                         field.type_and_field.underscore_separated()
-                    )
+                    ))
                 },
             );
 
@@ -215,11 +235,67 @@ export function iso(isographLiteralText: string):
     }
 }
 
-fn sorted_user_written_types<TNetworkProtocol: NetworkProtocol>(
-    schema: &Schema<TNetworkProtocol>,
-) -> Vec<(ClientSelectable<TNetworkProtocol>, ClientFieldDirectiveSet)> {
+/// Export the version of the compiler, and check (at the type level) that the
+/// installed version of @isograph/react is compatible with it, so that a mismatch
+/// is a type error instead of a runtime error.
+fn runtime_version_check() -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let compatible_versions = compatible_runtime_versions(version);
+    format!(
+        "
+// The version of the Isograph compiler that generated this file.
+export const ISOGRAPH_COMPILER_VERSION = '{version}';
+
+// If the following lines fail to type check, the installed version of
+// @isograph/react is not compatible with version {version} of the Isograph
+// compiler, which generated this file. Install a version of @isograph/react
+// that matches `{compatible_versions}`, and recompile.
+type AssertRuntimeVersionIsCompatible<
+  TRuntimeVersion extends `{compatible_versions}`,
+> = TRuntimeVersion;
+export type IsographRuntimeVersionCheck =
+  AssertRuntimeVersionIsCompatible<IsographRuntimeVersion>;
+"
+    )
+}
+
+/// A template literal type matching the versions of the runtime that are compatible
+/// with this version of the compiler, following semver: versions with the same major
+/// version, or with the same minor version if the major version is 0.
+fn compatible_runtime_versions(compiler_version: &str) -> String {
+    let mut parts = compiler_version.split('.');
+    let major = parts.next().unwrap_or("0");
+    match major {
+        "0" => format!("0.{}.${{string}}", parts.next().unwrap_or("0")),
+        _ => format!("{major}.${{string}}"),
+    }
+}
+
+/// If the artifacts are not laid out in the default way, tell the user how to update
+/// imports of artifacts that were written with the nested layout.
+fn artifact_layout_migration_note(artifact_layout: ArtifactLayout) -> String {
+    let (layout_name, example) = match artifact_layout {
+        ArtifactLayout::Nested => return String::new(),
+        ArtifactLayout::Flat => ("flat", "Pet__PetAvatar__resolver_reader"),
+        ArtifactLayout::FolderPerType => ("folder_per_type", "Pet/PetAvatar__resolver_reader"),
+    };
+    format!(
+        "// Artifacts are generated with the {layout_name} artifact_layout. If you are migrating\n\
+        // from the nested layout, update imports of artifacts such as\n\
+        // '__isograph/Pet/PetAvatar/resolver_reader' to '__isograph/{example}'.\n"
+    )
+}
+
+fn sorted_user_written_types<'a, TNetworkProtocol: NetworkProtocol>(
+    schema: &'a Schema<TNetworkProtocol>,
+    pruned_client_types: &BTreeSet<ClientSelectableId>,
+) -> Vec<(
+    ClientSelectable<'a, TNetworkProtocol>,
+    ClientFieldDirectiveSet,
+)> {
     let mut client_types = schema
         .user_written_client_types()
+        .filter(|x| !pruned_client_types.contains(&x.0))
         .map(|x| (x.1, x.2))
         .collect::<Vec<_>>();
     client_types.sort_by(|client_type_1, client_type_2| {
@@ -295,3 +371,14 @@ fn sort_field_name(field_1: SelectableName, field_2: SelectableName) -> Ordering
         field_1.cmp(field_2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::compatible_runtime_versions;
+
+    #[test]
+    fn compatible_runtime_versions_follow_semver() {
+        assert_eq!(compatible_runtime_versions("0.3.1"), "0.3.${string}");
+        assert_eq!(compatible_runtime_versions("1.2.0"), "1.${string}");
+    }
+}
@@ -0,0 +1,379 @@
+use graphql_lang_types::GraphQLConstantValue;
+use isograph_lang_types::{ArgumentKeyAndValue, NonConstantValue};
+use isograph_schema::graphql_string_value;
+
+/// A JavaScript value in a generated artifact, e.g. the serialized arguments of a
+/// field in a reader or normalization AST.
+///
+/// Printing a value takes care of escaping strings and of indentation, so that
+/// the artifacts are valid JavaScript regardless of the values they contain.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JavaScriptValue {
+    Null,
+    Boolean(bool),
+    /// The source text of the number, e.g. 3.14
+    Number(String),
+    String(String),
+    Array(Vec<JavaScriptValue>),
+    Object(Vec<(String, JavaScriptValue)>),
+}
+
+impl JavaScriptValue {
+    /// Print the value, which starts on a line indented at indentation_level.
+    ///
+    /// Objects whose values are not arrays or objects are printed on one line, e.g.
+    /// { kind: "Literal", value: 3 }. Each item of an array is printed on its own
+    /// line, followed by a comma.
+    pub(crate) fn print(&self, indentation_level: u8) -> String {
+        let indent_0 = "  ".repeat(indentation_level as usize);
+        let indent_1 = "  ".repeat((indentation_level + 1) as usize);
+        match self {
+            JavaScriptValue::Null => "null".to_string(),
+            JavaScriptValue::Boolean(boolean) => boolean.to_string(),
+            JavaScriptValue::Number(number) => number.clone(),
+            JavaScriptValue::String(string) => print_javascript_string(string),
+            JavaScriptValue::Array(items) => {
+                let mut s = "[".to_string();
+                for item in items {
+                    s.push_str(&format!(
+                        "\n{indent_1}{},",
+                        item.print(indentation_level + 1)
+                    ));
+                }
+                s.push_str(&format!("\n{indent_0}]"));
+                s
+            }
+            JavaScriptValue::Object(properties) => {
+                if properties.is_empty() {
+                    "{}".to_string()
+                } else if self.is_single_line() {
+                    let properties = properties
+                        .iter()
+                        .map(|(key, value)| format!("{key}: {}", value.print(indentation_level)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("{{ {properties} }}")
+                } else {
+                    let properties = properties
+                        .iter()
+                        .map(|(key, value)| {
+                            format!("{indent_1}{key}: {}", value.print(indentation_level + 1))
+                        })
+                        .collect::<Vec<_>>()
+                        .join(",\n");
+                    format!("{{\n{properties}\n{indent_0}}}")
+                }
+            }
+        }
+    }
+
+    fn is_single_line(&self) -> bool {
+        match self {
+            JavaScriptValue::Null
+            | JavaScriptValue::Boolean(_)
+            | JavaScriptValue::Number(_)
+            | JavaScriptValue::String(_) => true,
+            JavaScriptValue::Array(_) => false,
+            JavaScriptValue::Object(properties) => properties.iter().all(|(_, value)| {
+                !matches!(
+                    value,
+                    JavaScriptValue::Array(_) | JavaScriptValue::Object(_)
+                )
+            }),
+        }
+    }
+}
+
+/// The arguments of a field, as an array of [name, value] pairs, or null if there
+/// are none.
+pub(crate) fn arguments_to_javascript_value(arguments: &[ArgumentKeyAndValue]) -> JavaScriptValue {
+    if arguments.is_empty() {
+        return JavaScriptValue::Null;
+    }
+    JavaScriptValue::Array(
+        arguments
+            .iter()
+            .map(|argument| {
+                JavaScriptValue::Array(vec![
+                    JavaScriptValue::String(argument.key.to_string()),
+                    argument_value_to_javascript_value(&argument.value),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// An argument value, as it is represented in the reader and normalization ASTs,
+/// e.g. { kind: "Variable", name: "id" }.
+pub(crate) fn argument_value_to_javascript_value(value: &NonConstantValue) -> JavaScriptValue {
+    let (kind, value_key, value) = match value {
+        NonConstantValue::Variable(variable_name) => (
+            "Variable",
+            "name",
+            JavaScriptValue::String(variable_name.to_string()),
+        ),
+        NonConstantValue::Integer(integer) => (
+            "Literal",
+            "value",
+            JavaScriptValue::Number(integer.to_string()),
+        ),
+        NonConstantValue::Float(float) => (
+            "Literal",
+            "value",
+            JavaScriptValue::Number(float.as_float().to_string()),
+        ),
+        NonConstantValue::Boolean(boolean) => {
+            ("Literal", "value", JavaScriptValue::Boolean(*boolean))
+        }
+        NonConstantValue::Null => ("Literal", "value", JavaScriptValue::Null),
+        NonConstantValue::String(string) => (
+            "String",
+            "value",
+            JavaScriptValue::String(graphql_string_value(*string)),
+        ),
+        NonConstantValue::Enum(enum_literal) => (
+            "Enum",
+            "value",
+            JavaScriptValue::String(enum_literal.to_string()),
+        ),
+        NonConstantValue::List(items) => (
+            "List",
+            "value",
+            JavaScriptValue::Array(
+                items
+                    .iter()
+                    .map(|item| argument_value_to_javascript_value(&item.item))
+                    .collect(),
+            ),
+        ),
+        NonConstantValue::Object(entries) => (
+            "Object",
+            "value",
+            JavaScriptValue::Array(
+                entries
+                    .iter()
+                    .map(|entry| {
+                        JavaScriptValue::Array(vec![
+                            JavaScriptValue::String(entry.name.item.to_string()),
+                            argument_value_to_javascript_value(&entry.value.item),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+    };
+    JavaScriptValue::Object(vec![
+        (
+            "kind".to_string(),
+            JavaScriptValue::String(kind.to_string()),
+        ),
+        (value_key.to_string(), value),
+    ])
+}
+
+/// A constant value from the schema (e.g. the argument of a directive) as a plain
+/// JavaScript value, e.g. { maxAge: 60 }. Enum values are strings.
+pub(crate) fn constant_value_to_javascript_value(value: &GraphQLConstantValue) -> JavaScriptValue {
+    match value {
+        GraphQLConstantValue::Int(integer) => JavaScriptValue::Number(integer.to_string()),
+        GraphQLConstantValue::Float(float) => JavaScriptValue::Number(float.as_float().to_string()),
+        GraphQLConstantValue::String(string) => {
+            JavaScriptValue::String(graphql_string_value(*string))
+        }
+        GraphQLConstantValue::Boolean(boolean) => JavaScriptValue::Boolean(*boolean),
+        GraphQLConstantValue::Null => JavaScriptValue::Null,
+        GraphQLConstantValue::Enum(enum_literal) => {
+            JavaScriptValue::String(enum_literal.to_string())
+        }
+        GraphQLConstantValue::List(items) => JavaScriptValue::Array(
+            items
+                .iter()
+                .map(|item| constant_value_to_javascript_value(&item.item))
+                .collect(),
+        ),
+        GraphQLConstantValue::Object(entries) => JavaScriptValue::Object(
+            entries
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.name.item.to_string(),
+                        constant_value_to_javascript_value(&entry.value.item),
+                    )
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Print a string as a double-quoted JavaScript string literal.
+fn print_javascript_string(string: &str) -> String {
+    let mut s = String::with_capacity(string.len() + 2);
+    s.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            // Line and paragraph separators, and other control characters
+            '\u{2028}' | '\u{2029}' => s.push_str(&format!("\\u{:04x}", c as u32)),
+            c if c.is_control() => s.push_str(&format!("\\u{:04x}", c as u32)),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use common_lang_types::{Location, WithLocation};
+    use graphql_lang_types::{FloatValue, NameValuePair};
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    fn print_value(value: NonConstantValue) -> String {
+        argument_value_to_javascript_value(&value).print(0)
+    }
+
+    fn generated<T>(item: T) -> WithLocation<T> {
+        WithLocation::new(item, Location::generated())
+    }
+
+    fn string(source: &str) -> NonConstantValue {
+        NonConstantValue::String(source.intern().into())
+    }
+
+    #[test]
+    fn scalars() {
+        assert_eq!(
+            print_value(NonConstantValue::Variable("id".intern().into())),
+            "{ kind: \"Variable\", name: \"id\" }"
+        );
+        assert_eq!(
+            print_value(NonConstantValue::Integer(-3)),
+            "{ kind: \"Literal\", value: -3 }"
+        );
+        assert_eq!(
+            print_value(NonConstantValue::Float(FloatValue::new(2.5))),
+            "{ kind: \"Literal\", value: 2.5 }"
+        );
+        assert_eq!(
+            print_value(NonConstantValue::Boolean(true)),
+            "{ kind: \"Literal\", value: true }"
+        );
+        assert_eq!(
+            print_value(NonConstantValue::Null),
+            "{ kind: \"Literal\", value: null }"
+        );
+        assert_eq!(
+            print_value(NonConstantValue::Enum("DOG".intern().into())),
+            "{ kind: \"Enum\", value: \"DOG\" }"
+        );
+        assert_eq!(
+            print_value(string("hello")),
+            "{ kind: \"String\", value: \"hello\" }"
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(
+            print_value(string(r#"say \"hi\"\n\\ \/ \u00e9 \uD83D\uDE00 😀"#)),
+            r#"{ kind: "String", value: "say \"hi\"\n\\ / é 😀 😀" }"#
+        );
+        assert_eq!(
+            JavaScriptValue::String("a\u{2028}b\u{1}".to_string()).print(0),
+            r#""a\u2028b\u0001""#
+        );
+    }
+
+    #[test]
+    fn lists_and_objects() {
+        let value = NonConstantValue::Object(vec![
+            NameValuePair {
+                name: generated("name".intern().into()),
+                value: generated(string("Fido")),
+            },
+            NameValuePair {
+                name: generated("tags".intern().into()),
+                value: generated(NonConstantValue::List(vec![generated(
+                    NonConstantValue::Integer(1),
+                )])),
+            },
+        ]);
+        assert_eq!(
+            print_value(value),
+            r#"{
+  kind: "Object",
+  value: [
+    [
+      "name",
+      { kind: "String", value: "Fido" },
+    ],
+    [
+      "tags",
+      {
+        kind: "List",
+        value: [
+          { kind: "Literal", value: 1 },
+        ]
+      },
+    ],
+  ]
+}"#
+        );
+        assert_eq!(
+            print_value(NonConstantValue::List(vec![])),
+            "{\n  kind: \"List\",\n  value: [\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn constant_values() {
+        let value = GraphQLConstantValue::Object(vec![
+            NameValuePair {
+                name: generated("maxAge".intern().into()),
+                value: generated(GraphQLConstantValue::Int(60)),
+            },
+            NameValuePair {
+                name: generated("scope".intern().into()),
+                value: generated(GraphQLConstantValue::Enum("PRIVATE".intern().into())),
+            },
+            NameValuePair {
+                name: generated("note".intern().into()),
+                value: generated(GraphQLConstantValue::String(r#"\"hi\""#.intern().into())),
+            },
+        ]);
+        assert_eq!(
+            constant_value_to_javascript_value(&value).print(0),
+            r#"{ maxAge: 60, scope: "PRIVATE", note: "\"hi\"" }"#
+        );
+        assert_eq!(
+            JavaScriptValue::Object(vec![
+                (
+                    "Pet".to_string(),
+                    constant_value_to_javascript_value(&value)
+                ),
+                ("Query".to_string(), JavaScriptValue::Object(vec![])),
+            ])
+            .print(0),
+            "{\n  Pet: { maxAge: 60, scope: \"PRIVATE\", note: \"\\\"hi\\\"\" },\n  Query: {}\n}"
+        );
+    }
+
+    #[test]
+    fn arguments() {
+        assert_eq!(arguments_to_javascript_value(&[]).print(0), "null");
+        assert_eq!(
+            arguments_to_javascript_value(&[ArgumentKeyAndValue {
+                key: "id".intern().into(),
+                value: NonConstantValue::Variable("id".intern().into()),
+            }])
+            .print(1),
+            "[\n    [\n      \"id\",\n      { kind: \"Variable\", name: \"id\" },\n    ],\n  ]"
+        );
+    }
+}
@@ -1,12 +1,28 @@
+mod artifact_provenance;
+mod cache_hints;
+mod compile_manifest;
 mod eager_reader_artifact;
 mod entrypoint_artifact;
+mod entrypoint_metadata;
 mod format_parameter_type;
 pub mod generate_artifacts;
 mod imperatively_loaded_fields;
 mod import_statements;
 mod iso_overload_file;
+mod javascript_value;
 mod normalization_ast_text;
+mod operation_names;
+mod optimistic_response_type;
 mod reader_ast;
+mod reader_ast_types;
+mod refetch_query_index;
 mod refetch_reader_artifact;
+mod schema_metadata;
+mod schema_search_index;
 
-pub use generate_artifacts::get_artifact_path_and_content;
+pub use generate_artifacts::{generate_output_type, get_artifact_path_and_content};
+pub use operation_names::OperationNameError;
+pub use schema_search_index::{
+    fuzzy_score, SchemaSearchEntry, SchemaSearchEntryKind, SchemaSearchEntryLocation,
+    SchemaSearchIndex, SchemaSearchResult, SCHEMA_SEARCH_INDEX_FILE_NAME,
+};
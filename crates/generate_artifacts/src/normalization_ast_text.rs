@@ -1,9 +1,18 @@
+use std::collections::BTreeSet;
+
+use common_lang_types::DirectiveName;
+use isograph_lang_types::{
+    DefinitionLocation, SelectionType, ServerEntityId, ServerObjectEntityId,
+};
 use isograph_schema::{
-    MergedInlineFragmentSelection, MergedLinkedFieldSelection, MergedScalarFieldSelection,
-    MergedServerSelection, NetworkProtocol, Schema,
+    concrete_types, MergedInlineFragmentSelection, MergedLinkedFieldSelection,
+    MergedScalarFieldSelection, MergedSelectionMap, MergedServerSelection, NetworkProtocol, Schema,
 };
 
-use crate::generate_artifacts::{get_serialized_field_arguments, NormalizationAstText};
+use crate::{
+    generate_artifacts::{get_serialized_field_arguments, NormalizationAstText},
+    javascript_value::{constant_value_to_javascript_value, JavaScriptValue},
+};
 
 pub(crate) fn generate_normalization_ast_text<'schema, 'a, TNetworkProtocol: NetworkProtocol>(
     schema: &'schema Schema<TNetworkProtocol>,
@@ -101,3 +110,125 @@ fn generate_normalization_ast_node<TNetworkProtocol: NetworkProtocol>(
         }
     }
 }
+
+/// The propagated directives of the types whose records a query can fetch, e.g.
+/// { Pet: { cacheControl: { maxAge: 60 } } }, or None if none of them have any.
+///
+/// For abstract types, the directives of each concrete type are included, since
+/// records are stored under their concrete type.
+pub(crate) fn generate_type_metadata<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    root_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+    propagated_object_directives: &[DirectiveName],
+) -> Option<JavaScriptValue> {
+    if propagated_object_directives.is_empty() {
+        return None;
+    }
+
+    let mut object_entity_ids = BTreeSet::new();
+    collect_fetched_object_entities(
+        schema,
+        root_object_entity_id,
+        selection_map,
+        &mut object_entity_ids,
+    );
+
+    let mut objects = object_entity_ids
+        .into_iter()
+        .map(|object_entity_id| {
+            schema
+                .server_entity_data
+                .server_object_entity(object_entity_id)
+        })
+        .collect::<Vec<_>>();
+    objects.sort_by_key(|object| object.name.to_string());
+
+    let type_metadata = objects
+        .into_iter()
+        .filter_map(|object| {
+            let mut directives: Vec<(String, JavaScriptValue)> = vec![];
+            for directive in &object.directives {
+                let directive_name = directive.name.item.to_string();
+                if !propagated_object_directives.contains(&directive.name.item)
+                    || directives.iter().any(|(name, _)| *name == directive_name)
+                {
+                    continue;
+                }
+                directives.push((
+                    directive_name,
+                    JavaScriptValue::Object(
+                        directive
+                            .arguments
+                            .iter()
+                            .map(|argument| {
+                                (
+                                    argument.name.item.to_string(),
+                                    constant_value_to_javascript_value(&argument.value.item),
+                                )
+                            })
+                            .collect(),
+                    ),
+                ));
+            }
+            (!directives.is_empty())
+                .then(|| (object.name.to_string(), JavaScriptValue::Object(directives)))
+        })
+        .collect::<Vec<_>>();
+
+    (!type_metadata.is_empty()).then_some(JavaScriptValue::Object(type_metadata))
+}
+
+fn collect_fetched_object_entities<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+    object_entity_ids: &mut BTreeSet<ServerObjectEntityId>,
+) {
+    object_entity_ids.insert(object_entity_id);
+    object_entity_ids.extend(concrete_types(schema, object_entity_id));
+
+    for selection in selection_map.values() {
+        let (target_object_entity_id, selection_map) = match selection {
+            MergedServerSelection::ScalarField(_) => continue,
+            MergedServerSelection::LinkedField(linked_field) => (
+                schema
+                    .server_entity_data
+                    .server_object_entity_extra_info
+                    .get(&object_entity_id)
+                    .and_then(|extra_info| extra_info.selectables.get(&linked_field.name.into()))
+                    .and_then(|selectable_id| match selectable_id {
+                        DefinitionLocation::Server(SelectionType::Object(
+                            server_object_selectable_id,
+                        )) => Some(
+                            *schema
+                                .server_object_selectable(*server_object_selectable_id)
+                                .target_object_entity
+                                .inner(),
+                        ),
+                        _ => None,
+                    }),
+                &linked_field.selection_map,
+            ),
+            MergedServerSelection::InlineFragment(inline_fragment) => (
+                match schema
+                    .server_entity_data
+                    .defined_entities
+                    .get(&inline_fragment.type_to_refine_to.into())
+                {
+                    Some(ServerEntityId::Object(object_entity_id)) => Some(*object_entity_id),
+                    _ => None,
+                },
+                &inline_fragment.selection_map,
+            ),
+        };
+        if let Some(target_object_entity_id) = target_object_entity_id {
+            collect_fetched_object_entities(
+                schema,
+                target_object_entity_id,
+                selection_map,
+                object_entity_ids,
+            );
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use common_lang_types::{
+    error_codes, ErrorCode, HasErrorCode, Location, QueryOperationName, QueryText, WithLocation,
+};
+use thiserror::Error;
+
+/// The queries and mutations whose query texts are generated, by name.
+///
+/// Servers (and persisted query stores) identify operations by name, so two
+/// different operations must not have the same name. The same operation can be
+/// generated more than once, e.g. if two entrypoints on the same type select the
+/// same refetch field, which is fine.
+#[derive(Debug, Default)]
+pub(crate) struct GeneratedOperations {
+    operations: BTreeMap<QueryOperationName, Vec<GeneratedOperation>>,
+}
+
+#[derive(Debug)]
+struct GeneratedOperation {
+    /// e.g. "the entrypoint `Query.HomeRoute`"
+    description: String,
+    query_text: String,
+    location: Location,
+}
+
+impl GeneratedOperations {
+    pub(crate) fn insert(
+        &mut self,
+        operation_name: QueryOperationName,
+        query_text: &QueryText,
+        description: String,
+        location: Location,
+    ) {
+        let operations = self.operations.entry(operation_name).or_default();
+        if operations
+            .iter()
+            .all(|operation| operation.query_text != query_text.0)
+        {
+            operations.push(GeneratedOperation {
+                description,
+                query_text: query_text.0.clone(),
+                location,
+            });
+        }
+    }
+
+    /// Report an error for each name that is shared by operations with different
+    /// query texts.
+    pub(crate) fn validate(self) -> Result<(), Vec<WithLocation<OperationNameError>>> {
+        let errors = self
+            .operations
+            .into_iter()
+            .filter_map(|(operation_name, mut operations)| {
+                if operations.len() < 2 {
+                    return None;
+                }
+                operations.sort_by(|a, b| a.description.cmp(&b.description));
+                Some(WithLocation::new(
+                    OperationNameError::DuplicateOperationName {
+                        operation_name,
+                        descriptions: operations
+                            .iter()
+                            .map(|operation| operation.description.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    },
+                    operations[0].location,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum OperationNameError {
+    #[error(
+        "Different operations are generated with the name `{operation_name}`: \
+        {descriptions}. Include {{entrypoint}} in the refetch_operation_name_template \
+        config option (as the default does) or rename one of the entrypoints."
+    )]
+    DuplicateOperationName {
+        operation_name: QueryOperationName,
+        descriptions: String,
+    },
+}
+
+impl HasErrorCode for OperationNameError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            OperationNameError::DuplicateOperationName { .. } => error_codes::IS0609,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    fn query_text(text: &str) -> QueryText {
+        QueryText(text.to_string())
+    }
+
+    #[test]
+    fn identical_operations_with_the_same_name_are_allowed() {
+        let mut generated_operations = GeneratedOperations::default();
+        let name = "Query____refetch".intern().into();
+        for entrypoint in ["Query.A", "Query.B"] {
+            generated_operations.insert(
+                name,
+                &query_text("query Query____refetch { node { id } }"),
+                format!("the entrypoint `{entrypoint}`"),
+                Location::generated(),
+            );
+        }
+        assert_eq!(generated_operations.validate(), Ok(()));
+    }
+
+    #[test]
+    fn different_operations_with_the_same_name_are_reported() {
+        let mut generated_operations = GeneratedOperations::default();
+        let name = "Query____refetch".intern().into();
+        generated_operations.insert(
+            name,
+            &query_text("query Query____refetch { node { id } }"),
+            "the entrypoint `Query.B`".to_string(),
+            Location::generated(),
+        );
+        generated_operations.insert(
+            name,
+            &query_text("query Query____refetch { node { id name } }"),
+            "the entrypoint `Query.A`".to_string(),
+            Location::generated(),
+        );
+
+        let errors = generated_operations
+            .validate()
+            .expect_err("Expected the names to collide");
+        assert_eq!(
+            errors
+                .into_iter()
+                .map(|error| error.item)
+                .collect::<Vec<_>>(),
+            vec![OperationNameError::DuplicateOperationName {
+                operation_name: name,
+                descriptions: "the entrypoint `Query.A`, the entrypoint `Query.B`".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use common_lang_types::{ArtifactPathAndContent, ObjectTypeAndFieldName};
+use isograph_lang_types::{
+    ClientScalarSelectableId, DefinitionLocation, ScalarSelectionDirectiveSet, SelectionType,
+    ServerObjectEntityId,
+};
+use isograph_schema::{
+    current_target_merged_selections, ClientFieldVariant, FieldToCompletedMergeTraversalStateMap,
+    FieldTraversalResult, MergedSelectionMap, MergedServerSelection, NetworkProtocol, Schema,
+};
+
+use crate::generate_artifacts::{
+    print_javascript_type_declaration, OPTIMISTIC_RESPONSE_TYPE_FILE_NAME,
+};
+
+/// For each exposed mutation field, the union of the selections made on its parent
+/// wherever it is selected. These are the fields that the mutation refetches, and
+/// thus the portion of the store that the mutation can affect.
+pub(crate) fn get_mutation_field_selection_maps<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    encountered_client_type_map: &FieldToCompletedMergeTraversalStateMap,
+) -> BTreeMap<ClientScalarSelectableId, MergedSelectionMap> {
+    let mut mutation_field_selection_maps = BTreeMap::new();
+
+    for FieldTraversalResult {
+        traversal_state,
+        merged_selection_map,
+        ..
+    } in encountered_client_type_map.values()
+    {
+        for ((path, selection_variant), root_refetched_path) in &traversal_state.refetch_paths {
+            if matches!(selection_variant, ScalarSelectionDirectiveSet::Loadable(_)) {
+                continue;
+            }
+            let client_field_id = root_refetched_path
+                .path_to_refetch_field_info
+                .client_field_id;
+            if !is_mutation_field(schema, client_field_id) {
+                continue;
+            }
+
+            merge_selection_maps(
+                mutation_field_selection_maps
+                    .entry(client_field_id)
+                    .or_default(),
+                current_target_merged_selections(&path.linked_fields, merged_selection_map),
+            );
+        }
+    }
+
+    mutation_field_selection_maps
+}
+
+fn is_mutation_field<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field_id: ClientScalarSelectableId,
+) -> bool {
+    match &schema.client_field(client_field_id).variant {
+        ClientFieldVariant::ImperativelyLoadedField(variant) => schema
+            .fetchable_types
+            .get(&variant.root_object_entity_id)
+            .is_some_and(|root_operation_name| root_operation_name.0 == "mutation"),
+        ClientFieldVariant::UserWritten(_)
+        | ClientFieldVariant::Link
+        | ClientFieldVariant::Paginated(_) => false,
+    }
+}
+
+fn merge_selection_maps(target: &mut MergedSelectionMap, source: &MergedSelectionMap) {
+    for (normalization_key, selection) in source {
+        match target.entry(normalization_key.clone()) {
+            Entry::Vacant(vacant) => {
+                vacant.insert(selection.clone());
+            }
+            Entry::Occupied(mut occupied) => match (occupied.get_mut(), selection) {
+                (
+                    MergedServerSelection::LinkedField(existing),
+                    MergedServerSelection::LinkedField(new),
+                ) => merge_selection_maps(&mut existing.selection_map, &new.selection_map),
+                (
+                    MergedServerSelection::InlineFragment(existing),
+                    MergedServerSelection::InlineFragment(new),
+                ) => merge_selection_maps(&mut existing.selection_map, &new.selection_map),
+                _ => {}
+            },
+        }
+    }
+}
+
+pub(crate) fn generate_optimistic_response_type_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field_id: ClientScalarSelectableId,
+    selection_map: &MergedSelectionMap,
+) -> ArtifactPathAndContent {
+    let client_field = schema.client_field(client_field_id);
+    let parent_type = schema
+        .server_entity_data
+        .server_object_entity(client_field.parent_object_entity_id);
+
+    let optimistic_response_type = generate_optimistic_response_type(
+        schema,
+        client_field.parent_object_entity_id,
+        selection_map,
+        0,
+    );
+
+    ArtifactPathAndContent {
+        file_name: *OPTIMISTIC_RESPONSE_TYPE_FILE_NAME,
+        file_content: format!(
+            "export type {}__{}__optimistic_response_type = {};\n",
+            parent_type.name, client_field.name, optimistic_response_type
+        ),
+        type_and_field: Some(ObjectTypeAndFieldName {
+            type_name: parent_type.name,
+            field_name: client_field.name.into(),
+        }),
+    }
+}
+
+/// Every field is optional, since an optimistic update need not provide a value for
+/// each field that the mutation refetches.
+fn generate_optimistic_response_type<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+    indentation_level: u8,
+) -> String {
+    let mut fields = BTreeMap::new();
+    collect_optimistic_response_fields(
+        schema,
+        parent_object_entity_id,
+        selection_map,
+        indentation_level,
+        &mut fields,
+    );
+
+    let mut optimistic_response_type = "{\n".to_string();
+    for (name, field_type) in fields {
+        optimistic_response_type.push_str(&format!(
+            "{}readonly {name}?: {field_type},\n",
+            "  ".repeat((indentation_level + 1) as usize),
+        ));
+    }
+    optimistic_response_type.push_str(&format!("{}}}", "  ".repeat(indentation_level as usize)));
+    optimistic_response_type
+}
+
+fn collect_optimistic_response_fields<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+    indentation_level: u8,
+    fields: &mut BTreeMap<String, String>,
+) {
+    let selectables = &schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&parent_object_entity_id)
+        .expect(
+            "Expected parent_object_entity_id to exist \
+            in server_object_entity_extra_info",
+        )
+        .selectables;
+
+    for selection in selection_map.values() {
+        match selection {
+            MergedServerSelection::ScalarField(scalar_field) => {
+                let Some(DefinitionLocation::Server(SelectionType::Scalar(
+                    server_scalar_selectable_id,
+                ))) = selectables.get(&scalar_field.name.into())
+                else {
+                    continue;
+                };
+                let output_type = schema
+                    .server_scalar_selectable(*server_scalar_selectable_id)
+                    .target_scalar_entity
+                    .clone()
+                    .map(&mut |scalar_entity_id| {
+                        schema
+                            .server_entity_data
+                            .server_scalar_entity(scalar_entity_id)
+                            .javascript_name
+                    });
+                fields
+                    .entry(scalar_field.name.to_string())
+                    .or_insert_with(|| print_javascript_type_declaration(&output_type));
+            }
+            MergedServerSelection::LinkedField(linked_field) => {
+                let Some(DefinitionLocation::Server(SelectionType::Object(
+                    server_object_selectable_id,
+                ))) = selectables.get(&linked_field.name.into())
+                else {
+                    continue;
+                };
+                let target_object_entity = &schema
+                    .server_object_selectable(*server_object_selectable_id)
+                    .target_object_entity;
+                let output_type = target_object_entity.clone().map(&mut |_| {
+                    generate_optimistic_response_type(
+                        schema,
+                        *target_object_entity.inner(),
+                        &linked_field.selection_map,
+                        indentation_level + 1,
+                    )
+                });
+                fields
+                    .entry(linked_field.name.to_string())
+                    .or_insert_with(|| print_javascript_type_declaration(&output_type));
+            }
+            MergedServerSelection::InlineFragment(inline_fragment) => {
+                // The fields of a type refinement are only present if the object has
+                // that type, and since every field is optional, they can be included
+                // in the parent's type.
+                if let Some(SelectionType::Object(refined_object_entity_id)) = schema
+                    .server_entity_data
+                    .defined_entities
+                    .get(&inline_fragment.type_to_refine_to.into())
+                {
+                    collect_optimistic_response_fields(
+                        schema,
+                        *refined_object_entity_id,
+                        &inline_fragment.selection_map,
+                        indentation_level,
+                        fields,
+                    );
+                }
+            }
+        }
+    }
+}
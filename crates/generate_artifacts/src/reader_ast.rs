@@ -1,26 +1,157 @@
 use std::collections::{BTreeSet, HashSet};
 
 use common_lang_types::{ClientScalarSelectableName, ObjectTypeAndFieldName, WithSpan};
+use isograph_config::ArtifactLayout;
 use isograph_lang_types::{
     DefinitionLocation, EmptyDirectiveSet, LoadableDirectiveParameters,
     ObjectSelectionDirectiveSet, RefetchQueryIndex, ScalarSelectionDirectiveSet,
-    SelectionTypeContainingSelections,
+    SelectionConditionKind, SelectionTypeContainingSelections, ServerObjectEntityId,
 };
 use isograph_schema::{
-    categorize_field_loadability, transform_arguments_with_child_context, ClientFieldVariant,
-    ClientScalarOrObjectSelectable, ClientScalarSelectable, Loadability, NameAndArguments,
-    NetworkProtocol, NormalizationKey, PathToRefetchField, RefetchedPathsMap, Schema,
-    SchemaServerObjectSelectableVariant, ValidatedObjectSelection, ValidatedScalarSelection,
-    ValidatedSelection, VariableContext,
+    categorize_field_loadability, client_field_overrides, implicit_concrete_type_refinements,
+    transform_arguments_with_child_context, transform_conditions_with_child_context,
+    ClientFieldVariant, ClientOrServerObjectSelectable, ClientScalarOrObjectSelectable,
+    ClientScalarSelectable, Loadability, NameAndArguments, NetworkProtocol, NormalizationKey,
+    PathToRefetchField, RefetchedPathsMap, Schema, SchemaServerObjectSelectableVariant,
+    ValidatedObjectSelection, ValidatedScalarSelection, ValidatedSelection, VariableContext,
 };
 
 use crate::{
-    generate_artifacts::{get_serialized_field_arguments, ReaderAst},
+    generate_artifacts::{
+        get_serialized_argument_value, get_serialized_field_arguments, ReaderAst,
+    },
     import_statements::{ImportedFileCategory, ReaderImports},
 };
 
-// Can we do this when visiting the client field in when generating entrypoints?
+#[allow(clippy::too_many_arguments)]
 fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
+    selection: &WithSpan<ValidatedSelection>,
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    indentation_level: u8,
+    reader_imports: &mut ReaderImports,
+    root_refetched_paths: &RefetchedPathsMap,
+    path: &mut Vec<NormalizationKey>,
+    initial_variable_context: &VariableContext,
+    artifact_layout: ArtifactLayout,
+) -> String {
+    let (conditions, name_or_alias) = match &selection.item {
+        SelectionTypeContainingSelections::Scalar(scalar_field_selection) => (
+            &scalar_field_selection.conditions,
+            scalar_field_selection.name_or_alias().item,
+        ),
+        SelectionTypeContainingSelections::Object(linked_field_selection) => (
+            &linked_field_selection.conditions,
+            linked_field_selection.name_or_alias().item,
+        ),
+    };
+
+    let concrete_types =
+        implicit_concrete_type_refinements(schema, parent_object_entity_id, &selection.item).map(
+            |concrete_object_entity_ids| {
+                concrete_object_entity_ids
+                    .into_iter()
+                    .map(|concrete_object_entity_id| {
+                        schema
+                            .server_entity_data
+                            .server_object_entity(concrete_object_entity_id)
+                            .name
+                    })
+                    .collect::<Vec<_>>()
+            },
+        );
+
+    if conditions.is_empty() && concrete_types.is_none() {
+        return generate_unconditional_reader_ast_node(
+            selection,
+            schema,
+            indentation_level,
+            reader_imports,
+            root_refetched_paths,
+            path,
+            initial_variable_context,
+            artifact_layout,
+        );
+    }
+
+    // Fields selected with @include or @skip, and fields that are only defined on some
+    // concrete types of the (abstract) parent, are wrapped in a Conditional node, so that
+    // the runtime does not treat the data that was not fetched as missing.
+    //
+    // The latter are fetched in an inline fragment on each of those concrete types,
+    // so (like the merged selection set) we read them from the first.
+    let inline_fragment_key = concrete_types
+        .as_ref()
+        .and_then(|concrete_types| concrete_types.first())
+        .map(|concrete_type| NormalizationKey::InlineFragment(*concrete_type));
+    let pushed = inline_fragment_key.is_some();
+    path.extend(inline_fragment_key);
+    let inner_reader_ast_node = generate_unconditional_reader_ast_node(
+        selection,
+        schema,
+        indentation_level + 1,
+        reader_imports,
+        root_refetched_paths,
+        path,
+        initial_variable_context,
+        artifact_layout,
+    );
+    if pushed {
+        path.pop();
+    }
+    let inner_reader_ast_node = inner_reader_ast_node
+        .trim_start()
+        .strip_suffix(",\n")
+        .expect(
+        "Expected reader AST node to end with a comma. This is indicative of a bug in Isograph.",
+    );
+
+    let conditions = transform_conditions_with_child_context(
+        conditions.iter().map(|condition| condition.item.clone()),
+        initial_variable_context,
+    );
+    let indent_1 = "  ".repeat(indentation_level as usize);
+    let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+    let indent_3 = "  ".repeat((indentation_level + 2) as usize);
+    let serialized_conditions = conditions
+        .iter()
+        .map(|condition| {
+            format!(
+                "{indent_3}{{ kind: \"{}\", value: {} }},\n",
+                match condition.kind {
+                    SelectionConditionKind::Include => "Include",
+                    SelectionConditionKind::Skip => "Skip",
+                },
+                get_serialized_argument_value(&condition.value, indentation_level + 2)
+            )
+        })
+        .chain(concrete_types.map(|concrete_types| {
+            format!(
+                "{indent_3}{{ kind: \"ConcreteType\", concreteTypes: [{}] }},\n",
+                concrete_types
+                    .iter()
+                    .map(|concrete_type| format!("\"{concrete_type}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }))
+        .collect::<String>();
+
+    format!(
+        "{indent_1}{{\n\
+        {indent_2}kind: \"Conditional\",\n\
+        {indent_2}alias: \"{name_or_alias}\",\n\
+        {indent_2}conditions: [\n\
+        {serialized_conditions}\
+        {indent_2}],\n\
+        {indent_2}selection: {inner_reader_ast_node},\n\
+        {indent_1}}},\n"
+    )
+}
+
+// Can we do this when visiting the client field in when generating entrypoints?
+#[allow(clippy::too_many_arguments)]
+fn generate_unconditional_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
     selection: &WithSpan<ValidatedSelection>,
     schema: &Schema<TNetworkProtocol>,
     indentation_level: u8,
@@ -29,6 +160,7 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
     root_refetched_paths: &RefetchedPathsMap,
     path: &mut Vec<NormalizationKey>,
     initial_variable_context: &VariableContext,
+    artifact_layout: ArtifactLayout,
 ) -> String {
     match &selection.item {
         SelectionTypeContainingSelections::Scalar(scalar_field_selection) => {
@@ -49,23 +181,29 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
                         root_refetched_paths,
                         reader_imports,
                         initial_variable_context,
+                        artifact_layout,
                     )
                 }
             }
         }
         SelectionTypeContainingSelections::Object(linked_field_selection) => {
             match linked_field_selection.associated_data {
-                DefinitionLocation::Client(client_pointer_id) => {
-                    let client_pointer = schema.client_pointer(client_pointer_id);
-
+                DefinitionLocation::Client(_) => {
+                    // The selections made on the client pointer are read from the
+                    // record that it points to.
                     let inner_reader_ast = generate_reader_ast_with_path(
                         schema,
-                        client_pointer.refetch_strategy.refetch_selection_set(),
+                        &linked_field_selection.selection_set,
+                        *schema
+                            .object_selectable(linked_field_selection.associated_data)
+                            .target_object_entity_id()
+                            .inner(),
                         indentation_level + 1,
                         reader_imports,
                         root_refetched_paths,
                         path,
                         initial_variable_context,
+                        artifact_layout,
                     );
 
                     linked_field_ast_node(
@@ -80,45 +218,58 @@ fn generate_reader_ast_node<TNetworkProtocol: NetworkProtocol>(
                 DefinitionLocation::Server(server_object_selectable_id) => {
                     let server_object_selectable =
                         schema.server_object_selectable(server_object_selectable_id);
+                    // Selections within an upcast are read from the parent record, so
+                    // they are at the same path as the parent's selections.
                     let normalization_key = match server_object_selectable.object_selectable_variant
                     {
-                        SchemaServerObjectSelectableVariant::LinkedField => NameAndArguments {
-                            // TODO use alias
-                            name: linked_field_selection.name.item.into(),
-                            // TODO this clearly does something, but why are we able to pass
-                            // the initial variable context here??
-                            arguments: transform_arguments_with_child_context(
-                                linked_field_selection
-                                    .arguments
-                                    .iter()
-                                    .map(|x| x.item.into_key_and_value()),
-                                // TODO why is this not the transformed context?
-                                initial_variable_context,
-                            ),
-                        }
-                        .normalization_key(),
+                        SchemaServerObjectSelectableVariant::LinkedField => Some(
+                            NameAndArguments {
+                                // TODO use alias
+                                name: linked_field_selection.name.item.into(),
+                                // TODO this clearly does something, but why are we able to pass
+                                // the initial variable context here??
+                                arguments: transform_arguments_with_child_context(
+                                    linked_field_selection
+                                        .arguments
+                                        .iter()
+                                        .map(|x| x.item.into_key_and_value()),
+                                    // TODO why is this not the transformed context?
+                                    initial_variable_context,
+                                ),
+                            }
+                            .normalization_key(),
+                        ),
                         SchemaServerObjectSelectableVariant::InlineFragment => {
                             let target_object_entity =
                                 schema.server_entity_data.server_object_entity(
                                     *server_object_selectable.target_object_entity.inner(),
                                 );
-                            NormalizationKey::InlineFragment(target_object_entity.name)
+                            Some(NormalizationKey::InlineFragment(target_object_entity.name))
                         }
+                        SchemaServerObjectSelectableVariant::Upcast => None,
                     };
 
-                    path.push(normalization_key);
+                    let pushed = normalization_key.is_some();
+                    path.extend(normalization_key);
 
                     let inner_reader_ast = generate_reader_ast_with_path(
                         schema,
                         &linked_field_selection.selection_set,
+                        *schema
+                            .object_selectable(linked_field_selection.associated_data)
+                            .target_object_entity_id()
+                            .inner(),
                         indentation_level + 1,
                         reader_imports,
                         root_refetched_paths,
                         path,
                         initial_variable_context,
+                        artifact_layout,
                     );
 
-                    path.pop();
+                    if pushed {
+                        path.pop();
+                    }
 
                     linked_field_ast_node(
                         schema,
@@ -180,7 +331,8 @@ fn linked_field_ast_node<TNetworkProtocol: NetworkProtocol>(
         DefinitionLocation::Server(server_field_id) => {
             let server_field = schema.server_object_selectable(server_field_id);
             match &server_field.object_selectable_variant {
-                SchemaServerObjectSelectableVariant::InlineFragment => {
+                SchemaServerObjectSelectableVariant::InlineFragment
+                | SchemaServerObjectSelectableVariant::Upcast => {
                     let object = schema
                         .server_entity_data
                         .server_object_entity(server_field.parent_object_entity_id);
@@ -230,6 +382,7 @@ fn scalar_client_defined_field_ast_node<TNetworkProtocol: NetworkProtocol>(
     root_refetched_paths: &RefetchedPathsMap,
     reader_imports: &mut ReaderImports,
     parent_variable_context: &VariableContext,
+    artifact_layout: ArtifactLayout,
 ) -> String {
     let client_field_variable_context = parent_variable_context.child_variable_context(
         &scalar_field_selection.arguments,
@@ -250,6 +403,7 @@ fn scalar_client_defined_field_ast_node<TNetworkProtocol: NetworkProtocol>(
                 scalar_field_selection,
                 &client_field_variable_context,
                 loadable_directive_parameters,
+                artifact_layout,
             )
         }
         Some(Loadability::ImperativelyLoadedField(_)) => imperatively_loaded_variant_ast_node(
@@ -264,19 +418,19 @@ fn scalar_client_defined_field_ast_node<TNetworkProtocol: NetworkProtocol>(
             ClientFieldVariant::Link => {
                 link_variant_ast_node(scalar_field_selection, indentation_level)
             }
-            ClientFieldVariant::UserWritten(_) | ClientFieldVariant::ImperativelyLoadedField(_) => {
-                user_written_variant_ast_node(
-                    scalar_field_selection,
-                    indentation_level,
-                    client_field,
-                    schema,
-                    path,
-                    root_refetched_paths,
-                    reader_imports,
-                    &client_field_variable_context,
-                    parent_variable_context,
-                )
-            }
+            ClientFieldVariant::UserWritten(_)
+            | ClientFieldVariant::ImperativelyLoadedField(_)
+            | ClientFieldVariant::Paginated(_) => user_written_variant_ast_node(
+                scalar_field_selection,
+                indentation_level,
+                client_field,
+                schema,
+                path,
+                root_refetched_paths,
+                reader_imports,
+                &client_field_variable_context,
+                parent_variable_context,
+            ),
         },
     }
 }
@@ -351,6 +505,17 @@ fn user_written_variant_ast_node<TNetworkProtocol: NetworkProtocol>(
         ImportedFileCategory::ResolverReader,
     ));
 
+    let concrete_overrides = concrete_overrides_ast(
+        scalar_field_selection,
+        indentation_level + 1,
+        nested_client_field,
+        schema,
+        path,
+        root_refetched_paths,
+        reader_imports,
+        initial_variable_context,
+    );
+
     format!(
         "{indent_1}{{\n\
         {indent_2}kind: \"Resolver\",\n\
@@ -358,10 +523,77 @@ fn user_written_variant_ast_node<TNetworkProtocol: NetworkProtocol>(
         {indent_2}arguments: {arguments},\n\
         {indent_2}readerArtifact: {reader_artifact_import_name},\n\
         {indent_2}usedRefetchQueries: {nested_refetch_queries},\n\
+        {concrete_overrides}\
         {indent_1}}},\n",
     )
 }
 
+/// If the client field is defined on an abstract type and overridden by some of its
+/// concrete types, the reader artifacts of the overrides, by concrete type. The
+/// runtime reads the override of the record's __typename instead, if there is one.
+/// The selections of an override are made in an inline fragment on its type, so its
+/// refetch paths are, too.
+#[allow(clippy::too_many_arguments)]
+fn concrete_overrides_ast<TNetworkProtocol: NetworkProtocol>(
+    scalar_field_selection: &ValidatedScalarSelection,
+    indentation_level: u8,
+    nested_client_field: &ClientScalarSelectable<TNetworkProtocol>,
+    schema: &Schema<TNetworkProtocol>,
+    path: &mut Vec<NormalizationKey>,
+    root_refetched_paths: &RefetchedPathsMap,
+    reader_imports: &mut ReaderImports,
+    initial_variable_context: &VariableContext,
+) -> String {
+    let overrides = client_field_overrides(schema, nested_client_field);
+    if overrides.is_empty() {
+        return String::new();
+    }
+
+    let indent_1 = "  ".repeat(indentation_level as usize);
+    let indent_2 = "  ".repeat((indentation_level + 1) as usize);
+    let indent_3 = "  ".repeat((indentation_level + 2) as usize);
+    let mut s = format!("{indent_1}concreteOverrides: {{\n");
+    for (concrete_object_entity_id, override_id) in overrides {
+        let concrete_type = schema
+            .server_entity_data
+            .server_object_entity(concrete_object_entity_id)
+            .name;
+        let override_field = schema.client_field(override_id);
+        let override_variable_context = initial_variable_context.child_variable_context(
+            &scalar_field_selection.arguments,
+            &override_field.variable_definitions,
+            &scalar_field_selection.scalar_selection_directive_set,
+        );
+
+        path.push(NormalizationKey::InlineFragment(concrete_type));
+        let paths_to_refetch_field_in_override = refetched_paths_for_client_field(
+            override_field,
+            schema,
+            path,
+            &override_variable_context,
+        );
+        path.pop();
+        let nested_refetch_queries = get_nested_refetch_query_text(
+            root_refetched_paths,
+            &paths_to_refetch_field_in_override,
+        );
+
+        reader_imports.insert((
+            override_field.type_and_field,
+            ImportedFileCategory::ResolverReader,
+        ));
+        s.push_str(&format!(
+            "{indent_2}\"{concrete_type}\": {{\n\
+            {indent_3}readerArtifact: {}__resolver_reader,\n\
+            {indent_3}usedRefetchQueries: {nested_refetch_queries},\n\
+            {indent_2}}},\n",
+            override_field.type_and_field.underscore_separated()
+        ));
+    }
+    s.push_str(&format!("{indent_1}}},\n"));
+    s
+}
+
 #[allow(clippy::too_many_arguments)]
 fn imperatively_loaded_variant_ast_node<TNetworkProtocol: NetworkProtocol>(
     nested_client_field: &ClientScalarSelectable<TNetworkProtocol>,
@@ -408,6 +640,7 @@ fn imperatively_loaded_variant_ast_node<TNetworkProtocol: NetworkProtocol>(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn loadably_selected_field_ast_node<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     client_field: &ClientScalarSelectable<TNetworkProtocol>,
@@ -416,6 +649,7 @@ fn loadably_selected_field_ast_node<TNetworkProtocol: NetworkProtocol>(
     scalar_field_selection: &ValidatedScalarSelection,
     client_field_variable_context: &VariableContext,
     loadable_directive_parameters: &LoadableDirectiveParameters,
+    artifact_layout: ArtifactLayout,
 ) -> String {
     let name = scalar_field_selection.name.item;
     let alias = scalar_field_selection.name_or_alias().item;
@@ -431,12 +665,18 @@ fn loadably_selected_field_ast_node<TNetworkProtocol: NetworkProtocol>(
         format!("{type_and_field}__entrypoint")
     } else {
         let indent_3 = "  ".repeat((indentation_level + 2) as usize);
-        let field_parent_type = client_field.type_and_field.type_name;
+        let entrypoint_path = artifact_layout.field_import_path(
+            ObjectTypeAndFieldName {
+                type_name: client_field.type_and_field.type_name,
+                field_name: name.into(),
+            },
+            ImportedFileCategory::Entrypoint.filename(),
+        );
         format!(
             "{{ \n\
             {indent_3}kind: \"EntrypointLoader\",\n\
             {indent_3}typeAndField: \"{type_and_field}\",\n\
-            {indent_3}loader: () => import(\"../../{field_parent_type}/{name}/entrypoint\").then(module => module.default),\n\
+            {indent_3}loader: () => import(\"{entrypoint_path}\").then(module => module.default),\n\
             {indent_2}}}"
         )
     };
@@ -462,10 +702,12 @@ fn loadably_selected_field_ast_node<TNetworkProtocol: NetworkProtocol>(
                     This is indicative of a bug in Isograph.",
             )
             .refetch_selection_set(),
+        client_field.parent_object_entity_id,
         indentation_level + 1,
         // This is weird!
         &Default::default(),
         client_field_variable_context,
+        artifact_layout,
     );
 
     // N.B. additional_reader_imports will be empty for now, but at some point, we may have
@@ -524,26 +766,31 @@ fn server_defined_scalar_field_ast_node(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn generate_reader_ast_with_path<'schema, TNetworkProtocol: NetworkProtocol>(
     schema: &'schema Schema<TNetworkProtocol>,
     selection_set: &'schema [WithSpan<ValidatedSelection>],
+    parent_object_entity_id: ServerObjectEntityId,
     indentation_level: u8,
     nested_client_field_imports: &mut ReaderImports,
     // N.B. this is not root_refetched_paths when we're generating a non-fetchable client field :(
     root_refetched_paths: &RefetchedPathsMap,
     path: &mut Vec<NormalizationKey>,
     initial_variable_context: &VariableContext,
+    artifact_layout: ArtifactLayout,
 ) -> ReaderAst {
     let mut reader_ast = "[\n".to_string();
     for item in selection_set {
         let s = generate_reader_ast_node(
             item,
             schema,
+            parent_object_entity_id,
             indentation_level + 1,
             nested_client_field_imports,
             root_refetched_paths,
             path,
             initial_variable_context,
+            artifact_layout,
         );
         reader_ast.push_str(&s);
     }
@@ -609,16 +856,19 @@ fn find_imperatively_fetchable_query_index(
 pub(crate) fn generate_reader_ast<'schema, TNetworkProtocol: NetworkProtocol>(
     schema: &'schema Schema<TNetworkProtocol>,
     selection_set: &'schema [WithSpan<ValidatedSelection>],
+    parent_object_entity_id: ServerObjectEntityId,
     indentation_level: u8,
     // N.B. this is not root_refetched_paths when we're generating an entrypoint :(
     // ????
     root_refetched_paths: &RefetchedPathsMap,
     initial_variable_context: &VariableContext,
+    artifact_layout: ArtifactLayout,
 ) -> (ReaderAst, ReaderImports) {
     let mut client_field_imports = BTreeSet::new();
     let reader_ast = generate_reader_ast_with_path(
         schema,
         selection_set,
+        parent_object_entity_id,
         indentation_level,
         &mut client_field_imports,
         root_refetched_paths,
@@ -626,6 +876,7 @@ pub(crate) fn generate_reader_ast<'schema, TNetworkProtocol: NetworkProtocol>(
         // (and in theory some entrypoints).
         &mut vec![],
         initial_variable_context,
+        artifact_layout,
     );
     (reader_ast, client_field_imports)
 }
@@ -694,7 +945,34 @@ fn refetched_paths_with_path<TNetworkProtocol: NetworkProtocol>(
                                     ),
                                 );
 
-                                paths.extend(new_paths.into_iter());
+                                paths.extend(new_paths);
+
+                                for (concrete_object_entity_id, override_id) in
+                                    client_field_overrides(schema, client_field)
+                                {
+                                    let override_field = schema.client_field(override_id);
+                                    path.push(NormalizationKey::InlineFragment(
+                                        schema
+                                            .server_entity_data
+                                            .server_object_entity(concrete_object_entity_id)
+                                            .name,
+                                    ));
+                                    let new_paths = refetched_paths_with_path(
+                                        override_field.selection_set_for_parent_query(),
+                                        schema,
+                                        path,
+                                        &initial_variable_context.child_variable_context(
+                                            &scalar_field_selection.arguments,
+                                            &override_field.variable_definitions,
+                                            &ScalarSelectionDirectiveSet::None(
+                                                EmptyDirectiveSet {},
+                                            ),
+                                        ),
+                                    );
+                                    path.pop();
+
+                                    paths.extend(new_paths);
+                                }
                             }
                         }
                     }
@@ -711,30 +989,34 @@ fn refetched_paths_with_path<TNetworkProtocol: NetworkProtocol>(
                         let normalization_key = match server_object_selectable
                             .object_selectable_variant
                         {
-                            SchemaServerObjectSelectableVariant::LinkedField => NameAndArguments {
-                                // TODO use alias
-                                name: linked_field_selection.name.item.into(),
-                                arguments: transform_arguments_with_child_context(
-                                    linked_field_selection
-                                        .arguments
-                                        .iter()
-                                        .map(|x| x.item.into_key_and_value()),
-                                    // TODO this clearly does something, but why are we able to pass
-                                    // the initial variable context here??
-                                    initial_variable_context,
-                                ),
-                            }
-                            .normalization_key(),
+                            SchemaServerObjectSelectableVariant::LinkedField => Some(
+                                NameAndArguments {
+                                    // TODO use alias
+                                    name: linked_field_selection.name.item.into(),
+                                    arguments: transform_arguments_with_child_context(
+                                        linked_field_selection
+                                            .arguments
+                                            .iter()
+                                            .map(|x| x.item.into_key_and_value()),
+                                        // TODO this clearly does something, but why are we able to pass
+                                        // the initial variable context here??
+                                        initial_variable_context,
+                                    ),
+                                }
+                                .normalization_key(),
+                            ),
                             SchemaServerObjectSelectableVariant::InlineFragment => {
                                 let target_object_entity =
                                     schema.server_entity_data.server_object_entity(
                                         *server_object_selectable.target_object_entity.inner(),
                                     );
-                                NormalizationKey::InlineFragment(target_object_entity.name)
+                                Some(NormalizationKey::InlineFragment(target_object_entity.name))
                             }
+                            SchemaServerObjectSelectableVariant::Upcast => None,
                         };
 
-                        path.push(normalization_key);
+                        let pushed = normalization_key.is_some();
+                        path.extend(normalization_key);
 
                         let new_paths = refetched_paths_with_path(
                             &linked_field_selection.selection_set,
@@ -743,9 +1025,11 @@ fn refetched_paths_with_path<TNetworkProtocol: NetworkProtocol>(
                             initial_variable_context,
                         );
 
-                        paths.extend(new_paths.into_iter());
+                        paths.extend(new_paths);
 
-                        path.pop();
+                        if pushed {
+                            path.pop();
+                        }
                     }
                 };
             }
@@ -0,0 +1,186 @@
+use common_lang_types::{ArtifactFileName, ArtifactPathAndContent};
+use intern::string_key::Intern;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    pub static ref READER_AST_TYPES_FILE_NAME: ArtifactFileName = "reader-ast.ts".intern().into();
+}
+
+/// Generate a file describing the shapes of the reader and normalization AST
+/// nodes that this version of the compiler generates.
+///
+/// The file also contains type-level checks that fail to type check if the
+/// installed version of @isograph/react is not the version of the compiler, or
+/// if the runtime's types do not accept the nodes that the compiler generates.
+/// It is a .ts file (and not a .d.ts file), so that the checks are not skipped
+/// by projects that set skipLibCheck.
+///
+/// These types must be kept in sync with reader_ast.rs, normalization_ast_text.rs
+/// and javascript_value.rs.
+pub(crate) fn generate_reader_ast_types_artifact() -> ArtifactPathAndContent {
+    let version = env!("CARGO_PKG_VERSION");
+    ArtifactPathAndContent {
+        type_and_field: None,
+        file_name: *READER_AST_TYPES_FILE_NAME,
+        file_content: format!(
+            "import type {{
+  IsographRuntimeVersion,
+  NormalizationAstNode,
+  ReaderAstNode,
+  ReaderImperativelyLoadedField,
+  ReaderLinkedField,
+  ReaderLoadableField,
+  ReaderNonLoadableResolverField,
+}} from '@isograph/react';
+
+// The shapes of the reader and normalization AST nodes that version {version}
+// of the Isograph compiler generates.
+
+export type IsographCompilerVersion = '{version}';
+
+export type IsographArgumentValue =
+  | {{ readonly kind: 'Variable'; readonly name: string }}
+  | {{ readonly kind: 'Literal'; readonly value: number | boolean | null }}
+  | {{ readonly kind: 'String'; readonly value: string }}
+  | {{ readonly kind: 'Enum'; readonly value: string }}
+  | {{ readonly kind: 'Object'; readonly value: IsographArguments }}
+  | {{ readonly kind: 'List'; readonly value: readonly IsographArgumentValue[] }};
+
+export type IsographArguments = [string, IsographArgumentValue][];
+
+export type IsographReaderAstNode =
+  | IsographReaderScalarField
+  | IsographReaderLinkedField
+  | IsographReaderResolverField
+  | IsographReaderImperativelyLoadedField
+  | IsographReaderLoadableField
+  | IsographReaderLinkField
+  | IsographReaderConditionalField;
+
+export type IsographReaderScalarField = {{
+  readonly kind: 'Scalar';
+  readonly fieldName: string;
+  readonly alias: string | null;
+  readonly arguments: IsographArguments | null;
+  readonly isUpdatable: boolean;
+}};
+
+export type IsographReaderLinkedField = {{
+  readonly kind: 'Linked';
+  readonly fieldName: string;
+  readonly alias: string | null;
+  readonly arguments: IsographArguments | null;
+  readonly condition: ReaderLinkedField['condition'];
+  readonly isUpdatable: boolean;
+  readonly selections: ReadonlyArray<IsographReaderAstNode>;
+}};
+
+export type IsographReaderResolverField = {{
+  readonly kind: 'Resolver';
+  readonly alias: string;
+  readonly arguments: IsographArguments | null;
+  readonly readerArtifact: ReaderNonLoadableResolverField['readerArtifact'];
+  readonly usedRefetchQueries: number[];
+  readonly concreteOverrides?: ReaderNonLoadableResolverField['concreteOverrides'];
+}};
+
+export type IsographReaderImperativelyLoadedField = {{
+  readonly kind: 'ImperativelyLoadedField';
+  readonly alias: string;
+  readonly refetchReaderArtifact: ReaderImperativelyLoadedField['refetchReaderArtifact'];
+  readonly refetchQuery: number;
+  readonly name: string;
+}};
+
+export type IsographReaderLoadableField = {{
+  readonly kind: 'LoadablySelectedField';
+  readonly alias: string;
+  readonly name: string;
+  readonly queryArguments: IsographArguments | null;
+  readonly refetchReaderAst: ReadonlyArray<IsographReaderAstNode>;
+  readonly entrypoint: ReaderLoadableField['entrypoint'];
+}};
+
+export type IsographReaderLinkField = {{
+  readonly kind: 'Link';
+  readonly alias: string;
+}};
+
+export type IsographReaderConditionalField = {{
+  readonly kind: 'Conditional';
+  readonly alias: string;
+  readonly conditions: ReadonlyArray<
+    | {{
+        readonly kind: 'Include' | 'Skip';
+        readonly value: IsographArgumentValue;
+      }}
+    | {{
+        readonly kind: 'ConcreteType';
+        readonly concreteTypes: ReadonlyArray<string>;
+      }}
+  >;
+  readonly selection: IsographReaderAstNode;
+}};
+
+export type IsographNormalizationAstNode =
+  | IsographNormalizationScalarField
+  | IsographNormalizationLinkedField
+  | IsographNormalizationInlineFragment;
+
+export type IsographNormalizationScalarField = {{
+  readonly kind: 'Scalar';
+  readonly fieldName: string;
+  readonly arguments: IsographArguments | null;
+}};
+
+export type IsographNormalizationLinkedField = {{
+  readonly kind: 'Linked';
+  readonly fieldName: string;
+  readonly arguments: IsographArguments | null;
+  readonly concreteType: string | null;
+  readonly selections: ReadonlyArray<IsographNormalizationAstNode>;
+}};
+
+export type IsographNormalizationInlineFragment = {{
+  readonly kind: 'InlineFragment';
+  readonly type: string;
+  readonly selections: ReadonlyArray<IsographNormalizationAstNode>;
+}};
+
+// If the following lines fail to type check, the installed version of
+// @isograph/react does not match the version of the compiler that generated
+// these artifacts. Install the same version of both, and recompile.
+
+type AssertVersionsMatch<TRuntimeVersion extends IsographCompilerVersion> =
+  TRuntimeVersion;
+export type IsographVersionCheck = AssertVersionsMatch<IsographRuntimeVersion>;
+
+type AssertRuntimeAccepts<TGenerated extends TRuntime, TRuntime> = TGenerated;
+export type IsographReaderAstCheck = AssertRuntimeAccepts<
+  IsographReaderAstNode,
+  ReaderAstNode
+>;
+export type IsographNormalizationAstCheck = AssertRuntimeAccepts<
+  IsographNormalizationAstNode,
+  NormalizationAstNode
+>;
+"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn runtime_version_matches_compiler_version() {
+        let runtime_version_file = include_str!("../../../libs/isograph-react/src/core/version.ts");
+        assert!(
+            runtime_version_file.contains(&format!(
+                "export type IsographRuntimeVersion = '{}';",
+                env!("CARGO_PKG_VERSION")
+            )),
+            "Expected IsographRuntimeVersion in libs/isograph-react/src/core/version.ts \
+            to be the version of the compiler."
+        );
+    }
+}
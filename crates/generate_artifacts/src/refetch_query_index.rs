@@ -0,0 +1,111 @@
+use common_lang_types::{ArtifactPathAndContent, ObjectTypeAndFieldName, VariableName};
+use isograph_config::{ArtifactLayout, GenerateFileExtensionsOption};
+use isograph_schema::{NormalizationKey, REFETCH_FIELD_NAME};
+
+use crate::generate_artifacts::{get_serialized_field_arguments, REFETCH_QUERY_INDEX_FILE_NAME};
+
+/// A refetch query that is nested in an entrypoint, e.g. because a client field
+/// selects __refetch or a mutation field, or selects a client field loadably.
+pub(crate) struct RefetchQueryIndexEntry<'a> {
+    pub client_field: ObjectTypeAndFieldName,
+    pub operation_kind: String,
+    /// The path from the root of the entrypoint to the object that is refetched
+    pub path: &'a [NormalizationKey],
+    /// Already serialized, in the same format as in the entrypoint artifact
+    pub allowed_variables: String,
+    pub required_variables: Vec<VariableName>,
+}
+
+/// Generate an artifact that lists the refetch queries nested in an entrypoint, in the
+/// same order as the nestedRefetchQueries of the entrypoint artifact. This allows the
+/// runtime to prefetch or batch refetch queries without traversing reader ASTs.
+pub(crate) fn generate_refetch_query_index_artifact(
+    entrypoint_type_and_field: ObjectTypeAndFieldName,
+    entries: &[RefetchQueryIndexEntry],
+    file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
+) -> ArtifactPathAndContent {
+    let mut imports = "import type { RefetchQueryIndex } from '@isograph/react';\n".to_string();
+    let mut entries_text = String::new();
+
+    for (query_index, entry) in entries.iter().enumerate() {
+        imports.push_str(&format!(
+            "import refetchQuery{query_index} from '{}{}';\n",
+            artifact_layout.sibling_import_path(
+                entrypoint_type_and_field,
+                format!("{}__{}", *REFETCH_FIELD_NAME, query_index)
+            ),
+            file_extensions.ts()
+        ));
+
+        let mut path_text = String::new();
+        for normalization_key in entry.path {
+            path_text.push_str(&serialize_path_segment(normalization_key));
+        }
+
+        let required_variables = entry
+            .required_variables
+            .iter()
+            .map(|variable_name| format!("\"{variable_name}\", "))
+            .collect::<String>();
+
+        let (path_start, path_end) = if entry.path.is_empty() {
+            ("", "")
+        } else {
+            ("\n", "    ")
+        };
+        entries_text.push_str(&format!(
+            "  {{\n\
+            {indent_2}artifact: refetchQuery{query_index},\n\
+            {indent_2}clientField: \"{}.{}\",\n\
+            {indent_2}operationKind: \"{}\",\n\
+            {indent_2}path: [{path_start}{path_text}{path_end}],\n\
+            {indent_2}allowedVariables: {},\n\
+            {indent_2}requiredVariables: [{required_variables}],\n\
+            {indent_1}}},\n",
+            entry.client_field.type_name,
+            entry.client_field.field_name,
+            entry.operation_kind,
+            entry.allowed_variables,
+            indent_1 = "  ",
+            indent_2 = "    ",
+        ));
+    }
+
+    ArtifactPathAndContent {
+        file_content: format!(
+            "{imports}\n\
+            const refetchQueryIndex: RefetchQueryIndex = [{}{entries_text}];\n\n\
+            export default refetchQueryIndex;\n",
+            if entries.is_empty() { "" } else { "\n" },
+        ),
+        file_name: *REFETCH_QUERY_INDEX_FILE_NAME,
+        type_and_field: Some(entrypoint_type_and_field),
+    }
+}
+
+fn serialize_path_segment(normalization_key: &NormalizationKey) -> String {
+    let indent_1 = "  ".repeat(3);
+    let indent_2 = "  ".repeat(4);
+    match normalization_key {
+        NormalizationKey::ServerField(name_and_arguments) => format!(
+            "{indent_1}{{\n\
+            {indent_2}kind: \"Linked\",\n\
+            {indent_2}fieldName: \"{}\",\n\
+            {indent_2}arguments: {},\n\
+            {indent_1}}},\n",
+            name_and_arguments.name,
+            get_serialized_field_arguments(&name_and_arguments.arguments, 4),
+        ),
+        NormalizationKey::InlineFragment(type_to_refine_to) => format!(
+            "{indent_1}{{\n\
+            {indent_2}kind: \"InlineFragment\",\n\
+            {indent_2}type: \"{type_to_refine_to}\",\n\
+            {indent_1}}},\n"
+        ),
+        NormalizationKey::Discriminator | NormalizationKey::Id => panic!(
+            "Expected the path to a refetched object to only contain linked fields \
+            and inline fragments. This is indicative of a bug in Isograph."
+        ),
+    }
+}
@@ -1,6 +1,6 @@
 use common_lang_types::{ArtifactPathAndContent, ObjectTypeAndFieldName};
 
-use isograph_config::GenerateFileExtensionsOption;
+use isograph_config::{ArtifactLayout, GenerateFileExtensionsOption};
 use isograph_lang_types::SelectionType;
 use isograph_schema::{
     initial_variable_context, ClientScalarOrObjectSelectable, ClientScalarSelectable, FieldMapItem,
@@ -22,6 +22,7 @@ pub(crate) fn generate_refetch_reader_artifact<TNetworkProtocol: NetworkProtocol
     refetched_paths: &RefetchedPathsMap,
     was_selected_loadably: bool,
     file_extensions: GenerateFileExtensionsOption,
+    artifact_layout: ArtifactLayout,
     field_map: &[FieldMapItem],
 ) -> ArtifactPathAndContent {
     let read_out_data = get_read_out_data(field_map);
@@ -45,13 +46,15 @@ pub(crate) fn generate_refetch_reader_artifact<TNetworkProtocol: NetworkProtocol
         } else {
             client_field.selection_set_for_parent_query()
         },
+        client_field.parent_object_entity_id,
         0,
         refetched_paths,
         &initial_variable_context(&SelectionType::Scalar(client_field)),
+        artifact_layout,
     );
 
     let reader_import_statement =
-        reader_imports_to_import_statement(&reader_imports, file_extensions);
+        reader_imports_to_import_statement(&reader_imports, file_extensions, artifact_layout);
 
     let reader_content = format!(
             "import type {{ RefetchReaderArtifact, ReaderAst, RefetchQueryNormalizationArtifact }} from '@isograph/react';\n\
@@ -86,7 +89,7 @@ pub(crate) fn generate_refetch_output_type_artifact<TNetworkProtocol: NetworkPro
         .server_entity_data
         .server_object_entity(client_field.parent_object_entity_id);
 
-    let client_field_output_type = generate_output_type(client_field);
+    let client_field_output_type = generate_output_type(schema, client_field);
 
     let output_type_text = {
         let parent_type_name = parent_type.name;
@@ -162,7 +165,7 @@ fn get_read_out_data(field_map: &[FieldMapItem]) -> String {
         let split_to_arg = item.split_to_arg();
         let mut path_segments = Vec::with_capacity(1 + split_to_arg.to_field_names.len());
         path_segments.push(split_to_arg.to_argument_name);
-        path_segments.extend(split_to_arg.to_field_names.into_iter());
+        path_segments.extend(split_to_arg.to_field_names);
 
         let last_index = path_segments.len() - 1;
         let mut path_so_far = "".to_string();
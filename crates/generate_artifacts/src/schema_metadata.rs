@@ -0,0 +1,351 @@
+use std::collections::{BTreeSet, HashMap};
+
+use common_lang_types::{ArtifactFileName, ArtifactPathAndContent, WithSpan};
+use intern::{string_key::Intern, Lookup};
+use isograph_config::CompilerConfig;
+use isograph_lang_types::{
+    ClientFieldDirectiveSet, DefinitionLocation, SelectionType, ServerEntityId,
+    ServerObjectEntityId, VariableDefinition,
+};
+use isograph_schema::{
+    graphql_output_type, ClientFieldVariant, ClientScalarOrObjectSelectable, NetworkProtocol,
+    ObjectSelectableId, RefetchStrategy, ScalarSelectableId, Schema,
+    SchemaServerObjectSelectableVariant,
+};
+use lazy_static::lazy_static;
+use serde_json::{json, Value};
+
+lazy_static! {
+    pub static ref SCHEMA_METADATA_FILE_NAME: ArtifactFileName =
+        "schema-metadata.json".intern().into();
+}
+
+/// Generate a JSON description of the compiled project: every object and scalar
+/// in the schema, the server fields of each object (with their arguments), and
+/// the client fields and client pointers defined on each object. Devtools and
+/// runtime debuggers can read this instead of parsing the schema.
+///
+/// Everything is sorted by name, so that the artifact is stable across runs.
+pub(crate) fn generate_schema_metadata_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    config: &CompilerConfig,
+) -> ArtifactPathAndContent {
+    let refinements = refinements(schema);
+    let mut objects = schema
+        .server_entity_data
+        .server_object_entities_and_ids()
+        .map(|object| {
+            (
+                object.item.name.to_string(),
+                object_metadata(schema, config, object.id, refinements.get(&object.id)),
+            )
+        })
+        .collect::<Vec<_>>();
+    objects.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut scalars = schema
+        .server_entity_data
+        .server_scalars
+        .iter()
+        .map(|scalar| {
+            (
+                scalar.name.item.to_string(),
+                json!({
+                    "name": scalar.name.item.to_string(),
+                    "description": scalar.description.map(|description| description.item.to_string()),
+                    "javascript_type": scalar.javascript_name.to_string(),
+                    "specified_by_url": scalar.specified_by_url.map(|url| url.to_string()),
+                }),
+            )
+        })
+        .collect::<Vec<_>>();
+    scalars.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let metadata = json!({
+        "objects": objects.into_iter().map(|(_, object)| object).collect::<Vec<_>>(),
+        "scalars": scalars.into_iter().map(|(_, scalar)| scalar).collect::<Vec<_>>(),
+    });
+
+    ArtifactPathAndContent {
+        file_content: format!(
+            "{}\n",
+            serde_json::to_string_pretty(&metadata)
+                .expect("Expected schema metadata to be serializable")
+        ),
+        file_name: *SCHEMA_METADATA_FILE_NAME,
+        type_and_field: None,
+    }
+}
+
+fn object_metadata<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    config: &CompilerConfig,
+    object_entity_id: ServerObjectEntityId,
+    refinements: Option<&Refinements>,
+) -> Value {
+    let object = schema
+        .server_entity_data
+        .server_object_entity(object_entity_id);
+
+    let mut server_fields = vec![];
+    let mut client_fields = vec![];
+    let mut selectables = schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&object_entity_id)
+        .map(|extra_info| extra_info.selectables.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    selectables.sort_by_key(|(name, _)| name.to_string());
+
+    for (name, selectable_id) in selectables {
+        match selectable_id {
+            DefinitionLocation::Server(SelectionType::Scalar(server_scalar_selectable_id)) => {
+                let selectable = schema.server_scalar_selectable(*server_scalar_selectable_id);
+                server_fields.push(json!({
+                    "name": name.to_string(),
+                    "kind": "scalar",
+                    "type": graphql_output_type(&selectable.target_scalar_entity)
+                        .map(|scalar_entity_id| {
+                            entity_name(schema, ServerEntityId::Scalar(scalar_entity_id))
+                        })
+                        .to_string(),
+                    "description": selectable.description.map(|description| description.to_string()),
+                    "deprecation_reason": selectable
+                        .deprecation_reason
+                        .map(|reason| reason.to_string()),
+                    "cost": selectable.cost,
+                    "owner": selectable.owner.map(|owner| owner.to_string()),
+                    "arguments": arguments_metadata(
+                        schema,
+                        selectable.arguments.iter().map(|argument| &argument.item)
+                    ),
+                }));
+            }
+            DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => {
+                let selectable = schema.server_object_selectable(*server_object_selectable_id);
+                server_fields.push(json!({
+                    "name": name.to_string(),
+                    "kind": "object",
+                    "is_refinement": !matches!(
+                        selectable.object_selectable_variant,
+                        SchemaServerObjectSelectableVariant::LinkedField
+                    ),
+                    "type": graphql_output_type(&selectable.target_object_entity)
+                        .map(|object_entity_id| {
+                            entity_name(schema, ServerEntityId::Object(object_entity_id))
+                        })
+                        .to_string(),
+                    "description": selectable.description.map(|description| description.to_string()),
+                    "deprecation_reason": selectable
+                        .deprecation_reason
+                        .map(|reason| reason.to_string()),
+                    "cost": selectable.cost,
+                    "owner": selectable.owner.map(|owner| owner.to_string()),
+                    "arguments": arguments_metadata(
+                        schema,
+                        selectable.arguments.iter().map(|argument| &argument.item)
+                    ),
+                }));
+            }
+            DefinitionLocation::Client(client_type_id) => {
+                let client_type = schema.client_type(*client_type_id);
+                let (kind, variant, target_type) = match client_type {
+                    SelectionType::Scalar(client_field) => (
+                        "field",
+                        match &client_field.variant {
+                            ClientFieldVariant::UserWritten(info) => {
+                                match info.client_field_directive_set {
+                                    ClientFieldDirectiveSet::Component(_) => "component",
+                                    ClientFieldDirectiveSet::Plural(_) => "plural",
+                                    ClientFieldDirectiveSet::None(_) => "eager",
+                                }
+                            }
+                            ClientFieldVariant::ImperativelyLoadedField(_) => "imperatively_loaded",
+                            ClientFieldVariant::Link => "link",
+                            ClientFieldVariant::Paginated(_) => "paginated",
+                        },
+                        None,
+                    ),
+                    SelectionType::Object(client_pointer) => (
+                        "pointer",
+                        "eager",
+                        Some(
+                            graphql_output_type(&client_pointer.target_object_entity)
+                                .map(|object_entity_id| {
+                                    entity_name(schema, ServerEntityId::Object(object_entity_id))
+                                })
+                                .to_string(),
+                        ),
+                    ),
+                };
+                let is_entrypoint = match client_type_id {
+                    SelectionType::Scalar(client_field_id) => {
+                        schema.entrypoints.contains_key(client_field_id)
+                    }
+                    SelectionType::Object(_) => false,
+                };
+
+                client_fields.push(json!({
+                    "name": name.to_string(),
+                    "kind": kind,
+                    "variant": variant,
+                    "type": target_type,
+                    "description": client_type.description().map(|description| description.to_string()),
+                    "file": client_type.text_source().map(|text_source| {
+                        path_relative_to_project_root(
+                            config,
+                            text_source.relative_path_to_source_file.lookup(),
+                        )
+                    }),
+                    "variables": arguments_metadata(
+                        schema,
+                        client_type
+                            .variable_definitions()
+                            .iter()
+                            .map(|variable_definition| &variable_definition.item)
+                    ),
+                    "refetch_strategy": client_type
+                        .refetch_strategy()
+                        .map(|refetch_strategy| refetch_strategy_metadata(schema, refetch_strategy)),
+                    "is_entrypoint": is_entrypoint,
+                }));
+            }
+        }
+    }
+
+    json!({
+        "name": object.name.to_string(),
+        "description": object.description.map(|description| description.to_string()),
+        "concrete": object.concrete_type.is_some(),
+        "supertypes": refinements
+            .map(|refinements| refinements.supertypes.iter().collect::<Vec<_>>())
+            .unwrap_or_default(),
+        "subtypes": refinements
+            .map(|refinements| refinements.subtypes.iter().collect::<Vec<_>>())
+            .unwrap_or_default(),
+        "root_operation": schema
+            .fetchable_types
+            .get(&object_entity_id)
+            .map(|root_operation_name| root_operation_name.0.clone()),
+        "server_fields": server_fields,
+        "client_fields": client_fields,
+    })
+}
+
+/// The supertypes of an object (i.e. the interfaces that it implements and the unions
+/// that it is a member of) and its subtypes, sorted by name.
+#[derive(Default)]
+struct Refinements {
+    supertypes: BTreeSet<String>,
+    subtypes: BTreeSet<String>,
+}
+
+/// The schema does not record which interfaces an object implements, or which
+/// unions it is a member of. Instead, the compiler adds a refinement field (e.g.
+/// asPet) to each abstract type for each of its concrete subtypes, from which we
+/// recover these relationships.
+fn refinements<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> HashMap<ServerObjectEntityId, Refinements> {
+    let mut refinements = HashMap::<ServerObjectEntityId, Refinements>::new();
+    for (parent_object_entity_id, extra_info) in
+        &schema.server_entity_data.server_object_entity_extra_info
+    {
+        for selectable_id in extra_info.selectables.values() {
+            let DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) =
+                selectable_id
+            else {
+                continue;
+            };
+            let selectable = schema.server_object_selectable(*server_object_selectable_id);
+            let target_object_entity_id = *selectable.target_object_entity.inner();
+            let (supertype_id, subtype_id) = match selectable.object_selectable_variant {
+                SchemaServerObjectSelectableVariant::LinkedField => continue,
+                SchemaServerObjectSelectableVariant::InlineFragment => {
+                    (*parent_object_entity_id, target_object_entity_id)
+                }
+                SchemaServerObjectSelectableVariant::Upcast => {
+                    (target_object_entity_id, *parent_object_entity_id)
+                }
+            };
+            refinements
+                .entry(supertype_id)
+                .or_default()
+                .subtypes
+                .insert(entity_name(schema, ServerEntityId::Object(subtype_id)));
+            refinements
+                .entry(subtype_id)
+                .or_default()
+                .supertypes
+                .insert(entity_name(schema, ServerEntityId::Object(supertype_id)));
+        }
+    }
+    refinements
+}
+
+fn arguments_metadata<'a, TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    variable_definitions: impl Iterator<Item = &'a VariableDefinition<ServerEntityId>>,
+) -> Vec<Value> {
+    variable_definitions
+        .map(|variable_definition| {
+            json!({
+                "name": variable_definition.name.item.to_string(),
+                "type": variable_definition
+                    .type_
+                    .clone()
+                    .map(|entity_id| entity_name(schema, entity_id))
+                    .to_string(),
+                "has_default_value": variable_definition.default_value.is_some(),
+            })
+        })
+        .collect()
+}
+
+fn refetch_strategy_metadata<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    refetch_strategy: &RefetchStrategy<ScalarSelectableId, ObjectSelectableId>,
+) -> Value {
+    match refetch_strategy {
+        RefetchStrategy::UseRefetchField(use_refetch_field_strategy) => json!({
+            "kind": "use_refetch_field",
+            "root_type": schema
+                .server_entity_data
+                .server_object_entity(use_refetch_field_strategy.root_fetchable_type)
+                .name
+                .to_string(),
+            "refetch_selections": use_refetch_field_strategy
+                .refetch_selection_set
+                .iter()
+                .map(|selection: &WithSpan<_>| selection.item.name_or_alias().item.to_string())
+                .collect::<Vec<_>>(),
+        }),
+    }
+}
+
+fn entity_name<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    entity_id: ServerEntityId,
+) -> String {
+    match schema.server_entity_data.server_entity(entity_id) {
+        SelectionType::Scalar(server_scalar_entity) => server_scalar_entity.name.item.to_string(),
+        SelectionType::Object(server_object_entity) => server_object_entity.name.to_string(),
+    }
+}
+
+/// Source file paths are relative to the current working directory, which would
+/// make the artifact depend on where the compiler was run.
+pub(crate) fn path_relative_to_project_root(
+    config: &CompilerConfig,
+    relative_path: &str,
+) -> String {
+    let relative_project_root = pathdiff::diff_paths(
+        &config.project_root,
+        config.current_working_directory.lookup(),
+    )
+    .expect("Expected project root to be absolute");
+    pathdiff::diff_paths(relative_path, relative_project_root)
+        .expect("Relative path should work")
+        .to_string_lossy()
+        .to_string()
+}
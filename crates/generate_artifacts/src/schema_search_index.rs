@@ -0,0 +1,378 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use common_lang_types::{ArtifactFileName, ArtifactPathAndContent, Location, Span, TextSource};
+use intern::{string_key::Intern, Lookup};
+use isograph_lang_types::{DefinitionLocation, SelectionType};
+use isograph_schema::{ClientScalarOrObjectSelectable, NetworkProtocol, Schema};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    pub static ref SCHEMA_SEARCH_INDEX_FILE_NAME: ArtifactFileName =
+        "schema-search-index.json".intern().into();
+}
+
+/// The types of the schema, and the fields and client fields defined on them,
+/// along with a trigram index over their names and descriptions. The language
+/// server uses this to rank completions and to search for workspace symbols.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaSearchIndex {
+    pub entries: Vec<SchemaSearchEntry>,
+    /// Maps each trigram of the (lowercased) words of the names and descriptions
+    /// of the entries to the indices of the entries that contain it.
+    trigrams: BTreeMap<String, Vec<usize>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaSearchEntry {
+    pub name: String,
+    pub kind: SchemaSearchEntryKind,
+    /// The type on which a field is defined. None for types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Where the entry is defined, if known. Types do not track their location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SchemaSearchEntryLocation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaSearchEntryKind {
+    Object,
+    Scalar,
+    ServerField,
+    ClientField,
+    ClientPointer,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaSearchEntryLocation {
+    /// Relative to the current working directory
+    pub path: String,
+    /// Byte offsets into the file
+    pub start: u32,
+    pub end: u32,
+}
+
+/// A matching entry, and how well it matches. Higher scores are better matches.
+#[derive(Debug)]
+pub struct SchemaSearchResult<'a> {
+    pub entry: &'a SchemaSearchEntry,
+    pub score: u32,
+}
+
+impl SchemaSearchIndex {
+    pub fn new<TNetworkProtocol: NetworkProtocol>(schema: &Schema<TNetworkProtocol>) -> Self {
+        let mut entries = vec![];
+
+        let mut objects = schema
+            .server_entity_data
+            .server_object_entities_and_ids()
+            .filter(|object| !object.item.is_input_object)
+            .collect::<Vec<_>>();
+        objects.sort_by_key(|object| object.item.name.lookup());
+
+        for object in objects {
+            entries.push(SchemaSearchEntry {
+                name: object.item.name.to_string(),
+                kind: SchemaSearchEntryKind::Object,
+                parent_type: None,
+                description: object
+                    .item
+                    .description
+                    .map(|description| description.to_string()),
+                deprecated: false,
+                location: None,
+            });
+
+            let Some(extra_info) = schema
+                .server_entity_data
+                .server_object_entity_extra_info
+                .get(&object.id)
+            else {
+                continue;
+            };
+            for (name, selectable_id) in &extra_info.selectables {
+                let (kind, description, deprecated, location) = match selectable_id {
+                    DefinitionLocation::Server(SelectionType::Scalar(id)) => {
+                        let selectable = schema.server_scalar_selectable(*id);
+                        (
+                            SchemaSearchEntryKind::ServerField,
+                            selectable.description,
+                            selectable.deprecation_reason.is_some(),
+                            schema_location(&selectable.name.location),
+                        )
+                    }
+                    DefinitionLocation::Server(SelectionType::Object(id)) => {
+                        let selectable = schema.server_object_selectable(*id);
+                        (
+                            SchemaSearchEntryKind::ServerField,
+                            selectable.description,
+                            selectable.deprecation_reason.is_some(),
+                            schema_location(&selectable.name.location),
+                        )
+                    }
+                    DefinitionLocation::Client(SelectionType::Scalar(id)) => {
+                        let client_field = schema.client_field(*id);
+                        (
+                            SchemaSearchEntryKind::ClientField,
+                            client_field.description,
+                            false,
+                            client_field.text_source().map(iso_literal_location),
+                        )
+                    }
+                    DefinitionLocation::Client(SelectionType::Object(id)) => {
+                        let client_pointer = schema.client_pointer(*id);
+                        (
+                            SchemaSearchEntryKind::ClientPointer,
+                            client_pointer.description,
+                            false,
+                            client_pointer.text_source().map(iso_literal_location),
+                        )
+                    }
+                };
+                entries.push(SchemaSearchEntry {
+                    name: name.to_string(),
+                    kind,
+                    parent_type: Some(object.item.name.to_string()),
+                    description: description.map(|description| description.to_string()),
+                    deprecated,
+                    location,
+                });
+            }
+        }
+
+        let mut scalars = schema
+            .server_entity_data
+            .server_scalars
+            .iter()
+            .collect::<Vec<_>>();
+        scalars.sort_by_key(|scalar| scalar.name.item.lookup());
+        for scalar in scalars {
+            entries.push(SchemaSearchEntry {
+                name: scalar.name.item.to_string(),
+                kind: SchemaSearchEntryKind::Scalar,
+                parent_type: None,
+                description: scalar
+                    .description
+                    .map(|description| description.item.to_string()),
+                deprecated: false,
+                location: schema_location(&scalar.name.location),
+            });
+        }
+
+        let mut trigrams = BTreeMap::<String, Vec<usize>>::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let entry_trigrams = trigrams_of(&entry.name)
+                .into_iter()
+                .chain(entry.description.iter().flat_map(|text| trigrams_of(text)))
+                .collect::<BTreeSet<_>>();
+            for trigram in entry_trigrams {
+                trigrams.entry(trigram).or_default().push(index);
+            }
+        }
+
+        SchemaSearchIndex { entries, trigrams }
+    }
+
+    /// The entries that match the query, best matches first. An entry matches if
+    /// the query is a (case-insensitive) subsequence of its name, or if its name
+    /// or description contains at least half of the trigrams of the query.
+    /// Deprecated entries are ranked after entries that match equally well.
+    pub fn search(&self, query: &str) -> Vec<SchemaSearchResult<'_>> {
+        let query_trigrams = trigrams_of(query).into_iter().collect::<BTreeSet<_>>();
+        let mut trigram_matches = HashMap::<usize, u32>::new();
+        for trigram in &query_trigrams {
+            for index in self.trigrams.get(trigram).into_iter().flatten() {
+                *trigram_matches.entry(*index).or_default() += 1;
+            }
+        }
+        // Entries that share only a few trigrams with the query (e.g. `typename`
+        // and `petname`) are not matches.
+        trigram_matches.retain(|_, matches| *matches as usize * 2 >= query_trigrams.len());
+
+        let mut results = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let name_score = fuzzy_score(query, &entry.name).unwrap_or(0);
+                let trigram_score = trigram_matches.get(&index).copied().unwrap_or(0) * 10;
+                let score = name_score + trigram_score;
+                (score > 0).then_some(SchemaSearchResult {
+                    entry,
+                    score: if entry.deprecated { score / 2 } else { score },
+                })
+            })
+            .collect::<Vec<_>>();
+        results.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.entry.name.cmp(&b.entry.name))
+        });
+        results
+    }
+
+    /// How well the query matches each of the fields defined on the type, keyed
+    /// by field name. Fields that do not match are omitted.
+    pub fn field_scores(&self, parent_type: &str, query: &str) -> HashMap<String, u32> {
+        self.search(query)
+            .into_iter()
+            .filter(|result| result.entry.parent_type.as_deref() == Some(parent_type))
+            .map(|result| (result.entry.name.clone(), result.score))
+            .collect()
+    }
+}
+
+/// Generate the schema search index artifact. It is not pretty-printed, since
+/// it is read by tools and can be large.
+pub(crate) fn generate_schema_search_index_artifact<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> ArtifactPathAndContent {
+    ArtifactPathAndContent {
+        file_content: format!(
+            "{}\n",
+            serde_json::to_string(&SchemaSearchIndex::new(schema))
+                .expect("Expected schema search index to be serializable")
+        ),
+        file_name: *SCHEMA_SEARCH_INDEX_FILE_NAME,
+        type_and_field: None,
+    }
+}
+
+fn schema_location(location: &Location) -> Option<SchemaSearchEntryLocation> {
+    match location {
+        Location::Embedded(embedded_location) => {
+            let offset = embedded_location
+                .text_source
+                .span
+                .map(|span| span.start)
+                .unwrap_or(0);
+            let span = embedded_location.span.with_offset(offset);
+            Some(SchemaSearchEntryLocation {
+                path: embedded_location
+                    .text_source
+                    .relative_path_to_source_file
+                    .to_string(),
+                start: span.start,
+                end: span.end,
+            })
+        }
+        Location::Generated => None,
+    }
+}
+
+fn iso_literal_location(text_source: TextSource) -> SchemaSearchEntryLocation {
+    let span = text_source.span.unwrap_or(Span::new(0, 0));
+    SchemaSearchEntryLocation {
+        path: text_source.relative_path_to_source_file.to_string(),
+        start: span.start,
+        end: span.end,
+    }
+}
+
+/// The trigrams of the lowercased words of the text. camelCase and snake_case
+/// names are split into words, so that e.g. `fullName` matches `name`.
+fn trigrams_of(text: &str) -> Vec<String> {
+    let mut trigrams = vec![];
+    for word in words(text) {
+        let chars = word.chars().collect::<Vec<_>>();
+        for window in chars.windows(3) {
+            trigrams.push(window.iter().collect());
+        }
+    }
+    trigrams
+}
+
+fn words(text: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let mut previous_was_lowercase = false;
+    for char in text.chars() {
+        if !char.is_alphanumeric() {
+            words.push(std::mem::take(&mut current));
+            previous_was_lowercase = false;
+            continue;
+        }
+        if char.is_uppercase() && previous_was_lowercase {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_was_lowercase = char.is_lowercase() || char.is_numeric();
+        current.extend(char.to_lowercase());
+    }
+    words.push(current);
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// How well the query matches the name, if the query is a case-insensitive
+/// subsequence of it. Exact matches score highest, then prefixes, then
+/// subsequences whose characters start words of the name (e.g. `fn` in
+/// `fullName`), then other subsequences.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<u32> {
+    if query.is_empty() {
+        return None;
+    }
+    let query = query.to_lowercase();
+    let lowercase_name = name.to_lowercase();
+    if lowercase_name == query {
+        return Some(1000);
+    }
+    if lowercase_name.starts_with(&query) {
+        return Some(800 - (lowercase_name.len() - query.len()).min(100) as u32);
+    }
+
+    let name_chars = name.chars().collect::<Vec<_>>();
+    let mut score = 300u32;
+    let mut name_index = 0;
+    for query_char in query.chars() {
+        let start = name_index;
+        loop {
+            let name_char = *name_chars.get(name_index)?;
+            name_index += 1;
+            if name_char.to_lowercase().eq(std::iter::once(query_char)) {
+                let index = name_index - 1;
+                let starts_word = index == 0
+                    || name_char.is_uppercase()
+                    || !name_chars[index - 1].is_alphanumeric();
+                if starts_word {
+                    score += 30;
+                }
+                break;
+            }
+        }
+        // Penalize characters that were skipped
+        score = score.saturating_sub((name_index - start - 1) as u32 * 2);
+    }
+    Some(score.max(1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fuzzy_score, trigrams_of};
+
+    #[test]
+    fn fuzzy_score_ranks_exact_matches_and_prefixes_first() {
+        let exact = fuzzy_score("name", "name").unwrap();
+        let prefix = fuzzy_score("name", "nameAndAge").unwrap();
+        let word_starts = fuzzy_score("fn", "fullName").unwrap();
+        let subsequence = fuzzy_score("fn", "fooBarxn").unwrap();
+        assert!(exact > prefix);
+        assert!(prefix > word_starts);
+        assert!(word_starts > subsequence);
+        assert_eq!(fuzzy_score("xyz", "fullName"), None);
+        assert_eq!(fuzzy_score("", "fullName"), None);
+    }
+
+    #[test]
+    fn trigrams_split_names_into_words() {
+        assert_eq!(
+            trigrams_of("fullName pet_id"),
+            vec!["ful", "ull", "nam", "ame", "pet"]
+        );
+    }
+}
@@ -8,7 +8,7 @@ use common_lang_types::{
     GraphQLObjectTypeName, GraphQLScalarTypeName, GraphQLUnionTypeName, InputTypeName,
     InputValueName, ServerSelectableName, UnvalidatedTypeName, WithLocation, WithSpan,
 };
-use strum::EnumString;
+use strum::{Display, EnumString};
 
 // also Schema
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
@@ -161,7 +161,7 @@ pub struct GraphQLSchemaDefinition {
 }
 
 #[allow(unused)]
-#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, EnumString, Hash)]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, EnumString, Display, Hash)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum DirectiveLocation {
     Query,
@@ -289,7 +289,7 @@ impl fmt::Display for GraphQLInputValueDefinition {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RootOperationKind {
     Query,
     Subscription,
@@ -0,0 +1,555 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Write,
+};
+
+use common_lang_types::{
+    DescriptionValue, GraphQLInterfaceTypeName, SelectableName, WithLocation, WithSpan,
+};
+use graphql_lang_types::{
+    GraphQLConstantValue, GraphQLDirective, GraphQLDirectiveDefinition, GraphQLEnumDefinition,
+    GraphQLFieldDefinition, GraphQLInputObjectTypeDefinition, GraphQLInputValueDefinition,
+    GraphQLInterfaceTypeDefinition, GraphQLObjectTypeDefinition, GraphQLScalarTypeDefinition,
+    GraphQLSchemaDefinition, GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument,
+    GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
+    GraphQLTypeSystemExtensionOrDefinition, GraphQLUnionTypeDefinition,
+};
+use intern::Lookup;
+use isograph_schema::{ClientFieldVariant, NetworkProtocol, Schema};
+
+/// Print the schema and the schema extensions as a single GraphQL schema, for tools
+/// (such as GraphQL language servers and code generators) that do not read the
+/// schema extensions.
+///
+/// - Types defined in schema extensions are printed after the types of the schema,
+///   and object type extensions are merged into the types that they extend.
+/// - Each object and interface lists every interface that it implements, including
+///   the interfaces implemented by those interfaces. Isograph refines to these even
+///   if they are not listed, but the GraphQL spec requires them to be listed.
+/// - Unions list their members.
+/// - The fields that Isograph adds to the schema (e.g. `asUser` and `__typename`)
+///   are not printed.
+/// - Client fields and client pointers with a description are printed as comments
+///   in the type that they are defined on, since they cannot be queried from the
+///   server.
+pub(crate) fn print_combined_schema(
+    type_system_document: &GraphQLTypeSystemDocument,
+    extension_documents: &[&GraphQLTypeSystemExtensionDocument],
+    client_type_descriptions: &HashMap<&str, Vec<ClientTypeDescription>>,
+) -> String {
+    let mut definitions = type_system_document
+        .0
+        .iter()
+        .map(|definition| definition.item.clone())
+        .chain(extension_documents.iter().flat_map(|document| {
+            document.0.iter().filter_map(|item| match &item.item {
+                GraphQLTypeSystemExtensionOrDefinition::Definition(definition) => {
+                    Some(definition.clone())
+                }
+                GraphQLTypeSystemExtensionOrDefinition::Extension(_) => None,
+            })
+        }))
+        .collect::<Vec<_>>();
+
+    for extension in extension_documents.iter().flat_map(|document| {
+        document.0.iter().filter_map(|item| match &item.item {
+            GraphQLTypeSystemExtensionOrDefinition::Extension(extension) => Some(extension),
+            GraphQLTypeSystemExtensionOrDefinition::Definition(_) => None,
+        })
+    }) {
+        let GraphQLTypeSystemExtension::ObjectTypeExtension(extension) = extension;
+        // Extending a type that is not defined is reported when the schema is
+        // processed, so the extended type exists.
+        let extended_type = definitions
+            .iter_mut()
+            .find_map(|definition| match definition {
+                GraphQLTypeSystemDefinition::ObjectTypeDefinition(object)
+                    if object.name.item == extension.name.item =>
+                {
+                    Some((
+                        &mut object.interfaces,
+                        &mut object.directives,
+                        &mut object.fields,
+                    ))
+                }
+                GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface)
+                    if interface.name.item.lookup() == extension.name.item.lookup() =>
+                {
+                    Some((
+                        &mut interface.interfaces,
+                        &mut interface.directives,
+                        &mut interface.fields,
+                    ))
+                }
+                _ => None,
+            });
+        if let Some((interfaces, directives, fields)) = extended_type {
+            interfaces.extend(extension.interfaces.iter().cloned());
+            directives.extend(extension.directives.iter().cloned());
+            fields.extend(extension.fields.iter().cloned());
+        }
+    }
+
+    let implemented_interfaces = implemented_interfaces(&definitions);
+
+    definitions
+        .iter()
+        .map(|definition| {
+            let mut output = String::new();
+            match definition {
+                GraphQLTypeSystemDefinition::ObjectTypeDefinition(object) => print_object(
+                    &mut output,
+                    object,
+                    &implemented_interfaces,
+                    client_type_descriptions,
+                ),
+                GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar) => {
+                    print_scalar(&mut output, scalar)
+                }
+                GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface) => print_interface(
+                    &mut output,
+                    interface,
+                    &implemented_interfaces,
+                    client_type_descriptions,
+                ),
+                GraphQLTypeSystemDefinition::InputObjectTypeDefinition(input_object) => {
+                    print_input_object(&mut output, input_object)
+                }
+                GraphQLTypeSystemDefinition::DirectiveDefinition(directive) => {
+                    print_directive_definition(&mut output, directive)
+                }
+                GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
+                    print_enum(&mut output, enum_definition)
+                }
+                GraphQLTypeSystemDefinition::UnionTypeDefinition(union) => {
+                    print_union(&mut output, union)
+                }
+                GraphQLTypeSystemDefinition::SchemaDefinition(schema_definition) => {
+                    print_schema_definition(&mut output, schema_definition)
+                }
+            }
+            output
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// For each object and interface, the interfaces that it implements, directly or
+/// through other interfaces. Directly implemented interfaces come first.
+fn implemented_interfaces(
+    definitions: &[GraphQLTypeSystemDefinition],
+) -> HashMap<&str, Vec<GraphQLInterfaceTypeName>> {
+    let direct_interfaces = definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(object) => {
+                Some((object.name.item.lookup(), &object.interfaces))
+            }
+            GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface) => {
+                Some((interface.name.item.lookup(), &interface.interfaces))
+            }
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    direct_interfaces
+        .iter()
+        .map(|(name, interfaces)| {
+            let mut implemented_interfaces = vec![];
+            let mut seen = HashSet::new();
+            let mut queue = interfaces
+                .iter()
+                .map(|interface| interface.item)
+                .collect::<VecDeque<_>>();
+            while let Some(interface) = queue.pop_front() {
+                // An interface cannot implement itself, which is reported when the
+                // schema is processed. Cycles are nonetheless skipped here.
+                if interface.lookup() == *name || !seen.insert(interface) {
+                    continue;
+                }
+                implemented_interfaces.push(interface);
+                if let Some(interfaces) = direct_interfaces.get(interface.lookup()) {
+                    queue.extend(interfaces.iter().map(|interface| interface.item));
+                }
+            }
+            (*name, implemented_interfaces)
+        })
+        .collect()
+}
+
+/// A client field or client pointer with a description, e.g. `File.FileSize`.
+pub(crate) struct ClientTypeDescription {
+    /// "client field" or "client pointer"
+    pub kind: &'static str,
+    pub name: SelectableName,
+    pub description: DescriptionValue,
+}
+
+/// The user-written client fields and client pointers that have a description, by
+/// the name of the type they are defined on, ordered by name.
+pub(crate) fn client_type_descriptions<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> HashMap<&'static str, Vec<ClientTypeDescription>> {
+    let client_fields = schema
+        .client_scalar_selectables
+        .iter()
+        .filter(|client_field| matches!(client_field.variant, ClientFieldVariant::UserWritten(_)))
+        .filter_map(|client_field| {
+            Some((
+                client_field.type_and_field,
+                "client field",
+                client_field.description?,
+            ))
+        });
+    let client_pointers = schema
+        .client_object_selectables
+        .iter()
+        .filter_map(|client_pointer| {
+            Some((
+                client_pointer.type_and_field,
+                "client pointer",
+                client_pointer.description?,
+            ))
+        });
+
+    let mut client_type_descriptions: HashMap<_, Vec<_>> = HashMap::new();
+    for (type_and_field, kind, description) in client_fields.chain(client_pointers) {
+        client_type_descriptions
+            .entry(type_and_field.type_name.lookup())
+            .or_default()
+            .push(ClientTypeDescription {
+                kind,
+                name: type_and_field.field_name,
+                description,
+            });
+    }
+    for client_types in client_type_descriptions.values_mut() {
+        client_types.sort_by_key(|client_type| client_type.name.lookup());
+    }
+    client_type_descriptions
+}
+
+fn print_object(
+    output: &mut String,
+    object: &GraphQLObjectTypeDefinition,
+    implemented_interfaces: &HashMap<&str, Vec<GraphQLInterfaceTypeName>>,
+    client_type_descriptions: &HashMap<&str, Vec<ClientTypeDescription>>,
+) {
+    print_description(output, &object.description, "");
+    write!(output, "type {}", object.name.item).unwrap();
+    print_implemented_interfaces(
+        output,
+        implemented_interfaces.get(object.name.item.lookup()),
+    );
+    print_directives(output, &object.directives);
+    print_fields(
+        output,
+        &object.fields,
+        client_type_descriptions.get(object.name.item.lookup()),
+    );
+}
+
+fn print_interface(
+    output: &mut String,
+    interface: &GraphQLInterfaceTypeDefinition,
+    implemented_interfaces: &HashMap<&str, Vec<GraphQLInterfaceTypeName>>,
+    client_type_descriptions: &HashMap<&str, Vec<ClientTypeDescription>>,
+) {
+    print_description(output, &interface.description, "");
+    write!(output, "interface {}", interface.name.item).unwrap();
+    print_implemented_interfaces(
+        output,
+        implemented_interfaces.get(interface.name.item.lookup()),
+    );
+    print_directives(output, &interface.directives);
+    print_fields(
+        output,
+        &interface.fields,
+        client_type_descriptions.get(interface.name.item.lookup()),
+    );
+}
+
+fn print_implemented_interfaces(
+    output: &mut String,
+    interfaces: Option<&Vec<GraphQLInterfaceTypeName>>,
+) {
+    if let Some(interfaces) = interfaces.filter(|interfaces| !interfaces.is_empty()) {
+        write!(
+            output,
+            " implements {}",
+            interfaces
+                .iter()
+                .map(|interface| interface.lookup())
+                .collect::<Vec<_>>()
+                .join(" & ")
+        )
+        .unwrap();
+    }
+}
+
+fn print_fields(
+    output: &mut String,
+    fields: &[WithLocation<GraphQLFieldDefinition>],
+    client_types: Option<&Vec<ClientTypeDescription>>,
+) {
+    let client_types = client_types.map(Vec::as_slice).unwrap_or_default();
+    if fields.is_empty() {
+        output.push('\n');
+        print_client_type_descriptions(output, client_types, "");
+        return;
+    }
+    output.push_str(" {\n");
+    for field in fields {
+        let field = &field.item;
+        print_description(output, &field.description, "  ");
+        write!(output, "  {}", field.name.item).unwrap();
+        print_argument_definitions(output, &field.arguments, "  ");
+        write!(output, ": {}", field.type_).unwrap();
+        print_directives(output, &field.directives);
+        output.push('\n');
+    }
+    print_client_type_descriptions(output, client_types, "  ");
+    output.push_str("}\n");
+}
+
+/// e.g.
+/// # FileSize (client field)
+/// # The size of the file, in bytes.
+fn print_client_type_descriptions(
+    output: &mut String,
+    client_types: &[ClientTypeDescription],
+    indent: &str,
+) {
+    for client_type in client_types {
+        writeln!(
+            output,
+            "{indent}# {} ({})",
+            client_type.name, client_type.kind
+        )
+        .unwrap();
+        for line in client_type.description.lookup().lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                writeln!(output, "{indent}#").unwrap();
+            } else {
+                writeln!(output, "{indent}# {line}").unwrap();
+            }
+        }
+    }
+}
+
+fn print_scalar(output: &mut String, scalar: &GraphQLScalarTypeDefinition) {
+    print_description(output, &scalar.description, "");
+    write!(output, "scalar {}", scalar.name.item).unwrap();
+    print_directives(output, &scalar.directives);
+    output.push('\n');
+}
+
+fn print_input_object(output: &mut String, input_object: &GraphQLInputObjectTypeDefinition) {
+    print_description(output, &input_object.description, "");
+    write!(output, "input {}", input_object.name.item).unwrap();
+    print_directives(output, &input_object.directives);
+    if input_object.fields.is_empty() {
+        output.push('\n');
+        return;
+    }
+    output.push_str(" {\n");
+    for field in &input_object.fields {
+        print_description(output, &field.item.description, "  ");
+        output.push_str("  ");
+        print_input_value_definition(output, &field.item);
+        output.push('\n');
+    }
+    output.push_str("}\n");
+}
+
+fn print_enum(output: &mut String, enum_definition: &GraphQLEnumDefinition) {
+    print_description(output, &enum_definition.description, "");
+    write!(output, "enum {}", enum_definition.name.item).unwrap();
+    print_directives(output, &enum_definition.directives);
+    if enum_definition.enum_value_definitions.is_empty() {
+        output.push('\n');
+        return;
+    }
+    output.push_str(" {\n");
+    for value in &enum_definition.enum_value_definitions {
+        print_description(output, &value.item.description, "  ");
+        write!(output, "  {}", value.item.value.item).unwrap();
+        print_directives(output, &value.item.directives);
+        output.push('\n');
+    }
+    output.push_str("}\n");
+}
+
+fn print_union(output: &mut String, union: &GraphQLUnionTypeDefinition) {
+    print_description(output, &union.description, "");
+    write!(output, "union {}", union.name.item).unwrap();
+    print_directives(output, &union.directives);
+    if !union.union_member_types.is_empty() {
+        write!(
+            output,
+            " = {}",
+            union
+                .union_member_types
+                .iter()
+                .map(|member| member.item.lookup())
+                .collect::<Vec<_>>()
+                .join(" | ")
+        )
+        .unwrap();
+    }
+    output.push('\n');
+}
+
+fn print_directive_definition(output: &mut String, directive: &GraphQLDirectiveDefinition) {
+    print_description(output, &directive.description, "");
+    write!(output, "directive @{}", directive.name.item).unwrap();
+    print_argument_definitions(output, &directive.arguments, "");
+    if directive.repeatable.is_some() {
+        output.push_str(" repeatable");
+    }
+    writeln!(
+        output,
+        " on {}",
+        directive
+            .locations
+            .iter()
+            .map(|location| location.item.to_string())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    )
+    .unwrap();
+}
+
+fn print_schema_definition(output: &mut String, schema_definition: &GraphQLSchemaDefinition) {
+    print_description(output, &schema_definition.description, "");
+    output.push_str("schema");
+    print_directives(output, &schema_definition.directives);
+    output.push_str(" {\n");
+    for (operation, type_name) in [
+        ("query", &schema_definition.query),
+        ("mutation", &schema_definition.mutation),
+        ("subscription", &schema_definition.subscription),
+    ] {
+        if let Some(type_name) = type_name {
+            writeln!(output, "  {operation}: {}", type_name.item).unwrap();
+        }
+    }
+    output.push_str("}\n");
+}
+
+/// Arguments are printed on one line, unless one of them has a description.
+fn print_argument_definitions(
+    output: &mut String,
+    arguments: &[WithLocation<GraphQLInputValueDefinition>],
+    indent: &str,
+) {
+    if arguments.is_empty() {
+        return;
+    }
+    if arguments
+        .iter()
+        .all(|argument| argument.item.description.is_none())
+    {
+        output.push('(');
+        for (index, argument) in arguments.iter().enumerate() {
+            if index > 0 {
+                output.push_str(", ");
+            }
+            print_input_value_definition(output, &argument.item);
+        }
+        output.push(')');
+        return;
+    }
+
+    output.push_str("(\n");
+    let argument_indent = format!("{indent}  ");
+    for argument in arguments {
+        print_description(output, &argument.item.description, &argument_indent);
+        output.push_str(&argument_indent);
+        print_input_value_definition(output, &argument.item);
+        output.push('\n');
+    }
+    write!(output, "{indent})").unwrap();
+}
+
+fn print_input_value_definition(output: &mut String, input_value: &GraphQLInputValueDefinition) {
+    write!(output, "{}: {}", input_value.name.item, input_value.type_).unwrap();
+    if let Some(default_value) = &input_value.default_value {
+        output.push_str(" = ");
+        print_value(output, &default_value.item);
+    }
+    print_directives(output, &input_value.directives);
+}
+
+fn print_directives(output: &mut String, directives: &[GraphQLDirective<GraphQLConstantValue>]) {
+    for directive in directives {
+        write!(output, " @{}", directive.name.item).unwrap();
+        if directive.arguments.is_empty() {
+            continue;
+        }
+        output.push('(');
+        for (index, argument) in directive.arguments.iter().enumerate() {
+            if index > 0 {
+                output.push_str(", ");
+            }
+            write!(output, "{}: ", argument.name.item).unwrap();
+            print_value(output, &argument.value.item);
+        }
+        output.push(')');
+    }
+}
+
+fn print_value(output: &mut String, value: &GraphQLConstantValue) {
+    match value {
+        GraphQLConstantValue::List(items) => {
+            output.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.push_str(", ");
+                }
+                print_value(output, &item.item);
+            }
+            output.push(']');
+        }
+        GraphQLConstantValue::Object(entries) => {
+            output.push('{');
+            for (index, entry) in entries.iter().enumerate() {
+                if index > 0 {
+                    output.push_str(", ");
+                }
+                write!(output, "{}: ", entry.name.item).unwrap();
+                print_value(output, &entry.value.item);
+            }
+            output.push('}');
+        }
+        // Strings are stored as written (i.e. escaped), so they are printed as is.
+        value => write!(output, "{value}").unwrap(),
+    }
+}
+
+/// Descriptions are printed as block strings, in which (unlike in strings) no
+/// character other than """ needs to be escaped.
+fn print_description(
+    output: &mut String,
+    description: &Option<WithSpan<DescriptionValue>>,
+    indent: &str,
+) {
+    let Some(description) = description else {
+        return;
+    };
+    let description = description.item.lookup().replace(r#"""""#, r#"\""""#);
+    if !description.contains('\n') && !description.ends_with('"') {
+        writeln!(output, r#"{indent}"""{description}""""#).unwrap();
+        return;
+    }
+    writeln!(output, r#"{indent}""""#).unwrap();
+    for line in description.lines() {
+        if line.is_empty() {
+            output.push('\n');
+        } else {
+            writeln!(output, "{indent}{line}").unwrap();
+        }
+    }
+    writeln!(output, r#"{indent}""""#).unwrap();
+}
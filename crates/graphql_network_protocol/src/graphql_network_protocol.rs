@@ -1,10 +1,12 @@
 use std::{collections::BTreeMap, error::Error};
 
 use common_lang_types::{
-    DirectiveName, QueryOperationName, QueryText, RelativePathToSourceFile, WithLocation,
+    ArtifactFileName, ArtifactPathAndContent, CodedError, DirectiveName, Location,
+    QueryOperationName, QueryText, RelativePathToSourceFile, WithLocation,
 };
-use graphql_lang_types::{from_graphql_directive, DeserializationError};
+use graphql_lang_types::{from_graphql_directive, DeserializationError, RootOperationKind};
 use intern::string_key::Intern;
+use isograph_config::CompilerConfigOptions;
 use isograph_lang_types::SchemaSource;
 use isograph_schema::{
     CreateAdditionalFieldsError, ExposeAsFieldToInsert, MergedSelectionMap, NetworkProtocol,
@@ -14,16 +16,19 @@ use lazy_static::lazy_static;
 use pico::{Database, SourceId};
 
 use crate::{
-    parse_graphql_schema,
+    combined_schema::{client_type_descriptions, print_combined_schema},
+    parse_graphql_schema, parse_schema_extensions_file,
     process_type_system_definition::{
         process_graphql_type_extension_document, process_graphql_type_system_document,
-        ProcessGraphqlTypeSystemDefinitionError, QUERY_TYPE,
+        ProcessGraphqlTypeSystemDefinitionError,
     },
-    query_text::generate_query_text,
+    query_text::generate_graphql_query_text,
+    strict_schema::validate_strict_schema,
 };
 
 lazy_static! {
     static ref EXPOSE_FIELD_DIRECTIVE: DirectiveName = "exposeField".intern().into();
+    static ref COMBINED_SCHEMA_FILE_NAME: ArtifactFileName = "schema.graphql".intern().into();
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
@@ -37,21 +42,53 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
 
     type SchemaObjectAssociatedData = GraphQLSchemaObjectAssociatedData;
 
+    // Errors are converted to CodedErrors, so that the compiler (which does not know
+    // about ProcessGraphqlTypeSystemDefinitionError) can report their error codes.
     fn parse_and_process_type_system_documents(
         db: &Database,
         sources: &Self::Sources,
+        options: &CompilerConfigOptions,
     ) -> Result<ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>, Box<dyn Error>> {
         let (schema_source_id, schema_extension_sources) = sources;
 
-        let (type_system_document, type_system_extension_documents) =
-            parse_graphql_schema(db, *schema_source_id, schema_extension_sources).to_owned()?;
+        let type_system_document = parse_graphql_schema(db, *schema_source_id);
+        let type_system_document = type_system_document.as_ref().map_err(Clone::clone)?;
+
+        if options.strict_schema {
+            let type_system_extension_documents = schema_extension_sources
+                .values()
+                .map(|schema_extension_source_id| {
+                    parse_schema_extensions_file(db, *schema_extension_source_id)
+                })
+                .collect::<Vec<_>>();
+            let type_system_extension_documents = type_system_extension_documents
+                .iter()
+                .map(|document| document.as_ref().map_err(Clone::clone))
+                .collect::<Result<Vec<_>, _>>()?;
+            validate_strict_schema(
+                type_system_document,
+                &type_system_extension_documents,
+                options,
+            )
+            .map_err(|error| error.map(CodedError::new))?;
+        }
 
         let (mut result, mut directives, mut refetch_fields) =
-            process_graphql_type_system_document(type_system_document.to_owned())?;
-
-        for type_system_extension_document in type_system_extension_documents.values() {
+            process_graphql_type_system_document(type_system_document, options.root_type_names)
+                .map_err(|error| error.map(CodedError::new))?;
+
+        for schema_extension_source_id in schema_extension_sources.values() {
+            let type_system_extension_document =
+                parse_schema_extensions_file(db, *schema_extension_source_id);
+            let type_system_extension_document = type_system_extension_document
+                .as_ref()
+                .map_err(Clone::clone)?;
             let (outcome, objects_and_directives, new_refetch_fields) =
-                process_graphql_type_extension_document(type_system_extension_document.to_owned())?;
+                process_graphql_type_extension_document(
+                    type_system_extension_document,
+                    options.root_type_names,
+                )
+                .map_err(|error| error.map(CodedError::new))?;
 
             for (name, new_directives) in objects_and_directives {
                 directives.entry(name).or_default().extend(new_directives);
@@ -70,8 +107,15 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
         let query = result
             .objects
             .iter_mut()
-            .find(|(object, _)| object.server_object_entity.name == *QUERY_TYPE)
-            .expect("Expected query type to be defined. Renaming the query is not yet supported.");
+            .find(|(object, _)| object.encountered_root_kind == Some(RootOperationKind::Query))
+            .ok_or_else(|| {
+                WithLocation::new(
+                    CodedError::new(ProcessGraphqlTypeSystemDefinitionError::MissingQueryType {
+                        query_type_name: options.root_type_names.query,
+                    }),
+                    Location::generated(),
+                )
+            })?;
         query.0.expose_as_fields_to_insert.extend(refetch_fields);
 
         // - in the extension document, you may have added directives to objects, e.g. @exposeAs
@@ -103,15 +147,17 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
                                     parent_object_name: object.server_object_entity.name,
                                     description: None,
                                 });
+                        } else {
+                            object.server_object_entity.directives.push(directive);
                         }
                     }
                 }
                 None => {
-                    return Err(Box::new(
+                    return Err(Box::new(CodedError::new(
                         ProcessGraphqlTypeSystemDefinitionError::AttemptedToExtendUndefinedType {
                             type_name: name,
                         },
-                    ));
+                    )));
                 }
             }
         }
@@ -126,7 +172,7 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
         query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
         root_operation_name: &RootOperationName,
     ) -> QueryText {
-        generate_query_text(
+        generate_graphql_query_text(
             query_name,
             schema,
             selection_map,
@@ -134,6 +180,37 @@ impl NetworkProtocol for GraphQLNetworkProtocol {
             root_operation_name,
         )
     }
+
+    fn generate_combined_schema_artifact(
+        db: &Database,
+        sources: &Self::Sources,
+        schema: &Schema<Self>,
+    ) -> Result<Option<ArtifactPathAndContent>, Box<dyn Error>> {
+        let (schema_source_id, schema_extension_sources) = sources;
+
+        let type_system_document = parse_graphql_schema(db, *schema_source_id);
+        let type_system_document = type_system_document.as_ref().map_err(Clone::clone)?;
+        let type_system_extension_documents = schema_extension_sources
+            .values()
+            .map(|schema_extension_source_id| {
+                parse_schema_extensions_file(db, *schema_extension_source_id)
+            })
+            .collect::<Vec<_>>();
+        let type_system_extension_documents = type_system_extension_documents
+            .iter()
+            .map(|document| document.as_ref().map_err(Clone::clone))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(ArtifactPathAndContent {
+            file_content: print_combined_schema(
+                type_system_document,
+                &type_system_extension_documents,
+                &client_type_descriptions(schema),
+            ),
+            file_name: *COMBINED_SCHEMA_FILE_NAME,
+            type_and_field: None,
+        }))
+    }
 }
 
 #[derive(Debug)]
@@ -1,10 +1,13 @@
+mod combined_schema;
 mod graphql_network_protocol;
 mod process_type_system_definition;
 mod query_text;
 mod read_schema;
+mod strict_schema;
 
 pub use graphql_network_protocol::*;
 use isograph_schema::{ClientScalarSelectable, Schema, ServerObjectEntity};
+pub use query_text::generate_graphql_query_text;
 pub use read_schema::*;
 
 pub type ValidatedGraphqlSchema = Schema<GraphQLNetworkProtocol>;
@@ -1,19 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use common_lang_types::{
-    GraphQLInterfaceTypeName, IsographObjectTypeName, Location, SelectableName,
-    ServerScalarSelectableName, Span, UnvalidatedTypeName, WithLocation, WithSpan,
+    error_codes, DescriptionValue, DirectiveArgumentName, DirectiveName, ErrorCode,
+    GraphQLInterfaceTypeName, GraphQLUnionTypeName, HasErrorCode, IsographObjectTypeName, Location,
+    SelectableName, ServerScalarSelectableName, Span, StringLiteralValue, UnvalidatedTypeName,
+    WithLocation, WithSpan,
 };
 use graphql_lang_types::{
-    GraphQLConstantValue, GraphQLDirective, GraphQLNamedTypeAnnotation,
+    DirectiveLocation, GraphQLConstantValue, GraphQLDirective, GraphQLNamedTypeAnnotation,
     GraphQLNonNullTypeAnnotation, GraphQLScalarTypeDefinition, GraphQLTypeAnnotation,
     GraphQLTypeSystemDefinition, GraphQLTypeSystemDocument, GraphQLTypeSystemExtension,
     GraphQLTypeSystemExtensionDocument, GraphQLTypeSystemExtensionOrDefinition, RootOperationKind,
 };
-use intern::string_key::Intern;
+use intern::{string_key::Intern, Lookup};
+use isograph_config::RootTypeNames;
 use isograph_schema::{
-    CreateAdditionalFieldsError, ExposeAsFieldToInsert, ExposeFieldDirective, FieldMapItem,
-    FieldToInsert, IsographObjectTypeDefinition, ProcessObjectTypeDefinitionOutcome,
+    CacheControl, CreateAdditionalFieldsError, ExposeAsFieldToInsert, ExposeFieldDirective,
+    FieldMapItem, FieldToInsert, IsographObjectTypeDefinition, ProcessObjectTypeDefinitionOutcome,
     ProcessTypeSystemDocumentOutcome, RootTypes, ServerObjectEntity, ServerScalarEntity,
     STRING_JAVASCRIPT_TYPE, TYPENAME_FIELD_NAME,
 };
@@ -25,19 +28,29 @@ use crate::{
 };
 
 lazy_static! {
-    pub static ref QUERY_TYPE: IsographObjectTypeName = "Query".intern().into();
-    static ref MUTATION_TYPE: IsographObjectTypeName = "Mutation".intern().into();
     static ref ID_FIELD_NAME: ServerScalarSelectableName = "id".intern().into();
     // TODO use schema_data.string_type_id or something
     static ref STRING_TYPE_NAME: UnvalidatedTypeName = "String".intern().into();
     static ref NODE_INTERFACE_NAME: GraphQLInterfaceTypeName = "Node".intern().into();
+    static ref DEPRECATED_DIRECTIVE: DirectiveName = "deprecated".intern().into();
+    static ref REASON_ARGUMENT: DirectiveArgumentName = "reason".intern().into();
+    static ref DEFAULT_DEPRECATION_REASON: DescriptionValue = "No longer supported".intern().into();
+    static ref COST_DIRECTIVE: DirectiveName = "cost".intern().into();
+    static ref VALUE_ARGUMENT: DirectiveArgumentName = "value".intern().into();
+    static ref OWNER_DIRECTIVE: DirectiveName = "owner".intern().into();
+    static ref TEAM_ARGUMENT: DirectiveArgumentName = "team".intern().into();
+    static ref SPECIFIED_BY_DIRECTIVE: DirectiveName = "specifiedBy".intern().into();
+    static ref URL_ARGUMENT: DirectiveArgumentName = "url".intern().into();
+    static ref SEMANTIC_NON_NULL_DIRECTIVE: DirectiveName = "semanticNonNull".intern().into();
+    static ref LEVELS_ARGUMENT: DirectiveArgumentName = "levels".intern().into();
     pub static ref REFETCH_FIELD_NAME: SelectableName = "__refetch".intern().into();
 
 }
 
 #[allow(clippy::type_complexity)]
 pub fn process_graphql_type_system_document(
-    type_system_document: GraphQLTypeSystemDocument,
+    type_system_document: &GraphQLTypeSystemDocument,
+    root_type_names: RootTypeNames,
 ) -> ProcessGraphqlTypeDefinitionResult<(
     ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
     HashMap<IsographObjectTypeName, Vec<GraphQLDirective<GraphQLConstantValue>>>,
@@ -51,6 +64,11 @@ pub fn process_graphql_type_system_document(
 
     let mut supertype_to_subtype_map = HashMap::new();
 
+    // Interfaces can implement other interfaces, e.g. interface Resource implements Node.
+    // A type that implements Resource transitively implements Node, so it can be refined
+    // to from both.
+    let super_interfaces = transitive_super_interfaces(type_system_document)?;
+
     let mut processed_root_types = None;
 
     let mut scalars = vec![];
@@ -59,35 +77,50 @@ pub fn process_graphql_type_system_document(
 
     let mut refetch_fields = vec![];
 
-    for with_location in type_system_document.0 {
+    // The document is memoized, so we clone each definition as we process it,
+    // rather than cloning the entire document up front.
+    for with_location in type_system_document.0.iter() {
         let WithLocation {
             location,
             item: type_system_definition,
-        } = with_location;
+        } = with_location.clone();
         match type_system_definition {
             GraphQLTypeSystemDefinition::ObjectTypeDefinition(object_type_definition) => {
                 let concrete_type = Some(object_type_definition.name.item.into());
 
-                for interface_name in object_type_definition.interfaces.iter() {
+                let implemented_interfaces =
+                    implemented_interfaces(&object_type_definition.interfaces, &super_interfaces);
+                for interface_name in implemented_interfaces.iter() {
                     insert_into_type_refinement_map(
-                        interface_name.item.into(),
+                        (*interface_name).into(),
                         object_type_definition.name.item.into(),
                         &mut supertype_to_subtype_map,
                     );
                 }
+                let implements_node = implemented_interfaces.contains(&*NODE_INTERFACE_NAME);
 
                 let object_name = object_type_definition.name.item.unchecked_conversion();
                 let object_type_definition = object_type_definition.into();
 
-                let (object_definition_outcome, new_directives) = process_object_type_definition(
-                    object_type_definition,
-                    concrete_type,
-                    GraphQLSchemaObjectAssociatedData {
-                        original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
-                    },
-                    GraphQLObjectDefinitionType::Object,
-                    &mut refetch_fields,
-                )?;
+                let (mut object_definition_outcome, new_directives) =
+                    process_object_type_definition(
+                        object_type_definition,
+                        concrete_type,
+                        GraphQLSchemaObjectAssociatedData {
+                            original_definition_type: GraphQLSchemaOriginalDefinitionType::Object,
+                        },
+                        GraphQLObjectDefinitionType::Object,
+                        implements_node,
+                        &mut refetch_fields,
+                    )?;
+
+                // Objects that implement Node can be refined to Node, e.g. to pass them
+                // to a client field defined on Node.
+                if implements_node {
+                    object_definition_outcome
+                        .fields_to_insert
+                        .push(as_node_field_to_insert());
+                }
 
                 directives
                     .entry(object_name)
@@ -111,6 +144,7 @@ pub fn process_graphql_type_system_document(
                                 GraphQLSchemaOriginalDefinitionType::Interface,
                         },
                         GraphQLObjectDefinitionType::Interface,
+                        false,
                         &mut refetch_fields,
                     )?;
                 objects.push((process_object_type_definition_outcome, location));
@@ -139,6 +173,7 @@ pub fn process_graphql_type_system_document(
                                 GraphQLSchemaOriginalDefinitionType::InputObject,
                         },
                         GraphQLObjectDefinitionType::InputObject,
+                        false,
                         &mut refetch_fields,
                     )?;
                 objects.push((process_object_type_definition_outcome, location));
@@ -178,6 +213,7 @@ pub fn process_graphql_type_system_document(
                             original_definition_type: GraphQLSchemaOriginalDefinitionType::Union,
                         },
                         GraphQLObjectDefinitionType::Union,
+                        false,
                         &mut refetch_fields,
                     )?;
                 objects.push((process_object_type_definition_outcome, location));
@@ -210,6 +246,28 @@ pub fn process_graphql_type_system_document(
         }
     }
 
+    // The schema definition (e.g. schema { query: QueryRoot }) determines the root types.
+    // Without one, we fall back to the names in the config (Query and Mutation by default.)
+    let (query_type_name, mutation_type_name): (Option<IsographObjectTypeName>, _) =
+        match processed_root_types {
+            Some(root_types) => (
+                root_types.query.map(|name| name.item.into()),
+                root_types.mutation.map(|name| name.item.into()),
+            ),
+            None => (Some(root_type_names.query), Some(root_type_names.mutation)),
+        };
+    for (object_outcome, _) in objects.iter_mut() {
+        let name = Some(object_outcome.server_object_entity.name);
+        object_outcome.encountered_root_kind = if name == query_type_name {
+            Some(RootOperationKind::Query)
+        } else if name == mutation_type_name {
+            Some(RootOperationKind::Mutation)
+        } else {
+            // TODO subscription
+            None
+        };
+    }
+
     // For each supertype (e.g. Node) and a subtype (e.g. Pet), we need to add an asConcreteType field.
     for (supertype_name, subtypes) in supertype_to_subtype_map.iter() {
         if let Some((object_outcome, _)) = objects.iter_mut().find(|obj| {
@@ -234,6 +292,11 @@ pub fn process_graphql_type_system_document(
                             WithSpan::new(*subtype_name, Span::todo_generated()),
                         )),
                         arguments: vec![],
+                        deprecation_reason: None,
+                        cost: None,
+                        owner: None,
+                        cache_control: None,
+                        semantic_non_null_levels: vec![],
                         is_inline_fragment: true,
                     },
                     Location::generated(),
@@ -257,9 +320,41 @@ pub fn process_graphql_type_system_document(
     ))
 }
 
+/// The asNode field of an object that implements Node. Since every such object is a
+/// Node, it is non-null.
+fn as_node_field_to_insert() -> WithLocation<FieldToInsert> {
+    WithLocation::new(
+        FieldToInsert {
+            description: Some(WithSpan::new(
+                "A client pointer for the Node type.".intern().into(),
+                Span::todo_generated(),
+            )),
+            name: WithLocation::new(
+                format!("as{}", *NODE_INTERFACE_NAME).intern().into(),
+                Location::generated(),
+            ),
+            type_: GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::Named(
+                GraphQLNamedTypeAnnotation(WithSpan::new(
+                    (*NODE_INTERFACE_NAME).into(),
+                    Span::todo_generated(),
+                )),
+            ))),
+            arguments: vec![],
+            deprecation_reason: None,
+            cost: None,
+            owner: None,
+            cache_control: None,
+            semantic_non_null_levels: vec![],
+            is_inline_fragment: true,
+        },
+        Location::generated(),
+    )
+}
+
 #[allow(clippy::type_complexity)]
 pub fn process_graphql_type_extension_document(
-    extension_document: GraphQLTypeSystemExtensionDocument,
+    extension_document: &GraphQLTypeSystemExtensionDocument,
+    root_type_names: RootTypeNames,
 ) -> ProcessGraphqlTypeDefinitionResult<(
     ProcessTypeSystemDocumentOutcome<GraphQLNetworkProtocol>,
     HashMap<IsographObjectTypeName, Vec<GraphQLDirective<GraphQLConstantValue>>>,
@@ -268,20 +363,22 @@ pub fn process_graphql_type_extension_document(
     let mut definitions = Vec::with_capacity(extension_document.0.len());
     let mut extensions = Vec::with_capacity(extension_document.0.len());
 
-    for extension_or_definition in extension_document.0 {
+    for extension_or_definition in extension_document.0.iter() {
         let WithLocation { location, item } = extension_or_definition;
         match item {
             GraphQLTypeSystemExtensionOrDefinition::Definition(definition) => {
-                definitions.push(WithLocation::new(definition, location));
+                definitions.push(WithLocation::new(definition.clone(), *location));
             }
             GraphQLTypeSystemExtensionOrDefinition::Extension(extension) => {
-                extensions.push(WithLocation::new(extension, location))
+                extensions.push(WithLocation::new(extension.clone(), *location))
             }
         }
     }
 
-    let (outcome, mut directives, refetch_fields) =
-        process_graphql_type_system_document(GraphQLTypeSystemDocument(definitions))?;
+    let (outcome, mut directives, refetch_fields) = process_graphql_type_system_document(
+        &GraphQLTypeSystemDocument(definitions),
+        root_type_names,
+    )?;
 
     for extension in extensions.into_iter() {
         // TODO collect errors into vec
@@ -314,6 +411,166 @@ pub enum ProcessGraphqlTypeSystemDefinitionError {
         subtype_name: UnvalidatedTypeName,
         supertype_name: UnvalidatedTypeName,
     },
+
+    #[error(
+        "Type {type_name} claims to implement {implemented_type_name}, \
+        but {implemented_type_name} is not an interface."
+    )]
+    ImplementedTypeIsNotAnInterface {
+        type_name: UnvalidatedTypeName,
+        implemented_type_name: UnvalidatedTypeName,
+    },
+
+    #[error("Interface {interface_name} implements itself, through {cycle}.")]
+    InterfaceImplementationCycle {
+        interface_name: GraphQLInterfaceTypeName,
+        cycle: String,
+    },
+
+    #[error(
+        "The directive @{directive_name} is not defined in the schema. \
+        Define it, or disable the strict_schema option."
+    )]
+    UnknownSchemaDirective { directive_name: DirectiveName },
+
+    #[error("The directive @{directive_name} is defined more than once.")]
+    DuplicateDirectiveDefinition { directive_name: DirectiveName },
+
+    #[error(
+        "The directive @{directive_name} is used on a {directive_location}, \
+        but its definition does not allow that location."
+    )]
+    DirectiveNotAllowedAtLocation {
+        directive_name: DirectiveName,
+        directive_location: DirectiveLocation,
+    },
+
+    #[error("The union {union_name} has no members.")]
+    UnionWithoutMembers { union_name: GraphQLUnionTypeName },
+
+    #[error(
+        "The schema does not define a query type. Define a {query_type_name} type, \
+        add a schema definition (e.g. schema {{ query: QueryRoot }}), or set the \
+        query_type_name option."
+    )]
+    MissingQueryType {
+        query_type_name: IsographObjectTypeName,
+    },
+}
+
+impl HasErrorCode for ProcessGraphqlTypeSystemDefinitionError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ProcessGraphqlTypeSystemDefinitionError::DuplicateSchemaDefinition => {
+                error_codes::IS0100
+            }
+            ProcessGraphqlTypeSystemDefinitionError::CreateAdditionalFieldsError(error) => {
+                error.error_code()
+            }
+            ProcessGraphqlTypeSystemDefinitionError::AttemptedToExtendUndefinedType { .. } => {
+                error_codes::IS0101
+            }
+            ProcessGraphqlTypeSystemDefinitionError::AttemptedToImplementNonExistentType {
+                ..
+            } => error_codes::IS0102,
+            ProcessGraphqlTypeSystemDefinitionError::ImplementedTypeIsNotAnInterface { .. } => {
+                error_codes::IS0121
+            }
+            ProcessGraphqlTypeSystemDefinitionError::InterfaceImplementationCycle { .. } => {
+                error_codes::IS0122
+            }
+            ProcessGraphqlTypeSystemDefinitionError::UnknownSchemaDirective { .. } => {
+                error_codes::IS0123
+            }
+            ProcessGraphqlTypeSystemDefinitionError::DuplicateDirectiveDefinition { .. } => {
+                error_codes::IS0124
+            }
+            ProcessGraphqlTypeSystemDefinitionError::DirectiveNotAllowedAtLocation { .. } => {
+                error_codes::IS0125
+            }
+            ProcessGraphqlTypeSystemDefinitionError::UnionWithoutMembers { .. } => {
+                error_codes::IS0126
+            }
+            ProcessGraphqlTypeSystemDefinitionError::MissingQueryType { .. } => error_codes::IS0127,
+        }
+    }
+}
+
+/// If the field has a @deprecated directive, returns its reason (or the default
+/// reason, per the GraphQL spec.)
+fn deprecation_reason(
+    directives: &[GraphQLDirective<GraphQLConstantValue>],
+) -> Option<DescriptionValue> {
+    let directive = directives
+        .iter()
+        .find(|directive| directive.name.item == *DEPRECATED_DIRECTIVE)?;
+    let reason = directive
+        .arguments
+        .iter()
+        .find(|argument| argument.name.item == *REASON_ARGUMENT)
+        .and_then(|argument| argument.value.item.as_string());
+    Some(
+        reason
+            .map(|reason| reason.lookup().intern().into())
+            .unwrap_or(*DEFAULT_DEPRECATION_REASON),
+    )
+}
+
+/// If the field has a @cost(value: Int) directive, returns its value. Values that
+/// are not non-negative integers are ignored.
+fn cost(directives: &[GraphQLDirective<GraphQLConstantValue>]) -> Option<u32> {
+    match directive_argument(directives, *COST_DIRECTIVE, *VALUE_ARGUMENT)? {
+        GraphQLConstantValue::Int(value) => u32::try_from(*value).ok(),
+        _ => None,
+    }
+}
+
+/// If the field has an @owner(team: String) directive, returns the team.
+fn owner(directives: &[GraphQLDirective<GraphQLConstantValue>]) -> Option<StringLiteralValue> {
+    directive_argument(directives, *OWNER_DIRECTIVE, *TEAM_ARGUMENT)?.as_string()
+}
+
+/// If the scalar has a @specifiedBy(url: String!) directive, returns the url.
+fn specified_by_url(
+    directives: &[GraphQLDirective<GraphQLConstantValue>],
+) -> Option<StringLiteralValue> {
+    directive_argument(directives, *SPECIFIED_BY_DIRECTIVE, *URL_ARGUMENT)?.as_string()
+}
+
+/// If the field has a @semanticNonNull(levels: [Int] = [0]) directive, returns its
+/// levels. Levels that are not non-negative integers are ignored.
+fn semantic_non_null_levels(directives: &[GraphQLDirective<GraphQLConstantValue>]) -> Vec<u32> {
+    if !directives
+        .iter()
+        .any(|directive| directive.name.item == *SEMANTIC_NON_NULL_DIRECTIVE)
+    {
+        return vec![];
+    }
+    match directive_argument(directives, *SEMANTIC_NON_NULL_DIRECTIVE, *LEVELS_ARGUMENT) {
+        None => vec![0],
+        Some(GraphQLConstantValue::List(levels)) => levels
+            .iter()
+            .filter_map(|level| match level.item {
+                GraphQLConstantValue::Int(level) => u32::try_from(level).ok(),
+                _ => None,
+            })
+            .collect(),
+        Some(_) => vec![],
+    }
+}
+
+fn directive_argument(
+    directives: &[GraphQLDirective<GraphQLConstantValue>],
+    directive_name: DirectiveName,
+    argument_name: DirectiveArgumentName,
+) -> Option<&GraphQLConstantValue> {
+    directives
+        .iter()
+        .find(|directive| directive.name.item == directive_name)?
+        .arguments
+        .iter()
+        .find(|argument| argument.name.item == argument_name)
+        .map(|argument| &argument.value.item)
 }
 
 fn process_object_type_definition(
@@ -321,16 +578,25 @@ fn process_object_type_definition(
     concrete_type: Option<IsographObjectTypeName>,
     associated_data: GraphQLSchemaObjectAssociatedData,
     type_definition_type: GraphQLObjectDefinitionType,
+    // Whether the type is an object that implements Node, directly or through
+    // another interface.
+    object_implements_node: bool,
     refetch_fields: &mut Vec<ExposeAsFieldToInsert>,
 ) -> ProcessGraphqlTypeDefinitionResult<(
     ProcessObjectTypeDefinitionOutcome<GraphQLNetworkProtocol>,
     Vec<GraphQLDirective<GraphQLConstantValue>>,
 )> {
-    let object_implements_node = implements_node(&object_type_definition);
     let server_object_entity = ServerObjectEntity {
         description: object_type_definition.description.map(|d| d.item),
         name: object_type_definition.name.item,
         concrete_type,
+        is_input_object: matches!(
+            type_definition_type,
+            GraphQLObjectDefinitionType::InputObject
+        ),
+        // The directives are returned separately, and added to the object once
+        // the directives in schema extensions have been collected.
+        directives: vec![],
         output_associated_data: associated_data,
     };
 
@@ -343,6 +609,13 @@ fn process_object_type_definition(
                     description: field_definition.item.description,
                     name: field_definition.item.name,
                     type_: field_definition.item.type_,
+                    deprecation_reason: deprecation_reason(&field_definition.item.directives),
+                    cost: cost(&field_definition.item.directives),
+                    owner: owner(&field_definition.item.directives),
+                    cache_control: CacheControl::from_directives(&field_definition.item.directives),
+                    semantic_non_null_levels: semantic_non_null_levels(
+                        &field_definition.item.directives,
+                    ),
                     arguments: field_definition.item.arguments,
                     is_inline_fragment: field_definition.item.is_inline_fragment,
                 },
@@ -351,7 +624,7 @@ fn process_object_type_definition(
         })
         .collect();
 
-    // We need to define a typename field for objects and interfaces, but not unions or input objects
+    // We need to define a typename field for objects, interfaces and unions, but not input objects
     if type_definition_type.has_typename_field() {
         fields_to_insert.push(WithLocation::new(
             FieldToInsert {
@@ -364,6 +637,11 @@ fn process_object_type_definition(
                     ))),
                 )),
                 arguments: vec![],
+                deprecation_reason: None,
+                cost: None,
+                owner: None,
+                cache_control: None,
+                semantic_non_null_levels: vec![],
                 is_inline_fragment: false,
             },
             Location::generated(),
@@ -394,18 +672,11 @@ fn process_object_type_definition(
         });
     }
 
-    let encountered_root_kind = if object_type_definition.name.item == *QUERY_TYPE {
-        Some(RootOperationKind::Query)
-    } else if object_type_definition.name.item == *MUTATION_TYPE {
-        Some(RootOperationKind::Mutation)
-    } else {
-        // TODO subscription
-        None
-    };
-
     Ok((
         ProcessObjectTypeDefinitionOutcome {
-            encountered_root_kind,
+            // Root types are determined once the entire document has been processed,
+            // since the schema definition may come after them.
+            encountered_root_kind: None,
             server_object_entity,
             fields_to_insert,
             expose_as_fields_to_insert: vec![],
@@ -422,6 +693,7 @@ fn process_scalar_definition(
         description: scalar_type_definition.description,
         name: scalar_type_definition.name,
         javascript_name: *STRING_JAVASCRIPT_TYPE,
+        specified_by_url: specified_by_url(&scalar_type_definition.directives),
         output_format: std::marker::PhantomData,
     }
 }
@@ -454,7 +726,7 @@ impl GraphQLObjectDefinitionType {
     pub fn has_typename_field(&self) -> bool {
         match self {
             GraphQLObjectDefinitionType::InputObject => false,
-            GraphQLObjectDefinitionType::Union => false,
+            GraphQLObjectDefinitionType::Union => true,
             GraphQLObjectDefinitionType::Object => true,
             GraphQLObjectDefinitionType::Interface => true,
         }
@@ -474,9 +746,164 @@ fn insert_into_type_refinement_map(
 
 type UnvalidatedTypeRefinementMap = HashMap<UnvalidatedTypeName, Vec<UnvalidatedTypeName>>;
 
-fn implements_node(object_type_definition: &IsographObjectTypeDefinition) -> bool {
-    object_type_definition
-        .interfaces
-        .iter()
-        .any(|x| x.item == *NODE_INTERFACE_NAME)
+type SuperInterfaceMap = HashMap<GraphQLInterfaceTypeName, Vec<GraphQLInterfaceTypeName>>;
+
+/// The interfaces that a type implements: those that it lists, followed by those
+/// that they (transitively) implement, without duplicates.
+fn implemented_interfaces(
+    interfaces: &[WithLocation<GraphQLInterfaceTypeName>],
+    super_interfaces: &SuperInterfaceMap,
+) -> Vec<GraphQLInterfaceTypeName> {
+    let mut implemented_interfaces = vec![];
+    let transitive_interfaces = interfaces.iter().flat_map(|interface_name| {
+        std::iter::once(interface_name.item).chain(
+            super_interfaces
+                .get(&interface_name.item)
+                .into_iter()
+                .flatten()
+                .copied(),
+        )
+    });
+    for interface_name in transitive_interfaces {
+        if !implemented_interfaces.contains(&interface_name) {
+            implemented_interfaces.push(interface_name);
+        }
+    }
+    implemented_interfaces
+}
+
+/// For each interface in the document, find the interfaces that it implements,
+/// directly or through other interfaces. Returns an error if an interface (or
+/// object) implements a type that is not an interface, or if interfaces implement
+/// each other in a cycle.
+fn transitive_super_interfaces(
+    type_system_document: &GraphQLTypeSystemDocument,
+) -> ProcessGraphqlTypeDefinitionResult<SuperInterfaceMap> {
+    let mut direct_super_interfaces = HashMap::new();
+    let mut non_interface_types = HashSet::<UnvalidatedTypeName>::new();
+    for definition in type_system_document.0.iter() {
+        match &definition.item {
+            GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_definition) => {
+                direct_super_interfaces.insert(
+                    interface_definition.name.item,
+                    interface_definition.interfaces.clone(),
+                );
+            }
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(object_definition) => {
+                non_interface_types.insert(object_definition.name.item.into());
+            }
+            GraphQLTypeSystemDefinition::UnionTypeDefinition(union_definition) => {
+                non_interface_types.insert(union_definition.name.item.into());
+            }
+            GraphQLTypeSystemDefinition::InputObjectTypeDefinition(input_object_definition) => {
+                non_interface_types.insert(input_object_definition.name.item.into());
+            }
+            GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_definition) => {
+                non_interface_types.insert(scalar_definition.name.item.into());
+            }
+            GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
+                non_interface_types.insert(enum_definition.name.item.unchecked_conversion());
+            }
+            GraphQLTypeSystemDefinition::DirectiveDefinition(_)
+            | GraphQLTypeSystemDefinition::SchemaDefinition(_) => {}
+        }
+    }
+
+    for definition in type_system_document.0.iter() {
+        let (type_name, interfaces): (UnvalidatedTypeName, _) = match &definition.item {
+            GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_definition) => (
+                interface_definition.name.item.into(),
+                &interface_definition.interfaces,
+            ),
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(object_definition) => (
+                object_definition.name.item.into(),
+                &object_definition.interfaces,
+            ),
+            _ => continue,
+        };
+        for interface_name in interfaces {
+            if non_interface_types.contains(&interface_name.item.into()) {
+                return Err(WithLocation::new(
+                    ProcessGraphqlTypeSystemDefinitionError::ImplementedTypeIsNotAnInterface {
+                        type_name,
+                        implemented_type_name: interface_name.item.into(),
+                    },
+                    interface_name.location,
+                ));
+            }
+        }
+    }
+
+    let mut super_interfaces = HashMap::new();
+    let mut interface_names = direct_super_interfaces.keys().copied().collect::<Vec<_>>();
+    interface_names.sort_by_key(|interface_name| interface_name.to_string());
+    for interface_name in interface_names {
+        let mut path = vec![];
+        collect_super_interfaces(
+            interface_name,
+            &direct_super_interfaces,
+            &mut path,
+            &mut super_interfaces,
+        )?;
+    }
+    Ok(super_interfaces)
+}
+
+fn collect_super_interfaces(
+    interface_name: GraphQLInterfaceTypeName,
+    direct_super_interfaces: &HashMap<
+        GraphQLInterfaceTypeName,
+        Vec<WithLocation<GraphQLInterfaceTypeName>>,
+    >,
+    path: &mut Vec<GraphQLInterfaceTypeName>,
+    super_interfaces: &mut SuperInterfaceMap,
+) -> ProcessGraphqlTypeDefinitionResult<Vec<GraphQLInterfaceTypeName>> {
+    if let Some(already_collected) = super_interfaces.get(&interface_name) {
+        return Ok(already_collected.clone());
+    }
+
+    let mut collected = vec![];
+    for super_interface in direct_super_interfaces
+        .get(&interface_name)
+        .into_iter()
+        .flatten()
+    {
+        if super_interface.item == interface_name || path.contains(&super_interface.item) {
+            let cycle_start = path
+                .iter()
+                .position(|name| *name == super_interface.item)
+                .unwrap_or(path.len());
+            let cycle = path[cycle_start..]
+                .iter()
+                .chain([&interface_name, &super_interface.item])
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(WithLocation::new(
+                ProcessGraphqlTypeSystemDefinitionError::InterfaceImplementationCycle {
+                    interface_name: super_interface.item,
+                    cycle,
+                },
+                super_interface.location,
+            ));
+        }
+
+        path.push(interface_name);
+        let transitive = collect_super_interfaces(
+            super_interface.item,
+            direct_super_interfaces,
+            path,
+            super_interfaces,
+        )?;
+        path.pop();
+
+        for name in std::iter::once(super_interface.item).chain(transitive) {
+            if !collected.contains(&name) {
+                collected.push(name);
+            }
+        }
+    }
+
+    super_interfaces.insert(interface_name, collected.clone());
+    Ok(collected)
 }
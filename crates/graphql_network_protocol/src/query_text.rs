@@ -1,16 +1,16 @@
 use common_lang_types::{QueryOperationName, QueryText, UnvalidatedTypeName};
 use graphql_lang_types::GraphQLTypeAnnotation;
-use isograph_lang_types::{ArgumentKeyAndValue, NonConstantValue};
+use isograph_lang_types::{ArgumentKeyAndValue, NonConstantValue, SelectionCondition};
 use isograph_schema::{
-    MergedSelectionMap, MergedServerSelection, RootOperationName, ServerScalarOrObjectEntity,
-    ValidatedVariableDefinition,
+    MergedSelectionMap, MergedServerSelection, NetworkProtocol, RootOperationName, Schema,
+    ServerScalarOrObjectEntity, ValidatedVariableDefinition,
 };
 
-use crate::ValidatedGraphqlSchema;
-
-pub(crate) fn generate_query_text<'a>(
+/// Generic over the network protocol, so that protocols that fetch some fields
+/// with GraphQL (e.g. the mixed network protocol) can reuse this.
+pub fn generate_graphql_query_text<'a, TNetworkProtocol: NetworkProtocol>(
     query_name: QueryOperationName,
-    schema: &ValidatedGraphqlSchema,
+    schema: &Schema<TNetworkProtocol>,
     selection_map: &MergedSelectionMap,
     query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
     root_operation_name: &RootOperationName,
@@ -28,8 +28,8 @@ pub(crate) fn generate_query_text<'a>(
     QueryText(query_text)
 }
 
-fn write_variables_to_string<'a>(
-    schema: &ValidatedGraphqlSchema,
+fn write_variables_to_string<'a, TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
     variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
 ) -> String {
     let mut empty = true;
@@ -81,7 +81,8 @@ fn write_selections_for_query_text<'a>(
                 }
                 let name = scalar_field.name;
                 let arguments = get_serialized_arguments_for_query_text(&scalar_field.arguments);
-                query_text.push_str(&format!("{}{},\\\n", name, arguments));
+                let conditions = get_serialized_conditions_for_query_text(&scalar_field.conditions);
+                query_text.push_str(&format!("{}{}{},\\\n", name, arguments, conditions));
             }
             MergedServerSelection::LinkedField(linked_field) => {
                 query_text.push_str(&"  ".repeat(indentation_level as usize).to_string());
@@ -91,7 +92,8 @@ fn write_selections_for_query_text<'a>(
                 }
                 let name = linked_field.name;
                 let arguments = get_serialized_arguments_for_query_text(&linked_field.arguments);
-                query_text.push_str(&format!("{}{} {{\\\n", name, arguments));
+                let conditions = get_serialized_conditions_for_query_text(&linked_field.conditions);
+                query_text.push_str(&format!("{}{}{} {{\\\n", name, arguments, conditions));
                 write_selections_for_query_text(
                     query_text,
                     linked_field.selection_map.values(),
@@ -143,6 +145,19 @@ fn get_serialized_arguments_for_query_text(arguments: &[ArgumentKeyAndValue]) ->
     }
 }
 
+fn get_serialized_conditions_for_query_text(conditions: &[SelectionCondition]) -> String {
+    conditions
+        .iter()
+        .map(|condition| {
+            format!(
+                " @{}(if: {})",
+                condition.kind.directive_name(),
+                serialize_non_constant_value_for_graphql(&condition.value)
+            )
+        })
+        .collect()
+}
+
 fn serialize_non_constant_value_for_graphql(value: &NonConstantValue) -> String {
     match value {
         NonConstantValue::Variable(variable_name) => format!("${}", variable_name),
@@ -153,7 +168,13 @@ fn serialize_non_constant_value_for_graphql(value: &NonConstantValue) -> String
         NonConstantValue::Float(f) => f.as_float().to_string(),
         NonConstantValue::Null => "null".to_string(),
         NonConstantValue::Enum(e) => e.to_string(),
-        NonConstantValue::List(_) => panic!("Lists are not supported here"),
+        NonConstantValue::List(list) => format!(
+            "[{}]",
+            list.iter()
+                .map(|item| serialize_non_constant_value_for_graphql(&item.item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
         NonConstantValue::Object(object) => format!(
             "{{ {} }}",
             object
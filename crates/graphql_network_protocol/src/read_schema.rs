@@ -1,43 +1,29 @@
-use std::{collections::BTreeMap, path::PathBuf, str::Utf8Error};
+use std::{path::PathBuf, str::Utf8Error};
 
-use common_lang_types::{RelativePathToSourceFile, WithLocation};
+use common_lang_types::WithLocation;
 use graphql_lang_types::{GraphQLTypeSystemDocument, GraphQLTypeSystemExtensionDocument};
 use graphql_schema_parser::{parse_schema, parse_schema_extensions, SchemaParseError};
 use isograph_lang_types::SchemaSource;
-use pico::{Database, MemoRef, SourceId};
+use pico::{Database, SourceId};
 use pico_macros::memo;
 use thiserror::Error;
 
-#[allow(clippy::type_complexity)]
+/// The parsed document is returned directly (rather than interned), so that
+/// only one copy of it is kept in the database. For large schemas, the document
+/// is a significant fraction of the compiler's memory usage.
 #[memo]
 pub fn parse_graphql_schema(
     db: &Database,
     schema_source_id: SourceId<SchemaSource>,
-    schema_extension_sources: &BTreeMap<RelativePathToSourceFile, SourceId<SchemaSource>>,
-) -> Result<
-    (
-        MemoRef<GraphQLTypeSystemDocument>,
-        BTreeMap<RelativePathToSourceFile, MemoRef<GraphQLTypeSystemExtensionDocument>>,
-    ),
-    BatchCompileError,
-> {
+) -> Result<GraphQLTypeSystemDocument, BatchCompileError> {
     let SchemaSource {
         content,
         text_source,
         ..
     } = db.get(schema_source_id);
 
-    let schema = parse_schema(content, *text_source)
-        .map_err(|with_span| with_span.to_with_location(*text_source))?;
-
-    let mut schema_extensions = BTreeMap::new();
-    for (relative_path, schema_extension_source_id) in schema_extension_sources.iter() {
-        let extensions_document =
-            parse_schema_extensions_file(db, *schema_extension_source_id).to_owned()?;
-        schema_extensions.insert(*relative_path, extensions_document);
-    }
-
-    Ok((db.intern(schema), schema_extensions))
+    parse_schema(content, *text_source)
+        .map_err(|with_span| with_span.to_with_location(*text_source).into())
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -73,14 +59,13 @@ pub enum BatchCompileError {
 pub fn parse_schema_extensions_file(
     db: &Database,
     schema_extension_source_id: SourceId<SchemaSource>,
-) -> Result<MemoRef<GraphQLTypeSystemExtensionDocument>, BatchCompileError> {
+) -> Result<GraphQLTypeSystemExtensionDocument, BatchCompileError> {
     let SchemaSource {
         content,
         text_source,
         ..
     } = db.get(schema_extension_source_id);
-    let schema_extensions = parse_schema_extensions(content, *text_source)
-        .map_err(|with_span| with_span.to_with_location(*text_source))?;
 
-    Ok(db.intern(schema_extensions))
+    parse_schema_extensions(content, *text_source)
+        .map_err(|with_span| with_span.to_with_location(*text_source).into())
 }
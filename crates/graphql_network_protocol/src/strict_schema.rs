@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+
+use common_lang_types::{DirectiveName, Location, WithLocation};
+use graphql_lang_types::{
+    DirectiveLocation, GraphQLConstantValue, GraphQLDirective, GraphQLDirectiveDefinition,
+    GraphQLFieldDefinition, GraphQLInputValueDefinition, GraphQLTypeSystemDefinition,
+    GraphQLTypeSystemDocument, GraphQLTypeSystemExtension, GraphQLTypeSystemExtensionDocument,
+    GraphQLTypeSystemExtensionOrDefinition,
+};
+use intern::string_key::Intern;
+use isograph_config::CompilerConfigOptions;
+use lazy_static::lazy_static;
+
+use crate::process_type_system_definition::{
+    ProcessGraphqlTypeDefinitionResult, ProcessGraphqlTypeSystemDefinitionError,
+};
+
+lazy_static! {
+    /// Directives that can be used without being defined in the schema, because
+    /// they are part of the GraphQL spec, or because Isograph processes them.
+    static ref KNOWN_DIRECTIVES: HashSet<DirectiveName> = [
+        "deprecated",
+        "specifiedBy",
+        "oneOf",
+        "include",
+        "skip",
+        "exposeField",
+        "cost",
+        "owner",
+        "cacheControl",
+        "semanticNonNull",
+    ]
+    .into_iter()
+    .map(|name| name.intern().into())
+    .collect();
+}
+
+/// With the strict_schema option, validate that every directive used in the schema
+/// is either known to Isograph, or defined in the schema (once) and used in a
+/// location that its definition allows, and that every union has members.
+///
+/// Without this option, unknown directives and directive definitions are ignored,
+/// and unions without members cannot be refined to any type.
+pub(crate) fn validate_strict_schema(
+    type_system_document: &GraphQLTypeSystemDocument,
+    extension_documents: &[&GraphQLTypeSystemExtensionDocument],
+    options: &CompilerConfigOptions,
+) -> ProcessGraphqlTypeDefinitionResult<()> {
+    let definitions = type_system_document
+        .0
+        .iter()
+        .map(|definition| (&definition.item, definition.location))
+        .chain(extension_documents.iter().flat_map(|document| {
+            document.0.iter().filter_map(|item| match &item.item {
+                GraphQLTypeSystemExtensionOrDefinition::Definition(definition) => {
+                    Some((definition, item.location))
+                }
+                GraphQLTypeSystemExtensionOrDefinition::Extension(_) => None,
+            })
+        }))
+        .collect::<Vec<_>>();
+
+    let mut directive_definitions = HashMap::<DirectiveName, &GraphQLDirectiveDefinition>::new();
+    for (definition, _) in &definitions {
+        if let GraphQLTypeSystemDefinition::DirectiveDefinition(directive_definition) = definition {
+            if directive_definitions
+                .insert(directive_definition.name.item, directive_definition)
+                .is_some()
+            {
+                return Err(WithLocation::new(
+                    ProcessGraphqlTypeSystemDefinitionError::DuplicateDirectiveDefinition {
+                        directive_name: directive_definition.name.item,
+                    },
+                    directive_definition.name.location,
+                ));
+            }
+        }
+    }
+
+    let validator = DirectiveValidator {
+        directive_definitions,
+        propagated_object_directives: &options.propagated_object_directives,
+    };
+
+    for (definition, location) in definitions {
+        match definition {
+            GraphQLTypeSystemDefinition::ObjectTypeDefinition(object_type_definition) => {
+                validator.validate(
+                    &object_type_definition.directives,
+                    DirectiveLocation::Object,
+                )?;
+                validator.validate_fields(&object_type_definition.fields)?;
+            }
+            GraphQLTypeSystemDefinition::InterfaceTypeDefinition(interface_type_definition) => {
+                validator.validate(
+                    &interface_type_definition.directives,
+                    DirectiveLocation::Interface,
+                )?;
+                validator.validate_fields(&interface_type_definition.fields)?;
+            }
+            GraphQLTypeSystemDefinition::InputObjectTypeDefinition(
+                input_object_type_definition,
+            ) => {
+                validator.validate(
+                    &input_object_type_definition.directives,
+                    DirectiveLocation::InputObject,
+                )?;
+                validator.validate_input_values(
+                    &input_object_type_definition.fields,
+                    DirectiveLocation::InputFieldDefinition,
+                )?;
+            }
+            GraphQLTypeSystemDefinition::ScalarTypeDefinition(scalar_type_definition) => {
+                validator.validate(
+                    &scalar_type_definition.directives,
+                    DirectiveLocation::Scalar,
+                )?;
+            }
+            GraphQLTypeSystemDefinition::EnumDefinition(enum_definition) => {
+                validator.validate(&enum_definition.directives, DirectiveLocation::Enum)?;
+                for enum_value_definition in &enum_definition.enum_value_definitions {
+                    validator.validate(
+                        &enum_value_definition.item.directives,
+                        DirectiveLocation::EnumValue,
+                    )?;
+                }
+            }
+            GraphQLTypeSystemDefinition::UnionTypeDefinition(union_definition) => {
+                validator.validate(&union_definition.directives, DirectiveLocation::Union)?;
+                if union_definition.union_member_types.is_empty() {
+                    return Err(WithLocation::new(
+                        ProcessGraphqlTypeSystemDefinitionError::UnionWithoutMembers {
+                            union_name: union_definition.name.item,
+                        },
+                        location,
+                    ));
+                }
+            }
+            GraphQLTypeSystemDefinition::SchemaDefinition(schema_definition) => {
+                validator.validate(&schema_definition.directives, DirectiveLocation::Schema)?;
+            }
+            GraphQLTypeSystemDefinition::DirectiveDefinition(directive_definition) => {
+                validator.validate_input_values(
+                    &directive_definition.arguments,
+                    DirectiveLocation::ArgumentDefinition,
+                )?;
+            }
+        }
+    }
+
+    for extension_document in extension_documents {
+        for item in &extension_document.0 {
+            if let GraphQLTypeSystemExtensionOrDefinition::Extension(
+                GraphQLTypeSystemExtension::ObjectTypeExtension(object_type_extension),
+            ) = &item.item
+            {
+                validator.validate(&object_type_extension.directives, DirectiveLocation::Object)?;
+                validator.validate_fields(&object_type_extension.fields)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+struct DirectiveValidator<'a> {
+    directive_definitions: HashMap<DirectiveName, &'a GraphQLDirectiveDefinition>,
+    propagated_object_directives: &'a [DirectiveName],
+}
+
+impl DirectiveValidator<'_> {
+    fn validate(
+        &self,
+        directives: &[GraphQLDirective<GraphQLConstantValue>],
+        directive_location: DirectiveLocation,
+    ) -> ProcessGraphqlTypeDefinitionResult<()> {
+        for directive in directives {
+            let directive_name = directive.name.item;
+            let location = Location::Embedded(directive.name.location);
+            match self.directive_definitions.get(&directive_name) {
+                Some(directive_definition) => {
+                    if !directive_definition
+                        .locations
+                        .iter()
+                        .any(|allowed_location| allowed_location.item == directive_location)
+                    {
+                        return Err(WithLocation::new(
+                            ProcessGraphqlTypeSystemDefinitionError::DirectiveNotAllowedAtLocation {
+                                directive_name,
+                                directive_location,
+                            },
+                            location,
+                        ));
+                    }
+                }
+                None => {
+                    if !KNOWN_DIRECTIVES.contains(&directive_name)
+                        && !self.propagated_object_directives.contains(&directive_name)
+                    {
+                        return Err(WithLocation::new(
+                            ProcessGraphqlTypeSystemDefinitionError::UnknownSchemaDirective {
+                                directive_name,
+                            },
+                            location,
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_fields(
+        &self,
+        fields: &[WithLocation<GraphQLFieldDefinition>],
+    ) -> ProcessGraphqlTypeDefinitionResult<()> {
+        for field in fields {
+            self.validate(&field.item.directives, DirectiveLocation::FieldDefinition)?;
+            self.validate_input_values(
+                &field.item.arguments,
+                DirectiveLocation::ArgumentDefinition,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn validate_input_values(
+        &self,
+        input_values: &[WithLocation<GraphQLInputValueDefinition>],
+        directive_location: DirectiveLocation,
+    ) -> ProcessGraphqlTypeDefinitionResult<()> {
+        for input_value in input_values {
+            self.validate(&input_value.item.directives, directive_location)?;
+        }
+        Ok(())
+    }
+}
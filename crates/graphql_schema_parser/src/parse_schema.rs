@@ -413,11 +413,13 @@ fn parse_union_definition(
 
     let directives = parse_constant_directives(tokens, text_source)?;
 
-    let _equal = tokens
-        .parse_token_of_kind(TokenKind::Equals)
-        .map_err(|with_span| with_span.map(SchemaParseError::from))?;
-
-    let union_member_types = parse_union_member_types(tokens, text_source)?;
+    // Per the spec, the member types are optional, e.g. for a union whose members
+    // are added in a type extension.
+    let union_member_types = if tokens.parse_token_of_kind(TokenKind::Equals).is_ok() {
+        parse_union_member_types(tokens, text_source)?
+    } else {
+        vec![]
+    };
 
     Ok(GraphQLUnionTypeDefinition {
         description,
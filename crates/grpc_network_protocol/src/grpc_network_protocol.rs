@@ -0,0 +1,92 @@
+use std::{collections::BTreeMap, error::Error};
+
+use common_lang_types::{
+    CodedError, Location, QueryOperationName, QueryText, RelativePathToSourceFile, SelectableName,
+    WithLocation,
+};
+use isograph_config::CompilerConfigOptions;
+use isograph_lang_types::SchemaSource;
+use isograph_schema::{
+    MergedSelectionMap, NetworkProtocol, ProcessTypeSystemDocumentOutcome, RootOperationName,
+    Schema, ValidatedVariableDefinition,
+};
+use pico::{Database, SourceId};
+
+use crate::{
+    parse_proto::parse_proto_file,
+    process_proto_files::{process_proto_files, ProtoSource},
+    request_text::generate_request_text,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
+pub struct GrpcNetworkProtocol {}
+
+impl NetworkProtocol for GrpcNetworkProtocol {
+    type Sources = (
+        SourceId<SchemaSource>,
+        BTreeMap<RelativePathToSourceFile, SourceId<SchemaSource>>,
+    );
+
+    type SchemaObjectAssociatedData = GrpcSchemaObjectAssociatedData;
+
+    // Schema extensions are additional proto files. Types can be referenced across
+    // files, so (unlike with protoc) imports are not needed and are ignored.
+    fn parse_and_process_type_system_documents(
+        db: &Database,
+        sources: &Self::Sources,
+        _options: &CompilerConfigOptions,
+    ) -> Result<ProcessTypeSystemDocumentOutcome<GrpcNetworkProtocol>, Box<dyn Error>> {
+        let (schema_source_id, schema_extension_sources) = sources;
+
+        let sources = std::iter::once(schema_source_id)
+            .chain(schema_extension_sources.values())
+            .map(|source_id| {
+                let SchemaSource {
+                    content,
+                    text_source,
+                    ..
+                } = db.get(*source_id);
+                let file = parse_proto_file(content).map_err(|error| {
+                    WithLocation::new(
+                        CodedError::new(error.item),
+                        Location::new(*text_source, error.span),
+                    )
+                })?;
+                Ok(ProtoSource {
+                    file,
+                    text_source: *text_source,
+                })
+            })
+            .collect::<Result<Vec<_>, WithLocation<CodedError>>>()?;
+
+        Ok(process_proto_files(&sources).map_err(|error| error.map(CodedError::new))?)
+    }
+
+    // The "query text" of a gRPC entrypoint is a JSON description of the methods to
+    // call, which is interpreted by the network function (e.g.
+    // createGrpcNetworkFunction in @isograph/react.)
+    fn generate_query_text<'a>(
+        _query_name: QueryOperationName,
+        schema: &Schema<Self>,
+        selection_map: &MergedSelectionMap,
+        _query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
+        root_operation_name: &RootOperationName,
+    ) -> QueryText {
+        generate_request_text(schema, selection_map, root_operation_name)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GrpcSchemaObjectAssociatedData {
+    /// For the Query and Mutation types, the method that each field corresponds to.
+    /// Empty for all other types.
+    pub methods: BTreeMap<SelectableName, GrpcMethod>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GrpcMethod {
+    /// The fully qualified name of the service, e.g. `pets.PetService`
+    pub service: String,
+    /// The name of the method, e.g. `GetPet`
+    pub method: String,
+}
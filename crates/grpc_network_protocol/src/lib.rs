@@ -0,0 +1,9 @@
+mod grpc_network_protocol;
+mod parse_proto;
+mod process_proto_files;
+mod proto;
+mod request_text;
+
+pub use grpc_network_protocol::*;
+pub use process_proto_files::ProcessProtoFileError;
+pub use request_text::generate_grpc_requests;
@@ -0,0 +1,694 @@
+use common_lang_types::{Span, WithSpan};
+
+use crate::{
+    proto::{
+        ProtoEnum, ProtoField, ProtoFieldLabel, ProtoFile, ProtoMessage, ProtoMethod, ProtoService,
+    },
+    ProcessProtoFileError,
+};
+
+type ParseResult<T> = Result<T, WithSpan<ProcessProtoFileError>>;
+
+/// Parse a proto file. This is not a validating parser: it accepts some invalid
+/// files, and skips over constructs that do not affect the schema.
+pub(crate) fn parse_proto_file(source: &str) -> ParseResult<ProtoFile> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        source,
+        tokens,
+        index: 0,
+    };
+    parser.parse_file()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    /// Identifiers, including fully qualified names such as `.pets.Pet`
+    Identifier,
+    Number,
+    String,
+    Symbol(char),
+    EndOfFile,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+    /// The comments immediately preceding this token.
+    comment: Option<String>,
+}
+
+fn tokenize(source: &str) -> ParseResult<Vec<Token>> {
+    let bytes = source.as_bytes();
+    let mut tokens = vec![];
+    let mut comment_lines: Vec<String> = vec![];
+    let mut consecutive_newlines = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+        let char = bytes[index];
+        if char.is_ascii_whitespace() {
+            if char == b'\n' {
+                consecutive_newlines += 1;
+                // A blank line separates a comment from the following declaration.
+                if consecutive_newlines > 1 {
+                    comment_lines.clear();
+                }
+            }
+            index += 1;
+            continue;
+        }
+        consecutive_newlines = 0;
+
+        if source[index..].starts_with("//") {
+            let end = source[index..]
+                .find('\n')
+                .map_or(source.len(), |offset| index + offset);
+            comment_lines.push(source[index + 2..end].trim().to_string());
+            index = end;
+            continue;
+        }
+
+        if source[index..].starts_with("/*") {
+            let end = source[index + 2..]
+                .find("*/")
+                .map(|offset| index + 2 + offset)
+                .ok_or_else(|| {
+                    invalid_proto_file(
+                        "Unterminated comment.",
+                        Span::new(start as u32, start as u32 + 2),
+                    )
+                })?;
+            comment_lines.extend(
+                source[index + 2..end]
+                    .lines()
+                    .map(|line| line.trim().trim_start_matches('*').trim().to_string())
+                    .filter(|line| !line.is_empty()),
+            );
+            index = end + 2;
+            continue;
+        }
+
+        let kind = if char.is_ascii_alphabetic() || char == b'_' || char == b'.' {
+            index += 1;
+            while index < bytes.len()
+                && (bytes[index].is_ascii_alphanumeric()
+                    || bytes[index] == b'_'
+                    || bytes[index] == b'.')
+            {
+                index += 1;
+            }
+            TokenKind::Identifier
+        } else if char.is_ascii_digit()
+            || (char == b'-' && bytes.get(index + 1).is_some_and(u8::is_ascii_digit))
+        {
+            index += 1;
+            while index < bytes.len()
+                && (bytes[index].is_ascii_alphanumeric() || bytes[index] == b'.')
+            {
+                index += 1;
+            }
+            TokenKind::Number
+        } else if char == b'"' || char == b'\'' {
+            index += 1;
+            loop {
+                match bytes.get(index) {
+                    None | Some(b'\n') => {
+                        return Err(invalid_proto_file(
+                            "Unterminated string.",
+                            Span::new(start as u32, index as u32),
+                        ))
+                    }
+                    Some(b'\\') => index += 2,
+                    Some(quote) if *quote == char => {
+                        index += 1;
+                        break;
+                    }
+                    Some(_) => index += 1,
+                }
+            }
+            TokenKind::String
+        } else {
+            // Advance by an entire (possibly multi-byte) character.
+            let symbol = source[index..]
+                .chars()
+                .next()
+                .expect("Expected index to be within the source");
+            index += symbol.len_utf8();
+            TokenKind::Symbol(symbol)
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span::new(start as u32, index as u32),
+            comment: (!comment_lines.is_empty()).then(|| comment_lines.join("\n")),
+        });
+        comment_lines.clear();
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::EndOfFile,
+        span: Span::new(source.len() as u32, source.len() as u32),
+        comment: None,
+    });
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser<'_> {
+    fn parse_file(&mut self) -> ParseResult<ProtoFile> {
+        let mut file = ProtoFile::default();
+        loop {
+            let token = self.peek().clone();
+            match token.kind {
+                TokenKind::EndOfFile => return Ok(file),
+                TokenKind::Symbol(';') => {
+                    self.advance();
+                }
+                TokenKind::Identifier => match self.text(&token) {
+                    "package" => {
+                        self.advance();
+                        file.package = Some(self.parse_identifier()?.item);
+                        self.parse_symbol(';')?;
+                    }
+                    "message" => file.messages.push(self.parse_message()?),
+                    "enum" => file.enums.push(self.parse_enum()?),
+                    "service" => file.services.push(self.parse_service()?),
+                    "syntax" | "edition" | "import" | "option" | "extend" => {
+                        self.skip_statement()?
+                    }
+                    _ => return Err(self.unexpected_token(&token)),
+                },
+                _ => return Err(self.unexpected_token(&token)),
+            }
+        }
+    }
+
+    fn parse_message(&mut self) -> ParseResult<ProtoMessage> {
+        let description = self.advance().comment;
+        let name = self.parse_identifier()?;
+        self.parse_symbol('{')?;
+
+        let mut message = ProtoMessage {
+            name: name.item,
+            span: name.span,
+            description,
+            fields: vec![],
+            messages: vec![],
+            enums: vec![],
+        };
+        loop {
+            let token = self.peek().clone();
+            match token.kind {
+                TokenKind::Symbol('}') => {
+                    self.advance();
+                    return Ok(message);
+                }
+                TokenKind::Symbol(';') => {
+                    self.advance();
+                }
+                TokenKind::Identifier => match self.text(&token) {
+                    "message" => message.messages.push(self.parse_message()?),
+                    "enum" => message.enums.push(self.parse_enum()?),
+                    "oneof" => {
+                        self.advance();
+                        self.parse_identifier()?;
+                        self.parse_symbol('{')?;
+                        while !self.next_is_symbol('}') {
+                            if self.next_is_identifier("option") {
+                                self.skip_statement()?;
+                            } else if let Some(field) = self.parse_field()? {
+                                message.fields.push(ProtoField {
+                                    label: ProtoFieldLabel::Optional,
+                                    ..field
+                                });
+                            }
+                        }
+                        self.advance();
+                    }
+                    "option" | "reserved" | "extensions" | "extend" => self.skip_statement()?,
+                    _ => {
+                        if let Some(field) = self.parse_field()? {
+                            message.fields.push(field);
+                        }
+                    }
+                },
+                _ => return Err(self.unexpected_token(&token)),
+            }
+        }
+    }
+
+    /// Parse a field, e.g. `repeated string tags = 3 [deprecated = true];`. Returns
+    /// None for map fields and groups, which are not supported.
+    fn parse_field(&mut self) -> ParseResult<Option<ProtoField>> {
+        let description = self.peek().comment.clone();
+        let label = if self.next_is_identifier("repeated") {
+            self.advance();
+            ProtoFieldLabel::Repeated
+        } else if self.next_is_identifier("optional") || self.next_is_identifier("required") {
+            self.advance();
+            ProtoFieldLabel::Optional
+        } else {
+            ProtoFieldLabel::None
+        };
+
+        if self.next_is_identifier("group")
+            || (self.next_is_identifier("map") && self.peek_nth(1).kind == TokenKind::Symbol('<'))
+        {
+            self.skip_statement()?;
+            return Ok(None);
+        }
+
+        let type_name = self.parse_identifier()?.item;
+        let name = self.parse_identifier()?;
+        self.parse_symbol('=')?;
+        self.parse_number()?;
+
+        let mut json_name = lower_camel_case(&name.item);
+        let mut deprecated = false;
+        if self.next_is_symbol('[') {
+            for (option_name, value) in self.parse_options_list()? {
+                match option_name.as_str() {
+                    "deprecated" => deprecated = value == "true",
+                    "json_name" => json_name = unquote(&value),
+                    _ => {}
+                }
+            }
+        }
+        self.parse_symbol(';')?;
+
+        Ok(Some(ProtoField {
+            json_name,
+            span: name.span,
+            description,
+            type_name,
+            label,
+            deprecated,
+        }))
+    }
+
+    fn parse_enum(&mut self) -> ParseResult<ProtoEnum> {
+        let description = self.advance().comment;
+        let name = self.parse_identifier()?;
+        self.parse_symbol('{')?;
+        let mut values = vec![];
+        while !self.next_is_symbol('}') {
+            if self.next_is_identifier("option") || self.next_is_identifier("reserved") {
+                self.skip_statement()?;
+            } else if self.next_is_symbol(';') {
+                self.advance();
+            } else {
+                values.push(self.parse_identifier()?.item);
+                self.skip_statement()?;
+            }
+        }
+        self.advance();
+        Ok(ProtoEnum {
+            name: name.item,
+            span: name.span,
+            description,
+            values,
+        })
+    }
+
+    fn parse_service(&mut self) -> ParseResult<ProtoService> {
+        self.advance();
+        let name = self.parse_identifier()?.item;
+        self.parse_symbol('{')?;
+        let mut methods = vec![];
+        loop {
+            let token = self.peek().clone();
+            match token.kind {
+                TokenKind::Symbol('}') => {
+                    self.advance();
+                    return Ok(ProtoService { name, methods });
+                }
+                TokenKind::Symbol(';') => {
+                    self.advance();
+                }
+                TokenKind::Identifier if self.text(&token) == "rpc" => {
+                    methods.push(self.parse_method()?)
+                }
+                TokenKind::Identifier if self.text(&token) == "option" => self.skip_statement()?,
+                _ => return Err(self.unexpected_token(&token)),
+            }
+        }
+    }
+
+    /// Parse a method, e.g. `rpc GetPet(GetPetRequest) returns (Pet);`
+    fn parse_method(&mut self) -> ParseResult<ProtoMethod> {
+        let description = self.advance().comment;
+        let name = self.parse_identifier()?;
+
+        self.parse_symbol('(')?;
+        let is_request_streaming = self.parse_stream_keyword();
+        let request_type_name = self.parse_identifier()?.item;
+        self.parse_symbol(')')?;
+        self.parse_keyword("returns")?;
+        self.parse_symbol('(')?;
+        let is_response_streaming = self.parse_stream_keyword();
+        let response_type_name = self.parse_identifier()?.item;
+        self.parse_symbol(')')?;
+
+        let mut has_no_side_effects = false;
+        let mut deprecated = false;
+        if self.next_is_symbol('{') {
+            self.advance();
+            while !self.next_is_symbol('}') {
+                if self.next_is_symbol(';') {
+                    self.advance();
+                    continue;
+                }
+                self.parse_keyword("option")?;
+                let option_name = self.parse_identifier()?.item;
+                self.parse_symbol('=')?;
+                let value = self.parse_value()?;
+                self.parse_symbol(';')?;
+                match option_name.as_str() {
+                    "idempotency_level" => has_no_side_effects = value == "NO_SIDE_EFFECTS",
+                    "deprecated" => deprecated = value == "true",
+                    _ => {}
+                }
+            }
+            self.advance();
+        } else {
+            self.parse_symbol(';')?;
+        }
+
+        Ok(ProtoMethod {
+            name: name.item,
+            span: name.span,
+            description,
+            request_type_name,
+            response_type_name,
+            is_streaming: is_request_streaming || is_response_streaming,
+            has_no_side_effects,
+            deprecated,
+        })
+    }
+
+    fn parse_stream_keyword(&mut self) -> bool {
+        // `stream` can also be the name of a message, e.g. `rpc Foo(stream)`.
+        let is_stream =
+            self.next_is_identifier("stream") && self.peek_nth(1).kind == TokenKind::Identifier;
+        if is_stream {
+            self.advance();
+        }
+        is_stream
+    }
+
+    /// Parse a list of options, e.g. `[deprecated = true, json_name = "petId"]`.
+    fn parse_options_list(&mut self) -> ParseResult<Vec<(String, String)>> {
+        self.parse_symbol('[')?;
+        let mut options = vec![];
+        loop {
+            let option_name = if self.next_is_symbol('(') {
+                // A custom option, e.g. `(validate.rules).string.min_len = 1`
+                self.skip_until(|kind| kind == TokenKind::Symbol('='))?;
+                String::new()
+            } else {
+                self.parse_identifier()?.item
+            };
+            self.parse_symbol('=')?;
+            options.push((option_name, self.parse_value()?));
+            if self.next_is_symbol(',') {
+                self.advance();
+            } else {
+                self.parse_symbol(']')?;
+                return Ok(options);
+            }
+        }
+    }
+
+    /// Parse a constant. Aggregate values (e.g. `{ min: 1 }`) are skipped.
+    fn parse_value(&mut self) -> ParseResult<String> {
+        let token = self.peek().clone();
+        match token.kind {
+            TokenKind::Identifier | TokenKind::Number | TokenKind::String => {
+                self.advance();
+                Ok(self.text(&token).to_string())
+            }
+            TokenKind::Symbol('{') => {
+                self.skip_block()?;
+                Ok(String::new())
+            }
+            _ => Err(self.unexpected_token(&token)),
+        }
+    }
+
+    /// Skip a statement, i.e. everything until the next semicolon, or until the
+    /// end of the next block.
+    fn skip_statement(&mut self) -> ParseResult<()> {
+        loop {
+            let token = self.peek().clone();
+            match token.kind {
+                TokenKind::Symbol(';') => {
+                    self.advance();
+                    return Ok(());
+                }
+                TokenKind::Symbol('{') => {
+                    self.skip_block()?;
+                    // e.g. `option (foo) = { bar: 1 };`
+                    if self.next_is_symbol(';') {
+                        self.advance();
+                    }
+                    return Ok(());
+                }
+                TokenKind::Symbol('}') | TokenKind::EndOfFile => {
+                    return Err(self.unexpected_token(&token))
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    fn skip_block(&mut self) -> ParseResult<()> {
+        self.parse_symbol('{')?;
+        let mut depth = 1;
+        while depth > 0 {
+            let token = self.advance();
+            match token.kind {
+                TokenKind::Symbol('{') => depth += 1,
+                TokenKind::Symbol('}') => depth -= 1,
+                TokenKind::EndOfFile => return Err(self.unexpected_token(&token)),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_until(&mut self, is_end: impl Fn(TokenKind) -> bool) -> ParseResult<()> {
+        while !is_end(self.peek().kind) {
+            let token = self.advance();
+            if token.kind == TokenKind::EndOfFile {
+                return Err(self.unexpected_token(&token));
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_identifier(&mut self) -> ParseResult<WithSpan<String>> {
+        let token = self.peek().clone();
+        if token.kind != TokenKind::Identifier {
+            return Err(self.unexpected_token(&token));
+        }
+        self.advance();
+        Ok(WithSpan::new(self.text(&token).to_string(), token.span))
+    }
+
+    fn parse_keyword(&mut self, keyword: &str) -> ParseResult<()> {
+        if self.next_is_identifier(keyword) {
+            self.advance();
+            Ok(())
+        } else {
+            let token = self.peek().clone();
+            Err(invalid_proto_file(
+                &format!("Expected `{keyword}`, found `{}`.", self.text(&token)),
+                token.span,
+            ))
+        }
+    }
+
+    fn parse_number(&mut self) -> ParseResult<()> {
+        let token = self.peek().clone();
+        if token.kind != TokenKind::Number {
+            return Err(self.unexpected_token(&token));
+        }
+        self.advance();
+        Ok(())
+    }
+
+    fn parse_symbol(&mut self, symbol: char) -> ParseResult<()> {
+        if self.next_is_symbol(symbol) {
+            self.advance();
+            Ok(())
+        } else {
+            let token = self.peek().clone();
+            Err(invalid_proto_file(
+                &format!("Expected `{symbol}`, found `{}`.", self.text(&token)),
+                token.span,
+            ))
+        }
+    }
+
+    fn next_is_symbol(&self, symbol: char) -> bool {
+        self.peek().kind == TokenKind::Symbol(symbol)
+    }
+
+    fn next_is_identifier(&self, identifier: &str) -> bool {
+        let token = self.peek();
+        token.kind == TokenKind::Identifier && self.text(token) == identifier
+    }
+
+    fn peek(&self) -> &Token {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        self.tokens
+            .get(self.index + n)
+            .unwrap_or_else(|| self.tokens.last().expect("Expected tokens to end with EOF"))
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if token.kind != TokenKind::EndOfFile {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn text(&self, token: &Token) -> &str {
+        match token.kind {
+            TokenKind::EndOfFile => "end of file",
+            _ => &self.source[token.span.as_usize_range()],
+        }
+    }
+
+    fn unexpected_token(&self, token: &Token) -> WithSpan<ProcessProtoFileError> {
+        invalid_proto_file(&format!("Unexpected `{}`.", self.text(token)), token.span)
+    }
+}
+
+fn invalid_proto_file(message: &str, span: Span) -> WithSpan<ProcessProtoFileError> {
+    WithSpan::new(
+        ProcessProtoFileError::InvalidProtoFile {
+            message: message.to_string(),
+        },
+        span,
+    )
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .trim_matches(|char| char == '"' || char == '\'')
+        .to_string()
+}
+
+/// The default JSON name of a field, e.g. `pet_id` becomes `petId`.
+fn lower_camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut capitalize_next = false;
+    for char in name.chars() {
+        if char == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            result.extend(char.to_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(char);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_messages_and_services() {
+        let file = parse_proto_file(
+            r#"
+            syntax = "proto3";
+            package pets;
+            import "google/protobuf/empty.proto";
+
+            // A pet.
+            message Pet {
+              string pet_id = 1 [json_name = "id"];
+              repeated string tags = 2;
+              map<string, string> labels = 3;
+              oneof owner {
+                Person person = 4;
+              }
+              message Person { string name = 1; }
+              reserved 5 to 10;
+            }
+
+            enum Species { SPECIES_UNSPECIFIED = 0; DOG = 1 [deprecated = true]; }
+
+            service PetService {
+              /* Get a pet. */
+              rpc GetPet(GetPetRequest) returns (Pet) {
+                option idempotency_level = NO_SIDE_EFFECTS;
+              }
+              rpc WatchPets(WatchPetsRequest) returns (stream Pet);
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(file.package.as_deref(), Some("pets"));
+        let pet = &file.messages[0];
+        assert_eq!(pet.description.as_deref(), Some("A pet."));
+        let fields = pet
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.json_name.as_str(),
+                    field.type_name.as_str(),
+                    field.label,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("id", "string", ProtoFieldLabel::None),
+                ("tags", "string", ProtoFieldLabel::Repeated),
+                ("person", "Person", ProtoFieldLabel::Optional),
+            ]
+        );
+        assert_eq!(pet.messages[0].name, "Person");
+        assert_eq!(file.enums[0].values, ["SPECIES_UNSPECIFIED", "DOG"]);
+
+        let methods = &file.services[0].methods;
+        assert_eq!(methods[0].description.as_deref(), Some("Get a pet."));
+        assert!(methods[0].has_no_side_effects);
+        assert!(!methods[0].is_streaming);
+        assert!(methods[1].is_streaming);
+    }
+
+    #[test]
+    fn reports_unexpected_tokens() {
+        let error = parse_proto_file("message Pet { string name = ; }").unwrap_err();
+        assert_eq!(
+            error.item,
+            ProcessProtoFileError::InvalidProtoFile {
+                message: "Unexpected `;`.".to_string()
+            }
+        );
+        assert_eq!(error.span, Span::new(28, 29));
+    }
+}
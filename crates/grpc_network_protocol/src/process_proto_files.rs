@@ -0,0 +1,603 @@
+use std::collections::{BTreeMap, HashMap};
+
+use common_lang_types::{
+    error_codes, DescriptionValue, ErrorCode, HasErrorCode, IsographObjectTypeName, Location,
+    SelectableName, Span, TextSource, UnvalidatedTypeName, WithLocation, WithSpan,
+};
+use graphql_lang_types::{
+    GraphQLInputValueDefinition, GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation,
+    GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation, RootOperationKind,
+};
+use intern::string_key::Intern;
+use isograph_schema::{
+    FieldToInsert, ProcessObjectTypeDefinitionOutcome, ProcessTypeSystemDocumentOutcome,
+    ServerObjectEntity, ServerScalarEntity,
+};
+use lazy_static::lazy_static;
+use thiserror::Error;
+
+use crate::{
+    proto::{ProtoEnum, ProtoField, ProtoFieldLabel, ProtoFile, ProtoMessage, ProtoMethod},
+    GrpcMethod, GrpcNetworkProtocol, GrpcSchemaObjectAssociatedData,
+};
+
+lazy_static! {
+    static ref QUERY_TYPE: IsographObjectTypeName = "Query".intern().into();
+    static ref MUTATION_TYPE: IsographObjectTypeName = "Mutation".intern().into();
+    static ref DEFAULT_DEPRECATION_REASON: DescriptionValue = "No longer supported".intern().into();
+}
+
+/// Methods whose names start with one of these prefixes (i.e. the standard methods
+/// described in https://google.aip.dev/131 and https://google.aip.dev/132) are
+/// assumed to have no side effects.
+const QUERY_METHOD_PREFIXES: [&str; 2] = ["Get", "List"];
+
+/// A parsed proto file, along with its text source (which is used to locate errors.)
+pub(crate) struct ProtoSource {
+    pub file: ProtoFile,
+    pub text_source: TextSource,
+}
+
+/// Convert proto files (i.e. the schema and schema extensions) into objects and
+/// scalars. Messages become objects, enums become scalars, and unary methods become
+/// fields on the Query type (if they have no side effects) or the Mutation type.
+pub(crate) fn process_proto_files(
+    sources: &[ProtoSource],
+) -> ProcessProtoFileResult<ProcessTypeSystemDocumentOutcome<GrpcNetworkProtocol>> {
+    let mut processor = ProtoProcessor::default();
+
+    // Fields can refer to types defined later, or in other files, so we collect
+    // all types before processing any of them.
+    for source in sources {
+        processor.collect_types(
+            &source.file.messages,
+            &source.file.enums,
+            source.file.package.as_deref().unwrap_or(""),
+            "",
+        );
+    }
+
+    for source in sources {
+        let package = source.file.package.as_deref().unwrap_or("");
+        processor.process_messages_and_enums(
+            &source.file.messages,
+            &source.file.enums,
+            package,
+            "",
+            source.text_source,
+        );
+        for service in &source.file.services {
+            let service_name = qualify(package, &service.name);
+            for method in &service.methods {
+                // Streamed requests and responses cannot be fetched by an entrypoint.
+                if method.is_streaming {
+                    continue;
+                }
+                processor.process_method(&service_name, method, package, source.text_source)?;
+            }
+        }
+    }
+
+    Ok(processor.finish())
+}
+
+/// A message or enum, keyed by its fully qualified name (e.g. `pets.Pet.Owner`) in
+/// ProtoProcessor::types.
+struct ProtoType<'a> {
+    /// The name of the corresponding Isograph type, e.g. `PetOwner`.
+    isograph_name: String,
+    /// None for enums
+    message: Option<&'a ProtoMessage>,
+}
+
+#[derive(Default)]
+struct ProtoProcessor<'a> {
+    types: HashMap<String, ProtoType<'a>>,
+    scalars: Vec<(ServerScalarEntity<GrpcNetworkProtocol>, Location)>,
+    objects: Vec<(
+        ProcessObjectTypeDefinitionOutcome<GrpcNetworkProtocol>,
+        Location,
+    )>,
+    query_fields: Vec<WithLocation<FieldToInsert>>,
+    query_methods: BTreeMap<SelectableName, GrpcMethod>,
+    mutation_fields: Vec<WithLocation<FieldToInsert>>,
+    mutation_methods: BTreeMap<SelectableName, GrpcMethod>,
+}
+
+impl<'a> ProtoProcessor<'a> {
+    fn collect_types(
+        &mut self,
+        messages: &'a [ProtoMessage],
+        enums: &'a [ProtoEnum],
+        scope: &str,
+        isograph_name_prefix: &str,
+    ) {
+        for proto_enum in enums {
+            self.types.insert(
+                qualify(scope, &proto_enum.name),
+                ProtoType {
+                    isograph_name: format!("{isograph_name_prefix}{}", proto_enum.name),
+                    message: None,
+                },
+            );
+        }
+        for message in messages {
+            let full_name = qualify(scope, &message.name);
+            let isograph_name = format!("{isograph_name_prefix}{}", message.name);
+            self.collect_types(
+                &message.messages,
+                &message.enums,
+                &full_name,
+                &isograph_name,
+            );
+            self.types.insert(
+                full_name,
+                ProtoType {
+                    isograph_name,
+                    message: Some(message),
+                },
+            );
+        }
+    }
+
+    fn process_messages_and_enums(
+        &mut self,
+        messages: &[ProtoMessage],
+        enums: &[ProtoEnum],
+        scope: &str,
+        isograph_name_prefix: &str,
+        text_source: TextSource,
+    ) {
+        for proto_enum in enums {
+            let location = Location::new(text_source, proto_enum.span);
+            self.scalars.push((
+                ServerScalarEntity {
+                    description: proto_enum.description.as_deref().map(|description| {
+                        WithSpan::new(description_value(description), Span::todo_generated())
+                    }),
+                    name: WithLocation::new(
+                        format!("{isograph_name_prefix}{}", proto_enum.name)
+                            .intern()
+                            .into(),
+                        location,
+                    ),
+                    // In the JSON mapping, enum values are serialized as their names.
+                    javascript_name: proto_enum
+                        .values
+                        .iter()
+                        .map(|value| format!("'{value}'"))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                        .intern()
+                        .into(),
+                    specified_by_url: None,
+                    output_format: std::marker::PhantomData,
+                },
+                location,
+            ));
+        }
+
+        for message in messages {
+            let full_name = qualify(scope, &message.name);
+            let isograph_name = format!("{isograph_name_prefix}{}", message.name);
+            let name: IsographObjectTypeName = isograph_name.as_str().intern().into();
+
+            let fields_to_insert = message
+                .fields
+                .iter()
+                .map(|field| {
+                    let location = Location::new(text_source, field.span);
+                    WithLocation::new(
+                        FieldToInsert {
+                            description: field.description.as_deref().map(|description| {
+                                WithSpan::new(
+                                    description_value(description),
+                                    Span::todo_generated(),
+                                )
+                            }),
+                            name: WithLocation::new(
+                                field.json_name.as_str().intern().into(),
+                                location,
+                            ),
+                            type_: self.field_type(field, &full_name),
+                            arguments: vec![],
+                            deprecation_reason: field
+                                .deprecated
+                                .then_some(*DEFAULT_DEPRECATION_REASON),
+                            cost: None,
+                            owner: None,
+                            cache_control: None,
+                            semantic_non_null_levels: vec![],
+                            is_inline_fragment: false,
+                        },
+                        location,
+                    )
+                })
+                .collect();
+
+            self.objects.push((
+                ProcessObjectTypeDefinitionOutcome {
+                    encountered_root_kind: None,
+                    server_object_entity: ServerObjectEntity {
+                        description: message.description.as_deref().map(description_value),
+                        name,
+                        concrete_type: Some(name),
+                        is_input_object: false,
+                        directives: vec![],
+                        output_associated_data: GrpcSchemaObjectAssociatedData::default(),
+                    },
+                    fields_to_insert,
+                    expose_as_fields_to_insert: vec![],
+                },
+                Location::new(text_source, message.span),
+            ));
+
+            self.process_messages_and_enums(
+                &message.messages,
+                &message.enums,
+                &full_name,
+                &isograph_name,
+                text_source,
+            );
+        }
+    }
+
+    fn process_method(
+        &mut self,
+        service_name: &str,
+        method: &ProtoMethod,
+        scope: &str,
+        text_source: TextSource,
+    ) -> ProcessProtoFileResult<()> {
+        let location = Location::new(text_source, method.span);
+
+        // The fields of the request message become the arguments of the field.
+        let (request_message_name, request_message) = self
+            .resolve_type(&method.request_type_name, scope)
+            .and_then(|(full_name, proto_type)| Some((full_name, proto_type.message?)))
+            .ok_or_else(|| {
+                WithLocation::new(
+                    ProcessProtoFileError::RequestTypeIsNotAMessage {
+                        method_name: method.name.clone(),
+                        type_name: method.request_type_name.clone(),
+                    },
+                    location,
+                )
+            })?;
+        let arguments = request_message
+            .fields
+            .iter()
+            .map(|field| {
+                let location = Location::new(text_source, field.span);
+                WithLocation::new(
+                    GraphQLInputValueDefinition {
+                        description: field.description.as_deref().map(|description| {
+                            WithSpan::new(description_value(description), Span::todo_generated())
+                        }),
+                        name: WithLocation::new(field.json_name.as_str().intern().into(), location),
+                        // Every field of a proto3 message has a default value, so all
+                        // arguments are optional.
+                        type_: nullable(self.field_type(field, &request_message_name))
+                            .map(|type_name| type_name.unchecked_conversion()),
+                        default_value: None,
+                        directives: vec![],
+                    },
+                    location,
+                )
+            })
+            .collect();
+
+        let name: SelectableName = lower_first_char(&method.name).intern().into();
+        let field = WithLocation::new(
+            FieldToInsert {
+                description: method.description.as_deref().map(|description| {
+                    WithSpan::new(description_value(description), Span::todo_generated())
+                }),
+                name: WithLocation::new(name.unchecked_conversion(), location),
+                type_: named_type(&self.isograph_type_name(&method.response_type_name, scope)),
+                arguments,
+                deprecation_reason: method.deprecated.then_some(*DEFAULT_DEPRECATION_REASON),
+                cost: None,
+                owner: None,
+                cache_control: None,
+                semantic_non_null_levels: vec![],
+                is_inline_fragment: false,
+            },
+            location,
+        );
+        let grpc_method = GrpcMethod {
+            service: service_name.to_string(),
+            method: method.name.clone(),
+        };
+
+        let is_query = method.has_no_side_effects
+            || QUERY_METHOD_PREFIXES
+                .iter()
+                .any(|prefix| method.name.starts_with(prefix));
+        if is_query {
+            self.query_fields.push(field);
+            self.query_methods.insert(name, grpc_method);
+        } else {
+            self.mutation_fields.push(field);
+            self.mutation_methods.insert(name, grpc_method);
+        }
+        Ok(())
+    }
+
+    /// The type of a field of a message. As in the proto3 JSON mapping, scalars and
+    /// enums without a label, and repeated fields, always have a value, while
+    /// messages and fields with explicit presence can be null.
+    fn field_type(
+        &self,
+        field: &ProtoField,
+        scope: &str,
+    ) -> GraphQLTypeAnnotation<UnvalidatedTypeName> {
+        let type_name = if field.json_name == "id" && is_id_compatible(&field.type_name) {
+            "ID".to_string()
+        } else {
+            self.isograph_type_name(&field.type_name, scope)
+        };
+
+        let has_presence = well_known_type_name(&field.type_name).is_some()
+            || self
+                .resolve_type(&field.type_name, scope)
+                .is_some_and(|(_, proto_type)| proto_type.message.is_some());
+
+        match field.label {
+            ProtoFieldLabel::Repeated => {
+                GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::List(
+                    GraphQLListTypeAnnotation(non_null(named_type(&type_name))),
+                )))
+            }
+            ProtoFieldLabel::None if !has_presence => non_null(named_type(&type_name)),
+            ProtoFieldLabel::None | ProtoFieldLabel::Optional => named_type(&type_name),
+        }
+    }
+
+    /// The name of the Isograph type that corresponds to a proto type. Types that are
+    /// not defined are passed through (without dots), and the compiler reports an
+    /// error when the field is inserted.
+    fn isograph_type_name(&self, type_name: &str, scope: &str) -> String {
+        if let Some(scalar_name) =
+            scalar_type_name(type_name).or_else(|| well_known_type_name(type_name))
+        {
+            return scalar_name.to_string();
+        }
+        match self.resolve_type(type_name, scope) {
+            Some((_, proto_type)) => proto_type.isograph_name.clone(),
+            None => type_name.replace('.', ""),
+        }
+    }
+
+    /// Resolve a type name, as written in a proto file, to a fully qualified name.
+    /// As with protoc, relative names are looked up in the innermost scope first.
+    fn resolve_type(&self, type_name: &str, scope: &str) -> Option<(String, &ProtoType<'a>)> {
+        if let Some(full_name) = type_name.strip_prefix('.') {
+            return self
+                .types
+                .get(full_name)
+                .map(|proto_type| (full_name.to_string(), proto_type));
+        }
+        let mut scope = scope;
+        loop {
+            let full_name = qualify(scope, type_name);
+            if let Some(proto_type) = self.types.get(&full_name) {
+                return Some((full_name, proto_type));
+            }
+            if scope.is_empty() {
+                return None;
+            }
+            scope = scope.rfind('.').map_or("", |index| &scope[..index]);
+        }
+    }
+
+    fn finish(self) -> ProcessTypeSystemDocumentOutcome<GrpcNetworkProtocol> {
+        let mut objects = self.objects;
+        // The Query type must always exist, even if there are no query methods.
+        objects.push((
+            root_object(
+                *QUERY_TYPE,
+                RootOperationKind::Query,
+                self.query_fields,
+                self.query_methods,
+            ),
+            Location::generated(),
+        ));
+        if !self.mutation_fields.is_empty() {
+            objects.push((
+                root_object(
+                    *MUTATION_TYPE,
+                    RootOperationKind::Mutation,
+                    self.mutation_fields,
+                    self.mutation_methods,
+                ),
+                Location::generated(),
+            ));
+        }
+
+        ProcessTypeSystemDocumentOutcome {
+            scalars: self.scalars,
+            objects,
+        }
+    }
+}
+
+fn root_object(
+    name: IsographObjectTypeName,
+    root_operation_kind: RootOperationKind,
+    fields_to_insert: Vec<WithLocation<FieldToInsert>>,
+    methods: BTreeMap<SelectableName, GrpcMethod>,
+) -> ProcessObjectTypeDefinitionOutcome<GrpcNetworkProtocol> {
+    ProcessObjectTypeDefinitionOutcome {
+        encountered_root_kind: Some(root_operation_kind),
+        server_object_entity: ServerObjectEntity {
+            description: None,
+            name,
+            concrete_type: Some(name),
+            is_input_object: false,
+            directives: vec![],
+            output_associated_data: GrpcSchemaObjectAssociatedData { methods },
+        },
+        fields_to_insert,
+        expose_as_fields_to_insert: vec![],
+    }
+}
+
+/// The scalar that a proto scalar type corresponds to. In the JSON mapping, 64-bit
+/// integers are serialized as strings, since they may not fit in a number.
+fn scalar_type_name(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "double" | "float" => Some("Float"),
+        "int32" | "uint32" | "sint32" | "fixed32" | "sfixed32" => Some("Int"),
+        "int64" | "uint64" | "sint64" | "fixed64" | "sfixed64" => Some("String"),
+        "bool" => Some("Boolean"),
+        "string" | "bytes" => Some("String"),
+        _ => None,
+    }
+}
+
+/// The scalar that a well-known type with a special JSON representation corresponds
+/// to. These types are messages, so fields of these types can be null.
+fn well_known_type_name(type_name: &str) -> Option<&'static str> {
+    match type_name.trim_start_matches('.') {
+        "google.protobuf.Timestamp"
+        | "google.protobuf.Duration"
+        | "google.protobuf.FieldMask"
+        | "google.protobuf.StringValue"
+        | "google.protobuf.BytesValue"
+        | "google.protobuf.Int64Value"
+        | "google.protobuf.UInt64Value" => Some("String"),
+        "google.protobuf.Int32Value" | "google.protobuf.UInt32Value" => Some("Int"),
+        "google.protobuf.FloatValue" | "google.protobuf.DoubleValue" => Some("Float"),
+        "google.protobuf.BoolValue" => Some("Boolean"),
+        _ => None,
+    }
+}
+
+/// Whether a field named `id` with this type should have type ID.
+fn is_id_compatible(type_name: &str) -> bool {
+    scalar_type_name(type_name) == Some("String")
+}
+
+fn named_type(type_name: &str) -> GraphQLTypeAnnotation<UnvalidatedTypeName> {
+    GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+        type_name.intern().into(),
+        Span::todo_generated(),
+    )))
+}
+
+fn non_null(
+    type_: GraphQLTypeAnnotation<UnvalidatedTypeName>,
+) -> GraphQLTypeAnnotation<UnvalidatedTypeName> {
+    match type_ {
+        GraphQLTypeAnnotation::Named(named) => {
+            GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::Named(named)))
+        }
+        GraphQLTypeAnnotation::List(list) => {
+            GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::List(*list)))
+        }
+        GraphQLTypeAnnotation::NonNull(_) => type_,
+    }
+}
+
+fn nullable(
+    type_: GraphQLTypeAnnotation<UnvalidatedTypeName>,
+) -> GraphQLTypeAnnotation<UnvalidatedTypeName> {
+    match type_ {
+        GraphQLTypeAnnotation::NonNull(non_null) => match *non_null {
+            GraphQLNonNullTypeAnnotation::Named(named) => GraphQLTypeAnnotation::Named(named),
+            GraphQLNonNullTypeAnnotation::List(list) => GraphQLTypeAnnotation::List(Box::new(list)),
+        },
+        type_ => type_,
+    }
+}
+
+fn description_value(description: &str) -> DescriptionValue {
+    description.intern().into()
+}
+
+/// e.g. `GetPet` becomes `getPet`
+fn lower_first_char(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// e.g. `pets` and `Pet` become `pets.Pet`
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{scope}.{name}")
+    }
+}
+
+pub(crate) type ProcessProtoFileResult<T> = Result<T, WithLocation<ProcessProtoFileError>>;
+
+#[derive(Error, Eq, PartialEq, Debug)]
+pub enum ProcessProtoFileError {
+    #[error("Unable to parse proto file.\nReason: {message}")]
+    InvalidProtoFile { message: String },
+
+    #[error(
+        "The request type of {method_name}, {type_name}, is not a message defined \
+        in the schema or schema extensions."
+    )]
+    RequestTypeIsNotAMessage {
+        method_name: String,
+        type_name: String,
+    },
+}
+
+impl HasErrorCode for ProcessProtoFileError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ProcessProtoFileError::InvalidProtoFile { .. } => error_codes::IS0117,
+            ProcessProtoFileError::RequestTypeIsNotAMessage { .. } => error_codes::IS0118,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_type_names_in_the_innermost_scope() {
+        let mut processor = ProtoProcessor::default();
+        for (full_name, isograph_name) in [
+            ("pets.Owner", "Owner"),
+            ("pets.Pet", "Pet"),
+            ("pets.Pet.Owner", "PetOwner"),
+        ] {
+            processor.types.insert(
+                full_name.to_string(),
+                ProtoType {
+                    isograph_name: isograph_name.to_string(),
+                    message: None,
+                },
+            );
+        }
+
+        assert_eq!(
+            processor.isograph_type_name("Owner", "pets.Pet"),
+            "PetOwner"
+        );
+        assert_eq!(processor.isograph_type_name("Owner", "pets"), "Owner");
+        assert_eq!(
+            processor.isograph_type_name(".pets.Owner", "pets.Pet"),
+            "Owner"
+        );
+        assert_eq!(
+            processor.isograph_type_name("Pet.Owner", "pets"),
+            "PetOwner"
+        );
+        assert_eq!(processor.isograph_type_name("int64", "pets"), "String");
+        assert_eq!(
+            processor.isograph_type_name("other.Toy", "pets"),
+            "otherToy"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use common_lang_types::Span;
+
+// The subset of a proto3 file that Isograph understands. Options (other than
+// deprecated, json_name and idempotency_level), reserved ranges, extensions and
+// imports are ignored.
+
+#[derive(Debug, Default)]
+pub(crate) struct ProtoFile {
+    pub package: Option<String>,
+    pub messages: Vec<ProtoMessage>,
+    pub enums: Vec<ProtoEnum>,
+    pub services: Vec<ProtoService>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ProtoMessage {
+    pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub fields: Vec<ProtoField>,
+    pub messages: Vec<ProtoMessage>,
+    pub enums: Vec<ProtoEnum>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ProtoField {
+    /// The name used in the JSON mapping, i.e. the json_name option, or the field
+    /// name in lower camel case.
+    pub json_name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    /// The type, as written (e.g. `int32`, `Owner` or `.pets.Owner`).
+    pub type_name: String,
+    pub label: ProtoFieldLabel,
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProtoFieldLabel {
+    /// No label. In proto3, scalars without a label always have a value.
+    None,
+    /// `optional`, `required` (in proto2), or a member of a oneof.
+    Optional,
+    Repeated,
+}
+
+#[derive(Debug)]
+pub(crate) struct ProtoEnum {
+    pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub values: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ProtoService {
+    pub name: String,
+    pub methods: Vec<ProtoMethod>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ProtoMethod {
+    pub name: String,
+    pub span: Span,
+    pub description: Option<String>,
+    pub request_type_name: String,
+    pub response_type_name: String,
+    /// True if either the request or the response is streamed.
+    pub is_streaming: bool,
+    /// True if the method has `option idempotency_level = NO_SIDE_EFFECTS`.
+    pub has_no_side_effects: bool,
+    pub deprecated: bool,
+}
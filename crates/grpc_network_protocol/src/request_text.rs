@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use common_lang_types::{QueryText, SelectableName};
+use intern::Lookup;
+use isograph_lang_types::{
+    ArgumentKeyAndValue, DefinitionLocation, SelectionCondition, SelectionType,
+    ServerObjectEntityId, ServerScalarEntityId, TypeAnnotation,
+};
+use isograph_schema::{
+    serialize_request_value, MergedSelectionMap, MergedServerSelection, NetworkProtocol,
+    RootOperationName, Schema, ServerSelectableId,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{GrpcMethod, GrpcNetworkProtocol};
+
+/// Generate a JSON description of the methods called by an entrypoint: one call
+/// per root field. For example:
+///
+/// ```json
+/// {"kind":"GrpcRequests","requests":[{"responseKey":"getPet____id___v_id",
+/// "service":"pets.PetService","method":"GetPet","request":{"id":{"kind":"Variable",
+/// "name":"id"}},"response":{"displayName":{"default":""}},"conditions":[]}]}
+/// ```
+///
+/// The response of each call is stored under its responseKey, which matches the
+/// key that the normalization AST reads.
+///
+/// In the proto3 JSON mapping, fields with default values (and unset messages) are
+/// omitted, but the normalization AST treats missing fields as not fetched. So
+/// the default value of each selected field is included in response, and the
+/// network function fills it in.
+pub(crate) fn generate_request_text(
+    schema: &Schema<GrpcNetworkProtocol>,
+    selection_map: &MergedSelectionMap,
+    root_operation_name: &RootOperationName,
+) -> QueryText {
+    let root_object_entity_id = *schema
+        .fetchable_types
+        .iter()
+        .find(|(_, name)| name.0 == root_operation_name.0)
+        .expect("Expected root operation to be fetchable. This is indicative of a bug in Isograph.")
+        .0;
+    let methods = &schema
+        .server_entity_data
+        .server_object_entity(root_object_entity_id)
+        .output_associated_data
+        .methods;
+
+    let request_text =
+        generate_grpc_requests(schema, root_object_entity_id, selection_map, methods).to_string();
+
+    // The request text is printed within a single-quoted string literal.
+    QueryText(request_text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// The methods called for the root fields in selection_map, as JSON. This is used by
+/// protocols that fetch some fields with gRPC (e.g. the mixed network protocol.)
+pub fn generate_grpc_requests<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    root_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+    methods: &BTreeMap<SelectableName, GrpcMethod>,
+) -> Value {
+    let requests = selection_map
+        .values()
+        .map(|selection| {
+            let (name, response_key, arguments, conditions, response) = match selection {
+                MergedServerSelection::ScalarField(scalar_field) => (
+                    SelectableName::from(scalar_field.name),
+                    scalar_field.normalization_alias(),
+                    &scalar_field.arguments,
+                    &scalar_field.conditions,
+                    Value::Null,
+                ),
+                MergedServerSelection::LinkedField(linked_field) => (
+                    SelectableName::from(linked_field.name),
+                    linked_field.normalization_alias(),
+                    &linked_field.arguments,
+                    &linked_field.conditions,
+                    match server_selectable_id(
+                        schema,
+                        root_object_entity_id,
+                        linked_field.name.into(),
+                    ) {
+                        Some(SelectionType::Object(object_selectable_id)) => response_defaults(
+                            schema,
+                            *schema
+                                .server_object_selectable(object_selectable_id)
+                                .target_object_entity
+                                .inner(),
+                            &linked_field.selection_map,
+                        ),
+                        _ => Value::Null,
+                    },
+                ),
+                MergedServerSelection::InlineFragment(_) => {
+                    panic!(
+                        "Expected root selections not to be inline fragments. \
+                        This is indicative of a bug in Isograph."
+                    )
+                }
+            };
+            let method = methods.get(&name).expect(
+                "Expected root field to be a method. This is indicative of a bug in Isograph.",
+            );
+            request(
+                method,
+                response_key.unwrap_or_else(|| name.to_string()),
+                arguments,
+                conditions,
+                response,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "kind": "GrpcRequests", "requests": requests })
+}
+
+fn request(
+    method: &GrpcMethod,
+    response_key: String,
+    arguments: &[ArgumentKeyAndValue],
+    conditions: &[SelectionCondition],
+    response: Value,
+) -> Value {
+    // Field arguments are named after the fields of the request message, in the
+    // JSON mapping, so the request message is an object with the same keys.
+    let request = arguments
+        .iter()
+        .map(|argument| {
+            (
+                argument.key.lookup().to_string(),
+                serialize_request_value(&argument.value),
+            )
+        })
+        .collect::<Map<_, _>>();
+
+    json!({
+        "responseKey": response_key,
+        "service": method.service,
+        "method": method.method,
+        "request": request,
+        "response": response,
+        "conditions": conditions
+            .iter()
+            .map(|condition| json!({
+                "kind": condition.kind.directive_name(),
+                "value": serialize_request_value(&condition.value),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// The default value of each field in a selection map, keyed by response key. For
+/// linked fields, the defaults of the selected fields are nested under fields.
+fn response_defaults<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    selection_map: &MergedSelectionMap,
+) -> Value {
+    selection_map
+        .values()
+        .filter_map(|selection| match selection {
+            MergedServerSelection::ScalarField(scalar_field) => {
+                let Some(SelectionType::Scalar(scalar_selectable_id)) =
+                    server_selectable_id(schema, parent_object_entity_id, scalar_field.name.into())
+                else {
+                    // e.g. __typename, which is always present
+                    return None;
+                };
+                let target_scalar_entity = &schema
+                    .server_scalar_selectable(scalar_selectable_id)
+                    .target_scalar_entity;
+                Some((
+                    scalar_field
+                        .normalization_alias()
+                        .unwrap_or_else(|| scalar_field.name.to_string()),
+                    json!({ "default": default_scalar_value(schema, target_scalar_entity) }),
+                ))
+            }
+            MergedServerSelection::LinkedField(linked_field) => {
+                let Some(SelectionType::Object(object_selectable_id)) =
+                    server_selectable_id(schema, parent_object_entity_id, linked_field.name.into())
+                else {
+                    return None;
+                };
+                let target_object_entity = &schema
+                    .server_object_selectable(object_selectable_id)
+                    .target_object_entity;
+                Some((
+                    linked_field
+                        .normalization_alias()
+                        .unwrap_or_else(|| linked_field.name.to_string()),
+                    json!({
+                        "default": match target_object_entity {
+                            TypeAnnotation::Plural(_) => json!([]),
+                            _ => Value::Null,
+                        },
+                        "fields": response_defaults(
+                            schema,
+                            *target_object_entity.inner(),
+                            &linked_field.selection_map,
+                        ),
+                    }),
+                ))
+            }
+            // Messages cannot be refined, so there are no inline fragments.
+            MergedServerSelection::InlineFragment(_) => None,
+        })
+        .collect::<Map<_, _>>()
+        .into()
+}
+
+fn server_selectable_id<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    name: SelectableName,
+) -> Option<ServerSelectableId> {
+    match schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&parent_object_entity_id)?
+        .selectables
+        .get(&name)?
+    {
+        DefinitionLocation::Server(server_selectable_id) => Some(*server_selectable_id),
+        DefinitionLocation::Client(_) => None,
+    }
+}
+
+/// The value of a scalar or enum field that is omitted from a response.
+fn default_scalar_value<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    target_scalar_entity: &TypeAnnotation<ServerScalarEntityId>,
+) -> Value {
+    let scalar_entity_id = match target_scalar_entity {
+        TypeAnnotation::Scalar(scalar_entity_id) => *scalar_entity_id,
+        TypeAnnotation::Union(_) => return Value::Null,
+        TypeAnnotation::Plural(_) => return json!([]),
+    };
+    let server_entity_data = &schema.server_entity_data;
+    if scalar_entity_id == server_entity_data.string_type_id
+        || scalar_entity_id == server_entity_data.id_type_id
+    {
+        json!("")
+    } else if scalar_entity_id == server_entity_data.int_type_id
+        || scalar_entity_id == server_entity_data.float_type_id
+    {
+        json!(0)
+    } else if scalar_entity_id == server_entity_data.boolean_type_id {
+        json!(false)
+    } else {
+        // Every other scalar defined by a proto file is an enum, whose javascript
+        // name is a union of its values (e.g. 'UNSPECIFIED' | 'DOG'). The default
+        // is the first value.
+        let javascript_name = server_entity_data
+            .server_scalar_entity(scalar_entity_id)
+            .javascript_name
+            .lookup();
+        json!(javascript_name
+            .split(" | ")
+            .next()
+            .unwrap_or_default()
+            .trim_matches('\''))
+    }
+}
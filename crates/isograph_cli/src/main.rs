@@ -1,16 +1,32 @@
 mod opt;
+mod warnings;
 
 use clap::Parser;
 use colored::Colorize;
-use common_lang_types::CurrentWorkingDirectory;
+use common_lang_types::{explain_error_code, CurrentWorkingDirectory, ErrorCode};
 use graphql_network_protocol::GraphQLNetworkProtocol;
+use grpc_network_protocol::GrpcNetworkProtocol;
 use intern::string_key::Intern;
-use isograph_compiler::{compile_and_print, handle_watch_command};
-use isograph_config::create_config;
-use opt::{Command, CompileCommand, LspCommand, Opt};
-use std::io;
+use isograph_compiler::{
+    clean_and_print, compile_and_print, dependency_graph_and_print, handle_daemon_command,
+    handle_watch_command, init_and_print, schema_diff_and_print, DependencyGraphFormat,
+    OutputFormat, StandardSources,
+};
+use isograph_config::{create_config, create_configs, resolved_config_json, NetworkProtocolKind};
+use isograph_schema::NetworkProtocol;
+use mixed_network_protocol::MixedNetworkProtocol;
+use opt::{
+    CleanCommand, Command, CompileCommand, DiffCommand, GraphCommand, GraphFormatOpt, InitCommand,
+    LspCommand, Opt, OutputFormatOpt,
+};
+use rest_network_protocol::RestNetworkProtocol;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 use tracing::{error, info, level_filters::LevelFilter};
-use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::{fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt};
+use warnings::{HideWarningFields, JsonWarningLayer};
 
 #[tokio::main]
 async fn main() {
@@ -24,6 +40,30 @@ async fn main() {
         Command::Lsp(lsp_command) => {
             start_language_server(lsp_command, current_working_directory()).await;
         }
+        Command::Diff(diff_command) => {
+            start_diff(diff_command, current_working_directory());
+        }
+        Command::Graph(graph_command) => {
+            start_graph(graph_command, current_working_directory());
+        }
+        Command::Init(init_command) => {
+            start_init(init_command);
+        }
+        Command::Clean(clean_command) => {
+            start_clean(clean_command, current_working_directory());
+        }
+    }
+}
+
+fn explain(code: &str) {
+    match code.parse::<ErrorCode>().ok().and_then(explain_error_code) {
+        Some(info) => {
+            println!("{} {}\n\n{}", info.code, info.name.bold(), info.explanation);
+        }
+        None => {
+            eprintln!("{}", format!("Unknown error code {code}.").bright_red());
+            std::process::exit(1);
+        }
     }
 }
 
@@ -31,36 +71,284 @@ async fn start_compiler(
     compile_command: CompileCommand,
     current_working_directory: CurrentWorkingDirectory,
 ) {
-    configure_logger(compile_command.log_level);
+    if let Some(code) = compile_command.explain {
+        explain(&code);
+        return;
+    }
+    if compile_command.print_config {
+        let config_location = compile_command
+            .config
+            .unwrap_or("./isograph.config.json".into());
+        println!("{}", resolved_config_json(&config_location));
+        return;
+    }
+
+    let output_format = match compile_command.output_format {
+        OutputFormatOpt::Human => OutputFormat::Human,
+        OutputFormatOpt::Json => OutputFormat::Json,
+    };
+    // The daemon reports diagnostics in its responses, and stdout may be the
+    // channel over which it responds, so warnings must not be printed to it.
+    configure_logger(
+        compile_command.log_level,
+        if compile_command.daemon {
+            OutputFormat::Human
+        } else {
+            output_format
+        },
+    );
     let config_location = compile_command
         .config
         .unwrap_or("./isograph.config.json".into());
+    let compile_mode = if compile_command.daemon {
+        CompileMode::Daemon {
+            socket: compile_command.socket,
+        }
+    } else if compile_command.watch {
+        CompileMode::Watch
+    } else {
+        CompileMode::Once
+    };
 
-    if compile_command.watch {
-        match handle_watch_command::<GraphQLNetworkProtocol>(
-            config_location,
-            current_working_directory,
-        )
-        .await
-        {
-            Ok(_) => {
-                info!("{}", "Successfully watched. Exiting.\n")
+    match network_protocol(&config_location, current_working_directory) {
+        NetworkProtocolKind::GraphQL => {
+            compile_or_watch::<GraphQLNetworkProtocol>(
+                config_location,
+                current_working_directory,
+                output_format,
+                compile_mode,
+                compile_command.stats,
+                compile_command.keep_tmp_on_failure,
+            )
+            .await
+        }
+        NetworkProtocolKind::Rest => {
+            compile_or_watch::<RestNetworkProtocol>(
+                config_location,
+                current_working_directory,
+                output_format,
+                compile_mode,
+                compile_command.stats,
+                compile_command.keep_tmp_on_failure,
+            )
+            .await
+        }
+        NetworkProtocolKind::Grpc => {
+            compile_or_watch::<GrpcNetworkProtocol>(
+                config_location,
+                current_working_directory,
+                output_format,
+                compile_mode,
+                compile_command.stats,
+                compile_command.keep_tmp_on_failure,
+            )
+            .await
+        }
+        NetworkProtocolKind::Mixed => {
+            compile_or_watch::<MixedNetworkProtocol>(
+                config_location,
+                current_working_directory,
+                output_format,
+                compile_mode,
+                compile_command.stats,
+                compile_command.keep_tmp_on_failure,
+            )
+            .await
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CompileMode {
+    Once,
+    Watch,
+    Daemon { socket: Option<PathBuf> },
+}
+
+async fn compile_or_watch<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources> + 'static,
+>(
+    config_location: PathBuf,
+    current_working_directory: CurrentWorkingDirectory,
+    output_format: OutputFormat,
+    compile_mode: CompileMode,
+    print_stats: bool,
+    keep_tmp_on_failure: bool,
+) {
+    match compile_mode {
+        CompileMode::Once => {
+            if compile_and_print::<TNetworkProtocol>(
+                config_location,
+                current_working_directory,
+                output_format,
+                print_stats,
+                keep_tmp_on_failure,
+            )
+            .is_err()
+            {
+                std::process::exit(1);
             }
-            Err(err) => {
-                error!("{}\n{:?}", "Error in watch process of some sort.\n", err);
+        }
+        CompileMode::Watch => {
+            match handle_watch_command::<TNetworkProtocol>(
+                config_location,
+                current_working_directory,
+                output_format,
+                print_stats,
+                keep_tmp_on_failure,
+            )
+            .await
+            {
+                Ok(_) => {
+                    info!("{}", "Successfully watched. Exiting.\n")
+                }
+                Err(err) => {
+                    error!("{}\n{:?}", "Error in watch process of some sort.\n", err);
+                    std::process::exit(1);
+                }
+            };
+        }
+        CompileMode::Daemon { socket } => {
+            if let Err(err) = handle_daemon_command::<TNetworkProtocol>(
+                config_location,
+                current_working_directory,
+                socket,
+                keep_tmp_on_failure,
+            )
+            .await
+            {
+                error!("{}\n{}", "Error in daemon process.".bright_red(), err);
                 std::process::exit(1);
             }
-        };
-    } else if compile_and_print::<GraphQLNetworkProtocol>(
+        }
+    }
+}
+
+fn start_diff(diff_command: DiffCommand, current_working_directory: CurrentWorkingDirectory) {
+    let output_format = match diff_command.output_format {
+        OutputFormatOpt::Human => OutputFormat::Human,
+        OutputFormatOpt::Json => OutputFormat::Json,
+    };
+    configure_logger(diff_command.log_level, output_format);
+    let config_location = diff_command
+        .config
+        .unwrap_or("./isograph.config.json".into());
+
+    let result = match network_protocol(&config_location, current_working_directory) {
+        NetworkProtocolKind::GraphQL => schema_diff_and_print::<GraphQLNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            diff_command.old,
+            diff_command.new,
+            output_format,
+        ),
+        NetworkProtocolKind::Rest => schema_diff_and_print::<RestNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            diff_command.old,
+            diff_command.new,
+            output_format,
+        ),
+        NetworkProtocolKind::Grpc => schema_diff_and_print::<GrpcNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            diff_command.old,
+            diff_command.new,
+            output_format,
+        ),
+        NetworkProtocolKind::Mixed => schema_diff_and_print::<MixedNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            diff_command.old,
+            diff_command.new,
+            output_format,
+        ),
+    };
+    if result.is_err() {
+        std::process::exit(1);
+    }
+}
+
+fn start_graph(graph_command: GraphCommand, current_working_directory: CurrentWorkingDirectory) {
+    configure_logger(graph_command.log_level, OutputFormat::Human);
+    let config_location = graph_command
+        .config
+        .unwrap_or("./isograph.config.json".into());
+    let format = match graph_command.format {
+        GraphFormatOpt::Dot => DependencyGraphFormat::Dot,
+        GraphFormatOpt::Json => DependencyGraphFormat::Json,
+    };
+
+    let result = match network_protocol(&config_location, current_working_directory) {
+        NetworkProtocolKind::GraphQL => dependency_graph_and_print::<GraphQLNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            format,
+        ),
+        NetworkProtocolKind::Rest => dependency_graph_and_print::<RestNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            format,
+        ),
+        NetworkProtocolKind::Grpc => dependency_graph_and_print::<GrpcNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            format,
+        ),
+        NetworkProtocolKind::Mixed => dependency_graph_and_print::<MixedNetworkProtocol>(
+            config_location,
+            current_working_directory,
+            format,
+        ),
+    };
+    if result.is_err() {
+        std::process::exit(1);
+    }
+}
+
+fn start_init(init_command: InitCommand) {
+    configure_logger(init_command.log_level, OutputFormat::Human);
+    if let Err(err) = init_and_print(init_command.force, init_command.yes) {
+        error!("{}", err.to_string().bright_red());
+        std::process::exit(1);
+    }
+}
+
+fn start_clean(clean_command: CleanCommand, current_working_directory: CurrentWorkingDirectory) {
+    configure_logger(clean_command.log_level, OutputFormat::Human);
+    let config_location = clean_command
+        .config
+        .unwrap_or("./isograph.config.json".into());
+    if let Err(err) = clean_and_print(
         config_location,
         current_working_directory,
-    )
-    .is_err()
-    {
+        clean_command.orphans,
+        clean_command.dry_run,
+    ) {
+        error!("{}", err.to_string().bright_red());
         std::process::exit(1);
     }
 }
 
+/// The network protocol used by the projects in the config. All projects must use
+/// the same network protocol.
+fn network_protocol(
+    config_location: &Path,
+    current_working_directory: CurrentWorkingDirectory,
+) -> NetworkProtocolKind {
+    let configs = create_configs(config_location.to_path_buf(), current_working_directory);
+    let mut network_protocols = configs.iter().map(|config| config.options.network_protocol);
+    let network_protocol = network_protocols.next().unwrap_or_default();
+    if network_protocols.any(|other| other != network_protocol) {
+        error!(
+            "{}",
+            "All projects in the config must use the same network_protocol.".bright_red()
+        );
+        std::process::exit(1);
+    }
+    network_protocol
+}
+
 async fn start_language_server(
     lsp_command: LspCommand,
     current_working_directory: CurrentWorkingDirectory,
@@ -70,9 +358,24 @@ async fn start_language_server(
             .config
             .unwrap_or("./isograph.config.json".into()),
         current_working_directory,
+        lsp_command.project.as_deref(),
     );
     info!("Starting language server");
-    if let Err(_e) = isograph_lsp::start_language_server(config).await {
+    let result = match config.options.network_protocol {
+        NetworkProtocolKind::GraphQL => {
+            isograph_lsp::start_language_server::<GraphQLNetworkProtocol>(config).await
+        }
+        NetworkProtocolKind::Rest => {
+            isograph_lsp::start_language_server::<RestNetworkProtocol>(config).await
+        }
+        NetworkProtocolKind::Grpc => {
+            isograph_lsp::start_language_server::<GrpcNetworkProtocol>(config).await
+        }
+        NetworkProtocolKind::Mixed => {
+            isograph_lsp::start_language_server::<MixedNetworkProtocol>(config).await
+        }
+    };
+    if let Err(_e) = result {
         error!(
             "{}",
             "Error encountered when running language server.".bright_red(),
@@ -82,7 +385,7 @@ async fn start_language_server(
     }
 }
 
-fn configure_logger(log_level: LevelFilter) {
+fn configure_logger(log_level: LevelFilter, output_format: OutputFormat) {
     let mut collector = tracing_subscriber::fmt()
         .pretty()
         .without_time()
@@ -99,7 +402,14 @@ fn configure_logger(log_level: LevelFilter) {
                 .with_target(false);
         }
     }
-    collector.init();
+    let collector = collector.map_event_format(HideWarningFields);
+    match output_format {
+        OutputFormat::Human => collector.init(),
+        OutputFormat::Json => collector
+            .finish()
+            .with(JsonWarningLayer::new(io::stdout))
+            .init(),
+    }
 }
 
 fn current_working_directory() -> CurrentWorkingDirectory {
@@ -1,4 +1,4 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 use tracing::level_filters::LevelFilter;
 
@@ -15,6 +15,10 @@ pub struct Opt {
 pub enum Command {
     Compile(CompileCommand),
     Lsp(LspCommand),
+    Diff(DiffCommand),
+    Graph(GraphCommand),
+    Init(InitCommand),
+    Clean(CleanCommand),
 }
 
 /// Compile
@@ -23,6 +27,17 @@ pub(crate) struct CompileCommand {
     #[arg(long)]
     pub watch: bool,
 
+    /// Keep the compiler running, and compile when asked to over stdin (or
+    /// --socket), instead of compiling once. Build scripts can use this to get
+    /// incremental compilations without watching the file system.
+    #[arg(long, conflicts_with = "watch")]
+    pub daemon: bool,
+
+    /// With --daemon, listen for commands on this Unix socket instead of
+    /// reading them from stdin.
+    #[arg(long, requires = "daemon")]
+    pub socket: Option<PathBuf>,
+
     /// Compile using this config file. If not provided, searches for a config in
     /// package.json under the `isograph` key.
     #[arg(long)]
@@ -30,6 +45,38 @@ pub(crate) struct CompileCommand {
 
     #[arg(long, value_enum, default_value = "info")]
     pub log_level: LevelFilter,
+
+    /// How errors and warnings are reported. If json, each diagnostic is
+    /// printed to stdout as a single line of JSON.
+    #[arg(long, value_enum, default_value = "human")]
+    pub output_format: OutputFormatOpt,
+
+    /// Print the time spent in each compilation phase, and the number of items
+    /// compiled. Printed as JSON if --output-format=json.
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Artifacts are written to a temporary directory, which then replaces the
+    /// artifact directory. If writing them fails, keep the temporary directory
+    /// (instead of deleting it), so that it can be inspected.
+    #[arg(long)]
+    pub keep_tmp_on_failure: bool,
+
+    /// Print a detailed explanation of an error code (e.g. IS0104), instead
+    /// of compiling.
+    #[arg(long, value_name = "CODE")]
+    pub explain: Option<String>,
+
+    /// Print the config as JSON, with every option that is not set set to its
+    /// default, instead of compiling.
+    #[arg(long)]
+    pub print_config: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OutputFormatOpt {
+    Human,
+    Json,
 }
 
 /// LSP
@@ -39,4 +86,110 @@ pub(crate) struct LspCommand {
     /// package.json under the `isograph` key.
     #[arg(long)]
     pub config: Option<PathBuf>,
+
+    /// If the config contains multiple projects, the project that the language
+    /// server should use.
+    #[arg(long)]
+    pub project: Option<String>,
+}
+
+/// Diff
+///
+/// Report which client fields and entrypoints would break if the schema were
+/// changed from the old schema to the new schema.
+#[derive(Debug, Args)]
+pub(crate) struct DiffCommand {
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `isograph` key.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// The schema that the client fields are currently written against. Used
+    /// instead of the schema in the config.
+    #[arg(long)]
+    pub old: PathBuf,
+
+    /// The schema to check the client fields against.
+    #[arg(long)]
+    pub new: PathBuf,
+
+    #[arg(long, value_enum, default_value = "info")]
+    pub log_level: LevelFilter,
+
+    /// How breaking changes are reported. If json, each breaking change is
+    /// printed to stdout as a single line of JSON.
+    #[arg(long, value_enum, default_value = "human")]
+    pub output_format: OutputFormatOpt,
+}
+
+/// Graph
+///
+/// Print the graph of which client fields and client pointers select which
+/// others, e.g. to find client fields that can be removed.
+#[derive(Debug, Args)]
+pub(crate) struct GraphCommand {
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `isograph` key.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// dot prints a graphviz digraph. json prints the nodes, the edges and
+    /// the client fields reachable from each entrypoint, as a single line of
+    /// JSON per project.
+    #[arg(long, value_enum, default_value = "dot")]
+    pub format: GraphFormatOpt,
+
+    #[arg(long, value_enum, default_value = "info")]
+    pub log_level: LevelFilter,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GraphFormatOpt {
+    Dot,
+    Json,
+}
+
+/// Init
+///
+/// Create an isograph.config.json in the current directory, asking where the
+/// schema, the iso literals and the artifacts are, and optionally add the Isograph
+/// Babel plugin to the Babel config.
+#[derive(Debug, Args)]
+pub(crate) struct InitCommand {
+    /// Accept the default answer to every question, e.g. in scripts.
+    #[arg(long, short)]
+    pub yes: bool,
+
+    /// Overwrite isograph.config.json if it already exists.
+    #[arg(long)]
+    pub force: bool,
+
+    #[arg(long, value_enum, default_value = "info")]
+    pub log_level: LevelFilter,
+}
+
+/// Clean
+///
+/// Remove the artifact directory, or, with --orphans, only the artifacts of client
+/// fields, client pointers and entrypoints that no longer exist.
+#[derive(Debug, Args)]
+pub(crate) struct CleanCommand {
+    /// Use this config file. If not provided, searches for a config in
+    /// package.json under the `isograph` key.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Only remove artifacts that were generated from iso literals that no longer
+    /// exist. Requires the generate_artifact_provenance option, since artifacts
+    /// record which iso literal they were generated from in their provenance
+    /// comment.
+    #[arg(long)]
+    pub orphans: bool,
+
+    /// Print what would be removed, without removing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[arg(long, value_enum, default_value = "info")]
+    pub log_level: LevelFilter,
 }
@@ -0,0 +1,277 @@
+use std::{fmt, io::Write};
+
+use isograph_compiler::{Diagnostic, DiagnosticSpan, Severity};
+use tracing::{
+    field::{display, Field, Value, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{
+    fmt::{format::Writer, FmtContext, FormatEvent, FormatFields, MakeWriter},
+    layer::Context,
+    registry::LookupSpan,
+    Layer,
+};
+
+/// The fields with which warnings record their code and location, e.g. when a
+/// validation is configured to warn. See OptionalValidationLevel::on_failure.
+const WARNING_FIELD_PREFIX: &str = "warning.";
+
+/// In JSON mode, warnings (e.g. from validations configured to warn) are also
+/// printed to stdout as diagnostics, so that tools see both errors and warnings.
+pub(crate) struct JsonWarningLayer<W> {
+    make_writer: W,
+}
+
+impl<W> JsonWarningLayer<W> {
+    pub(crate) fn new(make_writer: W) -> Self {
+        Self { make_writer }
+    }
+}
+
+impl<S: Subscriber, W: for<'a> MakeWriter<'a> + 'static> Layer<S> for JsonWarningLayer<W> {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() != Level::WARN {
+            return;
+        }
+        let mut visitor = WarningVisitor::default();
+        event.record(&mut visitor);
+        let _ = writeln!(
+            self.make_writer.make_writer(),
+            "{}",
+            visitor.into_diagnostic().to_json()
+        );
+    }
+}
+
+#[derive(Default)]
+struct WarningVisitor {
+    message: String,
+    code: Option<String>,
+    warning_message: Option<String>,
+    file: Option<String>,
+    span_start: Option<u32>,
+    span_end: Option<u32>,
+}
+
+impl WarningVisitor {
+    fn into_diagnostic(self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(
+            strip_ansi_escape_codes(&self.warning_message.unwrap_or(self.message)),
+            Severity::Warning,
+            None,
+        );
+        diagnostic.code = self.code;
+        diagnostic.file = self.file;
+        diagnostic.span = self
+            .span_start
+            .zip(self.span_end)
+            .map(|(start, end)| DiagnosticSpan { start, end });
+        diagnostic
+    }
+}
+
+impl Visit for WarningVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "warning.message" => self.warning_message = Some(value.to_string()),
+            "warning.file" => self.file = Some(value.to_string()),
+            _ => self.record_debug(field, &value),
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "warning.span_start" => self.span_start = u32::try_from(value).ok(),
+            "warning.span_end" => self.span_end = u32::try_from(value).ok(),
+            _ => self.record_debug(field, &value),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            "warning.code" => self.code = Some(format!("{value:?}")),
+            _ => {}
+        }
+    }
+}
+
+/// Remove the escape codes with which messages are colored for the terminal.
+fn strip_ansi_escape_codes(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char == '\u{1b}' && chars.peek() == Some(&'[') {
+            // A control sequence ends with a character in the range @ to ~.
+            chars.next();
+            for char in chars.by_ref() {
+                if ('@'..='~').contains(&char) {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(char);
+        }
+    }
+    stripped
+}
+
+/// Formats events as the wrapped formatter does, except that the warning.* fields,
+/// which are only meant for tools, are left out.
+pub(crate) struct HideWarningFields<F>(pub F);
+
+impl<S, N, F> FormatEvent<S, N> for HideWarningFields<F>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let has_warning_fields = metadata
+            .fields()
+            .iter()
+            .any(|field| field.name().starts_with(WARNING_FIELD_PREFIX));
+        let message_field = metadata.fields().field("message");
+        let (true, Some(message_field)) = (has_warning_fields, message_field) else {
+            return self.0.format_event(ctx, writer, event);
+        };
+
+        // Fields cannot be removed from an event, so format an event with the
+        // same metadata and only the message instead.
+        let mut visitor = WarningVisitor::default();
+        event.record(&mut visitor);
+        let message = display(&visitor.message);
+        let values = [(&message_field, Some(&message as &dyn Value))];
+        let fields = metadata.fields().value_set(&values);
+        self.0
+            .format_event(ctx, writer, &Event::new(metadata, &fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use common_lang_types::{CodedError, ErrorCode, Location, Span, TextSource, WithLocation};
+    use intern::string_key::Intern;
+    use isograph_config::OptionalValidationLevel;
+    use tracing::warn;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Output {
+        type Writer = Output;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn json_warnings(emit_warnings: impl FnOnce()) -> Vec<String> {
+        let output = Output::default();
+        let subscriber = tracing_subscriber::registry().with(JsonWarningLayer::new(output.clone()));
+        tracing::subscriber::with_default(subscriber, emit_warnings);
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        output.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn warnings_are_printed_as_json_diagnostics() {
+        // The location of a warning is printed along with its source text, so the
+        // file must exist.
+        let file = std::env::temp_dir().join("isograph_json_warning.tsx");
+        std::fs::write(&file, "export const A = iso(`field Query.A { nmae }`);").unwrap();
+        let file = file.to_str().unwrap();
+
+        let warnings = json_warnings(|| {
+            OptionalValidationLevel::Warn
+                .on_failure(|| {
+                    WithLocation::new(
+                        CodedError {
+                            code: ErrorCode(400),
+                            message: "The field `Query.nmae` does not exist".to_string(),
+                        },
+                        Location::new(
+                            TextSource {
+                                current_working_directory: "/".intern().into(),
+                                relative_path_to_source_file: file.intern().into(),
+                                span: Some(Span::new(21, 45)),
+                            },
+                            Span::new(18, 22),
+                        ),
+                    )
+                })
+                .unwrap();
+            warn!(
+                "{}",
+                "A warning without a location".to_string() + "\u{1b}[0m"
+            );
+        });
+
+        assert_eq!(
+            warnings,
+            vec![
+                format!(
+                    r#"{{"code":"IS0400","message":"The field `Query.nmae` does not exist","severity":"warning","file":"{file}","span":{{"start":39,"end":43}}}}"#
+                ),
+                r#"{"code":null,"message":"A warning without a location","severity":"warning","file":null,"span":null}"#.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn warning_fields_are_hidden_from_human_output() {
+        let output = Output::default();
+        let subscriber = tracing_subscriber::fmt()
+            .pretty()
+            .without_time()
+            .with_target(false)
+            .with_file(false)
+            .with_line_number(false)
+            .with_ansi(false)
+            .with_writer(output.clone())
+            .map_event_format(HideWarningFields)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            warn!(
+                warning.code = %ErrorCode(600),
+                warning.file = "src/A.tsx",
+                "The client field `Query.A` is unused"
+            );
+        });
+
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output, "   WARN  The client field `Query.A` is unused\n\n");
+    }
+
+    #[test]
+    fn strips_ansi_escape_codes() {
+        assert_eq!(
+            strip_ansi_escape_codes("\u{1b}[31merror\u{1b}[0m: \u{1b}[1;4mfield\u{1b}[0m"),
+            "error: field"
+        );
+    }
+}
@@ -1,13 +1,17 @@
 use common_lang_types::{
-    IsographObjectTypeName, Location, SelectableName, UnvalidatedTypeName, WithLocation, WithSpan,
+    error_codes, ErrorCode, HasErrorCode, IsographObjectTypeName, ObjectTypeAndFieldName,
+    SelectableName, UnvalidatedTypeName, WithLocation, WithSpan,
 };
+use intern::{string_key::Intern, Lookup};
+use isograph_config::ServerFieldRestrictions;
 use isograph_lang_types::{
     DefinitionLocation, ObjectSelection, ScalarSelection, ScalarSelectionDirectiveSet,
     SelectionType, ServerObjectEntityId, UnvalidatedScalarFieldSelection, UnvalidatedSelection,
 };
 use isograph_schema::{
-    ClientScalarOrObjectSelectable, NetworkProtocol, ObjectSelectableId, RefetchStrategy,
-    ScalarSelectableId, Schema, ServerObjectEntity, UnprocessedClientFieldItem,
+    concrete_types, defines_server_field, ClientScalarOrObjectSelectable, ClientSelectableId,
+    NetworkProtocol, ObjectSelectableId, RefetchStrategy, ScalarSelectableId, Schema,
+    ServerObjectEntity, ServerSelectableId, UnprocessedClientFieldItem,
     UnprocessedClientPointerItem, UnprocessedItem, UseRefetchFieldRefetchStrategy,
     ValidatedObjectSelection, ValidatedScalarSelection, ValidatedSelection,
 };
@@ -16,24 +20,36 @@ use thiserror::Error;
 pub type ValidateAddSelectionSetsResultWithMultipleErrors<T> =
     Result<T, Vec<WithLocation<AddSelectionSetsError>>>;
 
+/// The config options that affect how selection sets are validated.
+#[derive(Clone, Copy)]
+pub(crate) struct SelectionSetValidationOptions<'a> {
+    pub restrictions: &'a ServerFieldRestrictions,
+    pub implicit_concrete_type_refinements: bool,
+}
+
 pub(crate) fn add_selection_sets_to_client_selectables<TNetworkProtocol: NetworkProtocol>(
     schema: &mut Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
     unprocessed_items: Vec<UnprocessedItem>,
 ) -> ValidateAddSelectionSetsResultWithMultipleErrors<()> {
     let mut errors = vec![];
     for unprocessed_item in unprocessed_items {
         match unprocessed_item {
             SelectionType::Scalar(unprocessed_client_field_item) => {
-                if let Err(e) =
-                    process_unprocessed_client_field_item(schema, unprocessed_client_field_item)
-                {
+                if let Err(e) = process_unprocessed_client_field_item(
+                    schema,
+                    options,
+                    unprocessed_client_field_item,
+                ) {
                     errors.extend(e)
                 }
             }
             SelectionType::Object(unprocessed_client_pointer_item) => {
-                if let Err(e) =
-                    process_unprocessed_client_pointer_item(schema, unprocessed_client_pointer_item)
-                {
+                if let Err(e) = process_unprocessed_client_pointer_item(
+                    schema,
+                    options,
+                    unprocessed_client_pointer_item,
+                ) {
                     errors.extend(e)
                 }
             }
@@ -50,6 +66,7 @@ pub(crate) fn add_selection_sets_to_client_selectables<TNetworkProtocol: Network
 // new items (the refetch and reader selection sets).
 fn process_unprocessed_client_field_item<TNetworkProtocol: NetworkProtocol>(
     schema: &mut Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
     unprocessed_item: UnprocessedClientFieldItem,
 ) -> ValidateAddSelectionSetsResultWithMultipleErrors<()> {
     let client_field = schema.client_field(unprocessed_item.client_field_id);
@@ -59,6 +76,7 @@ fn process_unprocessed_client_field_item<TNetworkProtocol: NetworkProtocol>(
 
     let new_selection_set = get_validated_selection_set(
         schema,
+        options,
         unprocessed_item.reader_selection_set,
         parent_object,
         client_field.parent_object_entity_id,
@@ -85,6 +103,7 @@ fn process_unprocessed_client_field_item<TNetworkProtocol: NetworkProtocol>(
 // new items (the refetch and reader selection sets).
 fn process_unprocessed_client_pointer_item<TNetworkProtocol: NetworkProtocol>(
     schema: &mut Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
     unprocessed_item: UnprocessedClientPointerItem,
 ) -> ValidateAddSelectionSetsResultWithMultipleErrors<()> {
     let client_pointer = schema.client_pointer(unprocessed_item.client_pointer_id);
@@ -94,6 +113,7 @@ fn process_unprocessed_client_pointer_item<TNetworkProtocol: NetworkProtocol>(
 
     let new_selection_set = get_validated_selection_set(
         schema,
+        options,
         unprocessed_item.reader_selection_set,
         parent_object,
         client_pointer.parent_object_entity_id,
@@ -115,6 +135,7 @@ fn process_unprocessed_client_pointer_item<TNetworkProtocol: NetworkProtocol>(
 /// - include the selectable id in the associated data
 fn get_validated_selection_set<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
     selection_set: Vec<WithSpan<UnvalidatedSelection>>,
     parent_object: &ServerObjectEntity<TNetworkProtocol>,
     selection_parent_object_id: ServerObjectEntityId,
@@ -123,6 +144,7 @@ fn get_validated_selection_set<TNetworkProtocol: NetworkProtocol>(
     get_all_errors_or_all_ok(selection_set.into_iter().map(|selection| {
         get_validated_selection(
             schema,
+            options,
             selection,
             parent_object,
             selection_parent_object_id,
@@ -133,6 +155,7 @@ fn get_validated_selection_set<TNetworkProtocol: NetworkProtocol>(
 
 fn get_validated_selection<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
     with_span: WithSpan<UnvalidatedSelection>,
     selection_parent_object: &ServerObjectEntity<TNetworkProtocol>,
     selection_parent_object_id: ServerObjectEntityId,
@@ -142,6 +165,7 @@ fn get_validated_selection<TNetworkProtocol: NetworkProtocol>(
         SelectionType::Scalar(scalar_selection) => Ok(SelectionType::Scalar(
             get_validated_scalar_selection(
                 schema,
+                options,
                 selection_parent_object,
                 selection_parent_object_id,
                 top_level_field_or_pointer,
@@ -152,6 +176,7 @@ fn get_validated_selection<TNetworkProtocol: NetworkProtocol>(
         SelectionType::Object(object_selection) => {
             Ok(SelectionType::Object(get_validated_object_selection(
                 schema,
+                options,
                 selection_parent_object,
                 selection_parent_object_id,
                 top_level_field_or_pointer,
@@ -163,35 +188,33 @@ fn get_validated_selection<TNetworkProtocol: NetworkProtocol>(
 
 fn get_validated_scalar_selection<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
     selection_parent_object: &ServerObjectEntity<TNetworkProtocol>,
     selection_parent_object_id: ServerObjectEntityId,
     top_level_field_or_pointer: &impl ClientScalarOrObjectSelectable,
     scalar_selection: UnvalidatedScalarFieldSelection,
 ) -> AddSelectionSetsResult<ValidatedScalarSelection> {
-    let location = schema
-        .server_entity_data
-        .server_object_entity_extra_info
-        .get(&selection_parent_object_id)
-        .expect(
-            "Expected selection_parent_object_id to exist \
-            in server_object_entity_available_selectables",
+    let (location, selection_parent_object) = find_selectable(
+        schema,
+        options,
+        selection_parent_object,
+        selection_parent_object_id,
+        scalar_selection.name.item.into(),
+    )
+    .ok_or_else(|| {
+        WithLocation::new(
+            AddSelectionSetsError::SelectionTypeSelectionFieldDoesNotExist {
+                client_field_parent_type_name: top_level_field_or_pointer
+                    .type_and_field()
+                    .type_name,
+                client_field_name: top_level_field_or_pointer.type_and_field().field_name,
+                field_parent_type_name: selection_parent_object.name,
+                field_name: scalar_selection.name.item.into(),
+                client_type: top_level_field_or_pointer.client_type().to_string(),
+            },
+            scalar_selection.name.location,
         )
-        .selectables
-        .get(&scalar_selection.name.item.into())
-        .ok_or_else(|| {
-            WithLocation::new(
-                AddSelectionSetsError::SelectionTypeSelectionFieldDoesNotExist {
-                    client_field_parent_type_name: top_level_field_or_pointer
-                        .type_and_field()
-                        .type_name,
-                    client_field_name: top_level_field_or_pointer.type_and_field().field_name,
-                    field_parent_type_name: selection_parent_object.name,
-                    field_name: scalar_selection.name.item.into(),
-                    client_type: top_level_field_or_pointer.client_type().to_string(),
-                },
-                scalar_selection.name.location,
-            )
-        })?;
+    })?;
 
     let associated_data = match *location {
         DefinitionLocation::Server(server_selectable_id) => {
@@ -228,12 +251,19 @@ fn get_validated_scalar_selection<TNetworkProtocol: NetworkProtocol>(
                                 field_name: scalar_selection.name.item.into(),
                                 target_type_name: object.name.into(),
                                 client_type: top_level_field_or_pointer.client_type().to_string(),
-                                field_type: top_level_field_or_pointer.client_type(),
                             },
                             scalar_selection.name.location,
                         )
                     })?;
 
+            validate_server_field_is_not_restricted(
+                options.restrictions,
+                selection_parent_object,
+                scalar_selection.name.item.into(),
+                top_level_field_or_pointer,
+            )
+            .map_err(|e| WithLocation::new(e, scalar_selection.name.location))?;
+
             DefinitionLocation::Server(server_scalar_selectable_id)
         }
         DefinitionLocation::Client(client_type) => {
@@ -261,40 +291,58 @@ fn get_validated_scalar_selection<TNetworkProtocol: NetworkProtocol>(
         associated_data,
         scalar_selection_directive_set: scalar_selection.scalar_selection_directive_set,
         arguments: scalar_selection.arguments,
+        conditions: scalar_selection.conditions,
     })
 }
 
 fn get_validated_object_selection<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
     selection_parent_object: &ServerObjectEntity<TNetworkProtocol>,
     selection_parent_object_id: ServerObjectEntityId,
     top_level_field_or_pointer: &impl ClientScalarOrObjectSelectable,
     object_selection: ObjectSelection<(), ()>,
 ) -> ValidateAddSelectionSetsResultWithMultipleErrors<ValidatedObjectSelection> {
-    let location = schema
-        .server_entity_data
-        .server_object_entity_extra_info
-        .get(&selection_parent_object_id)
-        .expect(
-            "Expected selection_parent_object_id to exist \
-            in server_object_entity_available_selectables",
-        )
-        .selectables
-        .get(&object_selection.name.item.into())
-        .ok_or_else(|| {
-            vec![WithLocation::new(
-                AddSelectionSetsError::SelectionTypeSelectionFieldDoesNotExist {
+    let (location, selection_parent_object) = find_selectable(
+        schema,
+        options,
+        selection_parent_object,
+        selection_parent_object_id,
+        object_selection.name.item.into(),
+    )
+    .ok_or_else(|| {
+        // asConcreteType fields (and ... on ConcreteType) are only defined for the
+        // subtypes of an abstract type, so if the field is missing, but the type
+        // exists, it is not a valid refinement.
+        if let Some(type_to_refine_to) =
+            type_to_refine_to(schema, object_selection.name.item.lookup())
+        {
+            return vec![WithLocation::new(
+                AddSelectionSetsError::SelectionTypeSelectionInvalidTypeRefinement {
                     client_field_parent_type_name: top_level_field_or_pointer
                         .type_and_field()
                         .type_name,
                     client_field_name: top_level_field_or_pointer.type_and_field().field_name,
                     field_parent_type_name: selection_parent_object.name,
-                    field_name: object_selection.name.item.into(),
+                    type_to_refine_to,
                     client_type: top_level_field_or_pointer.client_type().to_string(),
                 },
                 object_selection.name.location,
-            )]
-        })?;
+            )];
+        }
+        vec![WithLocation::new(
+            AddSelectionSetsError::SelectionTypeSelectionFieldDoesNotExist {
+                client_field_parent_type_name: top_level_field_or_pointer
+                    .type_and_field()
+                    .type_name,
+                client_field_name: top_level_field_or_pointer.type_and_field().field_name,
+                field_parent_type_name: selection_parent_object.name,
+                field_name: object_selection.name.item.into(),
+                client_type: top_level_field_or_pointer.client_type().to_string(),
+            },
+            object_selection.name.location,
+        )]
+    })?;
 
     let (associated_data, new_parent_object_entity_id) = match *location {
         DefinitionLocation::Server(server_selectable_id) => {
@@ -317,10 +365,17 @@ fn get_validated_object_selection<TNetworkProtocol: NetworkProtocol>(
                             target_type_name: server_scalar.name.item.into(),
                             client_type: top_level_field_or_pointer.client_type().to_string(),
                         },
-                        Location::generated(),
+                        object_selection.name.location,
                     )]
                 },
             )?;
+            validate_server_field_is_not_restricted(
+                options.restrictions,
+                selection_parent_object,
+                object_selection.name.item.into(),
+                top_level_field_or_pointer,
+            )
+            .map_err(|e| vec![WithLocation::new(e, object_selection.name.location)])?;
             let server_object_selectable =
                 schema.server_object_selectable(server_object_selectable_id);
 
@@ -332,7 +387,7 @@ fn get_validated_object_selection<TNetworkProtocol: NetworkProtocol>(
         DefinitionLocation::Client(client_type) => {
             let client_pointer_id = *client_type.as_object().ok_or_else(|| {
                 vec![WithLocation::new(
-                    AddSelectionSetsError::SelectionTypeSelectionClientPointerSelectedAsScalar {
+                    AddSelectionSetsError::SelectionTypeSelectionClientFieldSelectedAsLinked {
                         client_field_parent_type_name: top_level_field_or_pointer
                             .type_and_field()
                             .type_name,
@@ -341,7 +396,7 @@ fn get_validated_object_selection<TNetworkProtocol: NetworkProtocol>(
                         field_name: object_selection.name.item.into(),
                         client_type: top_level_field_or_pointer.client_type().to_string(),
                     },
-                    Location::generated(),
+                    object_selection.name.location,
                 )]
             })?;
             let client_pointer = schema.client_pointer(client_pointer_id);
@@ -353,18 +408,51 @@ fn get_validated_object_selection<TNetworkProtocol: NetworkProtocol>(
         }
     };
 
+    if object_selection.selection_set.is_empty() {
+        return Err(vec![WithLocation::new(
+            AddSelectionSetsError::SelectionTypeSelectionEmptySelectionSet {
+                client_field_parent_type_name: top_level_field_or_pointer
+                    .type_and_field()
+                    .type_name,
+                client_field_name: top_level_field_or_pointer.type_and_field().field_name,
+                field_parent_type_name: selection_parent_object.name,
+                field_name: object_selection.name.item.into(),
+                client_type: top_level_field_or_pointer.client_type().to_string(),
+            },
+            object_selection.name.location,
+        )]);
+    }
+
     let new_parent_object = schema
         .server_entity_data
         .server_object_entity(new_parent_object_entity_id);
 
+    if new_parent_object.is_input_object {
+        return Err(vec![WithLocation::new(
+            AddSelectionSetsError::SelectionTypeSelectionFieldIsInputObject {
+                client_field_parent_type_name: top_level_field_or_pointer
+                    .type_and_field()
+                    .type_name,
+                client_field_name: top_level_field_or_pointer.type_and_field().field_name,
+                field_parent_type_name: selection_parent_object.name,
+                field_name: object_selection.name.item.into(),
+                target_type_name: new_parent_object.name,
+                client_type: top_level_field_or_pointer.client_type().to_string(),
+            },
+            object_selection.name.location,
+        )]);
+    }
+
     Ok(ObjectSelection {
         name: object_selection.name,
         reader_alias: object_selection.reader_alias,
         object_selection_directive_set: object_selection.object_selection_directive_set,
         associated_data,
         arguments: object_selection.arguments,
+        conditions: object_selection.conditions,
         selection_set: get_validated_selection_set(
             schema,
+            options,
             object_selection.selection_set,
             new_parent_object,
             new_parent_object_entity_id,
@@ -373,6 +461,99 @@ fn get_validated_object_selection<TNetworkProtocol: NetworkProtocol>(
     })
 }
 
+/// Find the selectable with this name on the parent. With the
+/// implicit_concrete_type_refinements option, a server field that an abstract parent
+/// does not define is looked up on the first of its concrete types (by name) that
+/// defines it, which is returned instead of the parent.
+fn find_selectable<'a, TNetworkProtocol: NetworkProtocol>(
+    schema: &'a Schema<TNetworkProtocol>,
+    options: SelectionSetValidationOptions<'_>,
+    selection_parent_object: &'a ServerObjectEntity<TNetworkProtocol>,
+    selection_parent_object_id: ServerObjectEntityId,
+    name: SelectableName,
+) -> Option<(
+    &'a DefinitionLocation<ServerSelectableId, ClientSelectableId>,
+    &'a ServerObjectEntity<TNetworkProtocol>,
+)> {
+    let selectables = |object_entity_id: ServerObjectEntityId| {
+        &schema
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&object_entity_id)
+            .expect(
+                "Expected selection_parent_object_id to exist \
+                in server_object_entity_available_selectables",
+            )
+            .selectables
+    };
+    if let Some(location) = selectables(selection_parent_object_id).get(&name) {
+        return Some((location, selection_parent_object));
+    }
+    if !options.implicit_concrete_type_refinements
+        || selection_parent_object.concrete_type.is_some()
+    {
+        return None;
+    }
+
+    let concrete_object_entity_id = concrete_types(schema, selection_parent_object_id)
+        .filter(|concrete_object_entity_id| {
+            defines_server_field(schema, *concrete_object_entity_id, name)
+        })
+        .min_by_key(|concrete_object_entity_id| {
+            schema
+                .server_entity_data
+                .server_object_entity(*concrete_object_entity_id)
+                .name
+                .to_string()
+        })?;
+    Some((
+        selectables(concrete_object_entity_id).get(&name)?,
+        schema
+            .server_entity_data
+            .server_object_entity(concrete_object_entity_id),
+    ))
+}
+
+fn validate_server_field_is_not_restricted<TNetworkProtocol: NetworkProtocol>(
+    restrictions: &ServerFieldRestrictions,
+    selection_parent_object: &ServerObjectEntity<TNetworkProtocol>,
+    field_name: SelectableName,
+    top_level_field_or_pointer: &impl ClientScalarOrObjectSelectable,
+) -> Result<(), AddSelectionSetsError> {
+    match restrictions.restricted_by(ObjectTypeAndFieldName {
+        type_name: selection_parent_object.name,
+        field_name,
+    }) {
+        Some(config_option) => Err(
+            AddSelectionSetsError::SelectionTypeSelectionFieldIsRestricted {
+                client_field_parent_type_name: top_level_field_or_pointer
+                    .type_and_field()
+                    .type_name,
+                client_field_name: top_level_field_or_pointer.type_and_field().field_name,
+                field_parent_type_name: selection_parent_object.name,
+                field_name,
+                client_type: top_level_field_or_pointer.client_type().to_string(),
+                config_option,
+            },
+        ),
+        None => Ok(()),
+    }
+}
+
+/// If `field_name` is of the form `asConcreteType`, and `ConcreteType` is a defined
+/// type, return `ConcreteType`.
+fn type_to_refine_to<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    field_name: &str,
+) -> Option<UnvalidatedTypeName> {
+    let type_name: UnvalidatedTypeName = field_name.strip_prefix("as")?.intern().into();
+    schema
+        .server_entity_data
+        .defined_entities
+        .contains_key(&type_name)
+        .then_some(type_name)
+}
+
 fn get_validated_refetch_strategy<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     refetch_strategy: Option<RefetchStrategy<(), ()>>,
@@ -385,8 +566,14 @@ fn get_validated_refetch_strategy<TNetworkProtocol: NetworkProtocol>(
     match refetch_strategy {
         Some(RefetchStrategy::UseRefetchField(use_refetch_field_strategy)) => Ok(Some(
             RefetchStrategy::UseRefetchField(UseRefetchFieldRefetchStrategy {
+                // Refetch selection sets are generated, not written by the user, so
+                // the server field restrictions do not apply.
                 refetch_selection_set: get_validated_selection_set(
                     schema,
+                    SelectionSetValidationOptions {
+                        restrictions: &ServerFieldRestrictions::default(),
+                        implicit_concrete_type_refinements: false,
+                    },
                     use_refetch_field_strategy.refetch_selection_set,
                     parent_object,
                     selection_parent_object_id,
@@ -440,14 +627,14 @@ pub enum AddSelectionSetsError {
     #[error(
         "In the client {client_type} `{client_field_parent_type_name}.{client_field_name}`, \
         the field `{field_parent_type_name}.{field_name}` is selected as a scalar, \
-        but that field's type is `{target_type_name}`, which is {field_type}."
+        but that field's type is `{target_type_name}`, which is not a scalar. \
+        Select it with a selection set."
     )]
     SelectionTypeSelectionFieldIsNotScalar {
         client_field_parent_type_name: IsographObjectTypeName,
         client_field_name: SelectableName,
         field_parent_type_name: IsographObjectTypeName,
         field_name: SelectableName,
-        field_type: &'static str,
         target_type_name: UnvalidatedTypeName,
         client_type: String,
     },
@@ -479,6 +666,111 @@ pub enum AddSelectionSetsError {
         client_type: String,
     },
 
+    #[error(
+        "In the client {client_type} `{client_field_parent_type_name}.{client_field_name}`, the \
+        client field `{field_parent_type_name}.{field_name}` is selected with a selection set. \
+        However, client fields can only be selected as scalars."
+    )]
+    SelectionTypeSelectionClientFieldSelectedAsLinked {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableName,
+        client_type: String,
+    },
+
+    #[error(
+        "In the client {client_type} `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected with an empty \
+        selection set. Select at least one field."
+    )]
+    SelectionTypeSelectionEmptySelectionSet {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableName,
+        client_type: String,
+    },
+
+    #[error(
+        "In the client {client_type} `{client_field_parent_type_name}.{client_field_name}`, \
+        `{field_parent_type_name}` is refined to `{type_to_refine_to}`, but \
+        `{type_to_refine_to}` is not a subtype of `{field_parent_type_name}`"
+    )]
+    SelectionTypeSelectionInvalidTypeRefinement {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableName,
+        field_parent_type_name: IsographObjectTypeName,
+        type_to_refine_to: UnvalidatedTypeName,
+        client_type: String,
+    },
+
+    #[error(
+        "In the client {client_type} `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected, but that field's \
+        type is `{target_type_name}`, which is an input object. Input objects can only \
+        be the types of arguments, and cannot be selected."
+    )]
+    SelectionTypeSelectionFieldIsInputObject {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableName,
+        target_type_name: IsographObjectTypeName,
+        client_type: String,
+    },
+
+    #[error(
+        "In the client {client_type} `{client_field_parent_type_name}.{client_field_name}`, \
+        the field `{field_parent_type_name}.{field_name}` is selected, but the \
+        {config_option} config option does not allow it to be selected."
+    )]
+    SelectionTypeSelectionFieldIsRestricted {
+        client_field_parent_type_name: IsographObjectTypeName,
+        client_field_name: SelectableName,
+        field_parent_type_name: IsographObjectTypeName,
+        field_name: SelectableName,
+        client_type: String,
+        config_option: &'static str,
+    },
+
     #[error("`{server_field_name}` is a server field, and cannot be selected with `@loadable`")]
     ServerFieldCannotBeSelectedLoadably { server_field_name: SelectableName },
 }
+
+impl HasErrorCode for AddSelectionSetsError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            AddSelectionSetsError::SelectionTypeSelectionFieldDoesNotExist { .. } => {
+                error_codes::IS0400
+            }
+            AddSelectionSetsError::SelectionTypeSelectionFieldIsNotScalar { .. } => {
+                error_codes::IS0401
+            }
+            AddSelectionSetsError::SelectionTypeSelectionFieldIsScalar { .. } => {
+                error_codes::IS0402
+            }
+            AddSelectionSetsError::SelectionTypeSelectionClientPointerSelectedAsScalar {
+                ..
+            } => error_codes::IS0403,
+            AddSelectionSetsError::ServerFieldCannotBeSelectedLoadably { .. } => {
+                error_codes::IS0404
+            }
+            AddSelectionSetsError::SelectionTypeSelectionInvalidTypeRefinement { .. } => {
+                error_codes::IS0405
+            }
+            AddSelectionSetsError::SelectionTypeSelectionClientFieldSelectedAsLinked { .. } => {
+                error_codes::IS0406
+            }
+            AddSelectionSetsError::SelectionTypeSelectionEmptySelectionSet { .. } => {
+                error_codes::IS0407
+            }
+            AddSelectionSetsError::SelectionTypeSelectionFieldIsInputObject { .. } => {
+                error_codes::IS0408
+            }
+            AddSelectionSetsError::SelectionTypeSelectionFieldIsRestricted { .. } => {
+                error_codes::IS0409
+            }
+        }
+    }
+}
@@ -1,12 +1,17 @@
-use std::{path::PathBuf, str::Utf8Error};
+use std::path::PathBuf;
 
 use crate::{
-    compiler_state::{compile, StandardSources},
+    compilation_stats::CompilationStats,
+    compiler_state::{compile_sources, StandardSources},
+    diagnostics::{diagnostics_from_error, Diagnostic, OutputFormat},
+    file_system::OsFileSystem,
     source_files::SourceFiles,
     with_duration::WithDuration,
 };
 use colored::Colorize;
 use common_lang_types::{CurrentWorkingDirectory, WithLocation};
+use intern::Lookup;
+use isograph_config::{create_configs, CompilerConfig};
 use isograph_lang_parser::IsographLiteralParseError;
 use isograph_schema::{NetworkProtocol, ProcessClientFieldDeclarationError};
 use pretty_duration::pretty_duration;
@@ -15,34 +20,146 @@ use tracing::{error, info};
 
 use crate::compiler_state::CompilerState;
 
-pub struct CompilationStats {
-    pub client_field_count: usize,
-    pub entrypoint_count: usize,
-    pub total_artifacts_written: usize,
-}
-
 pub fn compile_and_print<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
     config_location: PathBuf,
     current_working_directory: CurrentWorkingDirectory,
+    output_format: OutputFormat,
+    print_stats: bool,
+    keep_tmp_on_failure: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     info!("{}", "Starting to compile.".cyan());
-    print_result(WithDuration::new(|| {
-        let mut state = CompilerState::new(config_location, current_working_directory);
-        let sources = SourceFiles::read_all(&mut state.db, &state.config)?;
-        compile::<TNetworkProtocol>(&state.db, &sources, &state.config)
-    }))
+    let mut configs = create_configs(config_location, current_working_directory);
+    for config in configs.iter_mut() {
+        config.keep_tmp_on_failure = keep_tmp_on_failure;
+    }
+
+    if configs.len() == 1 {
+        let config = configs.pop().expect("Expected config to exist");
+        return compile_project_and_print::<TNetworkProtocol>(config, output_format, print_stats);
+    }
+
+    // Projects are independent, so we compile them in parallel. Each project
+    // has its own database, which is created on the thread that uses it.
+    let failed_projects = std::thread::scope(|scope| {
+        let handles = configs
+            .into_iter()
+            .map(|config| {
+                let project_name = config.project_name.clone().unwrap_or_default();
+                let handle = scope.spawn(move || {
+                    compile_project_and_print::<TNetworkProtocol>(
+                        config,
+                        output_format,
+                        print_stats,
+                    )
+                    .is_ok()
+                });
+                (project_name, handle)
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .filter_map(|(project_name, handle)| {
+                let succeeded = handle.join().unwrap_or(false);
+                (!succeeded).then_some(project_name)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    if failed_projects.is_empty() {
+        Ok(())
+    } else {
+        Err(Box::new(BatchCompileError::ProjectsFailedToCompile {
+            project_names: failed_projects,
+        }))
+    }
 }
 
+fn compile_project_and_print<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config: CompilerConfig,
+    output_format: OutputFormat,
+    print_stats: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut state = CompilerState::new(config);
+    print_result(
+        WithDuration::new(|| {
+            let sources = SourceFiles::read_all(&mut state.db, &OsFileSystem, &state.config)?;
+            compile_sources::<TNetworkProtocol>(
+                &state.db,
+                &OsFileSystem,
+                &sources,
+                &state.config,
+                state.persist_artifacts.as_mut(),
+            )
+        }),
+        state.config.project_name.as_deref(),
+        output_format,
+        print_stats,
+    )
+}
+
+/// Print the result of compiling a project. If the config contains multiple
+/// projects, messages are prefixed with (and diagnostics contain) the project name.
 pub fn print_result(
     result: WithDuration<Result<CompilationStats, Box<dyn std::error::Error>>>,
+    project_name: Option<&str>,
+    output_format: OutputFormat,
+    print_stats: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let elapsed_time = result.elapsed_time;
+    let prefix = project_name
+        .map(|project_name| format!("[{project_name}] "))
+        .unwrap_or_default();
     match result.item {
+        Ok(stats) if !stats.skipped_iso_literals.is_empty() => {
+            let skipped_iso_literals = &stats.skipped_iso_literals;
+            let skipped_files = skipped_iso_literals
+                .files
+                .iter()
+                .map(|file| format!("\n  {}", file.lookup()))
+                .collect::<String>();
+            match output_format {
+                OutputFormat::Human => error!(
+                    "{}\n{}\n{}",
+                    format!(
+                        "{prefix}Compiled {} client fields and {} entrypoints, and wrote {} \
+                        artifacts, but skipped the following files, whose artifacts were \
+                        not updated:{skipped_files}",
+                        stats.client_field_count,
+                        stats.entrypoint_count,
+                        stats.total_artifacts_written,
+                    )
+                    .bright_red(),
+                    render_diagnostics(&skipped_iso_literals.diagnostics),
+                    format!("Compilation took {}.", pretty_duration(&elapsed_time, None))
+                        .bright_red()
+                ),
+                OutputFormat::Json => {
+                    print_json_diagnostics(&skipped_iso_literals.diagnostics, project_name);
+                    error!(
+                        "{}",
+                        format!(
+                            "{prefix}Skipped {} files with invalid iso literals, whose \
+                            artifacts were not updated, in {}.",
+                            skipped_iso_literals.files.len(),
+                            pretty_duration(&elapsed_time, None)
+                        )
+                        .bright_red()
+                    );
+                }
+            }
+            if print_stats {
+                stats.print_report(elapsed_time, project_name, output_format);
+            }
+            Err(Box::new(BatchCompileError::SkippedInvalidIsoLiterals {
+                file_count: skipped_iso_literals.files.len(),
+            }))
+        }
         Ok(stats) => {
             info!(
                 "{}",
                 format!(
-                    "Successfully compiled {} client fields and {} \
+                    "{prefix}Successfully compiled {} client fields and {} \
                         entrypoints, and wrote {} artifacts, in {}.",
                     stats.client_field_count,
                     stats.entrypoint_count,
@@ -50,17 +167,75 @@ pub fn print_result(
                     pretty_duration(&elapsed_time, None)
                 )
             );
+            if print_stats {
+                stats.print_report(elapsed_time, project_name, output_format);
+            }
             Ok(())
         }
-        Err(err) => {
-            error!(
-                "{}\n{}\n{}",
-                "Error when compiling.\n".bright_red(),
-                err,
-                format!("Compilation took {}.", pretty_duration(&elapsed_time, None)).bright_red()
+        Err(err)
+            if matches!(
+                err.downcast_ref::<BatchCompileError>(),
+                Some(BatchCompileError::Cancelled)
+            ) =>
+        {
+            info!(
+                "{}",
+                format!(
+                    "{prefix}Newer file changes detected. Cancelled the compilation after {}.",
+                    pretty_duration(&elapsed_time, None)
+                )
+                .cyan()
             );
             Err(err)
         }
+        Err(err) => match output_format {
+            OutputFormat::Human => {
+                let rendered = render_diagnostics(&diagnostics_from_error(err.as_ref()));
+                error!(
+                    "{}\n{}\n{}",
+                    format!("{prefix}Error when compiling.").bright_red(),
+                    rendered,
+                    format!("Compilation took {}.", pretty_duration(&elapsed_time, None))
+                        .bright_red()
+                );
+                Err(err)
+            }
+            OutputFormat::Json => {
+                let diagnostics = diagnostics_from_error(err.as_ref());
+                print_json_diagnostics(&diagnostics, project_name);
+                error!(
+                    "{}",
+                    format!(
+                        "{prefix}Compilation failed with {} {} in {}.",
+                        diagnostics.len(),
+                        if diagnostics.len() == 1 {
+                            "error"
+                        } else {
+                            "errors"
+                        },
+                        pretty_duration(&elapsed_time, None)
+                    )
+                    .bright_red()
+                );
+                Err(err)
+            }
+        },
+    }
+}
+
+fn render_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .fold(String::new(), |mut output, diagnostic| {
+            output.push_str(&format!("\n{diagnostic}\n"));
+            output
+        })
+}
+
+fn print_json_diagnostics(diagnostics: &[Diagnostic], project_name: Option<&str>) {
+    for mut diagnostic in diagnostics.iter().cloned() {
+        diagnostic.project = project_name.map(|x| x.to_string());
+        println!("{}", diagnostic.to_json());
     }
 }
 
@@ -120,8 +295,23 @@ pub enum BatchCompileError {
     #[error("Unable to strip prefix.\nReason: {0}")]
     UnableToStripPrefix(#[from] std::path::StripPrefixError),
 
-    #[error("Unable to convert file {path:?} to utf8.\nDetailed reason: {reason}")]
-    UnableToConvertToString { path: PathBuf, reason: Utf8Error },
+    #[error(
+        "Compilation failed for the following projects: {}",
+        project_names.join(", ")
+    )]
+    ProjectsFailedToCompile { project_names: Vec<String> },
+
+    #[error(
+        "Skipped {file_count} {} with invalid iso literals.",
+        if *file_count == 1 { "file" } else { "files" }
+    )]
+    SkippedInvalidIsoLiterals { file_count: usize },
+
+    #[error("The new schema would break client fields or entrypoints.")]
+    SchemaChangeWouldBreakClientFields,
+
+    #[error("The compilation was cancelled, because newer file changes were detected.")]
+    Cancelled,
 
     #[error("The __refetch field was already defined. Isograph creates it automatically; you cannot create it.")]
     DuplicateRefetchField,
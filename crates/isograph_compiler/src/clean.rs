@@ -0,0 +1,505 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use common_lang_types::CurrentWorkingDirectory;
+use intern::{string_key::Intern, Lookup};
+use isograph_config::{create_configs, ArtifactLayout, CompilerConfig, ISOGRAPH_FOLDER};
+use isograph_lang_parser::IsoLiteralExtractionResult;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::{
+    batch_compile::BatchCompileError,
+    file_system::{FileSystem, OsFileSystem},
+    isograph_literals::{
+        client_type_declaration, parse_iso_literals_in_file_content, read_files_in_folder,
+        ClientTypeKey,
+    },
+    write_artifacts::{journal_path, replaced_artifact_directory, temporary_artifact_directory},
+};
+
+/// The start of the comment added by the generate_artifact_provenance option.
+const PROVENANCE_PREFIX: &str = "// @generated-by isograph ";
+
+/// Remove the artifact directory of each project in the config. If orphans is set,
+/// instead only remove the artifacts of client fields, client pointers and
+/// entrypoints that are no longer declared in any iso literal. If dry_run is set,
+/// print what would be removed, without removing anything.
+pub fn clean_and_print(
+    config_location: PathBuf,
+    current_working_directory: CurrentWorkingDirectory,
+    orphans: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn Error>> {
+    let removed = if dry_run { "Would remove" } else { "Removed" };
+    for config in create_configs(config_location, current_working_directory) {
+        let prefix = config
+            .project_name
+            .as_ref()
+            .map(|project_name| format!("[{project_name}] "))
+            .unwrap_or_default();
+        let artifact_directory = &config.artifact_directory.absolute_path;
+
+        if !orphans {
+            for directory in remove_artifact_directory(&OsFileSystem, artifact_directory, dry_run)?
+            {
+                info!("{prefix}{removed} {}", directory.display());
+            }
+            continue;
+        }
+
+        if !config.options.generate_artifact_provenance {
+            warn!(
+                "{prefix}generate_artifact_provenance is not enabled, so artifacts do not \
+                record which iso literal they were generated from. Only artifacts that \
+                were generated with it enabled can be removed."
+            );
+        }
+        let declared_client_types = DeclaredClientTypes::read(&OsFileSystem, &config)?;
+        let orphaned_artifacts = find_orphaned_artifacts(
+            &OsFileSystem,
+            artifact_directory,
+            config.options.artifact_layout,
+            &config.project_root,
+            &declared_client_types,
+        )?;
+        if !dry_run {
+            remove_artifacts(&OsFileSystem, artifact_directory, &orphaned_artifacts)?;
+        }
+        for path in &orphaned_artifacts {
+            info!(
+                "{prefix}{removed} {}",
+                Path::new(config.artifact_directory.relative_path.lookup())
+                    .join(path)
+                    .display()
+            );
+        }
+        info!(
+            "{}",
+            format!(
+                "{prefix}{removed} {} orphaned {}.",
+                orphaned_artifacts.len(),
+                if orphaned_artifacts.len() == 1 {
+                    "artifact"
+                } else {
+                    "artifacts"
+                }
+            )
+            .bright_green()
+        );
+    }
+    Ok(())
+}
+
+/// Remove the artifact directory, along with the temporary directories and the
+/// journal that are left behind if the compiler is killed while writing artifacts.
+/// Returns the paths that were (or, if dry_run is set, would have been) removed.
+///
+/// Only directories named __isograph are removed, so that a misconfigured artifact
+/// directory cannot cause source files to be deleted.
+pub(crate) fn remove_artifact_directory(
+    fs: &dyn FileSystem,
+    artifact_directory: &Path,
+    dry_run: bool,
+) -> Result<Vec<PathBuf>, CleanError> {
+    if artifact_directory
+        .file_name()
+        .and_then(|name| name.to_str())
+        != Some(ISOGRAPH_FOLDER)
+    {
+        return Err(CleanError::NotAnArtifactDirectory {
+            path: artifact_directory.to_path_buf(),
+        });
+    }
+
+    let mut removed = vec![];
+    for directory in [
+        artifact_directory.to_path_buf(),
+        temporary_artifact_directory(artifact_directory),
+        replaced_artifact_directory(artifact_directory),
+    ] {
+        if !fs.is_dir(&directory) {
+            continue;
+        }
+        if !dry_run {
+            fs.remove_dir_all(&directory)?;
+        }
+        removed.push(directory);
+    }
+
+    let journal_path = journal_path(artifact_directory);
+    if fs.is_file(&journal_path) {
+        if !dry_run {
+            fs.remove_file(&journal_path)?;
+        }
+        removed.push(journal_path);
+    }
+    Ok(removed)
+}
+
+/// The client fields, client pointers and entrypoints declared in the iso literals
+/// of a project.
+#[derive(Debug, Default)]
+pub(crate) struct DeclaredClientTypes {
+    declared: HashSet<ClientTypeKey>,
+    /// We do not know what the files containing invalid iso literals declare, so
+    /// the artifacts generated from them are never considered orphaned.
+    files_with_invalid_iso_literals: HashSet<PathBuf>,
+}
+
+impl DeclaredClientTypes {
+    fn read(fs: &dyn FileSystem, config: &CompilerConfig) -> Result<Self, BatchCompileError> {
+        let mut declared_client_types = Self::default();
+        for (relative_path, content) in read_files_in_folder(
+            fs,
+            &config.project_root,
+            config.current_working_directory,
+            &config.source_file_filter,
+        )? {
+            match parse_iso_literals_in_file_content(
+                relative_path,
+                &content,
+                config.current_working_directory,
+                config.options.iso_literal_extractor,
+            ) {
+                Ok(extraction_results) => declared_client_types.declared.extend(
+                    extraction_results
+                        .iter()
+                        .map(|(extraction_result, _)| declared_client_type(extraction_result)),
+                ),
+                Err(_) => {
+                    declared_client_types
+                        .files_with_invalid_iso_literals
+                        .insert(
+                            Path::new(config.current_working_directory.lookup())
+                                .join(relative_path.lookup()),
+                        );
+                }
+            }
+        }
+        Ok(declared_client_types)
+    }
+}
+
+fn declared_client_type(extraction_result: &IsoLiteralExtractionResult) -> ClientTypeKey {
+    match extraction_result {
+        IsoLiteralExtractionResult::EntrypointDeclaration(declaration) => (
+            declaration.item.parent_type.item,
+            declaration.item.client_field_name.item.into(),
+        ),
+        _ => {
+            client_type_declaration(extraction_result)
+                .expect("Expected client field or client pointer declaration")
+                .0
+        }
+    }
+}
+
+/// Find the artifacts (relative to the artifact directory) that were generated from
+/// an iso literal that no longer exists.
+///
+/// An artifact is orphaned if its provenance comment records that it was generated
+/// from an iso literal, and no iso literal declares its type and field. Artifacts
+/// without provenance comments (e.g. iso.ts, or the artifacts of server fields) are
+/// never orphaned.
+pub(crate) fn find_orphaned_artifacts(
+    fs: &dyn FileSystem,
+    artifact_directory: &Path,
+    artifact_layout: ArtifactLayout,
+    project_root: &Path,
+    declared_client_types: &DeclaredClientTypes,
+) -> io::Result<Vec<PathBuf>> {
+    let mut artifacts = vec![];
+    visit_files(fs, artifact_directory, &mut artifacts)?;
+    artifacts.sort();
+
+    let mut orphaned_artifacts = vec![];
+    for path in artifacts {
+        let relative_path = path
+            .strip_prefix(artifact_directory)
+            .expect("Expected artifact to be in the artifact directory");
+        let Some((type_name, field_name)) = artifact_layout.type_and_field_of_path(relative_path)
+        else {
+            continue;
+        };
+        let Ok(content) = fs.read_to_string(&path) else {
+            continue;
+        };
+        let Some(source_file) = provenance_source_file(&content) else {
+            continue;
+        };
+        let is_declared = declared_client_types
+            .declared
+            .contains(&(type_name.intern().into(), field_name.intern().into()));
+        let is_from_invalid_file = declared_client_types
+            .files_with_invalid_iso_literals
+            .contains(&project_root.join(source_file));
+        if !is_declared && !is_from_invalid_file {
+            orphaned_artifacts.push(relative_path.to_path_buf());
+        }
+    }
+    Ok(orphaned_artifacts)
+}
+
+fn visit_files(fs: &dyn FileSystem, directory: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for path in fs.read_dir(directory)? {
+        if fs.is_dir(&path) {
+            visit_files(fs, &path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The file (relative to the project root) recorded in the provenance comment of an
+/// artifact, e.g. components/PetDetail.tsx in
+///
+/// // @generated-by isograph 0.3.1 from components/PetDetail.tsx:12
+///
+/// The provenance comment follows the generated_file_header, if any.
+fn provenance_source_file(content: &str) -> Option<&str> {
+    let provenance = content
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .find_map(|line| line.strip_prefix(PROVENANCE_PREFIX))?;
+    let (_version, source) = provenance.split_once(" from ")?;
+    let (source_file, _line) = source.rsplit_once(':')?;
+    Some(source_file)
+}
+
+/// Remove the artifacts (relative to the artifact directory), along with the
+/// directories that are left empty.
+pub(crate) fn remove_artifacts(
+    fs: &dyn FileSystem,
+    artifact_directory: &Path,
+    artifacts: &[PathBuf],
+) -> io::Result<()> {
+    for artifact in artifacts {
+        let path = artifact_directory.join(artifact);
+        fs.remove_file(&path)?;
+        for directory in path.ancestors().skip(1) {
+            if directory == artifact_directory || !fs.read_dir(directory)?.is_empty() {
+                break;
+            }
+            fs.remove_dir(directory)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum CleanError {
+    #[error(
+        "Refusing to remove {}, since it is not named {ISOGRAPH_FOLDER}.",
+        path.display()
+    )]
+    NotAnArtifactDirectory { path: PathBuf },
+
+    #[error("{0}")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use common_lang_types::ObjectTypeAndFieldName;
+
+    use crate::InMemoryFileSystem;
+
+    use super::*;
+
+    const ARTIFACT_DIRECTORY: &str = "/project/src/__isograph";
+
+    fn key(type_name: &str, field_name: &str) -> ClientTypeKey {
+        (type_name.intern().into(), field_name.intern().into())
+    }
+
+    fn artifact_path(
+        artifact_layout: ArtifactLayout,
+        type_name: &str,
+        field_name: &str,
+        file_name: &str,
+    ) -> PathBuf {
+        artifact_layout.generate_path(
+            Some(ObjectTypeAndFieldName {
+                type_name: type_name.intern().into(),
+                field_name: field_name.intern().into(),
+            }),
+            file_name.intern().into(),
+        )
+    }
+
+    fn provenance(source_file: &str) -> String {
+        format!(
+            "// @generated-by isograph 0.3.1 from {source_file}:3\n\
+            // @source-hash 5d41402abc4b2a76b9719d911017c592\n\
+            export default 'artifact';\n"
+        )
+    }
+
+    #[test]
+    fn removes_the_artifact_directory_and_temporary_directories() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/src/__isograph/iso.ts", "");
+        fs.add_file("/project/src/__isograph.old/iso.ts", "");
+        fs.add_file("/project/src/HomeRoute.tsx", "");
+
+        let removed = remove_artifact_directory(&fs, Path::new(ARTIFACT_DIRECTORY), false).unwrap();
+
+        assert_eq!(
+            removed,
+            vec![
+                PathBuf::from("/project/src/__isograph"),
+                PathBuf::from("/project/src/__isograph.old"),
+            ]
+        );
+        assert_eq!(
+            fs.files().into_keys().collect::<Vec<_>>(),
+            vec![PathBuf::from("/project/src/HomeRoute.tsx")]
+        );
+
+        assert!(matches!(
+            remove_artifact_directory(&fs, Path::new("/project/src"), false),
+            Err(CleanError::NotAnArtifactDirectory { .. })
+        ));
+        assert!(fs.is_file(Path::new("/project/src/HomeRoute.tsx")));
+    }
+
+    #[test]
+    fn removes_only_orphaned_artifacts() {
+        for artifact_layout in [
+            ArtifactLayout::Nested,
+            ArtifactLayout::Flat,
+            ArtifactLayout::FolderPerType,
+        ] {
+            let artifact_directory = Path::new(ARTIFACT_DIRECTORY);
+            let fs = InMemoryFileSystem::new();
+            let add_artifact = |type_name, field_name, file_name, content: &str| {
+                fs.add_file(
+                    artifact_directory.join(artifact_path(
+                        artifact_layout,
+                        type_name,
+                        field_name,
+                        file_name,
+                    )),
+                    content,
+                );
+            };
+            fs.add_file(artifact_directory.join("iso.ts"), "");
+            add_artifact(
+                "Query",
+                "HomeRoute",
+                "entrypoint.ts",
+                &provenance("HomeRoute.tsx"),
+            );
+            add_artifact(
+                "Pet",
+                "PetAvatar",
+                "resolver_reader.ts",
+                &provenance("PetAvatar.tsx"),
+            );
+            add_artifact(
+                "Pet",
+                "PetAvatar",
+                "param_type.ts",
+                &provenance("PetAvatar.tsx"),
+            );
+            add_artifact(
+                "Pet",
+                "PetSummary",
+                "resolver_reader.ts",
+                &provenance("PetSummary.tsx"),
+            );
+            add_artifact(
+                "Pet",
+                "__refetch",
+                "refetch_reader.ts",
+                "// @generated-by isograph 0.3.1\n",
+            );
+            add_artifact("Pet", "PetName", "resolver_reader.ts", "");
+
+            let declared_client_types = DeclaredClientTypes {
+                declared: HashSet::from([key("Query", "HomeRoute")]),
+                files_with_invalid_iso_literals: HashSet::from([PathBuf::from(
+                    "/project/src/PetSummary.tsx",
+                )]),
+            };
+            let orphaned_artifacts = find_orphaned_artifacts(
+                &fs,
+                artifact_directory,
+                artifact_layout,
+                Path::new("/project/src"),
+                &declared_client_types,
+            )
+            .unwrap();
+
+            assert_eq!(
+                orphaned_artifacts,
+                vec![
+                    artifact_path(artifact_layout, "Pet", "PetAvatar", "param_type.ts"),
+                    artifact_path(artifact_layout, "Pet", "PetAvatar", "resolver_reader.ts"),
+                ],
+                "{artifact_layout:?}"
+            );
+
+            remove_artifacts(&fs, artifact_directory, &orphaned_artifacts).unwrap();
+            assert!(
+                !fs.exists(&artifact_directory.join(artifact_path(
+                    artifact_layout,
+                    "Pet",
+                    "PetAvatar",
+                    "resolver_reader.ts"
+                ))),
+                "{artifact_layout:?}"
+            );
+            assert_eq!(fs.files().len(), 5, "{artifact_layout:?}");
+        }
+    }
+
+    #[test]
+    fn removes_directories_left_empty() {
+        let artifact_directory = Path::new(ARTIFACT_DIRECTORY);
+        let fs = InMemoryFileSystem::new();
+        fs.add_file(artifact_directory.join("iso.ts"), "");
+        fs.add_file(
+            artifact_directory.join("Pet/PetAvatar/resolver_reader.ts"),
+            provenance("PetAvatar.tsx"),
+        );
+
+        remove_artifacts(
+            &fs,
+            artifact_directory,
+            &[PathBuf::from("Pet/PetAvatar/resolver_reader.ts")],
+        )
+        .unwrap();
+
+        assert!(!fs.is_dir(&artifact_directory.join("Pet")));
+        assert!(fs.is_dir(artifact_directory));
+    }
+
+    #[test]
+    fn reads_the_provenance_after_the_generated_file_header() {
+        assert_eq!(
+            provenance_source_file(&format!(
+                "// This file is generated.\n{}",
+                provenance("components/PetDetail.tsx")
+            )),
+            Some("components/PetDetail.tsx")
+        );
+        assert_eq!(
+            provenance_source_file("// @generated-by isograph 0.3.1\nexport default '';\n"),
+            None
+        );
+        assert_eq!(
+            provenance_source_file(
+                "export default '';\n// @generated-by isograph 0.3.1 from A.tsx:1\n"
+            ),
+            None
+        );
+    }
+}
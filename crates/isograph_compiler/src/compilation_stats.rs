@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use pretty_duration::pretty_duration;
+use serde_json::json;
+
+use crate::{diagnostics::OutputFormat, skip_invalid_iso_literals::SkippedIsoLiterals};
+
+#[derive(Debug)]
+pub struct CompilationStats {
+    pub server_object_count: usize,
+    pub client_field_count: usize,
+    pub client_pointer_count: usize,
+    pub entrypoint_count: usize,
+    pub total_artifacts_written: usize,
+    pub phase_timings: PhaseTimings,
+    /// The most memory that the process has used at any point, in bytes. None
+    /// if this cannot be measured on this platform.
+    pub peak_memory_bytes: Option<u64>,
+    /// The source files that were skipped because they contain invalid iso
+    /// literals. Empty unless `skip_invalid_iso_literals` is set.
+    pub skipped_iso_literals: SkippedIsoLiterals,
+}
+
+/// How long each phase of a compilation took.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PhaseTimings {
+    /// Parsing the schema and schema extensions, and adding the server
+    /// fields to the schema.
+    pub schema_parsing: Duration,
+    /// Extracting and parsing iso literals.
+    pub iso_literal_extraction: Duration,
+    /// Adding client fields, pointers and entrypoints to the schema, and
+    /// validating their selection sets and use of arguments.
+    pub validation: Duration,
+    /// Generating the in-memory representation of artifacts.
+    pub artifact_generation: Duration,
+    /// Writing artifacts to disk.
+    pub file_writing: Duration,
+}
+
+impl PhaseTimings {
+    fn phases(&self) -> [(&'static str, Duration); 5] {
+        [
+            ("schema_parsing", self.schema_parsing),
+            ("iso_literal_extraction", self.iso_literal_extraction),
+            ("validation", self.validation),
+            ("artifact_generation", self.artifact_generation),
+            ("file_writing", self.file_writing),
+        ]
+    }
+}
+
+impl CompilationStats {
+    fn counts(&self) -> [(&'static str, usize); 5] {
+        [
+            ("server_objects", self.server_object_count),
+            ("client_fields", self.client_field_count),
+            ("client_pointers", self.client_pointer_count),
+            ("entrypoints", self.entrypoint_count),
+            ("artifacts_written", self.total_artifacts_written),
+        ]
+    }
+
+    /// Print a report of the time spent in each phase and of the number of
+    /// items compiled. The report is printed to stdout, either as a table or
+    /// as a single line of JSON.
+    pub fn print_report(
+        &self,
+        total_time: Duration,
+        project_name: Option<&str>,
+        output_format: OutputFormat,
+    ) {
+        match output_format {
+            OutputFormat::Human => println!("{}", self.table(total_time, project_name)),
+            OutputFormat::Json => println!("{}", self.json(total_time, project_name)),
+        }
+    }
+
+    fn table(&self, total_time: Duration, project_name: Option<&str>) -> String {
+        let width = self
+            .phases_and_counts_names()
+            .map(|name| name.len())
+            .max()
+            .unwrap_or_default();
+
+        let mut output = String::new();
+        if let Some(project_name) = project_name {
+            output.push_str(&format!("project: {project_name}\n\n"));
+        }
+        output.push_str(&format!("{:width$}  time\n", "phase"));
+        for (name, duration) in self.phase_timings.phases() {
+            output.push_str(&format!(
+                "{name:width$}  {}\n",
+                pretty_duration(&duration, None)
+            ));
+        }
+        output.push_str(&format!(
+            "{:width$}  {}\n\n",
+            "total",
+            pretty_duration(&total_time, None)
+        ));
+        output.push_str(&format!("{:width$}  count\n", "item"));
+        for (name, count) in self.counts() {
+            output.push_str(&format!("{name:width$}  {count}\n"));
+        }
+        if let Some(peak_memory_bytes) = self.peak_memory_bytes {
+            output.push_str(&format!(
+                "\n{:width$}  {:.1} MB\n",
+                "peak_memory",
+                peak_memory_bytes as f64 / (1024.0 * 1024.0)
+            ));
+        }
+        output
+    }
+
+    fn phases_and_counts_names(&self) -> impl Iterator<Item = &'static str> {
+        self.phase_timings
+            .phases()
+            .into_iter()
+            .map(|(name, _)| name)
+            .chain(self.counts().into_iter().map(|(name, _)| name))
+    }
+
+    fn json(&self, total_time: Duration, project_name: Option<&str>) -> String {
+        let mut timings_ms = serde_json::Map::new();
+        for (name, duration) in self.phase_timings.phases() {
+            timings_ms.insert(name.to_string(), json!(duration.as_secs_f64() * 1000.0));
+        }
+        timings_ms.insert(
+            "total".to_string(),
+            json!(total_time.as_secs_f64() * 1000.0),
+        );
+
+        let mut counts = serde_json::Map::new();
+        for (name, count) in self.counts() {
+            counts.insert(name.to_string(), json!(count));
+        }
+
+        let mut stats = serde_json::Map::new();
+        if let Some(project_name) = project_name {
+            stats.insert("project".to_string(), json!(project_name));
+        }
+        stats.insert("timings_ms".to_string(), json!(timings_ms));
+        stats.insert("counts".to_string(), json!(counts));
+        stats.insert(
+            "peak_memory_bytes".to_string(),
+            json!(self.peak_memory_bytes),
+        );
+
+        json!({ "stats": stats }).to_string()
+    }
+}
+
+/// The peak resident set size of the process. This includes memory used by
+/// previous compilations (e.g. in watch mode), not only by the current one.
+#[cfg(unix)]
+pub(crate) fn peak_memory_bytes() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: usage.as_mut_ptr() is a valid, aligned pointer to a rusage that
+    // getrusage may write to, and RUSAGE_SELF is a valid `who` argument. getrusage
+    // fully initializes the rusage when it returns 0, and we only call assume_init
+    // in that case.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let max_rss = u64::try_from(usage.ru_maxrss).ok()?;
+    // ru_maxrss is in bytes on macOS, and in kilobytes on other platforms.
+    if cfg!(target_os = "macos") {
+        Some(max_rss)
+    } else {
+        Some(max_rss * 1024)
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn peak_memory_bytes() -> Option<u64> {
+    None
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use isograph_config::CompilerConfig;
+use isograph_schema::NetworkProtocol;
+use pico::Database;
+
+use crate::{
+    compilation_stats::CompilationStats,
+    compiler_state::{compile_sources, StandardSources},
+    diagnostics::{diagnostics_from_error, Diagnostic},
+    file_system::FileSystem,
+    persist_artifacts::{persist_artifacts_for_config, PersistArtifacts},
+    source_files::SourceFiles,
+    with_duration::WithDuration,
+};
+
+/// Receives the outcome of a compilation as it happens. Build tools that embed
+/// the compiler implement this to surface diagnostics in their own UI, instead of
+/// the compiler printing them.
+pub trait Reporter {
+    /// Called once for each diagnostic, in the order in which they are found.
+    fn report_diagnostic(&mut self, _diagnostic: &Diagnostic) {}
+
+    /// Called if the artifacts were written.
+    fn report_stats(&mut self, _stats: &CompilationStats) {}
+}
+
+/// A reporter that ignores everything. The diagnostics and stats are still
+/// available on the CompileResult.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopReporter;
+
+impl Reporter for NoopReporter {}
+
+/// The outcome of calling [`compile`].
+#[derive(Debug)]
+pub struct CompileResult {
+    /// Some if the artifacts were written.
+    pub stats: Option<CompilationStats>,
+    /// Empty if the compilation succeeded. If invalid iso literals were skipped
+    /// (see `skip_invalid_iso_literals`), the artifacts were written, and this
+    /// contains the errors because of which they were skipped.
+    pub diagnostics: Vec<Diagnostic>,
+    pub elapsed_time: Duration,
+}
+
+impl CompileResult {
+    pub fn succeeded(&self) -> bool {
+        self.stats.is_some() && self.diagnostics.is_empty()
+    }
+}
+
+/// Compile a single project, i.e. read its schema and source files from fs and
+/// write the generated artifacts to fs. This is the entry point for build tools
+/// that embed the compiler instead of running the CLI.
+///
+/// The artifacts are persisted as configured by the artifact_persistence option,
+/// and the artifact directory is recreated from scratch on each call. Use
+/// `isograph_config::create_configs` to load the configs of the projects in a
+/// config file.
+pub fn compile<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config: &CompilerConfig,
+    fs: &dyn FileSystem,
+    reporter: &mut dyn Reporter,
+) -> CompileResult {
+    compile_with_persistence::<TNetworkProtocol>(
+        config,
+        fs,
+        persist_artifacts_for_config(config).as_mut(),
+        reporter,
+    )
+}
+
+/// Like [`compile`], but the artifacts are persisted by persist_artifacts instead
+/// of as configured, e.g. kept in memory with `InMemoryArtifacts`. Reusing the
+/// same persist_artifacts across calls only persists the artifacts that changed.
+pub fn compile_with_persistence<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config: &CompilerConfig,
+    fs: &dyn FileSystem,
+    persist_artifacts: &mut dyn PersistArtifacts,
+    reporter: &mut dyn Reporter,
+) -> CompileResult {
+    let WithDuration { elapsed_time, item } = WithDuration::new(|| {
+        let mut db = Database::new();
+        let source_files = SourceFiles::read_all(&mut db, fs, config)?;
+        compile_sources::<TNetworkProtocol>(&db, fs, &source_files, config, persist_artifacts)
+    });
+
+    match item {
+        Ok(stats) => {
+            let diagnostics = stats
+                .skipped_iso_literals
+                .diagnostics
+                .iter()
+                .cloned()
+                .map(|mut diagnostic| {
+                    diagnostic.project = config.project_name.clone();
+                    reporter.report_diagnostic(&diagnostic);
+                    diagnostic
+                })
+                .collect();
+            reporter.report_stats(&stats);
+            CompileResult {
+                stats: Some(stats),
+                diagnostics,
+                elapsed_time,
+            }
+        }
+        Err(err) => {
+            let diagnostics = diagnostics_from_error(err.as_ref())
+                .into_iter()
+                .map(|mut diagnostic| {
+                    diagnostic.project = config.project_name.clone();
+                    reporter.report_diagnostic(&diagnostic);
+                    diagnostic
+                })
+                .collect();
+            CompileResult {
+                stats: None,
+                diagnostics,
+                elapsed_time,
+            }
+        }
+    }
+}
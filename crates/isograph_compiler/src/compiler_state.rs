@@ -1,22 +1,36 @@
 use std::{
     collections::BTreeMap,
     error::Error,
-    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 
-use common_lang_types::{CurrentWorkingDirectory, RelativePathToSourceFile, WithLocation};
+use common_lang_types::{RelativePathToSourceFile, WithLocation};
 use generate_artifacts::get_artifact_path_and_content;
-use isograph_config::{create_config, CompilerConfig};
+use isograph_config::CompilerConfig;
 use isograph_lang_types::SchemaSource;
-use isograph_schema::{validate_use_of_arguments, NetworkProtocol};
+use isograph_schema::{
+    built_in_validation_rules, validate_client_field_overrides, validate_plural_client_fields,
+    validate_query_limits, validate_selection_budget, validate_unused_client_types,
+    validate_use_of_arguments, validate_with_rules, NetworkProtocol, Schema,
+};
 use pico::{Database, SourceId};
 
 use crate::{
-    batch_compile::{BatchCompileError, CompilationStats},
-    create_schema::create_schema,
+    batch_compile::BatchCompileError,
+    compilation_stats::{peak_memory_bytes, CompilationStats, PhaseTimings},
+    create_schema::{create_schema, ContainsIsoStats},
+    file_system::FileSystem,
+    persist_artifacts::{persist_artifacts_for_config, PersistArtifacts},
+    skip_invalid_iso_literals::{
+        create_validated_schema_skipping_invalid_iso_literals, SkippedIsoLiterals,
+    },
     source_files::SourceFiles,
-    write_artifacts::write_artifacts_to_disk,
+    unused_client_types_report::write_unused_client_types_report,
+    with_duration::WithDuration,
 };
 
 const GC_DURATION: u64 = 60;
@@ -25,19 +39,24 @@ pub struct CompilerState {
     pub db: Database,
     pub config: CompilerConfig,
     pub source_files: Option<SourceFiles>,
+    /// Persists the artifacts of each compilation, and keeps track of the artifacts
+    /// persisted by the previous one.
+    pub persist_artifacts: Box<dyn PersistArtifacts + Send>,
     pub last_gc_run: Instant,
+    /// Set by the file watcher when newer file changes arrive, so that the
+    /// compilation in progress is abandoned before it writes any artifacts.
+    pub cancelled: Arc<AtomicBool>,
 }
 
 impl CompilerState {
-    pub fn new(
-        config_location: PathBuf,
-        current_working_directory: CurrentWorkingDirectory,
-    ) -> Self {
+    pub fn new(config: CompilerConfig) -> Self {
         Self {
             db: Database::new(),
-            config: create_config(config_location, current_working_directory),
+            persist_artifacts: persist_artifacts_for_config(&config),
+            config,
             source_files: None,
             last_gc_run: Instant::now(),
+            cancelled: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -59,6 +78,109 @@ pub type StandardSources = (
     BTreeMap<RelativePathToSourceFile, SourceId<SchemaSource>>,
 );
 
+/// Create the schema, including client fields, client pointers and entrypoints, and
+/// validate it. This does everything that compile_sources does, except generating artifacts.
+///
+/// The language server uses this to answer questions about iso literals.
+pub fn create_validated_schema<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    db: &Database,
+    source_files: &SourceFiles,
+    config: &CompilerConfig,
+    phase_timings: &mut PhaseTimings,
+) -> Result<(Schema<TNetworkProtocol>, ContainsIsoStats), Box<dyn Error>> {
+    let (isograph_schema, stats) = create_schema::<TNetworkProtocol>(
+        db,
+        &(source_files.sources),
+        &source_files.iso_literals,
+        config,
+        phase_timings,
+    )?;
+
+    let validation_start = Instant::now();
+    validate_use_of_arguments(&isograph_schema).map_err(|messages| {
+        Box::new(BatchCompileError::MultipleErrorsWithLocations {
+            messages: messages
+                .into_iter()
+                .map(|x| {
+                    WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location)
+                })
+                .collect(),
+        })
+    })?;
+    validate_client_field_overrides(&isograph_schema).map_err(|messages| {
+        Box::new(BatchCompileError::MultipleErrorsWithLocations {
+            messages: messages
+                .into_iter()
+                .map(|x| {
+                    WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location)
+                })
+                .collect(),
+        })
+    })?;
+    validate_plural_client_fields(&isograph_schema).map_err(|messages| {
+        Box::new(BatchCompileError::MultipleErrorsWithLocations {
+            messages: messages
+                .into_iter()
+                .map(|x| {
+                    WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location)
+                })
+                .collect(),
+        })
+    })?;
+    validate_unused_client_types(&isograph_schema, config.options.on_unused_client_field).map_err(
+        |messages| {
+            Box::new(BatchCompileError::MultipleErrorsWithLocations {
+                messages: messages
+                    .into_iter()
+                    .map(|x| {
+                        WithLocation::new(
+                            Box::new(x.item) as Box<dyn std::error::Error>,
+                            x.location,
+                        )
+                    })
+                    .collect(),
+            })
+        },
+    )?;
+    validate_query_limits(&isograph_schema, &config.options).map_err(|messages| {
+        Box::new(BatchCompileError::MultipleErrorsWithLocations {
+            messages: messages
+                .into_iter()
+                .map(|x| {
+                    WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location)
+                })
+                .collect(),
+        })
+    })?;
+    validate_selection_budget(&isograph_schema, &config.options).map_err(|messages| {
+        Box::new(BatchCompileError::MultipleErrorsWithLocations {
+            messages: messages
+                .into_iter()
+                .map(|x| {
+                    WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location)
+                })
+                .collect(),
+        })
+    })?;
+    validate_with_rules(
+        &isograph_schema,
+        &built_in_validation_rules(config.options.validation_rules.clone()),
+    )
+    .map_err(|messages| {
+        Box::new(BatchCompileError::MultipleErrorsWithLocations {
+            messages: messages
+                .into_iter()
+                .map(|x| {
+                    WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location)
+                })
+                .collect(),
+        })
+    })?;
+    phase_timings.validation += validation_start.elapsed();
+
+    Ok((isograph_schema, stats))
+}
+
 /// This the "workhorse" command of batch compilation.
 ///
 /// ## Overall plan
@@ -77,7 +199,9 @@ pub type StandardSources = (
 /// - Generate an in-memory representation of all of the generated files
 ///   (called artifacts). This step should not fail. It should panic if any
 ///   invariant is violated, or represent that invariant in the type system.
-/// - Delete and recreate the artifacts on disk.
+/// - Delete and recreate the artifacts on disk. If we have written artifacts
+///   before (i.e. in watch mode), only write the artifacts whose content changed
+///   and delete the ones that are no longer generated.
 ///
 /// ## Additional things we do
 ///
@@ -90,20 +214,85 @@ pub type StandardSources = (
 ///
 /// These are less "core" to the overall mission, and thus invite the question
 /// of whether they belong in this function, or at all.
-pub fn compile<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+pub fn compile_sources<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
     db: &Database,
+    fs: &dyn FileSystem,
     source_files: &SourceFiles,
     config: &CompilerConfig,
+    persist_artifacts: &mut dyn PersistArtifacts,
 ) -> Result<CompilationStats, Box<dyn Error>> {
-    // Create schema
-    let (isograph_schema, stats) = create_schema::<TNetworkProtocol>(
+    compile_sources_unless_cancelled::<TNetworkProtocol>(
         db,
-        &(source_files.sources),
-        &source_files.iso_literals,
+        fs,
+        source_files,
         config,
-    )?;
+        persist_artifacts,
+        &AtomicBool::new(false),
+    )
+}
 
-    validate_use_of_arguments(&isograph_schema).map_err(|messages| {
+/// Like compile_sources, but returns BatchCompileError::Cancelled instead of
+/// writing anything to disk if cancelled is set while compiling.
+pub(crate) fn compile_sources_unless_cancelled<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources>,
+>(
+    db: &Database,
+    fs: &dyn FileSystem,
+    source_files: &SourceFiles,
+    config: &CompilerConfig,
+    persist_artifacts: &mut dyn PersistArtifacts,
+    cancelled: &AtomicBool,
+) -> Result<CompilationStats, Box<dyn Error>> {
+    let mut phase_timings = PhaseTimings::default();
+
+    let (isograph_schema, stats, skipped_iso_literals) =
+        match create_validated_schema::<TNetworkProtocol>(
+            db,
+            source_files,
+            config,
+            &mut phase_timings,
+        ) {
+            Ok((isograph_schema, stats)) => (isograph_schema, stats, SkippedIsoLiterals::default()),
+            Err(e) if config.options.skip_invalid_iso_literals => {
+                create_validated_schema_skipping_invalid_iso_literals::<TNetworkProtocol>(
+                    db,
+                    source_files,
+                    config,
+                    &mut phase_timings,
+                    e,
+                )?
+            }
+            Err(e) => return Err(e),
+        };
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(Box::new(BatchCompileError::Cancelled));
+    }
+
+    if let Some(report_path) = &config.options.unused_client_fields_report {
+        write_unused_client_types_report(fs, &isograph_schema, report_path)?;
+    }
+
+    // Note: we calculate all of the artifact paths and contents first, so that writing to
+    // disk can be as fast as possible and we minimize the chance that changes to the file
+    // system occur while we're writing and we get unpredictable results.
+
+    let WithDuration {
+        elapsed_time: artifact_generation_time,
+        item: artifacts,
+    } = WithDuration::new(|| {
+        let iso_literal_sources = source_files
+            .iso_literals
+            .iter()
+            .map(|(relative_path, iso_literals_source_id)| {
+                (
+                    *relative_path,
+                    db.get(*iso_literals_source_id).content.as_str(),
+                )
+            })
+            .collect();
+        get_artifact_path_and_content(&isograph_schema, config, &iso_literal_sources)
+    });
+    let mut artifacts = artifacts.map_err(|messages| {
         Box::new(BatchCompileError::MultipleErrorsWithLocations {
             messages: messages
                 .into_iter()
@@ -113,18 +302,35 @@ pub fn compile<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
                 .collect(),
         })
     })?;
+    if config.options.generate_combined_schema {
+        artifacts.extend(TNetworkProtocol::generate_combined_schema_artifact(
+            db,
+            &source_files.sources,
+            &isograph_schema,
+        )?);
+    }
+    phase_timings.artifact_generation = artifact_generation_time;
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(Box::new(BatchCompileError::Cancelled));
+    }
 
-    // Note: we calculate all of the artifact paths and contents first, so that writing to
-    // disk can be as fast as possible and we minimize the chance that changes to the file
-    // system occur while we're writing and we get unpredictable results.
-
-    let artifacts = get_artifact_path_and_content(&isograph_schema, config);
+    let WithDuration {
+        elapsed_time: file_writing_time,
+        item: total_artifacts_written,
+    } = WithDuration::new(|| {
+        persist_artifacts.persist_artifacts(fs, artifacts, config, !skipped_iso_literals.is_empty())
+    });
+    let total_artifacts_written = total_artifacts_written?;
+    phase_timings.file_writing = file_writing_time;
 
-    let total_artifacts_written =
-        write_artifacts_to_disk(artifacts, &config.artifact_directory.absolute_path)?;
     Ok(CompilationStats {
+        server_object_count: isograph_schema.server_entity_data.server_objects.len(),
         client_field_count: stats.client_field_count,
+        client_pointer_count: stats.client_pointer_count,
         entrypoint_count: stats.entrypoint_count,
         total_artifacts_written,
+        phase_timings,
+        peak_memory_bytes: peak_memory_bytes(),
+        skipped_iso_literals,
     })
 }
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     error::Error,
     ops::{Deref, DerefMut},
+    time::Instant,
 };
 
 use common_lang_types::{
@@ -11,22 +12,24 @@ use common_lang_types::{
 use graphql_lang_types::{
     GraphQLConstantValue, GraphQLInputValueDefinition, NameValuePair, RootOperationKind,
 };
-use isograph_config::{CompilerConfig, CompilerConfigOptions};
+use isograph_config::{CompilerConfig, CompilerConfigOptions, IsoLiteralExtractor};
 use isograph_lang_parser::IsoLiteralExtractionResult;
 use isograph_lang_types::{
     ConstantValue, IsoLiteralsSource, SelectionType, ServerEntityId, ServerObjectEntityId,
     TypeAnnotation, VariableDefinition,
 };
 use isograph_schema::{
-    validate_entrypoints, CreateAdditionalFieldsError, FieldToInsert, NetworkProtocol,
-    ProcessObjectTypeDefinitionOutcome, ProcessTypeSystemDocumentOutcome, RootOperationName,
-    Schema, SchemaServerObjectSelectableVariant, ServerObjectSelectable, ServerScalarSelectable,
+    validate_entrypoints, validate_node_types_have_id_fields, CreateAdditionalFieldsError,
+    FieldToInsert, NetworkProtocol, ProcessObjectTypeDefinitionOutcome,
+    ProcessTypeSystemDocumentOutcome, RootOperationName, Schema,
+    SchemaServerObjectSelectableVariant, ServerObjectSelectable, ServerScalarSelectable,
 };
 use pico::{Database, SourceId};
 
 use crate::{
-    add_selection_sets::add_selection_sets_to_client_selectables,
+    add_selection_sets::{add_selection_sets_to_client_selectables, SelectionSetValidationOptions},
     batch_compile::BatchCompileError,
+    compilation_stats::PhaseTimings,
     isograph_literals::{parse_iso_literal_in_source, process_iso_literals},
 };
 
@@ -35,9 +38,11 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
     sources: &TNetworkProtocol::Sources,
     iso_literals: &HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>,
     config: &CompilerConfig,
+    phase_timings: &mut PhaseTimings,
 ) -> Result<(Schema<TNetworkProtocol>, ContainsIsoStats), Box<dyn Error>> {
+    let schema_parsing_start = Instant::now();
     let ProcessTypeSystemDocumentOutcome { scalars, objects } =
-        TNetworkProtocol::parse_and_process_type_system_documents(db, sources)?;
+        TNetworkProtocol::parse_and_process_type_system_documents(db, sources, &config.options)?;
 
     let mut unvalidated_isograph_schema = Schema::<TNetworkProtocol>::new();
     for (server_scalar_entity, name_location) in scalars {
@@ -48,6 +53,7 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
 
     let mut field_queue = HashMap::new();
     let mut expose_as_field_queue = HashMap::new();
+    let mut name_locations = HashMap::new();
     for (
         ProcessObjectTypeDefinitionOutcome {
             encountered_root_kind,
@@ -62,6 +68,7 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
             .server_entity_data
             .insert_server_object_entity(server_object_entity, name_location)?;
         field_queue.insert(new_object_id, fields_to_insert);
+        name_locations.insert(new_object_id, name_location);
 
         match encountered_root_kind {
             Some(RootOperationKind::Query) => {
@@ -86,6 +93,17 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
         field_queue,
         &config.options,
     )?;
+    validate_node_types_have_id_fields(
+        &unvalidated_isograph_schema,
+        &config.options,
+        &name_locations,
+    )
+    .map_err(|messages| BatchCompileError::MultipleErrorsWithLocations {
+        messages: messages
+            .into_iter()
+            .map(|x| WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location))
+            .collect(),
+    })?;
 
     // Step one: we can create client selectables. However, we must create all
     // client selectables before being able to create their selection sets, because
@@ -103,18 +121,43 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
         }
     }
 
-    let contains_iso = parse_iso_literals(db, iso_literals, config.current_working_directory)?;
+    phase_timings.schema_parsing = schema_parsing_start.elapsed();
+
+    let iso_literal_extraction_start = Instant::now();
+    let contains_iso = parse_iso_literals(
+        db,
+        iso_literals,
+        config.current_working_directory,
+        config.options.iso_literal_extractor,
+    )?;
     let contains_iso_stats = contains_iso.stats();
+    phase_timings.iso_literal_extraction = iso_literal_extraction_start.elapsed();
+
+    let validation_start = Instant::now();
 
-    let (unprocessed_client_types, unprocessed_entrypoints) =
-        process_iso_literals(&mut unvalidated_isograph_schema, contains_iso)?;
+    let (unprocessed_client_types, unprocessed_entrypoints) = process_iso_literals(
+        db,
+        iso_literals,
+        &mut unvalidated_isograph_schema,
+        contains_iso,
+    )?;
     unprocessed_items.extend(unprocessed_client_types);
 
     unvalidated_isograph_schema.add_link_fields()?;
+    if config.options.generate_pagination_fields {
+        let unprocessed_pagination_field_items =
+            unvalidated_isograph_schema.add_pagination_fields()?;
+        unprocessed_items.extend(
+            unprocessed_pagination_field_items
+                .into_iter()
+                .map(SelectionType::Scalar),
+        );
+    }
 
     unvalidated_isograph_schema.entrypoints = validate_entrypoints(
         &unvalidated_isograph_schema,
         unprocessed_entrypoints,
+        &config.entrypoints,
     )
     .map_err(|e| BatchCompileError::MultipleErrorsWithLocations {
         messages: e
@@ -130,13 +173,21 @@ pub fn create_schema<TNetworkProtocol: NetworkProtocol>(
     // - to validate arguments (e.g. no missing arguments, etc.)
     // - validate loadability/updatability, and
     // - to store the selectable id,
-    add_selection_sets_to_client_selectables(&mut unvalidated_isograph_schema, unprocessed_items)
-        .map_err(|messages| BatchCompileError::MultipleErrorsWithLocations {
+    add_selection_sets_to_client_selectables(
+        &mut unvalidated_isograph_schema,
+        SelectionSetValidationOptions {
+            restrictions: &config.options.server_field_restrictions,
+            implicit_concrete_type_refinements: config.options.implicit_concrete_type_refinements,
+        },
+        unprocessed_items,
+    )
+    .map_err(|messages| BatchCompileError::MultipleErrorsWithLocations {
         messages: messages
             .into_iter()
             .map(|x| WithLocation::new(Box::new(x.item) as Box<dyn std::error::Error>, x.location))
             .collect(),
     })?;
+    phase_timings.validation = validation_start.elapsed();
 
     Ok((unvalidated_isograph_schema, contains_iso_stats))
 }
@@ -145,12 +196,18 @@ fn parse_iso_literals(
     db: &Database,
     iso_literals_sources: &HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>,
     current_working_directory: CurrentWorkingDirectory,
+    iso_literal_extractor: IsoLiteralExtractor,
 ) -> Result<ContainsIso, BatchCompileError> {
     let mut contains_iso = ContainsIso::default();
     let mut iso_literal_parse_errors = vec![];
     for (relative_path, iso_literals_source_id) in iso_literals_sources.iter() {
-        match parse_iso_literal_in_source(db, *iso_literals_source_id, current_working_directory)
-            .to_owned()
+        match parse_iso_literal_in_source(
+            db,
+            *iso_literals_source_id,
+            current_working_directory,
+            iso_literal_extractor,
+        )
+        .to_owned()
         {
             Ok(iso_literals) => {
                 if !iso_literals.is_empty() {
@@ -217,7 +274,6 @@ impl DerefMut for ContainsIso {
 pub struct ContainsIsoStats {
     pub client_field_count: usize,
     pub entrypoint_count: usize,
-    #[allow(unused)]
     pub client_pointer_count: usize,
 }
 
@@ -271,6 +327,12 @@ fn process_field_queue<TNetworkProtocol: NetworkProtocol>(
                 })
                 .collect::<Result<Vec<_>, _>>()?;
             let description = server_field_to_insert.item.description.map(|d| d.item);
+            let deprecation_reason = server_field_to_insert.item.deprecation_reason;
+            let cost = server_field_to_insert.item.cost;
+            let owner = server_field_to_insert.item.owner;
+            let cache_control = server_field_to_insert.item.cache_control;
+            let semantic_non_null_levels =
+                server_field_to_insert.item.semantic_non_null_levels.clone();
 
             match selection_type {
                 SelectionType::Scalar(scalar_entity_id) => {
@@ -288,6 +350,11 @@ fn process_field_queue<TNetworkProtocol: NetworkProtocol>(
                                 .map(&mut |_| *scalar_entity_id),
                                 parent_object_entity_id,
                                 arguments,
+                                deprecation_reason,
+                                cost,
+                                owner,
+                                cache_control,
+                                semantic_non_null_levels,
                                 phantom_data: std::marker::PhantomData,
                             },
                             options,
@@ -309,11 +376,27 @@ fn process_field_queue<TNetworkProtocol: NetworkProtocol>(
                             .map(&mut |_| *object_entity_id),
                             parent_object_entity_id,
                             arguments,
+                            deprecation_reason,
+                            cost,
+                            owner,
+                            cache_control,
+                            semantic_non_null_levels,
                             phantom_data: std::marker::PhantomData,
                             object_selectable_variant:
                                 // TODO this is hacky
                                 if server_field_to_insert.item.is_inline_fragment {
-                                    SchemaServerObjectSelectableVariant::InlineFragment
+                                    // Inline fragments to abstract types (e.g. asNode) are
+                                    // refinements of concrete types to a supertype.
+                                    if schema
+                                        .server_entity_data
+                                        .server_object_entity(*object_entity_id)
+                                        .concrete_type
+                                        .is_none()
+                                    {
+                                        SchemaServerObjectSelectableVariant::Upcast
+                                    } else {
+                                        SchemaServerObjectSelectableVariant::InlineFragment
+                                    }
                                 } else {
                                     SchemaServerObjectSelectableVariant::LinkedField
                                 }
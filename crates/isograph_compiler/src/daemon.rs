@@ -0,0 +1,425 @@
+use std::{io, path::PathBuf};
+
+use colored::Colorize;
+use common_lang_types::CurrentWorkingDirectory;
+use intern::Lookup;
+use isograph_config::{create_config, create_configs, CompilerConfig};
+use isograph_schema::NetworkProtocol;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::info;
+
+use crate::{
+    compiler_state::{CompilerState, StandardSources},
+    diagnostics::{diagnostics_from_error, Diagnostic},
+    watch::{
+        categorize_changed_file_and_filter_changes_in_artifact_directory, compile_from_scratch,
+        compile_incrementally, has_config_changes, SourceEventKind, SourceFileEvent,
+        MAX_CHANGED_FILES,
+    },
+    with_duration::WithDuration,
+};
+
+/// A request sent to the daemon, as a single line of JSON, e.g.
+/// `{"command": "invalidate", "paths": ["src/components/Avatar.tsx"]}`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum DaemonRequest {
+    /// Compile every project. If files were invalidated since the last
+    /// compilation, only those files are re-read. Otherwise, every source file
+    /// is re-read, but unchanged files are not re-parsed.
+    Compile,
+    /// Mark files (relative to the current working directory, or absolute) as
+    /// changed. Files that no longer exist are removed.
+    Invalidate {
+        paths: Vec<PathBuf>,
+    },
+    Status,
+    Shutdown,
+}
+
+/// The response to a request, written as a single line of JSON.
+#[derive(Debug, Serialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+enum DaemonResponse {
+    Compile {
+        succeeded: bool,
+        projects: Vec<ProjectCompilation>,
+    },
+    Invalidate {
+        invalidated_files: usize,
+    },
+    Status {
+        compilations: usize,
+        projects: Vec<ProjectStatus>,
+    },
+    Shutdown,
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectCompilation {
+    project: Option<String>,
+    succeeded: bool,
+    elapsed_ms: u128,
+    artifacts_written: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectStatus {
+    project: Option<String>,
+    compiled: bool,
+    pending_invalidations: usize,
+    last_compilation_succeeded: Option<bool>,
+}
+
+struct DaemonProject {
+    state: CompilerState,
+    pending_changes: Vec<SourceFileEvent>,
+    last_compilation_succeeded: Option<bool>,
+}
+
+struct Daemon {
+    projects: Vec<DaemonProject>,
+    current_working_directory: CurrentWorkingDirectory,
+    compilations: usize,
+}
+
+/// Keep the compiler state of each project in memory, and compile, invalidate
+/// files and report status on request. Unlike watch mode, the daemon does not
+/// watch the file system: it only does work when asked, so repeated builds from
+/// build scripts are incremental without each build starting from scratch.
+///
+/// Requests and responses are newline-delimited JSON. If socket is provided,
+/// the daemon listens on that Unix socket and serves one connection at a time.
+/// Otherwise, requests are read from stdin and responses written to stdout.
+pub async fn handle_daemon_command<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config_location: PathBuf,
+    current_working_directory: CurrentWorkingDirectory,
+    socket: Option<PathBuf>,
+    keep_tmp_on_failure: bool,
+) -> io::Result<()> {
+    let mut daemon = Daemon {
+        projects: create_configs(config_location, current_working_directory)
+            .into_iter()
+            .map(|config| DaemonProject {
+                state: CompilerState::new(CompilerConfig {
+                    keep_tmp_on_failure,
+                    ..config
+                }),
+                pending_changes: vec![],
+                last_compilation_succeeded: None,
+            })
+            .collect(),
+        current_working_directory,
+        compilations: 0,
+    };
+
+    match socket {
+        Some(socket) => serve_socket::<TNetworkProtocol>(&mut daemon, socket).await,
+        None => {
+            info!("{}", "Daemon started. Reading commands from stdin.".cyan());
+            serve_connection::<TNetworkProtocol>(
+                &mut daemon,
+                BufReader::new(tokio::io::stdin()),
+                tokio::io::stdout(),
+            )
+            .await
+            .map(|_| ())
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn serve_socket<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    daemon: &mut Daemon,
+    socket: PathBuf,
+) -> io::Result<()> {
+    // A socket left behind by a daemon that did not shut down cleanly would
+    // prevent us from binding.
+    if socket.exists() {
+        std::fs::remove_file(&socket)?;
+    }
+    let listener = tokio::net::UnixListener::bind(&socket)?;
+    info!(
+        "{}",
+        format!("Daemon started. Listening on {}.", socket.display()).cyan()
+    );
+
+    let result = loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => break Err(e),
+        };
+        let (reader, writer) = stream.into_split();
+        match serve_connection::<TNetworkProtocol>(daemon, BufReader::new(reader), writer).await {
+            Ok(ConnectionOutcome::Shutdown) => break Ok(()),
+            // The client disconnecting is not a reason to stop the daemon.
+            Ok(ConnectionOutcome::Closed) | Err(_) => {}
+        }
+    };
+    let _ = std::fs::remove_file(&socket);
+    result
+}
+
+#[cfg(not(unix))]
+async fn serve_socket<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    _daemon: &mut Daemon,
+    _socket: PathBuf,
+) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Sockets are not supported on this platform. Omit --socket to send commands over stdin.",
+    ))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ConnectionOutcome {
+    Closed,
+    Shutdown,
+}
+
+async fn serve_connection<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    daemon: &mut Daemon,
+    reader: impl AsyncBufRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+) -> io::Result<ConnectionOutcome> {
+    let mut lines = reader.lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<DaemonRequest>(&line) {
+            Ok(request) => daemon.handle_request::<TNetworkProtocol>(request),
+            Err(e) => DaemonResponse::Error {
+                message: format!("Invalid request: {e}"),
+            },
+        };
+        let is_shutdown = matches!(response, DaemonResponse::Shutdown);
+
+        let mut serialized =
+            serde_json::to_string(&response).expect("Expected response to be serializable");
+        serialized.push('\n');
+        writer.write_all(serialized.as_bytes()).await?;
+        writer.flush().await?;
+
+        if is_shutdown {
+            return Ok(ConnectionOutcome::Shutdown);
+        }
+    }
+    Ok(ConnectionOutcome::Closed)
+}
+
+impl Daemon {
+    fn handle_request<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+        &mut self,
+        request: DaemonRequest,
+    ) -> DaemonResponse {
+        match request {
+            DaemonRequest::Compile => self.compile::<TNetworkProtocol>(),
+            DaemonRequest::Invalidate { paths } => self.invalidate(paths),
+            DaemonRequest::Status => DaemonResponse::Status {
+                compilations: self.compilations,
+                projects: self
+                    .projects
+                    .iter()
+                    .map(|project| ProjectStatus {
+                        project: project.state.config.project_name.clone(),
+                        compiled: project.state.source_files.is_some(),
+                        pending_invalidations: project.pending_changes.len(),
+                        last_compilation_succeeded: project.last_compilation_succeeded,
+                    })
+                    .collect(),
+            },
+            DaemonRequest::Shutdown => DaemonResponse::Shutdown,
+        }
+    }
+
+    fn compile<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+        &mut self,
+    ) -> DaemonResponse {
+        self.compilations += 1;
+        let current_working_directory = self.current_working_directory;
+        let projects = self
+            .projects
+            .iter_mut()
+            .map(|project| project.compile::<TNetworkProtocol>(current_working_directory))
+            .collect::<Vec<_>>();
+
+        DaemonResponse::Compile {
+            succeeded: projects.iter().all(|project| project.succeeded),
+            projects,
+        }
+    }
+
+    /// Record the paths as changed in every project that they belong to.
+    fn invalidate(&mut self, paths: Vec<PathBuf>) -> DaemonResponse {
+        let mut invalidated_files = 0;
+        for path in paths {
+            let path = if path.is_absolute() {
+                path
+            } else {
+                PathBuf::from(self.current_working_directory.lookup()).join(path)
+            };
+
+            let mut invalidated = false;
+            for project in self.projects.iter_mut() {
+                if let Some(changed_file_kind) =
+                    categorize_changed_file_and_filter_changes_in_artifact_directory(
+                        &project.state.config,
+                        &path,
+                    )
+                {
+                    let event_kind = if path.exists() {
+                        SourceEventKind::CreateOrModify(path.clone())
+                    } else {
+                        SourceEventKind::Remove(path.clone())
+                    };
+                    project
+                        .pending_changes
+                        .push((event_kind, changed_file_kind));
+                    invalidated = true;
+                }
+            }
+            if invalidated {
+                invalidated_files += 1;
+            }
+        }
+        DaemonResponse::Invalidate { invalidated_files }
+    }
+}
+
+impl DaemonProject {
+    fn compile<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+        &mut self,
+        current_working_directory: CurrentWorkingDirectory,
+    ) -> ProjectCompilation {
+        let changes = std::mem::take(&mut self.pending_changes);
+        let result = if has_config_changes(&changes) {
+            self.state = CompilerState::new(CompilerConfig {
+                keep_tmp_on_failure: self.state.config.keep_tmp_on_failure,
+                ..create_config(
+                    self.state.config.config_location.clone(),
+                    current_working_directory,
+                    self.state.config.project_name.as_deref(),
+                )
+            });
+            WithDuration::new(|| compile_from_scratch::<TNetworkProtocol>(&mut self.state))
+        } else if !changes.is_empty() && changes.len() < MAX_CHANGED_FILES {
+            WithDuration::new(|| {
+                compile_incrementally::<TNetworkProtocol>(&mut self.state, &changes)
+            })
+        } else {
+            WithDuration::new(|| compile_from_scratch::<TNetworkProtocol>(&mut self.state))
+        };
+        self.state.run_garbage_collection();
+
+        let project_name = self.state.config.project_name.clone();
+        let prefix = project_name
+            .as_deref()
+            .map(|project_name| format!("[{project_name}] "))
+            .unwrap_or_default();
+        let compilation = match result.item {
+            Ok(stats) => {
+                info!(
+                    "{prefix}Compiled in {}ms, and wrote {} artifacts.",
+                    result.elapsed_time.as_millis(),
+                    stats.total_artifacts_written
+                );
+                // If files with invalid iso literals were skipped, the artifacts were
+                // written, but the compilation did not succeed.
+                let diagnostics = stats
+                    .skipped_iso_literals
+                    .diagnostics
+                    .into_iter()
+                    .map(|mut diagnostic| {
+                        diagnostic.project = project_name.clone();
+                        diagnostic
+                    })
+                    .collect::<Vec<_>>();
+                ProjectCompilation {
+                    project: project_name,
+                    succeeded: diagnostics.is_empty(),
+                    elapsed_ms: result.elapsed_time.as_millis(),
+                    artifacts_written: stats.total_artifacts_written,
+                    diagnostics,
+                }
+            }
+            Err(err) => {
+                let diagnostics = diagnostics_from_error(err.as_ref())
+                    .into_iter()
+                    .map(|mut diagnostic| {
+                        diagnostic.project = project_name.clone();
+                        diagnostic
+                    })
+                    .collect::<Vec<_>>();
+                info!(
+                    "{prefix}Compilation failed with {} errors in {}ms.",
+                    diagnostics.len(),
+                    result.elapsed_time.as_millis()
+                );
+                ProjectCompilation {
+                    project: project_name,
+                    succeeded: false,
+                    elapsed_ms: result.elapsed_time.as_millis(),
+                    artifacts_written: 0,
+                    diagnostics,
+                }
+            }
+        };
+        self.last_compilation_succeeded = Some(compilation.succeeded);
+        compilation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graphql_network_protocol::GraphQLNetworkProtocol;
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn daemon_responds_to_each_line_until_shutdown() {
+        let mut daemon = Daemon {
+            projects: vec![],
+            current_working_directory: "/".intern().into(),
+            compilations: 0,
+        };
+        let requests = concat!(
+            "{\"command\": \"compile\"}\n",
+            "\n",
+            "{\"command\": \"rebuild\"}\n",
+            "{\"command\": \"status\"}\n",
+            "{\"command\": \"shutdown\"}\n",
+            "{\"command\": \"status\"}\n",
+        );
+        let mut output = vec![];
+
+        let outcome = serve_connection::<GraphQLNetworkProtocol>(
+            &mut daemon,
+            requests.as_bytes(),
+            &mut output,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(outcome, ConnectionOutcome::Shutdown);
+        let responses = String::from_utf8(output).unwrap();
+        let responses = responses.lines().collect::<Vec<_>>();
+        assert_eq!(responses.len(), 4);
+        assert_eq!(
+            responses[0],
+            r#"{"response":"compile","succeeded":true,"projects":[]}"#
+        );
+        assert!(responses[1].starts_with(r#"{"response":"error","message":"Invalid request: "#));
+        assert_eq!(
+            responses[2],
+            r#"{"response":"status","compilations":1,"projects":[]}"#
+        );
+        assert_eq!(responses[3], r#"{"response":"shutdown"}"#);
+    }
+}
@@ -0,0 +1,171 @@
+use std::{collections::BTreeSet, error::Error, path::PathBuf};
+
+use common_lang_types::CurrentWorkingDirectory;
+use isograph_config::create_configs;
+use isograph_lang_types::SelectionType;
+use isograph_schema::{
+    client_type_dependency_graph, ClientScalarOrObjectSelectable, ClientSelectableId,
+    NetworkProtocol, Schema,
+};
+use pico::Database;
+use serde_json::json;
+
+use crate::{
+    compilation_stats::PhaseTimings, compiler_state::StandardSources, create_schema::create_schema,
+    diagnostics::OutputFormat, file_system::OsFileSystem, schema_diff::print_schema_error,
+    source_files::SourceFiles,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyGraphFormat {
+    /// A graphviz digraph, e.g. for `dot -Tsvg`.
+    Dot,
+    /// A single line of JSON, containing the nodes, the edges, and the client
+    /// fields and client pointers reachable from each entrypoint.
+    Json,
+}
+
+/// Print the graph of which client fields and client pointers select which
+/// others, for each project in the config. Nodes are named `Type.field`.
+///
+/// The schema is not validated beyond what is needed to build the graph, so that
+/// e.g. unused client fields (which one might want to find using the graph) do
+/// not prevent it from being printed.
+pub fn dependency_graph_and_print<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config_location: PathBuf,
+    current_working_directory: CurrentWorkingDirectory,
+    format: DependencyGraphFormat,
+) -> Result<(), Box<dyn Error>> {
+    for config in create_configs(config_location, current_working_directory) {
+        let mut db = Database::new();
+        let source_files = SourceFiles::read_all(&mut db, &OsFileSystem, &config)?;
+        let (schema, _) = create_schema::<TNetworkProtocol>(
+            &db,
+            &source_files.sources,
+            &source_files.iso_literals,
+            &config,
+            &mut PhaseTimings::default(),
+        )
+        .inspect_err(|e| {
+            let prefix = config
+                .project_name
+                .as_ref()
+                .map(|project_name| format!("[{project_name}] "))
+                .unwrap_or_default();
+            print_schema_error(
+                &format!("{prefix}Unable to create the dependency graph."),
+                e.as_ref(),
+                &config,
+                OutputFormat::Human,
+            )
+        })?;
+
+        let graph = format_graph(&schema, config.project_name.as_deref(), format);
+        println!("{graph}");
+    }
+    Ok(())
+}
+
+fn format_graph<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    project_name: Option<&str>,
+    format: DependencyGraphFormat,
+) -> String {
+    let graph = client_type_dependency_graph(schema);
+    let name = |client_type_id: ClientSelectableId| {
+        let type_and_field = schema.client_type(client_type_id).type_and_field();
+        format!("{}.{}", type_and_field.type_name, type_and_field.field_name)
+    };
+    let is_entrypoint = |client_type_id: ClientSelectableId| match client_type_id {
+        SelectionType::Scalar(client_field_id) => schema.entrypoints.contains_key(&client_field_id),
+        SelectionType::Object(_) => false,
+    };
+
+    let mut nodes = graph
+        .client_types
+        .iter()
+        .map(|client_type_id| (name(*client_type_id), *client_type_id))
+        .collect::<Vec<_>>();
+    nodes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let edges = graph
+        .selections
+        .iter()
+        .flat_map(|(selector, selected)| {
+            selected
+                .iter()
+                .map(move |selected| (name(*selector), name(*selected)))
+        })
+        .collect::<BTreeSet<_>>();
+
+    match format {
+        DependencyGraphFormat::Dot => {
+            let mut output = format!(
+                "digraph {} {{\n",
+                dot_id(project_name.unwrap_or("isograph"))
+            );
+            for (node_name, client_type_id) in nodes.iter() {
+                let shape = if is_entrypoint(*client_type_id) {
+                    "box"
+                } else {
+                    match client_type_id {
+                        SelectionType::Scalar(_) => "ellipse",
+                        SelectionType::Object(_) => "diamond",
+                    }
+                };
+                output.push_str(&format!("  {} [shape={shape}];\n", dot_id(node_name)));
+            }
+            for (from, to) in edges.iter() {
+                output.push_str(&format!("  {} -> {};\n", dot_id(from), dot_id(to)));
+            }
+            output.push('}');
+            output
+        }
+        DependencyGraphFormat::Json => {
+            let mut entrypoints = graph
+                .reachable_from_entrypoints
+                .iter()
+                .map(|(entrypoint_id, reachable)| {
+                    let mut reaches = reachable
+                        .iter()
+                        .map(|client_type_id| name(*client_type_id))
+                        .collect::<Vec<_>>();
+                    reaches.sort();
+                    (name(SelectionType::Scalar(*entrypoint_id)), reaches)
+                })
+                .collect::<Vec<_>>();
+            entrypoints.sort();
+
+            json!({
+                "project": project_name,
+                "nodes": nodes
+                    .iter()
+                    .map(|(node_name, client_type_id)| {
+                        let client_type = schema.client_type(*client_type_id);
+                        json!({
+                            "id": node_name,
+                            "kind": client_type.client_type(),
+                            "is_entrypoint": is_entrypoint(*client_type_id),
+                            "file": client_type
+                                .text_source()
+                                .map(|text_source| text_source.relative_path_to_source_file.to_string()),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                "edges": edges
+                    .iter()
+                    .map(|(from, to)| json!({ "from": from, "to": to }))
+                    .collect::<Vec<_>>(),
+                "entrypoints": entrypoints
+                    .into_iter()
+                    .map(|(entrypoint, reaches)| json!({ "entrypoint": entrypoint, "reaches": reaches }))
+                    .collect::<Vec<_>>(),
+            })
+            .to_string()
+        }
+    }
+}
+
+/// Quote an identifier for use in a DOT file.
+fn dot_id(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
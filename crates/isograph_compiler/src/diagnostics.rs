@@ -0,0 +1,244 @@
+use std::{error::Error, fmt};
+
+use common_lang_types::{CodedError, ErrorCode, HasErrorCode, Location, WithLocation};
+use generate_artifacts::OperationNameError;
+use intern::Lookup;
+use isograph_lang_parser::IsographLiteralParseError;
+use isograph_schema::{
+    ClientFieldOverrideError, CreateAdditionalFieldsError, PluralClientFieldError,
+    ProcessClientFieldDeclarationError, QueryLimitError, SchemaChangeError, UnusedClientTypeError,
+    ValidateEntrypointDeclarationError, ValidateUseOfArgumentsError, ValidationRuleError,
+};
+use serde::Serialize;
+
+use crate::{add_selection_sets::AddSelectionSetsError, batch_compile::BatchCompileError};
+
+/// How compilation results should be reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output, with the offending source text underlined.
+    #[default]
+    Human,
+    /// One JSON-serialized Diagnostic per line, printed to stdout.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single error or warning, in a form that can be consumed by tools
+/// (e.g. editor plugins and CI annotation bots).
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    /// The project in which this diagnostic occurred, if the config file
+    /// contains multiple projects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// A stable code (e.g. IS0104) that can be passed to `--explain`.
+    pub code: Option<String>,
+    pub message: String,
+    pub severity: Severity,
+    /// The path to the file, relative to the current working directory.
+    pub file: Option<String>,
+    /// Byte offsets into the file. Unlike the spans stored in Locations,
+    /// these are not relative to the iso literal.
+    pub span: Option<DiagnosticSpan>,
+    #[serde(skip)]
+    pub location: Option<Location>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DiagnosticSpan {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Diagnostic {
+    pub fn new(message: String, severity: Severity, location: Option<Location>) -> Self {
+        let (file, span) = match location {
+            Some(Location::Embedded(embedded_location)) => {
+                let span = embedded_location.span_in_file();
+                (
+                    Some(
+                        embedded_location
+                            .text_source
+                            .relative_path_to_source_file
+                            .lookup()
+                            .to_string(),
+                    ),
+                    Some(DiagnosticSpan {
+                        start: span.start,
+                        end: span.end,
+                    }),
+                )
+            }
+            Some(Location::Generated) | None => (None, None),
+        };
+        Diagnostic {
+            project: None,
+            code: None,
+            message,
+            severity,
+            file,
+            span,
+            location,
+        }
+    }
+
+    pub fn with_code(mut self, code: Option<ErrorCode>) -> Self {
+        self.code = code.map(|code| code.to_string());
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Expected diagnostic to be serializable")
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Renders the diagnostic for humans, e.g. `error[IS0104]: Duplicate field`,
+/// followed by the offending source text.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.severity)?;
+        if let Some(code) = &self.code {
+            write!(f, "[{code}]")?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(location @ Location::Embedded(_)) = self.location {
+            write!(f, "\n{location}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Flatten an error (which may contain many errors) into a list of diagnostics.
+///
+/// Errors with a location that we know about are reported with a file and span.
+/// Any other error is reported with its full message and no location.
+pub fn diagnostics_from_error(error: &(dyn Error + 'static)) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    collect_diagnostics(error, &mut diagnostics);
+    diagnostics
+}
+
+fn collect_diagnostics(error: &(dyn Error + 'static), diagnostics: &mut Vec<Diagnostic>) {
+    if let Some(batch_compile_error) = error.downcast_ref::<BatchCompileError>() {
+        match batch_compile_error {
+            BatchCompileError::UnableToParseIsographLiterals { messages } => {
+                diagnostics.extend(messages.iter().map(with_location_to_diagnostic));
+            }
+            BatchCompileError::UnableToCreateSchema(with_location) => {
+                diagnostics.push(with_location_to_diagnostic(with_location));
+            }
+            BatchCompileError::ErrorWhenProcessingClientFieldDeclaration { messages } => {
+                diagnostics.extend(messages.iter().map(with_location_to_diagnostic));
+            }
+            BatchCompileError::MultipleErrors { messages } => {
+                for message in messages {
+                    collect_diagnostics(message.as_ref(), diagnostics);
+                }
+            }
+            BatchCompileError::MultipleErrorsWithLocations { messages } => {
+                diagnostics.extend(messages.iter().map(|with_location| {
+                    Diagnostic::new(
+                        with_location.item.to_string(),
+                        Severity::Error,
+                        Some(with_location.location),
+                    )
+                    .with_code(error_code(with_location.item.as_ref()))
+                }));
+            }
+            _ => diagnostics.push(Diagnostic::new(error.to_string(), Severity::Error, None)),
+        }
+        return;
+    }
+
+    macro_rules! try_with_location {
+        ($($error_type:ty),* $(,)?) => {
+            $(
+                if let Some(with_location) = error.downcast_ref::<WithLocation<$error_type>>() {
+                    diagnostics.push(with_location_to_diagnostic(with_location));
+                    return;
+                }
+            )*
+        };
+    }
+    try_with_location!(
+        IsographLiteralParseError,
+        CreateAdditionalFieldsError,
+        ProcessClientFieldDeclarationError,
+        ValidateEntrypointDeclarationError,
+        ValidateUseOfArgumentsError,
+        ClientFieldOverrideError,
+        PluralClientFieldError,
+        AddSelectionSetsError,
+        UnusedClientTypeError,
+        QueryLimitError,
+        ValidationRuleError,
+        OperationNameError,
+        SchemaChangeError,
+        CodedError,
+    );
+
+    diagnostics.push(
+        Diagnostic::new(error.to_string(), Severity::Error, None).with_code(error_code(error)),
+    );
+}
+
+/// Find the error code of an error whose type has been erased, if it is one of
+/// the error types that has an error code.
+fn error_code(error: &(dyn Error + 'static)) -> Option<ErrorCode> {
+    macro_rules! try_error_code {
+        ($($error_type:ty),* $(,)?) => {
+            $(
+                if let Some(error) = error.downcast_ref::<$error_type>() {
+                    return Some(error.error_code());
+                }
+                if let Some(with_location) = error.downcast_ref::<WithLocation<$error_type>>() {
+                    return Some(with_location.item.error_code());
+                }
+            )*
+        };
+    }
+    try_error_code!(
+        IsographLiteralParseError,
+        CreateAdditionalFieldsError,
+        ProcessClientFieldDeclarationError,
+        ValidateEntrypointDeclarationError,
+        ValidateUseOfArgumentsError,
+        ClientFieldOverrideError,
+        PluralClientFieldError,
+        AddSelectionSetsError,
+        UnusedClientTypeError,
+        QueryLimitError,
+        ValidationRuleError,
+        OperationNameError,
+        SchemaChangeError,
+        CodedError,
+    );
+    None
+}
+
+pub(crate) fn with_location_to_diagnostic<T: fmt::Display + HasErrorCode>(
+    with_location: &WithLocation<T>,
+) -> Diagnostic {
+    Diagnostic::new(
+        with_location.item.to_string(),
+        Severity::Error,
+        Some(with_location.location),
+    )
+    .with_code(Some(with_location.item.error_code()))
+}
@@ -0,0 +1,467 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// The file system operations that the compiler performs when reading source
+/// files and writing artifacts.
+///
+/// Build tools that embed the compiler can provide their own implementation, e.g.
+/// to serve files from a cache or to collect the artifacts instead of writing them
+/// to disk.
+pub trait FileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// The paths of the entries of the directory at path, in no particular order.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn is_dir(&self, path: &Path) -> bool;
+
+    fn exists(&self, path: &Path) -> bool {
+        self.is_file(path) || self.is_dir(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+
+    /// Remove the directory at path, which must be empty.
+    fn remove_dir(&self, path: &Path) -> io::Result<()>;
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    /// Move the file or directory at from to to. A file replaces the file at to, if
+    /// there is one. Otherwise, to must not exist.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+}
+
+/// The file system of the operating system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect()
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        path.canonicalize()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        fs::write(path, contents)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir(path)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir_all(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+}
+
+/// A file system that only exists in memory. Files and directories are keyed by
+/// their absolute paths.
+///
+/// This allows tests to compile a project without touching the disk, and to
+/// inspect the artifacts that were written.
+#[derive(Debug, Default)]
+pub struct InMemoryFileSystem {
+    files: RefCell<BTreeMap<PathBuf, Vec<u8>>>,
+    directories: RefCell<BTreeSet<PathBuf>>,
+}
+
+impl InMemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, creating its parent directories if needed.
+    pub fn add_file(&self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) {
+        let path = normalize(path.as_ref());
+        if let Some(parent) = path.parent() {
+            self.add_directory_and_ancestors(parent);
+        }
+        self.files.borrow_mut().insert(path, contents.into());
+    }
+
+    /// The files that currently exist, keyed by absolute path.
+    pub fn files(&self) -> BTreeMap<PathBuf, Vec<u8>> {
+        self.files.borrow().clone()
+    }
+
+    fn add_directory_and_ancestors(&self, path: &Path) {
+        let mut directories = self.directories.borrow_mut();
+        for ancestor in path.ancestors() {
+            if !directories.insert(ancestor.to_path_buf()) {
+                break;
+            }
+        }
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not exist", path.display()),
+        )
+    }
+}
+
+impl FileSystem for InMemoryFileSystem {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.files
+            .borrow()
+            .get(&normalize(path))
+            .cloned()
+            .ok_or_else(|| Self::not_found(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let path = normalize(path);
+        if !self.is_dir(&path) {
+            return Err(Self::not_found(&path));
+        }
+        let is_child = |entry: &&PathBuf| entry.parent() == Some(path.as_path());
+        Ok(self
+            .directories
+            .borrow()
+            .iter()
+            .filter(is_child)
+            .chain(self.files.borrow().keys().filter(is_child))
+            .cloned()
+            .collect())
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let path = normalize(path);
+        if self.exists(&path) {
+            Ok(path)
+        } else {
+            Err(Self::not_found(&path))
+        }
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.borrow().contains_key(&normalize(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.directories.borrow().contains(&normalize(path))
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let path = normalize(path);
+        match path.parent() {
+            Some(parent) if self.is_dir(parent) => {
+                self.files.borrow_mut().insert(path, contents.to_vec());
+                Ok(())
+            }
+            _ => Err(Self::not_found(&path)),
+        }
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let path = normalize(path);
+        if let Some(file) = path.ancestors().find(|ancestor| self.is_file(ancestor)) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} is a file", file.display()),
+            ));
+        }
+        self.add_directory_and_ancestors(&path);
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.files
+            .borrow_mut()
+            .remove(&normalize(path))
+            .map(|_| ())
+            .ok_or_else(|| Self::not_found(path))
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let path = normalize(path);
+        if !self.read_dir(&path)?.is_empty() {
+            return Err(io::Error::other(format!("{} is not empty", path.display())));
+        }
+        self.directories.borrow_mut().remove(&path);
+        Ok(())
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        let path = normalize(path);
+        if !self.is_dir(&path) {
+            return Err(Self::not_found(&path));
+        }
+        self.files
+            .borrow_mut()
+            .retain(|file_path, _| !file_path.starts_with(&path));
+        self.directories
+            .borrow_mut()
+            .retain(|directory| !directory.starts_with(&path));
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let (from, to) = (normalize(from), normalize(to));
+        if !self.exists(&from) {
+            return Err(Self::not_found(&from));
+        }
+        if self.is_file(&from) && self.is_file(&to) {
+            self.files.borrow_mut().remove(&to);
+        } else if self.exists(&to) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", to.display()),
+            ));
+        }
+        match to.parent() {
+            Some(parent) if self.is_dir(parent) => {}
+            _ => return Err(Self::not_found(&to)),
+        }
+
+        let moved = |path: &PathBuf| match path.strip_prefix(&from) {
+            Ok(relative_path) if relative_path.as_os_str().is_empty() => to.clone(),
+            Ok(relative_path) => to.join(relative_path),
+            Err(_) => path.clone(),
+        };
+        let files = std::mem::take(&mut *self.files.borrow_mut());
+        *self.files.borrow_mut() = files
+            .into_iter()
+            .map(|(path, contents)| (moved(&path), contents))
+            .collect();
+        let directories = std::mem::take(&mut *self.directories.borrow_mut());
+        *self.directories.borrow_mut() = directories.iter().map(moved).collect();
+        Ok(())
+    }
+}
+
+/// A file system in which some files have different contents than in the
+/// underlying file system, e.g. the unsaved contents of documents that are open
+/// in an editor. Files in the overlay need not exist in the underlying file
+/// system. Writes go to the underlying file system.
+pub struct OverlayFileSystem<'a> {
+    base: &'a dyn FileSystem,
+    overlay: &'a HashMap<PathBuf, String>,
+}
+
+impl<'a> OverlayFileSystem<'a> {
+    pub fn new(base: &'a dyn FileSystem, overlay: &'a HashMap<PathBuf, String>) -> Self {
+        Self { base, overlay }
+    }
+}
+
+impl FileSystem for OverlayFileSystem<'_> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.overlay.get(path) {
+            Some(contents) => Ok(contents.as_bytes().to_vec()),
+            None => self.base.read(path),
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut entries = self.base.read_dir(path)?;
+        for overlaid_path in self.overlay.keys() {
+            if overlaid_path.parent() == Some(path) && !entries.contains(overlaid_path) {
+                entries.push(overlaid_path.clone());
+            }
+        }
+        Ok(entries)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        if self.overlay.contains_key(path) {
+            Ok(path.to_path_buf())
+        } else {
+            self.base.canonicalize(path)
+        }
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.overlay.contains_key(path) || self.base.is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.base.is_dir(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.base.write(path, contents)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.base.create_dir_all(path)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.base.remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        self.base.remove_dir(path)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.base.remove_dir_all(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.base.rename(from, to)
+    }
+}
+
+/// Remove `.` and `..` components from path, without consulting a file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_file_system_reads_and_writes() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/src/a.ts", "a");
+        fs.add_file("/project/src/nested/b.ts", "b");
+
+        assert_eq!(
+            fs.read_to_string(Path::new("/project/src/a.ts")).unwrap(),
+            "a"
+        );
+        assert!(fs.is_dir(Path::new("/project/src/nested")));
+        assert_eq!(
+            fs.canonicalize(Path::new("/project/src/../src/a.ts"))
+                .unwrap(),
+            PathBuf::from("/project/src/a.ts")
+        );
+        assert_eq!(
+            fs.read_dir(Path::new("/project/src")).unwrap(),
+            vec![
+                PathBuf::from("/project/src/nested"),
+                PathBuf::from("/project/src/a.ts")
+            ]
+        );
+
+        // Files can only be written to existing directories.
+        assert!(fs.write(Path::new("/artifacts/c.ts"), b"c").is_err());
+        fs.create_dir_all(Path::new("/artifacts")).unwrap();
+        fs.write(Path::new("/artifacts/c.ts"), b"c").unwrap();
+        assert!(fs.remove_dir(Path::new("/artifacts")).is_err());
+
+        fs.remove_dir_all(Path::new("/project/src")).unwrap();
+        assert_eq!(
+            fs.files().into_keys().collect::<Vec<_>>(),
+            vec![PathBuf::from("/artifacts/c.ts")]
+        );
+    }
+
+    #[test]
+    fn in_memory_file_system_renames_directories() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/__isograph.tmp/Query/a.ts", "a");
+        fs.add_file("/project/__isograph/Query/b.ts", "b");
+
+        // The destination must not exist.
+        assert!(fs
+            .rename(
+                Path::new("/project/__isograph.tmp"),
+                Path::new("/project/__isograph")
+            )
+            .is_err());
+
+        fs.remove_dir_all(Path::new("/project/__isograph")).unwrap();
+        fs.rename(
+            Path::new("/project/__isograph.tmp"),
+            Path::new("/project/__isograph"),
+        )
+        .unwrap();
+        assert_eq!(
+            fs.files().into_keys().collect::<Vec<_>>(),
+            vec![PathBuf::from("/project/__isograph/Query/a.ts")]
+        );
+        assert!(fs.is_dir(Path::new("/project/__isograph/Query")));
+        assert!(!fs.exists(Path::new("/project/__isograph.tmp")));
+    }
+
+    #[test]
+    fn overlay_file_system_prefers_overlaid_contents() {
+        let base = InMemoryFileSystem::new();
+        base.add_file("/project/a.ts", "saved");
+        let overlay = HashMap::from([
+            (PathBuf::from("/project/a.ts"), "unsaved".to_string()),
+            (PathBuf::from("/project/new.ts"), "new".to_string()),
+        ]);
+        let fs = OverlayFileSystem::new(&base, &overlay);
+
+        assert_eq!(
+            fs.read_to_string(Path::new("/project/a.ts")).unwrap(),
+            "unsaved"
+        );
+        assert!(fs.is_file(Path::new("/project/new.ts")));
+        let mut entries = fs.read_dir(Path::new("/project")).unwrap();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                PathBuf::from("/project/a.ts"),
+                PathBuf::from("/project/new.ts")
+            ]
+        );
+    }
+}
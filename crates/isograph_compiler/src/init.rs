@@ -0,0 +1,445 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use serde_json::Value;
+use thiserror::Error;
+use tracing::info;
+
+use crate::{FileSystem, OsFileSystem};
+
+const CONFIG_FILE_NAME: &str = "isograph.config.json";
+const CONFIG_JSON_SCHEMA: &str = "./node_modules/@isograph/compiler/isograph-config-schema.json";
+const DEFAULT_SCHEMA_PATH: &str = "./schema.graphql";
+const BABEL_PLUGIN_NAME: &str = "@isograph";
+
+/// Babel config files that are JSON, and which can therefore be edited.
+const JSON_BABEL_CONFIG_FILE_NAMES: [&str; 3] = [".babelrc", ".babelrc.json", "babel.config.json"];
+const JS_BABEL_CONFIG_FILE_NAMES: [&str; 6] = [
+    ".babelrc.js",
+    ".babelrc.cjs",
+    ".babelrc.mjs",
+    "babel.config.js",
+    "babel.config.cjs",
+    "babel.config.mjs",
+];
+
+/// Folders that are not searched for the schema.
+const IGNORED_FOLDER_NAMES: [&str; 6] = [
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "out",
+    "__isograph",
+];
+const MAX_SCHEMA_SEARCH_DEPTH: usize = 3;
+
+/// Asks the questions of `isograph init`.
+pub trait InitPrompt {
+    /// Returns the answer to the question, or the default if there is no answer.
+    fn ask(&mut self, question: &str, default: &str) -> String;
+
+    fn confirm(&mut self, question: &str, default: bool) -> bool {
+        let answer = self.ask(question, if default { "Y/n" } else { "y/N" });
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" => true,
+            "n" | "no" => false,
+            _ => default,
+        }
+    }
+}
+
+/// Asks questions over stdin, or accepts every default if accept_defaults is set
+/// or stdin is closed.
+pub struct StdinInitPrompt {
+    pub accept_defaults: bool,
+}
+
+impl InitPrompt for StdinInitPrompt {
+    fn ask(&mut self, question: &str, default: &str) -> String {
+        if self.accept_defaults {
+            return default.to_string();
+        }
+        print!("{question} {} ", format!("({default})").dimmed());
+        let _ = io::stdout().flush();
+        let mut answer = String::new();
+        match io::stdin().lock().read_line(&mut answer) {
+            Ok(0) | Err(_) => {
+                self.accept_defaults = true;
+                default.to_string()
+            }
+            Ok(_) if answer.trim().is_empty() => default.to_string(),
+            Ok(_) => answer.trim().to_string(),
+        }
+    }
+}
+
+/// Create an isograph.config.json in the current working directory, asking for
+/// the schema, the project root and the artifact directory, and optionally add
+/// the Isograph Babel plugin to the Babel config.
+pub fn init_and_print(force: bool, accept_defaults: bool) -> Result<(), Box<dyn Error>> {
+    let directory = std::env::current_dir()?;
+    let next_steps = init_project(
+        &OsFileSystem,
+        &directory,
+        force,
+        &mut StdinInitPrompt { accept_defaults },
+    )?;
+
+    info!("{}", format!("Created {CONFIG_FILE_NAME}.").bright_green());
+    info!(
+        "Next steps:\n{}",
+        next_steps
+            .iter()
+            .map(|step| format!("- {step}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    Ok(())
+}
+
+/// Write the config (and create the artifact directory) in directory, and return
+/// the steps that the user still needs to take.
+pub(crate) fn init_project(
+    fs: &dyn FileSystem,
+    directory: &Path,
+    force: bool,
+    prompt: &mut dyn InitPrompt,
+) -> Result<Vec<String>, InitError> {
+    let config_path = directory.join(CONFIG_FILE_NAME);
+    if fs.exists(&config_path) && !force {
+        return Err(InitError::ConfigAlreadyExists { path: config_path });
+    }
+
+    let mut next_steps = vec![];
+
+    let detected_schema = detect_schema(fs, directory);
+    let schema = prompt.ask(
+        "Where is your GraphQL schema?",
+        detected_schema.as_deref().unwrap_or(DEFAULT_SCHEMA_PATH),
+    );
+    if !fs.is_file(&directory.join(&schema)) {
+        next_steps.push(format!("Download your GraphQL schema to {schema}."));
+    }
+
+    let default_project_root = if fs.is_dir(&directory.join("src")) {
+        "./src"
+    } else {
+        "."
+    };
+    let project_root = prompt.ask(
+        "Which folder contains the files with iso literals?",
+        default_project_root,
+    );
+    let artifact_directory = prompt.ask(
+        "In which folder should the compiler generate the __isograph folder?",
+        &project_root,
+    );
+    fs.create_dir_all(&directory.join(&project_root))?;
+    fs.create_dir_all(&directory.join(&artifact_directory))?;
+
+    fs.write(
+        &config_path,
+        config_file_content(&project_root, &artifact_directory, &schema).as_bytes(),
+    )?;
+
+    if let Some(babel_step) = set_up_babel_plugin(fs, directory, prompt)? {
+        next_steps.push(babel_step);
+    }
+    next_steps.push(format!(
+        "Add \"@iso\": [\"{}/__isograph/iso.ts\"] to compilerOptions.paths in your tsconfig.json.",
+        artifact_directory.trim_end_matches('/')
+    ));
+    next_steps.push("Run `yarn iso --watch` to start the compiler.".to_string());
+
+    Ok(next_steps)
+}
+
+fn config_file_content(project_root: &str, artifact_directory: &str, schema: &str) -> String {
+    let mut fields = vec![
+        ("$schema", CONFIG_JSON_SCHEMA),
+        ("project_root", project_root),
+    ];
+    if artifact_directory != project_root {
+        fields.push(("artifact_directory", artifact_directory));
+    }
+    fields.push(("schema", schema));
+
+    let fields = fields
+        .into_iter()
+        .map(|(key, value)| format!("  \"{key}\": {}", Value::String(value.to_string())))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!("{{\n{fields}\n}}\n")
+}
+
+/// The path (relative to directory) of the file that is most likely to be the
+/// schema, i.e. a .graphql file whose name contains "schema", preferring files
+/// that are less deeply nested.
+fn detect_schema(fs: &dyn FileSystem, directory: &Path) -> Option<String> {
+    let mut schemas = vec![];
+    find_graphql_files(fs, directory, 0, &mut schemas);
+
+    schemas
+        .into_iter()
+        .filter_map(|path| {
+            let relative_path = path.strip_prefix(directory).ok()?.to_path_buf();
+            let file_stem = relative_path.file_stem()?.to_str()?.to_ascii_lowercase();
+            let rank = if file_stem == "schema" {
+                0
+            } else if file_stem.contains("schema") && !file_stem.contains("extension") {
+                1
+            } else {
+                2
+            };
+            Some((rank, relative_path.components().count(), relative_path))
+        })
+        .min()
+        .map(|(_, _, relative_path)| {
+            format!(
+                "./{}",
+                relative_path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            )
+        })
+}
+
+fn find_graphql_files(
+    fs: &dyn FileSystem,
+    directory: &Path,
+    depth: usize,
+    graphql_files: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs.read_dir(directory) else {
+        return;
+    };
+    for entry in entries {
+        let Some(file_name) = entry.file_name().and_then(|file_name| file_name.to_str()) else {
+            continue;
+        };
+        if fs.is_dir(&entry) {
+            if depth < MAX_SCHEMA_SEARCH_DEPTH
+                && !file_name.starts_with('.')
+                && !IGNORED_FOLDER_NAMES.contains(&file_name)
+            {
+                find_graphql_files(fs, &entry, depth + 1, graphql_files);
+            }
+        } else if file_name.ends_with(".graphql") || file_name.ends_with(".graphqls") {
+            graphql_files.push(entry);
+        }
+    }
+}
+
+/// Add the Isograph Babel plugin to the Babel config, if the user agrees, or
+/// return the step that the user needs to take to add it themselves.
+fn set_up_babel_plugin(
+    fs: &dyn FileSystem,
+    directory: &Path,
+    prompt: &mut dyn InitPrompt,
+) -> Result<Option<String>, InitError> {
+    if let Some(file_name) = JSON_BABEL_CONFIG_FILE_NAMES
+        .into_iter()
+        .find(|file_name| fs.is_file(&directory.join(file_name)))
+    {
+        let path = directory.join(file_name);
+        let babel_config = fs.read_to_string(&path)?;
+        let Ok(Value::Object(mut babel_config)) = serde_json::from_str::<Value>(&babel_config)
+        else {
+            return Ok(Some(add_babel_plugin_step(file_name)));
+        };
+        let plugins = babel_config
+            .entry("plugins")
+            .or_insert_with(|| Value::Array(vec![]));
+        let Value::Array(plugins) = plugins else {
+            return Ok(Some(add_babel_plugin_step(file_name)));
+        };
+        if plugins.iter().any(is_isograph_babel_plugin) {
+            return Ok(None);
+        }
+        if !prompt.confirm(
+            &format!("Add the Isograph Babel plugin to {file_name}?"),
+            true,
+        ) {
+            return Ok(Some(add_babel_plugin_step(file_name)));
+        }
+        plugins.push(Value::String(BABEL_PLUGIN_NAME.to_string()));
+        let babel_config = serde_json::to_string_pretty(&Value::Object(babel_config))
+            .expect("Expected Babel config to be serializable");
+        fs.write(&path, format!("{babel_config}\n").as_bytes())?;
+        return Ok(None);
+    }
+
+    if let Some(file_name) = JS_BABEL_CONFIG_FILE_NAMES
+        .into_iter()
+        .find(|file_name| fs.is_file(&directory.join(file_name)))
+    {
+        if fs
+            .read_to_string(&directory.join(file_name))?
+            .contains(BABEL_PLUGIN_NAME)
+        {
+            return Ok(None);
+        }
+        return Ok(Some(add_babel_plugin_step(file_name)));
+    }
+
+    if prompt.confirm(
+        "Create a babel.config.json that enables the Isograph Babel plugin?",
+        false,
+    ) {
+        fs.write(
+            &directory.join("babel.config.json"),
+            format!("{{\n  \"plugins\": [\"{BABEL_PLUGIN_NAME}\"]\n}}\n").as_bytes(),
+        )?;
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Add \"{BABEL_PLUGIN_NAME}\" to the plugins in your Babel config \
+        (install @isograph/babel-plugin first)."
+    )))
+}
+
+fn add_babel_plugin_step(file_name: &str) -> String {
+    format!("Add \"{BABEL_PLUGIN_NAME}\" to the plugins in {file_name}.")
+}
+
+/// e.g. "@isograph", ["@isograph", {}] or "./node_modules/@isograph/babel-plugin"
+fn is_isograph_babel_plugin(plugin: &Value) -> bool {
+    match plugin {
+        Value::String(plugin) => plugin.contains(BABEL_PLUGIN_NAME),
+        Value::Array(plugin_and_options) => plugin_and_options
+            .first()
+            .is_some_and(is_isograph_babel_plugin),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum InitError {
+    #[error(
+        "{} already exists. Pass --force to overwrite it.",
+        path.display()
+    )]
+    ConfigAlreadyExists { path: PathBuf },
+
+    #[error("{0}")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::InMemoryFileSystem;
+
+    use super::*;
+
+    /// Answers questions in order, and accepts the defaults once there are no
+    /// more answers.
+    struct ScriptedPrompt(Vec<&'static str>);
+
+    impl InitPrompt for ScriptedPrompt {
+        fn ask(&mut self, _question: &str, default: &str) -> String {
+            if self.0.is_empty() {
+                default.to_string()
+            } else {
+                self.0.remove(0).to_string()
+            }
+        }
+    }
+
+    fn read(fs: &InMemoryFileSystem, path: &str) -> String {
+        fs.read_to_string(Path::new(path))
+            .expect("Expected file to exist")
+    }
+
+    #[test]
+    fn detects_the_schema_and_project_root() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/src/index.tsx", "");
+        fs.add_file("/project/backend/schema-extension.graphql", "");
+        fs.add_file("/project/backend/schema.graphql", "");
+        fs.add_file("/project/node_modules/some-package/schema.graphql", "");
+        fs.add_file("/project/.babelrc", "{\"presets\": [\"next/babel\"]}");
+
+        let next_steps = init_project(
+            &fs,
+            Path::new("/project"),
+            false,
+            &mut ScriptedPrompt(vec![]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            read(&fs, "/project/isograph.config.json"),
+            "{\n  \
+            \"$schema\": \"./node_modules/@isograph/compiler/isograph-config-schema.json\",\n  \
+            \"project_root\": \"./src\",\n  \
+            \"schema\": \"./backend/schema.graphql\"\n}\n"
+        );
+        assert_eq!(
+            read(&fs, "/project/.babelrc"),
+            "{\n  \"plugins\": [\n    \"@isograph\"\n  ],\n  \"presets\": [\n    \"next/babel\"\n  ]\n}\n"
+        );
+        assert_eq!(
+            next_steps,
+            vec![
+                "Add \"@iso\": [\"./src/__isograph/iso.ts\"] to compilerOptions.paths in your tsconfig.json.",
+                "Run `yarn iso --watch` to start the compiler.",
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_the_answers() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/babel.config.js", "module.exports = {};");
+
+        let next_steps = init_project(
+            &fs,
+            Path::new("/project"),
+            false,
+            &mut ScriptedPrompt(vec!["./api/schema.graphql", "./app", "./generated"]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            read(&fs, "/project/isograph.config.json"),
+            "{\n  \
+            \"$schema\": \"./node_modules/@isograph/compiler/isograph-config-schema.json\",\n  \
+            \"project_root\": \"./app\",\n  \
+            \"artifact_directory\": \"./generated\",\n  \
+            \"schema\": \"./api/schema.graphql\"\n}\n"
+        );
+        assert!(fs.is_dir(Path::new("/project/app")));
+        assert!(fs.is_dir(Path::new("/project/generated")));
+        assert_eq!(
+            next_steps[..2],
+            [
+                "Download your GraphQL schema to ./api/schema.graphql.",
+                "Add \"@isograph\" to the plugins in babel.config.js.",
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_overwrite_an_existing_config() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/isograph.config.json", "{}");
+
+        assert!(matches!(
+            init_project(
+                &fs,
+                Path::new("/project"),
+                false,
+                &mut ScriptedPrompt(vec![])
+            ),
+            Err(InitError::ConfigAlreadyExists { .. })
+        ));
+        assert_eq!(read(&fs, "/project/isograph.config.json"), "{}");
+    }
+}
@@ -1,29 +1,41 @@
 use common_lang_types::{
-    relative_path_from_absolute_and_working_directory, CurrentWorkingDirectory, Location,
-    RelativePathToSourceFile, Span, TextSource, WithLocation, WithSpan,
+    decode_source, line_and_column, relative_path_from_absolute_and_working_directory,
+    CurrentWorkingDirectory, DecodedSource, Location, RelativePathToSourceFile, SelectableName,
+    Span, TextSource, UnvalidatedTypeName, WithLocation, WithSpan,
 };
+use intern::Lookup;
+use isograph_config::{IsoLiteralExtractor, SourceFileFilter};
 use isograph_lang_parser::{
     parse_iso_literal, IsoLiteralExtractionResult, IsographLiteralParseError,
 };
 use isograph_lang_types::{EntrypointDeclaration, IsoLiteralsSource, SelectionType};
-use isograph_schema::{NetworkProtocol, Schema, UnprocessedItem};
-use lazy_static::lazy_static;
+use isograph_literal_extraction::{
+    extract_iso_literals_with_regex, extract_iso_literals_with_swc, IsoLiteralExtraction,
+};
+use isograph_schema::{
+    NetworkProtocol, ProcessClientFieldDeclarationError, Schema, UnprocessedItem,
+};
 use pico::{Database, SourceId};
 use pico_macros::memo;
-use regex::Regex;
 use std::{
-    fs::{self, DirEntry},
+    collections::HashMap,
     io,
     path::{Path, PathBuf},
 };
 
-use crate::{batch_compile::BatchCompileError, create_schema::ContainsIso};
+use tracing::warn;
+
+use crate::{
+    batch_compile::BatchCompileError, create_schema::ContainsIso, file_system::FileSystem,
+};
 
 pub fn read_files_in_folder(
+    fs: &dyn FileSystem,
     folder: &Path,
     current_working_directory: CurrentWorkingDirectory,
+    source_file_filter: &SourceFileFilter,
 ) -> Result<Vec<(RelativePathToSourceFile, String)>, BatchCompileError> {
-    read_dir_recursive(folder)?
+    read_dir_recursive(fs, folder, source_file_filter)?
         .into_iter()
         .filter(|p| {
             let extension = p.extension().and_then(|x| x.to_str());
@@ -38,35 +50,57 @@ pub fn read_files_in_folder(
                 .expect("Expected path to be stringable")
                 .contains("__isograph")
         })
-        .map(|path| read_file(path, current_working_directory))
+        .filter(|p| source_file_filter.includes_file(p))
+        .map(|path| read_file(fs, path, current_working_directory))
         .collect()
 }
 
 pub fn read_file(
+    fs: &dyn FileSystem,
     path: PathBuf,
     current_working_directory: CurrentWorkingDirectory,
 ) -> Result<(RelativePathToSourceFile, String), BatchCompileError> {
     // N.B. we have previously ensured that path is a file
-    let contents = std::fs::read(&path).map_err(|e| BatchCompileError::UnableToReadFile {
-        path: path.clone(),
-        message: e.to_string(),
-    })?;
+    let contents = fs
+        .read(&path)
+        .map_err(|e| BatchCompileError::UnableToReadFile {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
 
     let relative_path =
         relative_path_from_absolute_and_working_directory(current_working_directory, &path);
 
-    let contents = std::str::from_utf8(&contents)
-        .map_err(|e| BatchCompileError::UnableToConvertToString { path, reason: e })?
-        .to_owned();
+    Ok((relative_path, decode_file_content(&path, &contents)))
+}
 
-    Ok((relative_path, contents))
+/// Decode the content of a schema or source file (see decode_source), warning
+/// if it is not valid UTF-8.
+pub(crate) fn decode_file_content(path: &Path, bytes: &[u8]) -> String {
+    let DecodedSource {
+        content,
+        first_invalid_byte,
+    } = decode_source(bytes);
+    if let Some(byte_offset) = first_invalid_byte {
+        let (line, column) = line_and_column(&content, byte_offset);
+        warn!(
+            "{}:{line}:{column}: The file is not valid UTF-8. The first invalid byte is at \
+            offset {byte_offset}. Invalid bytes were replaced with U+FFFD. Save the file as UTF-8.",
+            path.display()
+        );
+    }
+    content
 }
 
-fn read_dir_recursive(root_js_path: &Path) -> Result<Vec<PathBuf>, BatchCompileError> {
+fn read_dir_recursive(
+    fs: &dyn FileSystem,
+    root_js_path: &Path,
+    source_file_filter: &SourceFileFilter,
+) -> Result<Vec<PathBuf>, BatchCompileError> {
     let mut paths = vec![];
 
-    visit_dirs_skipping_isograph(root_js_path, &mut |dir_entry| {
-        paths.push(dir_entry.path());
+    visit_dirs_skipping_isograph(fs, root_js_path, source_file_filter, &mut |path| {
+        paths.push(path);
     })
     .map_err(|e| BatchCompileError::UnableToTraverseDirectory {
         message: e.to_string(),
@@ -76,16 +110,19 @@ fn read_dir_recursive(root_js_path: &Path) -> Result<Vec<PathBuf>, BatchCompileE
 }
 
 // Thanks https://doc.rust-lang.org/stable/std/fs/fn.read_dir.html
-fn visit_dirs_skipping_isograph(dir: &Path, cb: &mut dyn FnMut(&DirEntry)) -> io::Result<()> {
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            if !dir.ends_with(ISOGRAPH_FOLDER) {
-                visit_dirs_skipping_isograph(&path, cb)?;
+fn visit_dirs_skipping_isograph(
+    fs: &dyn FileSystem,
+    dir: &Path,
+    source_file_filter: &SourceFileFilter,
+    cb: &mut dyn FnMut(PathBuf),
+) -> io::Result<()> {
+    for path in fs.read_dir(dir)? {
+        if fs.is_dir(&path) {
+            if !dir.ends_with(ISOGRAPH_FOLDER) && !source_file_filter.excludes_folder(&path) {
+                visit_dirs_skipping_isograph(fs, &path, source_file_filter, cb)?;
             }
         } else {
-            cb(&entry);
+            cb(path);
         }
     }
     Ok(())
@@ -98,6 +135,7 @@ pub fn parse_iso_literals_in_file_content(
     relative_path_to_source_file: RelativePathToSourceFile,
     file_content: &str,
     current_working_directory: CurrentWorkingDirectory,
+    iso_literal_extractor: IsoLiteralExtractor,
 ) -> Result<
     Vec<(IsoLiteralExtractionResult, TextSource)>,
     Vec<WithLocation<IsographLiteralParseError>>,
@@ -105,14 +143,16 @@ pub fn parse_iso_literals_in_file_content(
     let mut extraction_results = vec![];
     let mut isograph_literal_parse_errors = vec![];
 
-    for iso_literal_extraction in extract_iso_literals_from_file_content(file_content) {
+    for iso_literal_extraction in
+        extract_iso_literals_from_file_content(file_content, iso_literal_extractor)
+    {
         match process_iso_literal_extraction(
             iso_literal_extraction,
             relative_path_to_source_file,
             current_working_directory,
         ) {
             Ok(result) => extraction_results.push(result),
-            Err(e) => isograph_literal_parse_errors.push(e),
+            Err(e) => isograph_literal_parse_errors.extend(e),
         }
     }
 
@@ -129,6 +169,7 @@ pub fn parse_iso_literal_in_source(
     db: &Database,
     iso_literals_source_id: SourceId<IsoLiteralsSource>,
     current_working_directory: CurrentWorkingDirectory,
+    iso_literal_extractor: IsoLiteralExtractor,
 ) -> Result<
     Vec<(IsoLiteralExtractionResult, TextSource)>,
     Vec<WithLocation<IsographLiteralParseError>>,
@@ -137,11 +178,18 @@ pub fn parse_iso_literal_in_source(
         relative_path,
         content,
     } = db.get(iso_literals_source_id);
-    parse_iso_literals_in_file_content(*relative_path, content, current_working_directory)
+    parse_iso_literals_in_file_content(
+        *relative_path,
+        content,
+        current_working_directory,
+        iso_literal_extractor,
+    )
 }
 
 #[allow(clippy::type_complexity)]
 pub(crate) fn process_iso_literals<TNetworkProtocol: NetworkProtocol>(
+    db: &Database,
+    iso_literals_sources: &HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>,
     schema: &mut Schema<TNetworkProtocol>,
     contains_iso: ContainsIso,
 ) -> Result<
@@ -151,11 +199,22 @@ pub(crate) fn process_iso_literals<TNetworkProtocol: NetworkProtocol>(
     ),
     BatchCompileError,
 > {
+    let first_declarations = first_client_type_declarations(&contains_iso);
     let mut errors = vec![];
     let mut unprocess_client_field_items = vec![];
     let mut unprocessed_entrypoints = vec![];
     for iso_literals in contains_iso.files.into_values() {
         for (extraction_result, text_source) in iso_literals {
+            if let Some(error) = duplicate_declaration_error(
+                db,
+                iso_literals_sources,
+                &first_declarations,
+                &extraction_result,
+                text_source,
+            ) {
+                errors.push(error);
+                continue;
+            }
             match extraction_result {
                 IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) => {
                     match schema
@@ -195,16 +254,112 @@ pub(crate) fn process_iso_literals<TNetworkProtocol: NetworkProtocol>(
     }
 }
 
+pub(crate) type ClientTypeKey = (UnvalidatedTypeName, SelectableName);
+
+/// The parent type and name of a client field or client pointer declaration, and
+/// the span of its name relative to the iso literal.
+pub(crate) fn client_type_declaration(
+    extraction_result: &IsoLiteralExtractionResult,
+) -> Option<(ClientTypeKey, Span)> {
+    match extraction_result {
+        IsoLiteralExtractionResult::ClientFieldDeclaration(declaration) => Some((
+            (
+                declaration.item.parent_type.item,
+                declaration.item.client_field_name.item.into(),
+            ),
+            declaration.item.client_field_name.span,
+        )),
+        IsoLiteralExtractionResult::ClientPointerDeclaration(declaration) => Some((
+            (
+                declaration.item.parent_type.item,
+                declaration.item.client_pointer_name.item.into(),
+            ),
+            declaration.item.client_pointer_name.span,
+        )),
+        IsoLiteralExtractionResult::EntrypointDeclaration(_) => None,
+    }
+}
+
+/// The span of a name, as byte offsets into the file.
+fn absolute_span(text_source: TextSource, span: Span) -> Span {
+    span.with_offset(text_source.span.map(|span| span.start).unwrap_or(0))
+}
+
+/// For each client field and client pointer, the file and absolute span of its first
+/// declaration. Files are visited in no particular order, so declarations are ordered
+/// by file path and position, so that the same duplicate is reported on every run.
+fn first_client_type_declarations(
+    contains_iso: &ContainsIso,
+) -> HashMap<ClientTypeKey, (RelativePathToSourceFile, Span)> {
+    let mut first_declarations: HashMap<ClientTypeKey, (RelativePathToSourceFile, Span)> =
+        HashMap::new();
+    for (extraction_result, text_source) in contains_iso.files.values().flatten() {
+        let Some((key, span)) = client_type_declaration(extraction_result) else {
+            continue;
+        };
+        let declaration = (
+            text_source.relative_path_to_source_file,
+            absolute_span(*text_source, span),
+        );
+        let sort_key =
+            |(path, span): &(RelativePathToSourceFile, Span)| (path.lookup(), span.start);
+        first_declarations
+            .entry(key)
+            .and_modify(|first_declaration| {
+                if sort_key(&declaration) < sort_key(first_declaration) {
+                    *first_declaration = declaration;
+                }
+            })
+            .or_insert(declaration);
+    }
+    first_declarations
+}
+
+/// If this declares a client field or client pointer that is also declared elsewhere,
+/// and this is not the first declaration, an error pointing at both declarations.
+fn duplicate_declaration_error(
+    db: &Database,
+    iso_literals_sources: &HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>,
+    first_declarations: &HashMap<ClientTypeKey, (RelativePathToSourceFile, Span)>,
+    extraction_result: &IsoLiteralExtractionResult,
+    text_source: TextSource,
+) -> Option<WithLocation<ProcessClientFieldDeclarationError>> {
+    let (key, span) = client_type_declaration(extraction_result)?;
+    let (previous_file, previous_span) = *first_declarations.get(&key)?;
+    if previous_file == text_source.relative_path_to_source_file
+        && previous_span == absolute_span(text_source, span)
+    {
+        return None;
+    }
+    let (parent_type_name, client_type_name) = key;
+    let (previous_line, previous_column) = line_and_column(
+        &db.get(iso_literals_sources[&previous_file]).content,
+        previous_span.start as usize,
+    );
+    Some(WithLocation::new(
+        ProcessClientFieldDeclarationError::DuplicateClientTypeDeclaration {
+            parent_type_name,
+            client_type_name,
+            previous_file,
+            previous_line,
+            previous_column,
+        },
+        Location::new(text_source, span),
+    ))
+}
+
 pub fn process_iso_literal_extraction(
     iso_literal_extraction: IsoLiteralExtraction<'_>,
     relative_path_to_source_file: RelativePathToSourceFile,
     current_working_directory: CurrentWorkingDirectory,
-) -> Result<(IsoLiteralExtractionResult, TextSource), WithLocation<IsographLiteralParseError>> {
+) -> Result<(IsoLiteralExtractionResult, TextSource), Vec<WithLocation<IsographLiteralParseError>>>
+{
     let IsoLiteralExtraction {
         iso_literal_text,
         iso_literal_start_index,
         has_associated_js_function,
         const_export_name,
+        const_export_name_start_index: _,
         iso_function_called_with_paren: has_paren,
     } = iso_literal_extraction;
     let text_source = TextSource {
@@ -217,10 +372,10 @@ pub fn process_iso_literal_extraction(
     };
 
     if !has_paren {
-        return Err(WithLocation::new(
+        return Err(vec![WithLocation::new(
             IsographLiteralParseError::ExpectedParenthesesAroundIsoLiteral,
             Location::new(text_source, Span::todo_generated()),
-        ));
+        )]);
     }
 
     let iso_literal_extraction_result = parse_iso_literal(
@@ -235,52 +390,27 @@ pub fn process_iso_literal_extraction(
         IsoLiteralExtractionResult::ClientFieldDeclaration(_)
     );
     if is_client_field_declaration && !has_associated_js_function {
-        return Err(WithLocation::new(
+        return Err(vec![WithLocation::new(
             IsographLiteralParseError::ExpectedAssociatedJsFunction,
             Location::new(text_source, Span::todo_generated()),
-        ));
+        )]);
     }
 
     Ok((iso_literal_extraction_result, text_source))
 }
 
 pub(crate) static ISOGRAPH_FOLDER: &str = "__isograph";
-lazy_static! {
-    static ref EXTRACT_ISO_LITERAL: Regex =
-        Regex::new(r"(// )?(export const ([^ ]+) =\s+)?iso(\()?`([^`]+)`(\))?(\()?").unwrap();
-}
-
-pub struct IsoLiteralExtraction<'a> {
-    pub const_export_name: Option<&'a str>,
-    pub iso_literal_text: &'a str,
-    pub iso_literal_start_index: usize,
-    pub has_associated_js_function: bool,
-    /// true if the iso function is called as iso(`...`), and false if it is
-    /// called as iso`...`. This is tracked as a separate field because some users
-    /// may assume that you write iso literals like you would graphql/gql literals
-    /// (which are written as graphql`...`), and having a separate field means
-    /// we can display a helpful error message (instead of silently ignoring.)
-    pub iso_function_called_with_paren: bool,
-}
 
+/// Find the iso literals in the contents of a file. With the SWC extractor, files
+/// that cannot be parsed (e.g. because they are being edited in the language
+/// server) are scanned with the regex extractor instead.
 pub fn extract_iso_literals_from_file_content(
     content: &str,
-) -> impl Iterator<Item = IsoLiteralExtraction> + '_ {
-    EXTRACT_ISO_LITERAL
-        .captures_iter(content)
-        .flat_map(|captures| {
-            let iso_literal_match = captures.get(5).unwrap();
-            if captures.get(1).is_some() {
-                // HACK
-                // this iso literal is commented out using //, so skip it.
-                return None;
-            }
-            Some(IsoLiteralExtraction {
-                const_export_name: captures.get(2).map(|_| captures.get(3).unwrap().as_str()),
-                iso_literal_text: iso_literal_match.as_str(),
-                iso_literal_start_index: iso_literal_match.start(),
-                has_associated_js_function: captures.get(7).is_some(),
-                iso_function_called_with_paren: captures.get(4).is_some(),
-            })
-        })
+    iso_literal_extractor: IsoLiteralExtractor,
+) -> Vec<IsoLiteralExtraction<'_>> {
+    match iso_literal_extractor {
+        IsoLiteralExtractor::Regex => extract_iso_literals_with_regex(content).collect(),
+        IsoLiteralExtractor::Swc => extract_iso_literals_with_swc(content)
+            .unwrap_or_else(|| extract_iso_literals_with_regex(content).collect()),
+    }
 }
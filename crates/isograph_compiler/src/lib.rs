@@ -1,16 +1,48 @@
 mod add_selection_sets;
 pub mod batch_compile;
+mod clean;
+mod compilation_stats;
+mod compiler_api;
 mod compiler_state;
 mod create_schema;
+pub mod daemon;
+mod dependency_graph;
+mod diagnostics;
+mod file_system;
+mod init;
 mod isograph_literals;
+mod persist_artifacts;
+mod schema_diff;
+mod skip_invalid_iso_literals;
 mod source_files;
+mod unused_client_types_report;
 pub mod watch;
 mod with_duration;
 mod write_artifacts;
 
 pub use batch_compile::compile_and_print;
+pub use clean::{clean_and_print, CleanError};
+pub use compilation_stats::*;
+pub use compiler_api::{compile, compile_with_persistence, CompileResult, NoopReporter, Reporter};
+pub use compiler_state::{
+    compile_sources, create_validated_schema, CompilerState, StandardSources,
+};
+pub use create_schema::ContainsIsoStats;
+pub use daemon::handle_daemon_command;
+pub use dependency_graph::{dependency_graph_and_print, DependencyGraphFormat};
+pub use diagnostics::*;
+pub use file_system::{FileSystem, InMemoryFileSystem, OsFileSystem, OverlayFileSystem};
+pub use init::{init_and_print, InitError, InitPrompt, StdinInitPrompt};
+pub use isograph_literal_extraction::IsoLiteralExtraction;
 pub use isograph_literals::{
     extract_iso_literals_from_file_content, parse_iso_literals_in_file_content,
-    IsoLiteralExtraction,
 };
+pub use persist_artifacts::{
+    persist_artifacts_for_config, BundleArtifacts, FileSystemArtifacts, InMemoryArtifacts,
+    PersistArtifacts, ARTIFACT_BUNDLE_FILE_NAME,
+};
+pub use schema_diff::schema_diff_and_print;
+pub use skip_invalid_iso_literals::SkippedIsoLiterals;
+pub use source_files::SourceFiles;
 pub use watch::handle_watch_command;
+pub use write_artifacts::GenerateArtifactsError;
@@ -0,0 +1,335 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use common_lang_types::ArtifactPathAndContent;
+use isograph_config::{ArtifactPersistence, CompilerConfig};
+
+use crate::{
+    file_system::FileSystem,
+    write_artifacts::{write_artifacts_to_disk, GenerateArtifactsError, WrittenArtifacts},
+};
+
+/// The file to which the bundle backend writes every artifact.
+pub const ARTIFACT_BUNDLE_FILE_NAME: &str = "artifacts.ts";
+
+/// Persists the artifacts generated by a compilation.
+///
+/// Build tools that embed the compiler can provide their own implementation, e.g.
+/// to upload the artifacts instead of writing them to the artifact directory.
+pub trait PersistArtifacts {
+    /// Persist the artifacts, returning the number of artifacts that were written.
+    ///
+    /// If `keep_stale_artifacts` is set (i.e. because some iso literals were skipped),
+    /// previously persisted artifacts that are no longer generated must be kept,
+    /// since they may have been generated from the skipped iso literals.
+    fn persist_artifacts(
+        &mut self,
+        fs: &dyn FileSystem,
+        artifacts: Vec<ArtifactPathAndContent>,
+        config: &CompilerConfig,
+        keep_stale_artifacts: bool,
+    ) -> Result<usize, GenerateArtifactsError>;
+}
+
+/// The backend selected by the artifact_persistence option.
+pub fn persist_artifacts_for_config(config: &CompilerConfig) -> Box<dyn PersistArtifacts + Send> {
+    match config.options.artifact_persistence {
+        ArtifactPersistence::FileSystem => Box::<FileSystemArtifacts>::default(),
+        ArtifactPersistence::Bundle => Box::<BundleArtifacts>::default(),
+    }
+}
+
+/// Writes each artifact to its own file in the artifact directory. This is the
+/// default.
+///
+/// The first compilation recreates the artifact directory. Subsequent compilations
+/// (e.g. in watch mode) only touch the artifacts that changed.
+#[derive(Debug, Default)]
+pub struct FileSystemArtifacts {
+    written_artifacts: Option<WrittenArtifacts>,
+}
+
+impl PersistArtifacts for FileSystemArtifacts {
+    fn persist_artifacts(
+        &mut self,
+        fs: &dyn FileSystem,
+        artifacts: Vec<ArtifactPathAndContent>,
+        config: &CompilerConfig,
+        keep_stale_artifacts: bool,
+    ) -> Result<usize, GenerateArtifactsError> {
+        write_artifacts_to_disk(
+            fs,
+            artifacts,
+            &config.artifact_directory.absolute_path,
+            config.options.artifact_layout,
+            &mut self.written_artifacts,
+            config.keep_tmp_on_failure,
+            keep_stale_artifacts,
+        )
+    }
+}
+
+/// Keeps the artifacts in memory instead of writing them anywhere, e.g. for tests,
+/// or to preview the artifacts of unsaved changes.
+#[derive(Debug, Default)]
+pub struct InMemoryArtifacts {
+    /// Keyed by path, relative to the artifact directory.
+    artifacts: BTreeMap<PathBuf, String>,
+}
+
+impl InMemoryArtifacts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The artifacts of the last compilation, keyed by path, relative to the
+    /// artifact directory.
+    pub fn artifacts(&self) -> &BTreeMap<PathBuf, String> {
+        &self.artifacts
+    }
+
+    pub fn get(&self, relative_path: impl AsRef<Path>) -> Option<&str> {
+        self.artifacts
+            .get(relative_path.as_ref())
+            .map(String::as_str)
+    }
+}
+
+impl PersistArtifacts for InMemoryArtifacts {
+    fn persist_artifacts(
+        &mut self,
+        _fs: &dyn FileSystem,
+        artifacts: Vec<ArtifactPathAndContent>,
+        config: &CompilerConfig,
+        keep_stale_artifacts: bool,
+    ) -> Result<usize, GenerateArtifactsError> {
+        let artifacts = artifacts_by_relative_path(artifacts, config);
+        Ok(replace_artifacts(
+            &mut self.artifacts,
+            artifacts,
+            keep_stale_artifacts,
+        ))
+    }
+}
+
+/// Writes every artifact into a single artifacts.ts file in the artifact directory,
+/// which exports a map from artifact path (relative to the artifact directory) to
+/// artifact content. This is useful in environments in which writing many files is
+/// slow or not possible.
+#[derive(Debug, Default)]
+pub struct BundleArtifacts {
+    /// The artifacts in the bundle that was last written. If some iso literals are
+    /// skipped, the artifacts generated from them are kept in the bundle.
+    artifacts: BTreeMap<PathBuf, String>,
+}
+
+impl PersistArtifacts for BundleArtifacts {
+    fn persist_artifacts(
+        &mut self,
+        fs: &dyn FileSystem,
+        artifacts: Vec<ArtifactPathAndContent>,
+        config: &CompilerConfig,
+        keep_stale_artifacts: bool,
+    ) -> Result<usize, GenerateArtifactsError> {
+        let artifacts = artifacts_by_relative_path(artifacts, config);
+        let mut bundled_artifacts = std::mem::take(&mut self.artifacts);
+        let count = replace_artifacts(&mut bundled_artifacts, artifacts, keep_stale_artifacts);
+
+        let artifact_directory = &config.artifact_directory.absolute_path;
+        let bundle_path = artifact_directory.join(ARTIFACT_BUNDLE_FILE_NAME);
+        let content = bundle_content(&bundled_artifacts);
+        // The bundle is only rewritten if it changed, so that watchers of the
+        // artifact directory are not triggered needlessly.
+        if fs.read_to_string(&bundle_path).ok().as_deref() != Some(content.as_str()) {
+            fs.create_dir_all(artifact_directory).map_err(|e| {
+                GenerateArtifactsError::UnableToCreateDirectory {
+                    path: artifact_directory.clone(),
+                    message: e.to_string(),
+                }
+            })?;
+            fs.write(&bundle_path, content.as_bytes()).map_err(|e| {
+                GenerateArtifactsError::UnableToWriteToArtifactFile {
+                    path: bundle_path.clone(),
+                    message: e.to_string(),
+                }
+            })?;
+        }
+
+        // If writing fails, the next compilation writes the bundle from scratch.
+        self.artifacts = bundled_artifacts;
+        Ok(count)
+    }
+}
+
+fn bundle_content(artifacts: &BTreeMap<PathBuf, String>) -> String {
+    let mut content = String::from(
+        "// Every artifact generated by the Isograph compiler, keyed by its path\n\
+        // relative to the artifact directory.\n\
+        const artifacts: { readonly [path: string]: string } = {\n",
+    );
+    for (relative_path, artifact_content) in artifacts {
+        content.push_str(&format!(
+            "  {}: {},\n",
+            json_string(&relative_path.to_string_lossy().replace('\\', "/")),
+            json_string(artifact_content)
+        ));
+    }
+    content.push_str("};\n\nexport default artifacts;\n");
+    content
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::to_string(value).expect("Expected string to be serializable")
+}
+
+fn artifacts_by_relative_path(
+    artifacts: Vec<ArtifactPathAndContent>,
+    config: &CompilerConfig,
+) -> BTreeMap<PathBuf, String> {
+    artifacts
+        .into_iter()
+        .map(|artifact| {
+            (
+                config
+                    .options
+                    .artifact_layout
+                    .generate_path(artifact.type_and_field, artifact.file_name),
+                artifact.file_content,
+            )
+        })
+        .collect()
+}
+
+/// Replace the previous artifacts with the new artifacts, returning the number of
+/// artifacts that changed.
+fn replace_artifacts(
+    previous_artifacts: &mut BTreeMap<PathBuf, String>,
+    artifacts: BTreeMap<PathBuf, String>,
+    keep_stale_artifacts: bool,
+) -> usize {
+    let count = artifacts
+        .iter()
+        .filter(|(relative_path, content)| previous_artifacts.get(*relative_path) != Some(content))
+        .count();
+    if keep_stale_artifacts {
+        previous_artifacts.extend(artifacts);
+    } else {
+        *previous_artifacts = artifacts;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use common_lang_types::ObjectTypeAndFieldName;
+    use intern::string_key::Intern;
+    use isograph_config::{absolute_and_relative_paths, SourceFileFilter};
+
+    use super::*;
+    use crate::file_system::InMemoryFileSystem;
+
+    fn artifact(type_name: &str, field_name: &str, file_content: &str) -> ArtifactPathAndContent {
+        ArtifactPathAndContent {
+            type_and_field: Some(ObjectTypeAndFieldName {
+                type_name: type_name.intern().into(),
+                field_name: field_name.intern().into(),
+            }),
+            file_name: "entrypoint.ts".intern().into(),
+            file_content: file_content.to_string(),
+        }
+    }
+
+    fn config() -> CompilerConfig {
+        let current_working_directory = "/project".intern().into();
+        let project_root = PathBuf::from("/project/src");
+        CompilerConfig {
+            config_location: PathBuf::from("/project/isograph.config.json"),
+            project_name: None,
+            project_root: project_root.clone(),
+            source_file_filter: SourceFileFilter::include_all(project_root.clone()),
+            artifact_directory: absolute_and_relative_paths(
+                current_working_directory,
+                project_root.join("__isograph"),
+            ),
+            schema: absolute_and_relative_paths(
+                current_working_directory,
+                PathBuf::from("/project/schema.graphql"),
+            ),
+            schema_extensions: vec![],
+            entrypoints: vec![],
+            options: Default::default(),
+            current_working_directory,
+            keep_tmp_on_failure: false,
+        }
+    }
+
+    #[test]
+    fn bundle_contains_every_artifact() {
+        let fs = InMemoryFileSystem::new();
+        let config = config();
+        let mut bundle = BundleArtifacts::default();
+
+        let count = bundle
+            .persist_artifacts(
+                &fs,
+                vec![
+                    artifact("Query", "PetDetail", "pet"),
+                    artifact("Query", "HomePage", "'home'\n"),
+                ],
+                &config,
+                false,
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            fs.read_to_string(Path::new("/project/src/__isograph/artifacts.ts"))
+                .unwrap(),
+            "// Every artifact generated by the Isograph compiler, keyed by its path\n\
+            // relative to the artifact directory.\n\
+            const artifacts: { readonly [path: string]: string } = {\n  \
+            \"Query/HomePage/entrypoint.ts\": \"'home'\\n\",\n  \
+            \"Query/PetDetail/entrypoint.ts\": \"pet\",\n\
+            };\n\n\
+            export default artifacts;\n"
+        );
+
+        // PetDetail was skipped, so it is kept in the bundle
+        let count = bundle
+            .persist_artifacts(
+                &fs,
+                vec![artifact("Query", "HomePage", "'home'\n")],
+                &config,
+                true,
+            )
+            .unwrap();
+        assert_eq!(count, 0);
+        assert_eq!(fs.files().len(), 1);
+        assert!(fs
+            .read_to_string(Path::new("/project/src/__isograph/artifacts.ts"))
+            .unwrap()
+            .contains("PetDetail"));
+    }
+
+    #[test]
+    fn in_memory_artifacts_are_not_written() {
+        let fs = InMemoryFileSystem::new();
+        let mut in_memory_artifacts = InMemoryArtifacts::new();
+
+        let count = in_memory_artifacts
+            .persist_artifacts(
+                &fs,
+                vec![artifact("Query", "HomePage", "home")],
+                &config(),
+                false,
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(fs.files().is_empty());
+        assert_eq!(
+            in_memory_artifacts.get("Query/HomePage/entrypoint.ts"),
+            Some("home")
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use common_lang_types::CurrentWorkingDirectory;
+use isograph_config::{absolute_and_relative_paths, create_configs, CompilerConfig};
+use isograph_schema::{schema_change_impact, NetworkProtocol, Schema};
+use pico::Database;
+use tracing::{error, info};
+
+use crate::{
+    batch_compile::BatchCompileError,
+    compilation_stats::PhaseTimings,
+    compiler_state::StandardSources,
+    create_schema::create_schema,
+    diagnostics::{diagnostics_from_error, with_location_to_diagnostic, Diagnostic, OutputFormat},
+    file_system::OsFileSystem,
+    source_files::{read_schema, read_schema_extensions, SourceFiles},
+};
+
+/// Report which client fields, client pointers and entrypoints of each project
+/// would break if the schema at old_schema_path were replaced with the schema at
+/// new_schema_path. The client fields are processed against the old schema, which
+/// is used instead of the schema in the config. Schema extensions are applied to
+/// both schemas.
+///
+/// Returns an error if anything would break, or if either schema is invalid.
+pub fn schema_diff_and_print<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config_location: PathBuf,
+    current_working_directory: CurrentWorkingDirectory,
+    old_schema_path: PathBuf,
+    new_schema_path: PathBuf,
+    output_format: OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    let mut failed = false;
+    for config in create_configs(config_location, current_working_directory) {
+        let prefix = config
+            .project_name
+            .as_ref()
+            .map(|project_name| format!("[{project_name}] "))
+            .unwrap_or_default();
+
+        let old_config = config_with_schema(&config, &old_schema_path)?;
+        let mut old_db = Database::new();
+        let old_source_files = SourceFiles::read_all(&mut old_db, &OsFileSystem, &old_config)?;
+        let old_schema = create_schema::<TNetworkProtocol>(
+            &old_db,
+            &old_source_files.sources,
+            &old_source_files.iso_literals,
+            &old_config,
+            &mut PhaseTimings::default(),
+        )
+        .inspect_err(|e| {
+            print_schema_error(
+                &format!("{prefix}Unable to process the old schema."),
+                e.as_ref(),
+                &config,
+                output_format,
+            )
+        })?
+        .0;
+
+        let new_schema = create_server_schema::<TNetworkProtocol>(&config, &new_schema_path)
+            .inspect_err(|e| {
+                print_schema_error(
+                    &format!("{prefix}Unable to process the new schema."),
+                    e.as_ref(),
+                    &config,
+                    output_format,
+                )
+            })?;
+
+        let impact = schema_change_impact(&old_schema, &new_schema);
+        if impact.breaking_changes.is_empty() {
+            info!(
+                "{}",
+                format!("{prefix}No client fields or entrypoints would break.").bright_green()
+            );
+            continue;
+        }
+        failed = true;
+
+        let diagnostics = impact
+            .breaking_changes
+            .iter()
+            .map(with_location_to_diagnostic)
+            .collect::<Vec<_>>();
+        print_diagnostics(
+            &format!(
+                "{prefix}The new schema would break {} {}.",
+                diagnostics.len(),
+                if diagnostics.len() == 1 {
+                    "selection"
+                } else {
+                    "selections"
+                }
+            ),
+            diagnostics,
+            &config,
+            output_format,
+        );
+
+        if !impact.affected_entrypoints.is_empty() {
+            error!(
+                "{}{}",
+                format!("{prefix}The following entrypoints would break:").bright_red(),
+                impact
+                    .affected_entrypoints
+                    .iter()
+                    .fold(String::new(), |mut output, entrypoint| {
+                        output.push_str(&format!(
+                            "\n  {}.{}",
+                            entrypoint.type_name, entrypoint.field_name
+                        ));
+                        output
+                    })
+            );
+        }
+    }
+
+    if failed {
+        Err(Box::new(
+            BatchCompileError::SchemaChangeWouldBreakClientFields,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn print_schema_error(
+    message: &str,
+    error: &(dyn Error + 'static),
+    config: &CompilerConfig,
+    output_format: OutputFormat,
+) {
+    print_diagnostics(
+        message,
+        diagnostics_from_error(error),
+        config,
+        output_format,
+    );
+}
+
+fn print_diagnostics(
+    message: &str,
+    diagnostics: Vec<Diagnostic>,
+    config: &CompilerConfig,
+    output_format: OutputFormat,
+) {
+    match output_format {
+        OutputFormat::Human => {
+            let rendered = diagnostics
+                .iter()
+                .fold(String::new(), |mut output, diagnostic| {
+                    output.push_str(&format!("\n{diagnostic}\n"));
+                    output
+                });
+            error!("{}\n{}", message.bright_red(), rendered);
+        }
+        OutputFormat::Json => {
+            error!("{}", message.bright_red());
+            for mut diagnostic in diagnostics {
+                diagnostic.project = config.project_name.clone();
+                println!("{}", diagnostic.to_json());
+            }
+        }
+    }
+}
+
+/// Create a schema containing only the server schema at schema_path (and the
+/// schema extensions of the config), i.e. without any client fields.
+fn create_server_schema<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config: &CompilerConfig,
+    schema_path: &Path,
+) -> Result<Schema<TNetworkProtocol>, Box<dyn Error>> {
+    let mut config = config_with_schema(config, schema_path)?;
+    // Entrypoints declared in the config refer to client fields, which we
+    // do not process.
+    config.entrypoints = vec![];
+
+    let mut db = Database::new();
+    let sources = (
+        read_schema(
+            &mut db,
+            &OsFileSystem,
+            &config.schema,
+            config.current_working_directory,
+        )?,
+        read_schema_extensions(&mut db, &OsFileSystem, &config)?,
+    );
+    Ok(create_schema::<TNetworkProtocol>(
+        &db,
+        &sources,
+        &HashMap::new(),
+        &config,
+        &mut PhaseTimings::default(),
+    )?
+    .0)
+}
+
+fn config_with_schema(
+    config: &CompilerConfig,
+    schema_path: &Path,
+) -> Result<CompilerConfig, BatchCompileError> {
+    let absolute_schema_path =
+        schema_path
+            .canonicalize()
+            .map_err(|e| BatchCompileError::UnableToLoadSchema {
+                path: schema_path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+    let mut config = config.clone();
+    config.schema =
+        absolute_and_relative_paths(config.current_working_directory, absolute_schema_path);
+    Ok(config)
+}
@@ -0,0 +1,188 @@
+use std::{collections::BTreeSet, error::Error};
+
+use common_lang_types::{Location, RelativePathToSourceFile};
+use isograph_config::CompilerConfig;
+use isograph_schema::{NetworkProtocol, Schema};
+use pico::Database;
+
+use crate::{
+    compilation_stats::PhaseTimings,
+    compiler_state::{create_validated_schema, StandardSources},
+    create_schema::ContainsIsoStats,
+    diagnostics::{diagnostics_from_error, Diagnostic},
+    source_files::SourceFiles,
+};
+
+/// The source files that were left out of a compilation because they contain
+/// invalid iso literals, or select client fields from such files. See the
+/// `skip_invalid_iso_literals` option.
+#[derive(Debug, Default)]
+pub struct SkippedIsoLiterals {
+    pub files: BTreeSet<RelativePathToSourceFile>,
+    /// The errors because of which the files were skipped.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl SkippedIsoLiterals {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Create the validated schema, leaving out the source files whose iso literals
+/// are invalid.
+///
+/// Leaving out a file removes its client fields, which may cause iso literals in
+/// other files that select them to become invalid. So, we repeatedly leave out the
+/// files in which errors occurred, until either the schema is valid, or an error
+/// occurs that is not in an iso literal (e.g. in the schema), in which case we
+/// return the original error.
+pub(crate) fn create_validated_schema_skipping_invalid_iso_literals<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources>,
+>(
+    db: &Database,
+    source_files: &SourceFiles,
+    config: &CompilerConfig,
+    phase_timings: &mut PhaseTimings,
+    original_error: Box<dyn Error>,
+) -> Result<
+    (
+        Schema<TNetworkProtocol>,
+        ContainsIsoStats,
+        SkippedIsoLiterals,
+    ),
+    Box<dyn Error>,
+> {
+    let mut skipped_iso_literals = SkippedIsoLiterals::default();
+    let mut remaining_source_files = source_files.clone();
+    let mut diagnostics = diagnostics_from_error(original_error.as_ref());
+
+    loop {
+        let Some(files_with_errors) = files_with_errors(&diagnostics, &remaining_source_files)
+            .filter(|files_with_errors| !files_with_errors.is_empty())
+        else {
+            return Err(original_error);
+        };
+        for file in &files_with_errors {
+            remaining_source_files.iso_literals.remove(file);
+        }
+        skipped_iso_literals.files.extend(files_with_errors);
+        skipped_iso_literals.diagnostics.extend(diagnostics);
+
+        match create_validated_schema::<TNetworkProtocol>(
+            db,
+            &remaining_source_files,
+            config,
+            phase_timings,
+        ) {
+            Ok((schema, stats)) => return Ok((schema, stats, skipped_iso_literals)),
+            Err(e) => diagnostics = diagnostics_from_error(e.as_ref()),
+        }
+    }
+}
+
+/// The iso literal files in which the errors occurred. None if any error did not
+/// occur in one of these files, since skipping files would not fix it.
+fn files_with_errors(
+    diagnostics: &[Diagnostic],
+    source_files: &SourceFiles,
+) -> Option<BTreeSet<RelativePathToSourceFile>> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| match diagnostic.location {
+            Some(Location::Embedded(location)) => {
+                let relative_path = location.text_source.relative_path_to_source_file;
+                source_files
+                    .iso_literals
+                    .contains_key(&relative_path)
+                    .then_some(relative_path)
+            }
+            Some(Location::Generated) | None => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use graphql_network_protocol::GraphQLNetworkProtocol;
+    use intern::{string_key::Intern, Lookup};
+    use isograph_config::{absolute_and_relative_paths, CompilerConfigOptions, SourceFileFilter};
+
+    use super::*;
+    use crate::{
+        compile,
+        file_system::{FileSystem, InMemoryFileSystem},
+        NoopReporter,
+    };
+
+    fn client_field(name: &str, selections: &str) -> String {
+        format!(
+            "import {{ iso }} from '@iso';\n\
+            export const {name} = iso(`\n  field Query.{name} {{\n    {selections}\n  }}\n`)\
+            (({{ data }}) => data);\n"
+        )
+    }
+
+    #[test]
+    fn skips_invalid_iso_literals_and_files_that_depend_on_them() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/schema.graphql", "type Query { name: String! }");
+        fs.add_file("/project/src/Valid.tsx", client_field("Valid", "name"));
+        fs.add_file("/project/src/Invalid.tsx", client_field("Invalid", "nmae"));
+        fs.add_file(
+            "/project/src/DependsOnInvalid.tsx",
+            client_field("DependsOnInvalid", "Invalid"),
+        );
+        let previous_artifact = "/project/src/__isograph/Query/Invalid/resolver_reader.ts";
+        fs.add_file(previous_artifact, "previous");
+
+        let current_working_directory = "/project".intern().into();
+        let project_root = PathBuf::from("/project/src");
+        let config = CompilerConfig {
+            config_location: PathBuf::from("/project/isograph.config.json"),
+            project_name: None,
+            project_root: project_root.clone(),
+            source_file_filter: SourceFileFilter::include_all(project_root.clone()),
+            artifact_directory: absolute_and_relative_paths(
+                current_working_directory,
+                project_root.join("__isograph"),
+            ),
+            schema: absolute_and_relative_paths(
+                current_working_directory,
+                PathBuf::from("/project/schema.graphql"),
+            ),
+            schema_extensions: vec![],
+            entrypoints: vec![],
+            options: CompilerConfigOptions {
+                skip_invalid_iso_literals: true,
+                ..Default::default()
+            },
+            current_working_directory,
+            keep_tmp_on_failure: false,
+        };
+
+        let result = compile::<GraphQLNetworkProtocol>(&config, &fs, &mut NoopReporter);
+
+        assert!(!result.succeeded());
+        assert_eq!(result.diagnostics.len(), 2);
+        let stats = result.stats.expect("Expected artifacts to be written");
+        assert_eq!(
+            stats
+                .skipped_iso_literals
+                .files
+                .iter()
+                .map(|file| file.lookup())
+                .collect::<Vec<_>>(),
+            vec!["src/DependsOnInvalid.tsx", "src/Invalid.tsx"]
+        );
+        assert!(fs.exists(Path::new(
+            "/project/src/__isograph/Query/Valid/param_type.ts"
+        )));
+        assert!(fs.exists(Path::new(previous_artifact)));
+        assert!(!fs.exists(Path::new(
+            "/project/src/__isograph/Query/DependsOnInvalid/param_type.ts"
+        )));
+    }
+}
@@ -11,12 +11,13 @@ use common_lang_types::{
 use intern::Lookup;
 use isograph_config::{absolute_and_relative_paths, CompilerConfig};
 use isograph_lang_types::{IsoLiteralsSource, SchemaSource};
-use pico::{Database, SourceId};
+use pico::{Database, Durability, SourceId};
 
 use crate::{
     batch_compile::BatchCompileError,
     compiler_state::StandardSources,
-    isograph_literals::{read_file, read_files_in_folder},
+    file_system::FileSystem,
+    isograph_literals::{decode_file_content, read_file, read_files_in_folder},
     watch::{ChangedFileKind, SourceEventKind, SourceFileEvent},
 };
 
@@ -27,10 +28,14 @@ pub struct SourceFiles {
 }
 
 impl SourceFiles {
-    pub fn read_all(db: &mut Database, config: &CompilerConfig) -> Result<Self, Box<dyn Error>> {
-        let schema = read_schema(db, &config.schema, config.current_working_directory)?;
-        let schema_extensions = read_schema_extensions(db, config)?;
-        let iso_literals = read_iso_literals_from_project_root(db, config)?;
+    pub fn read_all(
+        db: &mut Database,
+        fs: &dyn FileSystem,
+        config: &CompilerConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        let schema = read_schema(db, fs, &config.schema, config.current_working_directory)?;
+        let schema_extensions = read_schema_extensions(db, fs, config)?;
+        let iso_literals = read_iso_literals_from_project_root(db, fs, config)?;
         Ok(Self {
             sources: (schema, schema_extensions),
             iso_literals,
@@ -40,6 +45,7 @@ impl SourceFiles {
     pub fn read_updates(
         &mut self,
         db: &mut Database,
+        fs: &dyn FileSystem,
         config: &CompilerConfig,
         changes: &[SourceFileEvent],
     ) -> Result<(), Box<dyn Error>> {
@@ -51,16 +57,16 @@ impl SourceFiles {
                         "Unexpected config file change. This is indicative of a bug in Isograph."
                     );
                 }
-                ChangedFileKind::Schema => self.handle_update_schema(db, config, event).err(),
+                ChangedFileKind::Schema => self.handle_update_schema(db, fs, config, event).err(),
                 ChangedFileKind::SchemaExtension => self
-                    .handle_update_schema_extensions(db, config, event)
+                    .handle_update_schema_extensions(db, fs, config, event)
                     .err(),
                 ChangedFileKind::JavaScriptSourceFile => {
-                    self.handle_update_source_file(db, config, event).err()
-                }
-                ChangedFileKind::JavaScriptSourceFolder => {
-                    self.handle_update_source_folder(db, config, event).err()
+                    self.handle_update_source_file(db, fs, config, event).err()
                 }
+                ChangedFileKind::JavaScriptSourceFolder => self
+                    .handle_update_source_folder(db, fs, config, event)
+                    .err(),
             })
             .collect::<Vec<_>>();
         if !errors.is_empty() {
@@ -73,17 +79,22 @@ impl SourceFiles {
     fn handle_update_schema(
         &mut self,
         db: &mut Database,
+        fs: &dyn FileSystem,
         config: &CompilerConfig,
         event_kind: &SourceEventKind,
     ) -> Result<(), Box<dyn Error>> {
         match event_kind {
             SourceEventKind::CreateOrModify(_) => {
-                self.sources.0 = read_schema(db, &config.schema, config.current_working_directory)?;
+                self.sources.0 =
+                    read_schema(db, fs, &config.schema, config.current_working_directory)?;
             }
             SourceEventKind::Rename((_, target_path)) => {
                 if config.schema.absolute_path != *target_path {
                     return Err(Box::new(BatchCompileError::SchemaNotFound));
                 }
+                // e.g. an editor saved the schema by replacing it
+                self.sources.0 =
+                    read_schema(db, fs, &config.schema, config.current_working_directory)?;
             }
             SourceEventKind::Remove(_) => return Err(Box::new(BatchCompileError::SchemaNotFound)),
         }
@@ -93,12 +104,13 @@ impl SourceFiles {
     fn handle_update_schema_extensions(
         &mut self,
         db: &mut Database,
+        fs: &dyn FileSystem,
         config: &CompilerConfig,
         event_kind: &SourceEventKind,
     ) -> Result<(), Box<dyn Error>> {
         match event_kind {
             SourceEventKind::CreateOrModify(path) => {
-                self.create_or_update_schema_extension(db, path, config)?;
+                self.create_or_update_schema_extension(db, fs, path, config)?;
             }
             SourceEventKind::Rename((source_path, target_path)) => {
                 if config
@@ -106,13 +118,15 @@ impl SourceFiles {
                     .iter()
                     .any(|x| x.absolute_path == *target_path)
                 {
-                    self.create_or_update_schema_extension(db, target_path, config)?;
+                    self.create_or_update_schema_extension(db, fs, target_path, config)?;
                 } else {
                     let interned_file_path = relative_path_from_absolute_and_working_directory(
                         config.current_working_directory,
                         source_path,
                     );
-                    self.sources.1.remove(&interned_file_path);
+                    if let Some(source_id) = self.sources.1.remove(&interned_file_path) {
+                        db.remove(source_id);
+                    }
                 }
             }
             SourceEventKind::Remove(path) => {
@@ -120,7 +134,9 @@ impl SourceFiles {
                     config.current_working_directory,
                     path,
                 );
-                self.sources.1.remove(&interned_file_path);
+                if let Some(source_id) = self.sources.1.remove(&interned_file_path) {
+                    db.remove(source_id);
+                }
             }
         }
         Ok(())
@@ -129,12 +145,18 @@ impl SourceFiles {
     fn create_or_update_schema_extension(
         &mut self,
         db: &mut Database,
+        fs: &dyn FileSystem,
         path: &Path,
         config: &CompilerConfig,
     ) -> Result<(), Box<dyn Error>> {
         let absolute_and_relative =
             absolute_and_relative_paths(config.current_working_directory, path.to_path_buf());
-        let schema_id = read_schema(db, &absolute_and_relative, config.current_working_directory)?;
+        let schema_id = read_schema(
+            db,
+            fs,
+            &absolute_and_relative,
+            config.current_working_directory,
+        )?;
         self.sources
             .1
             .insert(absolute_and_relative.relative_path, schema_id);
@@ -144,20 +166,22 @@ impl SourceFiles {
     fn handle_update_source_file(
         &mut self,
         db: &mut Database,
+        fs: &dyn FileSystem,
         config: &CompilerConfig,
         event_kind: &SourceEventKind,
     ) -> Result<(), Box<dyn Error>> {
         match event_kind {
             SourceEventKind::CreateOrModify(path) => {
-                self.create_or_update_iso_literals(db, path, config)?;
+                self.create_or_update_iso_literals(db, fs, path, config)?;
             }
             SourceEventKind::Rename((source_path, target_path)) => {
                 let source_file_path = relative_path_from_absolute_and_working_directory(
                     config.current_working_directory,
                     source_path,
                 );
-                if self.iso_literals.remove(&source_file_path).is_some() {
-                    self.create_or_update_iso_literals(db, target_path, config)?
+                if let Some(source_id) = self.iso_literals.remove(&source_file_path) {
+                    db.remove(source_id);
+                    self.create_or_update_iso_literals(db, fs, target_path, config)?
                 }
             }
             SourceEventKind::Remove(path) => {
@@ -165,7 +189,9 @@ impl SourceFiles {
                     config.current_working_directory,
                     path,
                 );
-                self.iso_literals.remove(&interned_file_path);
+                if let Some(source_id) = self.iso_literals.remove(&interned_file_path) {
+                    db.remove(source_id);
+                }
             }
         }
         Ok(())
@@ -174,11 +200,12 @@ impl SourceFiles {
     fn create_or_update_iso_literals(
         &mut self,
         db: &mut Database,
+        fs: &dyn FileSystem,
         path: &Path,
         config: &CompilerConfig,
     ) -> Result<(), Box<dyn Error>> {
         let (relative_path, content) =
-            read_file(path.to_path_buf(), config.current_working_directory)?;
+            read_file(fs, path.to_path_buf(), config.current_working_directory)?;
         let source_id = db.set(IsoLiteralsSource {
             relative_path,
             content,
@@ -190,19 +217,24 @@ impl SourceFiles {
     fn handle_update_source_folder(
         &mut self,
         db: &mut Database,
+        fs: &dyn FileSystem,
         config: &CompilerConfig,
         event_kind: &SourceEventKind,
     ) -> Result<(), Box<dyn Error>> {
         match event_kind {
             SourceEventKind::CreateOrModify(folder) => {
-                read_iso_literals_from_folder(db, &mut self.iso_literals, folder, config)?;
+                read_iso_literals_from_folder(db, fs, &mut self.iso_literals, folder, config)?;
             }
             SourceEventKind::Rename((source_path, target_path)) => {
-                self.remove_iso_literals_from_folder(source_path, config.current_working_directory);
-                read_iso_literals_from_folder(db, &mut self.iso_literals, target_path, config)?;
+                self.remove_iso_literals_from_folder(
+                    db,
+                    source_path,
+                    config.current_working_directory,
+                );
+                read_iso_literals_from_folder(db, fs, &mut self.iso_literals, target_path, config)?;
             }
             SourceEventKind::Remove(path) => {
-                self.remove_iso_literals_from_folder(path, config.current_working_directory);
+                self.remove_iso_literals_from_folder(db, path, config.current_working_directory);
             }
         }
         Ok(())
@@ -210,6 +242,7 @@ impl SourceFiles {
 
     fn remove_iso_literals_from_folder(
         &mut self,
+        db: &mut Database,
         folder: &PathBuf,
         current_working_directory: CurrentWorkingDirectory,
     ) {
@@ -218,72 +251,80 @@ impl SourceFiles {
                 .expect("Expected path to be diffable")
                 .to_string_lossy()
                 .to_string();
-        self.iso_literals
-            .retain(|file_path, _| !file_path.to_string().starts_with(&relative_path));
+        self.iso_literals.retain(|file_path, source_id| {
+            let should_retain = !file_path.to_string().starts_with(&relative_path);
+            if !should_retain {
+                db.remove(*source_id);
+            }
+            should_retain
+        });
     }
 }
 
 pub fn read_schema(
     db: &mut Database,
+    fs: &dyn FileSystem,
     schema_path: &AbsolutePathAndRelativePath,
     current_working_directory: CurrentWorkingDirectory,
 ) -> Result<SourceId<SchemaSource>, Box<dyn Error>> {
-    let content = read_schema_file(&schema_path.absolute_path)?;
+    let content = read_schema_file(fs, &schema_path.absolute_path)?;
     let text_source = TextSource {
         relative_path_to_source_file: schema_path.relative_path,
         span: None,
         current_working_directory,
     };
-    let schema_id = db.set(SchemaSource {
-        relative_path: schema_path.relative_path,
-        content,
-        text_source,
-    });
+    // The schema changes much less often than the iso literals, so the nodes that
+    // only depend on the schema need not be verified when only iso literals change.
+    let schema_id = db.set_with_durability(
+        SchemaSource {
+            relative_path: schema_path.relative_path,
+            content,
+            text_source,
+        },
+        Durability::High,
+    );
     Ok(schema_id)
 }
 
-pub fn read_schema_file(path: &PathBuf) -> Result<String, BatchCompileError> {
+pub fn read_schema_file(fs: &dyn FileSystem, path: &PathBuf) -> Result<String, BatchCompileError> {
     let current_dir = std::env::current_dir().expect("current_dir should exist");
     let joined = current_dir.join(path);
     let canonicalized_existing_path =
-        joined
-            .canonicalize()
+        fs.canonicalize(&joined)
             .map_err(|e| BatchCompileError::UnableToLoadSchema {
                 path: joined,
                 message: e.to_string(),
             })?;
 
-    if !canonicalized_existing_path.is_file() {
+    if !fs.is_file(&canonicalized_existing_path) {
         return Err(BatchCompileError::SchemaNotAFile {
             path: canonicalized_existing_path,
         });
     }
 
-    let contents = std::fs::read(canonicalized_existing_path.clone()).map_err(|e| {
-        BatchCompileError::UnableToReadFile {
-            path: canonicalized_existing_path.clone(),
-            message: e.to_string(),
-        }
-    })?;
-
-    let contents = std::str::from_utf8(&contents)
-        .map_err(|e| BatchCompileError::UnableToConvertToString {
-            path: canonicalized_existing_path.clone(),
-            reason: e,
-        })?
-        .to_owned();
+    let contents =
+        fs.read(&canonicalized_existing_path)
+            .map_err(|e| BatchCompileError::UnableToReadFile {
+                path: canonicalized_existing_path.clone(),
+                message: e.to_string(),
+            })?;
 
-    Ok(contents)
+    Ok(decode_file_content(&canonicalized_existing_path, &contents))
 }
 
 pub fn read_schema_extensions(
     db: &mut Database,
+    fs: &dyn FileSystem,
     config: &CompilerConfig,
 ) -> Result<BTreeMap<RelativePathToSourceFile, SourceId<SchemaSource>>, Box<dyn Error>> {
     let mut schema_extensions = BTreeMap::new();
     for schema_extension_path in config.schema_extensions.iter() {
-        let schema_extension =
-            read_schema(db, schema_extension_path, config.current_working_directory)?;
+        let schema_extension = read_schema(
+            db,
+            fs,
+            schema_extension_path,
+            config.current_working_directory,
+        )?;
         schema_extensions.insert(schema_extension_path.relative_path, schema_extension);
     }
     Ok(schema_extensions)
@@ -291,21 +332,27 @@ pub fn read_schema_extensions(
 
 pub fn read_iso_literals_from_project_root(
     db: &mut Database,
+    fs: &dyn FileSystem,
     config: &CompilerConfig,
 ) -> Result<HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>, Box<dyn Error>> {
     let mut iso_literals = HashMap::new();
-    read_iso_literals_from_folder(db, &mut iso_literals, &config.project_root, config)?;
+    read_iso_literals_from_folder(db, fs, &mut iso_literals, &config.project_root, config)?;
     Ok(iso_literals)
 }
 
 pub fn read_iso_literals_from_folder(
     db: &mut Database,
+    fs: &dyn FileSystem,
     iso_literals: &mut HashMap<RelativePathToSourceFile, SourceId<IsoLiteralsSource>>,
     folder: &Path,
     config: &CompilerConfig,
 ) -> Result<(), Box<dyn Error>> {
-    for (relative_path, content) in read_files_in_folder(folder, config.current_working_directory)?
-    {
+    for (relative_path, content) in read_files_in_folder(
+        fs,
+        folder,
+        config.current_working_directory,
+        &config.source_file_filter,
+    )? {
         let source_id = db.set(IsoLiteralsSource {
             relative_path,
             content,
@@ -314,3 +361,116 @@ pub fn read_iso_literals_from_folder(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use graphql_network_protocol::GraphQLNetworkProtocol;
+    use intern::string_key::Intern;
+    use isograph_config::SourceFileFilter;
+
+    use super::*;
+    use crate::{
+        compiler_state::compile_sources, file_system::InMemoryFileSystem,
+        persist_artifacts::FileSystemArtifacts, watch::SourceEventKind,
+    };
+
+    fn client_field(name: &str, selections: &str) -> String {
+        format!(
+            "import {{ iso }} from '@iso';\n\
+            export const {name} = iso(`\n  field Query.{name} {{\n    {selections}\n  }}\n`)\
+            (({{ data }}) => data);\n"
+        )
+    }
+
+    #[test]
+    fn updates_only_rewrite_affected_artifacts_and_remove_stale_ones() {
+        let fs = InMemoryFileSystem::new();
+        fs.add_file(
+            "/project/schema.graphql",
+            "type Query { name: String! age: Int! }",
+        );
+        fs.add_file("/project/src/A.tsx", client_field("A", "name"));
+        fs.add_file("/project/src/B.tsx", client_field("B", "name"));
+
+        let current_working_directory = "/project".intern().into();
+        let project_root = PathBuf::from("/project/src");
+        let config = CompilerConfig {
+            config_location: PathBuf::from("/project/isograph.config.json"),
+            project_name: None,
+            project_root: project_root.clone(),
+            source_file_filter: SourceFileFilter::include_all(project_root.clone()),
+            artifact_directory: absolute_and_relative_paths(
+                current_working_directory,
+                project_root.join("__isograph"),
+            ),
+            schema: absolute_and_relative_paths(
+                current_working_directory,
+                PathBuf::from("/project/schema.graphql"),
+            ),
+            schema_extensions: vec![],
+            entrypoints: vec![],
+            options: Default::default(),
+            current_working_directory,
+            keep_tmp_on_failure: false,
+        };
+
+        let mut db = Database::new();
+        let mut persist_artifacts = FileSystemArtifacts::default();
+        let mut source_files = SourceFiles::read_all(&mut db, &fs, &config).unwrap();
+        let mut compile = |db: &Database, source_files: &SourceFiles| {
+            compile_sources::<GraphQLNetworkProtocol>(
+                db,
+                &fs,
+                source_files,
+                &config,
+                &mut persist_artifacts,
+            )
+            .unwrap()
+            .total_artifacts_written
+        };
+        compile(&db, &source_files);
+
+        // If B's artifacts were rewritten, this would be overwritten.
+        let b_param_type = Path::new("/project/src/__isograph/Query/B/param_type.ts");
+        fs.write(b_param_type, b"not rewritten").unwrap();
+
+        // Editing A only rewrites A's param type.
+        fs.write(
+            Path::new("/project/src/A.tsx"),
+            client_field("A", "age").as_bytes(),
+        )
+        .unwrap();
+        source_files
+            .read_updates(
+                &mut db,
+                &fs,
+                &config,
+                &[(
+                    SourceEventKind::CreateOrModify(PathBuf::from("/project/src/A.tsx")),
+                    ChangedFileKind::JavaScriptSourceFile,
+                )],
+            )
+            .unwrap();
+        assert_eq!(compile(&db, &source_files), 1);
+        assert_eq!(fs.read_to_string(b_param_type).unwrap(), "not rewritten");
+
+        // Removing B removes its source from the database, deletes its artifacts,
+        // and rewrites iso.ts, which no longer contains B's iso literal.
+        fs.remove_file(Path::new("/project/src/B.tsx")).unwrap();
+        source_files
+            .read_updates(
+                &mut db,
+                &fs,
+                &config,
+                &[(
+                    SourceEventKind::Remove(PathBuf::from("/project/src/B.tsx")),
+                    ChangedFileKind::JavaScriptSourceFile,
+                )],
+            )
+            .unwrap();
+        assert_eq!(source_files.iso_literals.len(), 1);
+        assert_eq!(compile(&db, &source_files), 1);
+        assert!(!fs.exists(Path::new("/project/src/__isograph/Query/B")));
+        assert!(fs.exists(Path::new("/project/src/__isograph/Query/A/param_type.ts")));
+    }
+}
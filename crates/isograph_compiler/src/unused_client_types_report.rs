@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use isograph_schema::{
+    unused_client_types, ClientScalarOrObjectSelectable, NetworkProtocol, Schema,
+};
+use serde_json::json;
+
+use crate::{file_system::FileSystem, write_artifacts::GenerateArtifactsError};
+
+/// Write a JSON report of the client fields and client pointers that are not
+/// reachable from any entrypoint, sorted by type and field name.
+pub(crate) fn write_unused_client_types_report<TNetworkProtocol: NetworkProtocol>(
+    fs: &dyn FileSystem,
+    schema: &Schema<TNetworkProtocol>,
+    report_path: &Path,
+) -> Result<(), GenerateArtifactsError> {
+    let mut unused_client_types = unused_client_types(schema)
+        .into_iter()
+        .map(|client_type_id| {
+            let client_type = schema.client_type(client_type_id);
+            let type_and_field = client_type.type_and_field();
+            (
+                (
+                    type_and_field.type_name.to_string(),
+                    type_and_field.field_name.to_string(),
+                ),
+                json!({
+                    "type": type_and_field.type_name.to_string(),
+                    "field": type_and_field.field_name.to_string(),
+                    "kind": client_type.client_type(),
+                    "file": client_type.text_source().map(|text_source| {
+                        text_source.relative_path_to_source_file.to_string()
+                    }),
+                }),
+            )
+        })
+        .collect::<Vec<_>>();
+    unused_client_types.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let report = json!({
+        "unused_client_fields": unused_client_types
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect::<Vec<_>>(),
+    });
+
+    fs.write(
+        report_path,
+        format!(
+            "{}\n",
+            serde_json::to_string_pretty(&report).expect("Expected report to be serializable")
+        )
+        .as_bytes(),
+    )
+    .map_err(|e| GenerateArtifactsError::UnableToWriteReport {
+        path: report_path.to_path_buf(),
+        message: e.to_string(),
+    })
+}
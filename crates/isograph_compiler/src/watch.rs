@@ -1,6 +1,6 @@
 use colored::Colorize;
 use common_lang_types::CurrentWorkingDirectory;
-use isograph_config::CompilerConfig;
+use isograph_config::{create_config, create_configs, CompilerConfig};
 use isograph_schema::NetworkProtocol;
 use notify::{
     event::{CreateKind, ModifyKind, RemoveKind, RenameMode},
@@ -9,113 +9,256 @@ use notify::{
 use notify_debouncer_full::{
     new_debouncer, DebounceEventResult, DebouncedEvent, Debouncer, RecommendedCache,
 };
-use std::{path::PathBuf, time::Duration};
-use tokio::{runtime::Handle, sync::mpsc::Receiver};
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::PathBuf,
+    sync::atomic::Ordering,
+    time::Duration,
+};
+use tokio::{runtime::Handle, sync::mpsc::Receiver, task::LocalSet};
 use tracing::info;
 
 use crate::{
     batch_compile::print_result,
-    compiler_state::{compile, CompilerState, StandardSources},
+    compilation_stats::CompilationStats,
+    compiler_state::{compile_sources_unless_cancelled, CompilerState, StandardSources},
+    diagnostics::OutputFormat,
+    file_system::OsFileSystem,
     source_files::SourceFiles,
     with_duration::WithDuration,
+    write_artifacts::{journal_path, replaced_artifact_directory, temporary_artifact_directory},
 };
 
-const MAX_CHANGED_FILES: usize = 100;
+pub(crate) const MAX_CHANGED_FILES: usize = 100;
+
+/// How long notify waits before reporting an event, which allows it to combine
+/// the events that make up a rename. Waiting for a burst of changes to end is
+/// controlled by the watch_debounce_ms option instead.
+const NOTIFY_DEBOUNCE: Duration = Duration::from_millis(50);
 
-pub async fn handle_watch_command<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+pub async fn handle_watch_command<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources> + 'static,
+>(
     config_location: PathBuf,
     current_working_directory: CurrentWorkingDirectory,
+    output_format: OutputFormat,
+    print_stats: bool,
+    keep_tmp_on_failure: bool,
 ) -> Result<(), Vec<Error>> {
-    let mut state = CompilerState::new(config_location, current_working_directory);
-    let (mut rx, mut watcher) = create_debounced_file_watcher(&state.config);
+    let mut configs = create_configs(config_location, current_working_directory);
+    for config in configs.iter_mut() {
+        config.keep_tmp_on_failure = keep_tmp_on_failure;
+    }
+    if configs.len() == 1 {
+        let config = configs.pop().expect("Expected config to exist");
+        return watch_project::<TNetworkProtocol>(
+            config,
+            current_working_directory,
+            output_format,
+            print_stats,
+        )
+        .await;
+    }
 
-    info!("{}", "Starting to compile.".cyan());
-    let _ = print_result(WithDuration::new(|| {
-        let source_files = SourceFiles::read_all(&mut state.db, &state.config)?;
-        let result = compile::<TNetworkProtocol>(&state.db, &source_files, &state.config);
-        state.source_files = Some(source_files);
-        result
-    }));
+    // Each project is watched independently. The compiler state is not Send,
+    // so the projects are watched concurrently on the current thread, and are
+    // compiled one after another (unlike in batch mode, which compiles them in
+    // parallel). A project's compilation errors are printed by watch_project, so
+    // only errors from the file watchers are returned.
+    let local_set = LocalSet::new();
+    local_set
+        .run_until(async move {
+            let handles = configs
+                .into_iter()
+                .map(|config| {
+                    tokio::task::spawn_local(watch_project::<TNetworkProtocol>(
+                        config,
+                        current_working_directory,
+                        output_format,
+                        print_stats,
+                    ))
+                })
+                .collect::<Vec<_>>();
 
-    while let Some(res) = rx.recv().await {
-        match res {
-            Ok(events) => {
-                if let Some(changes) = categorize_and_filter_events(&events, &state.config) {
-                    let result = if has_config_changes(&changes) {
-                        info!(
-                            "{}",
-                            "Config change detected. Starting a full compilation.".cyan()
-                        );
-                        state = CompilerState::new(
-                            state.config.config_location,
-                            current_working_directory,
-                        );
-                        watcher.stop();
-                        (rx, watcher) = create_debounced_file_watcher(&state.config);
-                        WithDuration::new(|| {
-                            let source_files = SourceFiles::read_all(&mut state.db, &state.config)?;
-                            let result = compile::<TNetworkProtocol>(
-                                &state.db,
-                                &source_files,
-                                &state.config,
-                            );
-                            state.source_files = Some(source_files);
-                            result
-                        })
-                    } else if changes.len() < MAX_CHANGED_FILES {
-                        info!("{}", "File changes detected. Starting to compile.".cyan());
-                        WithDuration::new(|| {
-                            if let Some(source_files) = state.source_files.as_mut() {
-                                source_files.read_updates(
-                                    &mut state.db,
-                                    &state.config,
-                                    &changes,
-                                )?;
-                                compile::<TNetworkProtocol>(&state.db, source_files, &state.config)
-                            } else {
-                                let source_files =
-                                    SourceFiles::read_all(&mut state.db, &state.config)?;
-                                let result = compile::<TNetworkProtocol>(
-                                    &state.db,
-                                    &source_files,
-                                    &state.config,
-                                );
-                                state.source_files = Some(source_files);
-                                result
-                            }
-                        })
-                    } else {
-                        info!(
-                            "{}",
-                            "Too many changes. Starting a full compilation.".cyan()
-                        );
-                        WithDuration::new(|| {
-                            let source_files = SourceFiles::read_all(&mut state.db, &state.config)?;
-                            let result = compile::<TNetworkProtocol>(
-                                &state.db,
-                                &source_files,
-                                &state.config,
-                            );
-                            state.source_files = Some(source_files);
-                            result
-                        })
-                    };
-                    let _ = print_result(result);
-                    state.run_garbage_collection();
+            let mut errors = vec![];
+            for handle in handles {
+                if let Ok(Err(project_errors)) = handle.await {
+                    errors.extend(project_errors);
                 }
             }
-            Err(errors) => return Err(errors),
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        })
+        .await
+}
+
+async fn watch_project<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    config: CompilerConfig,
+    current_working_directory: CurrentWorkingDirectory,
+    output_format: OutputFormat,
+    print_stats: bool,
+) -> Result<(), Vec<Error>> {
+    let mut state = CompilerState::new(config);
+    let (mut rx, mut watcher) = create_debounced_file_watcher(&state);
+
+    info!("{}", "Starting to compile.".cyan());
+    let _ = print_result(
+        WithDuration::new(|| compile_from_scratch::<TNetworkProtocol>(&mut state)),
+        state.config.project_name.as_deref(),
+        output_format,
+        print_stats,
+    );
+
+    while let Some(res) = rx.recv().await {
+        let mut events = res?;
+        // Wait until no files have changed for watch_debounce, so that a burst of
+        // changes (including the changes made while the last compilation was
+        // running) is compiled once.
+        while let Ok(Some(res)) =
+            tokio::time::timeout(state.config.options.watch_debounce, rx.recv()).await
+        {
+            events.extend(res?);
+        }
+        state.cancelled.store(false, Ordering::Relaxed);
+
+        if let Some(changes) = categorize_and_filter_events(&events, &state.config) {
+            let changes = coalesce_changes(changes);
+            let result = if has_config_changes(&changes) {
+                info!(
+                    "{}",
+                    "Config change detected. Starting a full compilation.".cyan()
+                );
+                state = CompilerState::new(CompilerConfig {
+                    keep_tmp_on_failure: state.config.keep_tmp_on_failure,
+                    ..create_config(
+                        state.config.config_location,
+                        current_working_directory,
+                        state.config.project_name.as_deref(),
+                    )
+                });
+                watcher.stop();
+                (rx, watcher) = create_debounced_file_watcher(&state);
+                WithDuration::new(|| compile_from_scratch::<TNetworkProtocol>(&mut state))
+            } else if changes.len() < MAX_CHANGED_FILES {
+                let message = if has_schema_changes(&changes) {
+                    // Everything that depends on the schema is re-validated.
+                    "Schema change detected. Starting to compile."
+                } else {
+                    "File changes detected. Starting to compile."
+                };
+                info!("{}", message.cyan());
+                WithDuration::new(|| {
+                    compile_incrementally::<TNetworkProtocol>(&mut state, &changes)
+                })
+            } else {
+                info!(
+                    "{}",
+                    "Too many changes. Starting a full compilation.".cyan()
+                );
+                WithDuration::new(|| compile_from_scratch::<TNetworkProtocol>(&mut state))
+            };
+            let _ = print_result(
+                result,
+                state.config.project_name.as_deref(),
+                output_format,
+                print_stats,
+            );
+            state.run_garbage_collection();
         }
     }
     Ok(())
 }
 
-fn has_config_changes(changes: &[SourceFileEvent]) -> bool {
+/// Re-read every source file and compile.
+///
+/// Sources whose content did not change keep their pico revision, so memoized
+/// work (e.g. parsing the iso literals in a file) is reused, and only artifacts
+/// whose content changed are written.
+pub(crate) fn compile_from_scratch<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut CompilerState,
+) -> Result<CompilationStats, Box<dyn std::error::Error>> {
+    let source_files = SourceFiles::read_all(&mut state.db, &OsFileSystem, &state.config)?;
+    let result = compile_sources_unless_cancelled::<TNetworkProtocol>(
+        &state.db,
+        &OsFileSystem,
+        &source_files,
+        &state.config,
+        state.persist_artifacts.as_mut(),
+        &state.cancelled,
+    );
+    state.source_files = Some(source_files);
+    result
+}
+
+/// Update only the sources affected by `changes`, then compile.
+///
+/// Each changed file is set (or removed) individually in the pico database, so
+/// only the memoized functions depending on that file are re-executed.
+pub(crate) fn compile_incrementally<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources>,
+>(
+    state: &mut CompilerState,
+    changes: &[SourceFileEvent],
+) -> Result<CompilationStats, Box<dyn std::error::Error>> {
+    match state.source_files.as_mut() {
+        Some(source_files) => {
+            source_files.read_updates(&mut state.db, &OsFileSystem, &state.config, changes)?;
+            compile_sources_unless_cancelled::<TNetworkProtocol>(
+                &state.db,
+                &OsFileSystem,
+                source_files,
+                &state.config,
+                state.persist_artifacts.as_mut(),
+                &state.cancelled,
+            )
+        }
+        None => compile_from_scratch::<TNetworkProtocol>(state),
+    }
+}
+
+pub(crate) fn has_config_changes(changes: &[SourceFileEvent]) -> bool {
     changes
         .iter()
         .any(|(_, changed_file_kind)| matches!(changed_file_kind, ChangedFileKind::Config))
 }
 
+/// Keep only the last event for each path, e.g. when a formatter writes a file
+/// several times, or a file is created and then removed. Renames are kept, since
+/// they affect two paths.
+pub(crate) fn coalesce_changes(changes: Vec<SourceFileEvent>) -> Vec<SourceFileEvent> {
+    let mut seen_paths = HashSet::new();
+    let mut coalesced_changes = vec![];
+    for (source_event_kind, changed_file_kind) in changes.into_iter().rev() {
+        match &source_event_kind {
+            SourceEventKind::CreateOrModify(path) | SourceEventKind::Remove(path) => {
+                if !seen_paths.insert(path.clone()) {
+                    continue;
+                }
+            }
+            SourceEventKind::Rename((from, to)) => {
+                // Earlier events for either path must still be applied before the rename.
+                seen_paths.remove(from);
+                seen_paths.remove(to);
+            }
+        }
+        coalesced_changes.push((source_event_kind, changed_file_kind));
+    }
+    coalesced_changes.reverse();
+    coalesced_changes
+}
+
+fn has_schema_changes(changes: &[SourceFileEvent]) -> bool {
+    changes.iter().any(|(_, changed_file_kind)| {
+        matches!(
+            changed_file_kind,
+            ChangedFileKind::Schema | ChangedFileKind::SchemaExtension
+        )
+    })
+}
+
 fn categorize_and_filter_events(
     events: &[DebouncedEvent],
     config: &CompilerConfig,
@@ -240,15 +383,29 @@ fn process_remove_event(
     }
 }
 
-fn categorize_changed_file_and_filter_changes_in_artifact_directory(
+pub(crate) fn categorize_changed_file_and_filter_changes_in_artifact_directory(
     config: &CompilerConfig,
     path: &PathBuf,
 ) -> Option<ChangedFileKind> {
-    if !path.starts_with(&config.artifact_directory.absolute_path) {
+    let artifact_directory = &config.artifact_directory.absolute_path;
+    // Artifacts are first written to a temporary directory, which then replaces
+    // the artifact directory, or whose files are moved into it as listed in the
+    // journal.
+    let is_in_artifact_directory = [
+        artifact_directory.clone(),
+        temporary_artifact_directory(artifact_directory),
+        replaced_artifact_directory(artifact_directory),
+        journal_path(artifact_directory),
+    ]
+    .iter()
+    .any(|directory| path.starts_with(directory));
+    if !is_in_artifact_directory {
         if path.starts_with(&config.project_root) {
             if path.is_file() {
-                return Some(ChangedFileKind::JavaScriptSourceFile);
-            } else {
+                if config.source_file_filter.includes_file(path) {
+                    return Some(ChangedFileKind::JavaScriptSourceFile);
+                }
+            } else if !config.source_file_filter.excludes_folder(path) {
                 return Some(ChangedFileKind::JavaScriptSourceFolder);
             }
         } else if path == &config.schema.absolute_path {
@@ -266,30 +423,37 @@ fn categorize_changed_file_and_filter_changes_in_artifact_directory(
     None
 }
 
+/// Watch the files of the project. When a batch of relevant changes arrives, the
+/// compilation in progress (if any) is cancelled, since its result is out of date.
 #[allow(clippy::complexity)]
 fn create_debounced_file_watcher(
-    config: &CompilerConfig,
+    state: &CompilerState,
 ) -> (
     Receiver<Result<Vec<DebouncedEvent>, Vec<Error>>>,
     Debouncer<RecommendedWatcher, RecommendedCache>,
 ) {
     let (tx, rx) = tokio::sync::mpsc::channel(1);
     let rt = Handle::current();
+    let config = &state.config;
+    let watched_config = config.clone();
+    let cancelled = state.cancelled.clone();
 
-    let mut watcher = new_debouncer(
-        // TODO control this with config
-        Duration::from_millis(500),
-        None,
-        move |result: DebounceEventResult| {
-            let tx = tx.clone();
+    let mut watcher = new_debouncer(NOTIFY_DEBOUNCE, None, move |result: DebounceEventResult| {
+        // This runs on the debouncer's thread, so it can cancel a compilation
+        // that is blocking the thread that receives the events.
+        if let Ok(events) = &result {
+            if categorize_and_filter_events(events, &watched_config).is_some() {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+        }
+        let tx = tx.clone();
 
-            rt.spawn(async move {
-                if let Err(e) = tx.send(result).await {
-                    println!("Error sending event result: {:?}", e);
-                }
-            });
-        },
-    )
+        rt.spawn(async move {
+            if let Err(e) = tx.send(result).await {
+                println!("Error sending event result: {:?}", e);
+            }
+        });
+    })
     .expect("Expected to be able to create debouncer");
 
     watcher
@@ -298,25 +462,30 @@ fn create_debounced_file_watcher(
     watcher
         .watch(&config.project_root, RecursiveMode::Recursive)
         .expect("Failure when watching project root");
-    watcher
-        .watch(&config.schema.absolute_path, RecursiveMode::NonRecursive)
-        .expect("Failing when watching schema");
-    for extension in &config.schema_extensions {
+    // Editors often save a file by replacing it, which would stop a watch on the
+    // file itself, so we watch the folders containing the schema and extensions.
+    let schema_folders = std::iter::once(&config.schema)
+        .chain(&config.schema_extensions)
+        .filter_map(|schema| schema.absolute_path.parent())
+        .filter(|folder| !folder.starts_with(&config.project_root))
+        .collect::<BTreeSet<_>>();
+    for folder in schema_folders {
         watcher
-            .watch(&extension.absolute_path, RecursiveMode::NonRecursive)
-            .expect("Failing when watching schema extension");
+            .watch(folder, RecursiveMode::NonRecursive)
+            .expect("Failing when watching schema");
     }
 
     (rx, watcher)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SourceEventKind {
     CreateOrModify(PathBuf),
     Rename((PathBuf, PathBuf)),
     Remove(PathBuf),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChangedFileKind {
     Config,
     Schema,
@@ -326,3 +495,65 @@ pub enum ChangedFileKind {
 }
 
 pub type SourceFileEvent = (SourceEventKind, ChangedFileKind);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modify(path: &str) -> SourceFileEvent {
+        (
+            SourceEventKind::CreateOrModify(PathBuf::from(path)),
+            ChangedFileKind::JavaScriptSourceFile,
+        )
+    }
+
+    fn remove(path: &str) -> SourceFileEvent {
+        (
+            SourceEventKind::Remove(PathBuf::from(path)),
+            ChangedFileKind::JavaScriptSourceFile,
+        )
+    }
+
+    fn rename(from: &str, to: &str) -> SourceFileEvent {
+        (
+            SourceEventKind::Rename((PathBuf::from(from), PathBuf::from(to))),
+            ChangedFileKind::JavaScriptSourceFile,
+        )
+    }
+
+    #[test]
+    fn coalesces_events_for_the_same_path() {
+        assert_eq!(
+            coalesce_changes(vec![
+                modify("/src/A.tsx"),
+                modify("/src/B.tsx"),
+                modify("/src/A.tsx"),
+                modify("/src/C.tsx"),
+                remove("/src/C.tsx"),
+            ]),
+            vec![
+                modify("/src/B.tsx"),
+                modify("/src/A.tsx"),
+                remove("/src/C.tsx"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_events_before_renames() {
+        assert_eq!(
+            coalesce_changes(vec![
+                modify("/src/A.tsx"),
+                modify("/src/A.tsx"),
+                rename("/src/A.tsx", "/src/B.tsx"),
+                modify("/src/B.tsx"),
+                modify("/src/B.tsx"),
+            ]),
+            vec![
+                modify("/src/A.tsx"),
+                rename("/src/A.tsx", "/src/B.tsx"),
+                modify("/src/B.tsx"),
+            ]
+        );
+    }
+}
@@ -1,65 +1,425 @@
 use std::{
-    fs::{self, File},
-    io::Write,
-    path::PathBuf,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
 };
 
 use common_lang_types::ArtifactPathAndContent;
-use intern::string_key::Lookup;
+use isograph_config::ArtifactLayout;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::file_system::FileSystem;
+
+/// The artifacts that were written to disk during the previous compilation,
+/// keyed by absolute path, along with a hash of their content.
+///
+/// In watch mode, this allows us to only touch the artifacts that changed,
+/// instead of deleting and recreating the entire artifact directory.
+#[derive(Debug, Default)]
+pub struct WrittenArtifacts {
+    content_hashes: HashMap<PathBuf, u64>,
+}
+
+/// Write artifacts to disk, returning the number of files that were written.
+///
+/// If `written_artifacts` is `None` (or the artifact directory no longer exists),
+/// every artifact is written to a temporary directory next to the artifact
+/// directory, which then replaces the artifact directory. So, if the compiler
+/// is killed or fails partway through, the previous artifacts are left intact,
+/// instead of a half-written artifact directory. If `keep_tmp_on_failure` is
+/// set, the temporary directory is not deleted on failure, so that it can be
+/// inspected.
+///
+/// Otherwise, only artifacts whose content changed since the previous
+/// compilation are written, and artifacts that are no longer generated are
+/// deleted. The changed artifacts are first written to the temporary directory.
+/// If that fails, the artifact directory is left as it was. Then, a journal of
+/// the files to move into the artifact directory and the files to delete is
+/// written, and carried out. Each file is moved into place with a rename, so no
+/// artifact is ever half-written. But if the compiler is killed while the
+/// journal is carried out, the artifact directory contains a mix of previous
+/// and new artifacts until the next compilation, which first finishes carrying
+/// out the journal.
+///
+/// If `keep_stale_artifacts` is set (i.e. because some iso literals were skipped),
+/// artifacts that are no longer generated are left on disk, since they may have
+/// been generated from the skipped iso literals.
 pub(crate) fn write_artifacts_to_disk(
+    fs: &dyn FileSystem,
     paths_and_contents: impl IntoIterator<Item = ArtifactPathAndContent>,
-    artifact_directory: &PathBuf,
+    artifact_directory: &Path,
+    artifact_layout: ArtifactLayout,
+    written_artifacts: &mut Option<WrittenArtifacts>,
+    keep_tmp_on_failure: bool,
+    keep_stale_artifacts: bool,
 ) -> Result<usize, GenerateArtifactsError> {
-    if artifact_directory.exists() {
-        fs::remove_dir_all(artifact_directory).map_err(|e| {
+    // If we fail partway through, we no longer know what is on disk, so the next
+    // compilation must start from scratch. The same is true if the artifact
+    // directory was deleted, e.g. while watching.
+    let previously_written_artifacts = written_artifacts
+        .take()
+        .filter(|_| fs.exists(artifact_directory));
+
+    // Left behind by a compilation that was killed or failed while carrying out
+    // its journal.
+    if fs.exists(&journal_path(artifact_directory)) {
+        carry_out_journal(fs, artifact_directory)?;
+    }
+
+    let (content_hashes, count) = match previously_written_artifacts {
+        Some(previously_written_artifacts) => write_changed_artifacts(
+            fs,
+            paths_and_contents,
+            artifact_directory,
+            artifact_layout,
+            previously_written_artifacts.content_hashes,
+            keep_tmp_on_failure,
+            keep_stale_artifacts,
+        )?,
+        None if keep_stale_artifacts => {
+            // Replacing the artifact directory would delete the artifacts of the skipped
+            // iso literals, so we write every artifact in place. We do not know which of
+            // the remaining files are stale, so the next compilation starts from scratch.
+            let (_, count) = write_changed_artifacts(
+                fs,
+                paths_and_contents,
+                artifact_directory,
+                artifact_layout,
+                HashMap::new(),
+                keep_tmp_on_failure,
+                keep_stale_artifacts,
+            )?;
+            return Ok(count);
+        }
+        None => {
+            let temporary_directory = temporary_artifact_directory(artifact_directory);
+            let result = write_all_artifacts(
+                fs,
+                paths_and_contents,
+                artifact_directory,
+                &temporary_directory,
+                artifact_layout,
+            );
+            if result.is_err() && !keep_tmp_on_failure {
+                let _ = fs.remove_dir_all(&temporary_directory);
+            }
+            result?
+        }
+    };
+
+    *written_artifacts = Some(WrittenArtifacts { content_hashes });
+    Ok(count)
+}
+
+/// The directory to which artifacts are written before it replaces the artifact
+/// directory, e.g. `__isograph.tmp`.
+pub(crate) fn temporary_artifact_directory(artifact_directory: &Path) -> PathBuf {
+    sibling_directory(artifact_directory, "tmp")
+}
+
+/// The directory to which the previous artifact directory is moved while it is
+/// replaced, e.g. `__isograph.old`.
+pub(crate) fn replaced_artifact_directory(artifact_directory: &Path) -> PathBuf {
+    sibling_directory(artifact_directory, "old")
+}
+
+/// The journal of the changes to the artifact directory that are being made,
+/// e.g. `__isograph.journal`.
+pub(crate) fn journal_path(artifact_directory: &Path) -> PathBuf {
+    sibling_directory(artifact_directory, "journal")
+}
+
+fn sibling_directory(artifact_directory: &Path, extension: &str) -> PathBuf {
+    let mut file_name = artifact_directory
+        .file_name()
+        .expect("Expected artifact directory to have a name")
+        .to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    artifact_directory.with_file_name(file_name)
+}
+
+fn write_all_artifacts(
+    fs: &dyn FileSystem,
+    paths_and_contents: impl IntoIterator<Item = ArtifactPathAndContent>,
+    artifact_directory: &Path,
+    temporary_directory: &Path,
+    artifact_layout: ArtifactLayout,
+) -> Result<(HashMap<PathBuf, u64>, usize), GenerateArtifactsError> {
+    // Left behind by a compilation that was killed, or that failed with
+    // --keep-tmp-on-failure.
+    for directory in [
+        temporary_directory,
+        &replaced_artifact_directory(artifact_directory),
+    ] {
+        remove_dir_all_if_exists(fs, directory)?;
+    }
+    create_dir_all(fs, temporary_directory)?;
+
+    let mut content_hashes = HashMap::new();
+    for path_and_content in paths_and_contents {
+        let relative_file_path = artifact_layout
+            .generate_path(path_and_content.type_and_field, path_and_content.file_name);
+        write_artifact(
+            fs,
+            &temporary_directory.join(&relative_file_path),
+            &path_and_content.file_content,
+        )?;
+        content_hashes.insert(
+            artifact_directory.join(relative_file_path),
+            hash_content(&path_and_content.file_content),
+        );
+    }
+
+    replace_directory(fs, temporary_directory, artifact_directory)?;
+
+    let count = content_hashes.len();
+    Ok((content_hashes, count))
+}
+
+/// Replace the artifact directory with the temporary directory. The previous
+/// artifact directory is moved out of the way first, since a directory cannot
+/// be renamed onto a non-empty directory.
+fn replace_directory(
+    fs: &dyn FileSystem,
+    temporary_directory: &Path,
+    artifact_directory: &Path,
+) -> Result<(), GenerateArtifactsError> {
+    let replaced_directory = replaced_artifact_directory(artifact_directory);
+    let unable_to_replace = |e: std::io::Error| GenerateArtifactsError::UnableToReplaceDirectory {
+        path: artifact_directory.to_path_buf(),
+        message: e.to_string(),
+    };
+
+    let had_artifact_directory = fs.exists(artifact_directory);
+    if had_artifact_directory {
+        fs.rename(artifact_directory, &replaced_directory)
+            .map_err(unable_to_replace)?;
+    }
+    fs.rename(temporary_directory, artifact_directory)
+        .map_err(unable_to_replace)?;
+    if had_artifact_directory {
+        fs.remove_dir_all(&replaced_directory).map_err(|e| {
             GenerateArtifactsError::UnableToDeleteDirectory {
-                path: artifact_directory.clone(),
+                path: replaced_directory.clone(),
                 message: e.to_string(),
             }
         })?;
     }
-    fs::create_dir_all(artifact_directory).map_err(|e| {
-        GenerateArtifactsError::UnableToCreateDirectory {
-            path: artifact_directory.clone(),
-            message: e.to_string(),
-        }
-    })?;
+    Ok(())
+}
+
+fn write_changed_artifacts(
+    fs: &dyn FileSystem,
+    paths_and_contents: impl IntoIterator<Item = ArtifactPathAndContent>,
+    artifact_directory: &Path,
+    artifact_layout: ArtifactLayout,
+    mut previous_content_hashes: HashMap<PathBuf, u64>,
+    keep_tmp_on_failure: bool,
+    keep_stale_artifacts: bool,
+) -> Result<(HashMap<PathBuf, u64>, usize), GenerateArtifactsError> {
+    let temporary_directory = temporary_artifact_directory(artifact_directory);
+    remove_dir_all_if_exists(fs, &temporary_directory)?;
 
-    let mut count = 0;
+    let mut content_hashes = HashMap::new();
+    let mut journal = Journal::default();
     for path_and_content in paths_and_contents {
-        // Is this better than materializing paths_and_contents sooner?
-        count += 1;
-
-        let absolute_directory = match path_and_content.type_and_field {
-            Some(type_and_field) => artifact_directory
-                .join(type_and_field.type_name.lookup())
-                .join(type_and_field.field_name.lookup()),
-            None => artifact_directory.clone(),
-        };
-        fs::create_dir_all(&absolute_directory).map_err(|e| {
-            GenerateArtifactsError::UnableToCreateDirectory {
-                path: absolute_directory.clone(),
-                message: e.to_string(),
+        let relative_file_path = artifact_layout
+            .generate_path(path_and_content.type_and_field, path_and_content.file_name);
+        let absolute_file_path = artifact_directory.join(&relative_file_path);
+        let content_hash = hash_content(&path_and_content.file_content);
+
+        let unchanged = previous_content_hashes.remove(&absolute_file_path) == Some(content_hash);
+        content_hashes.insert(absolute_file_path, content_hash);
+        if unchanged {
+            continue;
+        }
+
+        if let Err(e) = write_artifact(
+            fs,
+            &temporary_directory.join(&relative_file_path),
+            &path_and_content.file_content,
+        ) {
+            if !keep_tmp_on_failure {
+                let _ = fs.remove_dir_all(&temporary_directory);
             }
+            return Err(e);
+        }
+        journal.written.push(relative_file_path);
+    }
+
+    // Whatever remains was written previously, but is no longer generated. If we
+    // keep these artifacts, we keep track of them, so that they are deleted once
+    // they are no longer generated by a compilation that skipped nothing.
+    if keep_stale_artifacts {
+        content_hashes.extend(previous_content_hashes);
+    } else {
+        journal.deleted.extend(
+            previous_content_hashes
+                .into_keys()
+                .filter_map(|path| Some(path.strip_prefix(artifact_directory).ok()?.to_path_buf())),
+        );
+    }
+
+    let count = journal.written.len();
+    if journal.written.is_empty() && journal.deleted.is_empty() {
+        return Ok((content_hashes, count));
+    }
+
+    // The journal is written next to the artifact directory, which may not exist
+    // yet if nothing was written before.
+    create_dir_all(fs, artifact_directory)?;
+    let journal_path = journal_path(artifact_directory);
+    let journal_content =
+        serde_json::to_string_pretty(&journal).expect("Expected journal to be serializable");
+    fs.write(&journal_path, journal_content.as_bytes())
+        .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
+            path: journal_path,
+            message: e.to_string(),
         })?;
+    carry_out_journal(fs, artifact_directory)?;
+
+    Ok((content_hashes, count))
+}
 
-        let absolute_file_path = absolute_directory.join(path_and_content.file_name.lookup());
-        let mut file = File::create(&absolute_file_path).map_err(|e| {
-            GenerateArtifactsError::UnableToWriteToArtifactFile {
-                path: absolute_file_path.clone(),
+/// The changes to the artifact directory that an incremental write makes. The
+/// paths are relative to the artifact directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Journal {
+    /// Files that were written to the temporary directory, and are moved to the
+    /// artifact directory.
+    written: Vec<PathBuf>,
+    /// Files that are no longer generated, and are deleted.
+    deleted: Vec<PathBuf>,
+}
+
+/// Move the written files into the artifact directory, delete the deleted files,
+/// and then delete the journal. Carrying out a journal more than once has no
+/// further effect, so this can also finish a journal that was interrupted.
+fn carry_out_journal(
+    fs: &dyn FileSystem,
+    artifact_directory: &Path,
+) -> Result<(), GenerateArtifactsError> {
+    let journal_path = journal_path(artifact_directory);
+    let journal: Journal = fs
+        .read_to_string(&journal_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let temporary_directory = temporary_artifact_directory(artifact_directory);
+
+    for relative_file_path in &journal.written {
+        let temporary_file_path = temporary_directory.join(relative_file_path);
+        // If it was already moved, there is nothing to do.
+        if !fs.exists(&temporary_file_path) {
+            continue;
+        }
+        let absolute_file_path = artifact_directory.join(relative_file_path);
+        create_dir_all(
+            fs,
+            absolute_file_path
+                .parent()
+                .expect("Expected artifact path to have a parent"),
+        )?;
+        fs.rename(&temporary_file_path, &absolute_file_path)
+            .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
+                path: absolute_file_path,
+                message: e.to_string(),
+            })?;
+    }
+
+    for relative_file_path in &journal.deleted {
+        remove_stale_artifact(
+            fs,
+            &artifact_directory.join(relative_file_path),
+            artifact_directory,
+        )?;
+    }
+
+    remove_dir_all_if_exists(fs, &temporary_directory)?;
+    fs.remove_file(&journal_path)
+        .map_err(|e| GenerateArtifactsError::UnableToDeleteFile {
+            path: journal_path,
+            message: e.to_string(),
+        })
+}
+
+fn remove_dir_all_if_exists(
+    fs: &dyn FileSystem,
+    directory: &Path,
+) -> Result<(), GenerateArtifactsError> {
+    if fs.exists(directory) {
+        fs.remove_dir_all(directory).map_err(|e| {
+            GenerateArtifactsError::UnableToDeleteDirectory {
+                path: directory.to_path_buf(),
                 message: e.to_string(),
             }
         })?;
+    }
+    Ok(())
+}
 
-        file.write(path_and_content.file_content.as_bytes())
-            .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
-                path: absolute_file_path.clone(),
+fn write_artifact(
+    fs: &dyn FileSystem,
+    absolute_file_path: &Path,
+    content: &str,
+) -> Result<(), GenerateArtifactsError> {
+    create_dir_all(
+        fs,
+        absolute_file_path
+            .parent()
+            .expect("Expected artifact path to have a parent"),
+    )?;
+    fs.write(absolute_file_path, content.as_bytes())
+        .map_err(|e| GenerateArtifactsError::UnableToWriteToArtifactFile {
+            path: absolute_file_path.to_path_buf(),
+            message: e.to_string(),
+        })
+}
+
+fn create_dir_all(fs: &dyn FileSystem, directory: &Path) -> Result<(), GenerateArtifactsError> {
+    fs.create_dir_all(directory)
+        .map_err(|e| GenerateArtifactsError::UnableToCreateDirectory {
+            path: directory.to_path_buf(),
+            message: e.to_string(),
+        })
+}
+
+fn hash_content(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn remove_stale_artifact(
+    fs: &dyn FileSystem,
+    stale_file_path: &Path,
+    artifact_directory: &Path,
+) -> Result<(), GenerateArtifactsError> {
+    if let Err(e) = fs.remove_file(stale_file_path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            return Err(GenerateArtifactsError::UnableToDeleteFile {
+                path: stale_file_path.to_path_buf(),
                 message: e.to_string(),
-            })?;
+            });
+        }
     }
-    Ok(count)
+
+    // Clean up the now-empty Type/field directories, but never the artifact
+    // directory itself. remove_dir fails if the directory is not empty, which
+    // is what we want.
+    let mut directory = stale_file_path.parent();
+    while let Some(dir) = directory {
+        if dir == artifact_directory || !dir.starts_with(artifact_directory) {
+            break;
+        }
+        if fs.remove_dir(dir).is_err() {
+            break;
+        }
+        directory = dir.parent();
+    }
+    Ok(())
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -85,4 +445,279 @@ pub enum GenerateArtifactsError {
         \nReason: {message:?}"
     )]
     UnableToDeleteDirectory { path: PathBuf, message: String },
+
+    #[error(
+        "Unable to delete file at path {path:?}. \
+        Is there another instance of the Isograph compiler running?\
+        \nReason: {message:?}"
+    )]
+    UnableToDeleteFile { path: PathBuf, message: String },
+
+    #[error(
+        "Unable to replace the artifact directory at path {path:?}. \
+        Is there another instance of the Isograph compiler running?\
+        \nReason: {message:?}"
+    )]
+    UnableToReplaceDirectory { path: PathBuf, message: String },
+
+    #[error("Unable to write report at path {path:?}.\nReason: {message:?}")]
+    UnableToWriteReport { path: PathBuf, message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use common_lang_types::ObjectTypeAndFieldName;
+    use intern::string_key::Intern;
+
+    use super::*;
+    use crate::file_system::InMemoryFileSystem;
+
+    fn artifact(
+        type_and_field: Option<(&str, &str)>,
+        file_name: &str,
+        file_content: &str,
+    ) -> ArtifactPathAndContent {
+        ArtifactPathAndContent {
+            type_and_field: type_and_field.map(|(type_name, field_name)| ObjectTypeAndFieldName {
+                type_name: type_name.intern().into(),
+                field_name: field_name.intern().into(),
+            }),
+            file_name: file_name.intern().into(),
+            file_content: file_content.to_string(),
+        }
+    }
+
+    fn file_paths(fs: &InMemoryFileSystem) -> Vec<PathBuf> {
+        fs.files().into_keys().collect()
+    }
+
+    #[test]
+    fn failed_writes_leave_previous_artifacts_intact() {
+        let artifact_directory = Path::new("/project/__isograph");
+        let fs = InMemoryFileSystem::new();
+        fs.add_file("/project/__isograph/iso.ts", "previous");
+
+        // The Query directory cannot be created, because a file is written there first.
+        let conflicting_artifacts = || {
+            vec![
+                artifact(None, "Query", ""),
+                artifact(Some(("Query", "HomePage")), "entrypoint.ts", ""),
+            ]
+        };
+        let result = write_artifacts_to_disk(
+            &fs,
+            conflicting_artifacts(),
+            artifact_directory,
+            ArtifactLayout::Nested,
+            &mut None,
+            false,
+            false,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            file_paths(&fs),
+            vec![PathBuf::from("/project/__isograph/iso.ts")]
+        );
+
+        let result = write_artifacts_to_disk(
+            &fs,
+            conflicting_artifacts(),
+            artifact_directory,
+            ArtifactLayout::Nested,
+            &mut None,
+            true,
+            false,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            file_paths(&fs),
+            vec![
+                PathBuf::from("/project/__isograph/iso.ts"),
+                PathBuf::from("/project/__isograph.tmp/Query"),
+            ]
+        );
+
+        let mut written_artifacts = None;
+        let count = write_artifacts_to_disk(
+            &fs,
+            vec![artifact(Some(("Query", "HomePage")), "entrypoint.ts", "")],
+            artifact_directory,
+            ArtifactLayout::Nested,
+            &mut written_artifacts,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        assert!(written_artifacts.is_some());
+        assert_eq!(
+            file_paths(&fs),
+            vec![PathBuf::from(
+                "/project/__isograph/Query/HomePage/entrypoint.ts"
+            )]
+        );
+        assert!(!fs.exists(Path::new("/project/__isograph.tmp")));
+        assert!(!fs.exists(Path::new("/project/__isograph.old")));
+    }
+
+    #[test]
+    fn failed_incremental_writes_leave_previous_artifacts_intact() {
+        let artifact_directory = Path::new("/project/__isograph");
+        let fs = InMemoryFileSystem::new();
+        let home_page = |content| artifact(Some(("Query", "HomePage")), "entrypoint.ts", content);
+        let pet_detail = || artifact(Some(("Query", "PetDetail")), "entrypoint.ts", "pet");
+
+        let mut written_artifacts = None;
+        write_artifacts_to_disk(
+            &fs,
+            vec![home_page("home"), pet_detail()],
+            artifact_directory,
+            ArtifactLayout::Nested,
+            &mut written_artifacts,
+            false,
+            false,
+        )
+        .unwrap();
+        let previous_files = fs.files();
+
+        // The Query artifact is written to the temporary directory, and then writing
+        // the changed HomePage artifact fails. So nothing is moved into place, and
+        // PetDetail is not deleted.
+        let result = write_artifacts_to_disk(
+            &fs,
+            vec![artifact(None, "Query", ""), home_page("changed")],
+            artifact_directory,
+            ArtifactLayout::Nested,
+            &mut written_artifacts,
+            false,
+            false,
+        );
+        assert!(result.is_err());
+        assert!(written_artifacts.is_none());
+        assert_eq!(fs.files(), previous_files);
+        assert!(!fs.exists(Path::new("/project/__isograph.tmp")));
+        assert!(!fs.exists(Path::new("/project/__isograph.journal")));
+    }
+
+    #[test]
+    fn interrupted_journals_are_carried_out() {
+        let artifact_directory = Path::new("/project/__isograph");
+        let fs = InMemoryFileSystem::new();
+        let home_page = |content| artifact(Some(("Query", "HomePage")), "entrypoint.ts", content);
+        let pet_detail = || artifact(Some(("Query", "PetDetail")), "entrypoint.ts", "pet");
+
+        let mut written_artifacts = None;
+        let write = |artifacts, written_artifacts: &mut _| {
+            write_artifacts_to_disk(
+                &fs,
+                artifacts,
+                artifact_directory,
+                ArtifactLayout::Nested,
+                written_artifacts,
+                false,
+                false,
+            )
+            .unwrap()
+        };
+        write(
+            vec![home_page("home"), pet_detail()],
+            &mut written_artifacts,
+        );
+
+        // An incremental write that changes HomePage and deletes PetDetail
+        let count = write(vec![home_page("changed")], &mut written_artifacts);
+        assert_eq!(count, 1);
+        assert_eq!(
+            fs.files(),
+            [(
+                PathBuf::from("/project/__isograph/Query/HomePage/entrypoint.ts"),
+                b"changed".to_vec()
+            )]
+            .into()
+        );
+        assert!(!fs.exists(Path::new("/project/__isograph.journal")));
+
+        // A compilation that was killed after writing its journal, and after moving
+        // one of the files into place.
+        fs.add_file("/project/__isograph/Query/PetDetail/entrypoint.ts", "pet");
+        fs.add_file("/project/__isograph/Query/Other/entrypoint.ts", "other");
+        fs.add_file(
+            "/project/__isograph.tmp/Query/HomePage/entrypoint.ts",
+            "home",
+        );
+        fs.add_file(
+            "/project/__isograph.journal",
+            r#"{
+                "written": ["Query/Other/entrypoint.ts", "Query/HomePage/entrypoint.ts"],
+                "deleted": ["Query/PetDetail/entrypoint.ts"]
+            }"#,
+        );
+        carry_out_journal(&fs, artifact_directory).unwrap();
+        assert_eq!(
+            file_paths(&fs),
+            vec![
+                PathBuf::from("/project/__isograph/Query/HomePage/entrypoint.ts"),
+                PathBuf::from("/project/__isograph/Query/Other/entrypoint.ts"),
+            ]
+        );
+        assert_eq!(
+            fs.read_to_string(Path::new(
+                "/project/__isograph/Query/HomePage/entrypoint.ts"
+            ))
+            .unwrap(),
+            "home"
+        );
+        assert!(!fs.exists(Path::new("/project/__isograph/Query/PetDetail")));
+        assert!(!fs.exists(Path::new("/project/__isograph.tmp")));
+    }
+
+    #[test]
+    fn stale_artifacts_are_kept_while_iso_literals_are_skipped() {
+        let artifact_directory = Path::new("/project/__isograph");
+        let fs = InMemoryFileSystem::new();
+        let write = |artifacts, written_artifacts: &mut _, keep_stale_artifacts| {
+            write_artifacts_to_disk(
+                &fs,
+                artifacts,
+                artifact_directory,
+                ArtifactLayout::Nested,
+                written_artifacts,
+                false,
+                keep_stale_artifacts,
+            )
+            .unwrap()
+        };
+        let home_page = || artifact(Some(("Query", "HomePage")), "entrypoint.ts", "home");
+        let pet_detail = || artifact(Some(("Query", "PetDetail")), "entrypoint.ts", "pet");
+
+        let mut written_artifacts = None;
+        write(
+            vec![home_page(), pet_detail()],
+            &mut written_artifacts,
+            false,
+        );
+
+        // PetDetail was skipped, so its artifact is kept
+        let count = write(vec![home_page()], &mut written_artifacts, true);
+        assert_eq!(count, 0);
+        assert_eq!(fs.files().len(), 2);
+
+        // Once nothing is skipped, it is deleted
+        write(vec![home_page()], &mut written_artifacts, false);
+        assert_eq!(
+            file_paths(&fs),
+            vec![PathBuf::from(
+                "/project/__isograph/Query/HomePage/entrypoint.ts"
+            )]
+        );
+
+        // If we have not written artifacts before, we write them in place, and start
+        // from scratch the next time.
+        let mut written_artifacts = None;
+        fs.add_file("/project/__isograph/Query/PetDetail/entrypoint.ts", "pet");
+        let count = write(vec![home_page()], &mut written_artifacts, true);
+        assert_eq!(count, 1);
+        assert!(written_artifacts.is_none());
+        assert_eq!(fs.files().len(), 2);
+    }
 }
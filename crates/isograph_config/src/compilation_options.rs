@@ -1,13 +1,24 @@
 use common_lang_types::{
     relative_path_from_absolute_and_working_directory, AbsolutePathAndRelativePath,
-    CurrentWorkingDirectory, GeneratedFileHeader,
+    ArtifactFileName, CurrentWorkingDirectory, DirectiveName, GeneratedFileHeader,
+    IsographObjectTypeName, Location, ObjectTypeAndFieldName, SelectableName,
+    ServerScalarSelectableName, UnvalidatedTypeName, Warning,
 };
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use intern::string_key::Intern;
+use intern::Lookup;
 use schemars::JsonSchema;
-use serde::Deserialize;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 use tracing::warn;
 
+use crate::validate_config;
+
 pub static ISOGRAPH_FOLDER: &str = "__isograph";
 
 use std::error::Error;
@@ -18,8 +29,12 @@ use std::error::Error;
 pub struct CompilerConfig {
     // The absolute path to the config file
     pub config_location: PathBuf,
+    /// The name of the project, if the config file contains multiple projects
+    pub project_name: Option<String>,
     /// The folder where the compiler should look for Isograph literals
     pub project_root: PathBuf,
+    /// Which files in the project root should be searched for Isograph literals
+    pub source_file_filter: SourceFileFilter,
     /// The folder where the compiler should create artifacts
     pub artifact_directory: AbsolutePathAndRelativePath,
     /// The absolute path to the GraphQL schema
@@ -27,19 +42,178 @@ pub struct CompilerConfig {
     /// The absolute path to the schema extensions
     pub schema_extensions: Vec<AbsolutePathAndRelativePath>,
 
+    /// Entrypoints declared in the config, in addition to the entrypoints
+    /// declared in iso literals
+    pub entrypoints: Vec<ConfigEntrypoint>,
+
     /// Various options that are of lesser importance
     pub options: CompilerConfigOptions,
 
     pub current_working_directory: CurrentWorkingDirectory,
+
+    /// If writing the artifacts fails, keep the temporary directory to which they
+    /// were being written, for debugging. Set with --keep-tmp-on-failure, not in
+    /// the config file.
+    pub keep_tmp_on_failure: bool,
+}
+
+/// An entrypoint declared in the entrypoints of the config, e.g. Query.HomePage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigEntrypoint {
+    pub parent_type: UnvalidatedTypeName,
+    pub client_field_name: ServerScalarSelectableName,
+}
+
+impl Display for ConfigEntrypoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.parent_type, self.client_field_name)
+    }
+}
+
+/// Determines which files in the project root are searched for Isograph literals,
+/// based on the include and exclude globs in the config. Globs are matched against
+/// paths relative to the project root.
+#[derive(Debug, Clone)]
+pub struct SourceFileFilter {
+    project_root: PathBuf,
+    /// If None, all files are included.
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl SourceFileFilter {
+    pub fn new(
+        project_root: PathBuf,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<Self, globset::Error> {
+        Ok(SourceFileFilter {
+            project_root,
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(build_glob_set(include)?)
+            },
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    /// A filter that includes every file in the project root.
+    pub fn include_all(project_root: PathBuf) -> Self {
+        SourceFileFilter {
+            project_root,
+            include: None,
+            exclude: GlobSet::empty(),
+        }
+    }
+
+    /// Whether the file at this (absolute) path should be searched for Isograph literals.
+    pub fn includes_file(&self, path: &Path) -> bool {
+        let Ok(relative_path) = path.strip_prefix(&self.project_root) else {
+            return false;
+        };
+        // A file is also excluded if it is in an excluded folder.
+        !relative_path
+            .ancestors()
+            .any(|path| self.exclude.is_match(path))
+            && self
+                .include
+                .as_ref()
+                .is_none_or(|include| include.is_match(relative_path))
+    }
+
+    /// Whether the folder at this (absolute) path is excluded, in which case none
+    /// of its contents need to be visited.
+    pub fn excludes_folder(&self, path: &Path) -> bool {
+        match path.strip_prefix(&self.project_root) {
+            Ok(relative_path) => self.exclude.is_match(relative_path),
+            Err(_) => true,
+        }
+    }
+}
+
+fn build_glob_set(globs: &[String]) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob)?);
+    }
+    builder.build()
 }
 
 #[derive(Default, Debug, Clone)]
 pub struct CompilerConfigOptions {
     pub on_invalid_id_type: OptionalValidationLevel,
+    pub on_missing_node_id_field: OptionalValidationLevel,
+    /// Used instead of on_invalid_id_type and on_missing_node_id_field for the
+    /// id field of these types.
+    pub id_validation_overrides: BTreeMap<IsographObjectTypeName, OptionalValidationLevel>,
     pub no_babel_transform: bool,
     pub include_file_extensions_in_import_statements: GenerateFileExtensionsOption,
     pub module: JavascriptModule,
     pub generated_file_header: Option<GeneratedFileHeader>,
+    /// Sorted so that more specific (i.e. longer) directories come first.
+    pub import_aliases: Vec<ImportAlias>,
+    pub network_protocol: NetworkProtocolKind,
+    pub artifact_layout: ArtifactLayout,
+    pub artifact_persistence: ArtifactPersistence,
+    pub on_unused_client_field: OptionalValidationLevel,
+    /// The absolute path of the JSON report of unused client fields, if one should be written.
+    pub unused_client_fields_report: Option<PathBuf>,
+    pub prune_unused_client_fields: bool,
+    pub max_query_depth: Option<usize>,
+    pub max_query_field_count: Option<usize>,
+    pub selection_budget: Option<SelectionBudget>,
+    pub generate_schema_metadata: bool,
+    pub generate_combined_schema: bool,
+    pub generate_schema_search_index: bool,
+    pub generate_entrypoint_metadata: bool,
+    pub generate_compile_manifest: bool,
+    pub generate_reader_ast_types: bool,
+    pub validation_rules: BTreeMap<BuiltInValidationRule, OptionalValidationLevel>,
+    pub server_field_restrictions: ServerFieldRestrictions,
+    pub generate_pagination_fields: bool,
+    pub generate_artifact_provenance: bool,
+    pub skip_invalid_iso_literals: bool,
+    pub strict_schema: bool,
+    pub implicit_concrete_type_refinements: bool,
+    /// How long the watcher waits for more file changes before compiling.
+    pub watch_debounce: Duration,
+    pub nullable_fields: NullableFieldOptions,
+    pub iso_literal_extractor: IsoLiteralExtractor,
+    pub operation_names: OperationNameOptions,
+    pub root_type_names: RootTypeNames,
+    pub propagated_object_directives: Vec<DirectiveName>,
+    /// If true, the reader artifacts of client-rendered components start with a
+    /// "use client" directive.
+    pub react_server_components: bool,
+}
+
+impl CompilerConfigOptions {
+    /// What the compiler should do if the id field of this type is not of type ID!.
+    pub fn on_invalid_id_type(&self, type_name: IsographObjectTypeName) -> OptionalValidationLevel {
+        self.id_validation_overrides
+            .get(&type_name)
+            .copied()
+            .unwrap_or(self.on_invalid_id_type)
+    }
+
+    /// What the compiler should do if this type implements Node, but has no id field.
+    pub fn on_missing_node_id_field(
+        &self,
+        type_name: IsographObjectTypeName,
+    ) -> OptionalValidationLevel {
+        self.id_validation_overrides
+            .get(&type_name)
+            .copied()
+            .unwrap_or(self.on_missing_node_id_field)
+    }
+}
+
+/// An import path prefix (e.g. `@src`) and the directory it refers to.
+#[derive(Debug, Clone)]
+pub struct ImportAlias {
+    pub prefix: String,
+    pub directory: AbsolutePathAndRelativePath,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -58,9 +232,18 @@ impl GenerateFileExtensionsOption {
     }
 }
 
+/// The maximum number of scalar fields in the merged selection of a client field,
+/// and what the compiler should do if a client field exceeds it.
 #[derive(Debug, Clone, Copy)]
+pub struct SelectionBudget {
+    pub max_scalar_field_count: usize,
+    pub on_exceeded: OptionalValidationLevel,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
 pub enum OptionalValidationLevel {
     /// If this validation error is encountered, it will be ignored
+    #[default]
     Ignore,
     /// If this validation error is encountered, a warning will be issued
     Warn,
@@ -71,13 +254,28 @@ pub enum OptionalValidationLevel {
 impl OptionalValidationLevel {
     pub fn on_failure<E>(self, on_error: impl FnOnce() -> E) -> Result<(), E>
     where
-        E: Error,
+        E: Error + Warning,
     {
         match self {
             OptionalValidationLevel::Ignore => Ok(()),
             OptionalValidationLevel::Warn => {
                 let warning = on_error();
-                warn!("{warning}");
+                let (file, span) = match warning.warning_location() {
+                    Location::Embedded(location) => (
+                        Some(location.text_source.relative_path_to_source_file.lookup()),
+                        Some(location.span_in_file()),
+                    ),
+                    Location::Generated => (None, None),
+                };
+                // The warning.* fields are read when printing warnings as JSON.
+                warn!(
+                    warning.code = %warning.warning_code(),
+                    warning.message = warning.warning_message(),
+                    warning.file = file,
+                    warning.span_start = span.map(|span| span.start),
+                    warning.span_end = span.map(|span| span.end),
+                    "{warning}"
+                );
                 Ok(())
             }
             OptionalValidationLevel::Error => Err(on_error()),
@@ -85,12 +283,6 @@ impl OptionalValidationLevel {
     }
 }
 
-impl Default for OptionalValidationLevel {
-    fn default() -> Self {
-        Self::Ignore
-    }
-}
-
 #[derive(Default, Debug, Clone, Copy)]
 pub enum JavascriptModule {
     CommonJs,
@@ -98,12 +290,335 @@ pub enum JavascriptModule {
     EsModule,
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkProtocolKind {
+    /// The schema is a GraphQL schema, and entrypoints make GraphQL requests.
+    #[default]
+    GraphQL,
+    /// The schema is an OpenAPI 3 document (in JSON), and entrypoints make
+    /// REST requests.
+    Rest,
+    /// The schema is a set of .proto files, and entrypoints call gRPC methods
+    /// using a user-provided transport.
+    Grpc,
+    /// The schema is a GraphQL schema, and schema extensions can also be OpenAPI
+    /// documents and .proto files. Each field is fetched with the protocol of the
+    /// file that defines it.
+    Mixed,
+}
+
+/// Server fields that client fields and client pointers may not select.
+#[derive(Default, Debug, Clone)]
+pub struct ServerFieldRestrictions {
+    pub disallowed: BTreeSet<ObjectTypeAndFieldName>,
+    /// If a type has an entry, only the listed fields of that type may be selected.
+    pub allowed: BTreeMap<IsographObjectTypeName, BTreeSet<SelectableName>>,
+}
+
+impl ServerFieldRestrictions {
+    /// Some if the field may not be selected, with the name of the config option
+    /// that disallows it.
+    pub fn restricted_by(&self, type_and_field: ObjectTypeAndFieldName) -> Option<&'static str> {
+        if self.disallowed.contains(&type_and_field) {
+            return Some("disallowed_server_fields");
+        }
+        match self.allowed.get(&type_and_field.type_name) {
+            Some(allowed_fields) if !allowed_fields.contains(&type_and_field.field_name) => {
+                Some("allowed_server_fields")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// How nullable fields are typed in the param types of client fields and client
+/// pointers. Runtimes differ in how they represent missing data, e.g. some omit
+/// fields instead of setting them to null.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NullableFieldOptions {
+    /// If true, nullable fields are typed as T | null. Otherwise, they are typed as
+    /// T | null | undefined.
+    pub strict_nullability: bool,
+    /// If true, nullable fields are optional properties, e.g. readonly name?: T | null.
+    pub treat_nullable_as_optional_prop: bool,
+    /// If true, fields that are only null if there is an error, e.g. fields with
+    /// @semanticNonNull, are typed as non-null.
+    pub semantic_non_null: bool,
+}
+
+impl Default for NullableFieldOptions {
+    fn default() -> Self {
+        Self {
+            strict_nullability: true,
+            treat_nullable_as_optional_prop: false,
+            semantic_non_null: false,
+        }
+    }
+}
+
+impl NullableFieldOptions {
+    /// The type that is added to the type of a nullable field, e.g. null.
+    pub fn null_type(&self) -> &'static str {
+        if self.strict_nullability {
+            "null"
+        } else {
+            "null | undefined"
+        }
+    }
+
+    /// The suffix of the key of a nullable field, e.g. ? for an optional property.
+    pub fn optional_marker(&self) -> &'static str {
+        if self.treat_nullable_as_optional_prop {
+            "?"
+        } else {
+            ""
+        }
+    }
+}
+
+/// How the queries and mutations in the generated query texts are named.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationNameOptions {
+    /// Added to the start of the name of every operation.
+    pub prefix: String,
+    /// Added to the end of the name of every operation.
+    pub suffix: String,
+    /// The name of the refetch queries and mutations of an entrypoint, in which
+    /// {type}, {entrypoint} and {field} are replaced by the type on which the
+    /// entrypoint is defined, the name of the entrypoint, and the name of the
+    /// refetch field (e.g. __refetch).
+    pub refetch_template: String,
+}
+
+pub(crate) const REFETCH_OPERATION_NAME_PLACEHOLDERS: [&str; 3] =
+    ["{type}", "{entrypoint}", "{field}"];
+
+impl Default for OperationNameOptions {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            suffix: String::new(),
+            refetch_template: "{entrypoint}__{field}".to_string(),
+        }
+    }
+}
+
+impl OperationNameOptions {
+    /// The name of the query of an entrypoint, or of a loadable field.
+    pub fn entrypoint_operation_name(&self, entrypoint: impl Display) -> String {
+        format!("{}{entrypoint}{}", self.prefix, self.suffix)
+    }
+
+    /// The name of a refetch query or mutation of an entrypoint.
+    pub fn refetch_operation_name(
+        &self,
+        type_name: impl Display,
+        entrypoint: impl Display,
+        field: impl Display,
+    ) -> String {
+        let name = self
+            .refetch_template
+            .replace("{type}", &type_name.to_string())
+            .replace("{entrypoint}", &entrypoint.to_string())
+            .replace("{field}", &field.to_string());
+        format!("{}{name}{}", self.prefix, self.suffix)
+    }
+}
+
+/// The names of the query and mutation types, if the schema does not contain a
+/// schema definition (e.g. schema { query: QueryRoot }). If it does, the schema
+/// definition takes precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RootTypeNames {
+    pub query: IsographObjectTypeName,
+    pub mutation: IsographObjectTypeName,
+}
+
+impl Default for RootTypeNames {
+    fn default() -> Self {
+        Self {
+            query: "Query".intern().into(),
+            mutation: "Mutation".intern().into(),
+        }
+    }
+}
+
+/// A validation rule that ships with the compiler, and that can be enabled with the
+/// validation_rules config option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BuiltInValidationRule {
+    /// Client fields and client pointers may not select deprecated server fields.
+    NoDeprecatedFields,
+    /// The const export of a client field or client pointer must have the same name
+    /// as the client field or client pointer.
+    ExportNameMatchesFieldName,
+}
+
+/// How the compiler finds the iso literals in source files.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsoLiteralExtractor {
+    /// Scan the text of each file. This relies on iso literals being formatted
+    /// like they are by prettier.
+    #[default]
+    Regex,
+    /// Parse each file with SWC. Files that cannot be parsed are scanned instead.
+    Swc,
+}
+
+/// How the artifacts are written by the CLI and the language server.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactPersistence {
+    /// Each artifact is written to its own file in the artifact directory.
+    #[default]
+    FileSystem,
+    /// Every artifact is written into a single artifacts.ts file in the artifact
+    /// directory, which exports a map from artifact path to artifact content.
+    Bundle,
+}
+
+/// How the artifacts of each field are laid out in the artifact directory.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactLayout {
+    /// Each field has its own folder, e.g. Pet/PetAvatar/resolver_reader.ts
+    #[default]
+    Nested,
+    /// Every artifact is in the artifact directory, e.g. Pet__PetAvatar__resolver_reader.ts
+    Flat,
+    /// Each type has its own folder, which contains the artifacts of every field of
+    /// that type, e.g. Pet/PetAvatar__resolver_reader.ts
+    FolderPerType,
+}
+
+impl ArtifactLayout {
+    /// The path of an artifact, relative to the artifact directory. Artifacts without
+    /// a type and field (e.g. iso.ts) are always in the artifact directory.
+    pub fn generate_path(
+        self,
+        type_and_field: Option<ObjectTypeAndFieldName>,
+        file_name: ArtifactFileName,
+    ) -> PathBuf {
+        let Some(ObjectTypeAndFieldName {
+            type_name,
+            field_name,
+        }) = type_and_field
+        else {
+            return PathBuf::from(file_name.lookup());
+        };
+        match self {
+            ArtifactLayout::Nested => PathBuf::from(type_name.lookup())
+                .join(field_name.lookup())
+                .join(file_name.lookup()),
+            ArtifactLayout::Flat => {
+                PathBuf::from(format!("{type_name}__{field_name}__{file_name}"))
+            }
+            ArtifactLayout::FolderPerType => {
+                PathBuf::from(type_name.lookup()).join(format!("{field_name}__{file_name}"))
+            }
+        }
+    }
+
+    /// The type and field of an artifact, from its path relative to the artifact
+    /// directory, i.e. the inverse of generate_path. Returns None for artifacts
+    /// without a type and field (e.g. iso.ts).
+    pub fn type_and_field_of_path(self, path: &Path) -> Option<(&str, &str)> {
+        let mut components = path.iter().map(|component| component.to_str());
+        match self {
+            ArtifactLayout::Nested => {
+                let type_name = components.next()??;
+                let field_name = components.next()??;
+                let _file_name = components.next()??;
+                components
+                    .next()
+                    .is_none()
+                    .then_some((type_name, field_name))
+            }
+            ArtifactLayout::Flat => {
+                let file_name = components.next()??;
+                let (type_name, rest) = file_name.split_once("__")?;
+                let (field_name, _file_name) = rest.split_once("__")?;
+                components
+                    .next()
+                    .is_none()
+                    .then_some((type_name, field_name))
+            }
+            ArtifactLayout::FolderPerType => {
+                let type_name = components.next()??;
+                let (field_name, _file_name) = components.next()??.split_once("__")?;
+                components
+                    .next()
+                    .is_none()
+                    .then_some((type_name, field_name))
+            }
+        }
+    }
+
+    /// The path (without extension) used to import an artifact of the same field,
+    /// from an artifact of that field, e.g. ./param_type
+    pub fn sibling_import_path(
+        self,
+        type_and_field: ObjectTypeAndFieldName,
+        file_prefix: impl Display,
+    ) -> String {
+        let ObjectTypeAndFieldName {
+            type_name,
+            field_name,
+        } = type_and_field;
+        match self {
+            ArtifactLayout::Nested => format!("./{file_prefix}"),
+            ArtifactLayout::Flat => format!("./{type_name}__{field_name}__{file_prefix}"),
+            ArtifactLayout::FolderPerType => format!("./{field_name}__{file_prefix}"),
+        }
+    }
+
+    /// The path (without extension) used to import an artifact of a field, from an
+    /// artifact of any field, e.g. ../../Pet/PetAvatar/resolver_reader
+    pub fn field_import_path(
+        self,
+        type_and_field: ObjectTypeAndFieldName,
+        file_prefix: impl Display,
+    ) -> String {
+        format!(
+            "{}/{}",
+            self.path_to_artifact_directory(),
+            self.path_within_artifact_directory(type_and_field, file_prefix)
+        )
+    }
+
+    /// The relative path from the folder containing the artifacts of a field to the
+    /// artifact directory.
+    pub fn path_to_artifact_directory(self) -> &'static str {
+        match self {
+            ArtifactLayout::Nested => "../..",
+            ArtifactLayout::Flat => ".",
+            ArtifactLayout::FolderPerType => "..",
+        }
+    }
+
+    /// The path (without extension) of an artifact of a field, relative to the artifact
+    /// directory, e.g. Pet/PetAvatar/param_type
+    pub fn path_within_artifact_directory(
+        self,
+        type_and_field: ObjectTypeAndFieldName,
+        file_prefix: impl Display,
+    ) -> String {
+        let ObjectTypeAndFieldName {
+            type_name,
+            field_name,
+        } = type_and_field;
+        match self {
+            ArtifactLayout::Nested => format!("{type_name}/{field_name}/{file_prefix}"),
+            ArtifactLayout::Flat => format!("{type_name}__{field_name}__{file_prefix}"),
+            ArtifactLayout::FolderPerType => format!("{type_name}/{field_name}__{file_prefix}"),
+        }
+    }
+}
+
 /// This struct is deserialized from an isograph.config.json file.
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct IsographProjectConfig {
     /// The user may hard-code the JSON Schema for their version of the config.
-    #[serde(rename = "$schema")]
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
     #[allow(dead_code)]
     pub json_schema: Option<String>,
     /// The relative path to the folder where the compiler should look for Isograph literals
@@ -116,17 +631,65 @@ pub struct IsographProjectConfig {
     /// The relative path to schema extensions
     #[serde(default)]
     pub schema_extensions: Vec<PathBuf>,
+    /// Globs, relative to the project_root, of the files that should be searched
+    /// for Isograph literals, e.g. "**/*.tsx". Defaults to all files.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Globs, relative to the project_root, of the files and folders that should
+    /// not be searched for Isograph literals, e.g. "**/node_modules" or "**/*.stories.tsx".
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Entrypoints (e.g. "Query.HomePage") for which artifacts should be generated,
+    /// in addition to the entrypoints declared with iso entrypoint literals.
+    #[serde(default)]
+    pub entrypoints: Vec<String>,
 
     /// Various options of less importance
     #[serde(default)]
     pub options: ConfigFileOptions,
 }
 
-pub fn create_config(
-    config_location: PathBuf,
-    current_working_directory: CurrentWorkingDirectory,
-) -> CompilerConfig {
-    let config_contents = match std::fs::read_to_string(&config_location) {
+impl IsographProjectConfig {
+    /// Set the artifact directory, and the options that are optional but have a
+    /// default, to their defaults.
+    fn apply_defaults(&mut self) {
+        self.artifact_directory
+            .get_or_insert_with(|| self.project_root.clone());
+        self.options.apply_defaults();
+    }
+}
+
+/// This struct is deserialized from an isograph.config.json file that contains
+/// multiple, independent projects (e.g. in a monorepo.)
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IsographMultiProjectConfig {
+    /// The user may hard-code the JSON Schema for their version of the config.
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    #[allow(dead_code)]
+    pub json_schema: Option<String>,
+    /// A map from project name to project config. Each project has its own
+    /// schema, project root and artifact directory, and is compiled independently.
+    pub projects: BTreeMap<String, IsographProjectConfig>,
+}
+
+// This enum is only used to generate the JSON Schema of the config. When reading
+// the config, we check for the presence of the projects key instead, which gives
+// better error messages than an untagged enum.
+/// The contents of an isograph.config.json file: either a single project, or
+/// multiple projects.
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code, clippy::large_enum_variant)]
+pub enum IsographConfigFile {
+    SingleProject(IsographProjectConfig),
+    MultiProject(IsographMultiProjectConfig),
+}
+
+/// Read the config file, panicking if it is not valid JSON, or if it is not a valid
+/// config (e.g. because it contains unknown keys).
+fn read_config_file(config_location: &Path) -> serde_json::Value {
+    let config_contents = match std::fs::read_to_string(config_location) {
         Ok(contents) => contents,
         Err(_) => match config_location.to_str() {
             Some(loc) => {
@@ -138,9 +701,144 @@ pub fn create_config(
         },
     };
 
-    let config_parsed: IsographProjectConfig = serde_json::from_str(&config_contents)
+    let config_value: serde_json::Value = serde_json::from_str(&config_contents)
         .unwrap_or_else(|e| panic!("Error parsing config. Error: {}", e));
 
+    let validation_errors = validate_config(&config_value);
+    if !validation_errors.is_empty() {
+        panic!(
+            "Invalid config at {}:\n{}",
+            config_location.display(),
+            validation_errors
+                .iter()
+                .map(|error| format!("  {error}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    config_value
+}
+
+/// The contents of the config file, with every option that has a default and is
+/// not set (or is null) set to its default, pretty-printed as JSON.
+pub fn resolved_config_json(config_location: &Path) -> String {
+    let config_value = read_config_file(config_location);
+    let resolved_config_value = if config_value.get("projects").is_none() {
+        let mut config_parsed: IsographProjectConfig = serde_json::from_value(config_value)
+            .unwrap_or_else(|e| panic!("Error parsing config. Error: {}", e));
+        config_parsed.apply_defaults();
+        serde_json::to_value(config_parsed)
+    } else {
+        let mut multi_project_config: IsographMultiProjectConfig =
+            serde_json::from_value(config_value)
+                .unwrap_or_else(|e| panic!("Error parsing config. Error: {}", e));
+        for config_parsed in multi_project_config.projects.values_mut() {
+            config_parsed.apply_defaults();
+        }
+        serde_json::to_value(multi_project_config)
+    };
+    serde_json::to_string_pretty(&resolved_config_value.expect("Expected config to serialize"))
+        .expect("Expected config to serialize")
+}
+
+/// Create the config of every project in the config file. If the config file
+/// does not contain a projects key, this returns a single config.
+pub fn create_configs(
+    config_location: PathBuf,
+    current_working_directory: CurrentWorkingDirectory,
+) -> Vec<CompilerConfig> {
+    let config_value = read_config_file(&config_location);
+
+    if config_value.get("projects").is_none() {
+        let config_parsed: IsographProjectConfig = serde_json::from_value(config_value)
+            .unwrap_or_else(|e| panic!("Error parsing config. Error: {}", e));
+        return vec![create_project_config(
+            &config_location,
+            None,
+            config_parsed,
+            current_working_directory,
+        )];
+    }
+
+    let multi_project_config: IsographMultiProjectConfig = serde_json::from_value(config_value)
+        .unwrap_or_else(|e| panic!("Error parsing config. Error: {}", e));
+    if multi_project_config.projects.is_empty() {
+        panic!("config.projects should contain at least one project.");
+    }
+
+    let configs = multi_project_config
+        .projects
+        .into_iter()
+        .map(|(project_name, config_parsed)| {
+            create_project_config(
+                &config_location,
+                Some(project_name),
+                config_parsed,
+                current_working_directory,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Each project deletes and recreates its artifact directory, so projects
+    // cannot share one.
+    let mut artifact_directories = BTreeMap::new();
+    for config in configs.iter() {
+        if let Some(other_project_name) = artifact_directories.insert(
+            &config.artifact_directory.absolute_path,
+            config.project_name.as_deref(),
+        ) {
+            panic!(
+                "The projects {} and {} have the same artifact directory. \
+                Each project must have its own artifact directory.",
+                other_project_name.unwrap_or_default(),
+                config.project_name.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+
+    configs
+}
+
+/// Create the config of a single project. If the config file contains multiple
+/// projects, project_name must be provided, unless there is exactly one project.
+pub fn create_config(
+    config_location: PathBuf,
+    current_working_directory: CurrentWorkingDirectory,
+    project_name: Option<&str>,
+) -> CompilerConfig {
+    let mut configs = create_configs(config_location, current_working_directory);
+    match project_name {
+        Some(project_name) => {
+            let index = configs
+                .iter()
+                .position(|config| config.project_name.as_deref() == Some(project_name))
+                .unwrap_or_else(|| {
+                    panic!("Expected the config to contain a project named {project_name}.")
+                });
+            configs.swap_remove(index)
+        }
+        None => {
+            if configs.len() != 1 {
+                panic!(
+                    "The config contains multiple projects ({}). Specify which one to use.",
+                    configs
+                        .iter()
+                        .filter_map(|config| config.project_name.as_deref())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            configs.swap_remove(0)
+        }
+    }
+}
+
+fn create_project_config(
+    config_location: &PathBuf,
+    project_name: Option<String>,
+    config_parsed: IsographProjectConfig,
+    current_working_directory: CurrentWorkingDirectory,
+) -> CompilerConfig {
     let mut config = config_location.clone();
     config.pop();
     let config_dir = config;
@@ -153,24 +851,36 @@ pub fn create_config(
                 .unwrap_or(&config_parsed.project_root),
         )
         .join(ISOGRAPH_FOLDER);
-    std::fs::create_dir_all(&artifact_dir).expect("Unable to create artifact directory");
+    std::fs::create_dir_all(&artifact_dir).unwrap_or_else(|e| {
+        panic!("Unable to create artifact directory at {artifact_dir:?}. Reason: {e}")
+    });
 
     let project_root_dir = config_dir.join(&config_parsed.project_root);
     std::fs::create_dir_all(&project_root_dir).expect("Unable to create project root directory");
+    let project_root = project_root_dir.canonicalize().unwrap_or_else(|_| {
+        panic!(
+            "Unable to canonicalize project root at {:?}.",
+            config_parsed.project_root
+        )
+    });
+
+    let source_file_filter = SourceFileFilter::new(
+        project_root.clone(),
+        &config_parsed.include,
+        &config_parsed.exclude,
+    )
+    .unwrap_or_else(|e| panic!("Invalid include or exclude glob. Reason: {e}"));
 
     CompilerConfig {
+        project_name,
         config_location: config_location.canonicalize().unwrap_or_else(|_| {
             panic!(
                 "Unable to canonicalize config_file at {:?}.",
                 config_location
             )
         }),
-        project_root: project_root_dir.canonicalize().unwrap_or_else(|_| {
-            panic!(
-                "Unable to canonicalize project root at {:?}.",
-                config_parsed.project_root
-            )
-        }),
+        project_root,
+        source_file_filter,
         artifact_directory: absolute_and_relative_paths(
             current_working_directory,
             artifact_dir.canonicalize().unwrap_or_else(|_| {
@@ -210,18 +920,54 @@ pub fn create_config(
                 )
             })
             .collect(),
-        options: create_options(config_parsed.options),
+        entrypoints: config_parsed
+            .entrypoints
+            .iter()
+            .map(|entrypoint| create_config_entrypoint(entrypoint))
+            .collect(),
+        options: create_options(
+            config_parsed.options,
+            &config_dir,
+            current_working_directory,
+        ),
 
         current_working_directory,
+        keep_tmp_on_failure: false,
     }
 }
 
-#[derive(Deserialize, Default, JsonSchema)]
+fn create_config_entrypoint(entrypoint: &str) -> ConfigEntrypoint {
+    match entrypoint.trim().split_once('.') {
+        Some((parent_type, client_field_name))
+            if !parent_type.is_empty()
+                && !client_field_name.is_empty()
+                && !client_field_name.contains('.') =>
+        {
+            ConfigEntrypoint {
+                parent_type: parent_type.intern().into(),
+                client_field_name: client_field_name.intern().into(),
+            }
+        }
+        _ => panic!(
+            "Invalid entrypoint {entrypoint:?} in config.entrypoints. \
+            Entrypoints should be of the form Type.field, e.g. Query.HomePage."
+        ),
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, JsonSchema)]
 #[serde(default, deny_unknown_fields)]
 pub struct ConfigFileOptions {
     /// What the compiler should do if it encounters an id field whose
     /// type is not ID! or ID.
     on_invalid_id_type: ConfigFileOptionalValidationLevel,
+    /// What the compiler should do if it encounters a type that implements Node,
+    /// but has no id field. Defaults to "ignore".
+    on_missing_node_id_field: Option<ConfigFileOptionalValidationLevel>,
+    /// A map from type names to what the compiler should do if the id field of that
+    /// type is invalid or missing, instead of on_invalid_id_type and
+    /// on_missing_node_id_field. For example, { "LegacyThing": "ignore" }.
+    id_validation_overrides: BTreeMap<String, ConfigFileOptionalValidationLevel>,
     /// Set this to true if you don't have the babel transform enabled.
     no_babel_transform: bool,
     /// Should the compiler include file extensions in import statements in
@@ -233,9 +979,213 @@ pub struct ConfigFileOptions {
     module: ConfigFileJavascriptModule,
     /// A string to generate, in a comment, at the top of every generated file.
     generated_file_header: Option<String>,
+    /// A map from import path prefixes (e.g. "@src") to the folders they refer to,
+    /// relative to the config file (e.g. "./src"), like the paths in a tsconfig.json.
+    /// Generated artifacts import resolvers in these folders using the prefix,
+    /// instead of a relative path.
+    import_aliases: BTreeMap<String, PathBuf>,
+    /// The kind of API described by the schema. With "graphql", the schema (and
+    /// schema extensions) are GraphQL SDL files. With "rest", they are OpenAPI 3
+    /// documents, in JSON. With "grpc", they are proto3 files. With "mixed", the
+    /// schema is a GraphQL SDL file, and schema extensions that end in .json or
+    /// .proto are OpenAPI documents or proto files, whose types are prefixed with
+    /// Rest or Grpc.
+    network_protocol: ConfigFileNetworkProtocol,
+    /// How the artifacts of each field are laid out in the artifact directory. With
+    /// "nested", each field has its own folder (e.g. Pet/PetAvatar/resolver_reader.ts).
+    /// With "flat", every artifact is in the artifact directory (e.g.
+    /// Pet__PetAvatar__resolver_reader.ts). With "folder_per_type", each type has its
+    /// own folder, which contains one file per artifact of each of its fields (e.g.
+    /// Pet/PetAvatar__resolver_reader.ts).
+    artifact_layout: ConfigFileArtifactLayout,
+    /// How the artifacts are written. With "file_system", each artifact is written to
+    /// its own file in the artifact directory. With "bundle", every artifact is written
+    /// into a single artifacts.ts file in the artifact directory, which exports a map
+    /// from artifact path to artifact content, for environments in which writing many
+    /// files is not possible.
+    artifact_persistence: ConfigFileArtifactPersistence,
+    /// What the compiler should do if a client field or client pointer is not
+    /// reachable from any entrypoint. Defaults to "ignore".
+    on_unused_client_field: Option<ConfigFileOptionalValidationLevel>,
+    /// The relative path to a file to which the compiler should write a JSON report
+    /// of the client fields and client pointers that are not reachable from any
+    /// entrypoint.
+    unused_client_fields_report: Option<PathBuf>,
+    /// Set this to true to skip generating the param_type and output_type artifacts
+    /// of client fields and client pointers that are not reachable from any entrypoint.
+    /// The iso function will not have a typed overload for these.
+    prune_unused_client_fields: bool,
+    /// The maximum number of nested linked fields in the query of an entrypoint,
+    /// e.g. me { pets { name } } has a depth of 2. If set, the compiler reports an
+    /// error for each entrypoint whose query is deeper.
+    max_query_depth: Option<usize>,
+    /// The maximum number of fields in the query of an entrypoint, after selections
+    /// of the same field are merged. If set, the compiler reports an error for each
+    /// entrypoint whose query selects more fields.
+    max_query_field_count: Option<usize>,
+    /// The maximum number of scalar fields that a client field reads from the server,
+    /// including the fields read by the client fields that it (transitively) selects,
+    /// after selections of the same field are merged. If set, the compiler reports
+    /// each client field that reads more scalar fields, and the linked fields that
+    /// select the most scalar fields.
+    selection_budget: Option<usize>,
+    /// What the compiler should do if a client field exceeds the selection_budget.
+    /// Defaults to "warn".
+    on_selection_budget_exceeded: Option<ConfigFileOptionalValidationLevel>,
+    /// Set this to true to generate a schema-metadata.json file in the artifact
+    /// directory. It describes the objects and fields of the schema, and the client
+    /// fields and client pointers of the project, for use by devtools.
+    generate_schema_metadata: bool,
+    /// Set this to true to generate a schema.graphql file in the artifact directory.
+    /// It contains the schema with the schema extensions merged into it, and lists
+    /// every interface that each type implements, for GraphQL tools that do not read
+    /// schema extensions. Only GraphQL projects support this.
+    generate_combined_schema: bool,
+    /// Set this to true to generate a schema-search-index.json file in the artifact
+    /// directory. It contains the types of the schema and the fields defined on
+    /// them, with a trigram index over their names and descriptions. The language
+    /// server uses it to rank completions and to search for workspace symbols.
+    generate_schema_search_index: bool,
+    /// Set this to true to generate a metadata.json file for each entrypoint. It
+    /// contains the aggregate cost of the fields that the entrypoint's query selects,
+    /// and the teams that own them, as declared with the @cost(value: Int) and
+    /// @owner(team: String) directives in the schema.
+    generate_entrypoint_metadata: bool,
+    /// Set this to true to generate a compile-manifest.json file in the artifact
+    /// directory. It maps each source file to the iso literals it contains, and
+    /// each iso literal to the artifacts generated for it, so that JavaScript
+    /// transforms can rewrite iso calls without parsing the literals.
+    generate_compile_manifest: bool,
+    /// Set this to true to generate a reader-ast.ts file in the artifact directory.
+    /// It contains the types of the reader and normalization AST nodes that the
+    /// compiler generates, and fails to type check if the installed version of
+    /// @isograph/react does not match the version of the compiler.
+    generate_reader_ast_types: bool,
+    /// A map from the names of built-in validation rules to what the compiler should
+    /// do if a client field or client pointer violates them. For example,
+    /// { "no_deprecated_fields": "warn" }. Rules that are not listed are not checked.
+    validation_rules: BTreeMap<ConfigFileBuiltInValidationRule, ConfigFileOptionalValidationLevel>,
+    /// Server fields that client fields and client pointers may not select, e.g.
+    /// ["User.ssn"]. Selecting one of them is an error.
+    disallowed_server_fields: Vec<String>,
+    /// If a field of a type is listed here, e.g. "User.name", client fields and client
+    /// pointers can only select the listed fields of that type. Other types are not
+    /// restricted.
+    allowed_server_fields: Vec<String>,
+    /// Set this to true to generate a __paginated_<field> client field for each field
+    /// that returns a Relay-style connection, i.e. that accepts first and after
+    /// arguments, and returns an object with edges { node } and
+    /// pageInfo { hasNextPage endCursor }.
+    generate_pagination_fields: bool,
+    /// Set this to true to start every artifact with a comment like
+    /// // @generated-by isograph 0.3.1 from components/PetDetail.tsx:12, followed by
+    /// the md5 hash of that file, so that tooling can detect artifacts that are stale
+    /// or that were edited by hand.
+    generate_artifact_provenance: bool,
+    /// Set this to true to keep generating artifacts when some iso literals are
+    /// invalid. The files containing invalid iso literals (and the files that depend
+    /// on them) are skipped, and the artifacts generated from them are left as they
+    /// were. The errors are still reported, and the compilation still fails.
+    skip_invalid_iso_literals: bool,
+    /// Set this to true to report errors for schemas that are otherwise accepted,
+    /// but whose meaning is unclear: directives that are neither defined in the
+    /// schema nor known to Isograph, directives used where their definition does
+    /// not allow them, directives that are defined twice, and unions without members.
+    strict_schema: bool,
+    /// Set this to true to allow selecting fields on an abstract type (e.g. an
+    /// interface) that are not defined on it, but are defined on some of its concrete
+    /// types. They are fetched in an inline fragment on each of those types, and are
+    /// null if the record is of another type. Otherwise, selecting them is an error,
+    /// and they must be selected in the asConcreteType fields.
+    implicit_concrete_type_refinements: bool,
+    /// In watch mode, how many milliseconds to wait after a file changes before
+    /// compiling. Changes made within this time (e.g. by a formatter, or by git
+    /// checkout) are compiled together. Defaults to 500.
+    watch_debounce_ms: Option<u64>,
+    /// Whether nullable fields are typed as T | null in param types. Set this to
+    /// false to type them as T | null | undefined instead, e.g. if your runtime
+    /// omits missing fields. Defaults to true.
+    strict_nullability: Option<bool>,
+    /// Set this to true to make nullable fields optional properties in param types,
+    /// e.g. readonly nickname?: string | null.
+    treat_nullable_as_optional_prop: bool,
+    /// Set this to true to type fields with @semanticNonNull as non-null in param
+    /// types. These fields are only null if there is an error, so the query text
+    /// still selects them as nullable.
+    semantic_non_null: bool,
+    /// How the compiler and language server find iso literals. With "regex" (the
+    /// default), the text of each file is scanned, which relies on iso literals being
+    /// formatted like they are by prettier. With "swc", each file is parsed with SWC,
+    /// so iso literals are found regardless of formatting, and iso literals in
+    /// comments are ignored.
+    iso_literal_extractor: ConfigFileIsoLiteralExtractor,
+    /// A string to add to the start of the name of every generated query and
+    /// mutation, e.g. "MyApp_".
+    operation_name_prefix: Option<String>,
+    /// A string to add to the end of the name of every generated query and mutation.
+    operation_name_suffix: Option<String>,
+    /// The name of the refetch queries and mutations (e.g. of __refetch) of each
+    /// entrypoint. {type}, {entrypoint} and {field} are replaced by the type on which
+    /// the entrypoint is defined, the name of the entrypoint, and the name of the
+    /// refetch field. Defaults to "{entrypoint}__{field}", which is unique per
+    /// entrypoint. With "{type}__{field}", the refetch queries of entrypoints on the
+    /// same type have the same name.
+    refetch_operation_name_template: Option<String>,
+    /// The name of the query type. Defaults to "Query". This is only used if the
+    /// schema does not contain a schema definition, e.g. schema { query: QueryRoot }.
+    query_type_name: Option<String>,
+    /// The name of the mutation type. Defaults to "Mutation". This is only used if
+    /// the schema does not contain a schema definition.
+    mutation_type_name: Option<String>,
+    /// The names of directives on objects in the schema, e.g. ["cacheControl"], to
+    /// include in the normalization AST of each entrypoint, along with their
+    /// arguments, for the types that the entrypoint's query can fetch. For example,
+    /// the runtime's cache can use @cacheControl(maxAge: 60) to expire records.
+    propagated_object_directives: Vec<String>,
+    /// Set this to true if components are rendered with React Server Components.
+    /// Components are then client-rendered, and the reader artifact of each
+    /// component starts with "use client". Components that do not use hooks can
+    /// opt out with @component(boundary: server), in which case they can be rendered
+    /// by server components. Entrypoint artifacts never start with "use client", so
+    /// server components can fetch them.
+    react_server_components: bool,
 }
 
-#[derive(Deserialize, Debug, Clone, Copy, JsonSchema)]
+impl ConfigFileOptions {
+    fn on_missing_node_id_field(&self) -> ConfigFileOptionalValidationLevel {
+        self.on_missing_node_id_field
+            .unwrap_or(ConfigFileOptionalValidationLevel::Ignore)
+    }
+
+    fn on_unused_client_field(&self) -> ConfigFileOptionalValidationLevel {
+        self.on_unused_client_field
+            .unwrap_or(ConfigFileOptionalValidationLevel::Ignore)
+    }
+
+    fn on_selection_budget_exceeded(&self) -> ConfigFileOptionalValidationLevel {
+        self.on_selection_budget_exceeded
+            .unwrap_or(ConfigFileOptionalValidationLevel::Warn)
+    }
+
+    fn strict_nullability(&self) -> bool {
+        self.strict_nullability.unwrap_or(true)
+    }
+
+    fn watch_debounce_ms(&self) -> u64 {
+        self.watch_debounce_ms.unwrap_or(500)
+    }
+
+    /// Set the options that are optional, but have a default, to that default.
+    fn apply_defaults(&mut self) {
+        self.on_missing_node_id_field = Some(self.on_missing_node_id_field());
+        self.on_unused_client_field = Some(self.on_unused_client_field());
+        self.on_selection_budget_exceeded = Some(self.on_selection_budget_exceeded());
+        self.strict_nullability = Some(self.strict_nullability());
+        self.watch_debounce_ms = Some(self.watch_debounce_ms());
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ConfigFileOptionalValidationLevel {
     /// If this validation error is encountered, it will be ignored
@@ -243,16 +1193,11 @@ pub enum ConfigFileOptionalValidationLevel {
     /// If this validation error is encountered, a warning will be issued
     Warn,
     /// If this validation error is encountered, the compilation will fail
+    #[default]
     Error,
 }
 
-impl Default for ConfigFileOptionalValidationLevel {
-    fn default() -> Self {
-        Self::Error
-    }
-}
-
-#[derive(Deserialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ConfigFileJavascriptModule {
     CommonJs,
@@ -260,7 +1205,58 @@ pub enum ConfigFileJavascriptModule {
     EsModule,
 }
 
-fn create_options(options: ConfigFileOptions) -> CompilerConfigOptions {
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFileNetworkProtocol {
+    #[default]
+    GraphQL,
+    Rest,
+    Grpc,
+    Mixed,
+}
+
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileBuiltInValidationRule {
+    /// Client fields and client pointers may not select deprecated server fields.
+    NoDeprecatedFields,
+    /// The const export of a client field or client pointer must have the same name
+    /// as the client field or client pointer.
+    ExportNameMatchesFieldName,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileArtifactLayout {
+    #[default]
+    Nested,
+    Flat,
+    FolderPerType,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileArtifactPersistence {
+    #[default]
+    FileSystem,
+    Bundle,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFileIsoLiteralExtractor {
+    #[default]
+    Regex,
+    Swc,
+}
+
+fn create_options(
+    options: ConfigFileOptions,
+    config_dir: &Path,
+    current_working_directory: CurrentWorkingDirectory,
+) -> CompilerConfigOptions {
     if let Some(header) = options.generated_file_header.as_ref() {
         let line_count = header.lines().count();
         if line_count > 1 {
@@ -268,19 +1264,150 @@ fn create_options(options: ConfigFileOptions) -> CompilerConfigOptions {
         }
     }
 
+    // Read before the other options are moved out of options.
+    let on_missing_node_id_field =
+        create_optional_validation_level(options.on_missing_node_id_field());
+    let on_unused_client_field = create_optional_validation_level(options.on_unused_client_field());
+    let on_selection_budget_exceeded =
+        create_optional_validation_level(options.on_selection_budget_exceeded());
+    let strict_nullability = options.strict_nullability();
+    let watch_debounce = Duration::from_millis(options.watch_debounce_ms());
+
     let generated_file_header = options.generated_file_header.map(|x| x.intern().into());
 
     CompilerConfigOptions {
         on_invalid_id_type: create_optional_validation_level(options.on_invalid_id_type),
+        on_missing_node_id_field,
+        id_validation_overrides: options
+            .id_validation_overrides
+            .into_iter()
+            .map(|(type_name, validation_level)| {
+                (
+                    type_name.intern().into(),
+                    create_optional_validation_level(validation_level),
+                )
+            })
+            .collect(),
         no_babel_transform: options.no_babel_transform,
         include_file_extensions_in_import_statements: create_generate_file_extensions(
             options.include_file_extensions_in_import_statements,
         ),
         module: create_module(options.module),
         generated_file_header,
+        import_aliases: create_import_aliases(
+            options.import_aliases,
+            config_dir,
+            current_working_directory,
+        ),
+        network_protocol: create_network_protocol(options.network_protocol),
+        artifact_layout: create_artifact_layout(options.artifact_layout),
+        artifact_persistence: create_artifact_persistence(options.artifact_persistence),
+        on_unused_client_field,
+        unused_client_fields_report: options
+            .unused_client_fields_report
+            .map(|report| config_dir.join(report)),
+        prune_unused_client_fields: options.prune_unused_client_fields,
+        max_query_depth: options.max_query_depth,
+        max_query_field_count: options.max_query_field_count,
+        selection_budget: options
+            .selection_budget
+            .map(|max_scalar_field_count| SelectionBudget {
+                max_scalar_field_count,
+                on_exceeded: on_selection_budget_exceeded,
+            }),
+        generate_schema_metadata: options.generate_schema_metadata,
+        generate_combined_schema: options.generate_combined_schema,
+        generate_schema_search_index: options.generate_schema_search_index,
+        generate_entrypoint_metadata: options.generate_entrypoint_metadata,
+        generate_compile_manifest: options.generate_compile_manifest,
+        generate_reader_ast_types: options.generate_reader_ast_types,
+        generate_pagination_fields: options.generate_pagination_fields,
+        generate_artifact_provenance: options.generate_artifact_provenance,
+        skip_invalid_iso_literals: options.skip_invalid_iso_literals,
+        strict_schema: options.strict_schema,
+        implicit_concrete_type_refinements: options.implicit_concrete_type_refinements,
+        watch_debounce,
+        nullable_fields: NullableFieldOptions {
+            strict_nullability,
+            treat_nullable_as_optional_prop: options.treat_nullable_as_optional_prop,
+            semantic_non_null: options.semantic_non_null,
+        },
+        iso_literal_extractor: create_iso_literal_extractor(options.iso_literal_extractor),
+        operation_names: create_operation_name_options(
+            options.operation_name_prefix,
+            options.operation_name_suffix,
+            options.refetch_operation_name_template,
+        ),
+        root_type_names: create_root_type_names(
+            options.query_type_name,
+            options.mutation_type_name,
+        ),
+        propagated_object_directives: options
+            .propagated_object_directives
+            .iter()
+            .map(|directive_name| create_propagated_object_directive(directive_name))
+            .collect(),
+        react_server_components: options.react_server_components,
+        server_field_restrictions: ServerFieldRestrictions {
+            disallowed: options
+                .disallowed_server_fields
+                .iter()
+                .map(|field| parse_type_and_field("disallowed_server_fields", field))
+                .collect(),
+            allowed: options.allowed_server_fields.iter().fold(
+                BTreeMap::<_, BTreeSet<_>>::new(),
+                |mut allowed, field| {
+                    let type_and_field = parse_type_and_field("allowed_server_fields", field);
+                    allowed
+                        .entry(type_and_field.type_name)
+                        .or_default()
+                        .insert(type_and_field.field_name);
+                    allowed
+                },
+            ),
+        },
+        validation_rules: options
+            .validation_rules
+            .into_iter()
+            .map(|(rule, validation_level)| {
+                (
+                    create_built_in_validation_rule(rule),
+                    create_optional_validation_level(validation_level),
+                )
+            })
+            .collect(),
     }
 }
 
+fn create_import_aliases(
+    import_aliases: BTreeMap<String, PathBuf>,
+    config_dir: &Path,
+    current_working_directory: CurrentWorkingDirectory,
+) -> Vec<ImportAlias> {
+    let mut import_aliases = import_aliases
+        .into_iter()
+        .map(|(prefix, directory)| ImportAlias {
+            prefix: prefix.trim_end_matches('/').to_string(),
+            directory: absolute_and_relative_paths(
+                current_working_directory,
+                config_dir
+                    .join(&directory)
+                    .canonicalize()
+                    .unwrap_or_else(|_| {
+                        panic!(
+                            "Unable to canonicalize the directory of import alias {prefix:?}. \
+                        Does {directory:?} exist?"
+                        )
+                    }),
+            ),
+        })
+        .collect::<Vec<_>>();
+    import_aliases.sort_by_key(|import_alias| {
+        std::cmp::Reverse(import_alias.directory.absolute_path.components().count())
+    });
+    import_aliases
+}
+
 fn create_optional_validation_level(
     optional_validation_level: ConfigFileOptionalValidationLevel,
 ) -> OptionalValidationLevel {
@@ -307,6 +1434,129 @@ fn create_module(module: ConfigFileJavascriptModule) -> JavascriptModule {
     }
 }
 
+fn create_network_protocol(network_protocol: ConfigFileNetworkProtocol) -> NetworkProtocolKind {
+    match network_protocol {
+        ConfigFileNetworkProtocol::GraphQL => NetworkProtocolKind::GraphQL,
+        ConfigFileNetworkProtocol::Rest => NetworkProtocolKind::Rest,
+        ConfigFileNetworkProtocol::Grpc => NetworkProtocolKind::Grpc,
+        ConfigFileNetworkProtocol::Mixed => NetworkProtocolKind::Mixed,
+    }
+}
+
+fn parse_type_and_field(option_name: &str, field: &str) -> ObjectTypeAndFieldName {
+    match field.split_once('.') {
+        Some((type_name, field_name))
+            if !type_name.is_empty() && !field_name.is_empty() && !field_name.contains('.') =>
+        {
+            ObjectTypeAndFieldName {
+                type_name: type_name.intern().into(),
+                field_name: field_name.intern().into(),
+            }
+        }
+        _ => panic!(
+            "config.options.{option_name} should contain fields of the form \
+            Type.field, but it contains \"{field}\"."
+        ),
+    }
+}
+
+fn create_built_in_validation_rule(rule: ConfigFileBuiltInValidationRule) -> BuiltInValidationRule {
+    match rule {
+        ConfigFileBuiltInValidationRule::NoDeprecatedFields => {
+            BuiltInValidationRule::NoDeprecatedFields
+        }
+        ConfigFileBuiltInValidationRule::ExportNameMatchesFieldName => {
+            BuiltInValidationRule::ExportNameMatchesFieldName
+        }
+    }
+}
+
+/// The template and affixes have already been checked by validate_config.
+fn create_operation_name_options(
+    prefix: Option<String>,
+    suffix: Option<String>,
+    refetch_template: Option<String>,
+) -> OperationNameOptions {
+    let default = OperationNameOptions::default();
+    OperationNameOptions {
+        prefix: prefix.unwrap_or(default.prefix),
+        suffix: suffix.unwrap_or(default.suffix),
+        refetch_template: refetch_template.unwrap_or(default.refetch_template),
+    }
+}
+
+fn create_root_type_names(
+    query_type_name: Option<String>,
+    mutation_type_name: Option<String>,
+) -> RootTypeNames {
+    let default = RootTypeNames::default();
+    let create_type_name = |option: &str, type_name: Option<String>| {
+        type_name.map(|type_name| {
+            if !is_valid_type_name(&type_name) {
+                panic!(
+                    "config.options.{option} is \"{type_name}\", which is not a valid type name."
+                )
+            }
+            type_name.intern().into()
+        })
+    };
+
+    RootTypeNames {
+        query: create_type_name("query_type_name", query_type_name).unwrap_or(default.query),
+        mutation: create_type_name("mutation_type_name", mutation_type_name)
+            .unwrap_or(default.mutation),
+    }
+}
+
+fn is_valid_type_name(type_name: &str) -> bool {
+    let mut chars = type_name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn create_propagated_object_directive(directive_name: &str) -> DirectiveName {
+    let directive_name = directive_name.strip_prefix('@').unwrap_or(directive_name);
+    if directive_name.is_empty()
+        || !directive_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        panic!(
+            "config.options.propagated_object_directives contains \"{directive_name}\", \
+            which is not the name of a directive."
+        )
+    }
+    directive_name.intern().into()
+}
+
+fn create_iso_literal_extractor(
+    iso_literal_extractor: ConfigFileIsoLiteralExtractor,
+) -> IsoLiteralExtractor {
+    match iso_literal_extractor {
+        ConfigFileIsoLiteralExtractor::Regex => IsoLiteralExtractor::Regex,
+        ConfigFileIsoLiteralExtractor::Swc => IsoLiteralExtractor::Swc,
+    }
+}
+
+fn create_artifact_layout(artifact_layout: ConfigFileArtifactLayout) -> ArtifactLayout {
+    match artifact_layout {
+        ConfigFileArtifactLayout::Nested => ArtifactLayout::Nested,
+        ConfigFileArtifactLayout::Flat => ArtifactLayout::Flat,
+        ConfigFileArtifactLayout::FolderPerType => ArtifactLayout::FolderPerType,
+    }
+}
+
+fn create_artifact_persistence(
+    artifact_persistence: ConfigFileArtifactPersistence,
+) -> ArtifactPersistence {
+    match artifact_persistence {
+        ConfigFileArtifactPersistence::FileSystem => ArtifactPersistence::FileSystem,
+        ConfigFileArtifactPersistence::Bundle => ArtifactPersistence::Bundle,
+    }
+}
+
 pub fn absolute_and_relative_paths(
     current_working_directory: CurrentWorkingDirectory,
     absolute_path: PathBuf,
@@ -0,0 +1,460 @@
+use std::fmt::Display;
+
+use common_lang_types::{closest_match, did_you_mean};
+use schemars::{
+    schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec},
+    schema_for,
+};
+use serde_json::Value;
+
+use crate::{IsographConfigFile, REFETCH_OPERATION_NAME_PLACEHOLDERS};
+
+/// A problem with a config file, e.g. an unknown key or a value of the wrong type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigValidationError {
+    /// The path to the invalid value, e.g. config.options.on_invalid_id_type
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for ConfigValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Validate the contents of a config file against the JSON Schema of the config, so
+/// that every problem is reported at once, along with the path to the invalid value.
+/// Deserializing the config would only report the first problem, without its path.
+pub fn validate_config(config: &Value) -> Vec<ConfigValidationError> {
+    let root_schema = schema_for!(IsographConfigFile);
+    let validator = Validator {
+        root_schema: &root_schema,
+    };
+    // Like create_configs, we check for the presence of the projects key, instead of
+    // reporting the problems with whichever kind of config is closest to being valid.
+    let definition = if config.get("projects").is_some() {
+        "#/definitions/IsographMultiProjectConfig"
+    } else {
+        "#/definitions/IsographProjectConfig"
+    };
+    let mut errors = vec![];
+    validator.validate(
+        config,
+        validator
+            .resolve(definition)
+            .expect("Expected config definition to exist"),
+        "config",
+        &mut errors,
+    );
+
+    match config.get("projects").and_then(Value::as_object) {
+        Some(projects) => {
+            for (project_name, project) in projects {
+                validate_operation_names(
+                    project,
+                    &format!("config.projects.{project_name}"),
+                    &mut errors,
+                );
+            }
+        }
+        None => validate_operation_names(config, "config", &mut errors),
+    }
+    errors
+}
+
+/// Operation names end up in the generated query texts, so the options that make
+/// them up can only contain letters, digits, underscores and (in the template) the
+/// known placeholders. The JSON Schema cannot express this.
+fn validate_operation_names(project: &Value, path: &str, errors: &mut Vec<ConfigValidationError>) {
+    let Some(options) = project.get("options") else {
+        return;
+    };
+    for option in [
+        "operation_name_prefix",
+        "operation_name_suffix",
+        "refetch_operation_name_template",
+    ] {
+        let Some(value) = options.get(option).and_then(Value::as_str) else {
+            continue;
+        };
+        let option_path = format!("{path}.options.{option}");
+        let mut remaining_value = value.to_string();
+        if option == "refetch_operation_name_template" {
+            for placeholder in REFETCH_OPERATION_NAME_PLACEHOLDERS {
+                remaining_value = remaining_value.replace(placeholder, "");
+            }
+            if remaining_value.contains(['{', '}']) {
+                push_error(
+                    errors,
+                    &option_path,
+                    format!(
+                        "Contains an unknown placeholder. The valid placeholders are {}.",
+                        REFETCH_OPERATION_NAME_PLACEHOLDERS.join(", ")
+                    ),
+                );
+                continue;
+            }
+        }
+        if !remaining_value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            push_error(
+                errors,
+                &option_path,
+                "Can only contain letters, digits and underscores.".to_string(),
+            );
+        }
+    }
+}
+
+struct Validator<'a> {
+    root_schema: &'a RootSchema,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(
+        &self,
+        value: &Value,
+        schema: &SchemaObject,
+        path: &str,
+        errors: &mut Vec<ConfigValidationError>,
+    ) {
+        if let Some(reference) = &schema.reference {
+            if let Some(schema) = self.resolve(reference) {
+                self.validate(value, schema, path, errors);
+            }
+            return;
+        }
+
+        if let Some(allowed_values) = self.enum_values(schema) {
+            if !allowed_values.contains(value) {
+                let suggestion = value.as_str().and_then(|value| {
+                    closest_match(value, allowed_values.iter().filter_map(Value::as_str))
+                });
+                push_error(
+                    errors,
+                    path,
+                    format!(
+                        "Expected one of {}, found {}.{}",
+                        allowed_values
+                            .iter()
+                            .map(Value::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        value,
+                        did_you_mean("", suggestion)
+                    ),
+                );
+            }
+            return;
+        }
+
+        if let Some(instance_type) = &schema.instance_type {
+            let instance_types = match instance_type {
+                SingleOrVec::Single(instance_type) => std::slice::from_ref(instance_type.as_ref()),
+                SingleOrVec::Vec(instance_types) => instance_types.as_slice(),
+            };
+            if !instance_types
+                .iter()
+                .any(|instance_type| has_instance_type(value, *instance_type))
+            {
+                push_error(
+                    errors,
+                    path,
+                    format!(
+                        "Expected {}, found {}.",
+                        instance_types
+                            .iter()
+                            .map(|instance_type| describe_instance_type(*instance_type))
+                            .collect::<Vec<_>>()
+                            .join(" or "),
+                        describe_value(value)
+                    ),
+                );
+                return;
+            }
+        }
+
+        if let (Some(minimum), Some(number)) = (
+            schema.number.as_ref().and_then(|number| number.minimum),
+            value.as_f64(),
+        ) {
+            if number < minimum {
+                push_error(
+                    errors,
+                    path,
+                    format!("Expected a number of at least {minimum}, found {value}."),
+                );
+            }
+        }
+
+        if let Some(subschemas) = &schema.subschemas {
+            for subschema in subschemas.all_of.iter().flatten() {
+                self.validate_schema(value, subschema, path, errors);
+            }
+            for alternatives in [&subschemas.any_of, &subschemas.one_of]
+                .into_iter()
+                .flatten()
+            {
+                // Report the problems with the alternative that is closest to being
+                // valid, e.g. with the value of an optional field, rather than
+                // reporting that it is not null.
+                let closest_alternative_errors = alternatives
+                    .iter()
+                    .map(|alternative| {
+                        let mut alternative_errors = vec![];
+                        self.validate_schema(value, alternative, path, &mut alternative_errors);
+                        alternative_errors
+                    })
+                    .min_by_key(Vec::len);
+                errors.extend(closest_alternative_errors.into_iter().flatten());
+            }
+        }
+
+        if let (Some(object_schema), Value::Object(object)) = (&schema.object, value) {
+            for required_key in &object_schema.required {
+                if !object.contains_key(required_key) {
+                    push_error(
+                        errors,
+                        path,
+                        format!("Missing required key `{required_key}`."),
+                    );
+                }
+            }
+            for (key, value) in object {
+                let key_path = format!("{path}.{key}");
+                if let Some(property_schema) = object_schema.properties.get(key) {
+                    self.validate_schema(value, property_schema, &key_path, errors);
+                    continue;
+                }
+                match object_schema.additional_properties.as_deref() {
+                    Some(Schema::Bool(false)) => errors.push(ConfigValidationError {
+                        path: key_path,
+                        message: format!(
+                            "Unknown key `{key}`.{}",
+                            did_you_mean(
+                                "",
+                                closest_match(
+                                    key,
+                                    object_schema.properties.keys().map(String::as_str)
+                                )
+                            )
+                        ),
+                    }),
+                    Some(additional_properties) => {
+                        self.validate_schema(value, additional_properties, &key_path, errors)
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        if let (Some(array_schema), Value::Array(items)) = (&schema.array, value) {
+            if let Some(SingleOrVec::Single(item_schema)) = &array_schema.items {
+                for (index, item) in items.iter().enumerate() {
+                    self.validate_schema(item, item_schema, &format!("{path}[{index}]"), errors);
+                }
+            }
+        }
+    }
+
+    fn validate_schema(
+        &self,
+        value: &Value,
+        schema: &Schema,
+        path: &str,
+        errors: &mut Vec<ConfigValidationError>,
+    ) {
+        if let Schema::Object(schema) = schema {
+            self.validate(value, schema, path, errors);
+        }
+    }
+
+    fn resolve(&self, reference: &str) -> Option<&'a SchemaObject> {
+        match self
+            .root_schema
+            .definitions
+            .get(reference.strip_prefix("#/definitions/")?)?
+        {
+            Schema::Object(schema) => Some(schema),
+            Schema::Bool(_) => None,
+        }
+    }
+
+    /// The values allowed by an enum, which schemars represents either with enum,
+    /// or (if the variants have doc comments) as one_of single-valued enums.
+    fn enum_values(&self, schema: &SchemaObject) -> Option<Vec<Value>> {
+        if let Some(enum_values) = &schema.enum_values {
+            return Some(enum_values.clone());
+        }
+        let alternatives = schema.subschemas.as_ref()?.one_of.as_ref()?;
+        let mut enum_values = vec![];
+        for alternative in alternatives {
+            let Schema::Object(alternative) = alternative else {
+                return None;
+            };
+            let alternative = match &alternative.reference {
+                Some(reference) => self.resolve(reference)?,
+                None => alternative,
+            };
+            enum_values.extend(self.enum_values(alternative)?);
+        }
+        Some(enum_values)
+    }
+}
+
+fn push_error(errors: &mut Vec<ConfigValidationError>, path: &str, message: String) {
+    errors.push(ConfigValidationError {
+        path: path.to_string(),
+        message,
+    });
+}
+
+fn has_instance_type(value: &Value, instance_type: InstanceType) -> bool {
+    match instance_type {
+        InstanceType::Null => value.is_null(),
+        InstanceType::Boolean => value.is_boolean(),
+        InstanceType::Object => value.is_object(),
+        InstanceType::Array => value.is_array(),
+        InstanceType::Number => value.is_number(),
+        InstanceType::String => value.is_string(),
+        InstanceType::Integer => value.is_i64() || value.is_u64(),
+    }
+}
+
+fn describe_instance_type(instance_type: InstanceType) -> &'static str {
+    match instance_type {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "a boolean",
+        InstanceType::Object => "an object",
+        InstanceType::Array => "an array",
+        InstanceType::Number => "a number",
+        InstanceType::String => "a string",
+        InstanceType::Integer => "an integer",
+    }
+}
+
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) | Value::Number(_) | Value::String(_) => value.to_string(),
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn validation_errors(config: Value) -> Vec<String> {
+        validate_config(&config)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_valid_configs() {
+        assert_eq!(
+            validation_errors(json!({
+                "$schema": "./schema.json",
+                "project_root": "./src",
+                "schema": "./schema.graphql",
+                "options": {
+                    "on_invalid_id_type": "error",
+                    "on_unused_client_field": null,
+                    "max_query_depth": 10,
+                    "validation_rules": { "no_deprecated_fields": "warn" },
+                    "import_aliases": { "@src": "./src" }
+                }
+            })),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            validation_errors(json!({
+                "projects": {
+                    "web": { "project_root": "./web", "schema": "./schema.graphql" }
+                }
+            })),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn reports_unknown_keys_with_suggestions() {
+        assert_eq!(
+            validation_errors(json!({
+                "project_root": "./src",
+                "schema": "./schema.graphql",
+                "artifact_directroy": "./generated",
+                "options": { "on_invalid_id_tpe": "error", "something_else": true }
+            })),
+            vec![
+                "config.artifact_directroy: Unknown key `artifact_directroy`. Did you mean `artifact_directory`?",
+                "config.options.on_invalid_id_tpe: Unknown key `on_invalid_id_tpe`. Did you mean `on_invalid_id_type`?",
+                "config.options.something_else: Unknown key `something_else`.",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_values_with_paths() {
+        assert_eq!(
+            validation_errors(json!({
+                "projects": {
+                    "web": {
+                        "project_root": "./web",
+                        "schema_extensions": ["./a.graphql", 1],
+                        "options": {
+                            "no_babel_transform": "yes",
+                            "max_query_depth": -1,
+                            "on_missing_node_id_field": "eror"
+                        }
+                    }
+                }
+            })),
+            vec![
+                "config.projects.web: Missing required key `schema`.",
+                "config.projects.web.options.max_query_depth: Expected a number of at least 0, found -1.",
+                "config.projects.web.options.no_babel_transform: Expected a boolean, found \"yes\".",
+                "config.projects.web.options.on_missing_node_id_field: Expected one of \"ignore\", \"warn\", \"error\", found \"eror\". Did you mean `error`?",
+                "config.projects.web.schema_extensions[1]: Expected a string, found 1.",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_operation_names() {
+        assert_eq!(
+            validation_errors(json!({
+                "projects": {
+                    "web": {
+                        "project_root": "./web",
+                        "schema": "./schema.graphql",
+                        "options": {
+                            "operation_name_prefix": "My-App",
+                            "refetch_operation_name_template": "{entrypoint}__{fieldName}"
+                        }
+                    },
+                    "mobile": {
+                        "project_root": "./mobile",
+                        "schema": "./schema.graphql",
+                        "options": {
+                            "operation_name_suffix": "_Mobile",
+                            "refetch_operation_name_template": "{type}__{entrypoint}__{field}"
+                        }
+                    }
+                }
+            })),
+            vec![
+                "config.projects.web.options.operation_name_prefix: Can only contain letters, digits and underscores.",
+                "config.projects.web.options.refetch_operation_name_template: Contains an unknown placeholder. The valid placeholders are {type}, {entrypoint}, {field}.",
+            ]
+        );
+    }
+}
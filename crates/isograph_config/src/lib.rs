@@ -1,3 +1,5 @@
 mod compilation_options;
+mod config_validation;
 
 pub use compilation_options::*;
+pub use config_validation::*;
@@ -1,10 +1,10 @@
 use std::fs;
 
-use isograph_config::IsographProjectConfig;
+use isograph_config::IsographConfigFile;
 use schemars::schema_for;
 
 fn main() {
-    let schema = schema_for!(IsographProjectConfig);
+    let schema = schema_for!(IsographConfigFile);
 
     fs::write(
         "./libs/isograph-compiler/isograph-config-schema.json",
@@ -0,0 +1,36 @@
+import { iso } from '@iso';
+
+export const ActorGreeting = iso(`
+  field Actor.ActorGreeting {
+    __typename
+    name
+    asUser {
+      email
+    }
+    ... on Bot {
+      version
+    }
+  }
+`)(({ data }) => data.name);
+
+export const ActorRoute = iso(`
+  field Query.ActorRoute {
+    actor {
+      ActorGreeting
+    }
+    node(id: "1") {
+      __typename
+      id
+    }
+  }
+`)(({ data }) => data.actor?.ActorGreeting);
+
+export const ActorRouteEntrypoint = iso(`entrypoint Query.ActorRoute`);
+
+export const SearchResultKind = iso(`
+  field Query.SearchResultKind {
+    search {
+      kind: __typename
+    }
+  }
+`)(({ data }) => data.search?.kind);
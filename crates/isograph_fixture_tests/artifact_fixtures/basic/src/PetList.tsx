@@ -0,0 +1,36 @@
+import { iso } from '@iso';
+
+export const PetSummary = iso(`
+  field Pet.PetSummary @component {
+    name
+    nickname
+    bestFriend {
+      name
+    }
+  }
+`)(function PetSummary({ data }) {
+  return data.name;
+});
+
+export const PetList = iso(`
+  field Query.PetList @component {
+    pets {
+      id
+      PetSummary
+    }
+  }
+`)(function PetList({ data }) {
+  return data.pets.length;
+});
+
+export const PetListEntrypoint = iso(`entrypoint Query.PetList`);
+
+export const PetName = iso(`
+  field Query.PetName($id: ID!) {
+    pet(id: $id) {
+      name
+    }
+  }
+`)(({ data }) => data.pet?.name);
+
+export const PetNameEntrypoint = iso(`entrypoint Query.PetName`);
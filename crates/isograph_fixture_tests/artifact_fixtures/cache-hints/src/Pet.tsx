@@ -0,0 +1,32 @@
+import { iso } from '@iso';
+
+export const PetPrice = iso(`
+  field Query.PetPrice {
+    pet {
+      name
+      price
+    }
+  }
+`)(({ data }) => data.pet.price);
+
+export const PetOwner = iso(`
+  field Query.PetOwner {
+    pet {
+      owner {
+        name
+      }
+    }
+  }
+`)(({ data }) => data.pet.owner?.name);
+
+export const Balance = iso(`
+  field Query.Balance {
+    me {
+      balance
+    }
+  }
+`)(({ data }) => data.me?.balance);
+
+iso(`entrypoint Query.PetPrice`);
+iso(`entrypoint Query.PetOwner`);
+iso(`entrypoint Query.Balance`);
@@ -0,0 +1,24 @@
+import { iso } from '@iso';
+
+export const ActorLabel = iso(`
+  field Actor.Label {
+    name
+  }
+`)(({ data }) => data.name);
+
+export const UserLabel = iso(`
+  field User.Label {
+    name
+    email
+  }
+`)(({ data }) => data.name + ' <' + data.email + '>');
+
+export const LabelRoute = iso(`
+  field Query.LabelRoute {
+    actor {
+      Label
+    }
+  }
+`)(({ data }) => data.actor?.Label);
+
+export const LabelRouteEntrypoint = iso(`entrypoint Query.LabelRoute`);
@@ -0,0 +1,21 @@
+import { iso } from '@iso';
+
+export const BestFriend = iso(`
+  pointer User.BestFriend to User {
+    friend(zeta: 1, alpha: "a") {
+      link
+    }
+  }
+`)(({ data }) => data.friend?.link);
+
+export const ViewerBestFriend = iso(`
+  field Query.ViewerBestFriend {
+    viewer {
+      BestFriend {
+        name
+      }
+    }
+  }
+`)(({ data }) => data.viewer?.BestFriend?.name);
+
+export const ViewerBestFriendEntrypoint = iso(`entrypoint Query.ViewerBestFriend`);
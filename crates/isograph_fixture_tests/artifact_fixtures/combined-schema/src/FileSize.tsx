@@ -0,0 +1,8 @@
+import { iso } from '@iso';
+
+export const FileSize = iso(`
+  "The size of the file, in bytes"
+  field File.FileSize {
+    size
+  }
+`)(({ data }) => data.size);
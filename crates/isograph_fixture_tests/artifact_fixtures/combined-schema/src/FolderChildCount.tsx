@@ -0,0 +1,13 @@
+import { iso } from '@iso';
+
+export const FolderChildCount = iso(`
+  """
+  The number of files and folders
+  directly in this folder.
+  """
+  field Folder.FolderChildCount {
+    children {
+      id
+    }
+  }
+`)(({ data }) => data.children.length);
@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+export const PetSummary = iso(`
+  field Query.PetSummary {
+    pet {
+      name
+      barkVolume
+    }
+  }
+`)(({ data }) => data.pet?.barkVolume);
+
+export const PetSummaryEntrypoint = iso(`entrypoint Query.PetSummary`);
@@ -0,0 +1,19 @@
+import { iso } from '@iso';
+
+export const PetSummary = iso(`
+  field Query.PetSummary {
+    pet {
+      name
+      barkVolume
+      livesRemaining
+      owner {
+        name
+      }
+      asCat {
+        name
+      }
+    }
+  }
+`)(({ data }) => data.pet?.barkVolume ?? data.pet?.livesRemaining);
+
+export const PetSummaryEntrypoint = iso(`entrypoint Query.PetSummary`);
@@ -0,0 +1,7 @@
+import { iso } from '@iso';
+
+export const Greeting = iso(`
+  field Query.Greeting {
+    greeting
+  }
+`)(({ data }) => data.greeting.toUpperCase());
@@ -0,0 +1,12 @@
+﻿import { iso } from '@iso';
+
+// Saved by an editor using Latin-1: caf
+export const PetName = iso(`
+  field Query.PetName($id: ID!) {
+    pet(id: $id) {
+      name
+    }
+  }
+`)(({ data }) => data.pet?.name);
+
+export const PetNameEntrypoint = iso(`entrypoint Query.PetName`);
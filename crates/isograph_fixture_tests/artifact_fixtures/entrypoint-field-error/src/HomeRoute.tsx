@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+export const HomeRoute = iso(`
+  field Query.HomeRoute {
+    pet {
+      name
+    }
+  }
+`)(({ data }) => data);
+
+iso(`entrypoint Query.HomeRout`);
+iso(`entrypoint Query.pet`);
@@ -0,0 +1,15 @@
+import { iso } from '@iso';
+
+export const PetUpdater = iso(`
+  field Query.PetUpdater($id: ID!) @component {
+    pet(id: $id) {
+      tagline
+      set_pet_tagline
+      set_pet_owner
+    }
+  }
+`)(function PetUpdater({ data }) {
+  return data.pet?.tagline;
+});
+
+export const PetUpdaterEntrypoint = iso(`entrypoint Query.PetUpdater`);
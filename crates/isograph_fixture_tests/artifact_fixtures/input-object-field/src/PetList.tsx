@@ -0,0 +1,9 @@
+import { iso } from '@iso';
+
+export const PetList = iso(`
+  field Query.PetList {
+    lastFilter {
+      name
+    }
+  }
+`)(({ data }) => data);
@@ -0,0 +1,7 @@
+import { iso } from '@iso';
+
+export const FilterName = iso(`
+  field PetFilter.FilterName {
+    name
+  }
+`)(({ data }) => data.name);
@@ -0,0 +1,9 @@
+import { iso } from '@iso';
+
+export const NamedRoute = iso(`
+  field Query.NamedRoute {
+    named {
+      name
+    }
+  }
+`)(() => null);
@@ -0,0 +1,15 @@
+import { iso } from '@iso';
+
+export const PetLink = iso(`
+  field Query.PetLink($id: ID!) {
+    pet(id: $id) {
+      link
+      name
+    }
+    node(id: $id) {
+      link
+    }
+  }
+`)(({ data }) => [data.pet?.link, data.node?.link]);
+
+export const PetLinkEntrypoint = iso(`entrypoint Query.PetLink`);
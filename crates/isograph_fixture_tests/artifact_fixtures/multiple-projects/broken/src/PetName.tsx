@@ -0,0 +1,7 @@
+import { iso } from '@iso';
+
+export const PetName = iso(`
+  field Pet.PetName {
+    nmae
+  }
+`)(({ data }) => data.nmae);
@@ -0,0 +1,31 @@
+import { iso } from '@iso';
+
+export const FileSize = iso(`
+  field File.FileSize {
+    size
+    __refetch
+  }
+`)(({ data }) => data.size);
+
+export const ResourcesRoute = iso(`
+  field Query.ResourcesRoute {
+    resources {
+      name
+      asFile {
+        FileSize
+      }
+    }
+    node(id: "1") {
+      asFile {
+        name
+      }
+      asFolder {
+        children {
+          name
+        }
+      }
+    }
+  }
+`)(({ data }) => data.resources.length);
+
+export const ResourcesRouteEntrypoint = iso(`entrypoint Query.ResourcesRoute`);
@@ -0,0 +1,16 @@
+import { iso } from '@iso';
+
+iso(`
+  pointer Query.selectedUser to User
+`);
+
+export const UserRoute = iso(`
+  field Query.UserRoute($userId: ID!) {
+    selectedUser(id: $userId) {
+      name
+      email
+    }
+  }
+`)(({ data }) => data.selectedUser.name);
+
+export const UserRouteEntrypoint = iso(`entrypoint Query.UserRoute`);
@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+export const PetLabels = iso(`
+  field Query.PetLabels @plural(path: "pets") {
+    pets {
+      name
+      age
+    }
+  }
+`)(({ data }) => data.pets.map((pet) => `${pet.name} (${pet.age})`));
+
+export const PetLabelsEntrypoint = iso(`entrypoint Query.PetLabels`);
@@ -0,0 +1,21 @@
+import { iso } from '@iso';
+
+export const PetName = iso(`
+  field QueryRoot.PetName($id: ID!) {
+    pet(id: $id) {
+      name
+    }
+  }
+`)(({ data }) => data.pet?.name);
+
+export const PetNameEntrypoint = iso(`entrypoint QueryRoot.PetName`);
+
+export const SetPetName = iso(`
+  field MutationRoot.SetPetName($id: ID!, $name: String!) {
+    setPetName(id: $id, name: $name) {
+      name
+    }
+  }
+`)(({ data }) => data.setPetName?.name);
+
+export const SetPetNameEntrypoint = iso(`entrypoint MutationRoot.SetPetName`);
@@ -0,0 +1,11 @@
+import { iso } from '@iso';
+
+export const PetDates = iso(`
+  field Query.PetDates {
+    pet {
+      name
+      adoptedAt
+      birthday
+    }
+  }
+`)(({ data }) => data.pet.name);
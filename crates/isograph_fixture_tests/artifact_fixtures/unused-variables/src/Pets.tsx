@@ -0,0 +1,25 @@
+import { iso } from '@iso';
+
+export const PetFriends = iso(`
+  field Pet.PetFriends($friendCount: Int!) {
+    friends(first: $friendCount) {
+      name
+    }
+  }
+`)(({ data }) => data.friends.length);
+
+export const PetsRoute = iso(`
+  field Query.PetsRoute($species: String!, $locale: String, $friendCount: Int!) {
+    pets(species: [$species]) {
+      name
+      PetFriends(friendCount: $friendCount) @loadable
+    }
+    node(id: "1") {
+      asPet {
+        nickname(locale: $locale)
+      }
+    }
+  }
+`)(({ data }) => data.pets.length);
+
+export const PetsRouteEntrypoint = iso(`entrypoint Query.PetsRoute`);
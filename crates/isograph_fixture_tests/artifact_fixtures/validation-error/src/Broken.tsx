@@ -0,0 +1,12 @@
+import { iso } from '@iso';
+
+export const Broken = iso(`
+  field Query.Broken {
+    pets {
+      nmae
+    }
+    pet {
+      name
+    }
+  }
+`)(({ data }) => data);
@@ -0,0 +1,25 @@
+import { iso } from '@iso';
+
+export const PetFriends = iso(`
+  field Pet.PetFriends($friendCount: Int! = 3) {
+    friends(first: $friendCount) {
+      name
+    }
+  }
+`)(({ data }) => data.friends.length);
+
+export const PetsRoute = iso(`
+  field Query.PetsRoute($first: Int! = 10, $species: String = "dog") {
+    pets(first: $first, species: $species) {
+      name
+      PetFriends @loadable
+    }
+    node(id: "1") {
+      asPet {
+        PetFriends
+      }
+    }
+  }
+`)(({ data }) => data.pets.length);
+
+export const PetsRouteEntrypoint = iso(`entrypoint Query.PetsRoute`);
@@ -123,6 +123,7 @@ fn generate_content_for_output_file(
         relative_path_to_source_file,
         &content,
         config.current_working_directory,
+        config.options.iso_literal_extractor,
     ) {
         Ok(item) => {
             let item: Result<_, ()> = Ok(item);
@@ -0,0 +1,269 @@
+//! Each folder in artifact_fixtures is a mini-project, containing a schema.graphql
+//! file and a src folder with iso literals. The project is compiled in memory, and
+//! every generated artifact (or, if compilation fails, every diagnostic) is compared
+//! against the artifacts.snapshot file in that folder. If the folder contains an
+//! isograph.config.json file, it is used instead. If it has multiple projects, each
+//! project is compiled, and the snapshot contains the output of each project.
+//!
+//! Run with UPDATE_SNAPSHOTS=1 to write the snapshots instead of comparing them.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common_lang_types::{AbsolutePathAndRelativePath, CurrentWorkingDirectory};
+use graphql_network_protocol::GraphQLNetworkProtocol;
+use intern::string_key::Intern;
+use isograph_compiler::{
+    compile_with_persistence, Diagnostic, InMemoryArtifacts, InMemoryFileSystem, NoopReporter,
+};
+use isograph_config::{
+    absolute_and_relative_paths, create_configs, CompilerConfig, SourceFileFilter,
+};
+
+const FIXTURES_FOLDER: &str = "artifact_fixtures";
+const SNAPSHOT_FILE_NAME: &str = "artifacts.snapshot";
+const SCHEMA_FILE_NAME: &str = "schema.graphql";
+const CONFIG_FILE_NAME: &str = "isograph.config.json";
+const SOURCE_FOLDER: &str = "src";
+const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+/// The folder at which each fixture is mounted in the in-memory file system, so
+/// that snapshots do not depend on where the repository is checked out.
+const PROJECT_FOLDER: &str = "/fixture";
+
+#[test]
+fn artifact_snapshots() {
+    let fixtures_folder = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURES_FOLDER);
+    let update_snapshots = std::env::var(UPDATE_SNAPSHOTS).is_ok_and(|value| value == "1");
+
+    let mut fixtures = fs::read_dir(&fixtures_folder)
+        .expect("Expected artifact_fixtures to be readable")
+        .map(|entry| entry.expect("Expected entry to be readable").path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "Expected at least one fixture.");
+
+    let mut mismatched_fixtures = vec![];
+    for fixture in fixtures {
+        let actual = compile_fixture(&fixture);
+        let snapshot_path = fixture.join(SNAPSHOT_FILE_NAME);
+        if update_snapshots {
+            fs::write(&snapshot_path, actual)
+                .unwrap_or_else(|_| panic!("Failed to write to {snapshot_path:?}"));
+            continue;
+        }
+
+        let expected = fs::read_to_string(&snapshot_path).unwrap_or_default();
+        if expected != actual {
+            mismatched_fixtures.push(format!(
+                "{}\n{}",
+                fixture.display(),
+                first_difference(&expected, &actual)
+            ));
+        }
+    }
+
+    assert!(
+        mismatched_fixtures.is_empty(),
+        "The artifacts of the following fixtures do not match their snapshots. \
+        If the changes are expected, re-run with {UPDATE_SNAPSHOTS}=1.\n\n{}",
+        mismatched_fixtures.join("\n\n")
+    );
+}
+
+/// Compile the fixture, and print its artifacts, sorted by path, or its
+/// diagnostics if it fails to compile.
+fn compile_fixture(fixture: &Path) -> String {
+    let fs = InMemoryFileSystem::new();
+    let project_folder = PathBuf::from(PROJECT_FOLDER);
+    copy_folder_into(&fs, fixture, &project_folder);
+
+    let mut configs = fixture_configs(fixture, &project_folder);
+    if configs.len() == 1 {
+        return compile_project(&fs, &configs.pop().expect("Expected config to exist"));
+    }
+
+    // Each project is compiled independently, so one project failing to compile
+    // does not affect the others.
+    configs
+        .iter()
+        .map(|config| {
+            format!(
+                "##### project {} #####\n{}",
+                config.project_name.as_deref().unwrap_or_default(),
+                compile_project(&fs, config)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn compile_project(fs: &InMemoryFileSystem, config: &CompilerConfig) -> String {
+    let mut artifacts = InMemoryArtifacts::new();
+    let result = compile_with_persistence::<GraphQLNetworkProtocol>(
+        config,
+        fs,
+        &mut artifacts,
+        &mut NoopReporter,
+    );
+
+    if !result.succeeded() {
+        return result
+            .diagnostics
+            .iter()
+            .map(print_diagnostic)
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    artifacts
+        .artifacts()
+        .iter()
+        .map(|(relative_path, content)| format!("=== {} ===\n{}", relative_path.display(), content))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn copy_folder_into(fs: &InMemoryFileSystem, folder: &Path, target: &Path) {
+    for entry in fs::read_dir(folder).expect("Expected fixture to be readable") {
+        let path = entry.expect("Expected entry to be readable").path();
+        let file_name = path.file_name().expect("Expected path to have a file name");
+        if path.is_dir() {
+            copy_folder_into(fs, &path, &target.join(file_name));
+        } else if file_name != SNAPSHOT_FILE_NAME {
+            let content =
+                fs::read(&path).unwrap_or_else(|_| panic!("Expected {path:?} to be readable"));
+            fs.add_file(target.join(file_name), content);
+        }
+    }
+}
+
+/// The configs of the fixture's projects. If the fixture has a config file, it is
+/// read from disk, and its paths are moved into the project folder.
+fn fixture_configs(fixture: &Path, project_folder: &Path) -> Vec<CompilerConfig> {
+    let current_working_directory: CurrentWorkingDirectory = project_folder
+        .to_str()
+        .expect("Expected path to be stringable")
+        .intern()
+        .into();
+    let config_location = fixture.join(CONFIG_FILE_NAME);
+    if !config_location.exists() {
+        return vec![default_fixture_config(
+            project_folder,
+            current_working_directory,
+        )];
+    }
+
+    let fixture = fixture
+        .canonicalize()
+        .expect("Expected fixture to be canonicalizable");
+    let move_into_project_folder = |path: &Path| {
+        project_folder.join(
+            path.strip_prefix(&fixture)
+                .unwrap_or_else(|_| panic!("Expected {path:?} to be in the fixture")),
+        )
+    };
+    let move_into_project_folder_with_relative_path = |path: &AbsolutePathAndRelativePath| {
+        absolute_and_relative_paths(
+            current_working_directory,
+            move_into_project_folder(&path.absolute_path),
+        )
+    };
+
+    create_configs(
+        config_location,
+        fixture
+            .to_str()
+            .expect("Expected path to be stringable")
+            .intern()
+            .into(),
+    )
+    .into_iter()
+    .map(|config| {
+        let project_root = move_into_project_folder(&config.project_root);
+        CompilerConfig {
+            config_location: project_folder.join(CONFIG_FILE_NAME),
+            project_name: config.project_name,
+            source_file_filter: SourceFileFilter::include_all(project_root.clone()),
+            project_root,
+            artifact_directory: move_into_project_folder_with_relative_path(
+                &config.artifact_directory,
+            ),
+            schema: move_into_project_folder_with_relative_path(&config.schema),
+            schema_extensions: config
+                .schema_extensions
+                .iter()
+                .map(move_into_project_folder_with_relative_path)
+                .collect(),
+            entrypoints: config.entrypoints,
+            options: config.options,
+            current_working_directory,
+            keep_tmp_on_failure: false,
+        }
+    })
+    .collect()
+}
+
+fn default_fixture_config(
+    project_folder: &Path,
+    current_working_directory: CurrentWorkingDirectory,
+) -> CompilerConfig {
+    let project_root = project_folder.join(SOURCE_FOLDER);
+
+    CompilerConfig {
+        config_location: project_folder.join(CONFIG_FILE_NAME),
+        project_name: None,
+        project_root: project_root.clone(),
+        source_file_filter: SourceFileFilter::include_all(project_root.clone()),
+        artifact_directory: absolute_and_relative_paths(
+            current_working_directory,
+            project_root.join("__isograph"),
+        ),
+        schema: absolute_and_relative_paths(
+            current_working_directory,
+            project_folder.join(SCHEMA_FILE_NAME),
+        ),
+        schema_extensions: vec![],
+        entrypoints: vec![],
+        options: Default::default(),
+        current_working_directory,
+        keep_tmp_on_failure: false,
+    }
+}
+
+/// Print a diagnostic without its source text, which would be read from disk.
+fn print_diagnostic(diagnostic: &Diagnostic) -> String {
+    let code = diagnostic
+        .code
+        .as_ref()
+        .map(|code| format!("[{code}]"))
+        .unwrap_or_default();
+    let location = match (&diagnostic.file, diagnostic.span) {
+        (Some(file), Some(span)) => format!("\n  at {file}:{}-{}", span.start, span.end),
+        (Some(file), None) => format!("\n  at {file}"),
+        _ => String::new(),
+    };
+    format!("error{code}: {}{location}\n", diagnostic.message)
+}
+
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line_number = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return "The snapshots differ in trailing whitespace.".to_string(),
+            (expected_line, actual_line) if expected_line != actual_line => {
+                return format!(
+                    "First difference at line {line_number}:\n  expected: {}\n  actual:   {}",
+                    expected_line.unwrap_or("<end of snapshot>"),
+                    actual_line.unwrap_or("<end of artifacts>"),
+                );
+            }
+            _ => line_number += 1,
+        }
+    }
+}
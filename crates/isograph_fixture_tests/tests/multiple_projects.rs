@@ -0,0 +1,62 @@
+//! Compiles the multiple-projects fixture on disk, as `isograph compile` does, to
+//! check that a project that fails to compile does not prevent the other projects
+//! from writing their artifacts, and that the failed projects are reported.
+
+use std::{fs, path::Path};
+
+use graphql_network_protocol::GraphQLNetworkProtocol;
+use intern::string_key::Intern;
+use isograph_compiler::{batch_compile::BatchCompileError, compile_and_print, OutputFormat};
+
+const FIXTURE: &str = "artifact_fixtures/multiple-projects";
+
+#[test]
+fn failed_projects_do_not_prevent_other_projects_from_compiling() {
+    let project_folder = std::env::temp_dir().join("isograph_multiple_projects");
+    if project_folder.exists() {
+        fs::remove_dir_all(&project_folder).expect("Expected folder to be removable");
+    }
+    copy_folder(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURE),
+        &project_folder,
+    );
+
+    let result = compile_and_print::<GraphQLNetworkProtocol>(
+        project_folder.join("isograph.config.json"),
+        project_folder
+            .to_str()
+            .expect("Expected path to be stringable")
+            .intern()
+            .into(),
+        OutputFormat::Human,
+        false,
+        false,
+    );
+
+    let error = result.expect_err("Expected the broken project to fail to compile");
+    match error.downcast_ref::<BatchCompileError>() {
+        Some(BatchCompileError::ProjectsFailedToCompile { project_names }) => {
+            assert_eq!(project_names, &vec!["broken".to_string()])
+        }
+        _ => panic!("Expected ProjectsFailedToCompile, got {error}"),
+    }
+    assert!(project_folder
+        .join("working/src/__isograph/User/UserName/param_type.ts")
+        .exists());
+    assert!(!project_folder
+        .join("broken/src/__isograph/Pet/PetName/param_type.ts")
+        .exists());
+}
+
+fn copy_folder(folder: &Path, target: &Path) {
+    fs::create_dir_all(target).expect("Expected folder to be creatable");
+    for entry in fs::read_dir(folder).expect("Expected fixture to be readable") {
+        let path = entry.expect("Expected entry to be readable").path();
+        let target = target.join(path.file_name().expect("Expected path to have a file name"));
+        if path.is_dir() {
+            copy_folder(&path, &target);
+        } else {
+            fs::copy(&path, &target).unwrap_or_else(|_| panic!("Expected {path:?} to be copyable"));
+        }
+    }
+}
@@ -0,0 +1,4 @@
+export const BasicEntrypoint = iso(`
+  "Entrypoints cannot have descriptions"
+  entrypoint Type.Name
+`);
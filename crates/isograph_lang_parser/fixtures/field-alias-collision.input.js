@@ -0,0 +1,11 @@
+export const BasicField = iso(`
+  field Type.Name {
+    name: firstName
+    name
+    pet {
+      id
+      id: name
+    }
+    nickname: name
+  }
+`)();
@@ -0,0 +1,5 @@
+export const ValidArgs = iso(`
+  field Type.Name {
+    args(arg1: 3.14, arg2: -1e10)
+  }
+`)();
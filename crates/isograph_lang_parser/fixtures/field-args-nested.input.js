@@ -0,0 +1,5 @@
+export const ValidArgs = iso(`
+  field Type.Name {
+    args(arg1: [{ a: [1, $var], b: { c: ENUM } }, "str", null])
+  }
+`)();
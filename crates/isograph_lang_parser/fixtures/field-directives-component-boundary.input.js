@@ -0,0 +1,9 @@
+export const ServerField = iso(`
+  field Type.ServerField @component(boundary: server) {
+  }
+`)();
+
+export const ClientField = iso(`
+  field Type.ClientField @component(boundary: "client") {
+  }
+`)();
@@ -0,0 +1,8 @@
+export const BasicField = iso(`
+  field Type.Name {
+    scalar @include
+    linked @skip(if: "yes") {
+      id
+    }
+  }
+`)();
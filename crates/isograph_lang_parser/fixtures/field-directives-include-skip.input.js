@@ -0,0 +1,8 @@
+export const BasicField = iso(`
+  field Type.Name {
+    scalar @include(if: $showScalar)
+    linked @skip(if: true) {
+      id
+    }
+  }
+`)();
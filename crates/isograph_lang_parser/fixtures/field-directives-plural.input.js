@@ -0,0 +1,7 @@
+export const PetNames = iso(`
+  field Query.PetNames @plural(path: "pets") {
+    pets {
+      name
+    }
+  }
+`)();
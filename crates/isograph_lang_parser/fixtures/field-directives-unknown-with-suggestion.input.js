@@ -0,0 +1,8 @@
+export const BasicField = iso(`
+  field Type.Name {
+    scalar @loadabel(lazyLoadArtifact: true)
+    linked @updatabel {
+      id
+    }
+  }
+`)();
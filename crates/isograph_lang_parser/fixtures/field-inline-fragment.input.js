@@ -0,0 +1,8 @@
+export const BasicField = iso(`
+  field Actor.ActorGreeting {
+    login
+    ... on User {
+      twitterUsername
+    }
+  }
+`)();
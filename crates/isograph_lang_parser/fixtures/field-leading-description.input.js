@@ -0,0 +1,9 @@
+export const BasicField = iso(`
+  """
+  A field with a description
+  before the field keyword.
+  """
+  field Type.Name {
+    id
+  }
+`)();
@@ -0,0 +1,6 @@
+export const BasicField = iso(`
+  "Leading description"
+  field Type.Name "Trailing description" {
+    id
+  }
+`)();
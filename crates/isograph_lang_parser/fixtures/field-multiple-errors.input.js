@@ -0,0 +1,10 @@
+export const BasicField = iso(`
+  field Type.Name {
+    pet(id 1) {
+      name
+    }
+    name nickname
+    id
+    id
+  }
+`)();
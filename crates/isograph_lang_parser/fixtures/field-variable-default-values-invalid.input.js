@@ -0,0 +1,7 @@
+export const InvalidDefault = iso(`
+  field Query.Search($first: Int = $other) {
+    search(first: $first) {
+      id
+    }
+  }
+`)();
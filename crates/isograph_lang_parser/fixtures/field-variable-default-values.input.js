@@ -0,0 +1,7 @@
+export const BasicField = iso(`
+  field Query.Search($first: Int = 10, $query: String! = "pets", $ids: [ID!] = ["1", "2"], $after: String = null) {
+    search(first: $first, query: $query, ids: $ids, after: $after) {
+      id
+    }
+  }
+`)();
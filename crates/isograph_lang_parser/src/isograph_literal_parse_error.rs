@@ -1,4 +1,7 @@
-use common_lang_types::{SelectableName, SelectableNameOrAlias, WithLocation, WithSpan};
+use common_lang_types::{
+    did_you_mean, error_codes, ErrorCode, HasErrorCode, IsographDirectiveName, SelectableName,
+    SelectableNameOrAlias, WithLocation, WithSpan,
+};
 use isograph_lang_types::DeserializationError;
 use thiserror::Error;
 
@@ -47,7 +50,7 @@ pub enum IsographLiteralParseError {
     #[error("Found a variable, like $foo, in a context where variables are not allowed")]
     UnexpectedVariable,
 
-    #[error("Descriptions are currently disallowed")]
+    #[error("Entrypoints cannot have descriptions")]
     DescriptionsAreDisallowed,
 
     #[error("Expected a comma, linebreak or closing curly brace")]
@@ -66,16 +69,15 @@ pub enum IsographLiteralParseError {
     ExpectedParenthesesAroundIsoLiteral,
 
     #[error(
-        "A field with name or alias `{name_or_alias}` has already been defined in \
-        this client field declaration"
+        "A field with name or alias `{name_or_alias}` has already been selected in \
+        this selection set, as `{previous_selection}`. Give one of these selections \
+        a different alias."
     )]
     DuplicateNameOrAlias {
         name_or_alias: SelectableNameOrAlias,
+        previous_selection: String,
     },
 
-    #[error("Expected a boolean value (true or false).")]
-    ExpectedBoolean,
-
     #[error("Expected delimited `{delimiter} or `{closing_token}`")]
     ExpectedDelimiterOrClosingToken {
         closing_token: IsographLangTokenKind,
@@ -84,6 +86,30 @@ pub enum IsographLiteralParseError {
 
     #[error("Unable to process directives. Message: {message}")]
     UnableToDeserializeDirectives { message: DeserializationError },
+
+    #[error(
+        "This literal already has a description. Descriptions can be placed before \
+        the `field` or `pointer` keyword, or before the selection set, but not both."
+    )]
+    MultipleDescriptions,
+
+    #[error(
+        "`@{directive_name}` is not supported on {selection_kind}.{}",
+        did_you_mean("@", *suggestion)
+    )]
+    UnknownDirective {
+        directive_name: IsographDirectiveName,
+        selection_kind: &'static str,
+        suggestion: Option<&'static str>,
+    },
+
+    #[error(
+        "`@{directive_name}` must be passed exactly one argument, `if`, whose value \
+        is a variable or a boolean"
+    )]
+    InvalidSelectionCondition {
+        directive_name: IsographDirectiveName,
+    },
 }
 
 impl From<LowLevelParseError> for IsographLiteralParseError {
@@ -91,3 +117,31 @@ impl From<LowLevelParseError> for IsographLiteralParseError {
         IsographLiteralParseError::ParseError { error }
     }
 }
+
+impl HasErrorCode for IsographLiteralParseError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            IsographLiteralParseError::ParseError { .. } => error_codes::IS0001,
+            IsographLiteralParseError::ExpectedTypeAnnotation => error_codes::IS0002,
+            IsographLiteralParseError::LeftoverTokens => error_codes::IS0003,
+            IsographLiteralParseError::ExpectedAssociatedJsFunction => error_codes::IS0004,
+            IsographLiteralParseError::ExpectedFieldOrPointerOrEntrypoint => error_codes::IS0005,
+            IsographLiteralParseError::ExpectedTo => error_codes::IS0006,
+            IsographLiteralParseError::ExpectedLiteralToBeExported { .. } => error_codes::IS0007,
+            IsographLiteralParseError::ExpectedNonConstantValue => error_codes::IS0008,
+            IsographLiteralParseError::UnexpectedVariable => error_codes::IS0009,
+            IsographLiteralParseError::DescriptionsAreDisallowed => error_codes::IS0010,
+            IsographLiteralParseError::ExpectedCommaOrLineBreak => error_codes::IS0011,
+            IsographLiteralParseError::ExpectedSelectionSet => error_codes::IS0012,
+            IsographLiteralParseError::ExpectedParenthesesAroundIsoLiteral => error_codes::IS0013,
+            IsographLiteralParseError::DuplicateNameOrAlias { .. } => error_codes::IS0014,
+            IsographLiteralParseError::ExpectedDelimiterOrClosingToken { .. } => {
+                error_codes::IS0016
+            }
+            IsographLiteralParseError::UnableToDeserializeDirectives { .. } => error_codes::IS0017,
+            IsographLiteralParseError::MultipleDescriptions => error_codes::IS0018,
+            IsographLiteralParseError::UnknownDirective { .. } => error_codes::IS0019,
+            IsographLiteralParseError::InvalidSelectionCondition { .. } => error_codes::IS0020,
+        }
+    }
+}
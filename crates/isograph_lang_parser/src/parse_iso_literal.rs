@@ -1,20 +1,25 @@
 use common_lang_types::{
-    ClientObjectSelectableName, ClientScalarSelectableName, IsoLiteralText, Location,
-    RelativePathToSourceFile, Span, TextSource, UnvalidatedTypeName, ValueKeyName, WithLocation,
-    WithSpan,
+    closest_match, ClientObjectSelectableName, ClientScalarSelectableName, DescriptionValue,
+    IsoLiteralText, Location, RelativePathToSourceFile, SelectableNameOrAlias, Span, TextSource,
+    UnvalidatedTypeName, ValueKeyName, WithLocation, WithSpan,
 };
 use graphql_lang_types::{
-    GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation,
-    GraphQLTypeAnnotation, NameValuePair,
+    FloatValue, GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation,
+    GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation, NameValuePair,
+};
+use intern::{
+    string_key::{Intern, StringKey},
+    Lookup,
 };
-use intern::string_key::{Intern, StringKey};
 use isograph_lang_types::{
     from_isograph_field_directives, ClientFieldDeclaration, ClientPointerDeclaration,
-    ConstantValue, EntrypointDeclaration, IsographFieldDirective, NonConstantValue,
-    ObjectSelection, ScalarSelection, SelectionFieldArgument, SelectionTypeContainingSelections,
-    UnvalidatedSelection, VariableDefinition,
+    ConstantValue, DeserializationError, EntrypointDeclaration, IsographFieldDirective,
+    NonConstantValue, ObjectSelection, ObjectSelectionDirectiveSet, ScalarSelection,
+    ScalarSelectionDirectiveSet, SelectionCondition, SelectionConditionKind,
+    SelectionFieldArgument, SelectionTypeContainingSelections, UnvalidatedSelection,
+    VariableDefinition,
 };
-use std::{collections::HashSet, ops::ControlFlow};
+use std::{collections::HashMap, ops::ControlFlow};
 
 use crate::{
     parse_optional_description, IsographLangTokenKind, IsographLiteralParseError,
@@ -33,21 +38,89 @@ pub fn parse_iso_literal(
     definition_file_path: RelativePathToSourceFile,
     const_export_name: Option<&str>,
     text_source: TextSource,
-) -> Result<IsoLiteralExtractionResult, WithLocation<IsographLiteralParseError>> {
+) -> Result<IsoLiteralExtractionResult, Vec<WithLocation<IsographLiteralParseError>>> {
+    let (iso_literal_extraction_result, errors) = parse_iso_literal_with_recovery(
+        iso_literal_text,
+        definition_file_path,
+        const_export_name,
+        text_source,
+    );
+    match iso_literal_extraction_result {
+        Some(iso_literal_extraction_result) if errors.is_empty() => {
+            Ok(iso_literal_extraction_result)
+        }
+        _ => Err(errors),
+    }
+}
+
+/// Parse an iso literal, continuing after errors where possible. An invalid selection
+/// is skipped until the next comma, line break or closing brace, so every error in
+/// the literal is reported.
+///
+/// Returns a (possibly partial) declaration, unless the parser could not recover,
+/// as well as all of the errors that were encountered. This is useful when the
+/// literal is being edited, e.g. in the language server.
+pub fn parse_iso_literal_with_recovery(
+    iso_literal_text: &str,
+    definition_file_path: RelativePathToSourceFile,
+    const_export_name: Option<&str>,
+    text_source: TextSource,
+) -> (
+    Option<IsoLiteralExtractionResult>,
+    Vec<WithLocation<IsographLiteralParseError>>,
+) {
+    let mut errors = vec![];
+    let result = parse_iso_literal_inner(
+        iso_literal_text,
+        definition_file_path,
+        const_export_name,
+        text_source,
+        &mut errors,
+    );
+    let mut errors = errors
+        .into_iter()
+        .map(|error| error.to_with_location(text_source))
+        .collect::<Vec<_>>();
+    match result {
+        Ok(iso_literal_extraction_result) => (Some(iso_literal_extraction_result), errors),
+        Err(error) => {
+            errors.push(error);
+            (None, errors)
+        }
+    }
+}
+
+fn parse_iso_literal_inner(
+    iso_literal_text: &str,
+    definition_file_path: RelativePathToSourceFile,
+    const_export_name: Option<&str>,
+    text_source: TextSource,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> ParseResultWithLocation<IsoLiteralExtractionResult> {
     let mut tokens = PeekableLexer::new(iso_literal_text);
+    let leading_description = parse_optional_description(&mut tokens);
     let discriminator = tokens
         .parse_source_of_kind(IsographLangTokenKind::Identifier)
         .map_err(|with_span| with_span.map(IsographLiteralParseError::from))
         .map_err(|err| err.to_with_location(text_source))?;
     match discriminator.item {
-        "entrypoint" => Ok(IsoLiteralExtractionResult::EntrypointDeclaration(
-            parse_iso_entrypoint_declaration(
-                &mut tokens,
-                text_source,
-                discriminator.span,
-                iso_literal_text.intern().into(),
-            )?,
-        )),
+        "entrypoint" => {
+            if let Some(description) = leading_description {
+                errors.push(WithSpan::new(
+                    IsographLiteralParseError::DescriptionsAreDisallowed,
+                    description.span,
+                ));
+            }
+            Ok(IsoLiteralExtractionResult::EntrypointDeclaration(
+                parse_iso_entrypoint_declaration(
+                    &mut tokens,
+                    text_source,
+                    discriminator.span,
+                    iso_literal_text.intern().into(),
+                    errors,
+                )?,
+            ))
+        }
         "field" => Ok(IsoLiteralExtractionResult::ClientFieldDeclaration(
             parse_iso_client_field_declaration(
                 &mut tokens,
@@ -55,6 +128,8 @@ pub fn parse_iso_literal(
                 const_export_name,
                 text_source,
                 discriminator.span,
+                leading_description,
+                errors,
             )?,
         )),
         "pointer" => Ok(IsoLiteralExtractionResult::ClientPointerDeclaration(
@@ -64,6 +139,8 @@ pub fn parse_iso_literal(
                 const_export_name,
                 text_source,
                 discriminator.span,
+                leading_description,
+                errors,
             )?,
         )),
         _ => Err(WithLocation::new(
@@ -78,6 +155,7 @@ fn parse_iso_entrypoint_declaration(
     text_source: TextSource,
     entrypoint_keyword: Span,
     iso_literal_text: IsoLiteralText,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
 ) -> ParseResultWithLocation<WithSpan<EntrypointDeclaration>> {
     let entrypoint_declaration = tokens
         .with_span(|tokens| {
@@ -93,16 +171,8 @@ fn parse_iso_entrypoint_declaration(
 
             let directives = parse_directives(tokens, text_source)?;
 
-            let entrypoint_directive_set =
-                from_isograph_field_directives(&directives).map_err(|message| {
-                    WithSpan::new(
-                        IsographLiteralParseError::UnableToDeserializeDirectives { message },
-                        directives
-                            .first()
-                            .map(|x| x.span)
-                            .unwrap_or_else(Span::todo_generated),
-                    )
-                })?;
+            let entrypoint_directive_set = from_isograph_field_directives(&directives)
+                .map_err(|message| unable_to_deserialize_directives(message, &directives))?;
             Ok(EntrypointDeclaration {
                 parent_type,
                 client_field_name,
@@ -115,9 +185,9 @@ fn parse_iso_entrypoint_declaration(
         .map_err(|with_span: WithSpan<_>| with_span.to_with_location(text_source))?;
 
     if let Some(span) = tokens.remaining_token_span() {
-        return Err(WithLocation::new(
+        errors.push(WithSpan::new(
             IsographLiteralParseError::LeftoverTokens,
-            Location::new(text_source, span),
+            span,
         ));
     }
 
@@ -130,6 +200,8 @@ fn parse_iso_client_field_declaration(
     const_export_name: Option<&str>,
     text_source: TextSource,
     field_keyword_span: Span,
+    leading_description: Option<WithSpan<DescriptionValue>>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
 ) -> ParseResultWithLocation<WithSpan<ClientFieldDeclaration>> {
     let client_field_declaration = parse_client_field_declaration_inner(
         tokens,
@@ -137,13 +209,15 @@ fn parse_iso_client_field_declaration(
         const_export_name,
         text_source,
         field_keyword_span,
+        leading_description,
+        errors,
     )
     .map_err(|with_span| with_span.to_with_location(text_source))?;
 
     if let Some(span) = tokens.remaining_token_span() {
-        return Err(WithLocation::new(
+        errors.push(WithSpan::new(
             IsographLiteralParseError::LeftoverTokens,
-            Location::new(text_source, span),
+            span,
         ));
     }
 
@@ -156,6 +230,8 @@ fn parse_client_field_declaration_inner(
     const_export_name: Option<&str>,
     text_source: TextSource,
     field_keyword_span: Span,
+    leading_description: Option<WithSpan<DescriptionValue>>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
 ) -> ParseResultWithSpan<WithSpan<ClientFieldDeclaration>> {
     tokens.with_span(|tokens| {
         let parent_type = tokens
@@ -174,30 +250,25 @@ fn parse_client_field_declaration_inner(
 
         let directives = parse_directives(tokens, text_source)?;
 
-        let client_field_directive_set =
-            from_isograph_field_directives(&directives).map_err(|message| {
-                WithSpan::new(
-                    IsographLiteralParseError::UnableToDeserializeDirectives { message },
-                    directives
-                        .first()
-                        .map(|x| x.span)
-                        .unwrap_or_else(Span::todo_generated),
-                )
-            })?;
+        let client_field_directive_set = from_isograph_field_directives(&directives)
+            .map_err(|message| unable_to_deserialize_directives(message, &directives))?;
 
-        let description = parse_optional_description(tokens);
+        let description = parse_description(tokens, leading_description, errors);
 
-        let selection_set = parse_selection_set(tokens, text_source)?;
+        let selection_set = parse_selection_set(tokens, text_source, errors);
 
-        let const_export_name = const_export_name.ok_or_else(|| {
-            WithSpan::new(
+        // If the literal is not exported, we continue as if it were exported under
+        // the suggested name.
+        let const_export_name = const_export_name.unwrap_or_else(|| {
+            errors.push(WithSpan::new(
                 IsographLiteralParseError::ExpectedLiteralToBeExported {
                     literal_type: "field".to_string(),
                     suggested_const_export_name: client_field_name.item.into(),
                 },
-                Span::todo_generated(),
-            )
-        })?;
+                client_field_name.span,
+            ));
+            client_field_name.item.lookup()
+        });
 
         Ok(ClientFieldDeclaration {
             parent_type,
@@ -220,6 +291,8 @@ fn parse_iso_client_pointer_declaration(
     const_export_name: Option<&str>,
     text_source: TextSource,
     field_keyword_span: Span,
+    leading_description: Option<WithSpan<DescriptionValue>>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
 ) -> ParseResultWithLocation<WithSpan<ClientPointerDeclaration>> {
     let client_pointer_declaration = parse_client_pointer_declaration_inner(
         tokens,
@@ -227,13 +300,15 @@ fn parse_iso_client_pointer_declaration(
         const_export_name,
         text_source,
         field_keyword_span,
+        leading_description,
+        errors,
     )
     .map_err(|with_span| with_span.to_with_location(text_source))?;
 
     if let Some(span) = tokens.remaining_token_span() {
-        return Err(WithLocation::new(
+        errors.push(WithSpan::new(
             IsographLiteralParseError::LeftoverTokens,
-            Location::new(text_source, span),
+            span,
         ));
     }
 
@@ -263,6 +338,8 @@ fn parse_client_pointer_declaration_inner(
     const_export_name: Option<&str>,
     text_source: TextSource,
     pointer_keyword_span: Span,
+    leading_description: Option<WithSpan<DescriptionValue>>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
 ) -> ParseResultWithSpan<WithSpan<ClientPointerDeclaration>> {
     tokens.with_span(|tokens| {
         let parent_type = tokens
@@ -283,19 +360,26 @@ fn parse_client_pointer_declaration_inner(
 
         let directives = parse_directives(tokens, text_source)?;
 
-        let description = parse_optional_description(tokens);
-
-        let selection_set = parse_selection_set(tokens, text_source)?;
-
-        let const_export_name = const_export_name.ok_or_else(|| {
-            WithSpan::new(
-                IsographLiteralParseError::ExpectedLiteralToBeExported {
-                    literal_type: "pointer".to_string(),
-                    suggested_const_export_name: client_pointer_name.item.into(),
-                },
-                Span::todo_generated(),
-            )
-        })?;
+        let description = parse_description(tokens, leading_description, errors);
+
+        // A pointer without a selection set is a node pointer. It has no resolver,
+        // so it need not be exported.
+        let selection_set = parse_optional_selection_set(tokens, text_source, errors);
+
+        // If the literal is not exported, we continue as if it were exported under
+        // the suggested name.
+        let const_export_name = const_export_name.unwrap_or_else(|| {
+            if selection_set.is_some() {
+                errors.push(WithSpan::new(
+                    IsographLiteralParseError::ExpectedLiteralToBeExported {
+                        literal_type: "pointer".to_string(),
+                        suggested_const_export_name: client_pointer_name.item.into(),
+                    },
+                    client_pointer_name.span,
+                ));
+            }
+            client_pointer_name.item.lookup()
+        });
 
         Ok(ClientPointerDeclaration {
             directives,
@@ -313,20 +397,44 @@ fn parse_client_pointer_declaration_inner(
     })
 }
 
-// Note: for now, top-level selection sets are required
-//
-// TODO: perform some refactor to make type easier to read.
+/// Client fields and pointers can have a description before the `field` or `pointer`
+/// keyword, or before the selection set, but not both.
+fn parse_description(
+    tokens: &mut PeekableLexer<'_>,
+    leading_description: Option<WithSpan<DescriptionValue>>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> Option<WithSpan<DescriptionValue>> {
+    match (leading_description, parse_optional_description(tokens)) {
+        (Some(leading_description), Some(description)) => {
+            errors.push(WithSpan::new(
+                IsographLiteralParseError::MultipleDescriptions,
+                description.span,
+            ));
+            Some(leading_description)
+        }
+        (leading_description, description) => leading_description.or(description),
+    }
+}
+
+// Note: for now, top-level selection sets are required. If it is missing, we
+// record an error and continue with an empty selection set.
 fn parse_selection_set(
     tokens: &mut PeekableLexer<'_>,
     text_source: TextSource,
-) -> ParseResultWithSpan<Vec<WithSpan<UnvalidatedSelection>>> {
-    let selection_set = parse_optional_selection_set(tokens, text_source)?;
-    match selection_set {
-        Some(selection_set) => Ok(selection_set),
-        None => Err(WithSpan::new(
-            IsographLiteralParseError::ExpectedSelectionSet,
-            Span::new(0, 0),
-        )),
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> Vec<WithSpan<UnvalidatedSelection>> {
+    match parse_optional_selection_set(tokens, text_source, errors) {
+        Some(selection_set) => selection_set,
+        None => {
+            // Point to the end of the previous token, which is where the selection
+            // set should be inserted.
+            let end_of_previous_token = tokens.white_space_span().start;
+            errors.push(WithSpan::new(
+                IsographLiteralParseError::ExpectedSelectionSet,
+                Span::new(end_of_previous_token, end_of_previous_token),
+            ));
+            vec![]
+        }
     }
 }
 
@@ -334,38 +442,116 @@ fn parse_selection_set(
 fn parse_optional_selection_set(
     tokens: &mut PeekableLexer<'_>,
     text_source: TextSource,
-) -> ParseResultWithSpan<Option<Vec<WithSpan<UnvalidatedSelection>>>> {
-    let open_brace: Result<WithSpan<IsographLangTokenKind>, WithSpan<crate::LowLevelParseError>> =
-        tokens.parse_token_of_kind(IsographLangTokenKind::OpenBrace);
-    if open_brace.is_err() {
-        return Ok(None);
-    }
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> Option<Vec<WithSpan<UnvalidatedSelection>>> {
+    tokens
+        .parse_token_of_kind(IsographLangTokenKind::OpenBrace)
+        .ok()?;
 
-    let mut encountered_names_or_aliases = HashSet::new();
+    let mut encountered_names_or_aliases: HashMap<SelectableNameOrAlias, String> = HashMap::new();
     let mut selections = vec![];
-    while tokens
-        .parse_token_of_kind(IsographLangTokenKind::CloseBrace)
-        .is_err()
-    {
-        let selection = parse_selection(tokens, text_source)?;
+    loop {
+        match tokens.parse_token_of_kind(IsographLangTokenKind::CloseBrace) {
+            Ok(_) => break,
+            Err(error) if tokens.reached_eof() => {
+                errors.push(error.map(IsographLiteralParseError::from));
+                break;
+            }
+            Err(_) => {}
+        }
+
+        let selection = match parse_selection(tokens, text_source, errors) {
+            Ok(Some(selection)) => selection,
+            // The selection was invalid, but the error has already been recorded
+            // and the selection has been consumed.
+            Ok(None) => continue,
+            Err(error) => {
+                errors.push(error);
+                if tokens.reached_eof() {
+                    break;
+                }
+                skip_to_next_selection(tokens);
+                continue;
+            }
+        };
+
         let selection_name_or_alias = selection.item.name_or_alias().item;
-        if !encountered_names_or_aliases.insert(selection_name_or_alias) {
+        if let Some(previous_selection) = encountered_names_or_aliases.get(&selection_name_or_alias)
+        {
             // We have already encountered this name or alias, so we emit
-            // an error.
+            // an error and drop the selection.
             // TODO should SelectionSet be a HashMap<SelectableNameOrAlias, ...> instead of
             // a Vec??
-            // TODO find a way to include the location of the previous field with matching
-            // name or alias
-            return Err(WithSpan::new(
+            errors.push(WithSpan::new(
                 IsographLiteralParseError::DuplicateNameOrAlias {
                     name_or_alias: selection_name_or_alias,
+                    previous_selection: previous_selection.clone(),
                 },
                 selection.span,
             ));
+            continue;
         }
+        encountered_names_or_aliases
+            .insert(selection_name_or_alias, selection_text(&selection.item));
         selections.push(selection);
     }
-    Ok(Some(selections))
+    Some(selections)
+}
+
+/// How a selection is printed in error messages, e.g. `name` or `firstName: name`.
+fn selection_text(selection: &UnvalidatedSelection) -> String {
+    let (name, reader_alias): (SelectableNameOrAlias, _) = match selection {
+        SelectionTypeContainingSelections::Scalar(scalar_selection) => (
+            scalar_selection.name.item.into(),
+            scalar_selection.reader_alias,
+        ),
+        SelectionTypeContainingSelections::Object(object_selection) => (
+            object_selection.name.item.into(),
+            object_selection.reader_alias,
+        ),
+    };
+    match reader_alias {
+        Some(alias) => format!("{}: {}", alias.item, name),
+        None => name.to_string(),
+    }
+}
+
+/// After an error in a selection, skip tokens until the probable start of the next
+/// selection, i.e. until after a comma, or until a line break or a closing brace.
+/// Anything nested in braces, brackets or parentheses is skipped entirely.
+///
+/// At least one token is skipped, so that we always make progress.
+fn skip_to_next_selection(tokens: &mut PeekableLexer<'_>) {
+    let mut depth = 0usize;
+    let mut skipped_a_token = false;
+    loop {
+        let next_token = tokens.peek().item;
+        let at_top_level = depth == 0;
+        match next_token {
+            IsographLangTokenKind::EndOfFile => return,
+            IsographLangTokenKind::CloseBrace if at_top_level => return,
+            _ if at_top_level
+                && skipped_a_token
+                && tokens.source(tokens.white_space_span()).contains('\n') =>
+            {
+                return
+            }
+            _ => {}
+        }
+
+        tokens.parse_token();
+        skipped_a_token = true;
+        match next_token {
+            IsographLangTokenKind::Comma if at_top_level => return,
+            IsographLangTokenKind::OpenBrace
+            | IsographLangTokenKind::OpenBracket
+            | IsographLangTokenKind::OpenParen => depth += 1,
+            IsographLangTokenKind::CloseBrace
+            | IsographLangTokenKind::CloseBracket
+            | IsographLangTokenKind::CloseParen => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
 }
 
 /// Parse a list with a delimiter. Expect an optional final delimiter.
@@ -410,7 +596,12 @@ fn parse_comma_line_break_or_curly(tokens: &mut PeekableLexer<'_>) -> ParseResul
     let comma = tokens.parse_token_of_kind(IsographLangTokenKind::Comma);
     if comma.is_ok()
         || tokens.source(tokens.white_space_span()).contains('\n')
-        || matches!(tokens.peek().item, IsographLangTokenKind::CloseBrace)
+        // If we reach the end of the literal, the missing closing brace is reported
+        // by the enclosing selection set.
+        || matches!(
+            tokens.peek().item,
+            IsographLangTokenKind::CloseBrace | IsographLangTokenKind::EndOfFile
+        )
     {
         Ok(())
     } else {
@@ -421,11 +612,19 @@ fn parse_comma_line_break_or_curly(tokens: &mut PeekableLexer<'_>) -> ParseResul
     }
 }
 
+/// Parse a selection. Errors that do not prevent us from continuing with the next
+/// selection (e.g. a missing comma or invalid directives) are added to `errors`. In
+/// the latter case, the selection is consumed and `None` is returned.
 fn parse_selection(
     tokens: &mut PeekableLexer<'_>,
     text_source: TextSource,
-) -> ParseResultWithSpan<WithSpan<UnvalidatedSelection>> {
-    tokens.with_span(|tokens| {
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> ParseResultWithSpan<Option<WithSpan<UnvalidatedSelection>>> {
+    let selection = tokens.with_span(|tokens| {
+        if let Ok(spread) = tokens.parse_token_of_kind(IsographLangTokenKind::Spread) {
+            return parse_inline_fragment(tokens, text_source, errors, spread.span);
+        }
+
         let (field_name, alias) = parse_optional_alias_and_field_name(tokens)?;
         let field_name = field_name.to_with_location(text_source);
         let alias = alias.map(|alias| alias.to_with_location(text_source));
@@ -433,24 +632,31 @@ fn parse_selection(
         let arguments = parse_optional_arguments(tokens, text_source)?;
 
         let directives = parse_directives(tokens, text_source)?;
+        let (conditions, directives) = extract_selection_conditions(directives, errors);
 
         // If we encounter a selection set, we are parsing a linked field. Otherwise, a scalar field.
-        let selection_set = parse_optional_selection_set(tokens, text_source)?;
+        let selection_set = parse_optional_selection_set(tokens, text_source, errors);
 
-        parse_comma_line_break_or_curly(tokens)?;
+        if let Err(error) = parse_comma_line_break_or_curly(tokens) {
+            errors.push(error);
+        }
 
         let selection = match selection_set {
             Some(selection_set) => {
-                let object_selection_directive_set = from_isograph_field_directives(&directives)
-                    .map_err(|message| {
-                        WithSpan::new(
-                            IsographLiteralParseError::UnableToDeserializeDirectives { message },
-                            directives
-                                .first()
-                                .map(|x| x.span)
-                                .unwrap_or_else(Span::todo_generated),
-                        )
-                    })?;
+                let directives = remove_unknown_directives(
+                    directives,
+                    ObjectSelectionDirectiveSet::DIRECTIVE_NAMES,
+                    "linked fields",
+                    errors,
+                );
+                let object_selection_directive_set =
+                    match from_isograph_field_directives(&directives) {
+                        Ok(directive_set) => directive_set,
+                        Err(message) => {
+                            errors.push(unable_to_deserialize_directives(message, &directives));
+                            return Ok(None);
+                        }
+                    };
                 SelectionTypeContainingSelections::Object(ObjectSelection {
                     name: field_name.map(|string_key| string_key.into()),
                     reader_alias: alias
@@ -459,19 +665,24 @@ fn parse_selection(
                     selection_set,
                     arguments,
                     associated_data: (),
+                    conditions,
                 })
             }
             None => {
-                let scalar_selection_directive_set = from_isograph_field_directives(&directives)
-                    .map_err(|message| {
-                        WithSpan::new(
-                            IsographLiteralParseError::UnableToDeserializeDirectives { message },
-                            directives
-                                .first()
-                                .map(|x| x.span)
-                                .unwrap_or_else(Span::todo_generated),
-                        )
-                    })?;
+                let directives = remove_unknown_directives(
+                    directives,
+                    ScalarSelectionDirectiveSet::DIRECTIVE_NAMES,
+                    "scalar fields",
+                    errors,
+                );
+                let scalar_selection_directive_set =
+                    match from_isograph_field_directives(&directives) {
+                        Ok(directive_set) => directive_set,
+                        Err(message) => {
+                            errors.push(unable_to_deserialize_directives(message, &directives));
+                            return Ok(None);
+                        }
+                    };
                 SelectionTypeContainingSelections::Scalar(ScalarSelection {
                     name: field_name.map(|string_key| string_key.into()),
                     reader_alias: alias
@@ -479,11 +690,158 @@ fn parse_selection(
                     associated_data: (),
                     arguments,
                     scalar_selection_directive_set,
+                    conditions,
                 })
             }
         };
-        Ok(selection)
-    })
+        Ok(Some(selection))
+    })?;
+    let span = selection.span;
+    Ok(selection
+        .item
+        .map(|selection| WithSpan::new(selection, span)))
+}
+
+/// Parse an inline fragment (e.g. `... on User { name }`), starting after the spread.
+/// This is equivalent to selecting the `asUser` field, so that is what we produce.
+fn parse_inline_fragment(
+    tokens: &mut PeekableLexer<'_>,
+    text_source: TextSource,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+    spread_span: Span,
+) -> ParseResultWithSpan<Option<UnvalidatedSelection>> {
+    tokens
+        .parse_matching_identifier("on")
+        .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+    let type_to_refine_to = tokens
+        .parse_source_of_kind(IsographLangTokenKind::Identifier)
+        .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+    let field_name = WithSpan::new(
+        format!("as{}", type_to_refine_to.item).intern(),
+        Span::join(spread_span, type_to_refine_to.span),
+    )
+    .to_with_location(text_source);
+
+    let directives = parse_directives(tokens, text_source)?;
+    let (conditions, directives) = extract_selection_conditions(directives, errors);
+
+    let selection_set = parse_selection_set(tokens, text_source, errors);
+
+    if let Err(error) = parse_comma_line_break_or_curly(tokens) {
+        errors.push(error);
+    }
+
+    let directives = remove_unknown_directives(
+        directives,
+        ObjectSelectionDirectiveSet::DIRECTIVE_NAMES,
+        "linked fields",
+        errors,
+    );
+    let object_selection_directive_set = match from_isograph_field_directives(&directives) {
+        Ok(directive_set) => directive_set,
+        Err(message) => {
+            errors.push(unable_to_deserialize_directives(message, &directives));
+            return Ok(None);
+        }
+    };
+    Ok(Some(SelectionTypeContainingSelections::Object(
+        ObjectSelection {
+            name: field_name.map(|string_key| string_key.into()),
+            reader_alias: None,
+            object_selection_directive_set,
+            selection_set,
+            arguments: vec![],
+            associated_data: (),
+            conditions,
+        },
+    )))
+}
+
+/// Remove `@include` and `@skip` from the directives, and return the conditions that
+/// they represent. Invalid conditions are added to `errors` and ignored.
+fn extract_selection_conditions(
+    directives: Vec<WithSpan<IsographFieldDirective>>,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> (
+    Vec<WithLocation<SelectionCondition>>,
+    Vec<WithSpan<IsographFieldDirective>>,
+) {
+    let mut conditions = vec![];
+    let mut other_directives = vec![];
+    for directive in directives {
+        let directive_name = directive.item.name.item;
+        let Some(kind) = SelectionConditionKind::from_directive_name(directive_name.lookup())
+        else {
+            other_directives.push(directive);
+            continue;
+        };
+        match directive.item.arguments.as_slice() {
+            [argument]
+                if argument.item.name.item.lookup() == "if"
+                    && matches!(
+                        argument.item.value.item,
+                        NonConstantValue::Variable(_) | NonConstantValue::Boolean(_)
+                    ) =>
+            {
+                conditions.push(WithLocation::new(
+                    SelectionCondition {
+                        kind,
+                        value: argument.item.value.item.clone(),
+                    },
+                    argument.item.value.location,
+                ));
+            }
+            _ => errors.push(WithSpan::new(
+                IsographLiteralParseError::InvalidSelectionCondition { directive_name },
+                directive.span,
+            )),
+        }
+    }
+    (conditions, other_directives)
+}
+
+/// Record an error for each directive that is not supported on this kind of selection,
+/// and remove it, so that the remaining directives can be processed.
+fn remove_unknown_directives(
+    directives: Vec<WithSpan<IsographFieldDirective>>,
+    known_directive_names: &'static [&'static str],
+    selection_kind: &'static str,
+    errors: &mut Vec<WithSpan<IsographLiteralParseError>>,
+) -> Vec<WithSpan<IsographFieldDirective>> {
+    directives
+        .into_iter()
+        .filter(|directive| {
+            let directive_name = directive.item.name.item;
+            if known_directive_names.contains(&directive_name.lookup()) {
+                return true;
+            }
+            errors.push(WithSpan::new(
+                IsographLiteralParseError::UnknownDirective {
+                    directive_name,
+                    selection_kind,
+                    suggestion: closest_match(
+                        directive_name.lookup(),
+                        known_directive_names.iter().copied(),
+                    ),
+                },
+                directive.span,
+            ));
+            false
+        })
+        .collect()
+}
+
+fn unable_to_deserialize_directives(
+    message: DeserializationError,
+    directives: &[WithSpan<IsographFieldDirective>],
+) -> WithSpan<IsographLiteralParseError> {
+    WithSpan::new(
+        IsographLiteralParseError::UnableToDeserializeDirectives { message },
+        directives
+            .first()
+            .map(|x| x.span)
+            .unwrap_or_else(Span::todo_generated),
+    )
 }
 
 fn parse_optional_alias_and_field_name(
@@ -605,6 +963,42 @@ fn parse_non_constant_value(
             }))
         })?;
 
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let number = tokens
+                .parse_source_of_kind(IsographLangTokenKind::FloatLiteral)
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+            Ok(number.map(|number| {
+                NonConstantValue::Float(FloatValue::new(
+                    number.parse().expect("Expected valid float"),
+                ))
+            }))
+        })?;
+
+        to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
+            let open = tokens
+                .parse_token_of_kind(IsographLangTokenKind::OpenBracket)
+                .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
+
+            let items =
+                parse_delimited_list(
+                    tokens,
+                    move |tokens| {
+                        Ok(parse_non_constant_value(tokens, text_source)?
+                            .to_with_location(text_source))
+                    },
+                    IsographLangTokenKind::Comma,
+                    IsographLangTokenKind::CloseBracket,
+                )?;
+
+            Ok(WithSpan::new(
+                NonConstantValue::List(items.item),
+                Span {
+                    start: open.span.start,
+                    end: items.span.end,
+                },
+            ))
+        })?;
+
         to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
             let open = tokens
                 .parse_token_of_kind(IsographLangTokenKind::OpenBrace)
@@ -627,22 +1021,16 @@ fn parse_non_constant_value(
         })?;
 
         to_control_flow::<_, WithSpan<IsographLiteralParseError>>(|| {
-            let bool_or_null = tokens
+            let identifier = tokens
                 .parse_source_of_kind(IsographLangTokenKind::Identifier)
                 .map_err(|with_span| with_span.map(IsographLiteralParseError::from))?;
 
-            let span = bool_or_null.span;
-
-            bool_or_null.and_then(|bool_or_null| match bool_or_null {
-                "null" => Ok(NonConstantValue::Null),
-                bool => match bool.parse::<bool>() {
-                    Ok(b) => Ok(NonConstantValue::Boolean(b)),
-                    Err(_) => Err(WithSpan::new(
-                        IsographLiteralParseError::ExpectedBoolean,
-                        span,
-                    )),
-                },
-            })
+            Ok(identifier.map(|identifier| match identifier {
+                "null" => NonConstantValue::Null,
+                "true" => NonConstantValue::Boolean(true),
+                "false" => NonConstantValue::Boolean(false),
+                enum_value => NonConstantValue::Enum(enum_value.intern().into()),
+            }))
         })?;
 
         ControlFlow::Continue(WithSpan::new(
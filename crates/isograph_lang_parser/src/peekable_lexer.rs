@@ -4,7 +4,7 @@ use intern::string_key::{Intern, StringKey};
 use logos::Logos;
 use thiserror::Error;
 
-pub(crate) struct PeekableLexer<'source> {
+pub struct PeekableLexer<'source> {
     current: WithSpan<IsographLangTokenKind>,
     lexer: logos::Lexer<'source, IsographLangTokenKind>,
     source: &'source str,
@@ -121,7 +121,6 @@ impl<'source> PeekableLexer<'source> {
         Ok(WithSpan::new(source.into(), kind.span))
     }
 
-    #[allow(dead_code)]
     pub fn parse_matching_identifier(
         &mut self,
         identifier: &'static str,
@@ -38,8 +38,8 @@ pub enum IsographLangTokenKind {
     // IntegerPart:    -?(0|[1-9][0-9]*)
     // FractionalPart: \\.[0-9]+
     // ExponentPart:   [eE][+-]?[0-9]+
-    // #[regex("-?(0|[1-9][0-9]*)(\\.[0-9]+[eE][+-]?[0-9]+|\\.[0-9]+|[eE][+-]?[0-9]+)")]
-    // FloatLiteral,
+    #[regex("-?(0|[1-9][0-9]*)(\\.[0-9]+[eE][+-]?[0-9]+|\\.[0-9]+|[eE][+-]?[0-9]+)")]
+    FloatLiteral,
     #[regex("[a-zA-Z_][a-zA-Z0-9_]*")]
     Identifier,
 
@@ -68,9 +68,8 @@ pub enum IsographLangTokenKind {
 
     // #[token("|")]
     // Pipe,
-
-    // #[token("...")]
-    // Spread,
+    #[token("...")]
+    Spread,
 
     // Comments
     // #[regex("#[^\n\r]*")]
@@ -162,7 +161,7 @@ impl fmt::Display for IsographLangTokenKind {
             IsographLangTokenKind::EndOfFile => "end of file",
             IsographLangTokenKind::Equals => "equals ('=')",
             IsographLangTokenKind::Exclamation => "exclamation mark ('!')",
-            // IsographLangTokenKind::FloatLiteral => "floating point value (e.g. '3.14')",
+            IsographLangTokenKind::FloatLiteral => "floating point value (e.g. '3.14')",
             IsographLangTokenKind::Identifier => "non-variable identifier (e.g. 'x' or 'Foo')",
             IsographLangTokenKind::IntegerLiteral => "integer value (e.g. '0' or '42')",
             IsographLangTokenKind::OpenBrace => "open brace ('{')",
@@ -171,7 +170,7 @@ impl fmt::Display for IsographLangTokenKind {
             IsographLangTokenKind::Period => "period ('.')",
             // IsographLangTokenKind::PeriodPeriod => "double period ('..')",
             // IsographLangTokenKind::Pipe => "pipe ('|')",
-            // IsographLangTokenKind::Spread => "spread ('...')",
+            IsographLangTokenKind::Spread => "spread ('...')",
             IsographLangTokenKind::BlockStringLiteral => "block string (e.g. '\"\"\"hi\"\"\"')",
             IsographLangTokenKind::Error => "error",
             IsographLangTokenKind::ErrorFloatLiteralMissingZero => {
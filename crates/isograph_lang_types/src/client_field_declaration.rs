@@ -11,7 +11,7 @@ use std::fmt::Debug;
 
 use crate::{
     ClientFieldDirectiveSet, IsographFieldDirective, ObjectSelectionDirectiveSet,
-    ScalarSelectionDirectiveSet, SelectionType,
+    ScalarSelectionDirectiveSet, SelectionCondition, SelectionType,
 };
 
 pub type UnvalidatedSelection = SelectionTypeContainingSelections<(), ()>;
@@ -44,7 +44,9 @@ pub struct ClientPointerDeclaration {
     pub target_type: GraphQLTypeAnnotation<UnvalidatedTypeName>,
     pub client_pointer_name: WithSpan<ClientObjectSelectableName>,
     pub description: Option<WithSpan<DescriptionValue>>,
-    pub selection_set: Vec<WithSpan<UnvalidatedSelection>>,
+    /// A pointer without a selection set (e.g. `pointer Query.currentUser to User`)
+    /// is a node pointer, which points to the record with the id that is passed to it.
+    pub selection_set: Option<Vec<WithSpan<UnvalidatedSelection>>>,
     pub variable_definitions: Vec<WithSpan<VariableDefinition<UnvalidatedTypeName>>>,
     pub definition_path: RelativePathToSourceFile,
 
@@ -97,6 +99,7 @@ pub struct ScalarSelection<TScalarField> {
     pub associated_data: TScalarField,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
     pub scalar_selection_directive_set: ScalarSelectionDirectiveSet,
+    pub conditions: Vec<WithLocation<SelectionCondition>>,
 }
 // TODO impl_with_target_id!(ScalarSelection)
 
@@ -116,6 +119,7 @@ pub struct ObjectSelection<TScalar, TLinked> {
     pub selection_set: Vec<WithSpan<SelectionTypeContainingSelections<TScalar, TLinked>>>,
     pub arguments: Vec<WithLocation<SelectionFieldArgument>>,
     pub object_selection_directive_set: ObjectSelectionDirectiveSet,
+    pub conditions: Vec<WithLocation<SelectionCondition>>,
 }
 // TODO impl_with_target_id!(ObjectSelection)
 
@@ -178,32 +182,48 @@ pub enum NonConstantValue {
     Object(Vec<NameValuePair<ValueKeyName, NonConstantValue>>),
 }
 
+/// Aliases can only contain word characters, so e.g. the `.` in `1.5` is replaced
+/// with an underscore. This must be kept in sync with the runtime.
+fn replace_non_word_characters(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '_' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
 impl NonConstantValue {
     pub fn to_alias_str_chunk(&self) -> String {
         match self {
             NonConstantValue::Variable(name) => format!("v_{}", name),
             // l for literal, i.e. this is shared with others
-            NonConstantValue::Integer(int_value) => format!("l_{}", int_value),
+            NonConstantValue::Integer(int_value) => {
+                format!("l_{}", replace_non_word_characters(&int_value.to_string()))
+            }
             NonConstantValue::Boolean(bool) => format!("l_{}", bool),
-            NonConstantValue::String(string) => format!(
-                "s_{}",
-                string
-                    .lookup()
-                    .chars()
-                    .map(|c| match c {
-                        'A'..='Z' | 'a'..='z' | '0'..='9' | '_' => c,
-                        // N.B. This clearly isn't correct, the string can (for example) include
-                        // spaces, which would break things.
-                        // TODO get a solution or validate
-                        _ => '_',
-                    })
-                    .collect::<String>(),
-            ),
-            // Also not correct
-            NonConstantValue::Float(f) => format!("l_{}", f.as_float()),
+            // N.B. This clearly isn't correct, the string can (for example) include
+            // spaces, which would break things.
+            // TODO get a solution or validate
+            NonConstantValue::String(string) => {
+                format!("s_{}", replace_non_word_characters(string.lookup()))
+            }
+            NonConstantValue::Float(f) => {
+                format!(
+                    "l_{}",
+                    replace_non_word_characters(&f.as_float().to_string())
+                )
+            }
             NonConstantValue::Null => "l_null".to_string(),
             NonConstantValue::Enum(e) => format!("e_{e}"),
-            NonConstantValue::List(_) => panic!("Lists are not supported here"),
+            NonConstantValue::List(list) => format!(
+                "a_{}_c",
+                list.iter()
+                    .map(|item| item.item.to_alias_str_chunk())
+                    .collect::<Vec<_>>()
+                    .join("_")
+            ),
             NonConstantValue::Object(object) => {
                 format!(
                     "o_{}_c",
@@ -221,6 +241,42 @@ impl NonConstantValue {
         }
     }
 
+    /// Print this value as it would be written in an iso literal, e.g. in
+    /// error messages.
+    pub fn print_to_string(&self) -> String {
+        match self {
+            NonConstantValue::Variable(name) => format!("${name}"),
+            NonConstantValue::Integer(i) => i.to_string(),
+            NonConstantValue::Boolean(b) => b.to_string(),
+            NonConstantValue::String(s) => format!("\"{s}\""),
+            NonConstantValue::Float(f) => f.as_float().to_string(),
+            NonConstantValue::Null => "null".to_string(),
+            NonConstantValue::Enum(e) => e.to_string(),
+            NonConstantValue::List(l) => {
+                let inner = l
+                    .iter()
+                    .map(|value| value.item.print_to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("[{inner}]")
+            }
+            NonConstantValue::Object(o) => {
+                let inner = o
+                    .iter()
+                    .map(|key_value| {
+                        format!(
+                            "{}: {}",
+                            key_value.name.item,
+                            key_value.value.item.print_to_string()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{{{inner}}}")
+            }
+        }
+    }
+
     pub fn variables(&self) -> Vec<VariableName> {
         // TODO return impl Iterator
         match self {
@@ -1,3 +1,4 @@
+use common_lang_types::SelectableNameOrAlias;
 use serde::Deserialize;
 
 use crate::EmptyDirectiveSet;
@@ -6,6 +7,7 @@ use crate::EmptyDirectiveSet;
 #[serde(rename_all = "camelCase", untagged)]
 pub enum ClientFieldDirectiveSet {
     Component(ComponentDirectiveSet),
+    Plural(PluralDirectiveSet),
     None(EmptyDirectiveSet),
 }
 
@@ -16,4 +18,48 @@ pub struct ComponentDirectiveSet {
 }
 #[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
-pub struct ComponentDirectiveParameters {}
+pub struct ComponentDirectiveParameters {
+    /// Set with e.g. @component(boundary: server). Only used if the
+    /// react_server_components config option is set.
+    #[serde(default)]
+    pub boundary: Option<ComponentBoundary>,
+}
+
+/// Where a component is rendered, if React Server Components are used.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ComponentBoundary {
+    /// The component is rendered on the client, so it can use hooks. Its reader
+    /// artifact starts with "use client".
+    Client,
+    /// The component can be rendered by server components.
+    Server,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Copy, Hash)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PluralDirectiveSet {
+    pub plural: PluralDirectiveParameters,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PluralDirectiveParameters {
+    /// Set with e.g. @plural(path: "pets"). The name or alias of the top-level
+    /// linked field of the selection set, which must be a list. The resolver
+    /// returns an array with an item for each item of that list.
+    pub path: SelectableNameOrAlias,
+}
+
+impl ClientFieldDirectiveSet {
+    /// Whether the reader artifact of this client field should start with
+    /// "use client". Components are client-rendered unless they opt out.
+    pub fn is_client_component(&self) -> bool {
+        match self {
+            ClientFieldDirectiveSet::Component(component) => {
+                component.component.boundary != Some(ComponentBoundary::Server)
+            }
+            ClientFieldDirectiveSet::Plural(_) | ClientFieldDirectiveSet::None(_) => false,
+        }
+    }
+}
@@ -162,11 +162,15 @@ impl<'de> Deserializer<'de> for NonConstantValueDeserializer<'de> {
             NonConstantValue::String(s) => visitor.visit_str(s.lookup()),
             NonConstantValue::Float(f) => visitor.visit_f64(f.as_float()),
             NonConstantValue::Null => visitor.visit_none(),
-            NonConstantValue::Enum(_) => panic!("Enums not supported when deserializing"),
-            NonConstantValue::List(_) => {
-                panic!("Deserializing from lists is not yet supported here.")
-            }
-            NonConstantValue::Object(_) => panic!("Deserializing objects not yet supported here."),
+            // Visited as a string, so that enum values can be deserialized into unit
+            // variants, even when buffered (e.g. by untagged enums).
+            NonConstantValue::Enum(e) => visitor.visit_str(e.lookup()),
+            NonConstantValue::List(_) => Err(DeserializationError::Custom(
+                "Lists are not supported in directives.".to_string(),
+            )),
+            NonConstantValue::Object(_) => Err(DeserializationError::Custom(
+                "Objects are not supported in directives.".to_string(),
+            )),
         }
     }
 
@@ -177,10 +181,28 @@ impl<'de> Deserializer<'de> for NonConstantValueDeserializer<'de> {
         visitor.visit_some(self)
     }
 
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        // Unit variants can be written as enum values, e.g. @component(boundary: server),
+        // or as strings.
+        match self.value {
+            NonConstantValue::Enum(e) => visitor.visit_enum(e.lookup().into_deserializer()),
+            NonConstantValue::String(s) => visitor.visit_enum(s.lookup().into_deserializer()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
     serde::forward_to_deserialize_any! {
         bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
         bytes byte_buf unit unit_struct newtype_struct seq tuple
-        tuple_struct map struct enum ignored_any identifier
+        tuple_struct map struct ignored_any identifier
     }
 }
 
@@ -92,6 +92,18 @@ impl<TInner: Ord> TypeAnnotation<TInner> {
         }
     }
 
+    /// Whether the type is a list, e.g. [Pet!]! or [Pet].
+    pub fn is_plural(&self) -> bool {
+        match self {
+            TypeAnnotation::Scalar(_) => false,
+            TypeAnnotation::Union(union_type_annotation) => union_type_annotation
+                .variants
+                .iter()
+                .any(|variant| matches!(variant, UnionVariant::Plural(_))),
+            TypeAnnotation::Plural(_) => true,
+        }
+    }
+
     pub fn map<TInner2: Ord>(
         self,
         map: &mut impl FnMut(TInner) -> TInner2,
@@ -119,6 +131,46 @@ impl<TInner: Ord> TypeAnnotation<TInner> {
         }
     }
 
+    /// Make the type non-null at each of the levels, where level 0 is the type itself,
+    /// level 1 is the items of a list, and so on. This is used for fields that are
+    /// only null if there is an error, e.g. with @semanticNonNull in GraphQL.
+    pub fn with_non_null_levels(self, levels: &[u32]) -> Self {
+        self.with_non_null_levels_impl(levels, 0)
+    }
+
+    fn with_non_null_levels_impl(self, levels: &[u32], level: u32) -> Self {
+        match self {
+            TypeAnnotation::Scalar(s) => TypeAnnotation::Scalar(s),
+            TypeAnnotation::Plural(type_annotation) => TypeAnnotation::Plural(Box::new(
+                type_annotation.with_non_null_levels_impl(levels, level + 1),
+            )),
+            TypeAnnotation::Union(union_type_annotation) => {
+                let nullable = union_type_annotation.nullable && !levels.contains(&level);
+                let mut variants = union_type_annotation
+                    .variants
+                    .into_iter()
+                    .map(|variant| match variant {
+                        UnionVariant::Scalar(s) => UnionVariant::Scalar(s),
+                        UnionVariant::Plural(type_annotation) => UnionVariant::Plural(
+                            type_annotation.with_non_null_levels_impl(levels, level + 1),
+                        ),
+                    })
+                    .collect::<BTreeSet<_>>();
+
+                // A non-null union with a single variant is represented as that variant
+                if !nullable && variants.len() == 1 {
+                    return match variants.pop_first().expect("Expected variant to exist") {
+                        UnionVariant::Scalar(s) => TypeAnnotation::Scalar(s),
+                        UnionVariant::Plural(type_annotation) => {
+                            TypeAnnotation::Plural(Box::new(type_annotation))
+                        }
+                    };
+                }
+                TypeAnnotation::Union(UnionTypeAnnotation { variants, nullable })
+            }
+        }
+    }
+
     // TODO implement as_ref
 }
 
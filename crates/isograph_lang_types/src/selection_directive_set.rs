@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use crate::LoadableDirectiveParameters;
+use crate::{LoadableDirectiveParameters, NonConstantValue};
 
 #[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
@@ -14,6 +14,12 @@ pub enum ScalarSelectionDirectiveSet {
     None(EmptyDirectiveSet),
 }
 
+impl ScalarSelectionDirectiveSet {
+    /// The names of the directives that can be used on scalar selections.
+    pub const DIRECTIVE_NAMES: &'static [&'static str] =
+        &["loadable", "updatable", "include", "skip"];
+}
+
 #[derive(Deserialize, Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Copy, Hash)]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum ObjectSelectionDirectiveSet {
@@ -21,6 +27,11 @@ pub enum ObjectSelectionDirectiveSet {
     None(EmptyDirectiveSet),
 }
 
+impl ObjectSelectionDirectiveSet {
+    /// The names of the directives that can be used on linked field selections.
+    pub const DIRECTIVE_NAMES: &'static [&'static str] = &["updatable", "include", "skip"];
+}
+
 #[derive(Deserialize, Debug, Default, Clone, PartialEq, PartialOrd, Ord, Eq, Copy, Hash)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct UpdatableDirectiveSet {
@@ -37,3 +48,34 @@ pub struct LoadableDirectiveSet {
 #[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct EmptyDirectiveSet {}
+
+/// A condition under which a selection is fetched and read, i.e. `@include(if: $foo)`
+/// or `@skip(if: $foo)`. The value is a variable or a boolean.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
+pub struct SelectionCondition {
+    pub kind: SelectionConditionKind,
+    pub value: NonConstantValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
+pub enum SelectionConditionKind {
+    Include,
+    Skip,
+}
+
+impl SelectionConditionKind {
+    pub fn from_directive_name(directive_name: &str) -> Option<Self> {
+        match directive_name {
+            "include" => Some(SelectionConditionKind::Include),
+            "skip" => Some(SelectionConditionKind::Skip),
+            _ => None,
+        }
+    }
+
+    pub fn directive_name(self) -> &'static str {
+        match self {
+            SelectionConditionKind::Include => "include",
+            SelectionConditionKind::Skip => "skip",
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Finds the iso literals (e.g. iso(`field Pet.PetAvatar { ... }`)) in the
+//! contents of a JavaScript or TypeScript file. This does not depend on the rest
+//! of the compiler, so that it can be shared by the compiler and the language
+//! server.
+
+mod regex_extraction;
+mod swc_extraction;
+
+pub use regex_extraction::extract_iso_literals_with_regex;
+pub use swc_extraction::extract_iso_literals_with_swc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsoLiteralExtraction<'a> {
+    pub const_export_name: Option<&'a str>,
+    /// The byte offset of the const export name in the file, e.g. the offset of
+    /// `Foo` in `export const Foo = iso(...)`.
+    pub const_export_name_start_index: Option<usize>,
+    pub iso_literal_text: &'a str,
+    pub iso_literal_start_index: usize,
+    pub has_associated_js_function: bool,
+    /// true if the iso function is called as iso(`...`), and false if it is
+    /// called as iso`...`. This is tracked as a separate field because some users
+    /// may assume that you write iso literals like you would graphql/gql literals
+    /// (which are written as graphql`...`), and having a separate field means
+    /// we can display a helpful error message (instead of silently ignoring.)
+    pub iso_function_called_with_paren: bool,
+}
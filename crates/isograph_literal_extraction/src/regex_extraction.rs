@@ -0,0 +1,37 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::IsoLiteralExtraction;
+
+lazy_static! {
+    static ref EXTRACT_ISO_LITERAL: Regex =
+        Regex::new(r"(// )?(export const ([^ ]+) =\s+)?iso(\()?`([^`]+)`(\))?(\()?").unwrap();
+}
+
+/// Find the iso literals in a file by scanning its text. This is fast, and works
+/// on files that do not parse, but it relies on iso literals being formatted
+/// like they are by prettier (e.g. `export const Foo = iso(...)`.)
+pub fn extract_iso_literals_with_regex(
+    content: &str,
+) -> impl Iterator<Item = IsoLiteralExtraction<'_>> + '_ {
+    EXTRACT_ISO_LITERAL
+        .captures_iter(content)
+        .flat_map(|captures| {
+            let iso_literal_match = captures.get(5).unwrap();
+            if captures.get(1).is_some() {
+                // HACK
+                // this iso literal is commented out using //, so skip it.
+                return None;
+            }
+            Some(IsoLiteralExtraction {
+                const_export_name: captures.get(2).map(|_| captures.get(3).unwrap().as_str()),
+                const_export_name_start_index: captures
+                    .get(2)
+                    .map(|_| captures.get(3).unwrap().start()),
+                iso_literal_text: iso_literal_match.as_str(),
+                iso_literal_start_index: iso_literal_match.start(),
+                has_associated_js_function: captures.get(7).is_some(),
+                iso_function_called_with_paren: captures.get(4).is_some(),
+            })
+        })
+}
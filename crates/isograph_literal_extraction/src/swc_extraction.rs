@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_common::{input::StringInput, BytePos, Span, Spanned};
+use swc_ecma_ast::{
+    CallExpr, Callee, Decl, ExportDecl, Expr, Pat, Program, TaggedTpl, Tpl, VarDeclarator,
+};
+use swc_ecma_parser::{Parser, Syntax, TsSyntax};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::IsoLiteralExtraction;
+
+/// SWC reserves BytePos(0) for generated code, so the file starts at BytePos(1).
+const FILE_START: BytePos = BytePos(1);
+
+/// Find the iso literals in a file by parsing it with SWC. Unlike
+/// extract_iso_literals_with_regex, this finds iso literals regardless of how they
+/// are formatted, and ignores iso literals that are in comments or strings.
+///
+/// Files are parsed as TSX, and then as TypeScript (for files with type assertions
+/// like <T>value). Returns None if the file cannot be parsed either way, e.g.
+/// because it is being edited.
+pub fn extract_iso_literals_with_swc(content: &str) -> Option<Vec<IsoLiteralExtraction<'_>>> {
+    let program = parse_program(content, true).or_else(|| parse_program(content, false))?;
+
+    let mut visitor = IsoLiteralVisitor {
+        content,
+        const_export_names: HashMap::new(),
+        iso_calls_with_associated_js_function: HashSet::new(),
+        extractions: vec![],
+    };
+    program.visit_with(&mut visitor);
+    Some(visitor.extractions)
+}
+
+fn parse_program(content: &str, tsx: bool) -> Option<Program> {
+    let input = StringInput::new(
+        content,
+        FILE_START,
+        FILE_START + BytePos(content.len() as u32),
+    );
+    let syntax = Syntax::Typescript(TsSyntax {
+        tsx,
+        decorators: true,
+        ..Default::default()
+    });
+    Parser::new(syntax, input, None).parse_program().ok()
+}
+
+struct IsoLiteralVisitor<'a> {
+    content: &'a str,
+    /// The name and offset of the const export that is initialized with each iso
+    /// call, keyed by the start of the iso call.
+    const_export_names: HashMap<BytePos, (&'a str, usize)>,
+    /// The starts of the iso calls whose result is called with a function, as in
+    /// iso(`...`)(function Foo() {}).
+    iso_calls_with_associated_js_function: HashSet<BytePos>,
+    extractions: Vec<IsoLiteralExtraction<'a>>,
+}
+
+impl<'a> IsoLiteralVisitor<'a> {
+    fn offset(&self, pos: BytePos) -> usize {
+        (pos - FILE_START).0 as usize
+    }
+
+    fn text(&self, span: Span) -> &'a str {
+        &self.content[self.offset(span.lo)..self.offset(span.hi)]
+    }
+
+    fn record(&mut self, iso_call_span: Span, tpl: &Tpl, iso_function_called_with_paren: bool) {
+        // The text between the backticks
+        let literal_span = Span::new(tpl.span.lo + BytePos(1), tpl.span.hi - BytePos(1));
+        let iso_literal_text = self.text(literal_span);
+        if iso_literal_text.is_empty() {
+            return;
+        }
+        let const_export_name = self.const_export_names.get(&iso_call_span.lo).copied();
+        self.extractions.push(IsoLiteralExtraction {
+            const_export_name: const_export_name.map(|(name, _)| name),
+            const_export_name_start_index: const_export_name.map(|(_, start)| start),
+            iso_literal_text,
+            iso_literal_start_index: self.offset(literal_span.lo),
+            has_associated_js_function: self
+                .iso_calls_with_associated_js_function
+                .contains(&iso_call_span.lo),
+            iso_function_called_with_paren,
+        });
+    }
+}
+
+impl Visit for IsoLiteralVisitor<'_> {
+    fn visit_export_decl(&mut self, export_decl: &ExportDecl) {
+        if let Decl::Var(var_decl) = &export_decl.decl {
+            for declarator in &var_decl.decls {
+                self.insert_const_export_name(declarator);
+            }
+        }
+        export_decl.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if let Callee::Expr(callee) = &call_expr.callee {
+            if iso_literal(callee).is_some() {
+                self.iso_calls_with_associated_js_function
+                    .insert(callee.span().lo);
+            }
+        }
+        if let Some(tpl) = iso_call_literal(call_expr) {
+            self.record(call_expr.span, tpl, true);
+        }
+        call_expr.visit_children_with(self);
+    }
+
+    fn visit_tagged_tpl(&mut self, tagged_tpl: &TaggedTpl) {
+        if let Some(tpl) = iso_tagged_template_literal(tagged_tpl) {
+            self.record(tagged_tpl.span, tpl, false);
+        }
+        tagged_tpl.visit_children_with(self);
+    }
+}
+
+impl IsoLiteralVisitor<'_> {
+    /// If this declares a const that is initialized with an iso literal, either as
+    /// `Foo = iso(...)` or as `Foo = iso(...)(...)`, record its name.
+    fn insert_const_export_name(&mut self, declarator: &VarDeclarator) {
+        let (Pat::Ident(binding_ident), Some(init)) = (&declarator.name, &declarator.init) else {
+            return;
+        };
+        let iso_expr = match &**init {
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                ..
+            }) if iso_literal(callee).is_some() => &**callee,
+            init => init,
+        };
+        if iso_literal(iso_expr).is_none() {
+            return;
+        }
+        let name_span = binding_ident.id.span;
+        self.const_export_names.insert(
+            iso_expr.span().lo,
+            (self.text(name_span), self.offset(name_span.lo)),
+        );
+    }
+}
+
+fn iso_literal(expr: &Expr) -> Option<&Tpl> {
+    match expr {
+        Expr::Call(call_expr) => iso_call_literal(call_expr),
+        Expr::TaggedTpl(tagged_tpl) => iso_tagged_template_literal(tagged_tpl),
+        _ => None,
+    }
+}
+
+/// The template literal of iso(`...`)
+fn iso_call_literal(call_expr: &CallExpr) -> Option<&Tpl> {
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return None;
+    };
+    if !is_iso_identifier(callee) {
+        return None;
+    }
+    match call_expr.args.as_slice() {
+        [arg] if arg.spread.is_none() => match &*arg.expr {
+            Expr::Tpl(tpl) => Some(tpl),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The template literal of iso`...`
+fn iso_tagged_template_literal(tagged_tpl: &TaggedTpl) -> Option<&Tpl> {
+    is_iso_identifier(&tagged_tpl.tag).then_some(&*tagged_tpl.tpl)
+}
+
+fn is_iso_identifier(expr: &Expr) -> bool {
+    matches!(expr, Expr::Ident(ident) if &*ident.sym == "iso")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract_iso_literals_with_regex;
+
+    fn assert_same_as_regex(content: &str) {
+        assert_eq!(
+            extract_iso_literals_with_swc(content).expect("Expected file to parse"),
+            extract_iso_literals_with_regex(content).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn matches_regex_for_formatted_files() {
+        assert_same_as_regex(
+            "import { iso } from '@iso';\n\
+            \n\
+            export const PetAvatar = iso(`\n  field Pet.PetAvatar @component {\n    picture\n  }\n`)(\n\
+            function PetAvatarComponent({ data }: { data: any }) {\n\
+              return <img src={data.picture} />;\n\
+            },\n\
+            );\n\
+            \n\
+            const entrypoint = iso(`entrypoint Query.HomeRoute`);\n\
+            const tagged = iso`field Pet.Name { name }`;\n",
+        );
+    }
+
+    #[test]
+    fn finds_literals_regardless_of_formatting() {
+        let content = "export const   PetAvatar =\n  iso(\n    `field Pet.PetAvatar { picture }`,\n  )((x) => x);";
+        let extractions = extract_iso_literals_with_swc(content).unwrap();
+        assert_eq!(extractions.len(), 1);
+        let extraction = extractions[0];
+        assert_eq!(extraction.const_export_name, Some("PetAvatar"));
+        assert_eq!(
+            &content[extraction.const_export_name_start_index.unwrap()..][..9],
+            "PetAvatar"
+        );
+        assert_eq!(
+            extraction.iso_literal_text,
+            "field Pet.PetAvatar { picture }"
+        );
+        assert_eq!(&content[extraction.iso_literal_start_index..][..5], "field");
+        assert!(extraction.has_associated_js_function);
+        assert!(extraction.iso_function_called_with_paren);
+    }
+
+    #[test]
+    fn ignores_literals_in_comments_and_strings() {
+        let content = "/* iso(`field Pet.A { name }`) */\n\
+            const s = 'iso(`field Pet.B { name }`)';\n\
+            // iso(`field Pet.C { name }`)\n";
+        assert_eq!(extract_iso_literals_with_swc(content), Some(vec![]));
+    }
+
+    #[test]
+    fn parses_type_assertions() {
+        let content = "const x = <any>iso(`entrypoint Query.HomeRoute`);";
+        let extractions = extract_iso_literals_with_swc(content).unwrap();
+        assert_eq!(
+            extractions[0].iso_literal_text,
+            "entrypoint Query.HomeRoute"
+        );
+    }
+
+    #[test]
+    fn returns_none_for_files_that_do_not_parse() {
+        assert_eq!(extract_iso_literals_with_swc("iso(`field Pet.A {`)("), None);
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+
+use common_lang_types::{
+    error_codes, CurrentWorkingDirectory, ErrorCode, RelativePathToSourceFile, Span,
+    UnvalidatedTypeName, WithSpan,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_compiler::{
+    extract_iso_literals_from_file_content, parse_iso_literals_in_file_content, Diagnostic,
+    StandardSources,
+};
+use isograph_config::IsoLiteralExtractor;
+use isograph_lang_parser::IsoLiteralExtractionResult;
+use isograph_lang_types::{SelectionTypeContainingSelections, UnvalidatedSelection};
+use isograph_schema::{NetworkProtocol, Schema};
+use lsp_types::{
+    request::{CodeActionRequest, Request},
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, DiagnosticSeverity,
+    NumberOrString, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::{
+    completion::{available_selectables, object_entity_at_path},
+    location_utils::{position_to_offset, span_in_file_if_in, span_to_range},
+    lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult},
+    lsp_state::LSPState,
+};
+
+pub fn on_code_action<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <CodeActionRequest as Request>::Params,
+) -> LSPRuntimeResult<<CodeActionRequest as Request>::Result> {
+    let CodeActionParams {
+        text_document,
+        range,
+        context: _,
+        work_done_progress_params: _,
+        partial_result_params: _,
+    } = params;
+    let uri = text_document.uri;
+
+    let file_text = state
+        .text_for(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?
+        .to_string();
+    let relative_path = state
+        .relative_path(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let requested_span = Span::new(
+        position_to_offset(&file_text, range.start).ok_or(LSPRuntimeError::ExpectedError)?,
+        position_to_offset(&file_text, range.end).ok_or(LSPRuntimeError::ExpectedError)?,
+    );
+    let current_working_directory = state.config().current_working_directory;
+    let iso_literal_extractor = state.config().options.iso_literal_extractor;
+
+    let diagnostics = state
+        .diagnostics()
+        .iter()
+        .filter_map(|diagnostic| {
+            let span = span_in_file_if_in(&diagnostic.location?, relative_path)?;
+            (span.start <= requested_span.end && requested_span.start <= span.end)
+                .then(|| (diagnostic.clone(), span))
+        })
+        .collect::<Vec<_>>();
+    if diagnostics.is_empty() {
+        return Ok(None);
+    }
+
+    let file = FileForCodeActions {
+        uri,
+        relative_path,
+        text: file_text,
+        current_working_directory,
+        iso_literal_extractor,
+    };
+    let mut code_actions = vec![];
+    for (diagnostic, span) in diagnostics {
+        let code = diagnostic
+            .code
+            .as_deref()
+            .and_then(|code| code.parse::<ErrorCode>().ok());
+        let quick_fix = match code {
+            Some(error_codes::IS0007) => export_the_const(&file, span),
+            Some(error_codes::IS0012) => Some(insert_empty_selection_set(span)),
+            Some(error_codes::IS0400) => remove_unknown_field(&file, span),
+            Some(error_codes::IS0401) => {
+                // The schema is only used to decide which field to select, so it does
+                // not matter if it is stale.
+                let schema = state.last_valid_schema().ok();
+                add_missing_field_to_selection(&file, span, schema)
+            }
+            Some(error_codes::IS0402) | Some(error_codes::IS0406) => {
+                remove_selection_set(&file, span)
+            }
+            _ => None,
+        };
+        if let Some(quick_fix) = quick_fix {
+            code_actions.push(file.code_action(quick_fix, &diagnostic, span));
+        }
+    }
+
+    Ok(Some(code_actions))
+}
+
+struct FileForCodeActions {
+    uri: Url,
+    relative_path: RelativePathToSourceFile,
+    text: String,
+    current_working_directory: CurrentWorkingDirectory,
+    iso_literal_extractor: IsoLiteralExtractor,
+}
+
+/// A fix consisting of a single edit, with spans relative to the start of the file.
+struct QuickFix {
+    title: String,
+    span: Span,
+    new_text: String,
+}
+
+impl FileForCodeActions {
+    fn code_action(
+        &self,
+        quick_fix: QuickFix,
+        diagnostic: &Diagnostic,
+        diagnostic_span: Span,
+    ) -> CodeActionOrCommand {
+        let edit = TextEdit {
+            range: span_to_range(&self.text, quick_fix.span),
+            new_text: quick_fix.new_text,
+        };
+        CodeActionOrCommand::CodeAction(CodeAction {
+            title: quick_fix.title,
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(vec![lsp_types::Diagnostic {
+                range: span_to_range(&self.text, diagnostic_span),
+                severity: Some(DiagnosticSeverity::ERROR),
+                code: diagnostic.code.clone().map(NumberOrString::String),
+                source: Some("isograph".to_string()),
+                message: diagnostic.message.clone(),
+                ..Default::default()
+            }]),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(self.uri.clone(), vec![edit])])),
+                ..Default::default()
+            }),
+            is_preferred: Some(true),
+            ..Default::default()
+        })
+    }
+
+    /// Find the selection whose name has the given span, along with the parent type
+    /// of the literal and the names of the enclosing object selections.
+    fn selection_with_name_span(
+        &self,
+        name_span: Span,
+    ) -> Option<(UnvalidatedTypeName, Vec<&str>, Span)> {
+        parse_iso_literals_in_file_content(
+            self.relative_path,
+            &self.text,
+            self.current_working_directory,
+            self.iso_literal_extractor,
+        )
+        .ok()?
+        .into_iter()
+        .find_map(|(extraction_result, text_source)| {
+            let literal_start = text_source.span.map(|span| span.start).unwrap_or(0);
+            let (parent_type, selection_set) = match &extraction_result {
+                IsoLiteralExtractionResult::ClientFieldDeclaration(declaration) => (
+                    declaration.item.parent_type.item,
+                    &declaration.item.selection_set,
+                ),
+                IsoLiteralExtractionResult::ClientPointerDeclaration(declaration) => (
+                    declaration.item.parent_type.item,
+                    declaration.item.selection_set.as_ref()?,
+                ),
+                IsoLiteralExtractionResult::EntrypointDeclaration(_) => return None,
+            };
+            let mut path = vec![];
+            let selection_span = find_selection(
+                selection_set,
+                Span::new(
+                    name_span.start.checked_sub(literal_start)?,
+                    name_span.end.checked_sub(literal_start)?,
+                ),
+                &mut path,
+            )?;
+            Some((parent_type, path, selection_span.with_offset(literal_start)))
+        })
+    }
+}
+
+fn find_selection(
+    selection_set: &[WithSpan<UnvalidatedSelection>],
+    name_span: Span,
+    path: &mut Vec<&'static str>,
+) -> Option<Span> {
+    selection_set
+        .iter()
+        .find_map(|selection| match &selection.item {
+            SelectionTypeContainingSelections::Scalar(scalar_selection) => {
+                (scalar_selection.name.location.span() == Some(name_span)).then_some(selection.span)
+            }
+            SelectionTypeContainingSelections::Object(object_selection) => {
+                if object_selection.name.location.span() == Some(name_span) {
+                    return Some(selection.span);
+                }
+                path.push(object_selection.name.item.lookup());
+                let span = find_selection(&object_selection.selection_set, name_span, path);
+                if span.is_none() {
+                    path.pop();
+                }
+                span
+            }
+        })
+}
+
+/// IS0012: the literal has no selection set, so we insert an empty one after
+/// the previous token.
+fn insert_empty_selection_set(span: Span) -> QuickFix {
+    QuickFix {
+        title: "Insert empty selection set {}".to_string(),
+        span: Span::new(span.start, span.start),
+        new_text: " {}".to_string(),
+    }
+}
+
+/// IS0007: the literal is not exported. The error points to the name of the client
+/// field or pointer, which is also the suggested export name.
+fn export_the_const(file: &FileForCodeActions, span: Span) -> Option<QuickFix> {
+    let text = &file.text;
+    let suggested_const_export_name = &text[span.as_usize_range()];
+    let literal_start = extract_iso_literals_from_file_content(text, file.iso_literal_extractor)
+        .into_iter()
+        .map(|extraction| extraction.iso_literal_start_index)
+        .rfind(|literal_start| *literal_start <= span.start as usize)?;
+    let iso_call_start = text[..literal_start].rfind("iso")?;
+    let line_start = text[..iso_call_start]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let before_iso_call = &text[line_start..iso_call_start];
+
+    if before_iso_call.trim().is_empty() {
+        return Some(QuickFix {
+            title: format!("Export as `{suggested_const_export_name}`"),
+            span: Span::new(iso_call_start as u32, iso_call_start as u32),
+            new_text: format!("export const {suggested_const_export_name} = "),
+        });
+    }
+
+    // e.g. `const Foo = iso(...)`
+    let const_start = line_start + before_iso_call.find("const ")?;
+    if !text[line_start..const_start].trim().is_empty() {
+        return None;
+    }
+    Some(QuickFix {
+        title: "Export the const".to_string(),
+        span: Span::new(const_start as u32, const_start as u32),
+        new_text: "export ".to_string(),
+    })
+}
+
+/// IS0400: the selected field does not exist. Remove the selection, including its
+/// line if it is the only thing on it.
+fn remove_unknown_field(file: &FileForCodeActions, name_span: Span) -> Option<QuickFix> {
+    let (_, _, selection_span) = file.selection_with_name_span(name_span)?;
+    let text = &file.text;
+    let (start, end) = selection_span.as_usize();
+    let line_start = text[..start]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let line_end = text[end..]
+        .find('\n')
+        .map(|index| end + index + 1)
+        .unwrap_or(text.len());
+    let span = if text[line_start..start].trim().is_empty() && text[end..line_end].trim().is_empty()
+    {
+        Span::from_usize(line_start, line_end)
+    } else {
+        selection_span
+    };
+
+    Some(QuickFix {
+        title: format!(
+            "Remove unknown field `{}`",
+            &text[name_span.as_usize_range()]
+        ),
+        span,
+        new_text: String::new(),
+    })
+}
+
+/// IS0402 and IS0406: a scalar field or client field is selected with a selection set.
+/// Remove the selection set.
+fn remove_selection_set(file: &FileForCodeActions, name_span: Span) -> Option<QuickFix> {
+    let (_, _, selection_span) = file.selection_with_name_span(name_span)?;
+    let text = &file.text;
+    let field_name = &text[name_span.as_usize_range()];
+
+    // The selection's span includes a trailing comma, if there is one.
+    let selection_text = text[selection_span.as_usize_range()]
+        .trim_end_matches(',')
+        .trim_end();
+
+    // Find the brace that opens the selection set. Arguments can contain object
+    // literals, so we cannot just search for the first brace.
+    let mut depth = 0;
+    let open_brace_index = selection_text.char_indices().rev().find_map(|(index, c)| {
+        match c {
+            '}' => depth += 1,
+            '{' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+        None
+    })?;
+
+    let start = selection_span.start + selection_text[..open_brace_index].trim_end().len() as u32;
+    let end = selection_span.start + selection_text.len() as u32;
+
+    Some(QuickFix {
+        title: format!("Remove selection set from `{field_name}`"),
+        span: Span::new(start, end),
+        new_text: String::new(),
+    })
+}
+
+/// IS0401: an object field is selected as a scalar, i.e. it has no selection set.
+/// Add a selection set, selecting `id` if the field's type has it.
+fn add_missing_field_to_selection<TNetworkProtocol: NetworkProtocol>(
+    file: &FileForCodeActions,
+    name_span: Span,
+    schema: Option<&Schema<TNetworkProtocol>>,
+) -> Option<QuickFix> {
+    let (parent_type, mut path, selection_span) = file.selection_with_name_span(name_span)?;
+    let text = &file.text;
+    let field_name = &text[name_span.as_usize_range()];
+
+    // The selection's span includes a trailing comma, if there is one.
+    let selection_text = text[selection_span.as_usize_range()].trim_end_matches(',');
+    let insertion_point = selection_span.start + selection_text.trim_end().len() as u32;
+
+    path.push(field_name);
+    let has_id_field = schema
+        .and_then(|schema| {
+            let object_entity_id = object_entity_at_path(schema, parent_type.lookup(), &path)?;
+            Some(
+                available_selectables(schema, object_entity_id).contains_key(&"id".intern().into()),
+            )
+        })
+        .unwrap_or(false);
+
+    Some(if has_id_field {
+        QuickFix {
+            title: format!("Add missing field `id` to selection `{field_name}`"),
+            span: Span::new(insertion_point, insertion_point),
+            new_text: " { id }".to_string(),
+        }
+    } else {
+        QuickFix {
+            title: format!("Insert empty selection set {{}} for `{field_name}`"),
+            span: Span::new(insertion_point, insertion_point),
+            new_text: " {}".to_string(),
+        }
+    })
+}
@@ -0,0 +1,597 @@
+use std::collections::HashMap;
+
+use common_lang_types::SelectableName;
+use intern::{string_key::Intern, Lookup};
+use isograph_compiler::{extract_iso_literals_from_file_content, StandardSources};
+use isograph_lang_types::{
+    DefinitionLocation, SelectionType, ServerEntityId, ServerObjectEntityId,
+};
+use isograph_schema::{
+    ClientFieldVariant, ClientSelectableId, NetworkProtocol, Schema,
+    ServerObjectEntityAvailableSelectables, ServerScalarOrObjectEntity, ServerSelectableId,
+};
+use lsp_types::{
+    request::{Completion, Request},
+    CompletionItem, CompletionItemKind, CompletionItemTag, CompletionParams, CompletionResponse,
+    Documentation,
+};
+
+use crate::{
+    hover::format_type_annotation,
+    location_utils::position_to_offset,
+    lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult},
+    lsp_state::LSPState,
+};
+
+pub fn on_completion<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <Completion as Request>::Params,
+) -> LSPRuntimeResult<<Completion as Request>::Result> {
+    let CompletionParams {
+        text_document_position,
+        work_done_progress_params: _,
+        partial_result_params: _,
+        context: _,
+    } = params;
+
+    let file_text = state
+        .text_for(&text_document_position.text_document.uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?
+        .to_string();
+    let offset = position_to_offset(&file_text, text_document_position.position)
+        .ok_or(LSPRuntimeError::ExpectedError)? as usize;
+
+    let iso_literal_extractor = state.config().options.iso_literal_extractor;
+    let Some(literal_text_before_cursor) =
+        extract_iso_literals_from_file_content(&file_text, iso_literal_extractor)
+            .into_iter()
+            .find_map(|extraction| {
+                let start = extraction.iso_literal_start_index;
+                let end = start + extraction.iso_literal_text.len();
+                (start <= offset && offset <= end)
+                    .then(|| &extraction.iso_literal_text[..offset - start])
+            })
+    else {
+        return Ok(None);
+    };
+    let Some(context) = completion_context(literal_text_before_cursor) else {
+        return Ok(None);
+    };
+
+    // The document is probably invalid while the user is typing, so we use the
+    // last schema that could be created.
+    let schema = state.last_valid_schema()?;
+    let Some(parent_object_entity_id) =
+        object_entity_at_path(schema, context.parent_type, &context.path)
+    else {
+        return Ok(None);
+    };
+    let parent_type_name = schema
+        .server_entity_data
+        .server_object_entity(parent_object_entity_id)
+        .name;
+
+    // Rank the fields by how well they match the name being typed.
+    let typed_name = &literal_text_before_cursor[literal_text_before_cursor
+        .trim_end_matches(is_name_char)
+        .len()..];
+    let field_scores = match context.kind {
+        CompletionKind::Field => state
+            .schema_search_index()
+            .map(|search_index| search_index.field_scores(parent_type_name.lookup(), typed_name))
+            .unwrap_or_default(),
+        CompletionKind::Argument { .. } | CompletionKind::Directive { .. } => HashMap::new(),
+    };
+
+    let schema = state.last_valid_schema()?;
+    let selectables = available_selectables(schema, parent_object_entity_id);
+
+    let items = match context.kind {
+        CompletionKind::Field => field_completion_items(schema, selectables, &field_scores),
+        CompletionKind::Argument { field } => selectables
+            .get(&field.intern().into())
+            .map(|selectable_id| argument_completion_items(schema, *selectable_id))
+            .unwrap_or_default(),
+        CompletionKind::Directive { field } => selectables
+            .get(&field.intern().into())
+            .map(|selectable_id| directive_completion_items(*selectable_id))
+            .unwrap_or_default(),
+    };
+
+    Ok(Some(CompletionResponse::Array(items)))
+}
+
+pub(crate) type SelectableId = DefinitionLocation<ServerSelectableId, ClientSelectableId>;
+
+pub(crate) fn available_selectables<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    object_entity_id: ServerObjectEntityId,
+) -> &ServerObjectEntityAvailableSelectables {
+    &schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&object_entity_id)
+        .expect("Expected object entity to have extra info")
+        .selectables
+}
+
+/// Follow the path of selections (e.g. ["best_friend", "stats"]) from the parent
+/// type of the literal, returning the type of the innermost selection set.
+pub(crate) fn object_entity_at_path<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_type: &str,
+    path: &[&str],
+) -> Option<ServerObjectEntityId> {
+    let mut object_entity_id = match schema
+        .server_entity_data
+        .defined_entities
+        .get(&parent_type.intern().into())?
+    {
+        ServerEntityId::Object(object_entity_id) => *object_entity_id,
+        ServerEntityId::Scalar(_) => return None,
+    };
+
+    for selection_name in path {
+        let selectable_id =
+            available_selectables(schema, object_entity_id).get(&selection_name.intern().into())?;
+        object_entity_id = target_object_entity_id(schema, *selectable_id)?;
+    }
+
+    Some(object_entity_id)
+}
+
+/// The type of the selection set of a server object field or client pointer, or
+/// `None` for scalar fields.
+pub(crate) fn target_object_entity_id<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selectable_id: SelectableId,
+) -> Option<ServerObjectEntityId> {
+    match selectable_id {
+        DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => Some(
+            *schema
+                .server_object_selectable(server_object_selectable_id)
+                .target_object_entity
+                .inner(),
+        ),
+        DefinitionLocation::Client(SelectionType::Object(client_pointer_id)) => Some(
+            *schema
+                .client_pointer(client_pointer_id)
+                .target_object_entity
+                .inner(),
+        ),
+        DefinitionLocation::Server(SelectionType::Scalar(_))
+        | DefinitionLocation::Client(SelectionType::Scalar(_)) => None,
+    }
+}
+
+fn field_completion_items<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selectables: &ServerObjectEntityAvailableSelectables,
+    field_scores: &HashMap<String, u32>,
+) -> Vec<CompletionItem> {
+    selectables
+        .iter()
+        .map(|(name, selectable_id)| {
+            let score = field_scores.get(name.lookup()).copied().unwrap_or(0);
+            field_completion_item(schema, *name, *selectable_id, score)
+        })
+        .collect()
+}
+
+fn field_completion_item<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    name: SelectableName,
+    selectable_id: SelectableId,
+    score: u32,
+) -> CompletionItem {
+    let (kind, detail, description, deprecated) = match selectable_id {
+        DefinitionLocation::Server(SelectionType::Scalar(id)) => {
+            let server_scalar_selectable = schema.server_scalar_selectable(id);
+            (
+                CompletionItemKind::FIELD,
+                format_type_annotation(&server_scalar_selectable.target_scalar_entity.clone().map(
+                    &mut |scalar_entity_id| {
+                        schema
+                            .server_entity_data
+                            .server_scalar_entity(scalar_entity_id)
+                            .name
+                            .item
+                    },
+                )),
+                server_scalar_selectable.description,
+                server_scalar_selectable.deprecation_reason.is_some(),
+            )
+        }
+        DefinitionLocation::Server(SelectionType::Object(id)) => {
+            let server_object_selectable = schema.server_object_selectable(id);
+            (
+                CompletionItemKind::FIELD,
+                format_type_annotation(&server_object_selectable.target_object_entity.clone().map(
+                    &mut |object_entity_id| {
+                        schema
+                            .server_entity_data
+                            .server_object_entity(object_entity_id)
+                            .name
+                    },
+                )),
+                server_object_selectable.description,
+                server_object_selectable.deprecation_reason.is_some(),
+            )
+        }
+        DefinitionLocation::Client(SelectionType::Scalar(id)) => {
+            let client_field = schema.client_field(id);
+            let detail = match client_field.variant {
+                ClientFieldVariant::UserWritten(_) => "client field",
+                ClientFieldVariant::ImperativelyLoadedField(_)
+                | ClientFieldVariant::Link
+                | ClientFieldVariant::Paginated(_) => "client field (generated)",
+            };
+            (
+                CompletionItemKind::FUNCTION,
+                detail.to_string(),
+                client_field.description,
+                false,
+            )
+        }
+        DefinitionLocation::Client(SelectionType::Object(id)) => {
+            let client_pointer = schema.client_pointer(id);
+            (
+                CompletionItemKind::FUNCTION,
+                "client pointer".to_string(),
+                client_pointer.description,
+                false,
+            )
+        }
+    };
+
+    CompletionItem {
+        label: name.to_string(),
+        kind: Some(kind),
+        detail: Some(detail),
+        documentation: description
+            .map(|description| Documentation::String(description.to_string())),
+        tags: deprecated.then(|| vec![CompletionItemTag::DEPRECATED]),
+        // Deprecated fields are sorted after all other fields, and better matches
+        // of the name being typed before worse ones.
+        sort_text: Some(format!(
+            "{}{:04}{}",
+            if deprecated { 1 } else { 0 },
+            9999 - score.min(9999),
+            name
+        )),
+        ..Default::default()
+    }
+}
+
+fn argument_completion_items<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selectable_id: SelectableId,
+) -> Vec<CompletionItem> {
+    let arguments = match selectable_id {
+        DefinitionLocation::Server(SelectionType::Scalar(id)) => schema
+            .server_scalar_selectable(id)
+            .arguments
+            .iter()
+            .map(|argument| &argument.item)
+            .collect::<Vec<_>>(),
+        DefinitionLocation::Server(SelectionType::Object(id)) => schema
+            .server_object_selectable(id)
+            .arguments
+            .iter()
+            .map(|argument| &argument.item)
+            .collect(),
+        DefinitionLocation::Client(SelectionType::Scalar(id)) => schema
+            .client_field(id)
+            .variable_definitions
+            .iter()
+            .map(|argument| &argument.item)
+            .collect(),
+        DefinitionLocation::Client(SelectionType::Object(id)) => schema
+            .client_pointer(id)
+            .variable_definitions
+            .iter()
+            .map(|argument| &argument.item)
+            .collect(),
+    };
+
+    arguments
+        .into_iter()
+        .map(|argument| CompletionItem {
+            label: argument.name.item.to_string(),
+            kind: Some(CompletionItemKind::VARIABLE),
+            detail: Some(
+                argument
+                    .type_
+                    .clone()
+                    .map(|entity_id| schema.server_entity_data.server_entity(entity_id).name())
+                    .to_string(),
+            ),
+            insert_text: Some(format!("{}: ", argument.name.item)),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn directive_completion_items(selectable_id: SelectableId) -> Vec<CompletionItem> {
+    let directives: &[(&str, &str)] = match selectable_id {
+        DefinitionLocation::Server(_) => &[(
+            "updatable",
+            "Allows the field to be modified on the client.",
+        )],
+        DefinitionLocation::Client(SelectionType::Scalar(_)) => &[(
+            "loadable",
+            "Fetches the client field on demand, instead of as part of the parent query.",
+        )],
+        DefinitionLocation::Client(SelectionType::Object(_)) => &[],
+    };
+
+    directives
+        .iter()
+        .map(|(name, description)| CompletionItem {
+            label: name.to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some(description.to_string()),
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CompletionContext<'a> {
+    /// The parent type of the client field or pointer, e.g. Pet in
+    /// `field Pet.PetBestFriendCard`
+    parent_type: &'a str,
+    /// The names of the selections enclosing the cursor
+    path: Vec<&'a str>,
+    kind: CompletionKind<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum CompletionKind<'a> {
+    Field,
+    Argument { field: &'a str },
+    Directive { field: &'a str },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Name(&'a str),
+    Punctuation(char),
+    String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExpectedArgumentToken {
+    Name,
+    Colon,
+    Value,
+}
+
+/// The state of an unclosed set of parentheses, i.e. of field or directive arguments.
+struct OpenParentheses {
+    expected_argument_token: ExpectedArgumentToken,
+    belongs_to_directive: bool,
+    /// The depth of lists and object literals within an argument value.
+    nesting: usize,
+}
+
+/// Determine what should be completed, given the text of an iso literal up to
+/// the cursor. The text is usually invalid (e.g. it has unclosed braces), so this
+/// does not use the parser.
+fn completion_context(literal_text_before_cursor: &str) -> Option<CompletionContext<'_>> {
+    // Ignore the name that is currently being typed.
+    let text = literal_text_before_cursor.trim_end_matches(is_name_char);
+    let tokens = tokenize(text);
+    let mut tokens = tokens.into_iter().peekable();
+
+    // Skip the description, if it comes before the `field` keyword.
+    tokens.next_if_eq(&Token::String);
+    match tokens.next()? {
+        Token::Name("field") | Token::Name("pointer") => {}
+        _ => return None,
+    }
+    let Token::Name(parent_type) = tokens.next()? else {
+        return None;
+    };
+
+    let mut path = vec![];
+    let mut brace_depth = 0;
+    let mut last_field = None;
+    let mut last_name_was_directive = false;
+    let mut previous_token = None;
+    let mut open_parentheses: Option<OpenParentheses> = None;
+
+    for token in tokens {
+        if let Some(parentheses) = &mut open_parentheses {
+            match token {
+                Token::Punctuation(')') if parentheses.nesting == 0 => open_parentheses = None,
+                Token::Punctuation('[') | Token::Punctuation('{') => parentheses.nesting += 1,
+                Token::Punctuation(']') | Token::Punctuation('}') => {
+                    parentheses.nesting = parentheses.nesting.saturating_sub(1);
+                    if parentheses.nesting == 0 {
+                        parentheses.expected_argument_token = ExpectedArgumentToken::Name;
+                    }
+                }
+                _ if parentheses.nesting > 0 => {}
+                Token::Punctuation(',') => {
+                    parentheses.expected_argument_token = ExpectedArgumentToken::Name
+                }
+                Token::Punctuation(':') => {
+                    parentheses.expected_argument_token = ExpectedArgumentToken::Value
+                }
+                Token::Name(_) | Token::String => {
+                    parentheses.expected_argument_token = match parentheses.expected_argument_token
+                    {
+                        ExpectedArgumentToken::Name => ExpectedArgumentToken::Colon,
+                        ExpectedArgumentToken::Colon | ExpectedArgumentToken::Value => {
+                            ExpectedArgumentToken::Name
+                        }
+                    }
+                }
+                Token::Punctuation(_) => {}
+            }
+        } else {
+            match token {
+                Token::Punctuation('(') => {
+                    open_parentheses = Some(OpenParentheses {
+                        expected_argument_token: ExpectedArgumentToken::Name,
+                        belongs_to_directive: last_name_was_directive,
+                        nesting: 0,
+                    });
+                }
+                Token::Punctuation('{') => {
+                    if brace_depth > 0 {
+                        path.push(last_field?);
+                    }
+                    brace_depth += 1;
+                    last_field = None;
+                }
+                Token::Punctuation('}') => {
+                    brace_depth -= 1;
+                    if brace_depth == 0 {
+                        // The selection set of the literal has been closed.
+                        return None;
+                    }
+                    path.pop();
+                    last_field = None;
+                }
+                Token::Name(name) => {
+                    last_name_was_directive = previous_token == Some(Token::Punctuation('@'));
+                    if brace_depth > 0 && !last_name_was_directive {
+                        last_field = Some(name);
+                    }
+                }
+                _ => {}
+            }
+        }
+        previous_token = Some(token);
+    }
+
+    if brace_depth == 0 {
+        return None;
+    }
+
+    let kind = match open_parentheses {
+        Some(OpenParentheses {
+            expected_argument_token: ExpectedArgumentToken::Name,
+            belongs_to_directive: false,
+            nesting: 0,
+        }) => CompletionKind::Argument { field: last_field? },
+        Some(_) => return None,
+        None if previous_token == Some(Token::Punctuation('@')) => {
+            CompletionKind::Directive { field: last_field? }
+        }
+        None => CompletionKind::Field,
+    };
+
+    Some(CompletionContext {
+        parent_type,
+        path,
+        kind,
+    })
+}
+
+fn is_name_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || char == '_'
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut index = 0;
+    while let Some(char) = text[index..].chars().next() {
+        let rest = &text[index..];
+        if is_name_char(char) {
+            let length = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+            tokens.push(Token::Name(&rest[..length]));
+            index += length;
+        } else if char == '#' {
+            // Comments extend to the end of the line
+            index += rest.find('\n').unwrap_or(rest.len());
+        } else if let Some(block_string) = rest.strip_prefix("\"\"\"") {
+            index += 3 + block_string
+                .find("\"\"\"")
+                .map(|end| end + 3)
+                .unwrap_or(block_string.len());
+            tokens.push(Token::String);
+        } else if let Some(string) = rest.strip_prefix('"') {
+            index += 1 + string.find('"').map(|end| end + 1).unwrap_or(string.len());
+            tokens.push(Token::String);
+        } else {
+            if !char.is_whitespace() {
+                tokens.push(Token::Punctuation(char));
+            }
+            index += char.len_utf8();
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod test {
+    use super::{completion_context, CompletionContext, CompletionKind};
+
+    #[test]
+    fn completes_fields_in_nested_selection_sets() {
+        assert_eq!(
+            completion_context(
+                "\n  field Pet.PetCard @component\n  \"\"\"\n  A { description\n  \"\"\"\n  {\n    \
+                    id\n    best_friend_relationship {\n      best_friend { na"
+            ),
+            Some(CompletionContext {
+                parent_type: "Pet",
+                path: vec!["best_friend_relationship", "best_friend"],
+                kind: CompletionKind::Field,
+            })
+        );
+        assert_eq!(
+            completion_context("field Pet.PetCard {\n  stats { id }\n  "),
+            Some(CompletionContext {
+                parent_type: "Pet",
+                path: vec![],
+                kind: CompletionKind::Field,
+            })
+        );
+        assert_eq!(
+            completion_context("\"\"\"\n  A description\n\"\"\"\nfield Pet.PetCard {\n  "),
+            Some(CompletionContext {
+                parent_type: "Pet",
+                path: vec![],
+                kind: CompletionKind::Field,
+            })
+        );
+    }
+
+    #[test]
+    fn completes_arguments_and_directives() {
+        assert_eq!(
+            completion_context("field Query.Foo {\n  alias: pet(id: $id, "),
+            Some(CompletionContext {
+                parent_type: "Query",
+                path: vec![],
+                kind: CompletionKind::Argument { field: "pet" },
+            })
+        );
+        assert_eq!(completion_context("field Query.Foo {\n  pet(id: "), None);
+        assert_eq!(
+            completion_context("field Query.Foo {\n  PetSummary @lo"),
+            Some(CompletionContext {
+                parent_type: "Query",
+                path: vec![],
+                kind: CompletionKind::Directive {
+                    field: "PetSummary"
+                },
+            })
+        );
+        assert_eq!(
+            completion_context("field Query.Foo {\n  PetSummary @loadable("),
+            None
+        );
+    }
+
+    #[test]
+    fn does_not_complete_outside_of_selection_sets() {
+        assert_eq!(completion_context("field Pet.Pe"), None);
+        assert_eq!(completion_context("entrypoint Query.HomeRoute"), None);
+        assert_eq!(completion_context("field Pet.PetCard { id }\n"), None);
+    }
+}
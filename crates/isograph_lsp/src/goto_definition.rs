@@ -0,0 +1,188 @@
+use common_lang_types::{ConstExportName, Location, RelativePathToSourceFile, Span};
+use intern::Lookup;
+use isograph_compiler::{extract_iso_literals_from_file_content, StandardSources};
+use isograph_config::IsoLiteralExtractor;
+use isograph_lang_types::{DefinitionLocation, SelectionType};
+use isograph_schema::{
+    ClientFieldVariant, ClientPointerVariant, ClientSelectableId, NetworkProtocol,
+    ObjectSelectableId, ScalarSelectableId, Schema,
+};
+use lsp_types::{
+    request::{GotoDefinition, Request},
+    GotoDefinitionParams, GotoDefinitionResponse, Url,
+};
+
+use crate::{
+    location_utils::{position_to_offset, span_in_file, span_to_range},
+    lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult},
+    lsp_state::LSPState,
+    selection_at_position::{client_selectable_at_offset, selection_at_offset},
+};
+
+/// Where a definition can be found.
+enum DefinitionTarget {
+    /// A span in a file, e.g. a field in the GraphQL schema.
+    Span {
+        relative_path: RelativePathToSourceFile,
+        span: Span,
+    },
+    /// The `export const` of a client field or pointer.
+    ConstExport {
+        relative_path: RelativePathToSourceFile,
+        const_export_name: ConstExportName,
+    },
+}
+
+pub fn on_goto_definition<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <GotoDefinition as Request>::Params,
+) -> LSPRuntimeResult<<GotoDefinition as Request>::Result> {
+    let GotoDefinitionParams {
+        text_document_position_params,
+        work_done_progress_params: _,
+        partial_result_params: _,
+    } = params;
+    let uri = text_document_position_params.text_document.uri;
+
+    let file_text = state
+        .text_for(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?
+        .to_string();
+    let relative_path = state
+        .relative_path(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let offset = position_to_offset(&file_text, text_document_position_params.position)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let current_working_directory = state.config().current_working_directory;
+    let iso_literal_extractor = state.config().options.iso_literal_extractor;
+
+    let schema = state.schema()?;
+    let target = match selection_at_offset(schema, relative_path, offset) {
+        Some(selection) => selectable_definition_target(schema, selection.selectable_id),
+        // The cursor may be on the name of an entrypoint or client field declaration
+        None => client_selectable_at_offset(
+            schema,
+            relative_path,
+            &file_text,
+            offset,
+            current_working_directory,
+            iso_literal_extractor,
+        )
+        .and_then(|client_selectable| {
+            client_selectable_definition_target(schema, client_selectable.client_selectable_id)
+        }),
+    };
+    let Some(target) = target else {
+        return Ok(None);
+    };
+
+    let relative_path = match target {
+        DefinitionTarget::Span { relative_path, .. }
+        | DefinitionTarget::ConstExport { relative_path, .. } => relative_path,
+    };
+    let target_text = state
+        .text_for_relative_path(relative_path)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let span = match target {
+        DefinitionTarget::Span { span, .. } => span,
+        DefinitionTarget::ConstExport {
+            const_export_name, ..
+        } => const_export_name_span(&target_text, const_export_name, iso_literal_extractor)
+            .ok_or(LSPRuntimeError::ExpectedError)?,
+    };
+    let target_uri = Url::from_file_path(state.absolute_path(relative_path)).map_err(|_| {
+        LSPRuntimeError::UnexpectedError(format!(
+            "Unable to convert {} to a url",
+            relative_path.lookup()
+        ))
+    })?;
+
+    Ok(Some(GotoDefinitionResponse::Scalar(lsp_types::Location {
+        uri: target_uri,
+        range: span_to_range(&target_text, span),
+    })))
+}
+
+fn selectable_definition_target<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selectable_id: SelectionType<ScalarSelectableId, ObjectSelectableId>,
+) -> Option<DefinitionTarget> {
+    match selectable_id {
+        SelectionType::Scalar(DefinitionLocation::Server(id)) => {
+            location_definition_target(&schema.server_scalar_selectable(id).name.location)
+        }
+        SelectionType::Object(DefinitionLocation::Server(id)) => {
+            location_definition_target(&schema.server_object_selectable(id).name.location)
+        }
+        SelectionType::Scalar(DefinitionLocation::Client(id)) => {
+            client_selectable_definition_target(schema, SelectionType::Scalar(id))
+        }
+        SelectionType::Object(DefinitionLocation::Client(id)) => {
+            client_selectable_definition_target(schema, SelectionType::Object(id))
+        }
+    }
+}
+
+fn client_selectable_definition_target<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_selectable_id: ClientSelectableId,
+) -> Option<DefinitionTarget> {
+    match client_selectable_id {
+        SelectionType::Scalar(client_field_id) => {
+            match &schema.client_field(client_field_id).variant {
+                ClientFieldVariant::UserWritten(info) => Some(DefinitionTarget::ConstExport {
+                    relative_path: info.file_path,
+                    const_export_name: info.const_export_name,
+                }),
+                // Fields generated by Isograph have no definition to jump to.
+                ClientFieldVariant::ImperativelyLoadedField(_)
+                | ClientFieldVariant::Link
+                | ClientFieldVariant::Paginated(_) => None,
+            }
+        }
+        SelectionType::Object(client_pointer_id) => {
+            let client_pointer = schema.client_pointer(client_pointer_id);
+            let info = &client_pointer.info;
+            match client_pointer.variant {
+                ClientPointerVariant::UserWritten => Some(DefinitionTarget::ConstExport {
+                    relative_path: info.file_path,
+                    const_export_name: info.const_export_name,
+                }),
+                // Node pointers are not exported, so we jump to the iso literal.
+                ClientPointerVariant::Node => Some(DefinitionTarget::Span {
+                    relative_path: info.file_path,
+                    span: info.text_source.span.unwrap_or(Span::new(0, 0)),
+                }),
+            }
+        }
+    }
+}
+
+fn location_definition_target(location: &Location) -> Option<DefinitionTarget> {
+    match location {
+        Location::Embedded(embedded_location) => Some(DefinitionTarget::Span {
+            relative_path: embedded_location.text_source.relative_path_to_source_file,
+            span: span_in_file(embedded_location),
+        }),
+        Location::Generated => None,
+    }
+}
+
+fn const_export_name_span(
+    text: &str,
+    const_export_name: ConstExportName,
+    iso_literal_extractor: IsoLiteralExtractor,
+) -> Option<Span> {
+    extract_iso_literals_from_file_content(text, iso_literal_extractor)
+        .into_iter()
+        .find_map(|extraction| {
+            if extraction.const_export_name != Some(const_export_name.lookup()) {
+                return None;
+            }
+            let start = extraction.const_export_name_start_index? as u32;
+            Some(Span::new(
+                start,
+                start + const_export_name.lookup().len() as u32,
+            ))
+        })
+}
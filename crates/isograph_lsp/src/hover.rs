@@ -0,0 +1,296 @@
+use std::fmt::Display;
+
+use common_lang_types::{DescriptionValue, ObjectTypeAndFieldName, WithSpan};
+use generate_artifacts::generate_output_type;
+use intern::Lookup;
+use isograph_compiler::StandardSources;
+use isograph_lang_types::{
+    DefinitionLocation, SelectionType, ServerEntityId, TypeAnnotation, UnionVariant,
+    VariableDefinition,
+};
+use isograph_schema::{
+    ClientFieldVariant, ClientObjectSelectable, ClientPointerVariant, ClientScalarSelectable,
+    NetworkProtocol, Schema, ServerObjectSelectable, ServerScalarOrObjectEntity,
+    ServerScalarSelectable,
+};
+use lsp_types::{
+    request::{HoverRequest, Request},
+    Hover, HoverContents, HoverParams, MarkupContent, MarkupKind,
+};
+
+use crate::{
+    location_utils::{position_to_offset, span_to_range},
+    lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult},
+    lsp_state::LSPState,
+    selection_at_position::selection_at_offset,
+};
+
+pub fn on_hover<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <HoverRequest as Request>::Params,
+) -> LSPRuntimeResult<<HoverRequest as Request>::Result> {
+    let HoverParams {
+        text_document_position_params,
+        work_done_progress_params: _,
+    } = params;
+    let uri = text_document_position_params.text_document.uri;
+
+    let file_text = state
+        .text_for(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?
+        .to_string();
+    let relative_path = state
+        .relative_path(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let offset = position_to_offset(&file_text, text_document_position_params.position)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+
+    let schema = state.schema()?;
+    let Some(selection) = selection_at_offset(schema, relative_path, offset) else {
+        return Ok(None);
+    };
+
+    let markdown = match selection.selectable_id {
+        SelectionType::Scalar(DefinitionLocation::Server(id)) => {
+            server_scalar_selectable_hover(schema, schema.server_scalar_selectable(id))
+        }
+        SelectionType::Scalar(DefinitionLocation::Client(id)) => {
+            client_field_hover(schema, schema.client_field(id))
+        }
+        SelectionType::Object(DefinitionLocation::Server(id)) => {
+            server_object_selectable_hover(schema, schema.server_object_selectable(id))
+        }
+        SelectionType::Object(DefinitionLocation::Client(id)) => {
+            client_pointer_hover(schema, schema.client_pointer(id))
+        }
+    };
+
+    Ok(Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: markdown,
+        }),
+        range: Some(span_to_range(&file_text, selection.name_span)),
+    }))
+}
+
+fn server_scalar_selectable_hover<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    server_scalar_selectable: &ServerScalarSelectable<TNetworkProtocol>,
+) -> String {
+    let target_type =
+        format_type_annotation(&server_scalar_selectable.target_scalar_entity.clone().map(
+            &mut |scalar_entity_id| {
+                schema
+                    .server_entity_data
+                    .server_scalar_entity(scalar_entity_id)
+                    .name
+                    .item
+            },
+        ));
+    let signature = format!(
+        "{}.{}{}: {}",
+        schema
+            .server_entity_data
+            .server_object_entity(server_scalar_selectable.parent_object_entity_id)
+            .name,
+        server_scalar_selectable.name.item,
+        format_arguments(
+            schema,
+            server_scalar_selectable.arguments.iter().map(|x| &x.item)
+        ),
+        target_type
+    );
+    let mut markdown = server_selectable_markdown(
+        signature,
+        server_scalar_selectable.description,
+        server_scalar_selectable.deprecation_reason,
+    );
+    let target_scalar_entity = schema
+        .server_entity_data
+        .server_scalar_entity(*server_scalar_selectable.target_scalar_entity.inner());
+    if let Some(specified_by_url) = target_scalar_entity.specified_by_url {
+        markdown.push_str(&format!(
+            "\n\n`{}` is specified by {specified_by_url}",
+            target_scalar_entity.name.item
+        ));
+    }
+    markdown
+}
+
+fn server_object_selectable_hover<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    server_object_selectable: &ServerObjectSelectable<TNetworkProtocol>,
+) -> String {
+    let target_type =
+        format_type_annotation(&server_object_selectable.target_object_entity.clone().map(
+            &mut |object_entity_id| {
+                schema
+                    .server_entity_data
+                    .server_object_entity(object_entity_id)
+                    .name
+            },
+        ));
+    let signature = format!(
+        "{}.{}{}: {}",
+        schema
+            .server_entity_data
+            .server_object_entity(server_object_selectable.parent_object_entity_id)
+            .name,
+        server_object_selectable.name.item,
+        format_arguments(
+            schema,
+            server_object_selectable.arguments.iter().map(|x| &x.item)
+        ),
+        target_type
+    );
+    server_selectable_markdown(
+        signature,
+        server_object_selectable.description,
+        server_object_selectable.deprecation_reason,
+    )
+}
+
+fn server_selectable_markdown(
+    signature: String,
+    description: Option<DescriptionValue>,
+    deprecation_reason: Option<DescriptionValue>,
+) -> String {
+    let mut markdown = format!("```graphql\n{signature}\n```");
+    if let Some(description) = description {
+        markdown.push_str(&format!("\n\n{description}"));
+    }
+    if let Some(deprecation_reason) = deprecation_reason {
+        markdown.push_str(&format!("\n\n**Deprecated:** {deprecation_reason}"));
+    }
+    markdown
+}
+
+fn client_field_hover<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> String {
+    let mut markdown = client_selectable_signature(
+        schema,
+        client_field.type_and_field,
+        &client_field.variable_definitions,
+        client_field.description,
+    );
+    match &client_field.variant {
+        ClientFieldVariant::UserWritten(info) => {
+            markdown.push_str(&format!(
+                "\n\nClient field defined by `export const {}` in `{}`",
+                info.const_export_name,
+                info.file_path.lookup()
+            ));
+        }
+        ClientFieldVariant::ImperativelyLoadedField(_)
+        | ClientFieldVariant::Link
+        | ClientFieldVariant::Paginated(_) => {
+            markdown.push_str("\n\nClient field generated by Isograph");
+        }
+    }
+    markdown.push_str(&format!(
+        "\n\nOutput type: `{}`",
+        generate_output_type(schema, client_field)
+    ));
+    markdown
+}
+
+fn client_pointer_hover<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_pointer: &ClientObjectSelectable<TNetworkProtocol>,
+) -> String {
+    let mut markdown = client_selectable_signature(
+        schema,
+        client_pointer.type_and_field,
+        &client_pointer.variable_definitions,
+        client_pointer.description,
+    );
+    match client_pointer.variant {
+        ClientPointerVariant::UserWritten => markdown.push_str(&format!(
+            "\n\nClient pointer defined by `export const {}` in `{}`",
+            client_pointer.info.const_export_name,
+            client_pointer.info.file_path.lookup()
+        )),
+        ClientPointerVariant::Node => markdown.push_str(&format!(
+            "\n\nNode pointer defined in `{}`",
+            client_pointer.info.file_path.lookup()
+        )),
+    }
+    let target_type = format_type_annotation(&client_pointer.target_object_entity.clone().map(
+        &mut |object_entity_id| {
+            schema
+                .server_entity_data
+                .server_object_entity(object_entity_id)
+                .name
+        },
+    ));
+    markdown.push_str(&format!("\n\nOutput type: `{target_type}`"));
+    markdown
+}
+
+fn client_selectable_signature<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    type_and_field: ObjectTypeAndFieldName,
+    variable_definitions: &[WithSpan<VariableDefinition<ServerEntityId>>],
+    description: Option<DescriptionValue>,
+) -> String {
+    let mut markdown = format!(
+        "```graphql\n{}.{}{}\n```",
+        type_and_field.type_name,
+        type_and_field.field_name,
+        format_arguments(schema, variable_definitions.iter().map(|x| &x.item))
+    );
+    if let Some(description) = description {
+        markdown.push_str(&format!("\n\n{description}"));
+    }
+    markdown
+}
+
+fn format_arguments<'a, TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    arguments: impl Iterator<Item = &'a VariableDefinition<ServerEntityId>>,
+) -> String {
+    let arguments = arguments
+        .map(|argument| {
+            format!(
+                "{}: {}",
+                argument.name.item,
+                argument
+                    .type_
+                    .clone()
+                    .map(|entity_id| schema.server_entity_data.server_entity(entity_id).name())
+            )
+        })
+        .collect::<Vec<_>>();
+    if arguments.is_empty() {
+        String::new()
+    } else {
+        format!("({})", arguments.join(", "))
+    }
+}
+
+/// Prints a type annotation in GraphQL syntax, e.g. `[Pet!]!`.
+pub(crate) fn format_type_annotation<T: Display>(type_annotation: &TypeAnnotation<T>) -> String {
+    match type_annotation {
+        TypeAnnotation::Scalar(scalar) => format!("{scalar}!"),
+        TypeAnnotation::Plural(inner) => format!("[{}]!", format_type_annotation(inner)),
+        TypeAnnotation::Union(union_type_annotation) => {
+            let variants = union_type_annotation
+                .variants
+                .iter()
+                .map(|variant| match variant {
+                    UnionVariant::Scalar(scalar) => scalar.to_string(),
+                    UnionVariant::Plural(inner) => format!("[{}]", format_type_annotation(inner)),
+                })
+                .collect::<Vec<_>>()
+                .join(" | ");
+            if union_type_annotation.nullable {
+                variants
+            } else {
+                format!("{variants}!")
+            }
+        }
+    }
+}
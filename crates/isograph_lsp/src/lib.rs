@@ -1,21 +1,35 @@
+use isograph_compiler::StandardSources;
 use isograph_config::CompilerConfig;
+use isograph_schema::NetworkProtocol;
 use lsp_process_error::LSPProcessResult;
 use lsp_server::Connection;
 
+mod code_actions;
+mod completion;
+mod goto_definition;
+mod hover;
+mod location_utils;
 pub mod lsp_notification_dispatch;
 pub mod lsp_process_error;
 mod lsp_request_dispatch;
 pub mod lsp_runtime_error;
 mod lsp_state;
-mod row_col_offset;
+mod references;
+mod rename;
+mod selection_at_position;
 mod semantic_tokens;
 pub mod server;
 pub mod text_document;
+mod workspace_symbols;
 
-pub async fn start_language_server(config: CompilerConfig) -> LSPProcessResult<()> {
+pub async fn start_language_server<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources> + 'static,
+>(
+    config: CompilerConfig,
+) -> LSPProcessResult<()> {
     let (connection, io_handles) = Connection::stdio();
     let params = server::initialize(&connection)?;
-    server::run(connection, config, params).await?;
+    server::run::<TNetworkProtocol>(connection, config, params).await?;
     io_handles.join()?;
     Ok(())
 }
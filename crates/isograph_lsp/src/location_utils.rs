@@ -0,0 +1,113 @@
+use common_lang_types::{EmbeddedLocation, Location, RelativePathToSourceFile, Span};
+use lsp_types::{Position, Range};
+
+/// Converts an LSP position into a byte offset into the text. Per the LSP spec,
+/// the character of a position is measured in UTF-16 code units.
+pub(crate) fn position_to_offset(text: &str, position: Position) -> Option<u32> {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+    let line = text[line_start..].split('\n').next().unwrap_or_default();
+
+    let mut utf16_count = 0;
+    for (byte_index, char) in line.char_indices() {
+        if utf16_count >= position.character {
+            return Some((line_start + byte_index) as u32);
+        }
+        utf16_count += char.len_utf16() as u32;
+    }
+    Some((line_start + line.len()) as u32)
+}
+
+/// Converts a byte offset into the text into an LSP position.
+pub(crate) fn offset_to_position(text: &str, offset: u32) -> Position {
+    let offset = (offset as usize).min(text.len());
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+pub(crate) fn span_to_range(text: &str, span: Span) -> Range {
+    Range {
+        start: offset_to_position(text, span.start),
+        end: offset_to_position(text, span.end),
+    }
+}
+
+/// The span of an embedded location, relative to the start of the file (instead
+/// of the start of the iso literal.)
+pub(crate) fn span_in_file(location: &EmbeddedLocation) -> Span {
+    let offset = location
+        .text_source
+        .span
+        .map(|span| span.start)
+        .unwrap_or(0);
+    location.span.with_offset(offset)
+}
+
+/// If the location is in the given file, returns its span relative to the start
+/// of the file.
+pub(crate) fn span_in_file_if_in(
+    location: &Location,
+    relative_path: RelativePathToSourceFile,
+) -> Option<Span> {
+    match location {
+        Location::Embedded(embedded_location)
+            if embedded_location.text_source.relative_path_to_source_file == relative_path =>
+        {
+            Some(span_in_file(embedded_location))
+        }
+        _ => None,
+    }
+}
+
+/// Whether the cursor is in (or immediately after) the span.
+pub(crate) fn span_contains_offset(span: Span, offset: u32) -> bool {
+    span.start <= offset && offset <= span.end
+}
+
+#[cfg(test)]
+mod test {
+    use lsp_types::Position;
+
+    use super::{offset_to_position, position_to_offset};
+
+    #[test]
+    fn position_and_offset_round_trip() {
+        let text = "const a = 1;\nconst ü = iso(`field Query.Foo { id }`);\n";
+        let position = Position {
+            line: 1,
+            character: 8,
+        };
+        let offset = position_to_offset(text, position).unwrap();
+        assert_eq!(&text[offset as usize..offset as usize + 1], "=");
+        assert_eq!(offset_to_position(text, offset), position);
+    }
+
+    #[test]
+    fn position_past_the_end_of_a_line() {
+        let text = "ab\ncd";
+        let offset = position_to_offset(
+            text,
+            Position {
+                line: 0,
+                character: 10,
+            },
+        );
+        assert_eq!(offset, Some(2));
+        assert_eq!(
+            position_to_offset(
+                text,
+                Position {
+                    line: 3,
+                    character: 0
+                }
+            ),
+            None
+        );
+    }
+}
@@ -1,40 +1,93 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, error::Error, path::PathBuf};
 
+use common_lang_types::{
+    decode_source, relative_path_from_absolute_and_working_directory, RelativePathToSourceFile,
+};
 use crossbeam::channel::Sender;
+use generate_artifacts::{SchemaSearchIndex, SCHEMA_SEARCH_INDEX_FILE_NAME};
+use intern::Lookup;
+use isograph_compiler::{
+    compile_sources, create_validated_schema, diagnostics_from_error, CompilationStats,
+    CompilerState, Diagnostic, FileSystem, OsFileSystem, OverlayFileSystem, PhaseTimings,
+    SourceFiles, StandardSources,
+};
 use isograph_config::CompilerConfig;
+use isograph_lang_types::IsoLiteralsSource;
+use isograph_schema::{NetworkProtocol, Schema};
 use lsp_server::Message;
 use lsp_types::Url;
 
-use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult};
 
-#[derive(Debug)]
-pub struct LSPState {
+pub struct LSPState<TNetworkProtocol: NetworkProtocol> {
     open_docs: HashMap<Url, String>,
     sender: Sender<Message>,
-    pub config: CompilerConfig,
+    pub compiler_state: CompilerState,
+    /// The most recently successfully created schema.
+    schema: Option<Schema<TNetworkProtocol>>,
+    schema_status: SchemaStatus,
+    /// The errors encountered when the schema was last created, if it could not be.
+    diagnostics: Vec<Diagnostic>,
+    /// The search index of the most recently created schema, or, until a schema
+    /// has been created, the one read from the artifact directory.
+    search_index: Option<SchemaSearchIndex>,
 }
 
-impl LSPState {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SchemaStatus {
+    /// A source file has changed since the schema was last created.
+    Stale,
+    /// The schema reflects the current contents of the source files.
+    Current,
+    /// The current contents of the source files do not compile. The schema (if
+    /// any) reflects an earlier state.
+    FailedToCreate,
+}
+
+impl<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>> LSPState<TNetworkProtocol> {
     pub fn new(sender: Sender<Message>, config: CompilerConfig) -> Self {
         LSPState {
             open_docs: HashMap::new(),
             sender,
-            config,
+            compiler_state: CompilerState::new(config),
+            schema: None,
+            schema_status: SchemaStatus::Stale,
+            diagnostics: vec![],
+            search_index: None,
         }
     }
 
+    pub fn config(&self) -> &CompilerConfig {
+        &self.compiler_state.config
+    }
+
     pub fn document_opened(&mut self, uri: &Url, text: &str) -> LSPRuntimeResult<()> {
         self.open_docs.insert(uri.to_owned(), text.to_owned());
+        self.set_iso_literals_source(uri, Some(text.to_owned()));
         Ok(())
     }
 
     pub fn document_changed(&mut self, uri: &Url, text: &str) -> LSPRuntimeResult<()> {
+        if self.schema.is_none() {
+            // Documents are often invalid while being edited. Create the schema
+            // before applying the change, so that there is a last valid schema.
+            self.refresh_schema();
+        }
         self.open_docs.insert(uri.to_owned(), text.to_owned());
+        self.set_iso_literals_source(uri, Some(text.to_owned()));
         Ok(())
     }
 
     pub fn document_closed(&mut self, uri: &Url) -> LSPRuntimeResult<()> {
         self.open_docs.remove(uri);
+        // The document may have been closed without saving, so we revert to the
+        // contents on disk.
+        let text_on_disk = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| OsFileSystem.read(&path).ok())
+            .map(|bytes| decode_source(&bytes).content);
+        self.set_iso_literals_source(uri, text_on_disk);
         Ok(())
     }
 
@@ -42,7 +95,234 @@ impl LSPState {
         self.open_docs.get(uri).map(|s| s.as_str())
     }
 
+    /// The text of a file, from the open document if there is one, and from
+    /// disk otherwise.
+    pub fn text_for_relative_path(
+        &self,
+        relative_path: RelativePathToSourceFile,
+    ) -> Option<String> {
+        let absolute_path = self.absolute_path(relative_path);
+        if let Some(text) = Url::from_file_path(&absolute_path)
+            .ok()
+            .and_then(|uri| self.text_for(&uri))
+        {
+            return Some(text.to_string());
+        }
+        OsFileSystem
+            .read(&absolute_path)
+            .ok()
+            .map(|bytes| decode_source(&bytes).content)
+    }
+
+    pub fn relative_path(&self, uri: &Url) -> Option<RelativePathToSourceFile> {
+        let path = uri.to_file_path().ok()?;
+        Some(relative_path_from_absolute_and_working_directory(
+            self.config().current_working_directory,
+            &path,
+        ))
+    }
+
+    pub fn absolute_path(&self, relative_path: RelativePathToSourceFile) -> PathBuf {
+        PathBuf::from(self.config().current_working_directory.lookup()).join(relative_path.lookup())
+    }
+
     pub fn send_message(&self, message: Message) {
         self.sender.send(message).unwrap();
     }
+
+    /// Returns the validated schema, including the contents of open (and possibly
+    /// unsaved) documents. If the project does not compile, returns an error.
+    pub fn schema(&mut self) -> LSPRuntimeResult<&Schema<TNetworkProtocol>> {
+        self.refresh_schema();
+        match self.schema_status {
+            SchemaStatus::Current => Ok(self
+                .schema
+                .as_ref()
+                .expect("Expected schema to have been set")),
+            SchemaStatus::Stale | SchemaStatus::FailedToCreate => {
+                Err(LSPRuntimeError::ExpectedError)
+            }
+        }
+    }
+
+    /// Returns the most recent schema that could be created, which may not reflect
+    /// the latest edits. Documents are usually invalid while they are being edited
+    /// (e.g. when requesting completions), so this is preferable to [`Self::schema`]
+    /// when locations need not be exact.
+    pub fn last_valid_schema(&mut self) -> LSPRuntimeResult<&Schema<TNetworkProtocol>> {
+        self.refresh_schema();
+        self.schema.as_ref().ok_or(LSPRuntimeError::ExpectedError)
+    }
+
+    /// Returns the search index of the most recent schema that could be created.
+    /// If no schema could be created yet (e.g. because creating it failed), the
+    /// index that the compiler last wrote to the artifact directory is used, if
+    /// the generate_schema_search_index option is enabled.
+    pub fn schema_search_index(&mut self) -> LSPRuntimeResult<&SchemaSearchIndex> {
+        self.refresh_schema();
+        if self.search_index.is_none() {
+            self.search_index = match &self.schema {
+                Some(schema) => Some(SchemaSearchIndex::new(schema)),
+                None => read_schema_search_index(self.config()),
+            };
+        }
+        self.search_index
+            .as_ref()
+            .ok_or(LSPRuntimeError::ExpectedError)
+    }
+
+    /// Returns the errors that prevent the current contents of the source files
+    /// from compiling.
+    pub fn diagnostics(&mut self) -> &[Diagnostic] {
+        self.refresh_schema();
+        &self.diagnostics
+    }
+
+    fn refresh_schema(&mut self) {
+        if self.schema_status != SchemaStatus::Stale {
+            return;
+        }
+        match self.create_schema() {
+            Ok(schema) => {
+                self.schema = Some(schema);
+                self.search_index = None;
+                self.schema_status = SchemaStatus::Current;
+                self.diagnostics.clear();
+            }
+            Err(e) => {
+                eprintln!("Unable to create schema: {e}");
+                self.schema_status = SchemaStatus::FailedToCreate;
+                self.diagnostics = diagnostics_from_error(e.as_ref());
+            }
+        }
+        // Otherwise, derived nodes (and their params) from previous versions of the
+        // source files would be retained for as long as the language server runs.
+        self.compiler_state.run_garbage_collection();
+    }
+
+    fn create_schema(&mut self) -> Result<Schema<TNetworkProtocol>, Box<dyn Error>> {
+        self.read_source_files_if_needed()?;
+        let source_files = self
+            .compiler_state
+            .source_files
+            .as_ref()
+            .expect("Expected source files to have been read");
+        let (schema, _) = create_validated_schema(
+            &self.compiler_state.db,
+            source_files,
+            &self.compiler_state.config,
+            &mut PhaseTimings::default(),
+        )?;
+        Ok(schema)
+    }
+
+    /// Compile the project, including the contents of open documents, and write
+    /// the artifacts.
+    pub fn write_artifacts(&mut self) -> Result<CompilationStats, Box<dyn Error>> {
+        self.read_source_files_if_needed()?;
+        let source_files = self
+            .compiler_state
+            .source_files
+            .as_ref()
+            .expect("Expected source files to have been read");
+        compile_sources::<TNetworkProtocol>(
+            &self.compiler_state.db,
+            &OsFileSystem,
+            source_files,
+            &self.compiler_state.config,
+            self.compiler_state.persist_artifacts.as_mut(),
+        )
+    }
+
+    fn read_source_files_if_needed(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.compiler_state.source_files.is_some() {
+            return Ok(());
+        }
+        // Documents may have been opened before we first read the source files, in
+        // which case their (possibly unsaved) contents take precedence.
+        let open_docs = self
+            .open_docs
+            .iter()
+            .filter_map(|(uri, text)| Some((uri.to_file_path().ok()?, text.clone())))
+            .collect();
+        let source_files = SourceFiles::read_all(
+            &mut self.compiler_state.db,
+            &OverlayFileSystem::new(&OsFileSystem, &open_docs),
+            &self.compiler_state.config,
+        )?;
+        self.compiler_state.source_files = Some(source_files);
+        Ok(())
+    }
+
+    /// Update the iso literals source for this document, if it is a source file in
+    /// this project. If content is None, the file no longer exists.
+    fn set_iso_literals_source(&mut self, uri: &Url, content: Option<String>) {
+        if let Some(relative_path) = source_file_relative_path(&self.compiler_state.config, uri) {
+            self.set_source_file_content(relative_path, content);
+        }
+    }
+
+    /// Update the iso literals source for a file, which need not be open (e.g. if
+    /// a rename edits it.) If content is None, the file no longer exists.
+    pub fn set_source_file_content(
+        &mut self,
+        relative_path: RelativePathToSourceFile,
+        content: Option<String>,
+    ) {
+        self.schema_status = SchemaStatus::Stale;
+
+        // If we have not read the source files yet, they will be read (and the open
+        // documents will be applied) the next time the schema is requested.
+        let Some(source_files) = self.compiler_state.source_files.as_mut() else {
+            return;
+        };
+        match content {
+            Some(content) => {
+                let source_id = self.compiler_state.db.set(IsoLiteralsSource {
+                    relative_path,
+                    content,
+                });
+                source_files.iso_literals.insert(relative_path, source_id);
+            }
+            None => {
+                if let Some(source_id) = source_files.iso_literals.remove(&relative_path) {
+                    self.compiler_state.db.remove(source_id);
+                }
+            }
+        }
+    }
+}
+
+fn read_schema_search_index(config: &CompilerConfig) -> Option<SchemaSearchIndex> {
+    let path = config
+        .artifact_directory
+        .absolute_path
+        .join(SCHEMA_SEARCH_INDEX_FILE_NAME.lookup());
+    let bytes = OsFileSystem.read(&path).ok()?;
+    serde_json::from_slice(&bytes)
+        .inspect_err(|e| eprintln!("Unable to read schema search index: {e}"))
+        .ok()
+}
+
+/// The relative path of the document, if it is a file that is searched for
+/// Isograph literals.
+fn source_file_relative_path(
+    config: &CompilerConfig,
+    uri: &Url,
+) -> Option<RelativePathToSourceFile> {
+    let path = uri.to_file_path().ok()?;
+    let has_valid_extension = matches!(
+        path.extension().and_then(|x| x.to_str()),
+        Some("ts") | Some("tsx") | Some("js") | Some("jsx")
+    );
+    if !has_valid_extension
+        || !config.source_file_filter.includes_file(&path)
+        || path.starts_with(&config.artifact_directory.absolute_path)
+    {
+        return None;
+    }
+    Some(relative_path_from_absolute_and_working_directory(
+        config.current_working_directory,
+        &path,
+    ))
 }
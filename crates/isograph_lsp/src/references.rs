@@ -0,0 +1,221 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use common_lang_types::{
+    Location, ObjectTypeAndFieldName, RelativePathToSourceFile, Span, WithSpan,
+};
+use intern::Lookup;
+use isograph_compiler::StandardSources;
+use isograph_lang_types::{SelectionType, SelectionTypeContainingSelections};
+use isograph_schema::{
+    ClientFieldVariant, ClientSelectableId, NetworkProtocol, Schema, ValidatedSelection,
+};
+use lsp_types::{
+    request::{References, Request},
+    ReferenceContext, ReferenceParams, Url,
+};
+
+use crate::{
+    location_utils::{position_to_offset, span_in_file, span_to_range},
+    lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult},
+    lsp_state::LSPState,
+    selection_at_position::{client_selectable_at_offset, declaration_name_span},
+};
+
+pub fn on_references<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <References as Request>::Params,
+) -> LSPRuntimeResult<<References as Request>::Result> {
+    let ReferenceParams {
+        text_document_position,
+        work_done_progress_params: _,
+        partial_result_params: _,
+        context: ReferenceContext {
+            include_declaration,
+        },
+    } = params;
+    let uri = text_document_position.text_document.uri;
+
+    let file_text = state
+        .text_for(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?
+        .to_string();
+    let relative_path = state
+        .relative_path(&uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let offset = position_to_offset(&file_text, text_document_position.position)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let current_working_directory = state.config().current_working_directory;
+    let iso_literal_extractor = state.config().options.iso_literal_extractor;
+
+    let schema = state.schema()?;
+    let Some(client_selectable) = client_selectable_at_offset(
+        schema,
+        relative_path,
+        &file_text,
+        offset,
+        current_working_directory,
+        iso_literal_extractor,
+    ) else {
+        return Ok(None);
+    };
+    let mut references =
+        client_selectable_references(schema, client_selectable.client_selectable_id);
+    let declaration = client_selectable_declaration(schema, client_selectable.client_selectable_id);
+
+    if include_declaration {
+        if let Some((declaration_path, type_and_field)) = declaration {
+            let declaration_text = state
+                .text_for_relative_path(declaration_path)
+                .ok_or(LSPRuntimeError::ExpectedError)?;
+            if let Some(span) = declaration_name_span(
+                declaration_path,
+                &declaration_text,
+                current_working_directory,
+                iso_literal_extractor,
+                type_and_field,
+            ) {
+                references.insert(0, (declaration_path, span));
+            }
+        }
+    }
+
+    Ok(Some(lsp_locations(state, references)?))
+}
+
+/// The file and name of the client field or pointer, if it is declared in an iso
+/// literal (i.e. is not generated by Isograph.)
+pub(crate) fn client_selectable_declaration<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_selectable_id: ClientSelectableId,
+) -> Option<(RelativePathToSourceFile, ObjectTypeAndFieldName)> {
+    match client_selectable_id {
+        SelectionType::Scalar(client_field_id) => {
+            let client_field = schema.client_field(client_field_id);
+            match &client_field.variant {
+                ClientFieldVariant::UserWritten(info) => {
+                    Some((info.file_path, client_field.type_and_field))
+                }
+                ClientFieldVariant::ImperativelyLoadedField(_)
+                | ClientFieldVariant::Link
+                | ClientFieldVariant::Paginated(_) => None,
+            }
+        }
+        SelectionType::Object(client_pointer_id) => {
+            let client_pointer = schema.client_pointer(client_pointer_id);
+            Some((client_pointer.info.file_path, client_pointer.type_and_field))
+        }
+    }
+}
+
+/// Every selection of the client field or pointer in a reader selection set, and
+/// (for client fields) every entrypoint declaration.
+pub(crate) fn client_selectable_references<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_selectable_id: ClientSelectableId,
+) -> Vec<(RelativePathToSourceFile, Span)> {
+    let mut references = vec![];
+    let selection_sets = schema
+        .client_scalar_selectables
+        .iter()
+        .map(|client_field| &client_field.reader_selection_set)
+        .chain(
+            schema
+                .client_object_selectables
+                .iter()
+                .map(|client_pointer| &client_pointer.reader_selection_set),
+        );
+    for selection_set in selection_sets {
+        collect_references_in_selection_set(selection_set, client_selectable_id, &mut references);
+    }
+
+    if let SelectionType::Scalar(client_field_id) = client_selectable_id {
+        if let Some(entrypoint) = schema.entrypoints.get(&client_field_id) {
+            references.extend(
+                entrypoint
+                    .client_field_name_locations
+                    .iter()
+                    .filter_map(embedded_location_in_file),
+            );
+        }
+    }
+
+    references.sort();
+    references.dedup();
+    references
+}
+
+fn collect_references_in_selection_set(
+    selection_set: &[WithSpan<ValidatedSelection>],
+    client_selectable_id: ClientSelectableId,
+    references: &mut Vec<(RelativePathToSourceFile, Span)>,
+) {
+    for selection in selection_set {
+        let (selected_client_selectable_id, name_location) = match &selection.item {
+            SelectionTypeContainingSelections::Scalar(scalar_selection) => (
+                scalar_selection
+                    .associated_data
+                    .as_client()
+                    .map(|client_field_id| SelectionType::Scalar(*client_field_id)),
+                &scalar_selection.name.location,
+            ),
+            SelectionTypeContainingSelections::Object(object_selection) => {
+                collect_references_in_selection_set(
+                    &object_selection.selection_set,
+                    client_selectable_id,
+                    references,
+                );
+                (
+                    object_selection
+                        .associated_data
+                        .as_client()
+                        .map(|client_pointer_id| SelectionType::Object(*client_pointer_id)),
+                    &object_selection.name.location,
+                )
+            }
+        };
+        if selected_client_selectable_id == Some(client_selectable_id) {
+            references.extend(embedded_location_in_file(name_location));
+        }
+    }
+}
+
+fn embedded_location_in_file(location: &Location) -> Option<(RelativePathToSourceFile, Span)> {
+    match location {
+        Location::Embedded(embedded_location) => Some((
+            embedded_location.text_source.relative_path_to_source_file,
+            span_in_file(embedded_location),
+        )),
+        Location::Generated => None,
+    }
+}
+
+/// Convert spans within files to LSP locations, reading each file once.
+pub(crate) fn lsp_locations<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &LSPState<TNetworkProtocol>,
+    spans: Vec<(RelativePathToSourceFile, Span)>,
+) -> LSPRuntimeResult<Vec<lsp_types::Location>> {
+    let mut texts = HashMap::new();
+    spans
+        .into_iter()
+        .map(|(relative_path, span)| {
+            let text = match texts.entry(relative_path) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    state
+                        .text_for_relative_path(relative_path)
+                        .ok_or(LSPRuntimeError::ExpectedError)?,
+                ),
+            };
+            let uri = Url::from_file_path(state.absolute_path(relative_path)).map_err(|_| {
+                LSPRuntimeError::UnexpectedError(format!(
+                    "Unable to convert {} to a url",
+                    relative_path.lookup()
+                ))
+            })?;
+            Ok(lsp_types::Location {
+                uri,
+                range: span_to_range(text, span),
+            })
+        })
+        .collect()
+}
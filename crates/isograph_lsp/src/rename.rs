@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use common_lang_types::{RelativePathToSourceFile, Span};
+use intern::{string_key::Intern, Lookup};
+use isograph_compiler::StandardSources;
+use isograph_lang_types::{DefinitionLocation, SelectionType};
+use isograph_schema::{ClientSelectableId, NetworkProtocol, Schema};
+use lsp_types::{
+    request::{PrepareRenameRequest, Rename, Request},
+    PrepareRenameResponse, RenameParams, TextDocumentPositionParams, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::{
+    location_utils::{position_to_offset, span_to_range},
+    lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult},
+    lsp_state::LSPState,
+    references::{client_selectable_declaration, client_selectable_references},
+    selection_at_position::{
+        client_selectable_at_offset, declaration_name_span, ClientSelectableAtOffset,
+    },
+};
+
+pub fn on_prepare_rename<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <PrepareRenameRequest as Request>::Params,
+) -> LSPRuntimeResult<<PrepareRenameRequest as Request>::Result> {
+    let Some((file_text, client_selectable)) =
+        renameable_client_selectable_at_position(state, params)?
+    else {
+        return Ok(None);
+    };
+    Ok(Some(PrepareRenameResponse::Range(span_to_range(
+        &file_text,
+        client_selectable.name_span,
+    ))))
+}
+
+/// Rename a client field or pointer. This renames the declaration, every selection
+/// of it and every entrypoint declaration, and regenerates the artifacts.
+///
+/// Note that selections are renamed, not aliased, so the field will be available
+/// under its new name in the data passed to resolvers.
+pub fn on_rename<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <Rename as Request>::Params,
+) -> LSPRuntimeResult<<Rename as Request>::Result> {
+    let RenameParams {
+        text_document_position,
+        new_name,
+        work_done_progress_params: _,
+    } = params;
+
+    if !is_valid_name(&new_name) {
+        return Err(LSPRuntimeError::UnexpectedError(format!(
+            "`{new_name}` is not a valid field name."
+        )));
+    }
+
+    let Some((_, client_selectable)) =
+        renameable_client_selectable_at_position(state, text_document_position)?
+    else {
+        return Ok(None);
+    };
+    let client_selectable_id = client_selectable.client_selectable_id;
+    let current_working_directory = state.config().current_working_directory;
+    let iso_literal_extractor = state.config().options.iso_literal_extractor;
+
+    let schema = state.schema()?;
+    check_for_conflicting_field(schema, client_selectable_id, &new_name)?;
+    let mut spans = client_selectable_references(schema, client_selectable_id);
+    let (declaration_path, type_and_field) =
+        client_selectable_declaration(schema, client_selectable_id)
+            .expect("Expected client selectable to be renameable");
+
+    let declaration_text = state
+        .text_for_relative_path(declaration_path)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let declaration_span = declaration_name_span(
+        declaration_path,
+        &declaration_text,
+        current_working_directory,
+        iso_literal_extractor,
+        type_and_field,
+    )
+    .ok_or(LSPRuntimeError::ExpectedError)?;
+    spans.push((declaration_path, declaration_span));
+
+    let mut spans_by_file: HashMap<RelativePathToSourceFile, Vec<Span>> = HashMap::new();
+    for (relative_path, span) in spans {
+        spans_by_file.entry(relative_path).or_default().push(span);
+    }
+
+    let mut changes = HashMap::new();
+    for (relative_path, mut spans) in spans_by_file {
+        let text = state
+            .text_for_relative_path(relative_path)
+            .ok_or(LSPRuntimeError::ExpectedError)?;
+        let uri = Url::from_file_path(state.absolute_path(relative_path)).map_err(|_| {
+            LSPRuntimeError::UnexpectedError(format!(
+                "Unable to convert {} to a url",
+                relative_path.lookup()
+            ))
+        })?;
+        let edits = spans
+            .iter()
+            .map(|span| TextEdit {
+                range: span_to_range(&text, *span),
+                new_text: new_name.clone(),
+            })
+            .collect::<Vec<_>>();
+        changes.insert(uri, edits);
+
+        // Apply the edits to our copy of the file, so that we can regenerate the
+        // artifacts. Edits are applied from the end of the file, so that earlier
+        // spans remain valid.
+        spans.sort_by_key(|span| std::cmp::Reverse(span.start));
+        let mut new_text = text;
+        for span in spans {
+            new_text.replace_range(span.as_usize_range(), &new_name);
+        }
+        state.set_source_file_content(relative_path, Some(new_text));
+    }
+
+    if let Err(e) = state.write_artifacts() {
+        eprintln!("Unable to regenerate artifacts after renaming: {e}");
+    }
+
+    Ok(Some(WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }))
+}
+
+/// The client field or pointer at the position, if it was declared in an iso
+/// literal (and can thus be renamed), along with the text of the document.
+fn renameable_client_selectable_at_position<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources>,
+>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: TextDocumentPositionParams,
+) -> LSPRuntimeResult<Option<(String, ClientSelectableAtOffset)>> {
+    let TextDocumentPositionParams {
+        text_document,
+        position,
+    } = params;
+    let file_text = state
+        .text_for(&text_document.uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?
+        .to_string();
+    let relative_path = state
+        .relative_path(&text_document.uri)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    let offset = position_to_offset(&file_text, position).ok_or(LSPRuntimeError::ExpectedError)?;
+    let current_working_directory = state.config().current_working_directory;
+    let iso_literal_extractor = state.config().options.iso_literal_extractor;
+
+    let schema = state.schema()?;
+    let Some(client_selectable) = client_selectable_at_offset(
+        schema,
+        relative_path,
+        &file_text,
+        offset,
+        current_working_directory,
+        iso_literal_extractor,
+    ) else {
+        return Ok(None);
+    };
+    if client_selectable_declaration(schema, client_selectable.client_selectable_id).is_none() {
+        return Err(LSPRuntimeError::UnexpectedError(
+            "Fields generated by Isograph cannot be renamed.".to_string(),
+        ));
+    }
+    Ok(Some((file_text, client_selectable)))
+}
+
+fn check_for_conflicting_field<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_selectable_id: ClientSelectableId,
+    new_name: &str,
+) -> LSPRuntimeResult<()> {
+    let parent_object_entity_id = match client_selectable_id {
+        SelectionType::Scalar(client_field_id) => {
+            schema.client_field(client_field_id).parent_object_entity_id
+        }
+        SelectionType::Object(client_pointer_id) => {
+            schema
+                .client_pointer(client_pointer_id)
+                .parent_object_entity_id
+        }
+    };
+    let parent_object = schema
+        .server_entity_data
+        .server_object_entity(parent_object_entity_id);
+    let existing_field = schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&parent_object_entity_id)
+        .expect("Expected object entity to have extra info")
+        .selectables
+        .get(&new_name.intern().into());
+
+    match existing_field {
+        Some(DefinitionLocation::Server(_)) => Err(LSPRuntimeError::UnexpectedError(format!(
+            "Cannot rename to `{new_name}`, because `{}` already has a server field named `{new_name}`.",
+            parent_object.name
+        ))),
+        Some(DefinitionLocation::Client(_)) => Err(LSPRuntimeError::UnexpectedError(format!(
+            "Cannot rename to `{new_name}`, because `{}` already has a client field or pointer named `{new_name}`.",
+            parent_object.name
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
@@ -0,0 +1,221 @@
+use common_lang_types::{
+    CurrentWorkingDirectory, ObjectTypeAndFieldName, RelativePathToSourceFile, SelectableName,
+    Span, TextSource, UnvalidatedTypeName, WithSpan,
+};
+use intern::Lookup;
+use isograph_compiler::parse_iso_literals_in_file_content;
+use isograph_config::IsoLiteralExtractor;
+use isograph_lang_parser::IsoLiteralExtractionResult;
+use isograph_lang_types::{
+    DefinitionLocation, SelectionType, SelectionTypeContainingSelections, ServerEntityId,
+};
+use isograph_schema::{
+    ClientFieldVariant, ClientSelectableId, NetworkProtocol, ObjectSelectableId,
+    ScalarSelectableId, Schema, ValidatedSelection,
+};
+
+use crate::location_utils::{span_contains_offset, span_in_file_if_in};
+
+/// A selection (in a client field or client pointer's reader selection set) that
+/// is under the cursor.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SelectionAtOffset {
+    pub selectable_id: SelectionType<ScalarSelectableId, ObjectSelectableId>,
+    /// The span of the selection's name, relative to the start of the file.
+    pub name_span: Span,
+}
+
+/// Find the selection whose name contains the offset, by walking the reader
+/// selection sets of every client field and client pointer defined in the file.
+pub(crate) fn selection_at_offset<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    relative_path: RelativePathToSourceFile,
+    offset: u32,
+) -> Option<SelectionAtOffset> {
+    let client_field_selection_sets = schema
+        .client_scalar_selectables
+        .iter()
+        .filter(|client_field| match &client_field.variant {
+            ClientFieldVariant::UserWritten(info) => info.file_path == relative_path,
+            ClientFieldVariant::ImperativelyLoadedField(_)
+            | ClientFieldVariant::Link
+            | ClientFieldVariant::Paginated(_) => false,
+        })
+        .map(|client_field| &client_field.reader_selection_set);
+    let client_pointer_selection_sets = schema
+        .client_object_selectables
+        .iter()
+        .filter(|client_pointer| client_pointer.info.file_path == relative_path)
+        .map(|client_pointer| &client_pointer.reader_selection_set);
+
+    client_field_selection_sets
+        .chain(client_pointer_selection_sets)
+        .find_map(|selection_set| {
+            selection_in_selection_set_at_offset(selection_set, relative_path, offset)
+        })
+}
+
+fn selection_in_selection_set_at_offset(
+    selection_set: &[WithSpan<ValidatedSelection>],
+    relative_path: RelativePathToSourceFile,
+    offset: u32,
+) -> Option<SelectionAtOffset> {
+    selection_set
+        .iter()
+        .find_map(|selection| match &selection.item {
+            SelectionTypeContainingSelections::Scalar(scalar_selection) => {
+                let name_span = span_in_file_if_in(&scalar_selection.name.location, relative_path)?;
+                span_contains_offset(name_span, offset).then_some(SelectionAtOffset {
+                    selectable_id: SelectionType::Scalar(scalar_selection.associated_data),
+                    name_span,
+                })
+            }
+            SelectionTypeContainingSelections::Object(object_selection) => {
+                let name_span = span_in_file_if_in(&object_selection.name.location, relative_path)?;
+                if span_contains_offset(name_span, offset) {
+                    return Some(SelectionAtOffset {
+                        selectable_id: SelectionType::Object(object_selection.associated_data),
+                        name_span,
+                    });
+                }
+                selection_in_selection_set_at_offset(
+                    &object_selection.selection_set,
+                    relative_path,
+                    offset,
+                )
+            }
+        })
+}
+
+/// A client field or client pointer that is selected, declared, or used as an
+/// entrypoint at the cursor.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClientSelectableAtOffset {
+    pub client_selectable_id: ClientSelectableId,
+    /// The span of the name, relative to the start of the file.
+    pub name_span: Span,
+}
+
+pub(crate) fn client_selectable_at_offset<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    relative_path: RelativePathToSourceFile,
+    file_text: &str,
+    offset: u32,
+    current_working_directory: CurrentWorkingDirectory,
+    iso_literal_extractor: IsoLiteralExtractor,
+) -> Option<ClientSelectableAtOffset> {
+    if let Some(selection) = selection_at_offset(schema, relative_path, offset) {
+        let client_selectable_id = match selection.selectable_id {
+            SelectionType::Scalar(DefinitionLocation::Client(client_field_id)) => {
+                SelectionType::Scalar(client_field_id)
+            }
+            SelectionType::Object(DefinitionLocation::Client(client_pointer_id)) => {
+                SelectionType::Object(client_pointer_id)
+            }
+            SelectionType::Scalar(DefinitionLocation::Server(_))
+            | SelectionType::Object(DefinitionLocation::Server(_)) => return None,
+        };
+        return Some(ClientSelectableAtOffset {
+            client_selectable_id,
+            name_span: selection.name_span,
+        });
+    }
+
+    parse_iso_literals_in_file_content(
+        relative_path,
+        file_text,
+        current_working_directory,
+        iso_literal_extractor,
+    )
+    .ok()?
+    .into_iter()
+    .find_map(|(extraction_result, text_source)| {
+        let (parent_type, name) = declared_type_and_name(&extraction_result);
+        let name_span = name.span.with_offset(literal_start(text_source));
+        if !span_contains_offset(name_span, offset) {
+            return None;
+        }
+
+        let parent_object_entity_id = match schema
+            .server_entity_data
+            .defined_entities
+            .get(&parent_type)?
+        {
+            ServerEntityId::Object(object_entity_id) => *object_entity_id,
+            ServerEntityId::Scalar(_) => return None,
+        };
+        let client_selectable_id = match schema
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&parent_object_entity_id)?
+            .selectables
+            .get(&name.item)?
+        {
+            DefinitionLocation::Client(client_selectable_id) => *client_selectable_id,
+            DefinitionLocation::Server(_) => return None,
+        };
+        Some(ClientSelectableAtOffset {
+            client_selectable_id,
+            name_span,
+        })
+    })
+}
+
+/// Find the span of the name of the client field or pointer declared in the file,
+/// e.g. of `PetUpdater` in iso(`field Pet.PetUpdater { ... }`).
+pub(crate) fn declaration_name_span(
+    relative_path: RelativePathToSourceFile,
+    file_text: &str,
+    current_working_directory: CurrentWorkingDirectory,
+    iso_literal_extractor: IsoLiteralExtractor,
+    type_and_field: ObjectTypeAndFieldName,
+) -> Option<Span> {
+    parse_iso_literals_in_file_content(
+        relative_path,
+        file_text,
+        current_working_directory,
+        iso_literal_extractor,
+    )
+    .ok()?
+    .into_iter()
+    .find_map(|(extraction_result, text_source)| {
+        if matches!(
+            extraction_result,
+            IsoLiteralExtractionResult::EntrypointDeclaration(_)
+        ) {
+            return None;
+        }
+        let (parent_type, name) = declared_type_and_name(&extraction_result);
+        (parent_type.lookup() == type_and_field.type_name.lookup()
+            && name.item == type_and_field.field_name)
+            .then(|| name.span.with_offset(literal_start(text_source)))
+    })
+}
+
+/// The parent type and name of the client field or pointer declared (or used as
+/// an entrypoint) in an iso literal.
+fn declared_type_and_name(
+    extraction_result: &IsoLiteralExtractionResult,
+) -> (UnvalidatedTypeName, WithSpan<SelectableName>) {
+    match extraction_result {
+        IsoLiteralExtractionResult::ClientFieldDeclaration(declaration) => (
+            declaration.item.parent_type.item,
+            declaration.item.client_field_name.map(SelectableName::from),
+        ),
+        IsoLiteralExtractionResult::ClientPointerDeclaration(declaration) => (
+            declaration.item.parent_type.item,
+            declaration
+                .item
+                .client_pointer_name
+                .map(SelectableName::from),
+        ),
+        IsoLiteralExtractionResult::EntrypointDeclaration(declaration) => (
+            declaration.item.parent_type.item,
+            declaration.item.client_field_name.map(SelectableName::from),
+        ),
+    }
+}
+
+fn literal_start(text_source: TextSource) -> u32 {
+    text_source.span.map(|span| span.start).unwrap_or(0)
+}
@@ -0,0 +1,180 @@
+use common_lang_types::Span;
+use isograph_lang_parser::{IsographLangTokenKind, PeekableLexer};
+
+use super::{
+    semantic_token_legend::{
+        semantic_token_modifier_readonly, semantic_token_type_decorator,
+        semantic_token_type_keyword, semantic_token_type_number, semantic_token_type_operator,
+        semantic_token_type_parameter, semantic_token_type_property, semantic_token_type_string,
+        semantic_token_type_type, semantic_token_type_variable,
+    },
+    IdentifierTokenKinds, SemanticTokenKind,
+};
+
+/// Classify every token in an iso literal, in order. Identifiers are classified
+/// using the token kinds determined from the parsed literal, if any. Otherwise
+/// (e.g. for directives, or if the literal could not be parsed), they are
+/// classified using the surrounding tokens.
+///
+/// Spans are relative to the start of the iso literal.
+pub(crate) fn iso_literal_tokens(
+    iso_literal_text: &str,
+    identifier_token_kinds: &IdentifierTokenKinds,
+) -> Vec<(Span, SemanticTokenKind)> {
+    let mut lexer = PeekableLexer::new(iso_literal_text);
+    let mut tokens = vec![];
+    let mut is_first_token = true;
+
+    loop {
+        let token = lexer.parse_token();
+        let token_kind = match token.item {
+            IsographLangTokenKind::EndOfFile => break,
+            IsographLangTokenKind::At => {
+                // Directives are not stored in the parsed literal, so we highlight
+                // @ and the directive name together.
+                if lexer.peek().item == IsographLangTokenKind::Identifier {
+                    let name = lexer.parse_token();
+                    tokens.push((
+                        Span::join(token.span, name.span),
+                        SemanticTokenKind::new(semantic_token_type_decorator()),
+                    ));
+                    continue;
+                }
+                SemanticTokenKind::new(semantic_token_type_operator())
+            }
+            IsographLangTokenKind::Dollar => {
+                if lexer.peek().item == IsographLangTokenKind::Identifier {
+                    let name = lexer.parse_token();
+                    tokens.push((
+                        Span::join(token.span, name.span),
+                        SemanticTokenKind::new(semantic_token_type_variable())
+                            .with_modifiers(semantic_token_modifier_readonly()),
+                    ));
+                    continue;
+                }
+                SemanticTokenKind::new(semantic_token_type_operator())
+            }
+            IsographLangTokenKind::Identifier => match identifier_token_kinds.get(&token.span) {
+                Some(token_kind) => *token_kind,
+                None => unknown_identifier_token_kind(
+                    lexer.source(token.span),
+                    is_first_token,
+                    lexer.peek().item == IsographLangTokenKind::Colon,
+                ),
+            },
+            IsographLangTokenKind::Spread => {
+                // Inline fragments (e.g. `... on User`) are stored as `asUser` fields,
+                // so we classify `on` and the type here.
+                tokens.push((
+                    token.span,
+                    SemanticTokenKind::new(semantic_token_type_operator()),
+                ));
+                if let Ok(on) = lexer.parse_matching_identifier("on") {
+                    tokens.push((
+                        on.span,
+                        SemanticTokenKind::new(semantic_token_type_keyword()),
+                    ));
+                    if lexer.peek().item == IsographLangTokenKind::Identifier {
+                        let type_name = lexer.parse_token();
+                        tokens.push((
+                            type_name.span,
+                            SemanticTokenKind::new(semantic_token_type_type()),
+                        ));
+                    }
+                }
+                continue;
+            }
+            IsographLangTokenKind::IntegerLiteral | IsographLangTokenKind::FloatLiteral => {
+                SemanticTokenKind::new(semantic_token_type_number())
+            }
+            IsographLangTokenKind::StringLiteral => {
+                SemanticTokenKind::new(semantic_token_type_string())
+            }
+            IsographLangTokenKind::CloseBrace
+            | IsographLangTokenKind::CloseBracket
+            | IsographLangTokenKind::CloseParen
+            | IsographLangTokenKind::Colon
+            | IsographLangTokenKind::Comma
+            | IsographLangTokenKind::Equals
+            | IsographLangTokenKind::Exclamation
+            | IsographLangTokenKind::OpenBrace
+            | IsographLangTokenKind::OpenBracket
+            | IsographLangTokenKind::OpenParen
+            | IsographLangTokenKind::Period => {
+                SemanticTokenKind::new(semantic_token_type_operator())
+            }
+            // Descriptions can span multiple lines, which many clients do not support
+            // in semantic tokens. They are highlighted as part of the template literal.
+            IsographLangTokenKind::BlockStringLiteral
+            | IsographLangTokenKind::Error
+            | IsographLangTokenKind::ErrorUnterminatedString
+            | IsographLangTokenKind::ErrorUnsupportedStringCharacter
+            | IsographLangTokenKind::ErrorUnterminatedBlockString
+            | IsographLangTokenKind::ErrorNumberLiteralLeadingZero
+            | IsographLangTokenKind::ErrorNumberLiteralTrailingInvalid
+            | IsographLangTokenKind::ErrorFloatLiteralMissingZero => {
+                is_first_token &= token.item == IsographLangTokenKind::BlockStringLiteral;
+                continue;
+            }
+        };
+        // The `field` or `pointer` keyword can be preceded by a description.
+        is_first_token &= token.item == IsographLangTokenKind::StringLiteral;
+        tokens.push((token.span, token_kind));
+    }
+
+    tokens
+}
+
+fn unknown_identifier_token_kind(
+    identifier: &str,
+    is_first_token: bool,
+    is_followed_by_colon: bool,
+) -> SemanticTokenKind {
+    match identifier {
+        "field" | "pointer" | "entrypoint" if is_first_token => {
+            SemanticTokenKind::new(semantic_token_type_keyword())
+        }
+        "to" | "true" | "false" | "null" => SemanticTokenKind::new(semantic_token_type_keyword()),
+        // e.g. the arguments of directives
+        _ if is_followed_by_colon => SemanticTokenKind::new(semantic_token_type_parameter()),
+        _ => SemanticTokenKind::new(semantic_token_type_property()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn token_texts(iso_literal_text: &str) -> Vec<(&str, u32)> {
+        iso_literal_tokens(iso_literal_text, &IdentifierTokenKinds::new())
+            .into_iter()
+            .map(|(span, token_kind)| {
+                (
+                    &iso_literal_text[span.as_usize_range()],
+                    token_kind.token_type,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn classifies_directives_variables_and_keywords() {
+        let tokens =
+            token_texts("field Query.Foo @loadable(lazyLoadArtifact: true) { pet(id: $id) }");
+        assert!(tokens.contains(&("field", semantic_token_type_keyword())));
+        assert!(tokens.contains(&("@loadable", semantic_token_type_decorator())));
+        assert!(tokens.contains(&("lazyLoadArtifact", semantic_token_type_parameter())));
+        assert!(tokens.contains(&("true", semantic_token_type_keyword())));
+        assert!(tokens.contains(&("$id", semantic_token_type_variable())));
+        assert!(tokens.contains(&("pet", semantic_token_type_property())));
+    }
+
+    #[test]
+    fn classifies_inline_fragments() {
+        let tokens = token_texts("field Actor.Greeting { ... on User { name } }");
+        assert!(tokens.contains(&("...", semantic_token_type_operator())));
+        assert!(tokens.contains(&("on", semantic_token_type_keyword())));
+        assert!(tokens.contains(&("User", semantic_token_type_type())));
+        assert!(tokens.contains(&("name", semantic_token_type_property())));
+    }
+}
@@ -1,28 +1,59 @@
 mod client_field;
 mod entrypoint;
+mod lexical_tokens;
+mod selection_set;
 mod semantic_token_generator;
 pub(crate) mod semantic_token_legend;
 
-use std::path::PathBuf;
+use std::collections::HashMap;
 
-use crate::{
-    lsp_runtime_error::LSPRuntimeResult,
-    lsp_state::LSPState,
-    row_col_offset::{diff_to_end_of_slice, get_index_from_diff, RowColDiff},
+use crate::{lsp_runtime_error::LSPRuntimeResult, lsp_state::LSPState};
+use client_field::{client_field_declaration_token_kinds, client_pointer_declaration_token_kinds};
+use common_lang_types::{Span, TextSource};
+use entrypoint::entrypoint_declaration_token_kinds;
+use isograph_compiler::{
+    extract_iso_literals_from_file_content, IsoLiteralExtraction, StandardSources,
 };
-use client_field::client_field_declaration_to_tokens;
-use common_lang_types::{relative_path_from_absolute_and_working_directory, Span, TextSource};
-use entrypoint::entrypoint_declaration_to_tokens;
-use intern::string_key::Intern;
-use isograph_compiler::{extract_iso_literals_from_file_content, IsoLiteralExtraction};
-use isograph_lang_parser::{parse_iso_literal, IsoLiteralExtractionResult};
+use isograph_lang_parser::{parse_iso_literal_with_recovery, IsoLiteralExtractionResult};
+use isograph_schema::{NetworkProtocol, Schema};
+use lexical_tokens::iso_literal_tokens;
 use lsp_types::{
     request::{Request, SemanticTokensFullRequest},
-    SemanticToken, SemanticTokens, SemanticTokensParams, SemanticTokensResult,
+    SemanticTokens, SemanticTokensParams, SemanticTokensResult,
 };
+use semantic_token_generator::SemanticTokenGenerator;
 
-pub fn on_semantic_token_full_request(
-    state: &mut LSPState,
+/// A token type (an index into the legend's token types) and a bitset of modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SemanticTokenKind {
+    token_type: u32,
+    token_modifiers_bitset: u32,
+}
+
+impl SemanticTokenKind {
+    pub(crate) fn new(token_type: u32) -> Self {
+        Self {
+            token_type,
+            token_modifiers_bitset: 0,
+        }
+    }
+
+    pub(crate) fn with_modifiers(self, token_modifiers_bitset: u32) -> Self {
+        Self {
+            token_type: self.token_type,
+            token_modifiers_bitset: self.token_modifiers_bitset | token_modifiers_bitset,
+        }
+    }
+}
+
+/// The token kinds of identifiers in an iso literal, keyed by their span relative to
+/// the start of the literal.
+pub(crate) type IdentifierTokenKinds = HashMap<Span, SemanticTokenKind>;
+
+pub fn on_semantic_token_full_request<
+    TNetworkProtocol: NetworkProtocol<Sources = StandardSources>,
+>(
+    state: &mut LSPState<TNetworkProtocol>,
     params: <SemanticTokensFullRequest as Request>::Params,
 ) -> LSPRuntimeResult<<SemanticTokensFullRequest as Request>::Result> {
     let SemanticTokensParams {
@@ -31,22 +62,30 @@ pub fn on_semantic_token_full_request(
         partial_result_params: _,
     } = params;
 
-    let file_text = state.text_for(&text_document.uri).unwrap_or_else(|| {
-        panic!(
-            "Retrieving semantic tokens for document {}, which has not been opened before.",
-            text_document.uri
-        )
-    });
-    let literal_extractions = extract_iso_literals_from_file_content(file_text);
-    let mut semantic_tokens = vec![];
+    let file_text = state
+        .text_for(&text_document.uri)
+        .unwrap_or_else(|| {
+            panic!(
+                "Retrieving semantic tokens for document {}, which has not been opened before.",
+                text_document.uri
+            )
+        })
+        .to_string();
+    let relative_path = state.relative_path(&text_document.uri);
+    let current_working_directory = state.config().current_working_directory;
+    let iso_literal_extractor = state.config().options.iso_literal_extractor;
+
+    // The schema is used to distinguish server fields from client fields. The
+    // document is probably invalid while it is being edited, so we use the last
+    // schema that could be created. If there is none, we highlight without it.
+    let schema = state.last_valid_schema().ok();
 
-    // SemanticTokens are all relative to the start of the previous one, so we have to
-    // keep track of the start of the last token that we have pushed onto
-    // semantic_tokens
-    let mut index_of_last_token = 0;
+    let mut semantic_token_generator = SemanticTokenGenerator::new(&file_text);
 
     // N.B. we are relying on the literal extractions being in order on the page.
-    for literal_extraction in literal_extractions {
+    for literal_extraction in
+        extract_iso_literals_from_file_content(&file_text, iso_literal_extractor)
+    {
         let IsoLiteralExtraction {
             iso_literal_text,
             iso_literal_start_index,
@@ -54,66 +93,70 @@ pub fn on_semantic_token_full_request(
             ..
         } = literal_extraction;
 
-        let initial_diff =
-            diff_to_end_of_slice(&file_text[index_of_last_token..iso_literal_start_index]);
-
-        let file_path = relative_path_from_absolute_and_working_directory(
-            state.config.current_working_directory,
-            &PathBuf::from(text_document.uri.path()),
-        );
-        let text_source = TextSource {
-            relative_path_to_source_file: file_path,
-            span: Some(Span::new(
-                iso_literal_start_index as u32,
-                (iso_literal_start_index + iso_literal_text.len()) as u32,
-            )),
-            current_working_directory: state.config.current_working_directory,
-        };
-        let iso_literal_extraction_result = parse_iso_literal(
-            iso_literal_text,
-            text_document.uri.path().intern().into(),
-            const_export_name,
-            text_source,
-        );
-        if let Ok(iso_literal_extraction_result) = iso_literal_extraction_result {
-            // token_diff is from the start of the previous last token to the
-            // start of the current last token
-            let (new_tokens, token_diff) = iso_literal_parse_result_to_tokens(
-                iso_literal_extraction_result,
+        let mut identifier_token_kinds = IdentifierTokenKinds::new();
+        if let Some(relative_path) = relative_path {
+            let text_source = TextSource {
+                relative_path_to_source_file: relative_path,
+                span: Some(Span::new(
+                    iso_literal_start_index as u32,
+                    (iso_literal_start_index + iso_literal_text.len()) as u32,
+                )),
+                current_working_directory,
+            };
+            // If the literal contains errors, we still classify the parts that could
+            // be parsed.
+            if let (Some(iso_literal_extraction_result), _) = parse_iso_literal_with_recovery(
                 iso_literal_text,
-                initial_diff,
+                relative_path,
+                const_export_name,
+                text_source,
+            ) {
+                iso_literal_identifier_token_kinds(
+                    schema,
+                    &iso_literal_extraction_result,
+                    &mut identifier_token_kinds,
+                );
+            }
+        }
+
+        for (span, token_kind) in iso_literal_tokens(iso_literal_text, &identifier_token_kinds) {
+            semantic_token_generator.generate_semantic_token(
+                span.with_offset(iso_literal_start_index as u32),
+                token_kind.token_type,
+                token_kind.token_modifiers_bitset,
             );
-            semantic_tokens.extend(new_tokens);
-            let additional_index =
-                get_index_from_diff(&file_text[index_of_last_token..], token_diff);
-            index_of_last_token += additional_index;
         }
     }
+
     let result = SemanticTokensResult::Tokens(SemanticTokens {
-        data: semantic_tokens,
+        data: semantic_token_generator.consume(),
         result_id: None,
     });
     Ok(Some(result))
 }
 
-fn iso_literal_parse_result_to_tokens(
-    iso_literal_extraction_result: IsoLiteralExtractionResult,
-    iso_literal_text: &str,
-    initial_diff: RowColDiff,
-) -> (Vec<SemanticToken>, RowColDiff) {
+fn iso_literal_identifier_token_kinds<TNetworkProtocol: NetworkProtocol>(
+    schema: Option<&Schema<TNetworkProtocol>>,
+    iso_literal_extraction_result: &IsoLiteralExtractionResult,
+    identifier_token_kinds: &mut IdentifierTokenKinds,
+) {
     match iso_literal_extraction_result {
         IsoLiteralExtractionResult::ClientFieldDeclaration(client_field_declaration) => {
-            client_field_declaration_to_tokens(
-                client_field_declaration,
-                iso_literal_text,
-                initial_diff,
+            client_field_declaration_token_kinds(
+                schema,
+                &client_field_declaration.item,
+                identifier_token_kinds,
             )
         }
-        IsoLiteralExtractionResult::ClientPointerDeclaration(_) => {
-            todo!()
+        IsoLiteralExtractionResult::ClientPointerDeclaration(client_pointer_declaration) => {
+            client_pointer_declaration_token_kinds(
+                schema,
+                &client_pointer_declaration.item,
+                identifier_token_kinds,
+            )
         }
         IsoLiteralExtractionResult::EntrypointDeclaration(entrypoint_declaration) => {
-            entrypoint_declaration_to_tokens(entrypoint_declaration, iso_literal_text, initial_diff)
+            entrypoint_declaration_token_kinds(&entrypoint_declaration.item, identifier_token_kinds)
         }
     }
 }
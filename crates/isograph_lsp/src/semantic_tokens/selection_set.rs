@@ -0,0 +1,125 @@
+use common_lang_types::{SelectableAlias, SelectableName, WithLocation, WithSpan};
+use isograph_lang_types::{
+    DefinitionLocation, SelectionFieldArgument, SelectionType, SelectionTypeContainingSelections,
+    ServerObjectEntityId, UnvalidatedSelection,
+};
+use isograph_schema::{NetworkProtocol, Schema};
+
+use crate::completion::{available_selectables, target_object_entity_id, SelectableId};
+
+use super::{
+    semantic_token_legend::{
+        semantic_token_modifier_deprecated, semantic_token_type_method,
+        semantic_token_type_parameter, semantic_token_type_property, semantic_token_type_variable,
+    },
+    IdentifierTokenKinds, SemanticTokenKind,
+};
+
+/// Classify the field names, aliases and argument names in a selection set. Server
+/// fields are properties and client fields and pointers are methods. If there is no
+/// schema, or a field cannot be found in it, we assume it is a server field.
+pub(crate) fn selection_set_token_kinds<TNetworkProtocol: NetworkProtocol>(
+    schema: Option<&Schema<TNetworkProtocol>>,
+    parent_object_entity_id: Option<ServerObjectEntityId>,
+    selection_set: &[WithSpan<UnvalidatedSelection>],
+    token_kinds: &mut IdentifierTokenKinds,
+) {
+    for selection in selection_set {
+        match &selection.item {
+            SelectionTypeContainingSelections::Scalar(scalar_selection) => {
+                selection_token_kinds(
+                    schema,
+                    parent_object_entity_id,
+                    scalar_selection.name.map(SelectableName::from),
+                    scalar_selection.reader_alias.as_ref(),
+                    &scalar_selection.arguments,
+                    token_kinds,
+                );
+            }
+            SelectionTypeContainingSelections::Object(object_selection) => {
+                let selectable_id = selection_token_kinds(
+                    schema,
+                    parent_object_entity_id,
+                    object_selection.name.map(SelectableName::from),
+                    object_selection.reader_alias.as_ref(),
+                    &object_selection.arguments,
+                    token_kinds,
+                );
+                selection_set_token_kinds(
+                    schema,
+                    schema
+                        .zip(selectable_id)
+                        .and_then(|(schema, selectable_id)| {
+                            target_object_entity_id(schema, selectable_id)
+                        }),
+                    &object_selection.selection_set,
+                    token_kinds,
+                );
+            }
+        }
+    }
+}
+
+/// Classify the name, alias and arguments of a selection, and return the selected
+/// field, if it can be found.
+fn selection_token_kinds<TNetworkProtocol: NetworkProtocol>(
+    schema: Option<&Schema<TNetworkProtocol>>,
+    parent_object_entity_id: Option<ServerObjectEntityId>,
+    name: WithLocation<SelectableName>,
+    reader_alias: Option<&WithLocation<SelectableAlias>>,
+    arguments: &[WithLocation<SelectionFieldArgument>],
+    token_kinds: &mut IdentifierTokenKinds,
+) -> Option<SelectableId> {
+    let selectable_id =
+        schema
+            .zip(parent_object_entity_id)
+            .and_then(|(schema, parent_object_entity_id)| {
+                available_selectables(schema, parent_object_entity_id)
+                    .get(&name.item)
+                    .copied()
+            });
+
+    if let Some(span) = name.location.span() {
+        let token_kind = match (schema, selectable_id) {
+            (Some(schema), Some(selectable_id)) => selectable_token_kind(schema, selectable_id),
+            _ => SemanticTokenKind::new(semantic_token_type_property()),
+        };
+        token_kinds.insert(span, token_kind);
+    }
+    if let Some(span) = reader_alias.and_then(|alias| alias.location.span()) {
+        token_kinds.insert(span, SemanticTokenKind::new(semantic_token_type_variable()));
+    }
+    for argument in arguments {
+        token_kinds.insert(
+            argument.item.name.span,
+            SemanticTokenKind::new(semantic_token_type_parameter()),
+        );
+    }
+
+    selectable_id
+}
+
+fn selectable_token_kind<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selectable_id: SelectableId,
+) -> SemanticTokenKind {
+    let deprecated = match selectable_id {
+        DefinitionLocation::Server(SelectionType::Scalar(server_scalar_selectable_id)) => schema
+            .server_scalar_selectable(server_scalar_selectable_id)
+            .deprecation_reason
+            .is_some(),
+        DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => schema
+            .server_object_selectable(server_object_selectable_id)
+            .deprecation_reason
+            .is_some(),
+        DefinitionLocation::Client(_) => {
+            return SemanticTokenKind::new(semantic_token_type_method());
+        }
+    };
+    let token_kind = SemanticTokenKind::new(semantic_token_type_property());
+    if deprecated {
+        token_kind.with_modifiers(semantic_token_modifier_deprecated())
+    } else {
+        token_kind
+    }
+}
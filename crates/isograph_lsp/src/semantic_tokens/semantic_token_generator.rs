@@ -1,66 +1,71 @@
 use common_lang_types::Span;
-use lsp_types::SemanticToken;
-
-use crate::row_col_offset::{diff_to_end_of_slice, RowColDiff};
-
-#[derive(Debug)]
-pub(crate) enum SemanticTokenGeneratorState {
-    InitialDiff(RowColDiff),
-    LastSpan(Span),
-}
+use lsp_types::{Position, SemanticToken};
 
+/// Encodes tokens in the relative format expected by the LSP, i.e. each token's
+/// position is relative to the start of the previous token. Tokens must be
+/// generated in the order in which they appear in the file.
 #[derive(Debug)]
 pub(crate) struct SemanticTokenGenerator<'a> {
-    state: SemanticTokenGeneratorState,
-    text: &'a str,
+    file_text: &'a str,
+    /// The offset up to which we have computed the position, and that position.
+    last_offset: usize,
+    last_position: Position,
+    last_token_start: Position,
     tokens: Vec<SemanticToken>,
-    final_diff: RowColDiff,
 }
 
 impl<'a> SemanticTokenGenerator<'a> {
-    pub(crate) fn generate_semantic_token(&mut self, span: Span, token_type: u32) {
-        let token = match self.state {
-            SemanticTokenGeneratorState::InitialDiff(initial_diff) => {
-                let new_diff = diff_to_end_of_slice(&self.text[0..(span.start as usize)]);
-                let diff = initial_diff + new_diff;
-                self.state = SemanticTokenGeneratorState::LastSpan(span);
-                self.final_diff = self.final_diff + diff;
-                SemanticToken {
-                    delta_line: diff.delta_line(),
-                    delta_start: diff.delta_start(),
-                    length: span.len(),
-                    token_type,
-                    token_modifiers_bitset: 0,
-                }
-            }
-            SemanticTokenGeneratorState::LastSpan(last_span) => {
-                let diff = diff_to_end_of_slice(
-                    &self.text[(last_span.start as usize)..(span.start as usize)],
-                );
-                self.final_diff = self.final_diff + diff;
-                self.state = SemanticTokenGeneratorState::LastSpan(span);
-                SemanticToken {
-                    delta_line: diff.delta_line(),
-                    delta_start: diff.delta_start(),
-                    length: span.len(),
-                    token_type,
-                    token_modifiers_bitset: 0,
-                }
-            }
-        };
-        self.tokens.push(token);
-    }
-
-    pub(crate) fn new(text: &'a str, initial_diff: RowColDiff) -> Self {
+    pub(crate) fn new(file_text: &'a str) -> Self {
         Self {
-            state: SemanticTokenGeneratorState::InitialDiff(initial_diff),
-            text,
+            file_text,
+            last_offset: 0,
+            last_position: Position::default(),
+            last_token_start: Position::default(),
             tokens: vec![],
-            final_diff: RowColDiff::default(),
         }
     }
 
-    pub(crate) fn consume(self) -> (Vec<SemanticToken>, RowColDiff) {
-        (self.tokens, self.final_diff)
+    /// Generate a token for a span, relative to the start of the file.
+    pub(crate) fn generate_semantic_token(
+        &mut self,
+        span: Span,
+        token_type: u32,
+        token_modifiers_bitset: u32,
+    ) {
+        let start = span.start as usize;
+        if start < self.last_offset {
+            // Tokens must not overlap or go backwards.
+            return;
+        }
+
+        for char in self.file_text[self.last_offset..start].chars() {
+            if char == '\n' {
+                self.last_position.line += 1;
+                self.last_position.character = 0;
+            } else {
+                self.last_position.character += char.len_utf16() as u32;
+            }
+        }
+        self.last_offset = start;
+
+        let delta_line = self.last_position.line - self.last_token_start.line;
+        let delta_start = if delta_line == 0 {
+            self.last_position.character - self.last_token_start.character
+        } else {
+            self.last_position.character
+        };
+        self.last_token_start = self.last_position;
+
+        self.tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: self.file_text[span.as_usize_range()].encode_utf16().count() as u32,
+            token_type,
+            token_modifiers_bitset,
+        });
+    }
+
+    pub(crate) fn consume(self) -> Vec<SemanticToken> {
+        self.tokens
     }
 }
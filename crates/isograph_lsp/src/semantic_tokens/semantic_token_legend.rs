@@ -110,3 +110,14 @@ pub(crate) fn semantic_token_type_operator() -> u32 {
 pub(crate) fn semantic_token_type_decorator() -> u32 {
     22
 }
+
+// Modifiers are a bitset, indexed by the position in token_modifiers
+pub(crate) fn semantic_token_modifier_declaration() -> u32 {
+    1 << 0
+}
+pub(crate) fn semantic_token_modifier_readonly() -> u32 {
+    1 << 2
+}
+pub(crate) fn semantic_token_modifier_deprecated() -> u32 {
+    1 << 4
+}
@@ -1,26 +1,39 @@
 use std::ops::ControlFlow;
 
 use crate::{
+    code_actions::on_code_action,
+    completion::on_completion,
+    goto_definition::on_goto_definition,
+    hover::on_hover,
     lsp_notification_dispatch::LSPNotificationDispatch,
     lsp_process_error::LSPProcessResult,
     lsp_request_dispatch::LSPRequestDispatch,
     lsp_runtime_error::LSPRuntimeError,
     lsp_state::LSPState,
+    references::on_references,
+    rename::{on_prepare_rename, on_rename},
     semantic_tokens::{
         on_semantic_token_full_request, semantic_token_legend::semantic_token_legend,
     },
     text_document::{
         on_did_change_text_document, on_did_close_text_document, on_did_open_text_document,
     },
+    workspace_symbols::on_workspace_symbol,
 };
+use isograph_compiler::StandardSources;
 use isograph_config::CompilerConfig;
+use isograph_schema::NetworkProtocol;
 use lsp_server::{Connection, ErrorCode, Response, ResponseError};
-use lsp_types::request::SemanticTokensFullRequest;
+use lsp_types::request::{
+    CodeActionRequest, Completion, GotoDefinition, HoverRequest, PrepareRenameRequest, References,
+    Rename, SemanticTokensFullRequest, WorkspaceSymbolRequest,
+};
 use lsp_types::{
     notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument},
-    InitializeParams, SemanticTokensFullOptions, SemanticTokensOptions,
-    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
-    TextDocumentSyncKind, WorkDoneProgressOptions,
+    CodeActionKind, CodeActionOptions, CodeActionProviderCapability, CompletionOptions,
+    HoverProviderCapability, InitializeParams, OneOf, RenameOptions, SemanticTokensFullOptions,
+    SemanticTokensOptions, SemanticTokensServerCapabilities, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, WorkDoneProgressOptions,
 };
 
 /// Initializes an LSP connection, handling the `initialize` message and `initialized` notification
@@ -37,6 +50,22 @@ pub fn initialize(connection: &Connection) -> LSPProcessResult<InitializeParams>
                 full: Some(SemanticTokensFullOptions::Bool(true)),
             },
         )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            ..Default::default()
+        })),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions::default(),
+        })),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["@".to_string()]),
+            ..Default::default()
+        }),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
         ..Default::default()
     };
     let server_capabilities = serde_json::to_value(server_capabilities)?;
@@ -46,13 +75,13 @@ pub fn initialize(connection: &Connection) -> LSPProcessResult<InitializeParams>
 }
 
 /// Run the main server loop
-pub async fn run(
+pub async fn run<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
     connection: Connection,
     config: CompilerConfig,
     _params: InitializeParams,
 ) -> LSPProcessResult<()> {
     eprintln!("Running server loop");
-    let mut state = LSPState::new(connection.sender.clone(), config);
+    let mut state = LSPState::<TNetworkProtocol>::new(connection.sender.clone(), config);
     while let Ok(message) = connection.receiver.recv() {
         match message {
             lsp_server::Message::Request(request) => {
@@ -62,7 +91,12 @@ pub async fn run(
                 state.send_message(response.into());
             }
             lsp_server::Message::Notification(notification) => {
-                dispatch_notification(notification, &mut state);
+                let method = notification.method.clone();
+                if let ControlFlow::Break(Some(LSPRuntimeError::UnexpectedError(message))) =
+                    dispatch_notification(notification, &mut state)
+                {
+                    eprintln!("Error handling notification {method}: {message}");
+                }
             }
             lsp_server::Message::Response(response) => {
                 eprintln!("Received response: {:?}", response);
@@ -73,9 +107,9 @@ pub async fn run(
     panic!("Client exited without proper shutdown sequence.")
 }
 
-fn dispatch_notification(
+fn dispatch_notification<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
     notification: lsp_server::Notification,
-    lsp_state: &mut LSPState,
+    lsp_state: &mut LSPState<TNetworkProtocol>,
 ) -> ControlFlow<Option<LSPRuntimeError>, ()> {
     LSPNotificationDispatch::new(notification, lsp_state)
         .on_notification_sync::<DidOpenTextDocument>(on_did_open_text_document)?
@@ -85,12 +119,23 @@ fn dispatch_notification(
 
     ControlFlow::Continue(())
 }
-fn dispatch_request(request: lsp_server::Request, lsp_state: &mut LSPState) -> Response {
+fn dispatch_request<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    request: lsp_server::Request,
+    lsp_state: &mut LSPState<TNetworkProtocol>,
+) -> Response {
     // Returns ControlFlow::Break(ServerResponse) if the request
     // was handled, ControlFlow::Continue(Request) otherwise.
     let get_response = || {
         let request = LSPRequestDispatch::new(request, lsp_state)
             .on_request_sync::<SemanticTokensFullRequest>(on_semantic_token_full_request)?
+            .on_request_sync::<HoverRequest>(on_hover)?
+            .on_request_sync::<GotoDefinition>(on_goto_definition)?
+            .on_request_sync::<Completion>(on_completion)?
+            .on_request_sync::<References>(on_references)?
+            .on_request_sync::<PrepareRenameRequest>(on_prepare_rename)?
+            .on_request_sync::<Rename>(on_rename)?
+            .on_request_sync::<CodeActionRequest>(on_code_action)?
+            .on_request_sync::<WorkspaceSymbolRequest>(on_workspace_symbol)?
             .request();
 
         // If we have gotten here, we have not handled the request
@@ -1,3 +1,5 @@
+use isograph_compiler::StandardSources;
+use isograph_schema::NetworkProtocol;
 use lsp_types::{
     notification::{
         DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification,
@@ -7,8 +9,8 @@ use lsp_types::{
 
 use crate::{lsp_runtime_error::LSPRuntimeResult, lsp_state::LSPState};
 
-pub fn on_did_open_text_document(
-    lsp_state: &mut LSPState,
+pub fn on_did_open_text_document<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    lsp_state: &mut LSPState<TNetworkProtocol>,
     params: <DidOpenTextDocument as Notification>::Params,
 ) -> LSPRuntimeResult<()> {
     let DidOpenTextDocumentParams { text_document } = params;
@@ -18,16 +20,16 @@ pub fn on_did_open_text_document(
 }
 
 #[allow(clippy::unnecessary_wraps)]
-pub fn on_did_close_text_document(
-    lsp_state: &mut LSPState,
+pub fn on_did_close_text_document<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    lsp_state: &mut LSPState<TNetworkProtocol>,
     params: <DidCloseTextDocument as Notification>::Params,
 ) -> LSPRuntimeResult<()> {
     let uri = params.text_document.uri;
     lsp_state.document_closed(&uri)
 }
 
-pub fn on_did_change_text_document(
-    lsp_state: &mut LSPState,
+pub fn on_did_change_text_document<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    lsp_state: &mut LSPState<TNetworkProtocol>,
     params: <DidChangeTextDocument as Notification>::Params,
 ) -> LSPRuntimeResult<()> {
     let DidChangeTextDocumentParams {
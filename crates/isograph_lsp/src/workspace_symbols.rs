@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use common_lang_types::Span;
+use generate_artifacts::{SchemaSearchEntry, SchemaSearchEntryKind};
+use intern::string_key::Intern;
+use isograph_compiler::StandardSources;
+use isograph_schema::NetworkProtocol;
+use lsp_types::{
+    request::{Request, WorkspaceSymbolRequest},
+    OneOf, SymbolKind, Url, WorkspaceLocation, WorkspaceSymbol, WorkspaceSymbolParams,
+    WorkspaceSymbolResponse,
+};
+
+use crate::{
+    location_utils::span_to_range,
+    lsp_runtime_error::{LSPRuntimeError, LSPRuntimeResult},
+    lsp_state::LSPState,
+};
+
+/// The maximum number of symbols to return. Clients re-request symbols as the
+/// query changes, so there is no need to return every match.
+const MAX_WORKSPACE_SYMBOLS: usize = 100;
+
+pub fn on_workspace_symbol<TNetworkProtocol: NetworkProtocol<Sources = StandardSources>>(
+    state: &mut LSPState<TNetworkProtocol>,
+    params: <WorkspaceSymbolRequest as Request>::Params,
+) -> LSPRuntimeResult<<WorkspaceSymbolRequest as Request>::Result> {
+    let WorkspaceSymbolParams {
+        query,
+        work_done_progress_params: _,
+        partial_result_params: _,
+    } = params;
+
+    let schema_path = state.config().schema.absolute_path.clone();
+    let search_index = state.schema_search_index()?;
+    let results = search_index
+        .search(&query)
+        .into_iter()
+        .take(MAX_WORKSPACE_SYMBOLS)
+        .map(|result| (result.entry.name.clone(), symbol_parts(result.entry)))
+        .collect::<Vec<_>>();
+
+    // Many symbols are usually in the same file (e.g. the schema), so we only
+    // read each file once.
+    let mut texts = HashMap::new();
+    let symbols = results
+        .into_iter()
+        .map(|(name, (kind, container_name, location))| {
+            let location = match location {
+                Some((path, span)) => {
+                    let relative_path = path.intern().into();
+                    let uri = url_for_path(&state.absolute_path(relative_path))?;
+                    let text = texts
+                        .entry(relative_path)
+                        .or_insert_with(|| state.text_for_relative_path(relative_path));
+                    match text {
+                        Some(text) => OneOf::Left(lsp_types::Location {
+                            uri,
+                            range: span_to_range(text, span),
+                        }),
+                        None => OneOf::Right(WorkspaceLocation { uri }),
+                    }
+                }
+                // Types do not track their location, so we point to the schema.
+                None => OneOf::Right(WorkspaceLocation {
+                    uri: url_for_path(&schema_path)?,
+                }),
+            };
+            Ok(WorkspaceSymbol {
+                name,
+                kind,
+                tags: None,
+                container_name,
+                location,
+                data: None,
+            })
+        })
+        .collect::<LSPRuntimeResult<Vec<_>>>()?;
+
+    Ok(Some(WorkspaceSymbolResponse::Nested(symbols)))
+}
+
+type SymbolParts = (SymbolKind, Option<String>, Option<(String, Span)>);
+
+fn symbol_parts(entry: &SchemaSearchEntry) -> SymbolParts {
+    let kind = match entry.kind {
+        SchemaSearchEntryKind::Object => SymbolKind::CLASS,
+        SchemaSearchEntryKind::Scalar => SymbolKind::TYPE_PARAMETER,
+        SchemaSearchEntryKind::ServerField => SymbolKind::FIELD,
+        SchemaSearchEntryKind::ClientField | SchemaSearchEntryKind::ClientPointer => {
+            SymbolKind::FUNCTION
+        }
+    };
+    let location = entry.location.as_ref().map(|location| {
+        (
+            location.path.clone(),
+            Span::new(location.start, location.end),
+        )
+    });
+    (kind, entry.parent_type.clone(), location)
+}
+
+fn url_for_path(path: &std::path::Path) -> LSPRuntimeResult<Url> {
+    Url::from_file_path(path).map_err(|_| {
+        LSPRuntimeError::UnexpectedError(format!("Unable to convert {} to a url", path.display()))
+    })
+}
@@ -0,0 +1,49 @@
+use common_lang_types::{DirectiveArgumentName, DirectiveName};
+use graphql_lang_types::{GraphQLConstantValue, GraphQLDirective};
+use intern::{string_key::Intern, Lookup};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref CACHE_CONTROL_DIRECTIVE: DirectiveName = "cacheControl".intern().into();
+    static ref MAX_AGE_ARGUMENT: DirectiveArgumentName = "maxAge".intern().into();
+    static ref SCOPE_ARGUMENT: DirectiveArgumentName = "scope".intern().into();
+}
+
+/// How long the value of a field (or of the objects of a type) may be cached, e.g.
+/// from @cacheControl(maxAge: 60, scope: PRIVATE) in GraphQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheControl {
+    /// In seconds. Zero means that the value may not be cached.
+    pub max_age: Option<u32>,
+    /// Whether the value is specific to the user, e.g. from scope: PRIVATE, and so
+    /// may not be stored in a shared cache.
+    pub private: bool,
+}
+
+impl CacheControl {
+    /// The hint from a @cacheControl(maxAge: Int, scope: PUBLIC | PRIVATE) directive,
+    /// if there is one. maxAge values that are not non-negative integers are ignored.
+    pub fn from_directives(directives: &[GraphQLDirective<GraphQLConstantValue>]) -> Option<Self> {
+        let directive = directives
+            .iter()
+            .find(|directive| directive.name.item == *CACHE_CONTROL_DIRECTIVE)?;
+        let argument = |argument_name: DirectiveArgumentName| {
+            directive
+                .arguments
+                .iter()
+                .find(|argument| argument.name.item == argument_name)
+                .map(|argument| &argument.value.item)
+        };
+
+        Some(CacheControl {
+            max_age: match argument(*MAX_AGE_ARGUMENT) {
+                Some(GraphQLConstantValue::Int(max_age)) => u32::try_from(*max_age).ok(),
+                _ => None,
+            },
+            private: match argument(*SCOPE_ARGUMENT) {
+                Some(GraphQLConstantValue::Enum(scope)) => scope.lookup() == "PRIVATE",
+                _ => false,
+            },
+        })
+    }
+}
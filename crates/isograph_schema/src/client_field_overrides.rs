@@ -0,0 +1,229 @@
+use common_lang_types::{
+    error_codes, ErrorCode, HasErrorCode, Location, ObjectTypeAndFieldName, Span, WithLocation,
+};
+use isograph_lang_types::{
+    ClientFieldDirectiveSet, ClientScalarSelectableId, DefinitionLocation, SelectionType,
+    ServerObjectEntityId,
+};
+use thiserror::Error;
+
+use crate::{
+    ClientFieldVariant, ClientScalarSelectable, NetworkProtocol, Schema,
+    SchemaServerObjectSelectableVariant,
+};
+
+/// The client fields that override a client field defined on an abstract type, i.e.
+/// the user-written client fields with the same name that are defined on its concrete
+/// types, sorted by the name of the concrete type.
+///
+/// When the client field is read, the reader dispatches on the __typename of the
+/// record, and reads the override of that type instead, if there is one.
+pub fn client_field_overrides<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> Vec<(ServerObjectEntityId, ClientScalarSelectableId)> {
+    if !matches!(client_field.variant, ClientFieldVariant::UserWritten(_))
+        || schema
+            .server_entity_data
+            .server_object_entity(client_field.parent_object_entity_id)
+            .concrete_type
+            .is_some()
+    {
+        return vec![];
+    }
+
+    let mut overrides = concrete_types(schema, client_field.parent_object_entity_id)
+        .filter_map(|concrete_object_entity_id| {
+            let selectable_id = schema
+                .server_entity_data
+                .server_object_entity_extra_info
+                .get(&concrete_object_entity_id)?
+                .selectables
+                .get(&client_field.name.into())?;
+            match selectable_id {
+                DefinitionLocation::Client(SelectionType::Scalar(client_field_id)) => matches!(
+                    schema.client_field(*client_field_id).variant,
+                    ClientFieldVariant::UserWritten(_)
+                )
+                .then_some((concrete_object_entity_id, *client_field_id)),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    overrides.sort_by_key(|(concrete_object_entity_id, _)| {
+        schema
+            .server_entity_data
+            .server_object_entity(*concrete_object_entity_id)
+            .name
+            .to_string()
+    });
+    overrides.dedup();
+    overrides
+}
+
+/// The concrete types of an abstract type are the targets of its inline fragments
+/// (i.e. the asConcreteType fields.)
+pub fn concrete_types<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    abstract_object_entity_id: ServerObjectEntityId,
+) -> impl Iterator<Item = ServerObjectEntityId> + '_ {
+    schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&abstract_object_entity_id)
+        .into_iter()
+        .flat_map(|extra_info| extra_info.selectables.values())
+        .filter_map(|selectable_id| match selectable_id {
+            DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => {
+                let server_object_selectable =
+                    schema.server_object_selectable(*server_object_selectable_id);
+                match server_object_selectable.object_selectable_variant {
+                    SchemaServerObjectSelectableVariant::InlineFragment => {
+                        Some(*server_object_selectable.target_object_entity.inner())
+                    }
+                    SchemaServerObjectSelectableVariant::LinkedField
+                    | SchemaServerObjectSelectableVariant::Upcast => None,
+                }
+            }
+            _ => None,
+        })
+}
+
+/// Validate that each client field that overrides a client field defined on an
+/// abstract type can be read in its place, i.e. that it declares the same variables
+/// (with the same types), and that both or neither are components.
+pub fn validate_client_field_overrides<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> Result<(), Vec<WithLocation<ClientFieldOverrideError>>> {
+    let mut errors = vec![];
+    for client_field in &schema.client_scalar_selectables {
+        for (_, override_id) in client_field_overrides(schema, client_field) {
+            let override_field = schema.client_field(override_id);
+            let location = match &override_field.variant {
+                ClientFieldVariant::UserWritten(info) => {
+                    Location::new(info.text_source, Span::new(0, 0))
+                }
+                _ => Location::generated(),
+            };
+
+            if !have_same_variables(client_field, override_field) {
+                errors.push(WithLocation::new(
+                    ClientFieldOverrideError::OverrideHasDifferentVariables {
+                        overriding_field: override_field.type_and_field,
+                        overridden_field: client_field.type_and_field,
+                        variables: variable_names(client_field),
+                    },
+                    location,
+                ));
+            }
+
+            if is_component(client_field) != is_component(override_field) {
+                errors.push(WithLocation::new(
+                    ClientFieldOverrideError::OverrideHasDifferentKind {
+                        overriding_field: override_field.type_and_field,
+                        overridden_field: client_field.type_and_field,
+                        overridden_field_is_component: is_component(client_field),
+                    },
+                    location,
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn have_same_variables<TNetworkProtocol: NetworkProtocol>(
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+    override_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> bool {
+    let variables = |field: &ClientScalarSelectable<TNetworkProtocol>| {
+        let mut variables = field
+            .variable_definitions
+            .iter()
+            .map(|variable_definition| {
+                (
+                    variable_definition.item.name.item,
+                    variable_definition.item.type_.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        variables.sort_by_key(|(name, _)| name.to_string());
+        variables
+    };
+    variables(client_field) == variables(override_field)
+}
+
+fn variable_names<TNetworkProtocol: NetworkProtocol>(
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> String {
+    if client_field.variable_definitions.is_empty() {
+        return "no variables".to_string();
+    }
+    client_field
+        .variable_definitions
+        .iter()
+        .map(|variable_definition| format!("${}", variable_definition.item.name.item))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn is_component<TNetworkProtocol: NetworkProtocol>(
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+) -> bool {
+    matches!(
+        client_field.variant,
+        ClientFieldVariant::UserWritten(ref info)
+            if matches!(info.client_field_directive_set, ClientFieldDirectiveSet::Component(_))
+    )
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum ClientFieldOverrideError {
+    #[error(
+        "`{}.{}` overrides `{}.{}`, but declares different variables. Overriding \
+        client fields must declare the same variables, with the same types, as the \
+        client field they override (here, {variables}).",
+        overriding_field.type_name,
+        overriding_field.field_name,
+        overridden_field.type_name,
+        overridden_field.field_name
+    )]
+    OverrideHasDifferentVariables {
+        overriding_field: ObjectTypeAndFieldName,
+        overridden_field: ObjectTypeAndFieldName,
+        variables: String,
+    },
+
+    #[error(
+        "`{}.{}` overrides `{}.{}`, but {}. Either both or neither must be \
+        components.",
+        overriding_field.type_name,
+        overriding_field.field_name,
+        overridden_field.type_name,
+        overridden_field.field_name,
+        if *overridden_field_is_component {
+            "is not a component, while the overridden field is"
+        } else {
+            "is a component, while the overridden field is not"
+        }
+    )]
+    OverrideHasDifferentKind {
+        overriding_field: ObjectTypeAndFieldName,
+        overridden_field: ObjectTypeAndFieldName,
+        overridden_field_is_component: bool,
+    },
+}
+
+impl HasErrorCode for ClientFieldOverrideError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ClientFieldOverrideError::OverrideHasDifferentVariables { .. } => error_codes::IS0208,
+            ClientFieldOverrideError::OverrideHasDifferentKind { .. } => error_codes::IS0209,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use isograph_lang_types::{ClientScalarSelectableId, SelectionType};
+
+use crate::{accessible_client_fields, ClientSelectableId, NetworkProtocol, Schema};
+
+/// Which client fields and client pointers select which others, and which are
+/// reachable from each entrypoint.
+#[derive(Debug, Default)]
+pub struct ClientTypeDependencyGraph {
+    /// Every user-written client field and client pointer, and every client
+    /// field that one of them selects (e.g. a refetch field).
+    pub client_types: BTreeSet<ClientSelectableId>,
+    /// The client fields and client pointers selected in the reader selection
+    /// set of each client field and client pointer, including within linked
+    /// fields. Selecting a client field loadably counts as selecting it.
+    pub selections: BTreeMap<ClientSelectableId, BTreeSet<ClientSelectableId>>,
+    /// The client fields and client pointers reachable from each entrypoint,
+    /// directly or transitively, excluding the entrypoint itself.
+    pub reachable_from_entrypoints:
+        BTreeMap<ClientScalarSelectableId, BTreeSet<ClientSelectableId>>,
+}
+
+pub fn client_type_dependency_graph<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> ClientTypeDependencyGraph {
+    let mut graph = ClientTypeDependencyGraph::default();
+
+    let mut queue = schema
+        .user_written_client_types()
+        .map(|(client_type_id, _, _)| client_type_id)
+        .chain(
+            schema
+                .entrypoints
+                .keys()
+                .map(|client_field_id| SelectionType::Scalar(*client_field_id)),
+        )
+        .collect::<Vec<_>>();
+    while let Some(client_type_id) = queue.pop() {
+        if !graph.client_types.insert(client_type_id) {
+            continue;
+        }
+        let selected_client_types =
+            accessible_client_fields(&schema.client_type(client_type_id), schema)
+                .collect::<BTreeSet<_>>();
+        queue.extend(selected_client_types.iter().copied());
+        graph
+            .selections
+            .insert(client_type_id, selected_client_types);
+    }
+
+    for entrypoint_id in schema.entrypoints.keys() {
+        let mut reachable = BTreeSet::new();
+        let mut queue = vec![SelectionType::Scalar(*entrypoint_id)];
+        while let Some(client_type_id) = queue.pop() {
+            for selected_client_type_id in
+                graph.selections.get(&client_type_id).into_iter().flatten()
+            {
+                if reachable.insert(*selected_client_type_id) {
+                    queue.push(*selected_client_type_id);
+                }
+            }
+        }
+        reachable.remove(&SelectionType::Scalar(*entrypoint_id));
+        graph
+            .reachable_from_entrypoints
+            .insert(*entrypoint_id, reachable);
+    }
+
+    graph
+}
@@ -0,0 +1,66 @@
+use common_lang_types::SelectableName;
+use isograph_lang_types::{DefinitionLocation, SelectionType, ServerObjectEntityId};
+
+use crate::{concrete_types, NetworkProtocol, Schema, ValidatedSelection};
+
+/// With the implicit_concrete_type_refinements option, a server field that is not
+/// defined on an abstract type, but is defined on some of its concrete types, can be
+/// selected directly on the abstract type. It is fetched in an inline fragment on each
+/// of those concrete types, and is read as null if the record is of another type.
+///
+/// If the selection is such a field, return those concrete types, sorted by name.
+pub fn implicit_concrete_type_refinements<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    selection: &ValidatedSelection,
+) -> Option<Vec<ServerObjectEntityId>> {
+    let name: SelectableName = match selection {
+        SelectionType::Scalar(scalar_selection) => match scalar_selection.associated_data {
+            DefinitionLocation::Server(_) => scalar_selection.name.item.into(),
+            DefinitionLocation::Client(_) => return None,
+        },
+        SelectionType::Object(object_selection) => match object_selection.associated_data {
+            DefinitionLocation::Server(_) => object_selection.name.item.into(),
+            DefinitionLocation::Client(_) => return None,
+        },
+    };
+
+    if schema
+        .server_entity_data
+        .server_object_entity(parent_object_entity_id)
+        .concrete_type
+        .is_some()
+        || defines_server_field(schema, parent_object_entity_id, name)
+    {
+        return None;
+    }
+
+    let mut defining_concrete_types = concrete_types(schema, parent_object_entity_id)
+        .filter(|concrete_object_entity_id| {
+            defines_server_field(schema, *concrete_object_entity_id, name)
+        })
+        .collect::<Vec<_>>();
+    defining_concrete_types.sort_by_key(|concrete_object_entity_id| {
+        schema
+            .server_entity_data
+            .server_object_entity(*concrete_object_entity_id)
+            .name
+            .to_string()
+    });
+    defining_concrete_types.dedup();
+    Some(defining_concrete_types)
+}
+
+/// Whether the type has a server field with this name.
+pub fn defines_server_field<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    object_entity_id: ServerObjectEntityId,
+    name: SelectableName,
+) -> bool {
+    schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&object_entity_id)
+        .and_then(|extra_info| extra_info.selectables.get(&name))
+        .is_some_and(|location| matches!(location, DefinitionLocation::Server(_)))
+}
@@ -0,0 +1,316 @@
+use common_lang_types::{
+    IsographObjectTypeName, Location, ObjectTypeAndFieldName, SelectableName, Span, VariableName,
+    WithLocation, WithSpan,
+};
+use intern::{string_key::Intern, Lookup};
+use isograph_lang_types::{
+    DefinitionLocation, EmptyDirectiveSet, NonConstantValue, ObjectSelection,
+    ObjectSelectionDirectiveSet, ScalarSelection, ScalarSelectionDirectiveSet,
+    SelectionFieldArgument, SelectionType, SelectionTypeContainingSelections, ServerEntityId,
+    ServerObjectEntityId, ServerObjectSelectableId, VariableDefinition,
+};
+use lazy_static::lazy_static;
+
+use crate::{
+    ClientFieldVariant, ClientScalarSelectable, NetworkProtocol, PaginatedFieldVariant,
+    SchemaServerObjectSelectableVariant, UnprocessedClientFieldItem, UnprocessedSelection,
+    LINK_FIELD_NAME,
+};
+
+use super::create_additional_fields_error::{
+    CreateAdditionalFieldsError, ProcessTypeDefinitionResult,
+};
+
+lazy_static! {
+    static ref FIRST: SelectableName = "first".intern().into();
+    static ref AFTER: SelectableName = "after".intern().into();
+    static ref EDGES: SelectableName = "edges".intern().into();
+    static ref NODE: SelectableName = "node".intern().into();
+    static ref PAGE_INFO: SelectableName = "pageInfo".intern().into();
+    static ref HAS_NEXT_PAGE: SelectableName = "hasNextPage".intern().into();
+    static ref END_CURSOR: SelectableName = "endCursor".intern().into();
+    static ref ID: SelectableName = "id".intern().into();
+}
+
+/// A connection field (e.g. `User.repositories`) for which a pagination field is
+/// generated.
+struct ConnectionField {
+    parent_object_entity_id: ServerObjectEntityId,
+    parent_object_name: IsographObjectTypeName,
+    connection_field_id: ServerObjectSelectableId,
+    node_object_entity_id: ServerObjectEntityId,
+}
+
+impl<TNetworkProtocol: NetworkProtocol> crate::Schema<TNetworkProtocol> {
+    /// For each field that returns a Relay-style connection (i.e. it accepts `first` and
+    /// `after` arguments, and returns an object with `edges { node }` and
+    /// `pageInfo { hasNextPage endCursor }`), add a `__paginated_<field>` client field
+    /// that selects a page of the connection, and returns it. The `first` and `after`
+    /// arguments become variables of the client field, so it can be selected with
+    /// `@loadable` and passed to `useConnectionSpecPagination`.
+    ///
+    /// Each node selects only its `link` (and its `id`, if it has one).
+    ///
+    /// Must be called after `add_link_fields`.
+    pub fn add_pagination_fields(
+        &mut self,
+    ) -> ProcessTypeDefinitionResult<Vec<UnprocessedClientFieldItem>> {
+        let connection_fields = self
+            .server_object_selectables
+            .iter()
+            .enumerate()
+            .filter_map(|(connection_field_id, connection_field)| {
+                let parent_object = self
+                    .server_entity_data
+                    .server_object_entity(connection_field.parent_object_entity_id);
+                if parent_object.is_input_object
+                    || !matches!(
+                        connection_field.object_selectable_variant,
+                        SchemaServerObjectSelectableVariant::LinkedField
+                    )
+                    || !has_argument(&connection_field.arguments, *FIRST)
+                    || !has_argument(&connection_field.arguments, *AFTER)
+                {
+                    return None;
+                }
+                let node_object_entity_id =
+                    self.connection_node(*connection_field.target_object_entity.inner())?;
+                Some(ConnectionField {
+                    parent_object_entity_id: connection_field.parent_object_entity_id,
+                    parent_object_name: parent_object.name,
+                    connection_field_id: connection_field_id.into(),
+                    node_object_entity_id,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        connection_fields
+            .into_iter()
+            .map(|connection_field| self.add_pagination_field(connection_field))
+            .collect()
+    }
+
+    fn add_pagination_field(
+        &mut self,
+        connection_field: ConnectionField,
+    ) -> ProcessTypeDefinitionResult<UnprocessedClientFieldItem> {
+        let ConnectionField {
+            parent_object_entity_id,
+            parent_object_name,
+            connection_field_id,
+            node_object_entity_id,
+        } = connection_field;
+        let server_object_selectable = self.server_object_selectable(connection_field_id);
+        let connection_field_name = server_object_selectable.name.item;
+        let client_field_name = format!("__paginated_{connection_field_name}").intern();
+
+        // Every argument of the connection field (e.g. orderBy, in addition to first
+        // and after) is passed as a variable of the same name.
+        let variable_definitions = server_object_selectable
+            .arguments
+            .iter()
+            .map(|argument| WithSpan::new(argument.item.clone(), Span::todo_generated()))
+            .collect::<Vec<_>>();
+        let arguments = server_object_selectable
+            .arguments
+            .iter()
+            .map(|argument| argument.item.name.item)
+            .collect();
+
+        let mut node_selection_set = vec![scalar_selection((*LINK_FIELD_NAME).into())];
+        if self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&node_object_entity_id)
+            .is_some_and(|extra_info| extra_info.id_field.is_some())
+        {
+            node_selection_set.push(scalar_selection(*ID));
+        }
+
+        let reader_selection_set = vec![object_selection(
+            connection_field_name.into(),
+            arguments,
+            vec![
+                object_selection(
+                    *PAGE_INFO,
+                    vec![],
+                    vec![
+                        scalar_selection(*HAS_NEXT_PAGE),
+                        scalar_selection(*END_CURSOR),
+                    ],
+                ),
+                object_selection(
+                    *EDGES,
+                    vec![],
+                    vec![object_selection(*NODE, vec![], node_selection_set)],
+                ),
+            ],
+        )];
+
+        let next_client_field_id = self.client_scalar_selectables.len().into();
+        if self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .entry(parent_object_entity_id)
+            .or_default()
+            .selectables
+            .insert(
+                client_field_name.into(),
+                DefinitionLocation::Client(SelectionType::Scalar(next_client_field_id)),
+            )
+            .is_some()
+        {
+            return Err(WithLocation::new(
+                CreateAdditionalFieldsError::CompilerCreatedFieldExistsOnType {
+                    field_name: client_field_name.into(),
+                    parent_type: parent_object_name,
+                },
+                Location::generated(),
+            ));
+        }
+
+        self.client_scalar_selectables.push(ClientScalarSelectable {
+            description: Some(
+                format!("A page of the {connection_field_name} connection.")
+                    .intern()
+                    .into(),
+            ),
+            name: client_field_name.into(),
+            parent_object_entity_id,
+            variable_definitions,
+            reader_selection_set: vec![],
+            variant: ClientFieldVariant::Paginated(PaginatedFieldVariant {
+                connection_field_name,
+            }),
+            type_and_field: ObjectTypeAndFieldName {
+                type_name: parent_object_name,
+                field_name: client_field_name.into(),
+            },
+            refetch_strategy: None,
+            output_format: std::marker::PhantomData,
+        });
+
+        Ok(UnprocessedClientFieldItem {
+            client_field_id: next_client_field_id,
+            reader_selection_set,
+            refetch_strategy: self.client_field_refetch_strategy(parent_object_entity_id),
+        })
+    }
+
+    /// If the object has the shape of a connection, the type of its nodes.
+    fn connection_node(
+        &self,
+        object_entity_id: ServerObjectEntityId,
+    ) -> Option<ServerObjectEntityId> {
+        let page_info_object_entity_id = self.object_field_target(object_entity_id, *PAGE_INFO)?;
+        if !self.has_server_field(page_info_object_entity_id, *HAS_NEXT_PAGE)
+            || !self.has_server_field(page_info_object_entity_id, *END_CURSOR)
+        {
+            return None;
+        }
+        let edge_object_entity_id = self.object_field_target(object_entity_id, *EDGES)?;
+        self.object_field_target(edge_object_entity_id, *NODE)
+    }
+
+    fn object_field_target(
+        &self,
+        object_entity_id: ServerObjectEntityId,
+        field_name: SelectableName,
+    ) -> Option<ServerObjectEntityId> {
+        match self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&object_entity_id)?
+            .selectables
+            .get(&field_name)?
+        {
+            DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) => Some(
+                *self
+                    .server_object_selectable(*server_object_selectable_id)
+                    .target_object_entity
+                    .inner(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn has_server_field(
+        &self,
+        object_entity_id: ServerObjectEntityId,
+        field_name: SelectableName,
+    ) -> bool {
+        self.server_entity_data
+            .server_object_entity_extra_info
+            .get(&object_entity_id)
+            .and_then(|extra_info| extra_info.selectables.get(&field_name))
+            .is_some_and(|selectable| matches!(selectable, DefinitionLocation::Server(_)))
+    }
+}
+
+fn has_argument(
+    arguments: &[WithLocation<VariableDefinition<ServerEntityId>>],
+    argument_name: SelectableName,
+) -> bool {
+    arguments
+        .iter()
+        .any(|argument| is_argument(argument, argument_name))
+}
+
+fn is_argument(
+    argument: &WithLocation<VariableDefinition<ServerEntityId>>,
+    argument_name: SelectableName,
+) -> bool {
+    argument.item.name.item.lookup() == argument_name.lookup()
+}
+
+pub(crate) fn scalar_selection(name: SelectableName) -> UnprocessedSelection {
+    WithSpan::new(
+        SelectionTypeContainingSelections::Scalar(ScalarSelection {
+            name: WithLocation::new(name.unchecked_conversion(), Location::generated()),
+            reader_alias: None,
+            associated_data: (),
+            scalar_selection_directive_set: ScalarSelectionDirectiveSet::None(EmptyDirectiveSet {}),
+            arguments: vec![],
+            conditions: vec![],
+        }),
+        Span::todo_generated(),
+    )
+}
+
+/// A linked field selection, which passes each of the variables as the argument of the
+/// same name.
+pub(crate) fn object_selection(
+    name: SelectableName,
+    arguments: Vec<VariableName>,
+    selection_set: Vec<UnprocessedSelection>,
+) -> UnprocessedSelection {
+    WithSpan::new(
+        SelectionTypeContainingSelections::Object(ObjectSelection {
+            name: WithLocation::new(name.unchecked_conversion(), Location::generated()),
+            reader_alias: None,
+            associated_data: (),
+            selection_set,
+            arguments: arguments
+                .into_iter()
+                .map(|argument| {
+                    WithLocation::new(
+                        SelectionFieldArgument {
+                            name: WithSpan::new(
+                                argument.unchecked_conversion(),
+                                Span::todo_generated(),
+                            ),
+                            value: WithLocation::new(
+                                NonConstantValue::Variable(argument),
+                                Location::generated(),
+                            ),
+                        },
+                        Location::generated(),
+                    )
+                })
+                .collect(),
+            object_selection_directive_set: ObjectSelectionDirectiveSet::None(EmptyDirectiveSet {}),
+            conditions: vec![],
+        }),
+        Span::todo_generated(),
+    )
+}
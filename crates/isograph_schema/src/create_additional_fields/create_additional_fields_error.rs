@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use crate::{NetworkProtocol, Schema};
 use common_lang_types::{
-    IsographObjectTypeName, SelectableName, StringLiteralValue, UnvalidatedTypeName, VariableName,
-    WithLocation,
+    error_codes, ErrorCode, HasErrorCode, IsographObjectTypeName, SelectableName,
+    StringLiteralValue, UnvalidatedTypeName, VariableName, WithLocation,
 };
 use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::ServerObjectEntityId;
@@ -105,6 +105,18 @@ pub enum CreateAdditionalFieldsError {
         field_name: StringLiteralValue,
     },
 
+    #[error(
+        "Error when processing @exposeField directive on type `{primary_type_name}`. \
+        The argument `{argument_name}` of `{field_name}` has the same name as another argument \
+        of the exposed field. Arguments of fields in the path are passed as variables \
+        with the same name, so they must be unique."
+    )]
+    PrimaryFieldPathArgumentConflict {
+        primary_type_name: IsographObjectTypeName,
+        field_name: SelectableName,
+        argument_name: VariableName,
+    },
+
     #[error("Failed to deserialize {0}")]
     FailedToDeserialize(String),
 
@@ -117,6 +129,12 @@ pub enum CreateAdditionalFieldsError {
         strong_field_name: &'static str,
     },
 
+    #[error(
+        "The type \"{parent_type}\" implements Node, but has no id field.\n\
+        This error can be suppressed using the \"on_missing_node_id_field\" config parameter."
+    )]
+    NodeTypeMissingIdField { parent_type: IsographObjectTypeName },
+
     #[error(
         "The argument `{argument_name}` on field `{parent_type_name}.{field_name}` has inner type `{argument_type}`, which does not exist."
     )]
@@ -139,4 +157,37 @@ pub enum CreateAdditionalFieldsError {
     },
 }
 
+impl HasErrorCode for CreateAdditionalFieldsError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            CreateAdditionalFieldsError::CompilerCreatedFieldExistsOnType { .. } => {
+                error_codes::IS0103
+            }
+            CreateAdditionalFieldsError::DuplicateField { .. } => error_codes::IS0104,
+            CreateAdditionalFieldsError::InvalidField { .. } => error_codes::IS0105,
+            CreateAdditionalFieldsError::InvalidMutationField => error_codes::IS0106,
+            CreateAdditionalFieldsError::PrimaryDirectiveArgumentDoesNotExistOnField { .. } => {
+                error_codes::IS0107
+            }
+            CreateAdditionalFieldsError::PrimaryDirectiveCannotRemapObject { .. } => {
+                error_codes::IS0108
+            }
+            CreateAdditionalFieldsError::PrimaryDirectiveFieldNotFound { .. } => {
+                error_codes::IS0109
+            }
+            CreateAdditionalFieldsError::PrimaryFieldPathArgumentConflict { .. } => {
+                error_codes::IS0119
+            }
+            CreateAdditionalFieldsError::FailedToDeserialize(_) => error_codes::IS0110,
+            CreateAdditionalFieldsError::IdFieldMustBeNonNullIdType { .. } => error_codes::IS0111,
+            CreateAdditionalFieldsError::NodeTypeMissingIdField { .. } => error_codes::IS0120,
+            CreateAdditionalFieldsError::FieldArgumentTypeDoesNotExist { .. } => {
+                error_codes::IS0112
+            }
+            CreateAdditionalFieldsError::FieldTypenameDoesNotExist { .. } => error_codes::IS0113,
+            CreateAdditionalFieldsError::DuplicateTypeDefinition { .. } => error_codes::IS0114,
+        }
+    }
+}
+
 pub type CreateAdditionalFieldsResult<T> = Result<T, CreateAdditionalFieldsError>;
@@ -4,9 +4,10 @@ use common_lang_types::{
 };
 use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::{
-    ClientScalarSelectableId, DefinitionLocation, EmptyDirectiveSet, ScalarSelection,
-    ScalarSelectionDirectiveSet, SelectionType, SelectionTypeContainingSelections, ServerEntityId,
-    ServerObjectEntityId, ServerObjectSelectableId, VariableDefinition,
+    ArgumentKeyAndValue, ClientScalarSelectableId, DefinitionLocation, EmptyDirectiveSet,
+    NonConstantValue, ScalarSelection, ScalarSelectionDirectiveSet, SelectionType,
+    SelectionTypeContainingSelections, ServerEntityId, ServerObjectEntityId,
+    ServerObjectSelectableId, VariableDefinition,
 };
 
 use serde::Deserialize;
@@ -86,12 +87,6 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         let payload_object_type_annotation = &mutation_field.target_object_entity;
         let payload_object_entity_id = *payload_object_type_annotation.inner();
 
-        // TODO it's a bit annoying that we call .object twice!
-        let mutation_field_payload_type_name = self
-            .server_entity_data
-            .server_object_entity(payload_object_entity_id)
-            .name;
-
         let client_field_scalar_selection_name =
             expose_as.unwrap_or(mutation_field.name.item.into());
         // TODO what is going on here. Should mutation_field have a checked way of converting to LinkedField?
@@ -101,20 +96,6 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             .description
             .or(mutation_field.description);
 
-        let processed_field_map_items = skip_arguments_contained_in_field_map(
-            self,
-            mutation_field_arguments.clone(),
-            mutation_field_payload_type_name,
-            expose_field_to_insert.parent_object_name,
-            client_field_scalar_selection_name,
-            // TODO don't clone
-            field_map.clone(),
-        )?;
-
-        let payload_object_entity = self
-            .server_entity_data
-            .server_object_entity(payload_object_entity_id);
-
         let maybe_abstract_target_object_entity_with_id = self
             .traverse_object_selections(
                 payload_object_entity_id,
@@ -125,7 +106,16 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         let maybe_abstract_parent_object_entity_id = maybe_abstract_target_object_entity_with_id.id;
         let maybe_abstract_parent_object_entity_name =
             maybe_abstract_target_object_entity_with_id.item.name;
-        let maybe_abstract_parent_object_entity = maybe_abstract_target_object_entity_with_id.item;
+
+        let processed_field_map_items = skip_arguments_contained_in_field_map(
+            self,
+            mutation_field_arguments.clone(),
+            maybe_abstract_parent_object_entity_name,
+            expose_field_to_insert.parent_object_name,
+            client_field_scalar_selection_name,
+            // TODO don't clone
+            field_map.clone(),
+        )?;
 
         let fields = processed_field_map_items
             .iter()
@@ -144,6 +134,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                     ),
                     // TODO what about arguments? How would we handle them?
                     arguments: vec![],
+                    conditions: vec![],
                 };
 
                 WithSpan::new(
@@ -155,8 +146,10 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
 
         let mutation_field_client_field_id = self.client_scalar_selectables.len().into();
 
-        let top_level_schema_field_concrete_type = payload_object_entity.concrete_type;
-        let primary_field_concrete_type = maybe_abstract_parent_object_entity.concrete_type;
+        let top_level_schema_field_concrete_type = self
+            .server_entity_data
+            .server_object_entity(payload_object_entity_id)
+            .concrete_type;
 
         let top_level_schema_field_arguments = mutation_field_arguments
             .into_iter()
@@ -169,29 +162,62 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 primary_field_name_selection_parts.iter().copied(),
             )
             .map_err(|e| WithLocation::new(e, Location::generated()))?;
+
+        // The arguments of linked fields in the path are passed as variables with the
+        // same name, so they cannot conflict with the arguments of the exposed field.
+        let mut primary_field_path_arguments: Vec<VariableDefinition<ServerEntityId>> = vec![];
+        for server_object_selectable in parts_reversed.iter() {
+            for argument in server_object_selectable.arguments.iter() {
+                let argument_name = argument.item.name.item;
+                if top_level_schema_field_arguments
+                    .iter()
+                    .chain(primary_field_path_arguments.iter())
+                    .any(|existing_argument| existing_argument.name.item == argument_name)
+                {
+                    return Err(WithLocation::new(
+                        CreateAdditionalFieldsError::PrimaryFieldPathArgumentConflict {
+                            primary_type_name: maybe_abstract_parent_object_entity_name,
+                            field_name: server_object_selectable.name.item.into(),
+                            argument_name,
+                        },
+                        Location::generated(),
+                    ));
+                }
+                primary_field_path_arguments.push(argument.item.clone());
+            }
+        }
+
         parts_reversed.reverse();
 
         let mut subfields_or_inline_fragments = parts_reversed
             .iter()
-            .map(|server_object_selectable| {
+            .filter_map(|server_object_selectable| {
+                let target_object_entity = self
+                    .server_entity_data
+                    .server_object_entity(*server_object_selectable.target_object_entity.inner());
                 // The server object selectable may represent a linked field or an inline fragment
                 match server_object_selectable.object_selectable_variant {
                     SchemaServerObjectSelectableVariant::LinkedField => {
-                        WrappedSelectionMapSelection::LinkedField {
+                        Some(WrappedSelectionMapSelection::LinkedField {
                             server_object_selectable_name: server_object_selectable.name.item,
-                            arguments: vec![],
-                            concrete_type: primary_field_concrete_type,
-                        }
-                    }
-                    SchemaServerObjectSelectableVariant::InlineFragment => {
-                        WrappedSelectionMapSelection::InlineFragment(
-                            self.server_entity_data
-                                .server_object_entity(
-                                    *server_object_selectable.target_object_entity.inner(),
-                                )
-                                .name,
-                        )
+                            arguments: server_object_selectable
+                                .arguments
+                                .iter()
+                                .map(|argument| ArgumentKeyAndValue {
+                                    key: argument.item.name.item.unchecked_conversion(),
+                                    value: NonConstantValue::Variable(argument.item.name.item),
+                                })
+                                .collect(),
+                            // Each field in the path may have a different (and possibly
+                            // abstract) type than the primary field.
+                            concrete_type: target_object_entity.concrete_type,
+                        })
                     }
+                    SchemaServerObjectSelectableVariant::InlineFragment => Some(
+                        WrappedSelectionMapSelection::InlineFragment(target_object_entity.name),
+                    ),
+                    // The parent is already an instance of the supertype.
+                    SchemaServerObjectSelectableVariant::Upcast => None,
                 }
             })
             .collect::<Vec<_>>();
@@ -215,6 +241,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 root_object_entity_id: parent_object_entity_id,
                 subfields_or_inline_fragments: subfields_or_inline_fragments.clone(),
                 field_map,
+                primary_field_path_arguments,
             }),
             variable_definitions: vec![],
             type_and_field: ObjectTypeAndFieldName {
@@ -232,7 +259,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             client_field_scalar_selection_name,
             maybe_abstract_parent_object_entity_id,
             mutation_field_client_field_id,
-            mutation_field_payload_type_name,
+            maybe_abstract_parent_object_entity_name,
         )?;
         Ok(UnprocessedClientFieldItem {
             client_field_id: mutation_field_client_field_id,
@@ -1,4 +1,5 @@
 pub(crate) mod add_link_fields;
+pub(crate) mod add_pagination_fields;
 mod argument_map;
 mod create_additional_fields_error;
 pub(crate) mod expose_field_directive;
@@ -9,22 +9,25 @@ use graphql_lang_types::{
     GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation,
 };
 use intern::string_key::Intern;
+use isograph_config::OperationNameOptions;
 use isograph_lang_types::{
     ArgumentKeyAndValue, ClientScalarSelectableId, DefinitionLocation, EmptyDirectiveSet,
-    NonConstantValue, RefetchQueryIndex, ScalarSelection, ScalarSelectionDirectiveSet,
-    SelectionFieldArgument, SelectionType, SelectionTypeContainingSelections, ServerEntityId,
-    ServerObjectEntityId, ServerObjectSelectableId, ServerScalarEntityId, VariableDefinition,
+    NonConstantValue, ObjectSelection, RefetchQueryIndex, ScalarSelection,
+    ScalarSelectionDirectiveSet, SelectionCondition, SelectionFieldArgument, SelectionType,
+    SelectionTypeContainingSelections, ServerEntityId, ServerObjectEntityId,
+    ServerObjectSelectableId, ServerScalarEntityId, VariableDefinition,
 };
 use lazy_static::lazy_static;
 
 use crate::{
-    create_transformed_name_and_arguments,
+    client_field_overrides, create_transformed_name_and_arguments,
     field_loadability::{categorize_field_loadability, Loadability},
-    initial_variable_context, transform_arguments_with_child_context,
+    implicit_concrete_type_refinements, initial_variable_context,
+    transform_arguments_with_child_context, transform_conditions_with_child_context,
     transform_name_and_arguments_with_child_variable_context, ClientFieldVariant,
-    ClientOrServerObjectSelectable, ClientScalarOrObjectSelectable, ClientScalarSelectable,
-    ClientSelectable, ClientSelectableId, ImperativelyLoadedFieldVariant, NameAndArguments,
-    NetworkProtocol, PathToRefetchField, RootOperationName, Schema,
+    ClientObjectSelectable, ClientOrServerObjectSelectable, ClientScalarOrObjectSelectable,
+    ClientScalarSelectable, ClientSelectable, ClientSelectableId, ImperativelyLoadedFieldVariant,
+    NameAndArguments, NetworkProtocol, PathToRefetchField, RootOperationName, Schema,
     SchemaServerObjectSelectableVariant, ServerObjectEntity, ServerObjectEntityExtraInfo,
     ServerObjectSelectable, ValidatedScalarSelection, ValidatedSelection, VariableContext,
 };
@@ -69,10 +72,16 @@ pub enum MergedServerSelection {
 }
 
 impl MergedServerSelection {
+    /// The variables used by the arguments and conditions of this selection and
+    /// of the selections it contains, i.e. the variables that a query containing
+    /// it must declare.
     pub fn reachable_variables(&self) -> Vec<VariableName> {
         match self {
-            MergedServerSelection::ScalarField(field) => get_variables(&field.arguments).collect(),
+            MergedServerSelection::ScalarField(field) => get_variables(&field.arguments)
+                .chain(get_condition_variables(&field.conditions))
+                .collect(),
             MergedServerSelection::LinkedField(field) => get_variables(&field.arguments)
+                .chain(get_condition_variables(&field.conditions))
                 .chain(
                     field
                         .selection_map
@@ -80,23 +89,35 @@ impl MergedServerSelection {
                         .flat_map(|x| x.reachable_variables()),
                 )
                 .collect(),
-            MergedServerSelection::InlineFragment(_) => vec![],
+            MergedServerSelection::InlineFragment(inline_fragment) => inline_fragment
+                .selection_map
+                .values()
+                .flat_map(|x| x.reachable_variables())
+                .collect(),
         }
     }
 }
 
+fn get_condition_variables(
+    conditions: &[SelectionCondition],
+) -> impl Iterator<Item = VariableName> + '_ {
+    conditions
+        .iter()
+        .flat_map(|condition| condition.value.variables())
+}
+
+/// The variables used by the arguments, including variables nested within
+/// object and list values.
 fn get_variables(arguments: &[ArgumentKeyAndValue]) -> impl Iterator<Item = VariableName> + '_ {
-    arguments.iter().flat_map(|arg| match arg.value {
-        isograph_lang_types::NonConstantValue::Variable(v) => Some(v),
-        // TODO handle variables in objects and lists
-        _ => None,
-    })
+    arguments.iter().flat_map(|arg| arg.value.variables())
 }
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct MergedScalarFieldSelection {
     pub name: ScalarSelectableName,
     pub arguments: Vec<ArgumentKeyAndValue>,
+    /// If non-empty, the field is only fetched if all of the conditions are met.
+    pub conditions: Vec<SelectionCondition>,
 }
 
 impl MergedScalarFieldSelection {
@@ -121,6 +142,8 @@ pub struct MergedLinkedFieldSelection {
     pub arguments: Vec<ArgumentKeyAndValue>,
     /// Some if the object is concrete; None otherwise.
     pub concrete_type: Option<IsographObjectTypeName>,
+    /// If non-empty, the field is only fetched if all of the conditions are met.
+    pub conditions: Vec<SelectionCondition>,
 }
 
 impl MergedLinkedFieldSelection {
@@ -310,6 +333,10 @@ fn transform_and_merge_child_selection_map_into_parent_map(
                                 scalar_field_selection.arguments.into_iter(),
                                 parent_variable_context,
                             ),
+                            conditions: transform_conditions_with_child_context(
+                                scalar_field_selection.conditions.into_iter(),
+                                parent_variable_context,
+                            ),
                         })
                     }
                     MergedServerSelection::LinkedField(linked_field_selection) => {
@@ -324,6 +351,10 @@ fn transform_and_merge_child_selection_map_into_parent_map(
                                 linked_field_selection.arguments.into_iter(),
                                 parent_variable_context,
                             ),
+                            conditions: transform_conditions_with_child_context(
+                                linked_field_selection.conditions.into_iter(),
+                                parent_variable_context,
+                            ),
                         })
                     }
                     MergedServerSelection::InlineFragment(inline_fragment_selection) => {
@@ -341,21 +372,36 @@ fn transform_and_merge_child_selection_map_into_parent_map(
             Entry::Occupied(mut occupied) => {
                 let inner = occupied.get_mut();
                 match inner {
-                    MergedServerSelection::ScalarField(_) => {
-                        assert!(
-                            matches!(
-                                new_server_field_selection,
-                                MergedServerSelection::ScalarField(_)
-                            ),
-                            "Error: tried to merge a non-scalar into a scalar. This \
-                            is indicative of a bug in Isograph."
-                        );
-                        // N.B. no action is required, since a scalar has no subselections
+                    MergedServerSelection::ScalarField(target_scalar_field) => {
+                        if let MergedServerSelection::ScalarField(child_scalar_field) =
+                            new_server_field_selection
+                        {
+                            merge_conditions(
+                                &mut target_scalar_field.conditions,
+                                &transform_conditions_with_child_context(
+                                    child_scalar_field.conditions.iter().cloned(),
+                                    parent_variable_context,
+                                ),
+                            );
+                        } else {
+                            panic!(
+                                "Error: tried to merge a non-scalar into a scalar. This \
+                                is indicative of a bug in Isograph."
+                            )
+                        }
+                        // N.B. no other action is required, since a scalar has no subselections
                     }
                     MergedServerSelection::LinkedField(target_linked_field) => {
                         if let MergedServerSelection::LinkedField(child_linked_field) =
                             new_server_field_selection
                         {
+                            merge_conditions(
+                                &mut target_linked_field.conditions,
+                                &transform_conditions_with_child_context(
+                                    child_linked_field.conditions.iter().cloned(),
+                                    parent_variable_context,
+                                ),
+                            );
                             transform_and_merge_child_selection_map_into_parent_map(
                                 &mut target_linked_field.selection_map,
                                 &child_linked_field.selection_map,
@@ -390,6 +436,16 @@ fn transform_and_merge_child_selection_map_into_parent_map(
     }
 }
 
+/// If the same field is selected with different conditions, it is fetched unconditionally.
+fn merge_conditions(
+    target_conditions: &mut Vec<SelectionCondition>,
+    new_conditions: &[SelectionCondition],
+) {
+    if target_conditions.as_slice() != new_conditions {
+        target_conditions.clear();
+    }
+}
+
 fn transform_child_map_with_parent_context(
     selection_map: &MergedSelectionMap,
     parent_variable_context: &VariableContext,
@@ -459,6 +515,7 @@ pub fn get_imperatively_loaded_artifact_info<TNetworkProtocol: NetworkProtocol>(
     nested_selection_map: &MergedSelectionMap,
     reachable_variables: &BTreeSet<VariableName>,
     index: usize,
+    operation_names: &OperationNameOptions,
 ) -> ImperativelyLoadedFieldArtifactInfo {
     let RootRefetchedPath {
         path_to_refetch_field_info,
@@ -482,6 +539,7 @@ pub fn get_imperatively_loaded_artifact_info<TNetworkProtocol: NetworkProtocol>(
         index,
         reachable_variables,
         client_field,
+        operation_names,
     )
 }
 
@@ -503,12 +561,14 @@ fn process_imperatively_loaded_field<TNetworkProtocol: NetworkProtocol>(
     index: usize,
     reachable_variables: &BTreeSet<VariableName>,
     client_field: &ClientScalarSelectable<TNetworkProtocol>,
+    operation_names: &OperationNameOptions,
 ) -> ImperativelyLoadedFieldArtifactInfo {
     let ImperativelyLoadedFieldVariant {
         client_field_scalar_selection_name,
         root_object_entity_id,
         mut subfields_or_inline_fragments,
         top_level_schema_field_arguments,
+        primary_field_path_arguments,
         ..
     } = variant;
 
@@ -527,11 +587,13 @@ fn process_imperatively_loaded_field<TNetworkProtocol: NetworkProtocol>(
         );
     }
 
-    // TODO we need to extend this with variables used in subfields_or_inline_fragments
     let mut definitions_of_used_variables =
         get_used_variable_definitions(reachable_variables, client_field);
 
-    for variable_definition in top_level_schema_field_arguments.iter() {
+    for variable_definition in top_level_schema_field_arguments
+        .iter()
+        .chain(primary_field_path_arguments.iter())
+    {
         definitions_of_used_variables.push(VariableDefinition {
             name: variable_definition.name,
             type_: variable_definition.type_.clone(),
@@ -556,12 +618,14 @@ fn process_imperatively_loaded_field<TNetworkProtocol: NetworkProtocol>(
         )
         .clone();
 
-    let query_name = format!(
-        "{}__{}",
-        root_parent_object, client_field_scalar_selection_name
-    )
-    .intern()
-    .into();
+    let query_name = operation_names
+        .refetch_operation_name(
+            root_parent_object,
+            entrypoint.name,
+            client_field_scalar_selection_name,
+        )
+        .intern()
+        .into();
 
     ImperativelyLoadedFieldArtifactInfo {
         // TODO don't clone, have lifetime parameter
@@ -588,8 +652,6 @@ pub fn imperative_field_subfields_or_inline_fragments(
 ) -> WrappedSelectionMapSelection {
     let top_level_schema_field_arguments = top_level_schema_field_arguments
         .iter()
-        // TODO don't clone
-        .cloned()
         .map(|variable_definition| ArgumentKeyAndValue {
             key: variable_definition.name.item.unchecked_conversion(),
             value: NonConstantValue::Variable(variable_definition.name.item),
@@ -672,6 +734,46 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
     variable_context: &VariableContext,
 ) {
     for validated_selection in validated_selections.iter().filter(filter_id_fields) {
+        if let Some(concrete_object_entity_ids) = implicit_concrete_type_refinements(
+            schema,
+            parent_object_entity_id,
+            &validated_selection.item,
+        ) {
+            // The field is not defined on the (abstract) parent, so it is selected in
+            // an inline fragment on each concrete type that defines it.
+            for concrete_object_entity_id in concrete_object_entity_ids {
+                let concrete_object_entity = schema
+                    .server_entity_data
+                    .server_object_entity(concrete_object_entity_id);
+                let normalization_key =
+                    NormalizationKey::InlineFragment(concrete_object_entity.name);
+                merge_traversal_state
+                    .traversal_path
+                    .push(normalization_key.clone());
+                let inline_fragment = parent_map.entry(normalization_key).or_insert_with(|| {
+                    MergedServerSelection::InlineFragment(MergedInlineFragmentSelection {
+                        type_to_refine_to: concrete_object_entity.name,
+                        selection_map: BTreeMap::new(),
+                    })
+                });
+                let MergedServerSelection::InlineFragment(inline_fragment) = inline_fragment else {
+                    panic!("Expected inline fragment. This is indicative of a bug in Isograph.")
+                };
+                merge_validated_selections_into_selection_map(
+                    schema,
+                    &mut inline_fragment.selection_map,
+                    concrete_object_entity_id,
+                    concrete_object_entity,
+                    std::slice::from_ref(validated_selection),
+                    merge_traversal_state,
+                    encountered_client_field_map,
+                    variable_context,
+                );
+                merge_traversal_state.traversal_path.pop();
+            }
+            continue;
+        }
+
         match &validated_selection.item {
             SelectionType::Scalar(scalar_field_selection) => {
                 match &scalar_field_selection.associated_data {
@@ -741,7 +843,8 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                             None => match newly_encountered_scalar_client_selectable.variant {
                                 ClientFieldVariant::Link => {}
                                 ClientFieldVariant::ImperativelyLoadedField(_)
-                                | ClientFieldVariant::UserWritten(_) => {
+                                | ClientFieldVariant::UserWritten(_)
+                                | ClientFieldVariant::Paginated(_) => {
                                     merge_non_loadable_client_type(
                                         parent_object_entity_id,
                                         parent_object_entity,
@@ -757,7 +860,16 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                                         encountered_client_field_map,
                                         variable_context,
                                         &scalar_field_selection.arguments,
-                                    )
+                                    );
+                                    merge_client_field_overrides(
+                                        schema,
+                                        parent_map,
+                                        merge_traversal_state,
+                                        newly_encountered_scalar_client_selectable,
+                                        encountered_client_field_map,
+                                        variable_context,
+                                        &scalar_field_selection.arguments,
+                                    );
                                 }
                             },
                         }
@@ -769,13 +881,13 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                 };
             }
             SelectionType::Object(object_selection) => {
-                let parent_object_entity_id = *schema
+                let target_object_entity_id = *schema
                     .object_selectable(object_selection.associated_data)
                     .target_object_entity_id()
                     .inner();
                 let object_selection_parent_object = schema
                     .server_entity_data
-                    .server_object_entity(parent_object_entity_id);
+                    .server_object_entity(target_object_entity_id);
 
                 match object_selection.associated_data {
                     DefinitionLocation::Client(newly_encountered_client_object_selectable_id) => {
@@ -795,6 +907,30 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                             &object_selection.arguments,
                         );
 
+                        // The selections made on the client pointer are read from the
+                        // record that it points to, so they must be fetched there, too.
+                        let target_selections = client_pointer_target_selections(
+                            schema,
+                            parent_object_entity_id,
+                            newly_encountered_client_object_selectable,
+                            &object_selection.selection_set,
+                            &variable_context.child_variable_context(
+                                &object_selection.arguments,
+                                &newly_encountered_client_object_selectable.variable_definitions,
+                                &ScalarSelectionDirectiveSet::None(EmptyDirectiveSet {}),
+                            ),
+                        );
+                        merge_validated_selections_into_selection_map(
+                            schema,
+                            parent_map,
+                            parent_object_entity_id,
+                            parent_object_entity,
+                            &target_selections,
+                            merge_traversal_state,
+                            encountered_client_field_map,
+                            variable_context,
+                        );
+
                         merge_traversal_state.accessible_client_fields.insert(
                             SelectionType::Object(newly_encountered_client_object_selectable_id),
                         );
@@ -804,6 +940,41 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                             schema.server_object_selectable(server_object_selectable_id);
 
                         match &server_object_selectable.object_selectable_variant {
+                            SchemaServerObjectSelectableVariant::Upcast => {
+                                // The parent is an instance of the supertype, so the
+                                // selections are made on the parent, without a type
+                                // refinement. Since we did not push onto the traversal
+                                // path, we do not pop from it, either.
+                                let reader_selection_set = inline_fragment_reader_selection_set(
+                                    schema,
+                                    server_object_selectable,
+                                );
+                                for selection_set in
+                                    [&reader_selection_set, &object_selection.selection_set]
+                                {
+                                    merge_validated_selections_into_selection_map(
+                                        schema,
+                                        parent_map,
+                                        parent_object_entity_id,
+                                        parent_object_entity,
+                                        selection_set,
+                                        merge_traversal_state,
+                                        encountered_client_field_map,
+                                        variable_context,
+                                    );
+                                }
+
+                                create_merged_selection_map_for_field_and_insert_into_global_map(
+                                    schema,
+                                    target_object_entity_id,
+                                    parent_object_entity,
+                                    &object_selection.selection_set,
+                                    encountered_client_field_map,
+                                    DefinitionLocation::Server(server_object_selectable_id),
+                                    &server_object_selectable.initial_variable_context(),
+                                );
+                                continue;
+                            }
                             SchemaServerObjectSelectableVariant::InlineFragment => {
                                 let reader_selection_set = inline_fragment_reader_selection_set(
                                     schema,
@@ -848,12 +1019,12 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                                     ) => {
                                         let object_selection_parent_object = schema
                                             .server_entity_data
-                                            .server_object_entity(parent_object_entity_id);
+                                            .server_object_entity(target_object_entity_id);
 
                                         merge_validated_selections_into_selection_map(
                                             schema,
                                             &mut existing_inline_fragment.selection_map,
-                                            parent_object_entity_id,
+                                            target_object_entity_id,
                                             object_selection_parent_object,
                                             &reader_selection_set,
                                             merge_traversal_state,
@@ -863,7 +1034,7 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                                         merge_validated_selections_into_selection_map(
                                             schema,
                                             &mut existing_inline_fragment.selection_map,
-                                            parent_object_entity_id,
+                                            target_object_entity_id,
                                             object_selection_parent_object,
                                             &object_selection.selection_set,
                                             merge_traversal_state,
@@ -873,7 +1044,7 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
 
                                         create_merged_selection_map_for_field_and_insert_into_global_map(
                                             schema,
-                                            parent_object_entity_id,
+                                            target_object_entity_id,
                                             parent_object_entity,
                                             &object_selection.selection_set,
                                             encountered_client_field_map,
@@ -890,6 +1061,13 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                                     variable_context,
                                 )
                                 .normalization_key();
+                                let conditions = transform_conditions_with_child_context(
+                                    object_selection
+                                        .conditions
+                                        .iter()
+                                        .map(|condition| condition.item.clone()),
+                                    variable_context,
+                                );
 
                                 merge_traversal_state
                                     .traversal_path
@@ -925,6 +1103,7 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                                                         .map(|arg| arg.item.into_key_and_value()),
                                                     variable_context,
                                                 ),
+                                                conditions: conditions.clone(),
                                             },
                                         )
                                     });
@@ -936,10 +1115,14 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
                                         )
                                     }
                                     MergedServerSelection::LinkedField(existing_linked_field) => {
+                                        merge_conditions(
+                                            &mut existing_linked_field.conditions,
+                                            &conditions,
+                                        );
                                         merge_validated_selections_into_selection_map(
                                             schema,
                                             &mut existing_linked_field.selection_map,
-                                            parent_object_entity_id,
+                                            target_object_entity_id,
                                             object_selection_parent_object,
                                             &object_selection.selection_set,
                                             merge_traversal_state,
@@ -972,6 +1155,150 @@ fn merge_validated_selections_into_selection_map<TNetworkProtocol: NetworkProtoc
     );
 }
 
+/// A client pointer resolves to a link, and the selections made on the client pointer
+/// are read from the record with that link. We cannot know statically which record
+/// that is, so we select them wherever the client pointer's reader selection set
+/// selects the link of a record of the target type (or on the parent, if the client
+/// pointer selects the link of its parent.)
+///
+/// The returned selections are made on the parent of the client pointer, so the
+/// arguments and conditions of the linked fields leading to those records are
+/// transformed out of the client pointer's variable context.
+fn client_pointer_target_selections<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_object_entity_id: ServerObjectEntityId,
+    client_pointer: &ClientObjectSelectable<TNetworkProtocol>,
+    target_selection_set: &[WithSpan<ValidatedSelection>],
+    client_pointer_variable_context: &VariableContext,
+) -> Vec<WithSpan<ValidatedSelection>> {
+    let target_object_entity_id = *client_pointer.target_object_entity.inner();
+    let mut selections = selections_leading_to_links(
+        schema,
+        &client_pointer.reader_selection_set,
+        target_object_entity_id,
+        target_selection_set,
+        client_pointer_variable_context,
+    );
+    if parent_object_entity_id == target_object_entity_id
+        && selects_link(schema, &client_pointer.reader_selection_set)
+    {
+        selections.extend(target_selection_set.iter().cloned());
+    }
+    selections
+}
+
+fn selections_leading_to_links<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selection_set: &[WithSpan<ValidatedSelection>],
+    target_object_entity_id: ServerObjectEntityId,
+    target_selection_set: &[WithSpan<ValidatedSelection>],
+    client_pointer_variable_context: &VariableContext,
+) -> Vec<WithSpan<ValidatedSelection>> {
+    selection_set
+        .iter()
+        .filter_map(|selection| {
+            let object_selection = match &selection.item {
+                SelectionType::Object(object_selection) => object_selection,
+                SelectionType::Scalar(_) => return None,
+            };
+            let server_object_selectable_id = match object_selection.associated_data {
+                DefinitionLocation::Server(server_object_selectable_id) => {
+                    server_object_selectable_id
+                }
+                // Selections within other client pointers are read from other records.
+                DefinitionLocation::Client(_) => return None,
+            };
+
+            let mut inner_selection_set = selections_leading_to_links(
+                schema,
+                &object_selection.selection_set,
+                target_object_entity_id,
+                target_selection_set,
+                client_pointer_variable_context,
+            );
+            if *schema
+                .server_object_selectable(server_object_selectable_id)
+                .target_object_entity
+                .inner()
+                == target_object_entity_id
+                && selects_link(schema, &object_selection.selection_set)
+            {
+                inner_selection_set.extend(target_selection_set.iter().cloned());
+            }
+            if inner_selection_set.is_empty() {
+                return None;
+            }
+
+            // The transformed arguments are sorted by name, so they are matched with
+            // the selection's arguments by name, not by position.
+            let transformed_arguments = transform_arguments_with_child_context(
+                object_selection
+                    .arguments
+                    .iter()
+                    .map(|argument| argument.item.into_key_and_value()),
+                client_pointer_variable_context,
+            );
+            let arguments = object_selection
+                .arguments
+                .iter()
+                .map(|argument| {
+                    let transformed_value = transformed_arguments
+                        .iter()
+                        .find(|transformed_argument| {
+                            transformed_argument.key == argument.item.name.item
+                        })
+                        .expect("Expected each argument to be transformed")
+                        .value
+                        .clone();
+                    argument.clone().map(|argument| SelectionFieldArgument {
+                        name: argument.name,
+                        value: argument.value.map(|_| transformed_value),
+                    })
+                })
+                .collect();
+            let conditions = object_selection
+                .conditions
+                .iter()
+                .zip(transform_conditions_with_child_context(
+                    object_selection
+                        .conditions
+                        .iter()
+                        .map(|condition| condition.item.clone()),
+                    client_pointer_variable_context,
+                ))
+                .map(|(condition, transformed_condition)| {
+                    condition.clone().map(|_| transformed_condition)
+                })
+                .collect();
+
+            Some(selection.clone().map(|_| {
+                SelectionType::Object(ObjectSelection {
+                    selection_set: inner_selection_set,
+                    arguments,
+                    conditions,
+                    ..object_selection.clone()
+                })
+            }))
+        })
+        .collect()
+}
+
+fn selects_link<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    selection_set: &[WithSpan<ValidatedSelection>],
+) -> bool {
+    selection_set.iter().any(|selection| match &selection.item {
+        SelectionType::Scalar(scalar_selection) => match scalar_selection.associated_data {
+            DefinitionLocation::Client(client_scalar_selectable_id) => matches!(
+                schema.client_field(client_scalar_selectable_id).variant,
+                ClientFieldVariant::Link
+            ),
+            DefinitionLocation::Server(_) => false,
+        },
+        SelectionType::Object(_) => false,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn insert_imperative_field_into_refetch_paths<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
@@ -1089,6 +1416,82 @@ fn merge_non_loadable_client_type<TNetworkProtocol: NetworkProtocol>(
     );
 }
 
+/// A client field defined on an abstract type can be overridden by client fields with
+/// the same name defined on its concrete types. The reader reads the override of the
+/// record's concrete type instead, so the selections of each override are selected in
+/// an inline fragment on that type.
+fn merge_client_field_overrides<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_map: &mut MergedSelectionMap,
+    parent_merge_traversal_state: &mut ScalarClientFieldTraversalState,
+    client_field: &ClientScalarSelectable<TNetworkProtocol>,
+    encountered_client_field_map: &mut FieldToCompletedMergeTraversalStateMap,
+    parent_variable_context: &VariableContext,
+    selection_arguments: &[WithLocation<SelectionFieldArgument>],
+) {
+    for (concrete_object_entity_id, override_id) in client_field_overrides(schema, client_field) {
+        let concrete_object_entity = schema
+            .server_entity_data
+            .server_object_entity(concrete_object_entity_id);
+        let normalization_key = NormalizationKey::InlineFragment(concrete_object_entity.name);
+        parent_merge_traversal_state
+            .traversal_path
+            .push(normalization_key.clone());
+
+        let inline_fragment = parent_map.entry(normalization_key).or_insert_with(|| {
+            MergedServerSelection::InlineFragment(MergedInlineFragmentSelection {
+                type_to_refine_to: concrete_object_entity.name,
+                selection_map: BTreeMap::new(),
+            })
+        });
+        let MergedServerSelection::InlineFragment(inline_fragment) = inline_fragment else {
+            panic!(
+                "Expected inline fragment, but encountered another selection. \
+                This is indicative of a bug in Isograph."
+            )
+        };
+
+        // The __typename is selected in the inline fragment, as it is for the
+        // asConcreteType fields.
+        inline_fragment
+            .selection_map
+            .entry(NormalizationKey::ServerField(NameAndArguments {
+                name: (*TYPENAME_FIELD_NAME).into(),
+                arguments: vec![],
+            }))
+            .or_insert_with(|| {
+                MergedServerSelection::ScalarField(MergedScalarFieldSelection {
+                    name: (*TYPENAME_FIELD_NAME).into(),
+                    arguments: vec![],
+                    conditions: vec![],
+                })
+            });
+        merge_non_loadable_client_type(
+            concrete_object_entity_id,
+            concrete_object_entity,
+            schema,
+            &mut inline_fragment.selection_map,
+            parent_merge_traversal_state,
+            SelectionType::Scalar(override_id),
+            SelectionType::Scalar(schema.client_field(override_id)),
+            encountered_client_field_map,
+            parent_variable_context,
+            selection_arguments,
+        );
+        select_typename_and_id_fields_in_merged_selection(
+            schema,
+            &mut inline_fragment.selection_map,
+            concrete_object_entity,
+            concrete_object_entity_id,
+        );
+
+        parent_merge_traversal_state
+            .accessible_client_fields
+            .insert(SelectionType::Scalar(override_id));
+        parent_merge_traversal_state.traversal_path.pop();
+    }
+}
+
 fn merge_scalar_server_field(
     scalar_field: &ValidatedScalarSelection,
     parent_map: &mut MergedSelectionMap,
@@ -1099,12 +1502,20 @@ fn merge_scalar_server_field(
         &scalar_field.arguments,
         variable_context,
     ));
+    let conditions = transform_conditions_with_child_context(
+        scalar_field
+            .conditions
+            .iter()
+            .map(|condition| condition.item.clone()),
+        variable_context,
+    );
     match parent_map.entry(normalization_key) {
-        Entry::Occupied(occupied) => {
-            match occupied.get() {
-                MergedServerSelection::ScalarField(_) => {
+        Entry::Occupied(mut occupied) => {
+            match occupied.get_mut() {
+                MergedServerSelection::ScalarField(existing_scalar_field) => {
                     // TODO check that the existing server field matches the one we
                     // would create.
+                    merge_conditions(&mut existing_scalar_field.conditions, &conditions);
                 }
                 MergedServerSelection::LinkedField(_) => {
                     panic!("Unexpected linked field, probably a bug in Isograph");
@@ -1125,6 +1536,7 @@ fn merge_scalar_server_field(
                             .map(|arg| arg.item.into_key_and_value()),
                         variable_context,
                     ),
+                    conditions,
                 },
             ));
         }
@@ -1149,11 +1561,13 @@ fn select_typename_and_id_fields_in_merged_selection<TNetworkProtocol: NetworkPr
         .and_then(|ServerObjectEntityExtraInfo { id_field, .. }| *id_field)
     {
         match merged_selection_map.entry(NormalizationKey::Id) {
-            Entry::Occupied(occupied) => {
-                match occupied.get() {
-                    MergedServerSelection::ScalarField(_) => {
+            Entry::Occupied(mut occupied) => {
+                match occupied.get_mut() {
+                    MergedServerSelection::ScalarField(existing_id_field) => {
                         // TODO check that the existing server field matches the one we
                         // would create.
+                        // The id field must always be fetched.
+                        existing_id_field.conditions.clear();
                     }
                     MergedServerSelection::LinkedField(_) => {
                         panic!("Unexpected linked field for id, probably a bug in Isograph");
@@ -1174,6 +1588,7 @@ fn select_typename_and_id_fields_in_merged_selection<TNetworkProtocol: NetworkPr
                     MergedScalarFieldSelection {
                         name,
                         arguments: vec![],
+                        conditions: vec![],
                     },
                 ));
             }
@@ -1218,6 +1633,7 @@ pub fn selection_map_wrapped(
                         selection_map: inner_selection_map,
                         arguments,
                         concrete_type,
+                        conditions: vec![],
                     }),
                 );
             }
@@ -1245,6 +1661,7 @@ fn maybe_add_typename_selection(selections: &mut MergedSelectionMap) {
         MergedServerSelection::ScalarField(MergedScalarFieldSelection {
             name: (*TYPENAME_FIELD_NAME).into(),
             arguments: vec![],
+            conditions: vec![],
         }),
     );
 }
@@ -1305,6 +1722,7 @@ pub fn inline_fragment_reader_selection_set<TNetworkProtocol: NetworkProtocol>(
             ),
             name: WithLocation::new("__typename".intern().into(), Location::generated()),
             reader_alias: None,
+            conditions: vec![],
         }),
         Span::todo_generated(),
     );
@@ -1324,9 +1742,17 @@ pub fn inline_fragment_reader_selection_set<TNetworkProtocol: NetworkProtocol>(
             scalar_selection_directive_set: ScalarSelectionDirectiveSet::None(EmptyDirectiveSet {}),
             name: WithLocation::new((*LINK_FIELD_NAME).into(), Location::generated()),
             reader_alias: None,
+            conditions: vec![],
         }),
         Span::todo_generated(),
     );
 
-    vec![typename_selection, link_selection]
+    match server_object_selectable.object_selectable_variant {
+        // An upcast always succeeds, so the condition does not read the __typename.
+        SchemaServerObjectSelectableVariant::Upcast => vec![link_selection],
+        SchemaServerObjectSelectableVariant::InlineFragment
+        | SchemaServerObjectSelectableVariant::LinkedField => {
+            vec![typename_selection, link_selection]
+        }
+    }
 }
@@ -10,8 +10,8 @@ use isograph_lang_types::{
 };
 
 use crate::{
-    ClientFieldVariant, NetworkProtocol, ObjectSelectableId, RefetchStrategy, ScalarSelectableId,
-    UserWrittenClientPointerInfo, ValidatedSelection,
+    ClientFieldVariant, ClientPointerVariant, NetworkProtocol, ObjectSelectableId, RefetchStrategy,
+    ScalarSelectableId, UserWrittenClientPointerInfo, ValidatedSelection,
 };
 
 pub type ClientSelectableId = SelectionType<ClientScalarSelectableId, ClientObjectSelectableId>;
@@ -69,6 +69,7 @@ pub struct ClientObjectSelectable<TNetworkProtocol: NetworkProtocol> {
 
     pub output_format: PhantomData<TNetworkProtocol>,
     pub info: UserWrittenClientPointerInfo,
+    pub variant: ClientPointerVariant,
 }
 
 impl_with_id!(ClientObjectSelectable<TNetworkProtocol: NetworkProtocol>, ClientObjectSelectableId);
@@ -2,8 +2,9 @@ use std::{collections::BTreeMap, marker::PhantomData};
 
 use common_lang_types::{
     DescriptionValue, GraphQLScalarTypeName, IsographObjectTypeName, JavascriptName,
-    SelectableName, WithLocation, WithSpan,
+    SelectableName, StringLiteralValue, WithLocation, WithSpan,
 };
+use graphql_lang_types::{GraphQLConstantValue, GraphQLDirective};
 use isograph_lang_types::{
     impl_with_id, DefinitionLocation, SelectionType, ServerObjectEntityId, ServerScalarEntityId,
 };
@@ -15,6 +16,9 @@ pub struct ServerScalarEntity<TNetworkProtocol: NetworkProtocol> {
     pub description: Option<WithSpan<DescriptionValue>>,
     pub name: WithLocation<GraphQLScalarTypeName>,
     pub javascript_name: JavascriptName,
+    /// The URL of the specification of the scalar, e.g. from
+    /// @specifiedBy(url: "https://tools.ietf.org/html/rfc4122") in GraphQL.
+    pub specified_by_url: Option<StringLiteralValue>,
     pub output_format: PhantomData<TNetworkProtocol>,
 }
 
@@ -30,6 +34,12 @@ pub struct ServerObjectEntity<TNetworkProtocol: NetworkProtocol> {
     pub name: IsographObjectTypeName,
     /// Some if the object is concrete; None otherwise.
     pub concrete_type: Option<IsographObjectTypeName>,
+    /// Input objects can be the types of arguments, but cannot be selected.
+    pub is_input_object: bool,
+    /// Directives on the object that the compiler does not otherwise process, e.g.
+    /// @cacheControl(maxAge: 60) in GraphQL. Only the directives listed in the
+    /// propagated_object_directives config option are included in artifacts.
+    pub directives: Vec<GraphQLDirective<GraphQLConstantValue>>,
 
     pub output_associated_data: TNetworkProtocol::SchemaObjectAssociatedData,
 }
@@ -1,7 +1,8 @@
 use std::{fmt::Debug, marker::PhantomData};
 
 use common_lang_types::{
-    DescriptionValue, ServerObjectSelectableName, ServerScalarSelectableName, WithLocation,
+    DescriptionValue, ServerObjectSelectableName, ServerScalarSelectableName, StringLiteralValue,
+    WithLocation,
 };
 use isograph_lang_types::{
     impl_with_id, impl_with_target_id, SelectionType, ServerEntityId, ServerObjectEntityId,
@@ -9,7 +10,7 @@ use isograph_lang_types::{
     VariableDefinition,
 };
 
-use crate::{NetworkProtocol, SchemaServerObjectSelectableVariant};
+use crate::{CacheControl, NetworkProtocol, SchemaServerObjectSelectableVariant};
 
 #[derive(Debug, Clone)]
 pub struct ServerScalarSelectable<TNetworkProtocol: NetworkProtocol> {
@@ -20,6 +21,18 @@ pub struct ServerScalarSelectable<TNetworkProtocol: NetworkProtocol> {
 
     pub parent_object_entity_id: ServerObjectEntityId,
     pub arguments: Vec<WithLocation<VariableDefinition<ServerEntityId>>>,
+    /// Some if the field is deprecated. Used by the language server.
+    pub deprecation_reason: Option<DescriptionValue>,
+    /// The cost of selecting the field, e.g. from @cost(value: 5) in GraphQL.
+    pub cost: Option<u32>,
+    /// The team that owns the field, e.g. from @owner(team: "payments") in GraphQL.
+    pub owner: Option<StringLiteralValue>,
+    /// How long the field may be cached, e.g. from @cacheControl(maxAge: 60) in GraphQL.
+    pub cache_control: Option<CacheControl>,
+    /// The list levels at which the field is only null if there is an error, e.g.
+    /// from @semanticNonNull(levels: [0]) in GraphQL. Level 0 is the field itself,
+    /// level 1 is the items of a list, and so on.
+    pub semantic_non_null_levels: Vec<u32>,
     pub phantom_data: PhantomData<TNetworkProtocol>,
 }
 
@@ -37,6 +50,18 @@ pub struct ServerObjectSelectable<TNetworkProtocol: NetworkProtocol> {
 
     pub parent_object_entity_id: ServerObjectEntityId,
     pub arguments: Vec<WithLocation<VariableDefinition<ServerEntityId>>>,
+    /// Some if the field is deprecated. Used by the language server.
+    pub deprecation_reason: Option<DescriptionValue>,
+    /// The cost of selecting the field, e.g. from @cost(value: 5) in GraphQL.
+    pub cost: Option<u32>,
+    /// The team that owns the field, e.g. from @owner(team: "payments") in GraphQL.
+    pub owner: Option<StringLiteralValue>,
+    /// How long the field may be cached, e.g. from @cacheControl(maxAge: 60) in GraphQL.
+    pub cache_control: Option<CacheControl>,
+    /// The list levels at which the field is only null if there is an error, e.g.
+    /// from @semanticNonNull(levels: [0]) in GraphQL. Level 0 is the field itself,
+    /// level 1 is the items of a list, and so on.
+    pub semantic_non_null_levels: Vec<u32>,
     pub phantom_data: PhantomData<TNetworkProtocol>,
 }
 
@@ -1,10 +1,12 @@
-use common_lang_types::{ClientSelectableName, DescriptionValue, ObjectTypeAndFieldName, WithSpan};
+use common_lang_types::{
+    ClientSelectableName, DescriptionValue, ObjectTypeAndFieldName, TextSource, WithSpan,
+};
 use impl_base_types_macro::impl_for_selection_type;
 use isograph_lang_types::{ServerEntityId, ServerObjectEntityId, VariableDefinition};
 
 use crate::{
-    ClientFieldVariant, ClientObjectSelectable, ClientScalarSelectable, NetworkProtocol,
-    ObjectSelectableId, RefetchStrategy, ScalarSelectableId, ValidatedSelection,
+    ClientFieldVariant, ClientObjectSelectable, ClientPointerVariant, ClientScalarSelectable,
+    NetworkProtocol, ObjectSelectableId, RefetchStrategy, ScalarSelectableId, ValidatedSelection,
 };
 
 #[impl_for_selection_type]
@@ -19,6 +21,9 @@ pub trait ClientScalarOrObjectSelectable {
 
     fn variable_definitions(&self) -> &[WithSpan<VariableDefinition<ServerEntityId>>];
 
+    /// The iso literal in which this was declared, if it was written by the user.
+    fn text_source(&self) -> Option<TextSource>;
+
     fn client_type(&self) -> &'static str;
 }
 
@@ -67,6 +72,15 @@ impl<TNetworkProtocol: NetworkProtocol> ClientScalarOrObjectSelectable
         &self.variable_definitions
     }
 
+    fn text_source(&self) -> Option<TextSource> {
+        match self.variant {
+            ClientFieldVariant::UserWritten(info) => Some(info.text_source),
+            ClientFieldVariant::ImperativelyLoadedField(_)
+            | ClientFieldVariant::Link
+            | ClientFieldVariant::Paginated(_) => None,
+        }
+    }
+
     fn client_type(&self) -> &'static str {
         "field"
     }
@@ -100,13 +114,22 @@ impl<TNetworkProtocol: NetworkProtocol> ClientScalarOrObjectSelectable
     }
 
     fn selection_set_for_parent_query(&self) -> &[WithSpan<ValidatedSelection>] {
-        &self.reader_selection_set
+        match self.variant {
+            ClientPointerVariant::UserWritten => &self.reader_selection_set,
+            // The reader selection set of a node pointer fetches the record with
+            // node(id: $id), but the record is read from the store by its id.
+            ClientPointerVariant::Node => &[],
+        }
     }
 
     fn variable_definitions(&self) -> &[WithSpan<VariableDefinition<ServerEntityId>>] {
         &self.variable_definitions
     }
 
+    fn text_source(&self) -> Option<TextSource> {
+        Some(self.info.text_source)
+    }
+
     fn client_type(&self) -> &'static str {
         "pointer"
     }
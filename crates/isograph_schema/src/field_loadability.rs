@@ -27,13 +27,15 @@ pub fn categorize_field_loadability<'a, TNetworkProtocol: NetworkProtocol>(
 ) -> Option<Loadability<'a>> {
     match &client_field.variant {
         ClientFieldVariant::Link => None,
-        ClientFieldVariant::UserWritten(_) => match selection_variant {
-            ScalarSelectionDirectiveSet::None(_) => None,
-            ScalarSelectionDirectiveSet::Updatable(_) => None,
-            ScalarSelectionDirectiveSet::Loadable(l) => {
-                Some(Loadability::LoadablySelectedField(&l.loadable))
+        ClientFieldVariant::UserWritten(_) | ClientFieldVariant::Paginated(_) => {
+            match selection_variant {
+                ScalarSelectionDirectiveSet::None(_) => None,
+                ScalarSelectionDirectiveSet::Updatable(_) => None,
+                ScalarSelectionDirectiveSet::Loadable(l) => {
+                    Some(Loadability::LoadablySelectedField(&l.loadable))
+                }
             }
-        },
+        }
         ClientFieldVariant::ImperativelyLoadedField(i) => {
             Some(Loadability::ImperativelyLoadedField(i))
         }
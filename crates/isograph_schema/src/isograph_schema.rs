@@ -32,6 +32,7 @@ use crate::{
 lazy_static! {
     pub static ref ID_GRAPHQL_TYPE: GraphQLScalarTypeName = "ID".intern().into();
     pub static ref STRING_JAVASCRIPT_TYPE: JavascriptName = "string".intern().into();
+    static ref NODE_INTERFACE_NAME: UnvalidatedTypeName = "Node".intern().into();
 }
 
 #[derive(Debug, Clone)]
@@ -354,7 +355,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
     pub fn server_selectable(
         &self,
         server_selectable_id: ServerSelectableId,
-    ) -> ServerSelectable<TNetworkProtocol> {
+    ) -> ServerSelectable<'_, TNetworkProtocol> {
         match server_selectable_id {
             SelectionType::Scalar(server_scalar_selectable_id) => {
                 SelectionType::Scalar(self.server_scalar_selectable(server_scalar_selectable_id))
@@ -486,7 +487,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
     pub fn object_selectable(
         &self,
         field_id: ObjectSelectableId,
-    ) -> ObjectSelectable<TNetworkProtocol> {
+    ) -> ObjectSelectable<'_, TNetworkProtocol> {
         match field_id {
             DefinitionLocation::Server(server_field_id) => {
                 DefinitionLocation::Server(self.server_object_selectable(server_field_id))
@@ -560,7 +561,8 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                     SelectionType::Scalar(field),
                     info.client_field_directive_set,
                 )),
-                ClientFieldVariant::ImperativelyLoadedField(_) => None,
+                ClientFieldVariant::ImperativelyLoadedField(_)
+                | ClientFieldVariant::Paginated(_) => None,
             })
             .chain(
                 self.client_object_selectables
@@ -594,7 +596,7 @@ impl<TNetworkProtocol: NetworkProtocol> ServerEntityData<TNetworkProtocol> {
             .map(|(id, scalar)| WithId::new(id.into(), scalar))
     }
 
-    pub fn server_entity(&self, type_id: ServerEntityId) -> ServerEntity<TNetworkProtocol> {
+    pub fn server_entity(&self, type_id: ServerEntityId) -> ServerEntity<'_, TNetworkProtocol> {
         match type_id {
             ServerEntityId::Object(object_entity_id) => {
                 ServerEntity::Object(self.server_object_entity(object_entity_id))
@@ -722,6 +724,7 @@ fn add_schema_defined_scalar_type<TNetworkProtocol: NetworkProtocol>(
         description: None,
         name: typename,
         javascript_name,
+        specified_by_url: None,
         output_format: std::marker::PhantomData,
     });
     defined_types.insert(
@@ -735,7 +738,12 @@ fn add_schema_defined_scalar_type<TNetworkProtocol: NetworkProtocol>(
 // This struct is indicative of poor data modeling.
 pub enum SchemaServerObjectSelectableVariant {
     LinkedField,
+    /// A refinement to a subtype of the parent, e.g. Node.asPet
     InlineFragment,
+    /// A refinement to a supertype of the parent, e.g. Pet.asNode. Since the parent
+    /// is always an instance of the supertype, its selections are selected on the
+    /// parent directly.
+    Upcast,
 }
 
 pub type ValidatedSelection =
@@ -774,23 +782,99 @@ fn set_and_validate_id_field(
     match inner_non_null_named_type {
         Some(type_) => {
             if type_.0.item != *ID_GRAPHQL_TYPE {
-                options.on_invalid_id_type.on_failure(|| {
-                    CreateAdditionalFieldsError::IdFieldMustBeNonNullIdType {
+                options
+                    .on_invalid_id_type(parent_type_name)
+                    .on_failure(|| CreateAdditionalFieldsError::IdFieldMustBeNonNullIdType {
                         strong_field_name: "id",
                         parent_type: parent_type_name,
-                    }
-                })?;
+                    })?;
             }
             Ok(())
         }
         None => {
-            options.on_invalid_id_type.on_failure(|| {
-                CreateAdditionalFieldsError::IdFieldMustBeNonNullIdType {
+            options
+                .on_invalid_id_type(parent_type_name)
+                .on_failure(|| CreateAdditionalFieldsError::IdFieldMustBeNonNullIdType {
                     strong_field_name: "id",
                     parent_type: parent_type_name,
-                }
-            })?;
+                })?;
             Ok(())
         }
     }
 }
+
+/// Validate that every type that implements Node (i.e. every type to which Node
+/// can be refined) has an id field, according to the on_missing_node_id_field
+/// config option.
+// TODO this is a GraphQL-ism
+pub fn validate_node_types_have_id_fields<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    options: &CompilerConfigOptions,
+    name_locations: &HashMap<ServerObjectEntityId, Location>,
+) -> Result<(), Vec<WithLocation<CreateAdditionalFieldsError>>> {
+    let Some(ServerEntityId::Object(node_entity_id)) = schema
+        .server_entity_data
+        .defined_entities
+        .get(&(*NODE_INTERFACE_NAME))
+    else {
+        return Ok(());
+    };
+    let Some(node_extra_info) = schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(node_entity_id)
+    else {
+        return Ok(());
+    };
+
+    let mut errors = vec![];
+    for selectable_id in node_extra_info.selectables.values() {
+        let DefinitionLocation::Server(SelectionType::Object(server_object_selectable_id)) =
+            selectable_id
+        else {
+            continue;
+        };
+        let server_object_selectable =
+            schema.server_object_selectable(*server_object_selectable_id);
+        if !matches!(
+            server_object_selectable.object_selectable_variant,
+            SchemaServerObjectSelectableVariant::InlineFragment
+        ) {
+            continue;
+        }
+
+        let implementing_entity_id = *server_object_selectable.target_object_entity.inner();
+        let has_id_field = schema
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&implementing_entity_id)
+            .is_some_and(|extra_info| extra_info.id_field.is_some());
+        if has_id_field {
+            continue;
+        }
+
+        let type_name = schema
+            .server_entity_data
+            .server_object_entity(implementing_entity_id)
+            .name;
+        if let Err(error) = options.on_missing_node_id_field(type_name).on_failure(|| {
+            WithLocation::new(
+                CreateAdditionalFieldsError::NodeTypeMissingIdField {
+                    parent_type: type_name,
+                },
+                name_locations
+                    .get(&implementing_entity_id)
+                    .copied()
+                    .unwrap_or_else(Location::generated),
+            )
+        }) {
+            errors.push(error);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
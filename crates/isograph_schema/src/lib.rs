@@ -1,4 +1,8 @@
 mod accessible_client_fields_iterator;
+mod cache_control;
+mod client_field_overrides;
+mod client_type_dependency_graph;
+mod concrete_type_refinements;
 mod create_additional_fields;
 mod create_merged_selection_set;
 mod data_model;
@@ -7,16 +11,27 @@ mod field_loadability;
 mod isograph_schema;
 mod network_protocol;
 mod object_type_definition;
+mod plural_client_fields;
 mod process_client_field_declaration;
 mod refetch_strategy;
+mod request_values;
 mod root_types;
+mod schema_change_impact;
+mod selection_budget;
+mod unused_client_types;
 mod validate_argument_types;
 mod validate_entrypoint;
+mod validate_query_limits;
 mod validate_use_of_arguments;
+mod validation_rules;
 mod variable_context;
 mod visit_selection_set;
 
 pub use accessible_client_fields_iterator::*;
+pub use cache_control::*;
+pub use client_field_overrides::*;
+pub use client_type_dependency_graph::*;
+pub use concrete_type_refinements::*;
 pub use create_additional_fields::*;
 pub use create_merged_selection_set::*;
 pub use data_model::*;
@@ -25,9 +40,16 @@ pub use field_loadability::*;
 pub use isograph_schema::*;
 pub use network_protocol::*;
 pub use object_type_definition::*;
+pub use plural_client_fields::*;
 pub use process_client_field_declaration::*;
 pub use refetch_strategy::*;
+pub use request_values::*;
 pub use root_types::*;
+pub use schema_change_impact::*;
+pub use selection_budget::*;
+pub use unused_client_types::*;
 pub use validate_entrypoint::*;
+pub use validate_query_limits::*;
 pub use validate_use_of_arguments::*;
+pub use validation_rules::*;
 pub use variable_context::*;
@@ -1,15 +1,17 @@
 use std::{error::Error, fmt::Debug, hash::Hash};
 
 use common_lang_types::{
-    DescriptionValue, IsographObjectTypeName, Location, QueryOperationName, QueryText,
-    ServerSelectableName, UnvalidatedTypeName, WithLocation, WithSpan,
+    ArtifactPathAndContent, DescriptionValue, IsographObjectTypeName, Location, QueryOperationName,
+    QueryText, ServerSelectableName, StringLiteralValue, UnvalidatedTypeName, WithLocation,
+    WithSpan,
 };
 use graphql_lang_types::{GraphQLInputValueDefinition, GraphQLTypeAnnotation, RootOperationKind};
+use isograph_config::CompilerConfigOptions;
 use pico::Database;
 
 use crate::{
-    ExposeFieldDirective, MergedSelectionMap, RootOperationName, Schema, ServerObjectEntity,
-    ServerScalarEntity, ValidatedVariableDefinition,
+    CacheControl, ExposeFieldDirective, MergedSelectionMap, RootOperationName, Schema,
+    ServerObjectEntity, ServerScalarEntity, ValidatedVariableDefinition,
 };
 
 pub trait NetworkProtocol:
@@ -25,6 +27,7 @@ where
     fn parse_and_process_type_system_documents(
         db: &Database,
         sources: &Self::Sources,
+        options: &CompilerConfigOptions,
     ) -> Result<ProcessTypeSystemDocumentOutcome<Self>, Box<dyn Error>>;
 
     fn generate_query_text<'a>(
@@ -34,6 +37,18 @@ where
         query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
         root_operation_name: &RootOperationName,
     ) -> QueryText;
+
+    /// The schema, with the schema extensions merged into it, in the schema language
+    /// of the network protocol. It is written to the artifact directory with the
+    /// generate_combined_schema option, for tools that do not read schema extensions.
+    /// None if the network protocol cannot print its schema.
+    fn generate_combined_schema_artifact(
+        _db: &Database,
+        _sources: &Self::Sources,
+        _schema: &Schema<Self>,
+    ) -> Result<Option<ArtifactPathAndContent>, Box<dyn Error>> {
+        Ok(None)
+    }
 }
 
 pub struct ProcessTypeSystemDocumentOutcome<TNetworkProtocol: NetworkProtocol> {
@@ -59,6 +74,18 @@ pub struct FieldToInsert {
     pub name: WithLocation<ServerSelectableName>,
     pub type_: GraphQLTypeAnnotation<UnvalidatedTypeName>,
     pub arguments: Vec<WithLocation<GraphQLInputValueDefinition>>,
+    /// Some if the field is deprecated, e.g. with @deprecated in GraphQL.
+    pub deprecation_reason: Option<DescriptionValue>,
+    /// The cost of selecting the field, e.g. from @cost(value: 5) in GraphQL.
+    pub cost: Option<u32>,
+    /// The team that owns the field, e.g. from @owner(team: "payments") in GraphQL.
+    pub owner: Option<StringLiteralValue>,
+    /// How long the field may be cached, e.g. from @cacheControl(maxAge: 60) in GraphQL.
+    pub cache_control: Option<CacheControl>,
+    /// The list levels at which the field is only null if there is an error, e.g.
+    /// from @semanticNonNull(levels: [0]) in GraphQL. Level 0 is the field itself,
+    /// level 1 is the items of a list, and so on.
+    pub semantic_non_null_levels: Vec<u32>,
 
     // TODO we can probably restructure things to make this less awkward.
     // As in, we should not return GraphQLFieldDefinitions to the isograph side,
@@ -0,0 +1,132 @@
+use common_lang_types::{
+    error_codes, ErrorCode, HasErrorCode, Location, ObjectTypeAndFieldName, SelectableNameOrAlias,
+    Span, WithLocation,
+};
+use isograph_lang_types::{ClientFieldDirectiveSet, DefinitionLocation, SelectionType};
+use thiserror::Error;
+
+use crate::{ClientFieldVariant, NetworkProtocol, Schema};
+
+/// Validate that each plural client field (i.e. each client field declared with
+/// e.g. @plural(path: "pets")) selects a list at its path, and that the path is
+/// the only top-level selection, so that each item of the array returned by the
+/// resolver is derived from an item of that list.
+pub fn validate_plural_client_fields<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> Result<(), Vec<WithLocation<PluralClientFieldError>>> {
+    let mut errors = vec![];
+    for client_field in &schema.client_scalar_selectables {
+        let info = match client_field.variant {
+            ClientFieldVariant::UserWritten(info) => info,
+            _ => continue,
+        };
+        let path = match info.client_field_directive_set {
+            ClientFieldDirectiveSet::Plural(plural) => plural.plural.path,
+            ClientFieldDirectiveSet::Component(_) | ClientFieldDirectiveSet::None(_) => continue,
+        };
+
+        let mut path_was_selected = false;
+        for selection in &client_field.reader_selection_set {
+            let name_or_alias = selection.item.name_or_alias();
+            if name_or_alias.item != path {
+                errors.push(WithLocation::new(
+                    PluralClientFieldError::SelectionOutsidePluralPath {
+                        client_field: client_field.type_and_field,
+                        path,
+                        selection_name: name_or_alias.item,
+                    },
+                    name_or_alias.location,
+                ));
+                continue;
+            }
+
+            path_was_selected = true;
+            let is_plural = match &selection.item {
+                SelectionType::Scalar(_) => false,
+                SelectionType::Object(object_selection) => match object_selection.associated_data {
+                    DefinitionLocation::Server(server_object_selectable_id) => schema
+                        .server_object_selectable(server_object_selectable_id)
+                        .target_object_entity
+                        .is_plural(),
+                    DefinitionLocation::Client(client_object_selectable_id) => schema
+                        .client_pointer(client_object_selectable_id)
+                        .target_object_entity
+                        .is_plural(),
+                },
+            };
+            if !is_plural {
+                errors.push(WithLocation::new(
+                    PluralClientFieldError::PluralPathNotPlural {
+                        client_field: client_field.type_and_field,
+                        path,
+                    },
+                    name_or_alias.location,
+                ));
+            }
+        }
+
+        if !path_was_selected {
+            errors.push(WithLocation::new(
+                PluralClientFieldError::PluralPathNotSelected {
+                    client_field: client_field.type_and_field,
+                    path,
+                },
+                Location::new(info.text_source, Span::new(0, 0)),
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum PluralClientFieldError {
+    #[error(
+        "`{}.{}` is declared with @plural(path: \"{path}\"), but does not select `{path}`. \
+        Select a linked field that returns a list at that path.",
+        client_field.type_name,
+        client_field.field_name
+    )]
+    PluralPathNotSelected {
+        client_field: ObjectTypeAndFieldName,
+        path: SelectableNameOrAlias,
+    },
+
+    #[error(
+        "`{}.{}` is declared with @plural(path: \"{path}\"), but `{path}` is not a \
+        linked field that returns a list.",
+        client_field.type_name,
+        client_field.field_name
+    )]
+    PluralPathNotPlural {
+        client_field: ObjectTypeAndFieldName,
+        path: SelectableNameOrAlias,
+    },
+
+    #[error(
+        "`{}.{}` is declared with @plural(path: \"{path}\"), so it can only select \
+        `{path}` at the top level, but it also selects `{selection_name}`. Move \
+        `{selection_name}` within `{path}`.",
+        client_field.type_name,
+        client_field.field_name
+    )]
+    SelectionOutsidePluralPath {
+        client_field: ObjectTypeAndFieldName,
+        path: SelectableNameOrAlias,
+        selection_name: SelectableNameOrAlias,
+    },
+}
+
+impl HasErrorCode for PluralClientFieldError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            PluralClientFieldError::PluralPathNotSelected { .. } => error_codes::IS0211,
+            PluralClientFieldError::PluralPathNotPlural { .. } => error_codes::IS0212,
+            PluralClientFieldError::SelectionOutsidePluralPath { .. } => error_codes::IS0213,
+        }
+    }
+}
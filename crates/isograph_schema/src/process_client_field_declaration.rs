@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
 use common_lang_types::{
-    ClientScalarSelectableName, ConstExportName, IsographDirectiveName, IsographObjectTypeName,
-    Location, ObjectTypeAndFieldName, RelativePathToSourceFile, SelectableName, TextSource,
-    UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
+    error_codes, ClientObjectSelectableName, ClientScalarSelectableName, ConstExportName,
+    ErrorCode, HasErrorCode, IsographDirectiveName, IsographObjectTypeName, Location,
+    ObjectTypeAndFieldName, RelativePathToSourceFile, SelectableName, ServerObjectSelectableName,
+    Span, TextSource, UnvalidatedTypeName, VariableName, WithLocation, WithSpan,
 };
 use intern::string_key::Intern;
 use isograph_lang_types::{
@@ -16,9 +17,11 @@ use isograph_lang_types::{
 use thiserror::Error;
 
 use crate::{
+    create_additional_fields::add_pagination_fields::{object_selection, scalar_selection},
+    id_arguments,
     refetch_strategy::{generate_refetch_field_strategy, id_selection, RefetchStrategy},
     ClientObjectSelectable, ClientScalarSelectable, FieldMapItem, NetworkProtocol, Schema,
-    ValidatedVariableDefinition, WrappedSelectionMapSelection, NODE_FIELD_NAME,
+    ValidatedVariableDefinition, WrappedSelectionMapSelection, LINK_FIELD_NAME, NODE_FIELD_NAME,
 };
 
 pub type UnprocessedSelection = WithSpan<UnvalidatedSelection>;
@@ -52,9 +55,28 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 Location::new(text_source, client_field_declaration.item.parent_type.span),
             ))?;
 
+        if let ServerEntityId::Object(object_entity_id) = parent_type_id {
+            let object = self
+                .server_entity_data
+                .server_object_entity(*object_entity_id);
+            if object.is_input_object {
+                return Err(WithLocation::new(
+                    ProcessClientFieldDeclarationError::InputObjectParentType {
+                        literal_type: "field".to_string(),
+                        parent_type_name: object.name,
+                    },
+                    Location::new(text_source, client_field_declaration.item.parent_type.span),
+                ));
+            }
+        }
+
         let unprocess_client_field_items = match parent_type_id {
             ServerEntityId::Object(object_entity_id) => self
-                .add_client_field_to_object(*object_entity_id, client_field_declaration)
+                .add_client_field_to_object(
+                    *object_entity_id,
+                    client_field_declaration,
+                    text_source,
+                )
                 .map_err(|e| WithLocation::new(e.item, Location::new(text_source, e.span)))?,
             ServerEntityId::Scalar(scalar_entity_id) => {
                 let scalar_name = self
@@ -99,8 +121,8 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             .defined_entities
             .get(client_pointer_declaration.item.target_type.inner())
             .ok_or(WithLocation::new(
-                ProcessClientFieldDeclarationError::ParentTypeNotDefined {
-                    parent_type_name: *client_pointer_declaration.item.target_type.inner(),
+                ProcessClientFieldDeclarationError::ClientPointerTargetTypeNotDefined {
+                    target_type_name: *client_pointer_declaration.item.target_type.inner(),
                 },
                 Location::new(
                     text_source,
@@ -108,6 +130,40 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 ),
             ))?;
 
+        if let ServerEntityId::Object(object_entity_id) = parent_type_id {
+            let object = self
+                .server_entity_data
+                .server_object_entity(*object_entity_id);
+            if object.is_input_object {
+                return Err(WithLocation::new(
+                    ProcessClientFieldDeclarationError::InputObjectParentType {
+                        literal_type: "pointer".to_string(),
+                        parent_type_name: object.name,
+                    },
+                    Location::new(
+                        text_source,
+                        client_pointer_declaration.item.parent_type.span,
+                    ),
+                ));
+            }
+        }
+        if let ServerEntityId::Object(to_object_entity_id) = target_type_id {
+            let to_object = self
+                .server_entity_data
+                .server_object_entity(*to_object_entity_id);
+            if to_object.is_input_object {
+                return Err(WithLocation::new(
+                    ProcessClientFieldDeclarationError::ClientPointerTargetIsInputObject {
+                        target_type_name: to_object.name,
+                    },
+                    Location::new(
+                        text_source,
+                        *client_pointer_declaration.item.target_type.span(),
+                    ),
+                ));
+            }
+        }
+
         let unprocessed_client_pointer_items = match parent_type_id {
             ServerEntityId::Object(object_entity_id) => match target_type_id {
                 ServerEntityId::Object(to_object_entity_id) => self
@@ -121,6 +177,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                                 .map(|_| *to_object_entity_id),
                         ),
                         client_pointer_declaration,
+                        text_source,
                     )
                     .map_err(|e| WithLocation::new(e.item, Location::new(text_source, e.span)))?,
                 ServerEntityId::Scalar(scalar_entity_id) => {
@@ -163,8 +220,8 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         &mut self,
         parent_object_entity_id: ServerObjectEntityId,
         client_field_declaration: WithSpan<ClientFieldDeclaration>,
+        text_source: TextSource,
     ) -> ProcessClientFieldDeclarationResult<UnprocessedClientFieldItem> {
-        let query_id = self.query_id();
         let object =
             &mut self.server_entity_data.server_objects[parent_object_entity_id.as_usize()];
         let client_field_field_name_ws = client_field_declaration.item.client_field_name;
@@ -196,7 +253,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         }
 
         let name = client_field_declaration.item.client_field_name.item;
-        let variant = get_client_variant(&client_field_declaration.item);
+        let variant = get_client_variant(&client_field_declaration.item, text_source);
 
         self.client_scalar_selectables.push(ClientScalarSelectable {
             description: client_field_declaration.item.description.map(|x| x.item),
@@ -227,6 +284,25 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         });
 
         let selections = client_field_declaration.item.selection_set;
+        let refetch_strategy = self.client_field_refetch_strategy(parent_object_entity_id);
+
+        Ok(UnprocessedClientFieldItem {
+            client_field_id: next_client_field_id,
+            reader_selection_set: selections,
+            refetch_strategy,
+        })
+    }
+
+    /// Client fields on objects with an id field are refetched with
+    /// `node(id: $id)`. Client fields on other objects cannot be refetched.
+    pub(crate) fn client_field_refetch_strategy(
+        &self,
+        parent_object_entity_id: ServerObjectEntityId,
+    ) -> Option<RefetchStrategy<(), ()>> {
+        let query_id = self.query_id();
+        let object = self
+            .server_entity_data
+            .server_object_entity(parent_object_entity_id);
         let id_field = self
             .server_entity_data
             .server_object_entity_extra_info
@@ -236,7 +312,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                 server_object_entity_available_selectables",
             )
             .id_field;
-        let refetch_strategy = id_field.map(|_| {
+        id_field.map(|_| {
             // Assume that if we have an id field, this implements Node
             RefetchStrategy::UseRefetchField(generate_refetch_field_strategy(
                 vec![id_selection()],
@@ -250,12 +326,6 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
                     },
                 ],
             ))
-        });
-
-        Ok(UnprocessedClientFieldItem {
-            client_field_id: next_client_field_id,
-            reader_selection_set: selections,
-            refetch_strategy,
         })
     }
 
@@ -264,6 +334,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
         parent_object_entity_id: ServerObjectEntityId,
         to_object_entity_id: TypeAnnotation<ServerObjectEntityId>,
         client_pointer_declaration: WithSpan<ClientPointerDeclaration>,
+        text_source: TextSource,
     ) -> ProcessClientFieldDeclarationResult<UnprocessedClientPointerItem> {
         let query_id = self.query_id();
         let to_object = self
@@ -294,14 +365,72 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             }));
         }
 
-        let unprocessed_fields = client_pointer_declaration.item.selection_set;
+        let (unprocessed_fields, variable_definitions, variant) =
+            match client_pointer_declaration.item.selection_set {
+                Some(selection_set) => (
+                    selection_set,
+                    client_pointer_declaration
+                        .item
+                        .variable_definitions
+                        .into_iter()
+                        .map(|variable_definition| {
+                            validate_variable_definition(
+                                &self.server_entity_data.defined_entities,
+                                variable_definition,
+                                parent_object.name,
+                                client_pointer_name.into(),
+                            )
+                        })
+                        .collect::<Result<_, _>>()?,
+                    ClientPointerVariant::UserWritten,
+                ),
+                None => {
+                    if parent_object_entity_id != query_id {
+                        return Err(WithSpan::new(
+                            ProcessClientFieldDeclarationError::NodePointerParentIsNotQuery {
+                                parent_type_name: parent_object.name,
+                                client_pointer_name,
+                                query_type_name: self
+                                    .server_entity_data
+                                    .server_object_entity(query_id)
+                                    .name,
+                            },
+                            client_pointer_declaration.item.parent_type.span,
+                        ));
+                    }
+                    if let Some(variable_definition) =
+                        client_pointer_declaration.item.variable_definitions.first()
+                    {
+                        return Err(WithSpan::new(
+                            ProcessClientFieldDeclarationError::NodePointerHasVariables {
+                                parent_type_name: parent_object.name,
+                                client_pointer_name,
+                            },
+                            variable_definition.span,
+                        ));
+                    }
+                    (
+                        vec![self.node_pointer_selection(
+                            *to_object_entity_id.inner(),
+                            *client_pointer_declaration.item.target_type.span(),
+                        )?],
+                        id_arguments(self.server_entity_data.id_type_id)
+                            .into_iter()
+                            .map(|variable_definition| {
+                                WithSpan::new(variable_definition, client_pointer_name_span)
+                            })
+                            .collect(),
+                        ClientPointerVariant::Node,
+                    )
+                }
+            };
 
         let id_field = self
             .server_entity_data
             .server_object_entity_extra_info
-            .get(&parent_object_entity_id)
+            .get(to_object_entity_id.inner())
             .expect(
-                "Expected parent_object_entity_id \
+                "Expected to_object_entity_id \
                 to exist in server_object_entity_available_selectables",
             )
             .id_field;
@@ -336,19 +465,7 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             name,
             reader_selection_set: vec![],
 
-            variable_definitions: client_pointer_declaration
-                .item
-                .variable_definitions
-                .into_iter()
-                .map(|variable_definition| {
-                    validate_variable_definition(
-                        &self.server_entity_data.defined_entities,
-                        variable_definition,
-                        parent_object.name,
-                        client_pointer_name.into(),
-                    )
-                })
-                .collect::<Result<_, _>>()?,
+            variable_definitions,
             type_and_field: ObjectTypeAndFieldName {
                 type_name: parent_object.name,
                 field_name: name.into(),
@@ -362,7 +479,9 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             info: UserWrittenClientPointerInfo {
                 const_export_name: client_pointer_declaration.item.const_export_name,
                 file_path: client_pointer_declaration.item.definition_path,
+                text_source,
             },
+            variant,
         });
 
         if self
@@ -396,6 +515,79 @@ impl<TNetworkProtocol: NetworkProtocol> Schema<TNetworkProtocol> {
             refetch_selection_set: vec![id_selection()],
         })
     }
+
+    /// A node pointer (e.g. `pointer Query.currentUser to User`) is fetched with
+    /// `node(id: $id) { asUser { link } }`. It is not read from that field, though:
+    /// its reader returns a link to the record with that id, wherever that record
+    /// was fetched.
+    fn node_pointer_selection(
+        &self,
+        target_object_entity_id: ServerObjectEntityId,
+        target_type_span: Span,
+    ) -> ProcessClientFieldDeclarationResult<UnprocessedSelection> {
+        let query_id = self.query_id();
+        let target_object = self
+            .server_entity_data
+            .server_object_entity(target_object_entity_id);
+        let not_fetchable_error = || {
+            WithSpan::new(
+                ProcessClientFieldDeclarationError::NodePointerTargetIsNotFetchableByNode {
+                    target_type_name: target_object.name,
+                    query_type_name: self.server_entity_data.server_object_entity(query_id).name,
+                },
+                target_type_span,
+            )
+        };
+
+        if target_object.concrete_type.is_none() {
+            // The link that the reader returns must have a concrete __typename.
+            return Err(not_fetchable_error());
+        }
+
+        let node_field_target_entity_id = match self
+            .server_entity_data
+            .server_object_entity_extra_info
+            .get(&query_id)
+            .and_then(|extra_info| extra_info.selectables.get(&(*NODE_FIELD_NAME).into()))
+        {
+            Some(DefinitionLocation::Server(SelectionType::Object(
+                server_object_selectable_id,
+            ))) => *self
+                .server_object_selectable(*server_object_selectable_id)
+                .target_object_entity
+                .inner(),
+            _ => return Err(not_fetchable_error()),
+        };
+
+        let link_selection = scalar_selection((*LINK_FIELD_NAME).into());
+        let node_selection_set = if node_field_target_entity_id == target_object_entity_id {
+            vec![link_selection]
+        } else {
+            let inline_fragment_name: SelectableName =
+                format!("as{}", target_object.name).intern().into();
+            match self
+                .server_entity_data
+                .server_object_entity_extra_info
+                .get(&node_field_target_entity_id)
+                .and_then(|extra_info| extra_info.selectables.get(&inline_fragment_name))
+            {
+                Some(DefinitionLocation::Server(SelectionType::Object(_))) => {
+                    vec![object_selection(
+                        inline_fragment_name,
+                        vec![],
+                        vec![link_selection],
+                    )]
+                }
+                _ => return Err(not_fetchable_error()),
+            }
+        };
+
+        Ok(object_selection(
+            (*NODE_FIELD_NAME).into(),
+            vec!["id".intern().into()],
+            node_selection_set,
+        ))
+    }
 }
 
 type ProcessClientFieldDeclarationResult<T> =
@@ -426,12 +618,61 @@ pub enum ProcessClientFieldDeclarationError {
         target_type_name: UnvalidatedTypeName,
     },
 
+    #[error("Invalid parent type. `{parent_type_name}` is an input object. You are attempting to define a {literal_type} on it. \
+        Input objects can only be the types of arguments, and cannot be selected.")]
+    InputObjectParentType {
+        literal_type: String,
+        parent_type_name: IsographObjectTypeName,
+    },
+
+    #[error("Invalid client pointer target type. `{target_type_name}` is an input object. You are attempting to define a pointer to it. \
+        Input objects can only be the types of arguments, and cannot be selected.")]
+    ClientPointerTargetIsInputObject {
+        target_type_name: IsographObjectTypeName,
+    },
+
+    #[error("`{target_type_name}` is not a type that has been defined. You are attempting to define a pointer to it.")]
+    ClientPointerTargetTypeNotDefined {
+        target_type_name: UnvalidatedTypeName,
+    },
+
     #[error("Invalid client pointer target type. `{target_type_name}` has no id field. You are attempting to define a pointer to it. \
         In order to do so, the target must be an object implementing Node interface.")]
     ClientPointerTargetTypeHasNoId {
         target_type_name: UnvalidatedTypeName,
     },
 
+    #[error(
+        "`{parent_type_name}.{client_pointer_name}` has no selection set, so it is a node \
+        pointer. Node pointers can only be defined on `{query_type_name}`."
+    )]
+    NodePointerParentIsNotQuery {
+        parent_type_name: IsographObjectTypeName,
+        client_pointer_name: ClientObjectSelectableName,
+        query_type_name: IsographObjectTypeName,
+    },
+
+    #[error(
+        "`{parent_type_name}.{client_pointer_name}` has no selection set, so it is a node \
+        pointer. Node pointers are passed the id of the record that they point to, \
+        and cannot declare variables."
+    )]
+    NodePointerHasVariables {
+        parent_type_name: IsographObjectTypeName,
+        client_pointer_name: ClientObjectSelectableName,
+    },
+
+    #[error(
+        "`{target_type_name}` cannot be fetched with `{query_type_name}.node`, so a node \
+        pointer cannot point to it. Node pointers must point to a concrete type that \
+        implements Node, and `{query_type_name}` must have a node field. Alternatively, \
+        add a selection set to the pointer."
+    )]
+    NodePointerTargetIsNotFetchableByNode {
+        target_type_name: IsographObjectTypeName,
+        query_type_name: IsographObjectTypeName,
+    },
+
     #[error(
         "The Isograph object type \"{parent_type_name}\" already has a field named \"{client_field_name}\"."
     )]
@@ -440,6 +681,20 @@ pub enum ProcessClientFieldDeclarationError {
         client_field_name: SelectableName,
     },
 
+    #[error(
+        "`{parent_type_name}.{client_type_name}` is declared more than once. It is also \
+        declared at {previous_file}:{previous_line}:{previous_column}. Rename or remove \
+        one of the declarations."
+    )]
+    DuplicateClientTypeDeclaration {
+        parent_type_name: UnvalidatedTypeName,
+        client_type_name: SelectableName,
+        previous_file: RelativePathToSourceFile,
+        /// The (1-based) line and column of the name in the previous declaration.
+        previous_line: usize,
+        previous_column: usize,
+    },
+
     #[error("Error when deserializing directives. Message: {message}")]
     UnableToDeserializeDirectives { message: DeserializationError },
 
@@ -454,6 +709,50 @@ pub enum ProcessClientFieldDeclarationError {
     },
 }
 
+impl HasErrorCode for ProcessClientFieldDeclarationError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ProcessClientFieldDeclarationError::ParentTypeNotDefined { .. } => error_codes::IS0200,
+            ProcessClientFieldDeclarationError::DirectiveNotSupportedOnClientPointer { .. } => {
+                error_codes::IS0201
+            }
+            ProcessClientFieldDeclarationError::InvalidParentType { .. } => error_codes::IS0202,
+            ProcessClientFieldDeclarationError::ClientPointerInvalidTargetType { .. } => {
+                error_codes::IS0203
+            }
+            ProcessClientFieldDeclarationError::InputObjectParentType { .. }
+            | ProcessClientFieldDeclarationError::ClientPointerTargetIsInputObject { .. } => {
+                error_codes::IS0408
+            }
+            ProcessClientFieldDeclarationError::ClientPointerTargetTypeHasNoId { .. } => {
+                error_codes::IS0204
+            }
+            ProcessClientFieldDeclarationError::ParentAlreadyHasField { .. } => error_codes::IS0205,
+            ProcessClientFieldDeclarationError::NodePointerParentIsNotQuery { .. } => {
+                error_codes::IS0214
+            }
+            ProcessClientFieldDeclarationError::NodePointerHasVariables { .. } => {
+                error_codes::IS0215
+            }
+            ProcessClientFieldDeclarationError::NodePointerTargetIsNotFetchableByNode {
+                ..
+            } => error_codes::IS0216,
+            ProcessClientFieldDeclarationError::DuplicateClientTypeDeclaration { .. } => {
+                error_codes::IS0210
+            }
+            ProcessClientFieldDeclarationError::ClientPointerTargetTypeNotDefined { .. } => {
+                error_codes::IS0207
+            }
+            ProcessClientFieldDeclarationError::UnableToDeserializeDirectives { .. } => {
+                error_codes::IS0206
+            }
+            ProcessClientFieldDeclarationError::FieldArgumentTypeDoesNotExist { .. } => {
+                error_codes::IS0112
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ImperativelyLoadedFieldVariant {
     pub client_field_scalar_selection_name: ClientScalarSelectableName,
@@ -467,6 +766,10 @@ pub struct ImperativelyLoadedFieldVariant {
     /// but we nonetheless need to put them into the query definition, and we need
     /// the variable's type, not just the variable.
     pub top_level_schema_field_arguments: Vec<ValidatedVariableDefinition>,
+    /// The arguments of the linked fields between the top level schema field and the
+    /// primary field, e.g. of user in updateUser.user.profile. These are passed as
+    /// variables of the same name.
+    pub primary_field_path_arguments: Vec<ValidatedVariableDefinition>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -474,6 +777,9 @@ pub struct UserWrittenClientTypeInfo {
     // TODO use a shared struct
     pub const_export_name: ConstExportName,
     pub file_path: RelativePathToSourceFile,
+    /// The iso literal in which the client field is declared. Spans within the
+    /// client field (e.g. of its variable definitions) are relative to this.
+    pub text_source: TextSource,
     pub client_field_directive_set: ClientFieldDirectiveSet,
 }
 
@@ -482,6 +788,16 @@ pub struct UserWrittenClientTypeInfo {
 pub struct UserWrittenClientPointerInfo {
     pub const_export_name: ConstExportName,
     pub file_path: RelativePathToSourceFile,
+    pub text_source: TextSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClientPointerVariant {
+    /// A pointer with a selection set, whose resolver returns a link.
+    UserWritten,
+    /// A pointer from Query without a selection set (e.g. `pointer Query.currentUser
+    /// to User`), which points to the record with the id that is passed to it.
+    Node,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -489,12 +805,25 @@ pub enum ClientFieldVariant {
     UserWritten(UserWrittenClientTypeInfo),
     ImperativelyLoadedField(ImperativelyLoadedFieldVariant),
     Link,
+    /// A client field generated for a connection field (e.g. `repositories`), which
+    /// selects a page of it, and returns it.
+    Paginated(PaginatedFieldVariant),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PaginatedFieldVariant {
+    /// The connection field, e.g. `repositories` for `__paginated_repositories`.
+    pub connection_field_name: ServerObjectSelectableName,
 }
 
-fn get_client_variant(client_field_declaration: &ClientFieldDeclaration) -> ClientFieldVariant {
+fn get_client_variant(
+    client_field_declaration: &ClientFieldDeclaration,
+    text_source: TextSource,
+) -> ClientFieldVariant {
     ClientFieldVariant::UserWritten(UserWrittenClientTypeInfo {
         const_export_name: client_field_declaration.const_export_name,
         file_path: client_field_declaration.definition_path,
+        text_source,
         client_field_directive_set: client_field_declaration.client_field_directive_set,
     })
 }
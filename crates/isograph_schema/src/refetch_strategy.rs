@@ -138,6 +138,7 @@ pub fn id_selection() -> UnprocessedSelection {
             scalar_selection_directive_set: ScalarSelectionDirectiveSet::None(EmptyDirectiveSet {}),
             associated_data: (),
             arguments: vec![],
+            conditions: vec![],
         }),
         Span::todo_generated(),
     )
@@ -0,0 +1,117 @@
+use common_lang_types::StringLiteralValue;
+use intern::Lookup;
+use isograph_lang_types::NonConstantValue;
+use serde_json::{json, Map, Value};
+
+/// An argument value, as it is described in the query text of network protocols
+/// whose requests are made by the runtime (e.g. rest and grpc), and evaluated by
+/// `evaluateRequestValue`, e.g. { "kind": "Variable", "name": "id" }.
+pub fn serialize_request_value(value: &NonConstantValue) -> Value {
+    match value {
+        NonConstantValue::Variable(variable_name) => {
+            json!({ "kind": "Variable", "name": variable_name.lookup() })
+        }
+        NonConstantValue::Integer(int_value) => json!({ "kind": "Literal", "value": int_value }),
+        NonConstantValue::Boolean(bool) => json!({ "kind": "Literal", "value": bool }),
+        NonConstantValue::String(string) => {
+            json!({ "kind": "Literal", "value": graphql_string_value(*string) })
+        }
+        NonConstantValue::Float(float) => json!({ "kind": "Literal", "value": float.as_float() }),
+        NonConstantValue::Null => json!({ "kind": "Literal", "value": null }),
+        NonConstantValue::Enum(enum_value) => {
+            json!({ "kind": "Enum", "value": enum_value.lookup() })
+        }
+        NonConstantValue::List(items) => json!({
+            "kind": "List",
+            "value": items
+                .iter()
+                .map(|item| serialize_request_value(&item.item))
+                .collect::<Vec<_>>(),
+        }),
+        NonConstantValue::Object(entries) => json!({
+            "kind": "Object",
+            "value": entries
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.name.item.lookup().to_string(),
+                        serialize_request_value(&entry.value.item),
+                    )
+                })
+                .collect::<Map<_, _>>(),
+        }),
+    }
+}
+
+/// The value of a string literal in an iso literal or in the schema, whose escape
+/// sequences (e.g. \" or \u00e9) are still as they were written.
+pub fn graphql_string_value(string: StringLiteralValue) -> String {
+    let mut utf16 = vec![];
+    let mut chars = string.lookup().chars();
+    let mut buffer = [0; 2];
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            utf16.extend_from_slice(c.encode_utf16(&mut buffer));
+            continue;
+        }
+        let escaped = match chars.next() {
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                let hex = chars.by_ref().take(4).collect::<String>();
+                match u16::from_str_radix(&hex, 16) {
+                    Ok(code_unit) => utf16.push(code_unit),
+                    Err(_) => utf16.extend(format!("\\u{hex}").encode_utf16()),
+                }
+                continue;
+            }
+            // \", \\ and \/
+            Some(c) => c,
+            None => '\\',
+        };
+        utf16.extend_from_slice(escaped.encode_utf16(&mut buffer));
+    }
+    String::from_utf16_lossy(&utf16)
+}
+
+#[cfg(test)]
+mod tests {
+    use common_lang_types::{Location, WithLocation};
+    use graphql_lang_types::NameValuePair;
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    fn string(source: &str) -> NonConstantValue {
+        NonConstantValue::String(source.intern().into())
+    }
+
+    #[test]
+    fn strings_are_unescaped() {
+        assert_eq!(
+            serialize_request_value(&string(r#"say \"hi\"\n\\ \/ \u00e9"#)),
+            json!({ "kind": "Literal", "value": "say \"hi\"\n\\ / é" })
+        );
+        assert_eq!(
+            serialize_request_value(&NonConstantValue::Object(vec![NameValuePair {
+                name: WithLocation::new("name".intern().into(), Location::generated()),
+                value: WithLocation::new(string(r#"a\"b"#), Location::generated()),
+            }])),
+            json!({
+                "kind": "Object",
+                "value": { "name": { "kind": "Literal", "value": "a\"b" } },
+            })
+        );
+    }
+
+    #[test]
+    fn enums_have_their_own_kind() {
+        assert_eq!(
+            serialize_request_value(&NonConstantValue::Enum("DOG".intern().into())),
+            json!({ "kind": "Enum", "value": "DOG" })
+        );
+    }
+}
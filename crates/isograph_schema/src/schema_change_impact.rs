@@ -0,0 +1,550 @@
+use std::collections::HashSet;
+
+use common_lang_types::{
+    error_codes, ErrorCode, FieldArgumentName, HasErrorCode, IsographObjectTypeName, Location,
+    ObjectTypeAndFieldName, SelectableName, Span, VariableName, WithLocation, WithSpan,
+};
+use graphql_lang_types::{
+    GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation,
+    GraphQLTypeAnnotation,
+};
+use isograph_lang_types::{
+    DefinitionLocation, NonConstantValue, SelectionFieldArgument, SelectionType, ServerEntityId,
+    TypeAnnotation, UnionVariant,
+};
+use thiserror::Error;
+
+use crate::{
+    accessible_client_fields, visit_selection_set::visit_selection_set,
+    ClientScalarOrObjectSelectable, ClientSelectableId, NetworkProtocol, Schema,
+    ServerSelectableId, ValidatedVariableDefinition,
+};
+
+/// The result of comparing the schema that the client fields were written against
+/// with a new schema.
+pub struct SchemaChangeImpact {
+    /// The selections that are valid against the old schema, but not the new schema.
+    pub breaking_changes: Vec<WithLocation<SchemaChangeError>>,
+    /// The entrypoints that read a client field or client pointer with a breaking
+    /// change, sorted by type and field name.
+    pub affected_entrypoints: Vec<ObjectTypeAndFieldName>,
+}
+
+/// The type and arguments of a server field, with the type names as strings, so
+/// that server fields from different schemas can be compared.
+struct ServerFieldSignature {
+    type_and_field: ObjectTypeAndFieldName,
+    type_: GraphQLTypeAnnotation<String>,
+    arguments: Vec<ArgumentSignature>,
+}
+
+/// A field argument or a variable of a client type.
+struct ArgumentSignature {
+    name: VariableName,
+    type_: GraphQLTypeAnnotation<String>,
+    has_default_value: bool,
+}
+
+/// Determine which selections of the client fields and client pointers in the
+/// old schema would break if the server schema were replaced with the one in
+/// the new schema, i.e. selections of fields that were removed, whose type
+/// changed incompatibly, or that have new required arguments.
+///
+/// The new schema only needs to contain the server schema; its client fields
+/// are ignored.
+pub fn schema_change_impact<TNetworkProtocol: NetworkProtocol>(
+    old_schema: &Schema<TNetworkProtocol>,
+    new_schema: &Schema<TNetworkProtocol>,
+) -> SchemaChangeImpact {
+    let mut breaking_changes = vec![];
+    let mut broken_client_types = HashSet::new();
+
+    let mut client_types = old_schema.user_written_client_types().collect::<Vec<_>>();
+    client_types.sort_by_key(|(_, client_type, _)| {
+        let type_and_field = client_type.type_and_field();
+        (
+            type_and_field.type_name.to_string(),
+            type_and_field.field_name.to_string(),
+        )
+    });
+
+    for (client_type_id, client_type, _) in client_types {
+        let errors_before = breaking_changes.len();
+        let client_type_and_field = client_type.type_and_field();
+        let variables = argument_signatures(
+            old_schema,
+            client_type
+                .variable_definitions()
+                .iter()
+                .map(|variable_definition| &variable_definition.item),
+        );
+
+        visit_selection_set(client_type.reader_selection_set(), &mut |selection| {
+            let (server_selectable_id, arguments) = match selection {
+                SelectionType::Scalar(scalar_selection) => match scalar_selection.associated_data {
+                    DefinitionLocation::Server(server_scalar_selectable_id) => (
+                        SelectionType::Scalar(server_scalar_selectable_id),
+                        &scalar_selection.arguments,
+                    ),
+                    DefinitionLocation::Client(_) => return,
+                },
+                SelectionType::Object(object_selection) => match object_selection.associated_data {
+                    DefinitionLocation::Server(server_object_selectable_id) => (
+                        SelectionType::Object(server_object_selectable_id),
+                        &object_selection.arguments,
+                    ),
+                    DefinitionLocation::Client(_) => return,
+                },
+            };
+            let location = match selection {
+                SelectionType::Scalar(scalar_selection) => scalar_selection.name.location,
+                SelectionType::Object(object_selection) => object_selection.name.location,
+            };
+
+            let old_signature = server_field_signature(old_schema, server_selectable_id);
+            let Some(new_signature) = find_server_field_signature(
+                new_schema,
+                old_signature.type_and_field.type_name,
+                old_signature.type_and_field.field_name,
+            ) else {
+                breaking_changes.push(WithLocation::new(
+                    SchemaChangeError::SelectedFieldRemoved {
+                        client_type: client_type_and_field,
+                        type_and_field: old_signature.type_and_field,
+                    },
+                    location,
+                ));
+                return;
+            };
+
+            if !is_compatible_output_type(&old_signature.type_, &new_signature.type_) {
+                breaking_changes.push(WithLocation::new(
+                    SchemaChangeError::SelectedFieldTypeChanged {
+                        client_type: client_type_and_field,
+                        type_and_field: old_signature.type_and_field,
+                        old_type: old_signature.type_.to_string(),
+                        new_type: new_signature.type_.to_string(),
+                    },
+                    location,
+                ));
+            }
+
+            validate_arguments(
+                client_type_and_field,
+                &old_signature,
+                &new_signature,
+                arguments,
+                &variables,
+                location,
+                &mut breaking_changes,
+            );
+        });
+
+        if breaking_changes.len() > errors_before {
+            broken_client_types.insert(client_type_id);
+        }
+    }
+
+    SchemaChangeImpact {
+        breaking_changes,
+        affected_entrypoints: affected_entrypoints(old_schema, &broken_client_types),
+    }
+}
+
+fn validate_arguments(
+    client_type: ObjectTypeAndFieldName,
+    old_signature: &ServerFieldSignature,
+    new_signature: &ServerFieldSignature,
+    passed_arguments: &[WithLocation<SelectionFieldArgument>],
+    variables: &[ArgumentSignature],
+    selection_location: Location,
+    breaking_changes: &mut Vec<WithLocation<SchemaChangeError>>,
+) {
+    for passed_argument in passed_arguments {
+        let argument_name = passed_argument.item.name.item;
+        let old_argument = old_signature
+            .arguments
+            .iter()
+            .find(|argument| argument_name == argument.name);
+        match new_signature
+            .arguments
+            .iter()
+            .find(|argument| argument_name == argument.name)
+        {
+            None => breaking_changes.push(WithLocation::new(
+                SchemaChangeError::PassedArgumentRemoved {
+                    client_type,
+                    type_and_field: old_signature.type_and_field,
+                    argument_name,
+                },
+                passed_argument.location,
+            )),
+            Some(new_argument) => {
+                if let Some(old_argument) = old_argument {
+                    // Arguments are inputs, so the new type must accept every value
+                    // that can be passed, i.e. every value of the type of the passed
+                    // variable. If a literal that is not null is passed, the argument
+                    // may become non-null. Otherwise, the new type must accept every
+                    // value the old type accepted.
+                    let (new_type, passed_type) = match &passed_argument.item.value.item {
+                        NonConstantValue::Variable(variable_name) => (
+                            new_argument.type_.clone(),
+                            variables
+                                .iter()
+                                .find(|variable| variable.name == *variable_name)
+                                .map(|variable| &variable.type_)
+                                .unwrap_or(&old_argument.type_),
+                        ),
+                        NonConstantValue::Null => (new_argument.type_.clone(), &old_argument.type_),
+                        _ => (
+                            without_outer_non_null(&new_argument.type_),
+                            &old_argument.type_,
+                        ),
+                    };
+                    if !is_compatible_output_type(&new_type, passed_type) {
+                        breaking_changes.push(WithLocation::new(
+                            SchemaChangeError::PassedArgumentTypeChanged {
+                                client_type,
+                                type_and_field: old_signature.type_and_field,
+                                argument_name,
+                                old_type: old_argument.type_.to_string(),
+                                new_type: new_argument.type_.to_string(),
+                            },
+                            passed_argument.location,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    for new_argument in &new_signature.arguments {
+        let is_required = !new_argument.type_.is_nullable() && !new_argument.has_default_value;
+        let is_passed = passed_arguments
+            .iter()
+            .any(|passed_argument| passed_argument.item.name.item == new_argument.name);
+        if is_required && !is_passed {
+            breaking_changes.push(WithLocation::new(
+                SchemaChangeError::NewRequiredArgument {
+                    client_type,
+                    type_and_field: old_signature.type_and_field,
+                    argument_name: new_argument.name,
+                    argument_type: new_argument.type_.to_string(),
+                },
+                selection_location,
+            ));
+        }
+    }
+}
+
+/// Whether a field of type new can be read by code that expects a field of type
+/// old, i.e. the named types and list wrappers are the same, and no part of the
+/// type became nullable.
+fn is_compatible_output_type(
+    old: &GraphQLTypeAnnotation<String>,
+    new: &GraphQLTypeAnnotation<String>,
+) -> bool {
+    match (old, new) {
+        (GraphQLTypeAnnotation::NonNull(old), GraphQLTypeAnnotation::NonNull(new)) => {
+            is_compatible_non_null_output_type(old, new)
+        }
+        (GraphQLTypeAnnotation::NonNull(_), _) => false,
+        (old, GraphQLTypeAnnotation::NonNull(new)) => match (old, new.as_ref()) {
+            (GraphQLTypeAnnotation::Named(old), GraphQLNonNullTypeAnnotation::Named(new)) => {
+                old.0.item == new.0.item
+            }
+            (GraphQLTypeAnnotation::List(old), GraphQLNonNullTypeAnnotation::List(new)) => {
+                is_compatible_output_type(&old.0, &new.0)
+            }
+            _ => false,
+        },
+        (GraphQLTypeAnnotation::Named(old), GraphQLTypeAnnotation::Named(new)) => {
+            old.0.item == new.0.item
+        }
+        (GraphQLTypeAnnotation::List(old), GraphQLTypeAnnotation::List(new)) => {
+            is_compatible_output_type(&old.0, &new.0)
+        }
+        _ => false,
+    }
+}
+
+fn is_compatible_non_null_output_type(
+    old: &GraphQLNonNullTypeAnnotation<String>,
+    new: &GraphQLNonNullTypeAnnotation<String>,
+) -> bool {
+    match (old, new) {
+        (GraphQLNonNullTypeAnnotation::Named(old), GraphQLNonNullTypeAnnotation::Named(new)) => {
+            old.0.item == new.0.item
+        }
+        (GraphQLNonNullTypeAnnotation::List(old), GraphQLNonNullTypeAnnotation::List(new)) => {
+            is_compatible_output_type(&old.0, &new.0)
+        }
+        _ => false,
+    }
+}
+
+fn without_outer_non_null(type_: &GraphQLTypeAnnotation<String>) -> GraphQLTypeAnnotation<String> {
+    match type_ {
+        GraphQLTypeAnnotation::NonNull(non_null) => match non_null.as_ref() {
+            GraphQLNonNullTypeAnnotation::Named(named) => {
+                GraphQLTypeAnnotation::Named(named.clone())
+            }
+            GraphQLNonNullTypeAnnotation::List(list) => {
+                GraphQLTypeAnnotation::List(Box::new(list.clone()))
+            }
+        },
+        _ => type_.clone(),
+    }
+}
+
+/// Convert the type of a server field to a GraphQL type annotation. Unlike
+/// graphql_type_annotation_from_type_annotation, this treats
+/// TypeAnnotation::Scalar and TypeAnnotation::Plural as non-null.
+pub fn graphql_output_type<TInner: Ord + Copy>(
+    type_annotation: &TypeAnnotation<TInner>,
+) -> GraphQLTypeAnnotation<TInner> {
+    let named = |inner: TInner| {
+        GraphQLNonNullTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+            inner,
+            Span::todo_generated(),
+        )))
+    };
+    let list = |item: &TypeAnnotation<TInner>| {
+        GraphQLNonNullTypeAnnotation::List(GraphQLListTypeAnnotation(graphql_output_type(item)))
+    };
+
+    match type_annotation {
+        TypeAnnotation::Scalar(inner) => GraphQLTypeAnnotation::NonNull(Box::new(named(*inner))),
+        TypeAnnotation::Plural(item) => GraphQLTypeAnnotation::NonNull(Box::new(list(item))),
+        TypeAnnotation::Union(union_type_annotation) => {
+            let non_null = match union_type_annotation.variants.first().expect(
+                "Expected union to have a variant. This is indicative of a bug in Isograph.",
+            ) {
+                UnionVariant::Scalar(inner) => named(*inner),
+                UnionVariant::Plural(item) => list(item),
+            };
+            if !union_type_annotation.nullable {
+                return GraphQLTypeAnnotation::NonNull(Box::new(non_null));
+            }
+            match non_null {
+                GraphQLNonNullTypeAnnotation::Named(named) => GraphQLTypeAnnotation::Named(named),
+                GraphQLNonNullTypeAnnotation::List(list) => {
+                    GraphQLTypeAnnotation::List(Box::new(list))
+                }
+            }
+        }
+    }
+}
+
+fn find_server_field_signature<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    parent_type_name: IsographObjectTypeName,
+    field_name: SelectableName,
+) -> Option<ServerFieldSignature> {
+    let parent_object_entity_id = match schema
+        .server_entity_data
+        .defined_entities
+        .get(&parent_type_name.into())?
+    {
+        ServerEntityId::Object(parent_object_entity_id) => *parent_object_entity_id,
+        ServerEntityId::Scalar(_) => return None,
+    };
+
+    match schema
+        .server_entity_data
+        .server_object_entity_extra_info
+        .get(&parent_object_entity_id)?
+        .selectables
+        .get(&field_name)?
+    {
+        DefinitionLocation::Server(server_selectable_id) => {
+            Some(server_field_signature(schema, *server_selectable_id))
+        }
+        DefinitionLocation::Client(_) => None,
+    }
+}
+
+fn server_field_signature<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    server_selectable_id: ServerSelectableId,
+) -> ServerFieldSignature {
+    let (parent_object_entity_id, field_name, type_, arguments) = match schema
+        .server_selectable(server_selectable_id)
+    {
+        SelectionType::Scalar(server_scalar_selectable) => (
+            server_scalar_selectable.parent_object_entity_id,
+            server_scalar_selectable.name.item.into(),
+            graphql_output_type(&server_scalar_selectable.target_scalar_entity).map(
+                |scalar_entity_id| entity_name(schema, ServerEntityId::Scalar(scalar_entity_id)),
+            ),
+            &server_scalar_selectable.arguments,
+        ),
+        SelectionType::Object(server_object_selectable) => (
+            server_object_selectable.parent_object_entity_id,
+            server_object_selectable.name.item.into(),
+            graphql_output_type(&server_object_selectable.target_object_entity).map(
+                |object_entity_id| entity_name(schema, ServerEntityId::Object(object_entity_id)),
+            ),
+            &server_object_selectable.arguments,
+        ),
+    };
+
+    ServerFieldSignature {
+        type_and_field: ObjectTypeAndFieldName {
+            type_name: schema
+                .server_entity_data
+                .server_object_entity(parent_object_entity_id)
+                .name,
+            field_name,
+        },
+        type_,
+        arguments: argument_signatures(schema, arguments.iter().map(|argument| &argument.item)),
+    }
+}
+
+fn argument_signatures<'a, TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    variable_definitions: impl Iterator<Item = &'a ValidatedVariableDefinition>,
+) -> Vec<ArgumentSignature> {
+    variable_definitions
+        .map(|variable_definition| ArgumentSignature {
+            name: variable_definition.name.item,
+            type_: variable_definition
+                .type_
+                .clone()
+                .map(|entity_id| entity_name(schema, entity_id)),
+            has_default_value: variable_definition.default_value.is_some(),
+        })
+        .collect()
+}
+
+fn entity_name<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    entity_id: ServerEntityId,
+) -> String {
+    match schema.server_entity_data.server_entity(entity_id) {
+        SelectionType::Scalar(server_scalar_entity) => server_scalar_entity.name.item.to_string(),
+        SelectionType::Object(server_object_entity) => server_object_entity.name.to_string(),
+    }
+}
+
+fn affected_entrypoints<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    broken_client_types: &HashSet<ClientSelectableId>,
+) -> Vec<ObjectTypeAndFieldName> {
+    let mut affected_entrypoints = schema
+        .entrypoints
+        .keys()
+        .filter(|entrypoint_id| {
+            let mut visited = HashSet::new();
+            let mut queue = vec![SelectionType::Scalar(**entrypoint_id)];
+            while let Some(client_type_id) = queue.pop() {
+                if broken_client_types.contains(&client_type_id) {
+                    return true;
+                }
+                if visited.insert(client_type_id) {
+                    queue.extend(accessible_client_fields(
+                        &schema.client_type(client_type_id),
+                        schema,
+                    ));
+                }
+            }
+            false
+        })
+        .map(|entrypoint_id| schema.client_field(*entrypoint_id).type_and_field)
+        .collect::<Vec<_>>();
+    affected_entrypoints.sort_by_key(|type_and_field| {
+        (
+            type_and_field.type_name.to_string(),
+            type_and_field.field_name.to_string(),
+        )
+    });
+    affected_entrypoints
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum SchemaChangeError {
+    #[error(
+        "`{}.{}` selects `{}.{}`, which does not exist in the new schema.",
+        client_type.type_name,
+        client_type.field_name,
+        type_and_field.type_name,
+        type_and_field.field_name
+    )]
+    SelectedFieldRemoved {
+        client_type: ObjectTypeAndFieldName,
+        type_and_field: ObjectTypeAndFieldName,
+    },
+
+    #[error(
+        "`{}.{}` selects `{}.{}`, whose type changes from `{old_type}` to `{new_type}` \
+        in the new schema.",
+        client_type.type_name,
+        client_type.field_name,
+        type_and_field.type_name,
+        type_and_field.field_name
+    )]
+    SelectedFieldTypeChanged {
+        client_type: ObjectTypeAndFieldName,
+        type_and_field: ObjectTypeAndFieldName,
+        old_type: String,
+        new_type: String,
+    },
+
+    #[error(
+        "`{}.{}` selects `{}.{}`, which has a new required argument \
+        `{argument_name}: {argument_type}` in the new schema.",
+        client_type.type_name,
+        client_type.field_name,
+        type_and_field.type_name,
+        type_and_field.field_name
+    )]
+    NewRequiredArgument {
+        client_type: ObjectTypeAndFieldName,
+        type_and_field: ObjectTypeAndFieldName,
+        argument_name: VariableName,
+        argument_type: String,
+    },
+
+    #[error(
+        "`{}.{}` passes the argument `{argument_name}` to `{}.{}`, which does not \
+        exist in the new schema.",
+        client_type.type_name,
+        client_type.field_name,
+        type_and_field.type_name,
+        type_and_field.field_name
+    )]
+    PassedArgumentRemoved {
+        client_type: ObjectTypeAndFieldName,
+        type_and_field: ObjectTypeAndFieldName,
+        argument_name: FieldArgumentName,
+    },
+
+    #[error(
+        "`{}.{}` passes the argument `{argument_name}` to `{}.{}`, whose type changes \
+        from `{old_type}` to `{new_type}` in the new schema.",
+        client_type.type_name,
+        client_type.field_name,
+        type_and_field.type_name,
+        type_and_field.field_name
+    )]
+    PassedArgumentTypeChanged {
+        client_type: ObjectTypeAndFieldName,
+        type_and_field: ObjectTypeAndFieldName,
+        argument_name: FieldArgumentName,
+        old_type: String,
+        new_type: String,
+    },
+}
+
+impl HasErrorCode for SchemaChangeError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            SchemaChangeError::SelectedFieldRemoved { .. } => error_codes::IS0603,
+            SchemaChangeError::SelectedFieldTypeChanged { .. } => error_codes::IS0604,
+            SchemaChangeError::NewRequiredArgument { .. } => error_codes::IS0605,
+            SchemaChangeError::PassedArgumentRemoved { .. } => error_codes::IS0606,
+            SchemaChangeError::PassedArgumentTypeChanged { .. } => error_codes::IS0607,
+        }
+    }
+}
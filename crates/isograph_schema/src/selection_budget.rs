@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use common_lang_types::{
+    error_codes, ErrorCode, HasErrorCode, Location, ObjectTypeAndFieldName, Span, WithLocation,
+};
+use isograph_config::{CompilerConfigOptions, SelectionBudget};
+use isograph_lang_types::{DefinitionLocation, SelectionType};
+use thiserror::Error;
+
+use crate::{
+    create_merged_selection_map_for_field_and_insert_into_global_map, initial_variable_context,
+    validate_query_limits::selection_path_segment, ClientScalarOrObjectSelectable,
+    MergedSelectionMap, MergedServerSelection, NetworkProtocol, Schema,
+};
+
+/// The number of sub-paths that are listed when a client field exceeds the budget.
+const HEAVIEST_PATH_COUNT: usize = 3;
+
+/// Validate that the merged selection of each client field, i.e. the fields that it
+/// and every client field it (transitively) selects read from the server, contains
+/// at most the max_scalar_field_count of the selection_budget, if it is set.
+///
+/// This catches over-fetching that is introduced by long chains of client fields,
+/// each of which only selects a few fields itself.
+pub fn validate_selection_budget<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    options: &CompilerConfigOptions,
+) -> Result<(), Vec<WithLocation<SelectionBudgetError>>> {
+    let Some(SelectionBudget {
+        max_scalar_field_count,
+        on_exceeded,
+    }) = options.selection_budget
+    else {
+        return Ok(());
+    };
+
+    let mut client_fields = schema
+        .user_written_client_types()
+        .filter_map(
+            |(client_type_id, client_type, _)| match (client_type_id, client_type) {
+                (SelectionType::Scalar(client_field_id), SelectionType::Scalar(client_field)) => {
+                    Some((client_field_id, client_field))
+                }
+                _ => None,
+            },
+        )
+        .collect::<Vec<_>>();
+    client_fields.sort_by_key(|(_, client_field)| {
+        (
+            client_field.type_and_field.type_name.to_string(),
+            client_field.type_and_field.field_name.to_string(),
+        )
+    });
+
+    let mut encountered_client_type_map = BTreeMap::new();
+    let mut errors = vec![];
+    for (client_field_id, client_field) in client_fields {
+        let merged_selection_map =
+            create_merged_selection_map_for_field_and_insert_into_global_map(
+                schema,
+                client_field.parent_object_entity_id,
+                schema
+                    .server_entity_data
+                    .server_object_entity(client_field.parent_object_entity_id),
+                client_field.selection_set_for_parent_query(),
+                &mut encountered_client_type_map,
+                DefinitionLocation::Client(SelectionType::Scalar(client_field_id)),
+                &initial_variable_context(&SelectionType::Scalar(client_field)),
+            )
+            .merged_selection_map;
+
+        let mut path_weights = vec![];
+        let scalar_field_count =
+            count_scalar_fields(&merged_selection_map, &mut vec![], &mut path_weights);
+        if scalar_field_count <= max_scalar_field_count {
+            continue;
+        }
+
+        if let Err(error) =
+            on_exceeded.on_failure(|| SelectionBudgetError::SelectionBudgetExceeded {
+                client_field: client_field.type_and_field,
+                scalar_field_count,
+                max_scalar_field_count,
+                heaviest_paths: heaviest_paths(path_weights),
+            })
+        {
+            errors.push(WithLocation::new(
+                error,
+                client_field
+                    .text_source()
+                    .map(|text_source| Location::new(text_source, Span::new(0, 0)))
+                    .unwrap_or_else(Location::generated),
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Count the scalar fields in the selection map. For each linked field and inline
+/// fragment, record its path and the number of scalar fields that it selects
+/// directly (i.e. not in nested linked fields).
+fn count_scalar_fields(
+    selection_map: &MergedSelectionMap,
+    path: &mut Vec<String>,
+    path_weights: &mut Vec<(String, usize)>,
+) -> usize {
+    let mut scalar_field_count = 0;
+    for selection in selection_map.values() {
+        let nested_selection_map = match selection {
+            MergedServerSelection::ScalarField(_) => {
+                scalar_field_count += 1;
+                continue;
+            }
+            MergedServerSelection::LinkedField(linked_field) => &linked_field.selection_map,
+            MergedServerSelection::InlineFragment(inline_fragment) => {
+                &inline_fragment.selection_map
+            }
+        };
+        path.push(selection_path_segment(selection));
+        let direct_scalar_field_count = nested_selection_map
+            .values()
+            .filter(|selection| matches!(selection, MergedServerSelection::ScalarField(_)))
+            .count();
+        path_weights.push((path.join("."), direct_scalar_field_count));
+        scalar_field_count += count_scalar_fields(nested_selection_map, path, path_weights);
+        path.pop();
+    }
+    scalar_field_count
+}
+
+/// The paths that select the most scalar fields, e.g. "`pets` (12), `pets.owner` (8)".
+fn heaviest_paths(mut path_weights: Vec<(String, usize)>) -> String {
+    path_weights.sort_by(|(path_1, weight_1), (path_2, weight_2)| {
+        weight_2.cmp(weight_1).then_with(|| path_1.cmp(path_2))
+    });
+    path_weights
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0)
+        .take(HEAVIEST_PATH_COUNT)
+        .map(|(path, weight)| format!("`{path}` ({weight})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum SelectionBudgetError {
+    #[error(
+        "The client field `{}.{}` reads {scalar_field_count} scalar fields (including \
+        the fields read by the client fields it selects), which exceeds the \
+        selection_budget of {max_scalar_field_count}.{}",
+        client_field.type_name,
+        client_field.field_name,
+        if heaviest_paths.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " The linked fields that directly select the most scalar fields are: \
+                {heaviest_paths}."
+            )
+        }
+    )]
+    SelectionBudgetExceeded {
+        client_field: ObjectTypeAndFieldName,
+        scalar_field_count: usize,
+        max_scalar_field_count: usize,
+        heaviest_paths: String,
+    },
+}
+
+impl HasErrorCode for SelectionBudgetError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            SelectionBudgetError::SelectionBudgetExceeded { .. } => error_codes::IS0610,
+        }
+    }
+}
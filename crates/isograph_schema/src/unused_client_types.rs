@@ -0,0 +1,101 @@
+use std::collections::{BTreeSet, HashSet};
+
+use common_lang_types::{
+    error_codes, ErrorCode, HasErrorCode, Location, ObjectTypeAndFieldName, Span, WithLocation,
+};
+use isograph_config::OptionalValidationLevel;
+use isograph_lang_types::SelectionType;
+use thiserror::Error;
+
+use crate::{
+    accessible_client_fields, ClientScalarOrObjectSelectable, ClientSelectableId, NetworkProtocol,
+    Schema,
+};
+
+/// The user-written client fields and client pointers that are not reachable from
+/// any entrypoint, i.e. that are never read. Selecting a client field loadably
+/// counts as reaching it.
+pub fn unused_client_types<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+) -> BTreeSet<ClientSelectableId> {
+    let mut reachable_client_types = HashSet::new();
+    let mut queue = schema
+        .entrypoints
+        .keys()
+        .map(|client_field_id| SelectionType::Scalar(*client_field_id))
+        .collect::<Vec<ClientSelectableId>>();
+
+    while let Some(client_type_id) = queue.pop() {
+        if reachable_client_types.insert(client_type_id) {
+            queue.extend(accessible_client_fields(
+                &schema.client_type(client_type_id),
+                schema,
+            ));
+        }
+    }
+
+    schema
+        .user_written_client_types()
+        .map(|(client_type_id, _, _)| client_type_id)
+        .filter(|client_type_id| !reachable_client_types.contains(client_type_id))
+        .collect()
+}
+
+/// Report each unused client field and client pointer, according to the
+/// on_unused_client_field config option.
+pub fn validate_unused_client_types<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    on_unused_client_field: OptionalValidationLevel,
+) -> Result<(), Vec<WithLocation<UnusedClientTypeError>>> {
+    if matches!(on_unused_client_field, OptionalValidationLevel::Ignore) {
+        return Ok(());
+    }
+
+    let errors = unused_client_types(schema)
+        .into_iter()
+        .filter_map(|client_type_id| {
+            let client_type = schema.client_type(client_type_id);
+            on_unused_client_field
+                .on_failure(|| {
+                    WithLocation::new(
+                        UnusedClientTypeError::UnusedClientType {
+                            client_type: client_type.client_type(),
+                            type_and_field: client_type.type_and_field(),
+                        },
+                        client_type
+                            .text_source()
+                            .map(|text_source| Location::new(text_source, Span::new(0, 0)))
+                            .unwrap_or_else(Location::generated),
+                    )
+                })
+                .err()
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum UnusedClientTypeError {
+    #[error(
+        "The client {client_type} `{}.{}` is not reachable from any entrypoint.",
+        type_and_field.type_name,
+        type_and_field.field_name
+    )]
+    UnusedClientType {
+        client_type: &'static str,
+        type_and_field: ObjectTypeAndFieldName,
+    },
+}
+
+impl HasErrorCode for UnusedClientTypeError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            UnusedClientTypeError::UnusedClientType { .. } => error_codes::IS0600,
+        }
+    }
+}
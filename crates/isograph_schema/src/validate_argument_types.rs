@@ -1,16 +1,18 @@
 use common_lang_types::{
-    EnumLiteralValue, GraphQLScalarTypeName, Location, SelectableName, UnvalidatedTypeName,
-    ValueKeyName, VariableName, WithLocation, WithSpan,
+    closest_match, did_you_mean, error_codes, EnumLiteralValue, ErrorCode, GraphQLScalarTypeName,
+    HasErrorCode, Location, SelectableName, UnvalidatedTypeName, ValueKeyName, VariableName,
+    WithLocation, WithSpan,
 };
 use graphql_lang_types::{
     GraphQLListTypeAnnotation, GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation,
     GraphQLTypeAnnotation, NameValuePair,
 };
+use intern::{string_key::Intern, Lookup};
 use thiserror::Error;
 
 use isograph_lang_types::{
-    graphql_type_annotation_from_type_annotation, NonConstantValue, SelectionType, ServerEntityId,
-    ServerObjectEntityId, ServerScalarEntityId, VariableDefinition,
+    graphql_type_annotation_from_type_annotation, ConstantValue, NonConstantValue, SelectionType,
+    ServerEntityId, ServerObjectEntityId, ServerScalarEntityId, VariableDefinition,
 };
 
 use crate::{
@@ -132,12 +134,17 @@ pub fn value_satisfies_type<TNetworkProtocol: NetworkProtocol>(
 ) -> ValidateArgumentTypesResult<()> {
     match &selection_supplied_argument_value.item {
         NonConstantValue::Variable(variable_name) => {
-            let variable_type = get_variable_type(
+            let variable_definition = get_variable_definition(
                 variable_name,
                 variable_definitions,
                 selection_supplied_argument_value.location,
             )?;
+            let variable_type = &variable_definition.type_;
             if variable_type_satisfies_argument_type(variable_type, field_argument_definition_type)
+                || variable_with_default_value_satisfies_argument_type(
+                    variable_definition,
+                    field_argument_definition_type,
+                )
             {
                 Ok(())
             } else {
@@ -319,7 +326,6 @@ fn object_satisfies_type<TNetworkProtocol: NetworkProtocol>(
             )
             .selectables,
         object_literal,
-        selection_supplied_argument_value.location,
     )?;
 
     let missing_fields = get_non_nullable_missing_and_provided_fields(
@@ -429,27 +435,25 @@ fn get_non_nullable_missing_and_provided_fields<TNetworkProtocol: NetworkProtoco
 fn validate_no_extraneous_fields(
     object_fields: &ServerObjectEntityAvailableSelectables,
     object_literal: &[NameValuePair<ValueKeyName, NonConstantValue>],
-    location: Location,
 ) -> ValidateArgumentTypesResult<()> {
-    let extra_fields: Vec<_> = object_literal
-        .iter()
-        .filter_map(|field| {
-            let is_defined = object_fields
-                .iter()
-                .any(|(field_name, _)| *field_name == field.name.item);
-
-            if !is_defined {
-                return Some(field.clone());
-            }
-            None
-        })
-        .collect();
-
-    if !extra_fields.is_empty() {
-        return Err(WithLocation::new(
-            ValidateArgumentTypesError::ExtraneousFields { extra_fields },
-            location,
-        ));
+    for field in object_literal {
+        let is_defined = object_fields
+            .iter()
+            .any(|(field_name, _)| *field_name == field.name.item);
+
+        if !is_defined {
+            return Err(WithLocation::new(
+                ValidateArgumentTypesError::ExtraneousField {
+                    field_name: field.name.item,
+                    suggestion: closest_match(
+                        field.name.item.lookup(),
+                        object_fields.keys().map(|field_name| field_name.lookup()),
+                    )
+                    .map(|suggestion| suggestion.intern().into()),
+                },
+                field.name.location,
+            ));
+        }
     }
     Ok(())
 }
@@ -496,8 +500,35 @@ fn enum_satisfies_type<TNetworkProtocol: NetworkProtocol>(
                 location,
             ))
         }
-        SelectionType::Scalar(_scalar_entity_id) => {
-            todo!("Validate enum literal. Parser doesn't support enum literals yet")
+        SelectionType::Scalar(scalar_entity_id) => {
+            // Enums are represented as scalars, and we do not keep track of their
+            // values. So, we only reject enum literals passed to built-in scalars.
+            let built_in_scalars = [
+                schema_data.id_type_id,
+                schema_data.string_type_id,
+                schema_data.int_type_id,
+                schema_data.float_type_id,
+                schema_data.boolean_type_id,
+            ];
+            if built_in_scalars.contains(&scalar_entity_id) {
+                let expected = GraphQLTypeAnnotation::Named(GraphQLNamedTypeAnnotation(
+                    enum_type.clone().map(|_| {
+                        schema_data
+                            .server_scalar_entity(scalar_entity_id)
+                            .name
+                            .item
+                            .into()
+                    }),
+                ));
+                return Err(WithLocation::new(
+                    ValidateArgumentTypesError::ExpectedTypeFoundEnum {
+                        expected,
+                        actual: *enum_literal_value,
+                    },
+                    location,
+                ));
+            }
+            Ok(())
         }
     }
 }
@@ -522,25 +553,54 @@ fn list_satisfies_type<TNetworkProtocol: NetworkProtocol>(
     })
 }
 
-fn get_variable_type<'a>(
+fn get_variable_definition<'a>(
     variable_name: &'a VariableName,
     variable_definitions: &'a [WithSpan<ValidatedVariableDefinition>],
     location: Location,
-) -> ValidateArgumentTypesResult<&'a GraphQLTypeAnnotation<ServerEntityId>> {
+) -> ValidateArgumentTypesResult<&'a ValidatedVariableDefinition> {
     match variable_definitions
         .iter()
         .find(|definition| definition.item.name.item == *variable_name)
     {
-        Some(variable) => Ok(&variable.item.type_),
+        Some(variable) => Ok(&variable.item),
         None => Err(WithLocation::new(
             ValidateArgumentTypesError::UsedUndefinedVariable {
                 undefined_variable: *variable_name,
+                suggestion: closest_match(
+                    variable_name.lookup(),
+                    variable_definitions
+                        .iter()
+                        .map(|definition| definition.item.name.item.lookup()),
+                )
+                .map(|suggestion| suggestion.intern().into()),
             },
             location,
         )),
     }
 }
 
+/// A nullable variable with a non-null default value can be passed to a non-null
+/// argument, as the default value is used if the variable is not provided.
+fn variable_with_default_value_satisfies_argument_type(
+    variable_definition: &ValidatedVariableDefinition,
+    argument_type: &GraphQLTypeAnnotation<ServerEntityId>,
+) -> bool {
+    let has_non_null_default_value = variable_definition
+        .default_value
+        .as_ref()
+        .is_some_and(|default_value| default_value.item != ConstantValue::Null);
+
+    match argument_type {
+        GraphQLTypeAnnotation::NonNull(non_null_argument_type) if has_non_null_default_value => {
+            variable_type_satisfies_argument_type(
+                &variable_definition.type_,
+                &graphql_type_to_nullable_type(*non_null_argument_type.clone()),
+            )
+        }
+        _ => false,
+    }
+}
+
 type ValidateArgumentTypesResult<T> = Result<T, WithLocation<ValidateArgumentTypesError>>;
 
 #[derive(Debug, Error, PartialEq, Eq, Clone)]
@@ -579,8 +639,14 @@ pub enum ValidateArgumentTypesError {
         actual: EnumLiteralValue,
     },
 
-    #[error("This variable is not defined: ${undefined_variable}")]
-    UsedUndefinedVariable { undefined_variable: VariableName },
+    #[error(
+        "This variable is not defined: ${undefined_variable}.{}",
+        did_you_mean("$", *suggestion)
+    )]
+    UsedUndefinedVariable {
+        undefined_variable: VariableName,
+        suggestion: Option<VariableName>,
+    },
 
     #[error(
         "This object has missing fields: {0}",
@@ -591,10 +657,27 @@ pub enum ValidateArgumentTypesError {
     },
 
     #[error(
-        "This object has extra fields: {0}",
-        extra_fields.iter().map(|field| format!("{}", field.name.item)).collect::<Vec<_>>().join(", ")
+        "The field `{field_name}` is not defined on this input object.{}",
+        did_you_mean("", *suggestion)
     )]
-    ExtraneousFields {
-        extra_fields: Vec<NameValuePair<ValueKeyName, NonConstantValue>>,
+    ExtraneousField {
+        field_name: ValueKeyName,
+        suggestion: Option<ValueKeyName>,
     },
 }
+
+impl HasErrorCode for ValidateArgumentTypesError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ValidateArgumentTypesError::ExpectedTypeFoundVariable { .. } => error_codes::IS0503,
+            ValidateArgumentTypesError::ExpectedTypeFoundScalar { .. } => error_codes::IS0504,
+            ValidateArgumentTypesError::ExpectedTypeFoundObject { .. } => error_codes::IS0505,
+            ValidateArgumentTypesError::ExpectedTypeFoundList { .. } => error_codes::IS0506,
+            ValidateArgumentTypesError::ExpectedNonNullTypeFoundNull { .. } => error_codes::IS0507,
+            ValidateArgumentTypesError::ExpectedTypeFoundEnum { .. } => error_codes::IS0508,
+            ValidateArgumentTypesError::UsedUndefinedVariable { .. } => error_codes::IS0509,
+            ValidateArgumentTypesError::MissingFields { .. } => error_codes::IS0510,
+            ValidateArgumentTypesError::ExtraneousField { .. } => error_codes::IS0511,
+        }
+    }
+}
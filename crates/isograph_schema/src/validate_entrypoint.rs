@@ -1,12 +1,15 @@
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 use common_lang_types::{
+    closest_match, did_you_mean, error_codes, ClientScalarSelectableName, ErrorCode, HasErrorCode,
     IsoLiteralText, IsographObjectTypeName, Location, ServerScalarSelectableName, TextSource,
     UnvalidatedTypeName, WithLocation, WithSpan,
 };
+use intern::{string_key::Intern, Lookup};
+use isograph_config::ConfigEntrypoint;
 use isograph_lang_types::{
-    ClientScalarSelectableId, DefinitionLocation, EntrypointDeclaration, EntrypointDirectiveSet,
-    SelectionType, ServerEntityId, ServerObjectEntityId,
+    ClientScalarSelectableId, DefinitionLocation, EmptyDirectiveSet, EntrypointDeclaration,
+    EntrypointDirectiveSet, SelectionType, ServerEntityId, ServerObjectEntityId,
 };
 
 use thiserror::Error;
@@ -15,13 +18,20 @@ use crate::{NetworkProtocol, Schema};
 
 #[derive(Debug)]
 pub struct EntrypointDeclarationInfo {
-    pub iso_literal_text: IsoLiteralText,
+    /// None if the entrypoint is only declared in the config.
+    pub iso_literal_text: Option<IsoLiteralText>,
     pub directive_set: EntrypointDirectiveSet,
+    /// The location of the client field name in each declaration of this
+    /// entrypoint. Used by the language server.
+    pub client_field_name_locations: Vec<Location>,
 }
 
+/// Validate the entrypoints declared in iso literals and in the config. An
+/// entrypoint can be declared in both.
 pub fn validate_entrypoints<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     entrypoint_declarations: Vec<(TextSource, WithSpan<EntrypointDeclaration>)>,
+    config_entrypoints: &[ConfigEntrypoint],
 ) -> Result<
     HashMap<ClientScalarSelectableId, EntrypointDeclarationInfo>,
     Vec<WithLocation<ValidateEntrypointDeclarationError>>,
@@ -32,12 +42,21 @@ pub fn validate_entrypoints<TNetworkProtocol: NetworkProtocol>(
     for (text_source, entrypoint_declaration) in entrypoint_declarations {
         match validate_entrypoint_type_and_field(schema, text_source, entrypoint_declaration) {
             Ok(client_field_id) => {
+                let client_field_name_location = Location::new(
+                    text_source,
+                    entrypoint_declaration.item.client_field_name.span,
+                );
                 let new_entrypoint = EntrypointDeclarationInfo {
-                    iso_literal_text: entrypoint_declaration.item.iso_literal_text,
+                    iso_literal_text: Some(entrypoint_declaration.item.iso_literal_text),
                     directive_set: entrypoint_declaration.item.entrypoint_directive_set,
+                    client_field_name_locations: vec![client_field_name_location],
                 };
                 match entrypoints.entry(client_field_id) {
-                    Entry::Occupied(occupied_entry) => {
+                    Entry::Occupied(mut occupied_entry) => {
+                        occupied_entry
+                            .get_mut()
+                            .client_field_name_locations
+                            .push(client_field_name_location);
                         if occupied_entry.get().directive_set != new_entrypoint.directive_set {
                             errors.push(WithLocation::new(
                                 ValidateEntrypointDeclarationError::LazyLoadInconsistentEntrypoint,
@@ -59,6 +78,30 @@ pub fn validate_entrypoints<TNetworkProtocol: NetworkProtocol>(
         }
     }
 
+    let mut validated_config_entrypoints = HashSet::new();
+    for config_entrypoint in config_entrypoints {
+        match validate_config_entrypoint(schema, *config_entrypoint) {
+            Ok(client_field_id) => {
+                if !validated_config_entrypoints.insert(client_field_id) {
+                    errors.push(WithLocation::new(
+                        ValidateEntrypointDeclarationError::DuplicateConfigEntrypoint {
+                            entrypoint: config_entrypoint.to_string(),
+                        },
+                        Location::generated(),
+                    ));
+                }
+                entrypoints
+                    .entry(client_field_id)
+                    .or_insert_with(|| EntrypointDeclarationInfo {
+                        iso_literal_text: None,
+                        directive_set: EntrypointDirectiveSet::None(EmptyDirectiveSet {}),
+                        client_field_name_locations: vec![],
+                    });
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
     if errors.is_empty() {
         Ok(entrypoints)
     } else {
@@ -71,58 +114,72 @@ fn validate_entrypoint_type_and_field<TNetworkProtocol: NetworkProtocol>(
     text_source: TextSource,
     entrypoint_declaration: WithSpan<EntrypointDeclaration>,
 ) -> Result<ClientScalarSelectableId, WithLocation<ValidateEntrypointDeclarationError>> {
-    let parent_object_entity_id = validate_parent_object_entity_id(
-        schema,
-        entrypoint_declaration.item.parent_type,
-        text_source,
-    )?;
-    let client_field_id = validate_client_field(
-        schema,
-        entrypoint_declaration.item.client_field_name,
-        text_source,
-        parent_object_entity_id,
-    )?;
+    let parent_type = entrypoint_declaration.item.parent_type;
+    let client_field_name = entrypoint_declaration.item.client_field_name;
+
+    let parent_object_entity_id = validate_parent_object_entity_id(schema, parent_type.item)
+        .map_err(|e| WithLocation::new(e, Location::new(text_source, parent_type.span)))?;
+    let client_field_id =
+        validate_client_field(schema, client_field_name.item, parent_object_entity_id).map_err(
+            |e| WithLocation::new(e, Location::new(text_source, client_field_name.span)),
+        )?;
 
     Ok(client_field_id)
 }
 
+/// Entrypoints declared in the config have no location, so errors mention the
+/// entrypoint instead.
+fn validate_config_entrypoint<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    config_entrypoint: ConfigEntrypoint,
+) -> Result<ClientScalarSelectableId, WithLocation<ValidateEntrypointDeclarationError>> {
+    validate_parent_object_entity_id(schema, config_entrypoint.parent_type)
+        .and_then(|parent_object_entity_id| {
+            validate_client_field(
+                schema,
+                config_entrypoint.client_field_name,
+                parent_object_entity_id,
+            )
+        })
+        .map_err(|e| {
+            WithLocation::new(
+                ValidateEntrypointDeclarationError::InvalidConfigEntrypoint {
+                    entrypoint: config_entrypoint.to_string(),
+                    error: Box::new(e),
+                },
+                Location::generated(),
+            )
+        })
+}
+
 fn validate_parent_object_entity_id<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
-    parent_type: WithSpan<UnvalidatedTypeName>,
-    text_source: TextSource,
-) -> Result<ServerObjectEntityId, WithLocation<ValidateEntrypointDeclarationError>> {
+    parent_type_name: UnvalidatedTypeName,
+) -> Result<ServerObjectEntityId, ValidateEntrypointDeclarationError> {
     let parent_type_id = schema
         .server_entity_data
         .defined_entities
-        .get(&parent_type.item)
-        .ok_or(WithLocation::new(
-            ValidateEntrypointDeclarationError::ParentTypeNotDefined {
-                parent_type_name: parent_type.item,
-            },
-            Location::new(text_source, parent_type.span),
-        ))?;
+        .get(&parent_type_name)
+        .ok_or(ValidateEntrypointDeclarationError::ParentTypeNotDefined { parent_type_name })?;
 
     match parent_type_id {
         ServerEntityId::Object(object_entity_id) => {
             if !schema.fetchable_types.contains_key(object_entity_id) {
-                Err(WithLocation::new(
-                    ValidateEntrypointDeclarationError::NonFetchableParentType {
-                        parent_type_name: parent_type.item,
-                        fetchable_types: schema
-                            .fetchable_types
-                            .keys()
-                            .map(|object_entity_id| {
-                                schema
-                                    .server_entity_data
-                                    .server_object_entity(*object_entity_id)
-                                    .name
-                                    .to_string()
-                            })
-                            .collect::<Vec<_>>()
-                            .join(", "),
-                    },
-                    Location::new(text_source, parent_type.span),
-                ))
+                Err(ValidateEntrypointDeclarationError::NonFetchableParentType {
+                    parent_type_name,
+                    fetchable_types: schema
+                        .fetchable_types
+                        .keys()
+                        .map(|object_entity_id| {
+                            schema
+                                .server_entity_data
+                                .server_object_entity(*object_entity_id)
+                                .name
+                                .to_string()
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                })
             } else {
                 Ok(*object_entity_id)
             }
@@ -132,28 +189,24 @@ fn validate_parent_object_entity_id<TNetworkProtocol: NetworkProtocol>(
                 .server_entity_data
                 .server_scalar_entity(*scalar_entity_id)
                 .name;
-            Err(WithLocation::new(
-                ValidateEntrypointDeclarationError::InvalidParentType {
-                    parent_type: "scalar",
-                    parent_type_name: scalar_name.item.into(),
-                },
-                Location::new(text_source, parent_type.span),
-            ))
+            Err(ValidateEntrypointDeclarationError::InvalidParentType {
+                parent_type: "scalar",
+                parent_type_name: scalar_name.item.into(),
+            })
         }
     }
 }
 
 fn validate_client_field<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
-    field_name: WithSpan<ServerScalarSelectableName>,
-    text_source: TextSource,
+    field_name: ServerScalarSelectableName,
     parent_object_entity_id: ServerObjectEntityId,
-) -> Result<ClientScalarSelectableId, WithLocation<ValidateEntrypointDeclarationError>> {
+) -> Result<ClientScalarSelectableId, ValidateEntrypointDeclarationError> {
     let parent_object = schema
         .server_entity_data
         .server_object_entity(parent_object_entity_id);
 
-    match schema
+    let selectables = &schema
         .server_entity_data
         .server_object_entity_extra_info
         .get(&parent_object_entity_id)
@@ -161,29 +214,44 @@ fn validate_client_field<TNetworkProtocol: NetworkProtocol>(
             "Expected parent_object_entity_id to exist \
             in server_object_entity_available_selectables",
         )
-        .selectables
-        .get(&field_name.item.into())
-    {
+        .selectables;
+
+    // Only client fields can be entrypoints, so only they are suggested.
+    let suggestion = || {
+        closest_match(
+            field_name.lookup(),
+            selectables
+                .iter()
+                .filter(|(_, selectable_id)| {
+                    matches!(
+                        selectable_id,
+                        DefinitionLocation::Client(SelectionType::Scalar(_))
+                    )
+                })
+                .map(|(name, _)| name.lookup()),
+        )
+        .map(|suggestion| suggestion.intern().into())
+    };
+
+    match selectables.get(&field_name.into()) {
         Some(defined_field) => match defined_field {
             DefinitionLocation::Client(SelectionType::Object(_))
-            | DefinitionLocation::Server(_) => Err(WithLocation::new(
-                ValidateEntrypointDeclarationError::FieldMustBeClientField {
+            | DefinitionLocation::Server(_) => {
+                Err(ValidateEntrypointDeclarationError::FieldMustBeClientField {
                     parent_type_name: parent_object.name,
-                    client_field_name: field_name.item,
-                },
-                Location::new(text_source, field_name.span),
-            )),
+                    client_field_name: field_name,
+                    suggestion: suggestion(),
+                })
+            }
             DefinitionLocation::Client(SelectionType::Scalar(client_field_id)) => {
                 Ok(*client_field_id)
             }
         },
-        None => Err(WithLocation::new(
-            ValidateEntrypointDeclarationError::ClientFieldMustExist {
-                parent_type_name: parent_object.name,
-                client_field_name: field_name.item,
-            },
-            Location::new(text_source, field_name.span),
-        )),
+        None => Err(ValidateEntrypointDeclarationError::ClientFieldMustExist {
+            parent_type_name: parent_object.name,
+            client_field_name: field_name,
+            suggestion: suggestion(),
+        }),
     }
 }
 
@@ -208,19 +276,64 @@ pub enum ValidateEntrypointDeclarationError {
         fetchable_types: String,
     },
 
-    #[error("The client field `{parent_type_name}.{client_field_name}` is not defined.")]
+    #[error(
+        "The client field `{parent_type_name}.{client_field_name}` is not defined.{}",
+        did_you_mean("", *suggestion)
+    )]
     ClientFieldMustExist {
         parent_type_name: IsographObjectTypeName,
         client_field_name: ServerScalarSelectableName,
+        suggestion: Option<ClientScalarSelectableName>,
     },
 
     // N.B. We could conceivably support fetching server fields, though!
-    #[error("The field `{parent_type_name}.{client_field_name}` is a server field. It must be a client defined field.")]
+    #[error(
+        "The field `{parent_type_name}.{client_field_name}` is a server field. \
+        It must be a client defined field.{}",
+        did_you_mean("", *suggestion)
+    )]
     FieldMustBeClientField {
         parent_type_name: IsographObjectTypeName,
         client_field_name: ServerScalarSelectableName,
+        suggestion: Option<ClientScalarSelectableName>,
     },
 
     #[error("Entrypoint declared lazy in one location and declared eager in another location. Entrypoint must be either lazy or non-lazy in all instances.")]
     LazyLoadInconsistentEntrypoint,
+
+    #[error("Invalid entrypoint `{entrypoint}` in the entrypoints of the config. {error}")]
+    InvalidConfigEntrypoint {
+        entrypoint: String,
+        error: Box<ValidateEntrypointDeclarationError>,
+    },
+
+    #[error(
+        "The entrypoint `{entrypoint}` is listed more than once in the entrypoints of the config."
+    )]
+    DuplicateConfigEntrypoint { entrypoint: String },
+}
+
+impl HasErrorCode for ValidateEntrypointDeclarationError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ValidateEntrypointDeclarationError::ParentTypeNotDefined { .. } => error_codes::IS0300,
+            ValidateEntrypointDeclarationError::InvalidParentType { .. } => error_codes::IS0301,
+            ValidateEntrypointDeclarationError::NonFetchableParentType { .. } => {
+                error_codes::IS0302
+            }
+            ValidateEntrypointDeclarationError::ClientFieldMustExist { .. } => error_codes::IS0303,
+            ValidateEntrypointDeclarationError::FieldMustBeClientField { .. } => {
+                error_codes::IS0304
+            }
+            ValidateEntrypointDeclarationError::LazyLoadInconsistentEntrypoint => {
+                error_codes::IS0305
+            }
+            ValidateEntrypointDeclarationError::InvalidConfigEntrypoint { error, .. } => {
+                error.error_code()
+            }
+            ValidateEntrypointDeclarationError::DuplicateConfigEntrypoint { .. } => {
+                error_codes::IS0306
+            }
+        }
+    }
 }
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+
+use common_lang_types::{
+    error_codes, ErrorCode, HasErrorCode, Location, ObjectTypeAndFieldName, WithLocation,
+};
+use isograph_config::CompilerConfigOptions;
+use isograph_lang_types::{DefinitionLocation, SelectionType};
+use thiserror::Error;
+
+use crate::{
+    create_merged_selection_map_for_field_and_insert_into_global_map, initial_variable_context,
+    ClientScalarOrObjectSelectable, MergedSelectionMap, MergedServerSelection, NetworkProtocol,
+    Schema,
+};
+
+/// Validate that the query of each entrypoint is within the max_query_depth and
+/// max_query_field_count limits of the config, if they are set.
+///
+/// The depth of a query is the greatest number of nested linked fields, e.g.
+/// `me { pets { name } }` has a depth of 2. The field count is the number of
+/// fields in the query, after selections of the same field are merged.
+pub fn validate_query_limits<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    options: &CompilerConfigOptions,
+) -> Result<(), Vec<WithLocation<QueryLimitError>>> {
+    if options.max_query_depth.is_none() && options.max_query_field_count.is_none() {
+        return Ok(());
+    }
+
+    let mut entrypoints = schema
+        .entrypoints
+        .iter()
+        .map(|(entrypoint_id, info)| (schema.client_field(*entrypoint_id), entrypoint_id, info))
+        .collect::<Vec<_>>();
+    entrypoints.sort_by_key(|(entrypoint, _, _)| {
+        (
+            entrypoint.type_and_field.type_name.to_string(),
+            entrypoint.type_and_field.field_name.to_string(),
+        )
+    });
+
+    let mut encountered_client_type_map = BTreeMap::new();
+    let mut errors = vec![];
+    for (entrypoint, entrypoint_id, info) in entrypoints {
+        let merged_selection_map =
+            create_merged_selection_map_for_field_and_insert_into_global_map(
+                schema,
+                entrypoint.parent_object_entity_id,
+                schema
+                    .server_entity_data
+                    .server_object_entity(entrypoint.parent_object_entity_id),
+                entrypoint.selection_set_for_parent_query(),
+                &mut encountered_client_type_map,
+                DefinitionLocation::Client(SelectionType::Scalar(*entrypoint_id)),
+                &initial_variable_context(&SelectionType::Scalar(entrypoint)),
+            )
+            .merged_selection_map;
+
+        let location = info
+            .client_field_name_locations
+            .first()
+            .copied()
+            .unwrap_or_else(Location::generated);
+
+        if let Some(max_query_depth) = options.max_query_depth {
+            let (depth, path) = deepest_path(&merged_selection_map);
+            if depth > max_query_depth {
+                errors.push(WithLocation::new(
+                    QueryLimitError::MaxQueryDepthExceeded {
+                        entrypoint: entrypoint.type_and_field,
+                        depth,
+                        max_query_depth,
+                        path: path.join("."),
+                    },
+                    location,
+                ));
+            }
+        }
+
+        if let Some(max_query_field_count) = options.max_query_field_count {
+            let mut field_count = 0;
+            let mut exceeding_path = None;
+            count_fields(
+                &merged_selection_map,
+                &mut vec![],
+                max_query_field_count,
+                &mut field_count,
+                &mut exceeding_path,
+            );
+            if let Some(path) = exceeding_path {
+                errors.push(WithLocation::new(
+                    QueryLimitError::MaxQueryFieldCountExceeded {
+                        entrypoint: entrypoint.type_and_field,
+                        field_count,
+                        max_query_field_count,
+                        path,
+                    },
+                    location,
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+pub(crate) fn selection_path_segment(selection: &MergedServerSelection) -> String {
+    match selection {
+        MergedServerSelection::ScalarField(scalar_field) => scalar_field.name.to_string(),
+        MergedServerSelection::LinkedField(linked_field) => linked_field.name.to_string(),
+        MergedServerSelection::InlineFragment(inline_fragment) => {
+            format!("as{}", inline_fragment.type_to_refine_to)
+        }
+    }
+}
+
+/// The depth of the selection map, and the path to its most deeply nested linked
+/// field. Inline fragments are included in the path (e.g. as asPet), but do not
+/// count towards the depth.
+fn deepest_path(selection_map: &MergedSelectionMap) -> (usize, Vec<String>) {
+    let mut deepest = (0, vec![]);
+    for selection in selection_map.values() {
+        let (nested_selection_map, added_depth) = match selection {
+            MergedServerSelection::ScalarField(_) => continue,
+            MergedServerSelection::LinkedField(linked_field) => (&linked_field.selection_map, 1),
+            MergedServerSelection::InlineFragment(inline_fragment) => {
+                (&inline_fragment.selection_map, 0)
+            }
+        };
+        let (nested_depth, nested_path) = deepest_path(nested_selection_map);
+        if added_depth + nested_depth > deepest.0 {
+            let mut path = vec![selection_path_segment(selection)];
+            path.extend(nested_path);
+            deepest = (added_depth + nested_depth, path);
+        }
+    }
+    deepest
+}
+
+/// Count the fields in the selection map, in order. When the count first exceeds
+/// max_query_field_count, record the path of the field that exceeded it.
+fn count_fields(
+    selection_map: &MergedSelectionMap,
+    path: &mut Vec<String>,
+    max_query_field_count: usize,
+    field_count: &mut usize,
+    exceeding_path: &mut Option<String>,
+) {
+    for selection in selection_map.values() {
+        path.push(selection_path_segment(selection));
+        let nested_selection_map = match selection {
+            MergedServerSelection::ScalarField(_) => None,
+            MergedServerSelection::LinkedField(linked_field) => Some(&linked_field.selection_map),
+            MergedServerSelection::InlineFragment(inline_fragment) => {
+                Some(&inline_fragment.selection_map)
+            }
+        };
+        if !matches!(selection, MergedServerSelection::InlineFragment(_)) {
+            *field_count += 1;
+            if *field_count > max_query_field_count && exceeding_path.is_none() {
+                *exceeding_path = Some(path.join("."));
+            }
+        }
+        if let Some(nested_selection_map) = nested_selection_map {
+            count_fields(
+                nested_selection_map,
+                path,
+                max_query_field_count,
+                field_count,
+                exceeding_path,
+            );
+        }
+        path.pop();
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum QueryLimitError {
+    #[error(
+        "The query of the entrypoint `{}.{}` has a depth of {depth}, which exceeds the \
+        max_query_depth of {max_query_depth}. The deepest path is `{path}`.",
+        entrypoint.type_name,
+        entrypoint.field_name
+    )]
+    MaxQueryDepthExceeded {
+        entrypoint: ObjectTypeAndFieldName,
+        depth: usize,
+        max_query_depth: usize,
+        path: String,
+    },
+
+    #[error(
+        "The query of the entrypoint `{}.{}` selects {field_count} fields, which exceeds \
+        the max_query_field_count of {max_query_field_count}. The limit is first exceeded \
+        at `{path}`.",
+        entrypoint.type_name,
+        entrypoint.field_name
+    )]
+    MaxQueryFieldCountExceeded {
+        entrypoint: ObjectTypeAndFieldName,
+        field_count: usize,
+        max_query_field_count: usize,
+        path: String,
+    },
+}
+
+impl HasErrorCode for QueryLimitError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            QueryLimitError::MaxQueryDepthExceeded { .. } => error_codes::IS0601,
+            QueryLimitError::MaxQueryFieldCountExceeded { .. } => error_codes::IS0602,
+        }
+    }
+}
@@ -1,14 +1,18 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use common_lang_types::{
-    FieldArgumentName, IsographObjectTypeName, Location, ObjectTypeAndFieldName, SelectableName,
+    closest_match, did_you_mean, error_codes, ErrorCode, FieldArgumentName, HasErrorCode,
+    IsographObjectTypeName, Location, ObjectTypeAndFieldName, SelectableName, Span, TextSource,
     VariableName, WithLocation, WithSpan,
 };
+use graphql_lang_types::{
+    GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation,
+};
 
-use intern::string_key::Intern;
+use intern::{string_key::Intern, Lookup};
 use isograph_lang_types::{
-    DefinitionLocation, NonConstantValue, ScalarSelectionDirectiveSet, SelectionFieldArgument,
-    SelectionType,
+    DefinitionLocation, NonConstantValue, ScalarSelectionDirectiveSet, SelectionCondition,
+    SelectionFieldArgument, SelectionType, ServerObjectSelectableId, ServerScalarSelectableId,
 };
 use lazy_static::lazy_static;
 use thiserror::Error;
@@ -21,6 +25,16 @@ use crate::{
 
 type UsedVariables = BTreeSet<VariableName>;
 
+/// For each server field and alias, the arguments of the first selection that
+/// produced that alias, and the client type containing that selection.
+type SelectionsByNormalizationAlias = HashMap<
+    (
+        SelectionType<ServerScalarSelectableId, ServerObjectSelectableId>,
+        String,
+    ),
+    (String, ObjectTypeAndFieldName),
+>;
+
 lazy_static! {
     static ref ID: FieldArgumentName = "id".intern().into();
 }
@@ -28,8 +42,11 @@ lazy_static! {
 /// For all client types, validate that
 /// - there are no unused arguments
 /// - all arguments are used
-/// - there are no missing arguments, and
-/// - all args type-check
+/// - there are no missing arguments,
+/// - all args type-check,
+/// - the default values of variables type-check, and
+/// - no two selections of a server field with different arguments produce the
+///   same alias (and thus overwrite each other in the response and the store.)
 ///
 /// In addition, validate that no server field is selected loadably.
 /// This should not be validated here, and can be fixed with better modeling (i.e.
@@ -39,10 +56,12 @@ pub fn validate_use_of_arguments<TNetworkProtocol: NetworkProtocol>(
     validated_schema: &Schema<TNetworkProtocol>,
 ) -> Result<(), Vec<WithLocation<ValidateUseOfArgumentsError>>> {
     let mut errors = vec![];
+    let mut selections_by_normalization_alias = HashMap::new();
     for client_scalar_selectable in &validated_schema.client_scalar_selectables {
         validate_use_of_arguments_for_client_type(
             validated_schema,
             client_scalar_selectable,
+            &mut selections_by_normalization_alias,
             &mut errors,
         );
     }
@@ -50,6 +69,7 @@ pub fn validate_use_of_arguments<TNetworkProtocol: NetworkProtocol>(
         validate_use_of_arguments_for_client_type(
             validated_schema,
             client_object_selectable,
+            &mut selections_by_normalization_alias,
             &mut errors,
         );
     }
@@ -64,6 +84,7 @@ pub fn validate_use_of_arguments<TNetworkProtocol: NetworkProtocol>(
 fn validate_use_of_arguments_for_client_type<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
     client_type: impl ClientScalarOrObjectSelectable,
+    selections_by_normalization_alias: &mut SelectionsByNormalizationAlias,
     errors: &mut Vec<WithLocation<ValidateUseOfArgumentsError>>,
 ) {
     let mut reachable_variables = BTreeSet::new();
@@ -103,6 +124,26 @@ fn validate_use_of_arguments_for_client_type<TNetworkProtocol: NetworkProtocol>(
                     &scalar_selection.arguments,
                     scalar_selection.name.location,
                 );
+                if let DefinitionLocation::Server(server_scalar_selectable_id) =
+                    scalar_selection.associated_data
+                {
+                    validate_normalization_alias_is_unique(
+                        selections_by_normalization_alias,
+                        errors,
+                        SelectionType::Scalar(server_scalar_selectable_id),
+                        scalar_selection.name.item.into(),
+                        &scalar_selection.arguments,
+                        client_type.type_and_field(),
+                        scalar_selection.name.location,
+                    );
+                }
+                validate_conditions(
+                    schema,
+                    errors,
+                    &mut reachable_variables,
+                    &scalar_selection.conditions,
+                    client_type.variable_definitions(),
+                );
             }
             SelectionType::Object(object_selection) => {
                 let field_argument_definitions = match object_selection.associated_data {
@@ -126,26 +167,131 @@ fn validate_use_of_arguments_for_client_type<TNetworkProtocol: NetworkProtocol>(
                     &mut reachable_variables,
                     field_argument_definitions,
                     client_type.variable_definitions(),
-                    true,
+                    // Linked fields cannot be selected loadably, so they must be passed
+                    // all required arguments.
+                    false,
                     &object_selection.arguments,
                     object_selection.name.location,
                 );
+                if let DefinitionLocation::Server(server_object_selectable_id) =
+                    object_selection.associated_data
+                {
+                    validate_normalization_alias_is_unique(
+                        selections_by_normalization_alias,
+                        errors,
+                        SelectionType::Object(server_object_selectable_id),
+                        object_selection.name.item.into(),
+                        &object_selection.arguments,
+                        client_type.type_and_field(),
+                        object_selection.name.location,
+                    );
+                }
+                validate_conditions(
+                    schema,
+                    errors,
+                    &mut reachable_variables,
+                    &object_selection.conditions,
+                    client_type.variable_definitions(),
+                );
             }
         },
     );
 
-    maybe_push_errors(
+    validate_default_values(schema, errors, client_type.variable_definitions());
+
+    validate_all_variables_are_used(
         errors,
-        validate_all_variables_are_used(
-            client_type.variable_definitions(),
-            reachable_variables,
-            client_type.type_and_field(),
-            // TODO client_type name needs a location
-            Location::generated(),
-        ),
+        client_type.variable_definitions(),
+        reachable_variables,
+        client_type.type_and_field(),
+        client_type.text_source(),
     );
 }
 
+/// Validate that the default values of a client type's variables (e.g. the `10` in
+/// `field Query.Search($first: Int = 10)`) satisfy the variables' declared types.
+fn validate_default_values<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    errors: &mut Vec<WithLocation<ValidateUseOfArgumentsError>>,
+    variable_definitions: &[WithSpan<ValidatedVariableDefinition>],
+) {
+    for variable_definition in variable_definitions {
+        if let Some(default_value) = &variable_definition.item.default_value {
+            maybe_push_errors(
+                errors,
+                value_satisfies_type(
+                    &default_value.clone().map(NonConstantValue::from),
+                    &variable_definition.item.type_,
+                    // Default values are constant, so they cannot refer to other variables.
+                    &[],
+                    &schema.server_entity_data,
+                    &schema.server_scalar_selectables,
+                    &schema.server_object_selectables,
+                )
+                .map_err(|with_location| with_location.map(|e| e.into())),
+            );
+        }
+    }
+}
+
+/// Selections of a server field with arguments are aliased in the generated query
+/// (and stored) using a key derived from the arguments, which replaces non-word
+/// characters with underscores. So e.g. `name: "a b"` and `name: "a_b"` produce
+/// the same alias, and their values would overwrite each other.
+#[allow(clippy::too_many_arguments)]
+fn validate_normalization_alias_is_unique(
+    selections_by_normalization_alias: &mut SelectionsByNormalizationAlias,
+    errors: &mut Vec<WithLocation<ValidateUseOfArgumentsError>>,
+    server_selectable_id: SelectionType<ServerScalarSelectableId, ServerObjectSelectableId>,
+    field_name: SelectableName,
+    arguments: &[WithLocation<SelectionFieldArgument>],
+    client_type_and_field: ObjectTypeAndFieldName,
+    name_location: Location,
+) {
+    if arguments.is_empty() {
+        return;
+    }
+
+    let alias = arguments
+        .iter()
+        .map(|argument| argument.item.to_alias_str_chunk())
+        .collect::<Vec<_>>()
+        .join("____");
+    let printed_arguments = arguments
+        .iter()
+        .map(|argument| {
+            format!(
+                "{}: {}",
+                argument.item.name.item,
+                argument.item.value.item.print_to_string()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match selections_by_normalization_alias.get(&(server_selectable_id, alias.clone())) {
+        Some((previous_arguments, previous_client_type_and_field)) => {
+            if *previous_arguments != printed_arguments {
+                errors.push(WithLocation::new(
+                    ValidateUseOfArgumentsError::ConflictingNormalizationAlias {
+                        field_name,
+                        arguments: printed_arguments,
+                        previous_arguments: previous_arguments.clone(),
+                        previous_client_type_and_field: *previous_client_type_and_field,
+                    },
+                    name_location,
+                ));
+            }
+        }
+        None => {
+            selections_by_normalization_alias.insert(
+                (server_selectable_id, alias),
+                (printed_arguments, client_type_and_field),
+            );
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn validate_use_of_arguments_impl<TNetworkProtocol: NetworkProtocol>(
     schema: &Schema<TNetworkProtocol>,
@@ -183,13 +329,10 @@ fn validate_use_of_arguments_impl<TNetworkProtocol: NetworkProtocol>(
         }
     }
 
-    maybe_push_errors(
+    validate_no_extraneous_or_duplicate_arguments(
         errors,
-        validate_no_extraneous_arguments(
-            &field_argument_definitions,
-            selection_supplied_arguments,
-            name_location,
-        ),
+        &field_argument_definitions,
+        selection_supplied_arguments,
     );
 
     extend_reachable_variables_with_args(reachable_variables, selection_supplied_arguments);
@@ -202,35 +345,59 @@ fn validate_use_of_arguments_impl<TNetworkProtocol: NetworkProtocol>(
     }
 }
 
+/// The `if` argument of @include and @skip must be a Boolean!.
+fn validate_conditions<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    errors: &mut Vec<WithLocation<ValidateUseOfArgumentsError>>,
+    reachable_variables: &mut BTreeSet<VariableName>,
+    conditions: &[WithLocation<SelectionCondition>],
+    client_type_variable_definitions: &[WithSpan<ValidatedVariableDefinition>],
+) {
+    let boolean_type = GraphQLTypeAnnotation::NonNull(Box::new(
+        GraphQLNonNullTypeAnnotation::Named(GraphQLNamedTypeAnnotation(WithSpan::new(
+            SelectionType::Scalar(schema.server_entity_data.boolean_type_id),
+            Span::todo_generated(),
+        ))),
+    ));
+    for condition in conditions {
+        let value = condition.clone().map(|condition| condition.value);
+        maybe_push_errors(
+            errors,
+            value_satisfies_type(
+                &value,
+                &boolean_type,
+                client_type_variable_definitions,
+                &schema.server_entity_data,
+                &schema.server_scalar_selectables,
+                &schema.server_object_selectables,
+            )
+            .map_err(|with_location| with_location.map(|e| e.into())),
+        );
+        extend_reachable_variables_with_arg(&value, reachable_variables);
+    }
+}
+
 fn validate_all_variables_are_used(
+    errors: &mut Vec<WithLocation<ValidateUseOfArgumentsError>>,
     variable_definitions: &[WithSpan<ValidatedVariableDefinition>],
     used_variables: UsedVariables,
     top_level_type_and_field_name: ObjectTypeAndFieldName,
-    location: Location,
-) -> ValidateUseOfArgumentsResult<()> {
-    let unused_variables = variable_definitions
-        .iter()
-        .filter_map(|variable| {
-            let is_used = used_variables.contains(&variable.item.name.item);
-
-            if !is_used {
-                return Some(variable.clone());
-            }
-            None
-        })
-        .collect::<Vec<_>>();
-
-    if !unused_variables.is_empty() {
-        return Err(WithLocation::new(
-            ValidateUseOfArgumentsError::UnusedVariables {
-                unused_variables,
-                type_name: top_level_type_and_field_name.type_name,
-                field_name: top_level_type_and_field_name.field_name,
-            },
-            location,
-        ));
+    text_source: Option<TextSource>,
+) {
+    for variable in variable_definitions {
+        if !used_variables.contains(&variable.item.name.item) {
+            errors.push(WithLocation::new(
+                ValidateUseOfArgumentsError::UnusedVariable {
+                    variable_name: variable.item.name.item,
+                    type_name: top_level_type_and_field_name.type_name,
+                    field_name: top_level_type_and_field_name.field_name,
+                },
+                text_source
+                    .map(|text_source| Location::new(text_source, variable.span))
+                    .unwrap_or_else(Location::generated),
+            ));
+        }
     }
-    Ok(())
 }
 
 fn assert_no_missing_arguments(
@@ -280,41 +447,51 @@ fn get_missing_and_provided_arguments<'a>(
         })
 }
 
-fn validate_no_extraneous_arguments(
+fn validate_no_extraneous_or_duplicate_arguments(
+    errors: &mut Vec<WithLocation<ValidateUseOfArgumentsError>>,
     field_argument_definitions: &[&ValidatedVariableDefinition],
     selection_supplied_arguments: &[WithLocation<SelectionFieldArgument>],
-    location: Location,
-) -> ValidateUseOfArgumentsResult<()> {
-    let extra_arguments: Vec<_> = selection_supplied_arguments
-        .iter()
-        .filter_map(|arg| {
-            // TODO remove this
-            // With @exposeField on Query, id field is needed because the generated
-            // query is like node(id: $id) { ... everything else }, but that
-            // id field is added in somewhere else
-
-            if arg.item.name.item == *ID {
-                return None;
-            }
-
-            let is_defined = field_argument_definitions
-                .iter()
-                .any(|definition| definition.name.item == arg.item.name.item);
+) {
+    let mut seen_arguments = BTreeSet::new();
+    for arg in selection_supplied_arguments {
+        let argument_name = arg.item.name.item;
+
+        if !seen_arguments.insert(argument_name) {
+            errors.push(WithLocation::new(
+                ValidateUseOfArgumentsError::DuplicateArgument { argument_name },
+                arg.location,
+            ));
+            continue;
+        }
 
-            if !is_defined {
-                return Some(arg.clone());
-            }
-            None
-        })
-        .collect();
+        // TODO remove this
+        // With @exposeField on Query, id field is needed because the generated
+        // query is like node(id: $id) { ... everything else }, but that
+        // id field is added in somewhere else
+        if argument_name == *ID {
+            continue;
+        }
 
-    if !extra_arguments.is_empty() {
-        return Err(WithLocation::new(
-            ValidateUseOfArgumentsError::ExtraneousArgument { extra_arguments },
-            location,
-        ));
+        let is_defined = field_argument_definitions
+            .iter()
+            .any(|definition| definition.name.item == argument_name);
+
+        if !is_defined {
+            errors.push(WithLocation::new(
+                ValidateUseOfArgumentsError::ExtraneousArgument {
+                    argument_name,
+                    suggestion: closest_match(
+                        argument_name.lookup(),
+                        field_argument_definitions
+                            .iter()
+                            .map(|definition| definition.name.item.lookup()),
+                    )
+                    .map(|suggestion| suggestion.intern().into()),
+                },
+                arg.location,
+            ));
+        }
     }
-    Ok(())
 }
 
 pub fn extend_reachable_variables_with_arg(
@@ -362,27 +539,42 @@ type ValidateUseOfArgumentsResult<T> = Result<T, WithLocation<ValidateUseOfArgum
 #[derive(Debug, Error, PartialEq, Eq, Clone)]
 pub enum ValidateUseOfArgumentsError {
     #[error(
-        "This field has missing arguments: {0}",
-        missing_arguments.iter().map(|arg| format!("${}", arg.name.item)).collect::<Vec<_>>().join(", ")
+        "This field is missing required arguments: {0}",
+        missing_arguments.iter().map(|arg| format!("`{}`", arg.name.item)).collect::<Vec<_>>().join(", ")
     )]
     MissingArguments { missing_arguments: MissingArguments },
 
     #[error(
-        "This field has extra arguments: {0}",
-        extra_arguments.iter().map(|arg| format!("{}", arg.item.name)).collect::<Vec<_>>().join(", ")
+        "This field does not accept the argument `{argument_name}`.{}",
+        did_you_mean("", *suggestion)
     )]
     ExtraneousArgument {
-        extra_arguments: Vec<WithLocation<SelectionFieldArgument>>,
+        argument_name: FieldArgumentName,
+        suggestion: Option<FieldArgumentName>,
+    },
+
+    #[error("The argument `{argument_name}` is passed more than once")]
+    DuplicateArgument { argument_name: FieldArgumentName },
+
+    #[error("The variable `${variable_name}` is defined by `{type_name}.{field_name}`, but is never used")]
+    UnusedVariable {
+        variable_name: VariableName,
+        type_name: IsographObjectTypeName,
+        field_name: SelectableName,
     },
 
     #[error(
-        "The field `{type_name}.{field_name}` has unused variables: {0}",
-        unused_variables.iter().map(|variable| format!("${}", variable.item.name.item)).collect::<Vec<_>>().join(", ")
+        "`{field_name}({arguments})` and `{field_name}({previous_arguments})` (selected in \
+        `{}.{}`) produce the same alias in the generated query, so their values would \
+        overwrite each other. Change one of the argument values.",
+        previous_client_type_and_field.type_name,
+        previous_client_type_and_field.field_name
     )]
-    UnusedVariables {
-        unused_variables: Vec<WithSpan<ValidatedVariableDefinition>>,
-        type_name: IsographObjectTypeName,
+    ConflictingNormalizationAlias {
         field_name: SelectableName,
+        arguments: String,
+        previous_arguments: String,
+        previous_client_type_and_field: ObjectTypeAndFieldName,
     },
 
     #[error("{message}")]
@@ -391,3 +583,18 @@ pub enum ValidateUseOfArgumentsError {
         message: ValidateArgumentTypesError,
     },
 }
+
+impl HasErrorCode for ValidateUseOfArgumentsError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ValidateUseOfArgumentsError::MissingArguments { .. } => error_codes::IS0500,
+            ValidateUseOfArgumentsError::ExtraneousArgument { .. } => error_codes::IS0501,
+            ValidateUseOfArgumentsError::UnusedVariable { .. } => error_codes::IS0502,
+            ValidateUseOfArgumentsError::DuplicateArgument { .. } => error_codes::IS0512,
+            ValidateUseOfArgumentsError::ConflictingNormalizationAlias { .. } => {
+                error_codes::IS0513
+            }
+            ValidateUseOfArgumentsError::ValidateArgumentType { message } => message.error_code(),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use common_lang_types::{
+    error_codes, ErrorCode, HasErrorCode, Location, Span, WithLocation, WithSpan,
+};
+use intern::Lookup;
+use isograph_config::{BuiltInValidationRule, OptionalValidationLevel};
+use isograph_lang_types::{DefinitionLocation, SelectionType};
+use thiserror::Error;
+
+use crate::{
+    visit_selection_set::visit_selection_set, ClientFieldVariant, ClientObjectSelectable,
+    ClientPointerVariant, ClientScalarOrObjectSelectable, ClientScalarSelectable, NetworkProtocol,
+    Schema, ValidatedSelection,
+};
+
+/// A rule that the selection set of every user-written client field and client
+/// pointer must satisfy, e.g. to enforce an organization's conventions. Rules are
+/// checked after the schema is otherwise validated.
+///
+/// Built-in rules are enabled with the validation_rules config option. Tools that
+/// embed the compiler can check their own rules with [`validate_with_rules`].
+pub trait ValidationRule<TNetworkProtocol: NetworkProtocol> {
+    /// The name of the rule, which is included in each violation.
+    fn name(&self) -> &'static str;
+
+    /// Return a message for each violation of this rule, located at the offending
+    /// selection.
+    fn validate_client_type(
+        &self,
+        schema: &Schema<TNetworkProtocol>,
+        client_type: SelectionType<
+            &ClientScalarSelectable<TNetworkProtocol>,
+            &ClientObjectSelectable<TNetworkProtocol>,
+        >,
+        selection_set: &[WithSpan<ValidatedSelection>],
+    ) -> Vec<WithLocation<String>>;
+}
+
+/// Check each rule against every user-written client field and client pointer,
+/// and report violations according to the rule's validation level.
+pub fn validate_with_rules<TNetworkProtocol: NetworkProtocol>(
+    schema: &Schema<TNetworkProtocol>,
+    rules: &[(
+        Box<dyn ValidationRule<TNetworkProtocol>>,
+        OptionalValidationLevel,
+    )],
+) -> Result<(), Vec<WithLocation<ValidationRuleError>>> {
+    let mut errors = vec![];
+    for (client_type_id, client_type, _) in schema.user_written_client_types() {
+        let selection_set = match client_type {
+            SelectionType::Scalar(client_field) => &client_field.reader_selection_set,
+            SelectionType::Object(client_pointer) => &client_pointer.reader_selection_set,
+        };
+        for (rule, validation_level) in rules {
+            if matches!(validation_level, OptionalValidationLevel::Ignore) {
+                continue;
+            }
+            for violation in
+                rule.validate_client_type(schema, schema.client_type(client_type_id), selection_set)
+            {
+                if let Err(error) = validation_level.on_failure(|| {
+                    WithLocation::new(
+                        ValidationRuleError::RuleViolated {
+                            rule_name: rule.name(),
+                            message: violation.item,
+                        },
+                        violation.location,
+                    )
+                }) {
+                    errors.push(error);
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The validation rules enabled in the config.
+pub fn built_in_validation_rules<TNetworkProtocol: NetworkProtocol>(
+    validation_rules: impl IntoIterator<Item = (BuiltInValidationRule, OptionalValidationLevel)>,
+) -> Vec<(
+    Box<dyn ValidationRule<TNetworkProtocol>>,
+    OptionalValidationLevel,
+)> {
+    validation_rules
+        .into_iter()
+        .map(|(rule, validation_level)| {
+            let rule: Box<dyn ValidationRule<TNetworkProtocol>> = match rule {
+                BuiltInValidationRule::NoDeprecatedFields => Box::new(NoDeprecatedFields),
+                BuiltInValidationRule::ExportNameMatchesFieldName => {
+                    Box::new(ExportNameMatchesFieldName)
+                }
+            };
+            (rule, validation_level)
+        })
+        .collect()
+}
+
+struct NoDeprecatedFields;
+
+impl<TNetworkProtocol: NetworkProtocol> ValidationRule<TNetworkProtocol> for NoDeprecatedFields {
+    fn name(&self) -> &'static str {
+        "no_deprecated_fields"
+    }
+
+    fn validate_client_type(
+        &self,
+        schema: &Schema<TNetworkProtocol>,
+        _client_type: SelectionType<
+            &ClientScalarSelectable<TNetworkProtocol>,
+            &ClientObjectSelectable<TNetworkProtocol>,
+        >,
+        selection_set: &[WithSpan<ValidatedSelection>],
+    ) -> Vec<WithLocation<String>> {
+        let mut violations = vec![];
+        visit_selection_set(selection_set, &mut |selection| {
+            let (name, location, deprecation_reason) = match selection {
+                SelectionType::Scalar(scalar_selection) => match scalar_selection.associated_data {
+                    DefinitionLocation::Server(server_scalar_selectable_id) => (
+                        scalar_selection.name.item.to_string(),
+                        scalar_selection.name.location,
+                        schema
+                            .server_scalar_selectable(server_scalar_selectable_id)
+                            .deprecation_reason,
+                    ),
+                    DefinitionLocation::Client(_) => return,
+                },
+                SelectionType::Object(object_selection) => match object_selection.associated_data {
+                    DefinitionLocation::Server(server_object_selectable_id) => (
+                        object_selection.name.item.to_string(),
+                        object_selection.name.location,
+                        schema
+                            .server_object_selectable(server_object_selectable_id)
+                            .deprecation_reason,
+                    ),
+                    DefinitionLocation::Client(_) => return,
+                },
+            };
+            if let Some(deprecation_reason) = deprecation_reason {
+                violations.push(WithLocation::new(
+                    format!("The field `{name}` is deprecated: {deprecation_reason}"),
+                    location,
+                ));
+            }
+        });
+        violations
+    }
+}
+
+/// Mismatches between the name of the const export and the name of the client field
+/// (e.g. `field User.Avatar` exported as `UserAvatar`) make it hard to find the
+/// resolver of a client field.
+struct ExportNameMatchesFieldName;
+
+impl<TNetworkProtocol: NetworkProtocol> ValidationRule<TNetworkProtocol>
+    for ExportNameMatchesFieldName
+{
+    fn name(&self) -> &'static str {
+        "export_name_matches_field_name"
+    }
+
+    fn validate_client_type(
+        &self,
+        _schema: &Schema<TNetworkProtocol>,
+        client_type: SelectionType<
+            &ClientScalarSelectable<TNetworkProtocol>,
+            &ClientObjectSelectable<TNetworkProtocol>,
+        >,
+        _selection_set: &[WithSpan<ValidatedSelection>],
+    ) -> Vec<WithLocation<String>> {
+        let (const_export_name, text_source) = match client_type {
+            SelectionType::Scalar(client_field) => match client_field.variant {
+                ClientFieldVariant::UserWritten(info) => (info.const_export_name, info.text_source),
+                _ => return vec![],
+            },
+            SelectionType::Object(client_pointer) => match client_pointer.variant {
+                ClientPointerVariant::UserWritten => (
+                    client_pointer.info.const_export_name,
+                    client_pointer.info.text_source,
+                ),
+                // Node pointers need not be exported.
+                ClientPointerVariant::Node => return vec![],
+            },
+        };
+        let type_and_field = client_type.type_and_field();
+        if const_export_name.lookup() == type_and_field.field_name.lookup() {
+            return vec![];
+        }
+        vec![WithLocation::new(
+            format!(
+                "`{}.{}` is exported as `{const_export_name}`. Rename the export to `{}`.",
+                type_and_field.type_name, type_and_field.field_name, type_and_field.field_name
+            ),
+            Location::new(text_source, Span::new(0, 0)),
+        )]
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum ValidationRuleError {
+    #[error("{message} (validation rule `{rule_name}`)")]
+    RuleViolated {
+        rule_name: &'static str,
+        message: String,
+    },
+}
+
+impl HasErrorCode for ValidationRuleError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ValidationRuleError::RuleViolated { .. } => error_codes::IS0608,
+        }
+    }
+}
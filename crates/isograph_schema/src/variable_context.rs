@@ -1,9 +1,11 @@
 use std::{collections::HashMap, fmt::Debug};
 
-use common_lang_types::{SelectableName, VariableName, WithLocation, WithSpan};
+use common_lang_types::{Location, SelectableName, VariableName, WithLocation, WithSpan};
+use graphql_lang_types::NameValuePair;
+use intern::Lookup;
 use isograph_lang_types::{
     ArgumentKeyAndValue, ConstantValue, NonConstantValue, ScalarSelectionDirectiveSet,
-    SelectionFieldArgument, SelectionType,
+    SelectionCondition, SelectionFieldArgument, SelectionType,
 };
 
 use crate::{
@@ -188,14 +190,72 @@ pub fn transform_arguments_with_child_context(
     arguments: impl Iterator<Item = ArgumentKeyAndValue>,
     transformed_child_variable_context: &VariableContext,
 ) -> Vec<ArgumentKeyAndValue> {
-    arguments
+    let mut arguments = arguments
         .map(|arg| {
-            transform_selection_field_argument_into_merged_arg_with_child_context(
+            let arg = transform_selection_field_argument_into_merged_arg_with_child_context(
                 arg,
                 transformed_child_variable_context,
-            )
+            );
+            ArgumentKeyAndValue {
+                key: arg.key,
+                value: canonicalize_value(arg.value),
+            }
+        })
+        .collect::<Vec<_>>();
+    // Arguments are sorted, so that selections of the same field with the same arguments
+    // (in any order) are merged, and are stored under the same key.
+    arguments
+        .sort_by(|argument_1, argument_2| argument_1.key.lookup().cmp(argument_2.key.lookup()));
+    arguments
+}
+
+/// Sort the fields of object values, and drop the locations of list items and object
+/// fields, so that equal values compare as equal.
+fn canonicalize_value(value: NonConstantValue) -> NonConstantValue {
+    match value {
+        NonConstantValue::List(items) => NonConstantValue::List(
+            items
+                .into_iter()
+                .map(|item| WithLocation::new(canonicalize_value(item.item), Location::Generated))
+                .collect(),
+        ),
+        NonConstantValue::Object(fields) => {
+            let mut fields = fields
+                .into_iter()
+                .map(|field| NameValuePair {
+                    name: WithLocation::new(field.name.item, Location::Generated),
+                    value: WithLocation::new(
+                        canonicalize_value(field.value.item),
+                        Location::Generated,
+                    ),
+                })
+                .collect::<Vec<_>>();
+            fields.sort_by(|field_1, field_2| {
+                field_1.name.item.lookup().cmp(field_2.name.item.lookup())
+            });
+            NonConstantValue::Object(fields)
+        }
+        value => value,
+    }
+}
+
+pub fn transform_conditions_with_child_context(
+    conditions: impl Iterator<Item = SelectionCondition>,
+    transformed_child_variable_context: &VariableContext,
+) -> Vec<SelectionCondition> {
+    conditions
+        .map(|condition| SelectionCondition {
+            kind: condition.kind,
+            value: match condition.value {
+                NonConstantValue::Variable(variable_name) => transformed_child_variable_context
+                    .0
+                    .get(&variable_name)
+                    .cloned()
+                    .unwrap_or(NonConstantValue::Null),
+                value => value,
+            },
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
 pub fn transform_name_and_arguments_with_child_variable_context(
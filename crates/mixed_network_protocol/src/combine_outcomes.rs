@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use graphql_lang_types::GraphQLTypeAnnotation;
+use intern::{
+    string_key::{Intern, StringKey},
+    Lookup,
+};
+use isograph_schema::{
+    NetworkProtocol, ProcessObjectTypeDefinitionOutcome, ProcessTypeSystemDocumentOutcome,
+    ServerObjectEntity, ServerScalarEntity,
+};
+
+use crate::{MixedNetworkProtocol, MixedSchemaObjectAssociatedData};
+
+/// Convert the outcome of processing documents with another network protocol, so
+/// that it can be added to the mixed schema.
+pub(crate) fn convert_outcome<TNetworkProtocol: NetworkProtocol>(
+    outcome: ProcessTypeSystemDocumentOutcome<TNetworkProtocol>,
+    convert_associated_data: impl Fn(
+        TNetworkProtocol::SchemaObjectAssociatedData,
+    ) -> MixedSchemaObjectAssociatedData,
+) -> ProcessTypeSystemDocumentOutcome<MixedNetworkProtocol> {
+    ProcessTypeSystemDocumentOutcome {
+        scalars: outcome
+            .scalars
+            .into_iter()
+            .map(|(scalar, location)| {
+                (
+                    ServerScalarEntity {
+                        description: scalar.description,
+                        name: scalar.name,
+                        javascript_name: scalar.javascript_name,
+                        specified_by_url: scalar.specified_by_url,
+                        output_format: std::marker::PhantomData,
+                    },
+                    location,
+                )
+            })
+            .collect(),
+        objects: outcome
+            .objects
+            .into_iter()
+            .map(|(object, location)| {
+                let server_object_entity = object.server_object_entity;
+                (
+                    ProcessObjectTypeDefinitionOutcome {
+                        encountered_root_kind: object.encountered_root_kind,
+                        server_object_entity: ServerObjectEntity {
+                            description: server_object_entity.description,
+                            name: server_object_entity.name,
+                            concrete_type: server_object_entity.concrete_type,
+                            is_input_object: server_object_entity.is_input_object,
+                            directives: server_object_entity.directives,
+                            output_associated_data: convert_associated_data(
+                                server_object_entity.output_associated_data,
+                            ),
+                        },
+                        fields_to_insert: object.fields_to_insert,
+                        expose_as_fields_to_insert: object.expose_as_fields_to_insert,
+                    },
+                    location,
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Add the types defined by another protocol's documents to the outcome. Types
+/// are prefixed with the namespace (e.g. Pet becomes RestPet), and root fields are
+/// added to the existing root types. Root fields that have the same name as an
+/// existing field are reported by the compiler as duplicates.
+pub(crate) fn add_namespaced_outcome(
+    outcome: &mut ProcessTypeSystemDocumentOutcome<MixedNetworkProtocol>,
+    other: ProcessTypeSystemDocumentOutcome<MixedNetworkProtocol>,
+    namespace: &str,
+) {
+    let namespaced_names = other
+        .scalars
+        .iter()
+        .map(|(scalar, _)| scalar.name.item.lookup())
+        .chain(
+            other
+                .objects
+                .iter()
+                .filter(|(object, _)| object.encountered_root_kind.is_none())
+                .map(|(object, _)| object.server_object_entity.name.lookup()),
+        )
+        .map(|name| (name, format!("{namespace}{name}")))
+        .collect::<HashMap<_, _>>();
+    let namespaced = |name: &'static str| match namespaced_names.get(name) {
+        Some(namespaced_name) => namespaced_name.intern(),
+        None => name.intern(),
+    };
+
+    for (mut scalar, location) in other.scalars {
+        scalar.name.item = namespaced(scalar.name.item.lookup()).into();
+        outcome.scalars.push((scalar, location));
+    }
+
+    for (mut object, location) in other.objects {
+        for field in object.fields_to_insert.iter_mut() {
+            field.item.type_ = namespaced_type(&field.item.type_, &namespaced);
+            for argument in field.item.arguments.iter_mut() {
+                argument.item.type_ = namespaced_type(&argument.item.type_, &namespaced);
+            }
+        }
+
+        let existing_root_object = object.encountered_root_kind.and_then(|root_kind| {
+            outcome.objects.iter_mut().find(|(existing_object, _)| {
+                existing_object.encountered_root_kind == Some(root_kind)
+            })
+        });
+        match existing_root_object {
+            Some((existing_root_object, _)) => {
+                existing_root_object
+                    .fields_to_insert
+                    .extend(object.fields_to_insert);
+                let associated_data = object.server_object_entity.output_associated_data;
+                let existing_associated_data = &mut existing_root_object
+                    .server_object_entity
+                    .output_associated_data;
+                existing_associated_data
+                    .rest_operations
+                    .extend(associated_data.rest_operations);
+                existing_associated_data
+                    .grpc_methods
+                    .extend(associated_data.grpc_methods);
+            }
+            None => {
+                if object.encountered_root_kind.is_none() {
+                    let name = namespaced(object.server_object_entity.name.lookup()).into();
+                    object.server_object_entity.name = name;
+                    object.server_object_entity.concrete_type = Some(name);
+                }
+                outcome.objects.push((object, location));
+            }
+        }
+    }
+}
+
+fn namespaced_type<TName: Lookup + From<StringKey> + Copy>(
+    type_: &GraphQLTypeAnnotation<TName>,
+    namespaced: &impl Fn(&'static str) -> StringKey,
+) -> GraphQLTypeAnnotation<TName> {
+    type_.clone().map(|name| namespaced(name.lookup()).into())
+}
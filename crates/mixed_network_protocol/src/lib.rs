@@ -0,0 +1,5 @@
+mod combine_outcomes;
+mod mixed_network_protocol;
+mod query_text;
+
+pub use mixed_network_protocol::*;
@@ -0,0 +1,146 @@
+use std::{collections::BTreeMap, error::Error};
+
+use common_lang_types::{QueryOperationName, QueryText, RelativePathToSourceFile, SelectableName};
+use graphql_network_protocol::{GraphQLNetworkProtocol, GraphQLSchemaObjectAssociatedData};
+use grpc_network_protocol::{GrpcMethod, GrpcNetworkProtocol};
+use intern::Lookup;
+use isograph_config::CompilerConfigOptions;
+use isograph_lang_types::SchemaSource;
+use isograph_schema::{
+    MergedSelectionMap, NetworkProtocol, ProcessTypeSystemDocumentOutcome, RootOperationName,
+    Schema, ValidatedVariableDefinition,
+};
+use pico::{Database, SourceId};
+use rest_network_protocol::{RestNetworkProtocol, RestOperation};
+
+use crate::{
+    combine_outcomes::{add_namespaced_outcome, convert_outcome},
+    query_text::generate_mixed_query_text,
+};
+
+/// Types defined in OpenAPI documents are prefixed with this, e.g. `RestPet`.
+const REST_NAMESPACE: &str = "Rest";
+/// Types defined in proto files are prefixed with this, e.g. `GrpcPet`.
+const GRPC_NAMESPACE: &str = "Grpc";
+
+/// A network protocol for projects whose schema is a GraphQL schema, supplemented
+/// by REST APIs (described by OpenAPI documents) and gRPC services (described by
+/// proto files.) Each root field is fetched with the protocol of the document that
+/// defines it.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
+pub struct MixedNetworkProtocol {}
+
+impl NetworkProtocol for MixedNetworkProtocol {
+    type Sources = (
+        SourceId<SchemaSource>,
+        BTreeMap<RelativePathToSourceFile, SourceId<SchemaSource>>,
+    );
+
+    type SchemaObjectAssociatedData = MixedSchemaObjectAssociatedData;
+
+    // The schema is a GraphQL schema. Schema extensions are OpenAPI documents (if
+    // they end in .json), proto files (if they end in .proto), or GraphQL schema
+    // extensions. The types defined by OpenAPI documents and proto files are
+    // namespaced, so that they do not collide with GraphQL types, and their root
+    // fields are added to the GraphQL root types.
+    fn parse_and_process_type_system_documents(
+        db: &Database,
+        sources: &Self::Sources,
+        options: &CompilerConfigOptions,
+    ) -> Result<ProcessTypeSystemDocumentOutcome<MixedNetworkProtocol>, Box<dyn Error>> {
+        let (schema_source_id, schema_extension_sources) = sources;
+
+        let mut graphql_extension_sources = BTreeMap::new();
+        let mut rest_sources = BTreeMap::new();
+        let mut grpc_sources = BTreeMap::new();
+        for (path, source_id) in schema_extension_sources {
+            let sources = if path.lookup().ends_with(".json") {
+                &mut rest_sources
+            } else if path.lookup().ends_with(".proto") {
+                &mut grpc_sources
+            } else {
+                &mut graphql_extension_sources
+            };
+            sources.insert(*path, *source_id);
+        }
+
+        let mut outcome = convert_outcome(
+            GraphQLNetworkProtocol::parse_and_process_type_system_documents(
+                db,
+                &(*schema_source_id, graphql_extension_sources),
+                options,
+            )?,
+            |graphql| MixedSchemaObjectAssociatedData {
+                graphql: Some(graphql),
+                ..Default::default()
+            },
+        );
+
+        if let Some((_, first_rest_source_id)) = rest_sources.pop_first() {
+            let rest_outcome = RestNetworkProtocol::parse_and_process_type_system_documents(
+                db,
+                &(first_rest_source_id, rest_sources),
+                options,
+            )?;
+            add_namespaced_outcome(
+                &mut outcome,
+                convert_outcome(rest_outcome, |rest| MixedSchemaObjectAssociatedData {
+                    rest_operations: rest.operations,
+                    ..Default::default()
+                }),
+                REST_NAMESPACE,
+            );
+        }
+
+        if let Some((_, first_grpc_source_id)) = grpc_sources.pop_first() {
+            let grpc_outcome = GrpcNetworkProtocol::parse_and_process_type_system_documents(
+                db,
+                &(first_grpc_source_id, grpc_sources),
+                options,
+            )?;
+            add_namespaced_outcome(
+                &mut outcome,
+                convert_outcome(grpc_outcome, |grpc| MixedSchemaObjectAssociatedData {
+                    grpc_methods: grpc.methods,
+                    ..Default::default()
+                }),
+                GRPC_NAMESPACE,
+            );
+        }
+
+        Ok(outcome)
+    }
+
+    // Entrypoints whose root fields are all fetched with the same protocol have the
+    // same query text as they would with that protocol. Other entrypoints have a
+    // JSON query text that contains the query text for each protocol, which is
+    // interpreted by the network function (e.g. createMixedNetworkFunction in
+    // @isograph/react.)
+    fn generate_query_text<'a>(
+        query_name: QueryOperationName,
+        schema: &Schema<Self>,
+        selection_map: &MergedSelectionMap,
+        query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
+        root_operation_name: &RootOperationName,
+    ) -> QueryText {
+        generate_mixed_query_text(
+            query_name,
+            schema,
+            selection_map,
+            query_variables,
+            root_operation_name,
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MixedSchemaObjectAssociatedData {
+    /// None for types defined by OpenAPI documents and proto files.
+    pub graphql: Option<GraphQLSchemaObjectAssociatedData>,
+    /// For the root types, the fields that are fetched with REST. Empty for all
+    /// other types.
+    pub rest_operations: BTreeMap<SelectableName, RestOperation>,
+    /// For the root types, the fields that are fetched with gRPC. Empty for all
+    /// other types.
+    pub grpc_methods: BTreeMap<SelectableName, GrpcMethod>,
+}
@@ -0,0 +1,122 @@
+use common_lang_types::{QueryOperationName, QueryText, SelectableName};
+use graphql_network_protocol::generate_graphql_query_text;
+use grpc_network_protocol::generate_grpc_requests;
+use isograph_schema::{
+    get_reachable_variables, MergedSelectionMap, MergedServerSelection, RootOperationName, Schema,
+    ValidatedVariableDefinition,
+};
+use rest_network_protocol::generate_rest_requests;
+use serde_json::{json, Value};
+
+use crate::MixedNetworkProtocol;
+
+/// Generate the query text of an entrypoint. If its root fields are fetched with
+/// more than one protocol, the query text is a JSON object with the query text for
+/// each protocol, e.g.:
+///
+/// ```json
+/// {"kind":"MixedRequests","graphql":"query PetDetail {\n  viewer {...","rest":
+/// {"kind":"RestRequests","requests":[...]},"grpc":null}
+/// ```
+pub(crate) fn generate_mixed_query_text<'a>(
+    query_name: QueryOperationName,
+    schema: &Schema<MixedNetworkProtocol>,
+    selection_map: &MergedSelectionMap,
+    query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
+    root_operation_name: &RootOperationName,
+) -> QueryText {
+    let root_object_entity_id = *schema
+        .fetchable_types
+        .iter()
+        .find(|(_, name)| name.0 == root_operation_name.0)
+        .expect("Expected root operation to be fetchable. This is indicative of a bug in Isograph.")
+        .0;
+    let associated_data = &schema
+        .server_entity_data
+        .server_object_entity(root_object_entity_id)
+        .output_associated_data;
+
+    let mut graphql_selection_map = MergedSelectionMap::new();
+    let mut rest_selection_map = MergedSelectionMap::new();
+    let mut grpc_selection_map = MergedSelectionMap::new();
+    for (normalization_key, selection) in selection_map {
+        let name: SelectableName = match selection {
+            MergedServerSelection::ScalarField(scalar_field) => scalar_field.name.into(),
+            MergedServerSelection::LinkedField(linked_field) => linked_field.name.into(),
+            MergedServerSelection::InlineFragment(_) => {
+                panic!(
+                    "Expected root selections not to be inline fragments. \
+                    This is indicative of a bug in Isograph."
+                )
+            }
+        };
+        let protocol_selection_map = if associated_data.rest_operations.contains_key(&name) {
+            &mut rest_selection_map
+        } else if associated_data.grpc_methods.contains_key(&name) {
+            &mut grpc_selection_map
+        } else {
+            &mut graphql_selection_map
+        };
+        protocol_selection_map.insert(normalization_key.clone(), selection.clone());
+    }
+
+    let rest_requests = (!rest_selection_map.is_empty())
+        .then(|| generate_rest_requests(&rest_selection_map, &associated_data.rest_operations));
+    let grpc_requests = (!grpc_selection_map.is_empty()).then(|| {
+        generate_grpc_requests(
+            schema,
+            root_object_entity_id,
+            &grpc_selection_map,
+            &associated_data.grpc_methods,
+        )
+    });
+
+    let request_text = match (
+        graphql_selection_map.is_empty(),
+        rest_requests,
+        grpc_requests,
+    ) {
+        (_, None, None) => {
+            return generate_graphql_query_text(
+                query_name,
+                schema,
+                selection_map,
+                query_variables,
+                root_operation_name,
+            );
+        }
+        (true, Some(requests), None) | (true, None, Some(requests)) => requests,
+        (_, rest_requests, grpc_requests) => {
+            let graphql_query_text = (!graphql_selection_map.is_empty()).then(|| {
+                // Only declare the variables that are used by the GraphQL fields, since
+                // GraphQL servers reject operations with unused variables.
+                let reachable_variables = get_reachable_variables(&graphql_selection_map);
+                let QueryText(query_text) = generate_graphql_query_text(
+                    query_name,
+                    schema,
+                    &graphql_selection_map,
+                    query_variables
+                        .filter(move |variable| reachable_variables.contains(&variable.name.item)),
+                    root_operation_name,
+                );
+                // The GraphQL query text contains escaped newlines, since it is printed
+                // within a string literal. They are escaped again below.
+                query_text.replace("\\\n", "\n")
+            });
+            json!({
+                "kind": "MixedRequests",
+                "graphql": graphql_query_text,
+                "rest": rest_requests.unwrap_or(Value::Null),
+                "grpc": grpc_requests.unwrap_or(Value::Null),
+            })
+        }
+    };
+
+    // The request text is printed within a single-quoted string literal.
+    QueryText(
+        request_text
+            .to_string()
+            .replace('\\', "\\\\")
+            .replace('\'', "\\'"),
+    )
+}
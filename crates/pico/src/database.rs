@@ -2,8 +2,10 @@ use std::{any::Any, hash::Hash, num::NonZeroUsize};
 
 use crate::{
     dependency::{Dependency, DependencyStack, NodeKind},
+    durability::{Durability, LastChanged},
     dyn_eq::DynEq,
     epoch::Epoch,
+    history::{History, HistoryNode},
     index::Index,
     intern::{Key, ParamId},
     macro_fns::{get_param, init_param_vec, intern_borrowed_param, intern_owned_param},
@@ -23,6 +25,9 @@ pub struct Database {
     pub(crate) top_level_calls: BoxcarVec<DerivedNodeId>,
     pub(crate) top_level_call_lru_cache: LruCache<DerivedNodeId, ()>,
     pub(crate) retained_calls: DashMap<DerivedNodeId, usize>,
+    /// If set, a warning is logged whenever a derived node is created with more
+    /// dependencies than this.
+    pub(crate) wide_node_threshold: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -35,6 +40,9 @@ pub(crate) struct DatabaseStorage {
     pub(crate) source_nodes: BoxcarVec<Option<SourceNode>>,
     pub(crate) params: BoxcarVec<Box<dyn Any>>,
     pub(crate) current_epoch: Epoch,
+    pub(crate) last_changed: LastChanged,
+    /// If set, the values of nodes that have been replaced are retained.
+    pub(crate) history: Option<History>,
 }
 
 static DEFAULT_CAPACITY: usize = 10_000;
@@ -57,17 +65,92 @@ impl Database {
                 params: BoxcarVec::new(),
 
                 current_epoch: Epoch::new(),
+                last_changed: LastChanged::default(),
+                history: None,
             },
             top_level_calls: BoxcarVec::new(),
             top_level_call_lru_cache: LruCache::new(capacity),
             retained_calls: DashMap::new(),
+            wide_node_threshold: None,
         }
     }
 
+    /// Log a warning whenever a memoized function reads more than `threshold`
+    /// sources and memoized functions. Verifying that such a derived node can be
+    /// reused is slow, since each dependency must be checked.
+    pub fn set_wide_node_threshold(&mut self, threshold: Option<usize>) {
+        self.wide_node_threshold = threshold;
+    }
+
+    /// The derived nodes with more than `threshold` dependencies, and their number
+    /// of dependencies, sorted by decreasing number of dependencies.
+    pub fn wide_derived_nodes(&self, threshold: usize) -> Vec<(DerivedNodeId, usize)> {
+        let mut wide_derived_nodes = self
+            .storage
+            .derived_node_id_to_revision
+            .iter()
+            .filter_map(|entry| {
+                let derived_node = self.storage.derived_nodes.get(entry.value().index.idx)?;
+                let dependency_count = derived_node.dependencies.len();
+                (dependency_count > threshold).then_some((*entry.key(), dependency_count))
+            })
+            .collect::<Vec<_>>();
+        wide_derived_nodes
+            .sort_by_key(|(_, dependency_count)| std::cmp::Reverse(*dependency_count));
+        wide_derived_nodes
+    }
+
+    pub fn current_epoch(&self) -> Epoch {
+        self.storage.current_epoch
+    }
+
+    /// Retain the values of source and derived nodes for the last `epochs` epochs,
+    /// so that they can be retrieved with [`Database::value_at`]. Passing `None`
+    /// discards the retained values.
+    pub fn set_history_epochs(&mut self, epochs: Option<usize>) {
+        self.storage.history = epochs.map(History::new);
+    }
+
+    /// The value that `node` had at `epoch`. Returns `None` if the value is not known,
+    /// e.g. because history was not enabled at that epoch, the value was discarded
+    /// (which happens during garbage collection), or a derived node was not invoked
+    /// at that epoch.
+    ///
+    /// This does not register a dependency, and is meant for debugging, e.g. to
+    /// find out why the value of a memoized function changed between two epochs.
+    pub fn value_at<T: 'static>(&self, node: impl Into<HistoryNode>, epoch: Epoch) -> Option<&T> {
+        let node = node.into();
+        if epoch > self.storage.current_epoch {
+            return None;
+        }
+        let current_value = match node {
+            HistoryNode::Source(key) => self
+                .storage
+                .get_source_node(key)
+                .filter(|source_node| source_node.time_updated <= epoch)
+                .map(|source_node| source_node.value.as_ref()),
+            HistoryNode::Derived(derived_node_id) => self
+                .storage
+                .get_derived_node_revision(derived_node_id)
+                .filter(|rev| rev.time_updated <= epoch && epoch <= rev.time_verified)
+                .and_then(|_| self.storage.get_derived_node(derived_node_id))
+                .map(|derived_node| derived_node.value.as_ref()),
+        };
+        let value = match current_value {
+            Some(value) => value,
+            None => {
+                let entry = self.storage.history.as_ref()?.entry_at(node, epoch)?;
+                self.storage.historical_value(entry.value)
+            }
+        };
+        value.as_any().downcast_ref::<T>()
+    }
+
     pub(crate) fn register_dependency_in_parent_memoized_fn(
         &self,
         node: NodeKind,
         time_updated: Epoch,
+        durability: Durability,
     ) {
         self.dependency_stack.push_if_not_empty(
             Dependency {
@@ -75,6 +158,7 @@ impl Database {
                 time_verified_or_updated: self.storage.current_epoch,
             },
             time_updated,
+            durability,
         );
     }
 
@@ -86,6 +170,7 @@ impl Database {
         self.register_dependency_in_parent_memoized_fn(
             NodeKind::Source(id.key),
             source_node.time_updated,
+            source_node.durability,
         );
         source_node.value.as_any().downcast_ref::<T>().expect(
             "unexpected struct type. \
@@ -94,8 +179,18 @@ impl Database {
     }
 
     pub fn set<T: Source + DynEq>(&mut self, source: T) -> SourceId<T> {
+        self.set_with_durability(source, Durability::Low)
+    }
+
+    /// Like [`Database::set`], but for sources that are expected to change more
+    /// rarely (or more often) than others. See [`Durability`].
+    pub fn set_with_durability<T: Source + DynEq>(
+        &mut self,
+        source: T,
+        durability: Durability,
+    ) -> SourceId<T> {
         self.assert_empty_dependency_stack();
-        self.storage.set_source(source)
+        self.storage.set_source(source, durability)
     }
 
     pub fn remove<T>(&mut self, id: SourceId<T>) {
@@ -181,6 +276,18 @@ impl DatabaseStorage {
         rev.time_verified = self.current_epoch;
     }
 
+    pub(crate) fn set_derived_node_durability(
+        &self,
+        derived_node_id: DerivedNodeId,
+        durability: Durability,
+    ) {
+        let mut rev = self
+            .derived_node_id_to_revision
+            .get_mut(&derived_node_id)
+            .unwrap();
+        rev.durability = durability;
+    }
+
     pub(crate) fn get_derived_node_revision(
         &self,
         derived_node_id: DerivedNodeId,
@@ -195,6 +302,7 @@ impl DatabaseStorage {
         derived_node_id: DerivedNodeId,
         time_updated: Epoch,
         time_verified: Epoch,
+        durability: Durability,
         index: Index<DerivedNodeId>,
     ) {
         self.derived_node_id_to_revision.insert(
@@ -202,6 +310,7 @@ impl DatabaseStorage {
             DerivedNodeRevision {
                 time_updated,
                 time_verified,
+                durability,
                 index,
             },
         );
@@ -227,8 +336,12 @@ impl DatabaseStorage {
     }
 
     /// Sets a source in the database. If there is an existing item and it does not equal
-    /// the new source, increment the current epoch.
-    pub fn set_source<T: Source + DynEq>(&mut self, source: T) -> SourceId<T> {
+    /// the new source (or has a different durability), increment the current epoch.
+    pub fn set_source<T: Source + DynEq>(
+        &mut self,
+        source: T,
+        durability: Durability,
+    ) -> SourceId<T> {
         let id = SourceId::new(&source);
         match self.source_node_key_to_index.entry(id.key) {
             Entry::Occupied(occupied_entry) => {
@@ -244,14 +357,31 @@ impl DatabaseStorage {
                         "indexes should always point to a non-empty source node. \
                         This is indicative of a bug in Pico.",
                     );
-                if !source_node.value.dyn_eq(&source) {
+                if !source_node.value.dyn_eq(&source) || source_node.durability != durability {
                     // We cannot call self.increment_epoch() because that borrows
                     // the entire struct, but self.source_nodes is already borrowed
                     let next_epoch = self.current_epoch.increment();
-                    *source_node = SourceNode {
-                        time_updated: next_epoch,
-                        value: Box::new(source),
-                    };
+                    // Derived nodes that read the previous value have at most its durability.
+                    self.last_changed.record(
+                        std::cmp::max(source_node.durability, durability),
+                        next_epoch,
+                    );
+                    let prev_source_node = std::mem::replace(
+                        source_node,
+                        SourceNode {
+                            time_updated: next_epoch,
+                            durability,
+                            value: Box::new(source),
+                        },
+                    );
+                    if let Some(history) = &self.history {
+                        history.record_owned(
+                            HistoryNode::Source(id.key),
+                            prev_source_node.time_updated,
+                            next_epoch,
+                            prev_source_node.value,
+                        );
+                    }
                 } else {
                     source_node.time_updated = self.current_epoch;
                 }
@@ -259,6 +389,7 @@ impl DatabaseStorage {
             Entry::Vacant(vacant_entry) => {
                 let index = self.insert_source_node(SourceNode {
                     time_updated: self.current_epoch,
+                    durability,
                     value: Box::new(source),
                 });
                 vacant_entry.insert(index);
@@ -269,14 +400,27 @@ impl DatabaseStorage {
 
     pub fn remove_source<T>(&mut self, id: SourceId<T>) {
         if let Some((_, index)) = self.source_node_key_to_index.remove(&id.key) {
-            self.current_epoch.increment();
-            self.source_nodes
+            let next_epoch = self.current_epoch.increment();
+            let prev_source_node = self
+                .source_nodes
                 .get_mut(index.idx)
                 .expect(
                     "indexes should always be valid. \
                     This is indicative of a bug in Pico.",
                 )
                 .take();
+            if let Some(prev_source_node) = &prev_source_node {
+                self.last_changed
+                    .record(prev_source_node.durability, next_epoch);
+            }
+            if let (Some(history), Some(prev_source_node)) = (&self.history, prev_source_node) {
+                history.record_owned(
+                    HistoryNode::Source(id.key),
+                    prev_source_node.time_updated,
+                    next_epoch,
+                    prev_source_node.value,
+                );
+            }
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::cell::RefCell;
 
-use crate::{derived_node::DerivedNodeId, epoch::Epoch, intern::Key};
+use crate::{derived_node::DerivedNodeId, durability::Durability, epoch::Epoch, intern::Key};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Dependency {
@@ -12,12 +12,16 @@ pub struct Dependency {
 pub enum NodeKind {
     Source(Key),
     Derived(DerivedNodeId),
+    /// The revision of the database, i.e. the current epoch, which changes whenever
+    /// a source changes. Coarse derived nodes depend only on this.
+    Revision,
 }
 
 #[derive(Debug)]
 pub struct TrackedDependencies {
     pub dependencies: Vec<Dependency>,
     pub max_time_updated: Epoch,
+    pub min_durability: Durability,
     pub derived_node_id: DerivedNodeId,
 }
 
@@ -26,12 +30,15 @@ impl TrackedDependencies {
         Self {
             dependencies: vec![],
             max_time_updated: Epoch::new(),
+            // A node that reads no sources can never change.
+            min_durability: Durability::High,
             derived_node_id,
         }
     }
 
-    pub fn push(&mut self, dependency: Dependency, time_updated: Epoch) {
+    pub fn push(&mut self, dependency: Dependency, time_updated: Epoch, durability: Durability) {
         self.max_time_updated = std::cmp::max(time_updated, self.max_time_updated);
+        self.min_durability = std::cmp::min(durability, self.min_durability);
         self.dependencies.push(dependency);
     }
 }
@@ -72,9 +79,14 @@ impl DependencyStack {
             .expect("Dependency stack should not be empty. Leave must be called after enter.")
     }
 
-    pub fn push_if_not_empty(&self, dependency: Dependency, time_updated: Epoch) {
+    pub fn push_if_not_empty(
+        &self,
+        dependency: Dependency,
+        time_updated: Epoch,
+        durability: Durability,
+    ) {
         if let Some(entry) = self.0.borrow_mut().last_mut() {
-            entry.push(dependency, time_updated);
+            entry.push(dependency, time_updated, durability);
         } else {
             // If the dependency stack is empty, this function call is the outermost invocation
             // (i.e., the user directly called the memoized function). So, there's no parent
@@ -6,6 +6,7 @@ use tinyvec::ArrayVec;
 
 use crate::{
     dependency::Dependency,
+    durability::Durability,
     dyn_eq::DynEq,
     epoch::Epoch,
     index::Index,
@@ -36,10 +37,26 @@ impl From<ParamId> for DerivedNodeId {
 }
 
 #[derive(Debug, Copy, Clone)]
-pub struct InnerFn(pub fn(&Database, DerivedNodeId) -> Option<Box<dyn DynEq>>);
+pub struct InnerFn {
+    pub(crate) call: fn(&Database, DerivedNodeId) -> Option<Box<dyn DynEq>>,
+    /// If true, the derived node depends on the revision of the database, instead of
+    /// on the sources and derived nodes that it reads. See `#[memo(coarse)]`.
+    pub(crate) coarse: bool,
+}
+
 impl InnerFn {
     pub fn new(inner_fn: fn(&Database, DerivedNodeId) -> Option<Box<dyn DynEq>>) -> Self {
-        InnerFn(inner_fn)
+        InnerFn {
+            call: inner_fn,
+            coarse: false,
+        }
+    }
+
+    pub fn new_coarse(inner_fn: fn(&Database, DerivedNodeId) -> Option<Box<dyn DynEq>>) -> Self {
+        InnerFn {
+            call: inner_fn,
+            coarse: true,
+        }
     }
 }
 
@@ -62,5 +79,7 @@ impl fmt::Debug for DerivedNode {
 pub struct DerivedNodeRevision {
     pub time_updated: Epoch,
     pub time_verified: Epoch,
+    /// The durability of the least durable source that this node read.
+    pub durability: Durability,
     pub index: Index<DerivedNodeId>,
 }
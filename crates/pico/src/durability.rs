@@ -0,0 +1,45 @@
+use crate::epoch::Epoch;
+
+/// How rarely a source is expected to change.
+///
+/// Each derived node has the durability of the least durable source that it
+/// (transitively) reads. If no source of at least that durability has changed
+/// since the derived node was last verified, it is reused without checking each
+/// of its dependencies. So, when only low durability sources change, derived
+/// nodes that read only high durability sources are verified in constant time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Durability {
+    /// e.g. files that are edited frequently
+    #[default]
+    Low,
+    /// e.g. a schema that rarely changes
+    High,
+}
+
+impl Durability {
+    const COUNT: usize = 2;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The last epoch in which a source of each durability changed.
+#[derive(Debug, Default)]
+pub(crate) struct LastChanged([Epoch; Durability::COUNT]);
+
+impl LastChanged {
+    /// Record that a source with the given durability changed. This also
+    /// invalidates every less durable derived node.
+    pub(crate) fn record(&mut self, durability: Durability, epoch: Epoch) {
+        for last_changed in &mut self.0[..=durability.index()] {
+            *last_changed = epoch;
+        }
+    }
+
+    /// Whether a source that a derived node with the given durability may have
+    /// read has changed since `epoch`.
+    pub(crate) fn changed_since(&self, durability: Durability, epoch: Epoch) -> bool {
+        self.0[durability.index()] > epoch
+    }
+}
@@ -1,11 +1,14 @@
 use dashmap::Entry;
+use tracing::warn;
 
 use crate::{
     database::Database,
-    dependency::{NodeKind, TrackedDependencies},
+    dependency::{Dependency, NodeKind, TrackedDependencies},
     derived_node::{DerivedNode, DerivedNodeId},
+    durability::Durability,
     dyn_eq::DynEq,
     epoch::Epoch,
+    history::{HistoricalValue, HistoryNode},
     intern::Key,
     InnerFn,
 };
@@ -36,6 +39,8 @@ pub enum DidRecalculate {
 ///   - This is done by checking:
 ///     - whether the dependency was
 ///       [verified in the current epoch][crate::DatabaseStorage::node_verified_in_current_epoch], or
+///     - whether any source with at least the [`Durability`][crate::Durability] of the
+///       [`DerivedNode`] has changed since it was last verified, or
 ///     - whether [any dependency has changed][any_dependency_changed]
 ///       since this [`DerivedNode`] was last verified.
 ///   - Memoized functions are assumed to be pure functions of their params
@@ -77,8 +82,21 @@ impl Database {
                         DidRecalculate::ReusedMemoizedValue,
                     )
                 } else {
+                    let revision = self
+                        .storage
+                        .get_derived_node_revision(derived_node_id)
+                        .expect("Expected revision to exist. This is indicative of a bug in Pico.");
                     self.storage.verify_derived_node(derived_node_id);
-                    if any_dependency_changed(self, derived_node) {
+                    if !self
+                        .storage
+                        .last_changed
+                        .changed_since(revision.durability, revision.time_verified)
+                    {
+                        (
+                            self.storage.current_epoch,
+                            DidRecalculate::ReusedMemoizedValue,
+                        )
+                    } else if any_dependency_changed(self, derived_node) {
                         update_derived_node(
                             self,
                             derived_node_id,
@@ -86,6 +104,12 @@ impl Database {
                             inner_fn,
                         )
                     } else {
+                        // A dependency may have been recalculated without its value
+                        // changing, but now read a less durable source.
+                        self.storage.set_derived_node_durability(
+                            derived_node_id,
+                            dependencies_durability(self, derived_node),
+                        );
                         (
                             self.storage.current_epoch,
                             DidRecalculate::ReusedMemoizedValue,
@@ -95,9 +119,15 @@ impl Database {
             } else {
                 create_derived_node(self, derived_node_id, inner_fn)
             };
+        let durability = self
+            .storage
+            .get_derived_node_revision(derived_node_id)
+            .map(|revision| revision.durability)
+            .unwrap_or_default();
         self.register_dependency_in_parent_memoized_fn(
             NodeKind::Derived(derived_node_id),
             time_updated,
+            durability,
         );
         did_recalculate
     }
@@ -121,6 +151,7 @@ fn create_derived_node(
         derived_node_id,
         tracked_dependencies.max_time_updated,
         db.storage.current_epoch,
+        tracked_dependencies.min_durability,
         index,
     );
     (
@@ -148,6 +179,15 @@ fn update_derived_node(
             };
 
             let did_recalculate = if *prev_value != *value {
+                if let Some(history) = &db.storage.history {
+                    let prev_revision = *occupied.get();
+                    history.record(
+                        HistoryNode::Derived(derived_node_id),
+                        prev_revision.time_updated,
+                        tracked_dependencies.max_time_updated,
+                        HistoricalValue::DerivedNode(prev_revision.index),
+                    );
+                }
                 occupied.get_mut().time_updated = tracked_dependencies.max_time_updated;
                 DidRecalculate::Recalculated
             } else {
@@ -161,6 +201,7 @@ fn update_derived_node(
             });
 
             occupied.get_mut().index = index;
+            occupied.get_mut().durability = tracked_dependencies.min_durability;
 
             (tracked_dependencies.max_time_updated, did_recalculate)
         }
@@ -180,7 +221,30 @@ fn any_dependency_changed(db: &Database, derived_node: &DerivedNode) -> bool {
             NodeKind::Derived(dep_node_id) => {
                 derived_node_changed_since(db, dep_node_id, dependency.time_verified_or_updated)
             }
+            // The epoch only changes when a source changes
+            NodeKind::Revision => true,
+        })
+}
+
+fn dependencies_durability(db: &Database, derived_node: &DerivedNode) -> Durability {
+    derived_node
+        .dependencies
+        .iter()
+        .map(|dependency| match dependency.node_to {
+            NodeKind::Source(key) => db
+                .storage
+                .get_source_node(key)
+                .map(|source| source.durability)
+                .unwrap_or_default(),
+            NodeKind::Derived(dep_node_id) => db
+                .storage
+                .get_derived_node_revision(dep_node_id)
+                .map(|rev| rev.durability)
+                .unwrap_or_default(),
+            NodeKind::Revision => Durability::Low,
         })
+        .min()
+        .unwrap_or(Durability::High)
 }
 
 fn source_node_changed_since(db: &Database, key: Key, since: Epoch) -> bool {
@@ -219,7 +283,25 @@ fn invoke_with_dependency_tracking(
     inner_fn: InnerFn,
 ) -> Option<(Box<dyn DynEq>, TrackedDependencies)> {
     let guard = db.dependency_stack.enter(derived_node_id);
-    let result = inner_fn.0(db, derived_node_id);
-    let dependencies = guard.release();
-    Some((result?, dependencies))
+    let result = (inner_fn.call)(db, derived_node_id);
+    let mut tracked_dependencies = guard.release();
+    if inner_fn.coarse {
+        // Instead of verifying each dependency, coarse nodes are re-invoked whenever
+        // the revision changes. The max_time_updated of the dependencies is kept, so
+        // that the node is backdated if the value did not change.
+        tracked_dependencies.dependencies = vec![Dependency {
+            node_to: NodeKind::Revision,
+            time_verified_or_updated: db.storage.current_epoch,
+        }];
+    } else if let Some(threshold) = db.wide_node_threshold {
+        let dependency_count = tracked_dependencies.dependencies.len();
+        if dependency_count > threshold {
+            warn!(
+                "Derived node {derived_node_id:?} has {dependency_count} dependencies, \
+                which is more than the threshold of {threshold}. Verifying it is slow. \
+                Consider using #[memo(coarse)]."
+            );
+        }
+    }
+    Some((result?, tracked_dependencies))
 }
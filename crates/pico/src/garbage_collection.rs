@@ -22,6 +22,8 @@ impl DatabaseStorage {
         &mut self,
         retained_derived_node_ids: impl Iterator<Item = DerivedNodeId>,
     ) {
+        self.compact_history();
+
         let mut derived_node_id_queue = retained_derived_node_ids.collect::<Vec<_>>();
 
         // We need to keep track of nodes that we have already processed, since one top-level retained node
@@ -73,6 +75,7 @@ impl DatabaseStorage {
                 DerivedNodeRevision {
                     time_updated: old_derived_node_revision.time_updated,
                     time_verified: old_derived_node_revision.time_verified,
+                    durability: old_derived_node_revision.durability,
                     index: new_index,
                 },
             );
@@ -126,7 +129,7 @@ fn add_dependencies_to_queue<'a>(
 ) {
     for dependency in dependencies {
         match dependency.node_to {
-            NodeKind::Source(_) => {}
+            NodeKind::Source(_) | NodeKind::Revision => {}
             NodeKind::Derived(dependency_id) => {
                 derived_node_id_queue.push(dependency_id);
             }
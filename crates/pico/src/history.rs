@@ -0,0 +1,167 @@
+use boxcar::Vec as BoxcarVec;
+use dashmap::DashMap;
+
+use crate::{
+    dyn_eq::DynEq, epoch::Epoch, index::Index, intern::Key, DatabaseStorage, DerivedNodeId,
+    MemoRef, SourceId,
+};
+
+/// A source or derived node whose prior values can be retrieved with
+/// [`Database::value_at`][crate::Database::value_at].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoryNode {
+    Source(Key),
+    Derived(DerivedNodeId),
+}
+
+impl<T> From<SourceId<T>> for HistoryNode {
+    fn from(source_id: SourceId<T>) -> Self {
+        HistoryNode::Source(source_id.key)
+    }
+}
+
+impl<T> From<MemoRef<T>> for HistoryNode {
+    fn from(memo_ref: MemoRef<T>) -> Self {
+        HistoryNode::Derived(memo_ref.derived_node_id)
+    }
+}
+
+impl From<DerivedNodeId> for HistoryNode {
+    fn from(derived_node_id: DerivedNodeId) -> Self {
+        HistoryNode::Derived(derived_node_id)
+    }
+}
+
+/// The values of source and derived nodes that have since been replaced, which are
+/// retained for the last `epochs` epochs.
+#[derive(Debug)]
+pub(crate) struct History {
+    pub(crate) epochs: usize,
+    pub(crate) entries: DashMap<HistoryNode, Vec<HistoryEntry>>,
+    /// Values that are owned by the history. Values of derived nodes are instead
+    /// left in `DatabaseStorage::derived_nodes` until garbage collection.
+    pub(crate) values: BoxcarVec<Box<dyn DynEq>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HistoryEntry {
+    /// The value was current from `time_updated`...
+    pub(crate) time_updated: Epoch,
+    /// ...until (but not including) `time_replaced`.
+    pub(crate) time_replaced: Epoch,
+    pub(crate) value: HistoricalValue,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum HistoricalValue {
+    Owned(Index<HistoryNode>),
+    DerivedNode(Index<DerivedNodeId>),
+}
+
+impl History {
+    pub(crate) fn new(epochs: usize) -> Self {
+        Self {
+            epochs,
+            entries: DashMap::new(),
+            values: BoxcarVec::new(),
+        }
+    }
+
+    pub(crate) fn record(
+        &self,
+        node: HistoryNode,
+        time_updated: Epoch,
+        time_replaced: Epoch,
+        value: HistoricalValue,
+    ) {
+        self.entries.entry(node).or_default().push(HistoryEntry {
+            time_updated,
+            time_replaced,
+            value,
+        });
+    }
+
+    pub(crate) fn record_owned(
+        &self,
+        node: HistoryNode,
+        time_updated: Epoch,
+        time_replaced: Epoch,
+        value: Box<dyn DynEq>,
+    ) {
+        let index = Index::new(self.values.push(value));
+        self.record(
+            node,
+            time_updated,
+            time_replaced,
+            HistoricalValue::Owned(index),
+        );
+    }
+
+    pub(crate) fn entry_at(&self, node: HistoryNode, epoch: Epoch) -> Option<HistoryEntry> {
+        self.entries
+            .get(&node)?
+            .iter()
+            .find(|entry| entry.time_updated <= epoch && epoch < entry.time_replaced)
+            .copied()
+    }
+}
+
+impl DatabaseStorage {
+    pub(crate) fn historical_value(&self, value: HistoricalValue) -> &dyn DynEq {
+        match value {
+            HistoricalValue::Owned(index) => self
+                .history
+                .as_ref()
+                .and_then(|history| history.values.get(index.idx))
+                .expect(
+                    "indexes should always be valid. \
+                    This is indicative of a bug in Pico.",
+                )
+                .as_ref(),
+            HistoricalValue::DerivedNode(index) => self
+                .derived_nodes
+                .get(index.idx)
+                .expect(
+                    "indexes should always be valid. \
+                    This is indicative of a bug in Pico.",
+                )
+                .value
+                .as_ref(),
+        }
+    }
+
+    /// Drop the entries that were replaced before the last `history.epochs` epochs,
+    /// and move the remaining values into storage owned by the history, since
+    /// garbage collection will create a new `self.derived_nodes`.
+    pub(crate) fn compact_history(&mut self) {
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+        let oldest_retained_epoch = (usize::from(self.current_epoch) + 1)
+            .saturating_sub(history.epochs)
+            .max(1);
+
+        let new_values = BoxcarVec::new();
+        for mut entries in history.entries.iter_mut() {
+            entries.retain(|entry| usize::from(entry.time_replaced) > oldest_retained_epoch);
+            for entry in entries.iter_mut() {
+                let value = match entry.value {
+                    HistoricalValue::Owned(index) => history.values.get_mut(index.idx),
+                    HistoricalValue::DerivedNode(index) => self
+                        .derived_nodes
+                        .get_mut(index.idx)
+                        .map(|derived_node| &mut derived_node.value),
+                }
+                .expect(
+                    "indexes should always be valid. \
+                    This is indicative of a bug in Pico.",
+                );
+                // We do this to avoid cloning the value
+                let value = std::mem::replace(value, Box::new(()));
+                entry.value = HistoricalValue::Owned(Index::new(new_values.push(value)));
+            }
+        }
+        history.entries.retain(|_, entries| !entries.is_empty());
+        history.values = new_values;
+    }
+}
@@ -1,10 +1,12 @@
 mod database;
 mod dependency;
 mod derived_node;
+mod durability;
 mod dyn_eq;
 mod epoch;
 mod execute_memoized_function;
 mod garbage_collection;
+mod history;
 mod index;
 mod intern;
 pub mod macro_fns;
@@ -14,7 +16,10 @@ mod source;
 
 pub use database::*;
 pub use derived_node::*;
+pub use durability::Durability;
+pub use epoch::Epoch;
 pub use execute_memoized_function::*;
+pub use history::HistoryNode;
 pub use intern::*;
 pub use memo_ref::*;
 pub use source::*;
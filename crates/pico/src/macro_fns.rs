@@ -12,6 +12,10 @@ pub fn init_param_vec() -> ArrayVec<[ParamId; 8]> {
     ArrayVec::<[ParamId; 8]>::default()
 }
 
+/// Store a param, if a structurally equal param has not already been stored.
+/// Params are keyed by their hash (which includes their type), so a param is
+/// stored once, even if it is passed to several memoized functions. Params are
+/// dropped during garbage collection, once no retained derived node uses them.
 pub fn intern_borrowed_param<T: Hash + Clone + 'static>(db: &Database, param: &T) -> ParamId {
     let param_id = hash(param).into();
     if let Entry::Vacant(v) = db.storage.param_id_to_index.entry(param_id) {
@@ -21,6 +25,7 @@ pub fn intern_borrowed_param<T: Hash + Clone + 'static>(db: &Database, param: &T
     param_id
 }
 
+/// See [`intern_borrowed_param`].
 pub fn intern_owned_param<T: Hash + Clone + 'static>(db: &Database, param: T) -> ParamId {
     let param_id = hash(&param).into();
     if let Entry::Vacant(v) = db.storage.param_id_to_index.entry(param_id) {
@@ -3,7 +3,7 @@ use std::{
     marker::PhantomData,
 };
 
-use crate::{dyn_eq::DynEq, epoch::Epoch, intern::Key, ParamId};
+use crate::{durability::Durability, dyn_eq::DynEq, epoch::Epoch, intern::Key, ParamId};
 
 pub trait Source {
     fn get_key(&self) -> Key;
@@ -54,5 +54,6 @@ impl<T> From<ParamId> for SourceId<T> {
 #[derive(Debug)]
 pub struct SourceNode {
     pub time_updated: Epoch,
+    pub durability: Durability,
     pub value: Box<dyn DynEq>,
 }
@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use pico::{Database, SourceId};
+use pico_macros::{memo, Source};
+
+static ALL_FIRST_LETTERS_COUNTER: AtomicUsize = AtomicUsize::new(0);
+static FIRST_LETTER_COUNT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn coarse_memo() {
+    let mut db = Database::default();
+
+    let input_ids = ["a", "b", "c"].map(|key| {
+        db.set(Input {
+            key,
+            value: "asdf".to_string(),
+        })
+    });
+    let unrelated_id = db.set(Unrelated {
+        key: "unrelated",
+        value: "isograph".to_string(),
+    });
+
+    assert_eq!(*first_letter_count(&db, input_ids), 3);
+    assert_eq!(ALL_FIRST_LETTERS_COUNTER.load(Ordering::SeqCst), 1);
+    assert_eq!(FIRST_LETTER_COUNT_COUNTER.load(Ordering::SeqCst), 1);
+
+    // The coarse node depends only on the revision, not on each input
+    assert_eq!(db.wide_derived_nodes(2).len(), 0);
+
+    db.set(Unrelated {
+        key: "unrelated",
+        value: "pico".to_string(),
+    });
+    assert_eq!(db.get(unrelated_id).value, "pico");
+
+    assert_eq!(*first_letter_count(&db, input_ids), 3);
+    // all_first_letters is coarse, so it was re-executed even though it does not
+    // read the source that changed
+    assert_eq!(ALL_FIRST_LETTERS_COUNTER.load(Ordering::SeqCst), 2);
+    // but its value did not change, so first_letter_count was not re-executed
+    assert_eq!(FIRST_LETTER_COUNT_COUNTER.load(Ordering::SeqCst), 1);
+
+    db.set(Input {
+        key: "a",
+        value: "qwer".to_string(),
+    });
+
+    assert_eq!(*first_letter_count(&db, input_ids), 2);
+    assert_eq!(ALL_FIRST_LETTERS_COUNTER.load(Ordering::SeqCst), 3);
+    assert_eq!(FIRST_LETTER_COUNT_COUNTER.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn wide_derived_nodes() {
+    let mut db = Database::default();
+
+    let input_ids = ["a", "b", "c"].map(|key| {
+        db.set(Input {
+            key,
+            value: key.to_string(),
+        })
+    });
+
+    assert_eq!(*concatenated(&db, input_ids), "abc");
+
+    let wide_derived_nodes = db.wide_derived_nodes(2);
+    assert_eq!(wide_derived_nodes.len(), 1);
+    assert_eq!(wide_derived_nodes[0].1, 3);
+    assert_eq!(db.wide_derived_nodes(3).len(), 0);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Source)]
+struct Input {
+    #[key]
+    pub key: &'static str,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Source)]
+struct Unrelated {
+    #[key]
+    pub key: &'static str,
+    pub value: String,
+}
+
+#[memo(coarse)]
+fn all_first_letters(db: &Database, input_ids: [SourceId<Input>; 3]) -> Vec<char> {
+    ALL_FIRST_LETTERS_COUNTER.fetch_add(1, Ordering::SeqCst);
+    input_ids
+        .iter()
+        .map(|input_id| db.get(*input_id).value.chars().next().unwrap())
+        .collect()
+}
+
+#[memo]
+fn first_letter_count(db: &Database, input_ids: [SourceId<Input>; 3]) -> usize {
+    FIRST_LETTER_COUNT_COUNTER.fetch_add(1, Ordering::SeqCst);
+    all_first_letters(db, input_ids)
+        .iter()
+        .filter(|letter| **letter == 'a')
+        .count()
+}
+
+#[memo]
+fn concatenated(db: &Database, input_ids: [SourceId<Input>; 3]) -> String {
+    input_ids
+        .iter()
+        .map(|input_id| db.get(*input_id).value.clone())
+        .collect()
+}
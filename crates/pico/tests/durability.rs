@@ -0,0 +1,130 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use pico::{Database, Durability, SourceId};
+use pico_macros::{memo, Source};
+
+static ALL_FIRST_LETTERS_COUNTER: AtomicUsize = AtomicUsize::new(0);
+static DOUBLED_LENGTH_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn durable_nodes_are_reused_when_less_durable_sources_change() {
+    let mut db = Database::default();
+
+    let input_ids = ["a", "b", "c"].map(|key| {
+        db.set_with_durability(
+            Input {
+                key,
+                value: "asdf".to_string(),
+            },
+            Durability::High,
+        )
+    });
+    db.set(Input {
+        key: "unrelated",
+        value: "isograph".to_string(),
+    });
+
+    assert_eq!(*all_first_letters(&db, input_ids), vec!['a', 'a', 'a']);
+    assert_eq!(ALL_FIRST_LETTERS_COUNTER.load(Ordering::SeqCst), 1);
+
+    db.set(Input {
+        key: "unrelated",
+        value: "pico".to_string(),
+    });
+
+    assert_eq!(*all_first_letters(&db, input_ids), vec!['a', 'a', 'a']);
+    // all_first_letters is coarse, so it would be re-executed whenever any source
+    // changes. But it only reads high durability sources, and only a low
+    // durability source changed.
+    assert_eq!(ALL_FIRST_LETTERS_COUNTER.load(Ordering::SeqCst), 1);
+
+    db.set_with_durability(
+        Input {
+            key: "b",
+            value: "qwer".to_string(),
+        },
+        Durability::High,
+    );
+
+    assert_eq!(*all_first_letters(&db, input_ids), vec!['a', 'q', 'a']);
+    assert_eq!(ALL_FIRST_LETTERS_COUNTER.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn durability_is_lowered_when_a_dependency_reads_a_less_durable_source() {
+    let mut db = Database::default();
+
+    let config_id = db.set_with_durability(
+        Config {
+            key: "config",
+            read_file: false,
+        },
+        Durability::High,
+    );
+    let file_id = db.set(Input {
+        key: "file",
+        value: "asdf".to_string(),
+    });
+
+    assert_eq!(*doubled_length(&db, config_id, file_id), 8);
+    assert_eq!(DOUBLED_LENGTH_COUNTER.load(Ordering::SeqCst), 1);
+
+    // length now reads the file, but its value does not change, so doubled_length
+    // is reused.
+    db.set_with_durability(
+        Config {
+            key: "config",
+            read_file: true,
+        },
+        Durability::High,
+    );
+    assert_eq!(*doubled_length(&db, config_id, file_id), 8);
+    assert_eq!(DOUBLED_LENGTH_COUNTER.load(Ordering::SeqCst), 1);
+
+    // doubled_length now (indirectly) reads a low durability source, so it must be
+    // re-executed when that source changes.
+    db.set(Input {
+        key: "file",
+        value: "isograph".to_string(),
+    });
+    assert_eq!(*doubled_length(&db, config_id, file_id), 16);
+    assert_eq!(DOUBLED_LENGTH_COUNTER.load(Ordering::SeqCst), 2);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Source)]
+struct Input {
+    #[key]
+    pub key: &'static str,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Source)]
+struct Config {
+    #[key]
+    pub key: &'static str,
+    pub read_file: bool,
+}
+
+#[memo(coarse)]
+fn all_first_letters(db: &Database, input_ids: [SourceId<Input>; 3]) -> Vec<char> {
+    ALL_FIRST_LETTERS_COUNTER.fetch_add(1, Ordering::SeqCst);
+    input_ids
+        .iter()
+        .map(|input_id| db.get(*input_id).value.chars().next().unwrap())
+        .collect()
+}
+
+#[memo]
+fn length(db: &Database, config_id: SourceId<Config>, file_id: SourceId<Input>) -> usize {
+    if db.get(config_id).read_file {
+        db.get(file_id).value.len()
+    } else {
+        4
+    }
+}
+
+#[memo]
+fn doubled_length(db: &Database, config_id: SourceId<Config>, file_id: SourceId<Input>) -> usize {
+    DOUBLED_LENGTH_COUNTER.fetch_add(1, Ordering::SeqCst);
+    *length(db, config_id, file_id) * 2
+}
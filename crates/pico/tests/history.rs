@@ -0,0 +1,126 @@
+use pico::{Database, Epoch, SourceId};
+use pico_macros::{memo, Source};
+
+#[test]
+fn history() {
+    let mut db = Database::default();
+    db.set_history_epochs(Some(10));
+
+    let input_id = db.set(Input {
+        key: "key",
+        value: "asdf".to_string(),
+    });
+    let first_epoch = db.current_epoch();
+    let first_letter_ref = first_letter(&db, input_id);
+    assert_eq!(*first_letter_ref, 'a');
+
+    db.set(Input {
+        key: "key",
+        value: "qwer".to_string(),
+    });
+    let second_epoch = db.current_epoch();
+    assert_eq!(*first_letter(&db, input_id), 'q');
+
+    assert_eq!(
+        db.value_at::<Input>(input_id, first_epoch)
+            .map(|input| input.value.as_str()),
+        Some("asdf")
+    );
+    assert_eq!(
+        db.value_at::<Input>(input_id, second_epoch)
+            .map(|input| input.value.as_str()),
+        Some("qwer")
+    );
+    assert_eq!(
+        db.value_at::<char>(first_letter_ref, first_epoch),
+        Some(&'a')
+    );
+    assert_eq!(
+        db.value_at::<char>(first_letter_ref, second_epoch),
+        Some(&'q')
+    );
+
+    db.set(Input {
+        key: "key",
+        value: "zxcv".to_string(),
+    });
+    let third_epoch = db.current_epoch();
+
+    // first_letter was not invoked in the third epoch, so its value is not known
+    assert_eq!(db.value_at::<char>(first_letter_ref, third_epoch), None);
+    assert_eq!(
+        db.value_at::<char>(first_letter_ref, Epoch::from(usize::from(third_epoch) + 1)),
+        None
+    );
+
+    // Values are retained during garbage collection
+    db.run_garbage_collection();
+    assert_eq!(
+        db.value_at::<Input>(input_id, first_epoch)
+            .map(|input| input.value.as_str()),
+        Some("asdf")
+    );
+    assert_eq!(
+        db.value_at::<char>(first_letter_ref, first_epoch),
+        Some(&'a')
+    );
+}
+
+#[test]
+fn history_only_retains_last_epochs() {
+    let mut db = Database::default();
+    db.set_history_epochs(Some(2));
+
+    let input_id = db.set(Input {
+        key: "key",
+        value: "asdf".to_string(),
+    });
+    let first_epoch = db.current_epoch();
+
+    for value in ["qwer", "zxcv", "uiop"] {
+        db.set(Input {
+            key: "key",
+            value: value.to_string(),
+        });
+    }
+    let last_epoch = db.current_epoch();
+    assert!(db.value_at::<Input>(input_id, first_epoch).is_some());
+
+    db.run_garbage_collection();
+    assert!(db.value_at::<Input>(input_id, first_epoch).is_none());
+    assert_eq!(
+        db.value_at::<Input>(input_id, Epoch::from(usize::from(last_epoch) - 1))
+            .map(|input| input.value.as_str()),
+        Some("zxcv")
+    );
+}
+
+#[test]
+fn history_disabled() {
+    let mut db = Database::default();
+
+    let input_id = db.set(Input {
+        key: "key",
+        value: "asdf".to_string(),
+    });
+    let first_epoch = db.current_epoch();
+
+    db.set(Input {
+        key: "key",
+        value: "qwer".to_string(),
+    });
+    assert!(db.value_at::<Input>(input_id, first_epoch).is_none());
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Source)]
+struct Input {
+    #[key]
+    pub key: &'static str,
+    pub value: String,
+}
+
+#[memo]
+fn first_letter(db: &Database, input_id: SourceId<Input>) -> char {
+    let input = db.get(input_id);
+    input.value.chars().next().unwrap()
+}
@@ -0,0 +1,86 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    LazyLock, Mutex,
+};
+
+use pico::Database;
+use pico_macros::memo;
+
+static PARAM_CLONE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+static PARAM_DROP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+static RUN_SERIALLY: LazyLock<Mutex<()>> = LazyLock::new(Mutex::default);
+
+#[test]
+fn param_shared_across_memoized_functions() {
+    let _serial_lock = RUN_SERIALLY.lock();
+    PARAM_CLONE_COUNTER.store(0, Ordering::SeqCst);
+
+    let db = Database::default();
+
+    let param = Param { value: 1 };
+    first_function(&db, &param);
+    second_function(&db, &param);
+
+    // Structurally equal params are stored once, even if they are passed to
+    // different memoized functions.
+    assert_eq!(PARAM_CLONE_COUNTER.load(Ordering::SeqCst), 1);
+
+    second_function(&db, &Param { value: 2 });
+    assert_eq!(PARAM_CLONE_COUNTER.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn param_dropped_after_derived_nodes_are_garbage_collected() {
+    let _serial_lock = RUN_SERIALLY.lock();
+    PARAM_DROP_COUNTER.store(0, Ordering::SeqCst);
+
+    let mut db = Database::new_with_capacity(1.try_into().unwrap());
+
+    first_function(&db, &Param { value: 1 });
+    second_function(&db, &Param { value: 2 });
+
+    // first_function is not retained, so the param that only it used is dropped.
+    let drops_before_garbage_collection = PARAM_DROP_COUNTER.load(Ordering::SeqCst);
+    db.run_garbage_collection();
+    assert_eq!(
+        PARAM_DROP_COUNTER.load(Ordering::SeqCst),
+        drops_before_garbage_collection + 1
+    );
+
+    // second_function is not retained after this, so its param is dropped.
+    first_function(&db, &Param { value: 1 });
+    let drops_before_garbage_collection = PARAM_DROP_COUNTER.load(Ordering::SeqCst);
+    db.run_garbage_collection();
+    assert_eq!(
+        PARAM_DROP_COUNTER.load(Ordering::SeqCst),
+        drops_before_garbage_collection + 1
+    );
+}
+
+#[derive(Hash)]
+struct Param {
+    value: usize,
+}
+
+impl Clone for Param {
+    fn clone(&self) -> Self {
+        PARAM_CLONE_COUNTER.fetch_add(1, Ordering::SeqCst);
+        Self { value: self.value }
+    }
+}
+
+impl Drop for Param {
+    fn drop(&mut self) {
+        PARAM_DROP_COUNTER.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[memo]
+fn first_function(_db: &Database, param: &Param) -> usize {
+    param.value
+}
+
+#[memo]
+fn second_function(_db: &Database, param: &Param) -> usize {
+    param.value + 1
+}
@@ -10,5 +10,6 @@ mod garbage_collection {
 mod params {
     mod memo_ref_never_cloned;
     mod other_param_cloned_on_execute;
+    mod param_lifetime;
     mod source_id_never_cloned;
 }
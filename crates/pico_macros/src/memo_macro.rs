@@ -2,9 +2,23 @@ use std::hash::{DefaultHasher, Hash, Hasher};
 
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, parse_quote, Error, FnArg, ItemFn, PatType, ReturnType, Signature};
+use syn::{
+    parse_macro_input, parse_quote, Error, FnArg, Ident, ItemFn, PatType, ReturnType, Signature,
+};
+
+pub(crate) fn memo(args: TokenStream, item: TokenStream) -> TokenStream {
+    // #[memo(coarse)] functions depend on the revision of the database, instead of on
+    // the sources and memoized functions that they read.
+    let coarse = match parse_macro_input!(args as Option<Ident>) {
+        None => false,
+        Some(ident) if ident == "coarse" => true,
+        Some(ident) => {
+            return Error::new_spanned(ident, "Expected #[memo] or #[memo(coarse)]")
+                .to_compile_error()
+                .into();
+        }
+    };
 
-pub(crate) fn memo(_args: TokenStream, item: TokenStream) -> TokenStream {
     let ItemFn {
         sig,
         vis,
@@ -114,6 +128,12 @@ pub(crate) fn memo(_args: TokenStream, item: TokenStream) -> TokenStream {
             }
         });
 
+    let new_inner_fn = if coarse {
+        quote!(::pico::InnerFn::new_coarse)
+    } else {
+        quote!(::pico::InnerFn::new)
+    };
+
     let output = quote! {
         #(#attrs)*
         #vis #new_sig {
@@ -125,7 +145,7 @@ pub(crate) fn memo(_args: TokenStream, item: TokenStream) -> TokenStream {
             let did_recalculate = ::pico::macro_fns::execute_memoized_function(
                 #db_arg,
                 derived_node_id,
-                ::pico::InnerFn::new(|#db_arg, derived_node_id| {
+                #new_inner_fn(|#db_arg, derived_node_id| {
                     #(
                         #extract_parameters
                     )*
@@ -0,0 +1,8 @@
+mod open_api;
+mod process_open_api_document;
+mod request_text;
+mod rest_network_protocol;
+
+pub use process_open_api_document::ProcessOpenApiDocumentError;
+pub use request_text::generate_rest_requests;
+pub use rest_network_protocol::*;
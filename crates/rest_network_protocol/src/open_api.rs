@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+// The subset of an OpenAPI 3 document that Isograph understands. Everything else
+// (servers, security, headers, etc.) is ignored.
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct OpenApiDocument {
+    #[serde(default)]
+    pub paths: BTreeMap<String, PathItem>,
+    #[serde(default)]
+    pub components: Components,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct Components {
+    #[serde(default)]
+    pub schemas: BTreeMap<String, Schema>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct PathItem {
+    pub get: Option<Operation>,
+    pub put: Option<Operation>,
+    pub post: Option<Operation>,
+    pub delete: Option<Operation>,
+    pub patch: Option<Operation>,
+    /// Parameters shared by all operations of this path.
+    #[serde(default)]
+    pub parameters: Vec<Parameter>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Operation {
+    pub operation_id: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<Parameter>,
+    pub request_body: Option<RequestBody>,
+    #[serde(default)]
+    pub responses: BTreeMap<String, Response>,
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub(crate) struct Parameter {
+    pub name: String,
+    #[serde(rename = "in")]
+    pub location: ParameterLocation,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    pub schema: Option<Schema>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+    Cookie,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct RequestBody {
+    pub description: Option<String>,
+    #[serde(default)]
+    pub content: BTreeMap<String, MediaType>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct Response {
+    #[serde(default)]
+    pub content: BTreeMap<String, MediaType>,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct MediaType {
+    pub schema: Option<Schema>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub(crate) struct Schema {
+    #[serde(rename = "$ref")]
+    pub reference: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, Schema>,
+    #[serde(default)]
+    pub required: Vec<String>,
+    pub items: Option<Box<Schema>>,
+    pub default: Option<serde_json::Value>,
+    #[serde(default)]
+    pub nullable: bool,
+    #[serde(default)]
+    pub deprecated: bool,
+    // These are not supported, but we parse them in order to report an error.
+    #[serde(rename = "oneOf")]
+    pub one_of: Option<serde_json::Value>,
+    #[serde(rename = "anyOf")]
+    pub any_of: Option<serde_json::Value>,
+    #[serde(rename = "allOf")]
+    pub all_of: Option<serde_json::Value>,
+}
+
+/// The JSON media type, which is the only media type Isograph reads and sends.
+pub(crate) const JSON_MEDIA_TYPE: &str = "application/json";
+
+/// The prefix of references to schemas defined in the components section.
+pub(crate) const SCHEMA_REFERENCE_PREFIX: &str = "#/components/schemas/";
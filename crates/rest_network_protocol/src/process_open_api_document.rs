@@ -0,0 +1,735 @@
+use std::collections::BTreeMap;
+
+use common_lang_types::{
+    error_codes, DescriptionValue, ErrorCode, HasErrorCode, IsographObjectTypeName, Location,
+    SelectableName, Span, TextSource, UnvalidatedTypeName, WithLocation, WithSpan,
+};
+use graphql_lang_types::{
+    FloatValue, GraphQLConstantValue, GraphQLInputValueDefinition, GraphQLListTypeAnnotation,
+    GraphQLNamedTypeAnnotation, GraphQLNonNullTypeAnnotation, GraphQLTypeAnnotation, NameValuePair,
+    RootOperationKind,
+};
+use intern::string_key::Intern;
+use isograph_schema::{
+    FieldToInsert, ProcessObjectTypeDefinitionOutcome, ProcessTypeSystemDocumentOutcome,
+    ServerObjectEntity, ServerScalarEntity, STRING_JAVASCRIPT_TYPE,
+};
+use lazy_static::lazy_static;
+use thiserror::Error;
+
+use crate::{
+    open_api::{
+        OpenApiDocument, Operation, Parameter, ParameterLocation, PathItem, Schema,
+        JSON_MEDIA_TYPE, SCHEMA_REFERENCE_PREFIX,
+    },
+    RestMethod, RestNetworkProtocol, RestOperation, RestParameter, RestParameterLocation,
+    RestSchemaObjectAssociatedData,
+};
+
+lazy_static! {
+    static ref QUERY_TYPE: IsographObjectTypeName = "Query".intern().into();
+    static ref MUTATION_TYPE: IsographObjectTypeName = "Mutation".intern().into();
+    static ref DEFAULT_DEPRECATION_REASON: DescriptionValue = "No longer supported".intern().into();
+}
+
+/// The name of the argument that holds the request body of an operation.
+pub(crate) const BODY_ARGUMENT_NAME: &str = "body";
+
+/// An OpenAPI document, along with its text (which is used to locate errors.)
+pub(crate) struct OpenApiSource<'a> {
+    pub content: &'a str,
+    pub text_source: TextSource,
+}
+
+pub(crate) fn parse_open_api_document(
+    source: &OpenApiSource,
+) -> ProcessOpenApiDocumentResult<OpenApiDocument> {
+    serde_json::from_str(source.content).map_err(|error| {
+        let offset = byte_offset(source.content, error.line(), error.column());
+        WithLocation::new(
+            ProcessOpenApiDocumentError::InvalidOpenApiDocument {
+                message: error.to_string(),
+            },
+            Location::new(
+                source.text_source,
+                Span::new(offset, (offset + 1).min(source.content.len() as u32)),
+            ),
+        )
+    })
+}
+
+/// Convert the OpenAPI documents (i.e. the schema and the schema extensions) into
+/// objects and scalars. Schemas in the components section become objects (or scalars,
+/// if they are not objects), and operations become fields on the Query type (for GET
+/// operations) or the Mutation type (for all other operations).
+pub(crate) fn process_open_api_documents(
+    documents: Vec<(OpenApiDocument, OpenApiSource)>,
+) -> ProcessOpenApiDocumentResult<ProcessTypeSystemDocumentOutcome<RestNetworkProtocol>> {
+    let mut processor = OpenApiProcessor::default();
+
+    for (document, source) in documents {
+        for (schema_name, schema) in document.components.schemas {
+            let location = key_location(&source, &schema_name);
+            processor.process_component_schema(schema_name, schema, location)?;
+        }
+
+        for (path, path_item) in document.paths {
+            let location = key_location(&source, &path);
+            processor.process_path_item(path, path_item, location)?;
+        }
+    }
+
+    Ok(processor.finish())
+}
+
+#[derive(Default)]
+struct OpenApiProcessor {
+    scalars: Vec<(ServerScalarEntity<RestNetworkProtocol>, Location)>,
+    objects: Vec<(
+        ProcessObjectTypeDefinitionOutcome<RestNetworkProtocol>,
+        Location,
+    )>,
+    query_fields: Vec<WithLocation<FieldToInsert>>,
+    query_operations: BTreeMap<SelectableName, RestOperation>,
+    mutation_fields: Vec<WithLocation<FieldToInsert>>,
+    mutation_operations: BTreeMap<SelectableName, RestOperation>,
+}
+
+impl OpenApiProcessor {
+    fn process_component_schema(
+        &mut self,
+        schema_name: String,
+        schema: Schema,
+        location: Location,
+    ) -> ProcessOpenApiDocumentResult<()> {
+        validate_schema_is_supported(&schema, &schema_name, location)?;
+        match schema.type_.as_deref() {
+            Some("object") | None if schema.reference.is_none() => {
+                self.process_object_schema(schema_name, schema, location)
+            }
+            Some("string") | Some("integer") | Some("number") | Some("boolean") => {
+                let javascript_name = match schema.type_.as_deref() {
+                    Some("string") => *STRING_JAVASCRIPT_TYPE,
+                    Some("boolean") => "boolean".intern().into(),
+                    _ => "number".intern().into(),
+                };
+                self.scalars.push((
+                    ServerScalarEntity {
+                        description: schema.description.map(|description| {
+                            WithSpan::new(description_value(&description), Span::todo_generated())
+                        }),
+                        name: WithLocation::new(schema_name.intern().into(), location),
+                        javascript_name,
+                        specified_by_url: None,
+                        output_format: std::marker::PhantomData,
+                    },
+                    location,
+                ));
+                Ok(())
+            }
+            _ => Err(WithLocation::new(
+                ProcessOpenApiDocumentError::UnsupportedOpenApiSchema {
+                    schema_name,
+                    reason: "Only objects, strings, integers, numbers and booleans \
+                        can be defined in the components section."
+                        .to_string(),
+                },
+                location,
+            )),
+        }
+    }
+
+    fn process_object_schema(
+        &mut self,
+        object_name: String,
+        schema: Schema,
+        location: Location,
+    ) -> ProcessOpenApiDocumentResult<()> {
+        let name: IsographObjectTypeName = object_name.as_str().intern().into();
+        let mut fields_to_insert = vec![];
+        for (property_name, property_schema) in schema.properties {
+            // The property name is also the key in the JSON response, so we cannot
+            // rename properties whose names are not valid identifiers.
+            if !is_identifier(&property_name) {
+                continue;
+            }
+            validate_schema_is_supported(&property_schema, &object_name, location)?;
+            let is_required = schema.required.contains(&property_name);
+            let type_ = if property_name == "id" && property_schema.reference.is_none() {
+                wrap_in_non_null(
+                    "ID".intern().into(),
+                    is_required && !property_schema.nullable,
+                )
+            } else {
+                self.type_annotation(
+                    &property_schema,
+                    &format!("{object_name}{}", pascal_case(&property_name)),
+                    is_required,
+                    location,
+                )?
+            };
+            fields_to_insert.push(WithLocation::new(
+                FieldToInsert {
+                    description: property_schema.description.as_deref().map(|description| {
+                        WithSpan::new(description_value(description), Span::todo_generated())
+                    }),
+                    name: WithLocation::new(property_name.intern().into(), location),
+                    type_,
+                    arguments: vec![],
+                    deprecation_reason: property_schema
+                        .deprecated
+                        .then_some(*DEFAULT_DEPRECATION_REASON),
+                    cost: None,
+                    owner: None,
+                    cache_control: None,
+                    semantic_non_null_levels: vec![],
+                    is_inline_fragment: false,
+                },
+                location,
+            ));
+        }
+
+        self.objects.push((
+            ProcessObjectTypeDefinitionOutcome {
+                encountered_root_kind: None,
+                server_object_entity: ServerObjectEntity {
+                    description: schema.description.as_deref().map(description_value),
+                    name,
+                    concrete_type: Some(name),
+                    is_input_object: false,
+                    directives: vec![],
+                    output_associated_data: RestSchemaObjectAssociatedData::default(),
+                },
+                fields_to_insert,
+                expose_as_fields_to_insert: vec![],
+            },
+            location,
+        ));
+        Ok(())
+    }
+
+    fn process_path_item(
+        &mut self,
+        path: String,
+        path_item: PathItem,
+        location: Location,
+    ) -> ProcessOpenApiDocumentResult<()> {
+        let PathItem {
+            get,
+            put,
+            post,
+            delete,
+            patch,
+            parameters,
+        } = path_item;
+        for (method, operation) in [
+            (RestMethod::Get, get),
+            (RestMethod::Put, put),
+            (RestMethod::Post, post),
+            (RestMethod::Delete, delete),
+            (RestMethod::Patch, patch),
+        ] {
+            if let Some(operation) = operation {
+                self.process_operation(&path, method, operation, &parameters, location)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn process_operation(
+        &mut self,
+        path: &str,
+        method: RestMethod,
+        operation: Operation,
+        path_item_parameters: &[Parameter],
+        location: Location,
+    ) -> ProcessOpenApiDocumentResult<()> {
+        let field_name = operation
+            .operation_id
+            .as_deref()
+            .map(camel_case)
+            .unwrap_or_else(|| operation_name_from_path(method, path));
+
+        // Operations without a JSON response cannot be read, so we skip them.
+        let Some(response_schema) = response_schema(&operation) else {
+            return Ok(());
+        };
+        validate_schema_is_supported(response_schema, &field_name, location)?;
+        let type_ = self.type_annotation(
+            response_schema,
+            &format!("{}Response", pascal_case(&field_name)),
+            false,
+            location,
+        )?;
+
+        // Parameters defined on the operation override parameters with the same
+        // name and location defined on the path.
+        let mut parameters = BTreeMap::new();
+        for parameter in path_item_parameters
+            .iter()
+            .chain(operation.parameters.iter())
+        {
+            parameters.insert((parameter.location, parameter.name.clone()), parameter);
+        }
+
+        let mut arguments = vec![];
+        let mut rest_parameters = BTreeMap::new();
+        for ((parameter_location, parameter_name), parameter) in parameters {
+            let rest_parameter_location = match parameter_location {
+                ParameterLocation::Path => RestParameterLocation::Path,
+                ParameterLocation::Query => RestParameterLocation::Query,
+                // Headers and cookies are the responsibility of the network function.
+                ParameterLocation::Header | ParameterLocation::Cookie => continue,
+            };
+            let argument_name = camel_case(&parameter_name);
+            let schema = parameter.schema.clone().unwrap_or_default();
+            validate_schema_is_supported(&schema, &field_name, location)?;
+            let type_ = self.type_annotation(
+                &schema,
+                &format!(
+                    "{}{}",
+                    pascal_case(&field_name),
+                    pascal_case(&argument_name)
+                ),
+                // Path parameters are always required.
+                parameter.required || parameter_location == ParameterLocation::Path,
+                location,
+            )?;
+            arguments.push(input_value_definition(
+                &argument_name,
+                parameter.description.as_deref(),
+                type_,
+                schema.default.as_ref(),
+                location,
+            ));
+            rest_parameters.insert(
+                argument_name,
+                RestParameter {
+                    name: parameter_name,
+                    location: rest_parameter_location,
+                },
+            );
+        }
+
+        if let Some(request_body) = &operation.request_body {
+            if let Some(body_schema) = request_body
+                .content
+                .get(JSON_MEDIA_TYPE)
+                .and_then(|media_type| media_type.schema.as_ref())
+            {
+                validate_schema_is_supported(body_schema, &field_name, location)?;
+                let type_ = self.type_annotation(
+                    body_schema,
+                    &format!("{}Body", pascal_case(&field_name)),
+                    request_body.required,
+                    location,
+                )?;
+                arguments.push(input_value_definition(
+                    BODY_ARGUMENT_NAME,
+                    request_body.description.as_deref(),
+                    type_,
+                    None,
+                    location,
+                ));
+                rest_parameters.insert(
+                    BODY_ARGUMENT_NAME.to_string(),
+                    RestParameter {
+                        name: BODY_ARGUMENT_NAME.to_string(),
+                        location: RestParameterLocation::Body,
+                    },
+                );
+            }
+        }
+
+        let name: SelectableName = field_name.intern().into();
+        let field = WithLocation::new(
+            FieldToInsert {
+                description: operation
+                    .description
+                    .as_deref()
+                    .or(operation.summary.as_deref())
+                    .map(|description| {
+                        WithSpan::new(description_value(description), Span::todo_generated())
+                    }),
+                name: WithLocation::new(name.unchecked_conversion(), location),
+                type_,
+                arguments,
+                deprecation_reason: operation.deprecated.then_some(*DEFAULT_DEPRECATION_REASON),
+                cost: None,
+                owner: None,
+                cache_control: None,
+                semantic_non_null_levels: vec![],
+                is_inline_fragment: false,
+            },
+            location,
+        );
+        let rest_operation = RestOperation {
+            method,
+            path: path.to_string(),
+            parameters: rest_parameters,
+        };
+
+        let (fields, operations) = match method {
+            RestMethod::Get => (&mut self.query_fields, &mut self.query_operations),
+            _ => (&mut self.mutation_fields, &mut self.mutation_operations),
+        };
+        fields.push(field);
+        operations.insert(name, rest_operation);
+        Ok(())
+    }
+
+    /// The type of a property, parameter, request body or response. Objects that are
+    /// defined inline are added to the schema, using the name_for_inline_object.
+    fn type_annotation(
+        &mut self,
+        schema: &Schema,
+        name_for_inline_object: &str,
+        is_required: bool,
+        location: Location,
+    ) -> ProcessOpenApiDocumentResult<GraphQLTypeAnnotation<UnvalidatedTypeName>> {
+        let is_non_null = is_required && !schema.nullable;
+
+        if let Some(reference) = &schema.reference {
+            let Some(schema_name) = reference.strip_prefix(SCHEMA_REFERENCE_PREFIX) else {
+                return Err(WithLocation::new(
+                    ProcessOpenApiDocumentError::UnsupportedOpenApiSchema {
+                        schema_name: name_for_inline_object.to_string(),
+                        reason: format!(
+                            "The reference {reference} does not refer to a schema in the \
+                            components section."
+                        ),
+                    },
+                    location,
+                ));
+            };
+            return Ok(wrap_in_non_null(schema_name.intern().into(), is_non_null));
+        }
+
+        let scalar_name = match schema.type_.as_deref() {
+            Some("string") => "String",
+            Some("integer") => "Int",
+            Some("number") => "Float",
+            Some("boolean") => "Boolean",
+            Some("array") => {
+                let items = schema.items.as_deref().ok_or_else(|| {
+                    WithLocation::new(
+                        ProcessOpenApiDocumentError::UnsupportedOpenApiSchema {
+                            schema_name: name_for_inline_object.to_string(),
+                            reason: "Arrays must have an items schema.".to_string(),
+                        },
+                        location,
+                    )
+                })?;
+                validate_schema_is_supported(items, name_for_inline_object, location)?;
+                // Items of arrays are non-null, unless they are explicitly nullable.
+                let list = GraphQLListTypeAnnotation(self.type_annotation(
+                    items,
+                    &format!("{name_for_inline_object}Item"),
+                    true,
+                    location,
+                )?);
+                return Ok(if is_non_null {
+                    GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::List(
+                        list,
+                    )))
+                } else {
+                    GraphQLTypeAnnotation::List(Box::new(list))
+                });
+            }
+            Some("object") | None if !schema.properties.is_empty() => {
+                self.process_object_schema(
+                    name_for_inline_object.to_string(),
+                    schema.clone(),
+                    location,
+                )?;
+                return Ok(wrap_in_non_null(
+                    name_for_inline_object.intern().into(),
+                    is_non_null,
+                ));
+            }
+            _ => {
+                return Err(WithLocation::new(
+                    ProcessOpenApiDocumentError::UnsupportedOpenApiSchema {
+                        schema_name: name_for_inline_object.to_string(),
+                        reason: "Schemas must have a type (and objects defined inline must \
+                            have properties), or be a reference."
+                            .to_string(),
+                    },
+                    location,
+                ))
+            }
+        };
+        Ok(wrap_in_non_null(scalar_name.intern().into(), is_non_null))
+    }
+
+    fn finish(mut self) -> ProcessTypeSystemDocumentOutcome<RestNetworkProtocol> {
+        // The Query type must always exist, even if there are no GET operations.
+        self.objects.push((
+            root_object(
+                *QUERY_TYPE,
+                RootOperationKind::Query,
+                self.query_fields,
+                self.query_operations,
+            ),
+            Location::generated(),
+        ));
+        if !self.mutation_fields.is_empty() {
+            self.objects.push((
+                root_object(
+                    *MUTATION_TYPE,
+                    RootOperationKind::Mutation,
+                    self.mutation_fields,
+                    self.mutation_operations,
+                ),
+                Location::generated(),
+            ));
+        }
+
+        ProcessTypeSystemDocumentOutcome {
+            scalars: self.scalars,
+            objects: self.objects,
+        }
+    }
+}
+
+fn root_object(
+    name: IsographObjectTypeName,
+    root_operation_kind: RootOperationKind,
+    fields_to_insert: Vec<WithLocation<FieldToInsert>>,
+    operations: BTreeMap<SelectableName, RestOperation>,
+) -> ProcessObjectTypeDefinitionOutcome<RestNetworkProtocol> {
+    ProcessObjectTypeDefinitionOutcome {
+        encountered_root_kind: Some(root_operation_kind),
+        server_object_entity: ServerObjectEntity {
+            description: None,
+            name,
+            concrete_type: Some(name),
+            is_input_object: false,
+            directives: vec![],
+            output_associated_data: RestSchemaObjectAssociatedData { operations },
+        },
+        fields_to_insert,
+        expose_as_fields_to_insert: vec![],
+    }
+}
+
+/// The JSON schema of the successful response of an operation, if any.
+fn response_schema(operation: &Operation) -> Option<&Schema> {
+    let response = operation
+        .responses
+        .get("200")
+        .or_else(|| operation.responses.get("201"))
+        .or_else(|| {
+            operation
+                .responses
+                .iter()
+                .find(|(status, _)| status.starts_with('2'))
+                .map(|(_, response)| response)
+        })
+        .or_else(|| operation.responses.get("default"))?;
+    response.content.get(JSON_MEDIA_TYPE)?.schema.as_ref()
+}
+
+fn validate_schema_is_supported(
+    schema: &Schema,
+    schema_name: &str,
+    location: Location,
+) -> ProcessOpenApiDocumentResult<()> {
+    if schema.one_of.is_some() || schema.any_of.is_some() || schema.all_of.is_some() {
+        return Err(WithLocation::new(
+            ProcessOpenApiDocumentError::UnsupportedOpenApiSchema {
+                schema_name: schema_name.to_string(),
+                reason: "oneOf, anyOf and allOf are not supported.".to_string(),
+            },
+            location,
+        ));
+    }
+    Ok(())
+}
+
+fn input_value_definition(
+    name: &str,
+    description: Option<&str>,
+    type_: GraphQLTypeAnnotation<UnvalidatedTypeName>,
+    default_value: Option<&serde_json::Value>,
+    location: Location,
+) -> WithLocation<GraphQLInputValueDefinition> {
+    WithLocation::new(
+        GraphQLInputValueDefinition {
+            description: description.map(|description| {
+                WithSpan::new(description_value(description), Span::todo_generated())
+            }),
+            name: WithLocation::new(name.intern().into(), location),
+            type_: type_.map(|type_name| type_name.unchecked_conversion()),
+            default_value: default_value
+                .map(|value| WithLocation::new(constant_value(value), location)),
+            directives: vec![],
+        },
+        location,
+    )
+}
+
+fn constant_value(value: &serde_json::Value) -> GraphQLConstantValue {
+    match value {
+        serde_json::Value::Null => GraphQLConstantValue::Null,
+        serde_json::Value::Bool(bool) => GraphQLConstantValue::Boolean(*bool),
+        serde_json::Value::Number(number) => match number.as_i64() {
+            Some(int) => GraphQLConstantValue::Int(int),
+            None => GraphQLConstantValue::Float(FloatValue::new(
+                number.as_f64().expect("Expected number to be a float"),
+            )),
+        },
+        serde_json::Value::String(string) => GraphQLConstantValue::String(string.intern().into()),
+        serde_json::Value::Array(items) => GraphQLConstantValue::List(
+            items
+                .iter()
+                .map(|item| WithLocation::new(constant_value(item), Location::generated()))
+                .collect(),
+        ),
+        serde_json::Value::Object(entries) => GraphQLConstantValue::Object(
+            entries
+                .iter()
+                .map(|(name, value)| NameValuePair {
+                    name: WithLocation::new(name.intern().into(), Location::generated()),
+                    value: WithLocation::new(constant_value(value), Location::generated()),
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn wrap_in_non_null(
+    name: UnvalidatedTypeName,
+    is_non_null: bool,
+) -> GraphQLTypeAnnotation<UnvalidatedTypeName> {
+    let named = GraphQLNamedTypeAnnotation(WithSpan::new(name, Span::todo_generated()));
+    if is_non_null {
+        GraphQLTypeAnnotation::NonNull(Box::new(GraphQLNonNullTypeAnnotation::Named(named)))
+    } else {
+        GraphQLTypeAnnotation::Named(named)
+    }
+}
+
+fn description_value(description: &str) -> DescriptionValue {
+    description.intern().into()
+}
+
+/// e.g. `get` and `/pets/{petId}/toys` become `getPetsByPetIdToys`
+fn operation_name_from_path(method: RestMethod, path: &str) -> String {
+    let mut name = method.as_str().to_lowercase();
+    for segment in path.split('/') {
+        match segment
+            .strip_prefix('{')
+            .and_then(|segment| segment.strip_suffix('}'))
+        {
+            Some(parameter) => {
+                name.push_str("By");
+                name.push_str(&pascal_case(parameter));
+            }
+            None => name.push_str(&pascal_case(segment)),
+        }
+    }
+    name
+}
+
+/// e.g. `list-pets` and `list_pets` become `listPets`
+fn camel_case(name: &str) -> String {
+    let pascal_case = pascal_case(name);
+    let mut chars = pascal_case.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => pascal_case,
+    }
+}
+
+/// e.g. `list-pets` and `listPets` become `ListPets`
+fn pascal_case(name: &str) -> String {
+    name.split(|char: char| !char.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// The location of the first occurrence of a key (e.g. a schema name or path) in
+/// the document. serde_json does not keep track of where values are located, so
+/// this is a best effort.
+fn key_location(source: &OpenApiSource, key: &str) -> Location {
+    let quoted_key = format!("\"{key}\"");
+    let span = match source.content.find(&quoted_key) {
+        Some(start) => Span::new(start as u32, (start + quoted_key.len()) as u32),
+        None => Span::new(0, 0),
+    };
+    Location::new(source.text_source, span)
+}
+
+/// Convert a 1-indexed line and column (as reported by serde_json) to a byte offset.
+fn byte_offset(content: &str, line: usize, column: usize) -> u32 {
+    let line_start: usize = content
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    (line_start + column.saturating_sub(1)).min(content.len()) as u32
+}
+
+pub(crate) type ProcessOpenApiDocumentResult<T> =
+    Result<T, WithLocation<ProcessOpenApiDocumentError>>;
+
+#[derive(Error, Eq, PartialEq, Debug)]
+pub enum ProcessOpenApiDocumentError {
+    #[error("Unable to parse OpenAPI document.\nReason: {message}")]
+    InvalidOpenApiDocument { message: String },
+
+    #[error("The schema of {schema_name} is not supported. {reason}")]
+    UnsupportedOpenApiSchema { schema_name: String, reason: String },
+}
+
+impl HasErrorCode for ProcessOpenApiDocumentError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ProcessOpenApiDocumentError::InvalidOpenApiDocument { .. } => error_codes::IS0115,
+            ProcessOpenApiDocumentError::UnsupportedOpenApiSchema { .. } => error_codes::IS0116,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_operations() {
+        assert_eq!(camel_case("list-pets"), "listPets");
+        assert_eq!(camel_case("ListPets"), "listPets");
+        assert_eq!(
+            operation_name_from_path(RestMethod::Get, "/pets/{pet-id}/toys"),
+            "getPetsByPetIdToys"
+        );
+        assert_eq!(
+            operation_name_from_path(RestMethod::Post, "/pets"),
+            "postPets"
+        );
+    }
+
+    #[test]
+    fn converts_line_and_column_to_byte_offset() {
+        let content = "{\n  \"a\": 1,\n}";
+        assert_eq!(byte_offset(content, 1, 1), 0);
+        assert_eq!(byte_offset(content, 2, 3), 4);
+        assert_eq!(byte_offset(content, 3, 1), 12);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use common_lang_types::{QueryText, SelectableName};
+use intern::Lookup;
+use isograph_lang_types::{ArgumentKeyAndValue, SelectionCondition};
+use isograph_schema::{
+    serialize_request_value, MergedSelectionMap, MergedServerSelection, RootOperationName, Schema,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{RestNetworkProtocol, RestOperation, RestParameterLocation};
+
+/// Generate a JSON description of the requests made by an entrypoint: one request
+/// per root field. For example:
+///
+/// ```json
+/// {"kind":"RestRequests","requests":[{"responseKey":"pet____id___v_id",
+/// "method":"GET","path":"/pets/{id}","pathParameters":{"id":{"kind":"Variable",
+/// "name":"id"}},"queryParameters":{},"body":null,"conditions":[]}]}
+/// ```
+///
+/// The response of each request is stored under its responseKey, which matches
+/// the key that the normalization AST reads.
+pub(crate) fn generate_request_text(
+    schema: &Schema<RestNetworkProtocol>,
+    selection_map: &MergedSelectionMap,
+    root_operation_name: &RootOperationName,
+) -> QueryText {
+    let root_object_entity_id = schema
+        .fetchable_types
+        .iter()
+        .find(|(_, name)| name.0 == root_operation_name.0)
+        .expect("Expected root operation to be fetchable. This is indicative of a bug in Isograph.")
+        .0;
+    let operations = &schema
+        .server_entity_data
+        .server_object_entity(*root_object_entity_id)
+        .output_associated_data
+        .operations;
+
+    let request_text = generate_rest_requests(selection_map, operations).to_string();
+
+    // The request text is printed within a single-quoted string literal.
+    QueryText(request_text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// The requests made for the root fields in selection_map, as JSON. This is used by
+/// protocols that fetch some fields with REST (e.g. the mixed network protocol.)
+pub fn generate_rest_requests(
+    selection_map: &MergedSelectionMap,
+    operations: &BTreeMap<SelectableName, RestOperation>,
+) -> Value {
+    let requests = selection_map
+        .values()
+        .map(|selection| {
+            let (name, response_key, arguments, conditions) = match selection {
+                MergedServerSelection::ScalarField(scalar_field) => (
+                    SelectableName::from(scalar_field.name),
+                    scalar_field.normalization_alias(),
+                    &scalar_field.arguments,
+                    &scalar_field.conditions,
+                ),
+                MergedServerSelection::LinkedField(linked_field) => (
+                    SelectableName::from(linked_field.name),
+                    linked_field.normalization_alias(),
+                    &linked_field.arguments,
+                    &linked_field.conditions,
+                ),
+                MergedServerSelection::InlineFragment(_) => {
+                    panic!(
+                        "Expected root selections not to be inline fragments. \
+                        This is indicative of a bug in Isograph."
+                    )
+                }
+            };
+            let operation = operations.get(&name).expect(
+                "Expected root field to be an operation. This is indicative of a bug in Isograph.",
+            );
+            request(
+                operation,
+                response_key.unwrap_or_else(|| name.to_string()),
+                arguments,
+                conditions,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "kind": "RestRequests", "requests": requests })
+}
+
+fn request(
+    operation: &RestOperation,
+    response_key: String,
+    arguments: &[ArgumentKeyAndValue],
+    conditions: &[SelectionCondition],
+) -> Value {
+    let mut path_parameters = Map::new();
+    let mut query_parameters = Map::new();
+    let mut body = Value::Null;
+    for argument in arguments {
+        let parameter = operation.parameters.get(argument.key.lookup()).expect(
+            "Expected argument to be a parameter. This is indicative of a bug in Isograph.",
+        );
+        let value = serialize_request_value(&argument.value);
+        match parameter.location {
+            RestParameterLocation::Path => {
+                path_parameters.insert(parameter.name.clone(), value);
+            }
+            RestParameterLocation::Query => {
+                query_parameters.insert(parameter.name.clone(), value);
+            }
+            RestParameterLocation::Body => body = value,
+        }
+    }
+
+    json!({
+        "responseKey": response_key,
+        "method": operation.method.as_str(),
+        "path": operation.path,
+        "pathParameters": path_parameters,
+        "queryParameters": query_parameters,
+        "body": body,
+        "conditions": conditions
+            .iter()
+            .map(|condition| json!({
+                "kind": condition.kind.directive_name(),
+                "value": serialize_request_value(&condition.value),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
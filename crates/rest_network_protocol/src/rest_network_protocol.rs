@@ -0,0 +1,127 @@
+use std::{collections::BTreeMap, error::Error};
+
+use common_lang_types::{
+    CodedError, QueryOperationName, QueryText, RelativePathToSourceFile, SelectableName,
+    WithLocation,
+};
+use isograph_config::CompilerConfigOptions;
+use isograph_lang_types::SchemaSource;
+use isograph_schema::{
+    MergedSelectionMap, NetworkProtocol, ProcessTypeSystemDocumentOutcome, RootOperationName,
+    Schema, ValidatedVariableDefinition,
+};
+use pico::{Database, SourceId};
+
+use crate::{
+    process_open_api_document::{
+        parse_open_api_document, process_open_api_documents, OpenApiSource,
+    },
+    request_text::generate_request_text,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash, Default)]
+pub struct RestNetworkProtocol {}
+
+impl NetworkProtocol for RestNetworkProtocol {
+    type Sources = (
+        SourceId<SchemaSource>,
+        BTreeMap<RelativePathToSourceFile, SourceId<SchemaSource>>,
+    );
+
+    type SchemaObjectAssociatedData = RestSchemaObjectAssociatedData;
+
+    // Schema extensions are additional OpenAPI documents. Their schemas and paths
+    // are added to the schema, as if they were defined in the main document.
+    fn parse_and_process_type_system_documents(
+        db: &Database,
+        sources: &Self::Sources,
+        _options: &CompilerConfigOptions,
+    ) -> Result<ProcessTypeSystemDocumentOutcome<RestNetworkProtocol>, Box<dyn Error>> {
+        let (schema_source_id, schema_extension_sources) = sources;
+
+        let documents = std::iter::once(schema_source_id)
+            .chain(schema_extension_sources.values())
+            .map(|source_id| {
+                let SchemaSource {
+                    content,
+                    text_source,
+                    ..
+                } = db.get(*source_id);
+                let source = OpenApiSource {
+                    content,
+                    text_source: *text_source,
+                };
+                let document =
+                    parse_open_api_document(&source).map_err(|error| error.map(CodedError::new))?;
+                Ok((document, source))
+            })
+            .collect::<Result<Vec<_>, WithLocation<CodedError>>>()?;
+
+        Ok(process_open_api_documents(documents).map_err(|error| error.map(CodedError::new))?)
+    }
+
+    // Instead of a GraphQL query, the "query text" of a REST entrypoint is a JSON
+    // description of the requests to make, which is interpreted by the network
+    // function (e.g. createRestNetworkFunction in @isograph/react.)
+    fn generate_query_text<'a>(
+        _query_name: QueryOperationName,
+        schema: &Schema<Self>,
+        selection_map: &MergedSelectionMap,
+        _query_variables: impl Iterator<Item = &'a ValidatedVariableDefinition> + 'a,
+        root_operation_name: &RootOperationName,
+    ) -> QueryText {
+        generate_request_text(schema, selection_map, root_operation_name)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RestSchemaObjectAssociatedData {
+    /// For the Query and Mutation types, the operation that each field corresponds
+    /// to. Empty for all other types.
+    pub operations: BTreeMap<SelectableName, RestOperation>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RestOperation {
+    pub method: RestMethod,
+    /// The path template, e.g. `/pets/{petId}`
+    pub path: String,
+    /// Keyed by the name of the field argument.
+    pub parameters: BTreeMap<String, RestParameter>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestMethod {
+    Get,
+    Put,
+    Post,
+    Delete,
+    Patch,
+}
+
+impl RestMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RestMethod::Get => "GET",
+            RestMethod::Put => "PUT",
+            RestMethod::Post => "POST",
+            RestMethod::Delete => "DELETE",
+            RestMethod::Patch => "PATCH",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RestParameter {
+    /// The name of the parameter in the OpenAPI document, which can differ from the
+    /// name of the field argument (e.g. `pet-id` and `petId`).
+    pub name: String,
+    pub location: RestParameterLocation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestParameterLocation {
+    Path,
+    Query,
+    Body,
+}
@@ -1,4 +1,4 @@
-export default 'query Query____refetch($id: ID!) {\
+export default 'query HomePage____refetch($id: ID!) {\
   node____id___v_id: node(id: $id) {\
     ... on User {\
       __typename,\
@@ -6,7 +6,7 @@ export default 'query Query____refetch($id: ID!) {\
       avatarUrl,\
       login,\
       name,\
-      repositories____first___l_10____after___l_null: repositories(first: 10, after: null) {\
+      repositories____after___l_null____first___l_10: repositories(after: null, first: 10) {\
         edges {\
           node {\
             id,\
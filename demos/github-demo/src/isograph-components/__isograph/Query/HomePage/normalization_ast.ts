@@ -32,15 +32,14 @@ const normalizationAst: NormalizationAst = {
           kind: "Linked",
           fieldName: "repositories",
           arguments: [
-            [
-              "first",
-              { kind: "Literal", value: 10 },
-            ],
-
             [
               "after",
               { kind: "Literal", value: null },
             ],
+            [
+              "first",
+              { kind: "Literal", value: 10 },
+            ],
           ],
           concreteType: "RepositoryConnection",
           selections: [
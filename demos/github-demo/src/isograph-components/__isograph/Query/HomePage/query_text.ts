@@ -4,7 +4,7 @@ export default 'query HomePage {\
     avatarUrl,\
     login,\
     name,\
-    repositories____first___l_10____after___l_null: repositories(first: 10, after: null) {\
+    repositories____after___l_null____first___l_10: repositories(after: null, first: 10) {\
       edges {\
         node {\
           id,\
@@ -0,0 +1,21 @@
+import type { RefetchQueryIndex } from '@isograph/react';
+import refetchQuery0 from './__refetch__0';
+
+const refetchQueryIndex: RefetchQueryIndex = [
+  {
+    artifact: refetchQuery0,
+    clientField: "User.__refetch",
+    operationKind: "query",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "viewer",
+        arguments: null,
+      },
+    ],
+    allowedVariables: ["id", ],
+    requiredVariables: ["id", ],
+  },
+];
+
+export default refetchQueryIndex;
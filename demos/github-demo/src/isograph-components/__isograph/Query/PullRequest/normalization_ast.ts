@@ -6,15 +6,14 @@ const normalizationAst: NormalizationAst = {
       kind: "Linked",
       fieldName: "repository",
       arguments: [
-        [
-          "owner",
-          { kind: "Variable", name: "repositoryOwner" },
-        ],
-
         [
           "name",
           { kind: "Variable", name: "repositoryName" },
         ],
+        [
+          "owner",
+          { kind: "Variable", name: "repositoryOwner" },
+        ],
       ],
       concreteType: "Repository",
       selections: [
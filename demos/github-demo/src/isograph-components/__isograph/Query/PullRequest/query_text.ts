@@ -1,5 +1,5 @@
 export default 'query PullRequest($repositoryOwner: String!, $repositoryName: String!, $pullRequestNumber: Int!) {\
-  repository____owner___v_repositoryOwner____name___v_repositoryName: repository(owner: $repositoryOwner, name: $repositoryName) {\
+  repository____name___v_repositoryName____owner___v_repositoryOwner: repository(name: $repositoryName, owner: $repositoryOwner) {\
     id,\
     pullRequest____number___v_pullRequestNumber: pullRequest(number: $pullRequestNumber) {\
       id,\
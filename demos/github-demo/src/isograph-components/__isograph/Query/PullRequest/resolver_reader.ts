@@ -17,18 +17,16 @@ const readerAst: ReaderAst<Query__PullRequest__param> = [
     alias: "PullRequestDetail",
     arguments: [
       [
-        "repositoryOwner",
-        { kind: "Variable", name: "repositoryOwner" },
+        "pullRequestNumber",
+        { kind: "Variable", name: "pullRequestNumber" },
       ],
-
       [
         "repositoryName",
         { kind: "Variable", name: "repositoryName" },
       ],
-
       [
-        "pullRequestNumber",
-        { kind: "Variable", name: "pullRequestNumber" },
+        "repositoryOwner",
+        { kind: "Variable", name: "repositoryOwner" },
       ],
     ],
     readerArtifact: Query__PullRequestDetail__resolver_reader,
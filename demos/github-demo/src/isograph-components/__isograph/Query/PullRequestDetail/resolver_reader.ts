@@ -9,15 +9,14 @@ const readerAst: ReaderAst<Query__PullRequestDetail__param> = [
     fieldName: "repository",
     alias: null,
     arguments: [
-      [
-        "owner",
-        { kind: "Variable", name: "repositoryOwner" },
-      ],
-
       [
         "name",
         { kind: "Variable", name: "repositoryName" },
       ],
+      [
+        "owner",
+        { kind: "Variable", name: "repositoryOwner" },
+      ],
     ],
     condition: null,
     isUpdatable: false,
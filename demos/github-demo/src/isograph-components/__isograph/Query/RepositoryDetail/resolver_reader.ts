@@ -15,7 +15,6 @@ const readerAst: ReaderAst<Query__RepositoryDetail__param> = [
         "name",
         { kind: "Variable", name: "repositoryName" },
       ],
-
       [
         "owner",
         { kind: "Variable", name: "repositoryOwner" },
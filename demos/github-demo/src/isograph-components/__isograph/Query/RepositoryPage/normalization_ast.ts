@@ -10,7 +10,6 @@ const normalizationAst: NormalizationAst = {
           "name",
           { kind: "Variable", name: "repositoryName" },
         ],
-
         [
           "owner",
           { kind: "Variable", name: "repositoryOwner" },
@@ -16,20 +16,18 @@ const readerAst: ReaderAst<Query__RepositoryPage__param> = [
     kind: "Resolver",
     alias: "RepositoryDetail",
     arguments: [
+      [
+        "first",
+        { kind: "Variable", name: "first" },
+      ],
       [
         "repositoryName",
         { kind: "Variable", name: "repositoryName" },
       ],
-
       [
         "repositoryOwner",
         { kind: "Variable", name: "repositoryOwner" },
       ],
-
-      [
-        "first",
-        { kind: "Variable", name: "first" },
-      ],
     ],
     readerArtifact: Query__RepositoryDetail__resolver_reader,
     usedRefetchQueries: [],
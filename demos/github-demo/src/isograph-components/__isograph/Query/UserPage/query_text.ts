@@ -2,7 +2,7 @@ export default 'query UserPage($userLogin: String!) {\
   user____login___v_userLogin: user(login: $userLogin) {\
     id,\
     name,\
-    repositories____first___l_10____after___l_null: repositories(first: 10, after: null) {\
+    repositories____after___l_null____first___l_10: repositories(after: null, first: 10) {\
       edges {\
         node {\
           id,\
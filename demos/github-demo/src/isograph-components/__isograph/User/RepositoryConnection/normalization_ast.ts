@@ -31,15 +31,14 @@ const normalizationAst: NormalizationAst = {
               kind: "Linked",
               fieldName: "repositories",
               arguments: [
-                [
-                  "first",
-                  { kind: "Variable", name: "first" },
-                ],
-
                 [
                   "after",
                   { kind: "Variable", name: "after" },
                 ],
+                [
+                  "first",
+                  { kind: "Variable", name: "first" },
+                ],
               ],
               concreteType: "RepositoryConnection",
               selections: [
@@ -3,7 +3,7 @@ export default 'query RepositoryConnection($first: Int, $after: String, $id: ID!
     ... on User {\
       __typename,\
       id,\
-      repositories____first___v_first____after___v_after: repositories(first: $first, after: $after) {\
+      repositories____after___v_after____first___v_first: repositories(after: $after, first: $first) {\
         edges {\
           node {\
             id,\
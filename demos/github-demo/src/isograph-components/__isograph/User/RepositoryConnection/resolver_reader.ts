@@ -10,15 +10,14 @@ const readerAst: ReaderAst<User__RepositoryConnection__param> = [
     fieldName: "repositories",
     alias: null,
     arguments: [
-      [
-        "first",
-        { kind: "Variable", name: "first" },
-      ],
-
       [
         "after",
         { kind: "Variable", name: "after" },
       ],
+      [
+        "first",
+        { kind: "Variable", name: "first" },
+      ],
     ],
     condition: null,
     isUpdatable: false,
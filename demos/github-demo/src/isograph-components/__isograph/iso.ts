@@ -1,4 +1,4 @@
-import type { IsographEntrypoint } from '@isograph/react';
+import type { IsographEntrypoint, IsographRuntimeVersion } from '@isograph/react';
 import { type Actor__UserLink__param } from './Actor/UserLink/param_type';
 import { type IssueComment__formattedCommentCreationDate__param } from './IssueComment/formattedCommentCreationDate/param_type';
 import { type PullRequest__CommentList__param } from './PullRequest/CommentList/param_type';
@@ -25,6 +25,19 @@ import entrypoint_Query__PullRequest from '../__isograph/Query/PullRequest/entry
 import entrypoint_Query__RepositoryPage from '../__isograph/Query/RepositoryPage/entrypoint';
 import entrypoint_Query__UserPage from '../__isograph/Query/UserPage/entrypoint';
 
+// The version of the Isograph compiler that generated this file.
+export const ISOGRAPH_COMPILER_VERSION = '0.3.1';
+
+// If the following lines fail to type check, the installed version of
+// @isograph/react is not compatible with version 0.3.1 of the Isograph
+// compiler, which generated this file. Install a version of @isograph/react
+// that matches `0.3.${string}`, and recompile.
+type AssertRuntimeVersionIsCompatible<
+  TRuntimeVersion extends `0.3.${string}`,
+> = TRuntimeVersion;
+export type IsographRuntimeVersionCheck =
+  AssertRuntimeVersionIsCompatible<IsographRuntimeVersion>;
+
 // This is the type given to regular client fields.
 // This means that the type of the exported iso literal is exactly
 // the type of the passed-in function, which takes one parameter
@@ -52,8 +65,16 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
   ? Whitespace<In>
   : In;
 
+// Client fields and pointers can be preceded by a description.
+type WithoutDescription<In> = Whitespace<In> extends `"""${string}"""${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In> extends `"${string}"${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In>;
+
 // This is a recursive TypeScript type that matches strings that
-// start with whitespace, followed by TString. So e.g. if we have
+// start with whitespace and an optional description, followed by
+// TString. So e.g. if we have
 // ```
 // export function iso<T>(
 //   isographLiteralText: T & MatchesWhitespaceAndString<'field Query.foo', T>
@@ -71,7 +92,7 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
 type MatchesWhitespaceAndString<
   TString extends string,
   T
-> = Whitespace<T> extends `${TString}${string}` ? T : never;
+> = WithoutDescription<T> extends `${TString}${string}` ? T : never;
 
 export function iso<T>(
   param: T & MatchesWhitespaceAndString<'field Actor.UserLink', T>
@@ -0,0 +1,21 @@
+import type { EagerReaderArtifact, ReaderAst, Link } from '@isograph/react';
+
+const readerAst: ReaderAst<{ data: any, parameters: Record<PropertyKey, never> }> = [
+  {
+    kind: "Link",
+    alias: "link",
+  },
+];
+
+const artifact: EagerReaderArtifact<
+  { data: any, parameters: Record<PropertyKey, never> },
+  Link<"AdItem">
+> = {
+  kind: "EagerReaderArtifact",
+  fieldName: "AdItem.link",
+  resolver: ({ data }) => data.link,
+  readerAst,
+  hasUpdatable: false,
+};
+
+export default artifact;
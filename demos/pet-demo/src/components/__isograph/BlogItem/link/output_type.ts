@@ -0,0 +1,2 @@
+import type { Link } from '@isograph/react';
+export type BlogItem__link__output_type = Link<"BlogItem">;
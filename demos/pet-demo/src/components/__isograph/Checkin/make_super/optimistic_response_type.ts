@@ -0,0 +1,5 @@
+export type Checkin__make_super__optimistic_response_type = {
+  readonly id?: string,
+  readonly location?: string,
+  readonly time?: string,
+};
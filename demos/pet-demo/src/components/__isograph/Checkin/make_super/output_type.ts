@@ -1,3 +1,3 @@
 import type React from 'react';
 import { RefetchQueryNormalizationArtifact } from '@isograph/react';
-export type Checkin__make_super__output_type = (params?: any) => [string, () => void];
\ No newline at end of file
+export type Checkin__make_super__output_type = (params?: Record<PropertyKey, never>) => [string, () => void];
\ No newline at end of file
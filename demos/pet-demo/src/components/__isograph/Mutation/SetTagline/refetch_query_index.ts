@@ -0,0 +1,5 @@
+import type { RefetchQueryIndex } from '@isograph/react';
+
+const refetchQueryIndex: RefetchQueryIndex = [];
+
+export default refetchQueryIndex;
@@ -9,15 +9,14 @@ const readerAst: ReaderAst<Pet__FirstCheckinMakeSuperButton__param> = [
     fieldName: "checkins",
     alias: null,
     arguments: [
-      [
-        "skip",
-        { kind: "Literal", value: 0 },
-      ],
-
       [
         "limit",
         { kind: "Literal", value: 1 },
       ],
+      [
+        "skip",
+        { kind: "Literal", value: 0 },
+      ],
     ],
     condition: null,
     isUpdatable: false,
@@ -16,15 +16,14 @@ const readerAst: ReaderAst<Pet__PetCheckinsCard__param> = [
     fieldName: "checkins",
     alias: null,
     arguments: [
-      [
-        "skip",
-        { kind: "Variable", name: "skip" },
-      ],
-
       [
         "limit",
         { kind: "Variable", name: "limit" },
       ],
+      [
+        "skip",
+        { kind: "Variable", name: "skip" },
+      ],
     ],
     condition: null,
     isUpdatable: false,
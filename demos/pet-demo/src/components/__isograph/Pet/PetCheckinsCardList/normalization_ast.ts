@@ -31,15 +31,14 @@ const normalizationAst: NormalizationAst = {
               kind: "Linked",
               fieldName: "checkins",
               arguments: [
-                [
-                  "skip",
-                  { kind: "Variable", name: "skip" },
-                ],
-
                 [
                   "limit",
                   { kind: "Variable", name: "limit" },
                 ],
+                [
+                  "skip",
+                  { kind: "Variable", name: "skip" },
+                ],
               ],
               concreteType: "Checkin",
               selections: [
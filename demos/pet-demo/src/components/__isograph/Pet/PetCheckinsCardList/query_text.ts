@@ -3,7 +3,7 @@ export default 'query PetCheckinsCardList($skip: Int!, $limit: Int!, $id: ID!) {
     ... on Pet {\
       __typename,\
       id,\
-      checkins____skip___v_skip____limit___v_limit: checkins(skip: $skip, limit: $limit) {\
+      checkins____limit___v_limit____skip___v_skip: checkins(limit: $limit, skip: $skip) {\
         id,\
         location,\
         time,\
@@ -0,0 +1,44 @@
+import type { RefetchQueryIndex } from '@isograph/react';
+import refetchQuery0 from './__refetch__0';
+
+const refetchQueryIndex: RefetchQueryIndex = [
+  {
+    artifact: refetchQuery0,
+    clientField: "Checkin.make_super",
+    operationKind: "mutation",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "node",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+      {
+        kind: "InlineFragment",
+        type: "Pet",
+      },
+      {
+        kind: "Linked",
+        fieldName: "checkins",
+        arguments: [
+          [
+            "limit",
+            { kind: "Variable", name: "limit" },
+          ],
+          [
+            "skip",
+            { kind: "Variable", name: "skip" },
+          ],
+        ],
+      },
+    ],
+    allowedVariables: ["checkin_id", ],
+    requiredVariables: ["checkin_id", ],
+  },
+];
+
+export default refetchQueryIndex;
@@ -3,6 +3,11 @@ import { type Pet__set_best_friend__output_type } from '../../Pet/set_best_frien
 import { type Pet__set_pet_tagline__output_type } from '../../Pet/set_pet_tagline/output_type';
 import type { StartUpdate } from '@isograph/react';
 
+/**
+Pet.PetUpdater
+A component to test behavior with respect to mutations.
+You can update the best friend and the tagline.
+*/
 export type Pet__PetUpdater__param = {
   readonly data: {
     readonly set_best_friend: Pet__set_best_friend__output_type,
@@ -1,3 +1,3 @@
 import type React from 'react';
 import { RefetchQueryNormalizationArtifact } from '@isograph/react';
-export type Pet____refetch__output_type = (params?: any) => [string, () => void];
\ No newline at end of file
+export type Pet____refetch__output_type = (params?: Record<PropertyKey, never>) => [string, () => void];
\ No newline at end of file
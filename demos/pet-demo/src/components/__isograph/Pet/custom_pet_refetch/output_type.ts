@@ -1,3 +1,5 @@
 import type React from 'react';
 import { RefetchQueryNormalizationArtifact } from '@isograph/react';
-export type Pet__custom_pet_refetch__output_type = (params?: any) => [string, () => void];
\ No newline at end of file
+export type Pet__custom_pet_refetch__output_type = (params: {
+  readonly id: string,
+}) => [string, () => void];
\ No newline at end of file
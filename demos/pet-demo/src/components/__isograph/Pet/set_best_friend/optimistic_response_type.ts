@@ -0,0 +1,33 @@
+export type Pet__set_best_friend__optimistic_response_type = {
+  readonly age?: number,
+  readonly best_friend_relationship?: ({
+    readonly best_friend?: {
+      readonly id?: string,
+      readonly name?: string,
+      readonly picture?: string,
+    },
+    readonly picture_together?: (string | null),
+  } | null),
+  readonly checkins?: ReadonlyArray<{
+    readonly id?: string,
+    readonly location?: string,
+    readonly time?: string,
+  }>,
+  readonly favorite_phrase?: (string | null),
+  readonly id?: string,
+  readonly name?: string,
+  readonly nickname?: (string | null),
+  readonly potential_new_best_friends?: ReadonlyArray<{
+    readonly id?: string,
+    readonly name?: string,
+  }>,
+  readonly stats?: ({
+    readonly cuteness?: (number | null),
+    readonly energy?: (number | null),
+    readonly hunger?: (number | null),
+    readonly intelligence?: (number | null),
+    readonly sociability?: (number | null),
+    readonly weight?: (number | null),
+  } | null),
+  readonly tagline?: string,
+};
@@ -1,3 +1,6 @@
 import type React from 'react';
 import { RefetchQueryNormalizationArtifact } from '@isograph/react';
-export type Pet__set_best_friend_do_not_use__output_type = (params?: any) => [string, () => void];
\ No newline at end of file
+export type Pet__set_best_friend_do_not_use__output_type = (params: {
+  readonly id: string,
+  readonly new_best_friend_id: string,
+}) => [string, () => void];
\ No newline at end of file
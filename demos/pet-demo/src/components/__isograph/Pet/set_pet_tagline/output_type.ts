@@ -1,3 +1,7 @@
 import type React from 'react';
 import { RefetchQueryNormalizationArtifact } from '@isograph/react';
-export type Pet__set_pet_tagline__output_type = (params?: any) => [string, () => void];
\ No newline at end of file
+export type Pet__set_pet_tagline__output_type = (params: {
+  readonly input: {
+    readonly tagline: string,
+  },
+}) => [string, () => void];
\ No newline at end of file
@@ -1,3 +1,5 @@
 import type React from 'react';
 import { RefetchQueryNormalizationArtifact } from '@isograph/react';
-export type PetStats__refetch_pet_stats__output_type = (params?: any) => [string, () => void];
\ No newline at end of file
+export type PetStats__refetch_pet_stats__output_type = (params: {
+  readonly id: string,
+}) => [string, () => void];
\ No newline at end of file
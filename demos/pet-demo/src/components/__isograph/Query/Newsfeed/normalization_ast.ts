@@ -17,15 +17,14 @@ const normalizationAst: NormalizationAst = {
           kind: "Linked",
           fieldName: "newsfeed",
           arguments: [
-            [
-              "skip",
-              { kind: "Literal", value: 0 },
-            ],
-
             [
               "limit",
               { kind: "Literal", value: 6 },
             ],
+            [
+              "skip",
+              { kind: "Literal", value: 0 },
+            ],
           ],
           concreteType: null,
           selections: [
@@ -1,7 +1,7 @@
 export default 'query Newsfeed {\
   viewer {\
     id,\
-    newsfeed____skip___l_0____limit___l_6: newsfeed(skip: 0, limit: 6) {\
+    newsfeed____limit___l_6____skip___l_0: newsfeed(limit: 6, skip: 0) {\
       __typename,\
       ... on AdItem {\
         id,\
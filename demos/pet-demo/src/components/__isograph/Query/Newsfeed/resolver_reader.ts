@@ -17,15 +17,14 @@ const readerAst: ReaderAst<Query__Newsfeed__param> = [
         kind: "Resolver",
         alias: "initial",
         arguments: [
-          [
-            "skip",
-            { kind: "Literal", value: 0 },
-          ],
-
           [
             "limit",
             { kind: "Literal", value: 6 },
           ],
+          [
+            "skip",
+            { kind: "Literal", value: 0 },
+          ],
         ],
         readerArtifact: Viewer__NewsfeedPaginationComponent__resolver_reader,
         usedRefetchQueries: [],
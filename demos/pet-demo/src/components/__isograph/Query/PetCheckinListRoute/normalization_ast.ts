@@ -22,15 +22,14 @@ const normalizationAst: NormalizationAst = {
           kind: "Linked",
           fieldName: "checkins",
           arguments: [
-            [
-              "skip",
-              { kind: "Literal", value: 0 },
-            ],
-
             [
               "limit",
               { kind: "Literal", value: 1 },
             ],
+            [
+              "skip",
+              { kind: "Literal", value: 0 },
+            ],
           ],
           concreteType: "Checkin",
           selections: [
@@ -1,7 +1,7 @@
 export default 'query PetCheckinListRoute($id: ID!) {\
   pet____id___v_id: pet(id: $id) {\
     id,\
-    checkins____skip___l_0____limit___l_1: checkins(skip: 0, limit: 1) {\
+    checkins____limit___l_1____skip___l_0: checkins(limit: 1, skip: 0) {\
       id,\
       location,\
     },\
@@ -0,0 +1,40 @@
+import type { RefetchQueryIndex } from '@isograph/react';
+import refetchQuery0 from './__refetch__0';
+
+const refetchQueryIndex: RefetchQueryIndex = [
+  {
+    artifact: refetchQuery0,
+    clientField: "Checkin.make_super",
+    operationKind: "mutation",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "pet",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+      {
+        kind: "Linked",
+        fieldName: "checkins",
+        arguments: [
+          [
+            "limit",
+            { kind: "Literal", value: 1 },
+          ],
+          [
+            "skip",
+            { kind: "Literal", value: 0 },
+          ],
+        ],
+      },
+    ],
+    allowedVariables: ["checkin_id", ],
+    requiredVariables: ["checkin_id", ],
+  },
+];
+
+export default refetchQueryIndex;
@@ -38,7 +38,6 @@ const normalizationAst: NormalizationAst = {
                 "name",
                 { kind: "String", value: "ThisIsJustHereToTestObjectLiterals" },
               ],
-
             ]
           },
         ],
@@ -40,7 +40,6 @@ const readerAst: ReaderAst<Query__PetDetailDeferredRoute__param> = [
               "name",
               { kind: "String", value: "ThisIsJustHereToTestObjectLiterals" },
             ],
-
           ]
         },
       ],
@@ -12,7 +12,6 @@ const normalizationAst: NormalizationAst = {
           "id",
           { kind: "Variable", name: "id" },
         ],
-
         [
           "new_best_friend_id",
           { kind: "Variable", name: "new_best_friend_id" },
@@ -77,12 +76,11 @@ const normalizationAst: NormalizationAst = {
               fieldName: "checkins",
               arguments: [
                 [
-                  "skip",
+                  "limit",
                   { kind: "Literal", value: null },
                 ],
-
                 [
-                  "limit",
+                  "skip",
                   { kind: "Literal", value: null },
                 ],
               ],
@@ -1,4 +1,4 @@
-export default 'query Query__custom_pet_refetch($id: ID!) {\
+export default 'query PetDetailRoute__custom_pet_refetch($id: ID!) {\
   node____id___v_id: node(id: $id) {\
     ... on Pet {\
       __typename,\
@@ -12,7 +12,7 @@ export default 'query Query__custom_pet_refetch($id: ID!) {\
         },\
         picture_together,\
       },\
-      checkins____skip___l_null____limit___l_null: checkins(skip: null, limit: null) {\
+      checkins____limit___l_null____skip___l_null: checkins(limit: null, skip: null) {\
         id,\
         location,\
         time,\
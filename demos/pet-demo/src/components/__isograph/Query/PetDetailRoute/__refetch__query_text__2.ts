@@ -1,4 +1,4 @@
-export default 'mutation Query__set_best_friend($id: ID!, $new_best_friend_id: ID!) {\
+export default 'mutation PetDetailRoute__set_best_friend($id: ID!, $new_best_friend_id: ID!) {\
   set_pet_best_friend____id___v_id____new_best_friend_id___v_new_best_friend_id: set_pet_best_friend(id: $id, new_best_friend_id: $new_best_friend_id) {\
     pet {\
       id,\
@@ -11,7 +11,7 @@ export default 'mutation Query__set_best_friend($id: ID!, $new_best_friend_id: I
         },\
         picture_together,\
       },\
-      checkins____skip___l_null____limit___l_null: checkins(skip: null, limit: null) {\
+      checkins____limit___l_null____skip___l_null: checkins(limit: null, skip: null) {\
         id,\
         location,\
         time,\
@@ -1,4 +1,4 @@
-export default 'mutation Query__set_pet_tagline($input: SetPetTaglineParams!) {\
+export default 'mutation PetDetailRoute__set_pet_tagline($input: SetPetTaglineParams!) {\
   set_pet_tagline____input___v_input: set_pet_tagline(input: $input) {\
     pet {\
       id,\
@@ -11,7 +11,7 @@ export default 'mutation Query__set_pet_tagline($input: SetPetTaglineParams!) {\
         },\
         picture_together,\
       },\
-      checkins____skip___l_null____limit___l_null: checkins(skip: null, limit: null) {\
+      checkins____limit___l_null____skip___l_null: checkins(limit: null, skip: null) {\
         id,\
         location,\
         time,\
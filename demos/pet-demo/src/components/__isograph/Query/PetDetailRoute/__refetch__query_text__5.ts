@@ -1,4 +1,4 @@
-export default 'query Query__refetch_pet_stats($id: ID!) {\
+export default 'query PetDetailRoute__refetch_pet_stats($id: ID!) {\
   pet____id___v_id: pet(id: $id) {\
     stats {\
       cuteness,\
@@ -64,12 +64,11 @@ const normalizationAst: NormalizationAst = {
           fieldName: "checkins",
           arguments: [
             [
-              "skip",
+              "limit",
               { kind: "Literal", value: null },
             ],
-
             [
-              "limit",
+              "skip",
               { kind: "Literal", value: null },
             ],
           ],
@@ -10,7 +10,7 @@ export default 'query PetDetailRoute($id: ID!) {\
       },\
       picture_together,\
     },\
-    checkins____skip___l_null____limit___l_null: checkins(skip: null, limit: null) {\
+    checkins____limit___l_null____skip___l_null: checkins(limit: null, skip: null) {\
       id,\
       location,\
       time,\
@@ -0,0 +1,145 @@
+import type { RefetchQueryIndex } from '@isograph/react';
+import refetchQuery0 from './__refetch__0';
+import refetchQuery1 from './__refetch__1';
+import refetchQuery2 from './__refetch__2';
+import refetchQuery3 from './__refetch__3';
+import refetchQuery4 from './__refetch__4';
+import refetchQuery5 from './__refetch__5';
+
+const refetchQueryIndex: RefetchQueryIndex = [
+  {
+    artifact: refetchQuery0,
+    clientField: "Pet.__refetch",
+    operationKind: "query",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "pet",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+    ],
+    allowedVariables: ["id", ],
+    requiredVariables: ["id", ],
+  },
+  {
+    artifact: refetchQuery1,
+    clientField: "Pet.custom_pet_refetch",
+    operationKind: "query",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "pet",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+    ],
+    allowedVariables: ["id", ],
+    requiredVariables: ["id", ],
+  },
+  {
+    artifact: refetchQuery2,
+    clientField: "Pet.set_best_friend",
+    operationKind: "mutation",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "pet",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+    ],
+    allowedVariables: ["id", "new_best_friend_id", ],
+    requiredVariables: ["id", "new_best_friend_id", ],
+  },
+  {
+    artifact: refetchQuery3,
+    clientField: "Pet.set_pet_tagline",
+    operationKind: "mutation",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "pet",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+    ],
+    allowedVariables: ["input", ],
+    requiredVariables: ["input", ],
+  },
+  {
+    artifact: refetchQuery4,
+    clientField: "Checkin.make_super",
+    operationKind: "mutation",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "pet",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+      {
+        kind: "Linked",
+        fieldName: "checkins",
+        arguments: [
+          [
+            "limit",
+            { kind: "Literal", value: null },
+          ],
+          [
+            "skip",
+            { kind: "Literal", value: null },
+          ],
+        ],
+      },
+    ],
+    allowedVariables: ["checkin_id", ],
+    requiredVariables: ["checkin_id", ],
+  },
+  {
+    artifact: refetchQuery5,
+    clientField: "PetStats.refetch_pet_stats",
+    operationKind: "query",
+    path: [
+      {
+        kind: "Linked",
+        fieldName: "pet",
+        arguments: [
+          [
+            "id",
+            { kind: "Variable", name: "id" },
+          ],
+        ],
+      },
+      {
+        kind: "Linked",
+        fieldName: "stats",
+        arguments: null,
+      },
+    ],
+    allowedVariables: ["id", ],
+    requiredVariables: ["id", ],
+  },
+];
+
+export default refetchQueryIndex;
@@ -3,7 +3,7 @@ export default 'query NewsfeedPaginationComponent($skip: Int!, $limit: Int!, $id
     ... on Viewer {\
       __typename,\
       id,\
-      newsfeed____skip___v_skip____limit___v_limit: newsfeed(skip: $skip, limit: $limit) {\
+      newsfeed____limit___v_limit____skip___v_skip: newsfeed(limit: $limit, skip: $skip) {\
         __typename,\
         ... on AdItem {\
           id,\
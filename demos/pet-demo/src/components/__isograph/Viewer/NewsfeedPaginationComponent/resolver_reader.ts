@@ -12,15 +12,14 @@ const readerAst: ReaderAst<Viewer__NewsfeedPaginationComponent__param> = [
     fieldName: "newsfeed",
     alias: null,
     arguments: [
-      [
-        "skip",
-        { kind: "Variable", name: "skip" },
-      ],
-
       [
         "limit",
         { kind: "Variable", name: "limit" },
       ],
+      [
+        "skip",
+        { kind: "Variable", name: "skip" },
+      ],
     ],
     condition: null,
     isUpdatable: false,
@@ -1,4 +1,4 @@
-import type { IsographEntrypoint } from '@isograph/react';
+import type { IsographEntrypoint, IsographRuntimeVersion } from '@isograph/react';
 import { type AdItem__AdItemDisplayWrapper__param } from './AdItem/AdItemDisplayWrapper/param_type';
 import { type AdItem__AdItemDisplay__param } from './AdItem/AdItemDisplay/param_type';
 import { type BlogItem__BlogItemDisplay__param } from './BlogItem/BlogItemDisplay/param_type';
@@ -38,6 +38,19 @@ import entrypoint_Query__PetDetailDeferredRoute from '../__isograph/Query/PetDet
 import entrypoint_Query__PetDetailRoute from '../__isograph/Query/PetDetailRoute/entrypoint';
 import entrypoint_Query__PetFavoritePhrase from '../__isograph/Query/PetFavoritePhrase/entrypoint';
 
+// The version of the Isograph compiler that generated this file.
+export const ISOGRAPH_COMPILER_VERSION = '0.3.1';
+
+// If the following lines fail to type check, the installed version of
+// @isograph/react is not compatible with version 0.3.1 of the Isograph
+// compiler, which generated this file. Install a version of @isograph/react
+// that matches `0.3.${string}`, and recompile.
+type AssertRuntimeVersionIsCompatible<
+  TRuntimeVersion extends `0.3.${string}`,
+> = TRuntimeVersion;
+export type IsographRuntimeVersionCheck =
+  AssertRuntimeVersionIsCompatible<IsographRuntimeVersion>;
+
 // This is the type given to regular client fields.
 // This means that the type of the exported iso literal is exactly
 // the type of the passed-in function, which takes one parameter
@@ -65,8 +78,16 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
   ? Whitespace<In>
   : In;
 
+// Client fields and pointers can be preceded by a description.
+type WithoutDescription<In> = Whitespace<In> extends `"""${string}"""${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In> extends `"${string}"${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In>;
+
 // This is a recursive TypeScript type that matches strings that
-// start with whitespace, followed by TString. So e.g. if we have
+// start with whitespace and an optional description, followed by
+// TString. So e.g. if we have
 // ```
 // export function iso<T>(
 //   isographLiteralText: T & MatchesWhitespaceAndString<'field Query.foo', T>
@@ -84,7 +105,7 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
 type MatchesWhitespaceAndString<
   TString extends string,
   T
-> = Whitespace<T> extends `${TString}${string}` ? T : never;
+> = WithoutDescription<T> extends `${TString}${string}` ? T : never;
 
 export function iso<T>(
   param: T & MatchesWhitespaceAndString<'field AdItem.AdItemDisplayWrapper', T>
@@ -162,6 +183,11 @@ export function iso<T>(
   param: T & MatchesWhitespaceAndString<'field Pet.PetTaglineCard', T>
 ): IdentityWithParamComponent<Pet__PetTaglineCard__param>;
 
+/**
+Pet.PetUpdater
+A component to test behavior with respect to mutations.
+You can update the best friend and the tagline.
+*/
 export function iso<T>(
   param: T & MatchesWhitespaceAndString<'field Pet.PetUpdater', T>
 ): IdentityWithParamComponent<Pet__PetUpdater__param>;
@@ -6,15 +6,14 @@ const normalizationAst: NormalizationAst = {
       kind: "Linked",
       fieldName: "getAllPokemon",
       arguments: [
-        [
-          "take",
-          { kind: "Literal", value: 232 },
-        ],
-
         [
           "offset",
           { kind: "Literal", value: 93 },
         ],
+        [
+          "take",
+          { kind: "Literal", value: 232 },
+        ],
       ],
       concreteType: "Pokemon",
       selections: [
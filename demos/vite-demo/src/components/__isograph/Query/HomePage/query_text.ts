@@ -1,5 +1,5 @@
 export default 'query HomePage {\
-  getAllPokemon____take___l_232____offset___l_93: getAllPokemon(take: 232, offset: 93) {\
+  getAllPokemon____offset___l_93____take___l_232: getAllPokemon(offset: 93, take: 232) {\
     bulbapediaPage,\
     forme,\
     key,\
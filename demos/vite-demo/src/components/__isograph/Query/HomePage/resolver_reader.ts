@@ -9,15 +9,14 @@ const readerAst: ReaderAst<Query__HomePage__param> = [
     fieldName: "getAllPokemon",
     alias: null,
     arguments: [
-      [
-        "take",
-        { kind: "Literal", value: 232 },
-      ],
-
       [
         "offset",
         { kind: "Literal", value: 93 },
       ],
+      [
+        "take",
+        { kind: "Literal", value: 232 },
+      ],
     ],
     condition: null,
     isUpdatable: false,
@@ -19,10 +19,15 @@ const sidebars: SidebarsConfig = {
     'loadable-fields',
     'pagination',
     'mutation',
+    'updatable-fields',
     'conditional-fetching',
     'abstract-types',
+    'client-pointers',
     'data-driven-dependencies',
     'parameters',
+    'rest',
+    'grpc',
+    'mixed-protocols',
     'faq',
     {
       type: 'category',
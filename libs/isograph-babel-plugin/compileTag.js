@@ -41,9 +41,9 @@ function compileTag(t, path, config) {
   return false;
 }
 
+// Client fields can be preceded by a description, which is skipped.
 const typeAndFieldRegex = new RegExp(
-  '\\s*(entrypoint|field)\\s*([^\\.\\s]+)\\.([^\\s\\(]+)',
-  'm',
+  '^\\s*(?:"""[\\s\\S]*?"""\\s*|"[^"\\n]*"\\s*)?(entrypoint|field)\\s*([^\\.\\s]+)\\.([^\\s\\(]+)',
 );
 
 /**
@@ -103,9 +103,15 @@ function compileImportStatement(t, path, type, field, artifactType, config) {
     config.config['artifact_directory'] ?? config.config['project_root'],
   );
   const module = config.config['options']?.['module'];
+  const artifactLayout = config.config['options']?.['artifact_layout'];
 
   const fileToArtifactDir = pathModule.relative(folder, artifactDirectory);
-  const artifactDirToArtifact = `/__isograph/${type}/${field}/${artifactType}.ts`;
+  const artifactDirToArtifact = `/__isograph/${getArtifactPath(
+    type,
+    field,
+    artifactType,
+    artifactLayout,
+  )}.ts`;
   let fileToArtifact = pathModule.join(
     fileToArtifactDir,
     artifactDirToArtifact,
@@ -148,4 +154,24 @@ function compileImportStatement(t, path, type, field, artifactType, config) {
   }
 }
 
+/**
+ * The path of an artifact, relative to the __isograph folder. This must match
+ * the artifact_layout option of the compiler.
+ *
+ * @param {string} type
+ * @param {string} field
+ * @param {string} artifactType
+ * @param {string | undefined} artifactLayout
+ */
+function getArtifactPath(type, field, artifactType, artifactLayout) {
+  switch (artifactLayout) {
+    case 'flat':
+      return `${type}__${field}__${artifactType}`;
+    case 'folder_per_type':
+      return `${type}/${field}__${artifactType}`;
+    default:
+      return `${type}/${field}/${artifactType}`;
+  }
+}
+
 module.exports = compileTag;
@@ -84,9 +84,11 @@ export type IsographNetworkFunction = (
   variables: Variables,
 ) => Promise<any>;
 
-export type Link = {
+// A pointer to a record in the store. Link fields are typed with the typename of
+// their parent, e.g. Link<'Pet'>.
+export type Link<TTypeName extends TypeName = TypeName> = {
   readonly __link: DataId;
-  readonly __typename: TypeName;
+  readonly __typename: TTypeName;
 };
 
 export type DataTypeValue =
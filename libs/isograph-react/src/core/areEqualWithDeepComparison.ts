@@ -116,6 +116,28 @@ export function mergeObjectsUsingReaderAst(
         }
         break;
       }
+      case 'Conditional': {
+        const key = field.alias;
+        // @ts-expect-error
+        const oldValue = oldItemObject[key];
+        // @ts-expect-error
+        const newValue = newItemObject[key];
+
+        if (oldValue == null || newValue == null) {
+          if (oldValue !== newValue) {
+            canRecycle = false;
+          }
+        } else if (
+          mergeObjectsUsingReaderAst(
+            [field.selection],
+            oldItemObject,
+            newItemObject,
+          ) !== oldItemObject
+        ) {
+          canRecycle = false;
+        }
+        break;
+      }
       case 'ImperativelyLoadedField':
       case 'LoadablySelectedField':
         break;
@@ -467,6 +467,11 @@ function normalizeScalarField(
   const networkResponseData = networkResponseParentRecord[networkResponseKey];
   const parentRecordKey = getParentRecordKey(astNode, variables);
 
+  if (networkResponseData === undefined) {
+    // The field was not fetched, e.g. because it was selected with @skip or @include.
+    return false;
+  }
+
   if (
     networkResponseData == null ||
     isScalarOrEmptyArray(networkResponseData)
@@ -495,6 +500,12 @@ function normalizeLinkedField(
   const networkResponseKey = getNetworkResponseKey(astNode);
   const networkResponseData = networkResponseParentRecord[networkResponseKey];
   const parentRecordKey = getParentRecordKey(astNode, variables);
+
+  if (networkResponseData === undefined) {
+    // The field was not fetched, e.g. because it was selected with @skip or @include.
+    return false;
+  }
+
   const existingValue = targetParentRecord[parentRecordKey];
 
   if (networkResponseData == null) {
@@ -721,7 +732,7 @@ export function getParentRecordKey(
   return parentRecordKey;
 }
 
-function getStoreKeyChunkForArgumentValue(
+export function getStoreKeyChunkForArgumentValue(
   argumentValue: ArgumentValue,
   variables: Variables,
 ): VariableValue {
@@ -737,6 +748,11 @@ function getStoreKeyChunkForArgumentValue(
         }),
       );
     }
+    case 'List': {
+      return argumentValue.value.map((item) =>
+        getStoreKeyChunkForArgumentValue(item, variables),
+      );
+    }
     case 'Literal': {
       return argumentValue.value;
     }
@@ -802,8 +818,14 @@ function getArgumentValueChunk(argumentValue: ArgumentValue): string {
         '_c'
       );
     }
+    case 'List': {
+      return (
+        'a_' + argumentValue.value.map(getArgumentValueChunk).join('_') + '_c'
+      );
+    }
     case 'Literal': {
-      return 'l_' + argumentValue.value;
+      // replace e.g. the period in 1.5 with an underscore
+      return 'l_' + String(argumentValue.value).replaceAll(/\W/g, '_');
     }
     case 'Variable': {
       return 'v_' + argumentValue.name;
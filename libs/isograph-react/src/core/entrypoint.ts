@@ -35,6 +35,19 @@ export type IsographEntrypoint<
     TClientFieldValue
   >;
   readonly concreteType: TypeName;
+  readonly cacheHints?: EntrypointCacheHints;
+};
+
+// The cache hints (e.g. from @cacheControl directives in the schema) of the
+// fields that the entrypoint's query selects. The network layer can use them to
+// choose a fetch policy. Omitted if none of the fields have a cache hint.
+export type EntrypointCacheHints = {
+  // The smallest maxAge (in seconds) of the selected fields that have one.
+  readonly maxAge: number | null;
+  // Whether any selected field has a maxAge of 0.
+  readonly uncacheable: boolean;
+  // Whether any selected field is specific to the user (scope: PRIVATE).
+  readonly private: boolean;
 };
 
 export type IsographEntrypointLoader<
@@ -58,6 +71,18 @@ export type NormalizationAstNodes = ReadonlyArray<NormalizationAstNode>;
 export type NormalizationAst = {
   readonly kind: 'NormalizationAst';
   readonly selections: NormalizationAstNodes;
+  readonly typeMetadata?: NormalizationTypeMetadata;
+};
+
+// The arguments of the directives listed in the propagated_object_directives
+// config option, by type and directive name, for the types that the query can
+// fetch, e.g. { Pet: { cacheControl: { maxAge: 60 } } }.
+export type NormalizationTypeMetadata = {
+  readonly [typeName: string]: {
+    readonly [directiveName: string]: {
+      readonly [argumentName: string]: unknown;
+    };
+  };
 };
 
 export type NormalizationAstLoader = {
@@ -98,6 +123,34 @@ export type RefetchQueryNormalizationArtifactWrapper = {
   readonly allowedVariables: string[];
 };
 
+// The linked fields and type refinements that lead from the root of an
+// entrypoint to the object that a refetch query refetches.
+export type RefetchQueryPathSegment =
+  | {
+      readonly kind: 'Linked';
+      readonly fieldName: string;
+      readonly arguments: Arguments | null;
+    }
+  | {
+      readonly kind: 'InlineFragment';
+      readonly type: string;
+    };
+
+// Describes one of the refetch queries that are nested in an entrypoint, so
+// that they can be prefetched or batched without traversing reader ASTs.
+export type RefetchQueryIndexEntry = {
+  readonly artifact: RefetchQueryNormalizationArtifact;
+  // e.g. "Pet.__refetch"
+  readonly clientField: string;
+  readonly operationKind: string;
+  readonly path: ReadonlyArray<RefetchQueryPathSegment>;
+  readonly allowedVariables: ReadonlyArray<string>;
+  // The variables of the refetch query that are non-null and have no default value.
+  readonly requiredVariables: ReadonlyArray<string>;
+};
+
+export type RefetchQueryIndex = ReadonlyArray<RefetchQueryIndexEntry>;
+
 export function assertIsEntrypoint<
   TReadFromStore extends UnknownTReadFromStore,
   TClientFieldValue,
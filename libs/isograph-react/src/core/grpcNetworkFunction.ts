@@ -0,0 +1,117 @@
+import type { Variables, VariableValue } from './FragmentReference';
+import type { IsographNetworkFunction } from './IsographEnvironment';
+import {
+  conditionsAreMet,
+  evaluateRequestValue,
+  type RequestCondition,
+  type RequestValue,
+} from './requestValues';
+
+/**
+ * The query text of entrypoints compiled with the grpc network protocol is a
+ * JSON description of the methods to call, one per root field.
+ */
+type GrpcRequests = {
+  readonly kind: 'GrpcRequests';
+  readonly requests: ReadonlyArray<GrpcRequest>;
+};
+
+type GrpcRequest = {
+  readonly responseKey: string;
+  /** The fully qualified name of the service, e.g. pets.PetService */
+  readonly service: string;
+  /** e.g. GetPet */
+  readonly method: string;
+  readonly request: { readonly [jsonName: string]: RequestValue };
+  readonly response: ResponseDefaults | null;
+  readonly conditions: ReadonlyArray<RequestCondition>;
+};
+
+/**
+ * The proto3 JSON mapping omits fields with default values, but the store
+ * treats missing fields as not fetched. So the query text includes the default
+ * value of each selected field.
+ */
+type ResponseDefaults = {
+  readonly [responseKey: string]: {
+    readonly default: unknown;
+    readonly fields?: ResponseDefaults;
+  };
+};
+
+/**
+ * Calls a unary method, and resolves with the response message. The request
+ * and response messages use the proto3 JSON mapping.
+ */
+export type GrpcTransport = (
+  service: string,
+  method: string,
+  request: { readonly [jsonName: string]: VariableValue },
+) => Promise<unknown>;
+
+/**
+ * Create a network function for entrypoints compiled with the grpc network
+ * protocol. Each root field is fetched by calling its method with the
+ * transport (in parallel), and the response is stored under that field.
+ */
+export function createGrpcNetworkFunction(
+  transport: GrpcTransport,
+): IsographNetworkFunction {
+  return async (queryText: string, variables: Variables) => {
+    const grpcRequests: GrpcRequests = JSON.parse(queryText);
+    const data: { [responseKey: string]: unknown } = {};
+    const errors: { message: string; responseKey: string }[] = [];
+
+    await Promise.all(
+      grpcRequests.requests
+        .filter((request) => conditionsAreMet(request.conditions, variables))
+        .map(async (request) => {
+          const requestMessage: { [jsonName: string]: VariableValue } = {};
+          for (const [jsonName, value] of Object.entries(request.request)) {
+            const evaluatedValue = evaluateRequestValue(value, variables);
+            if (evaluatedValue !== undefined) {
+              requestMessage[jsonName] = evaluatedValue;
+            }
+          }
+          try {
+            const response = await transport(
+              request.service,
+              request.method,
+              requestMessage,
+            );
+            data[request.responseKey] =
+              request.response == null
+                ? response
+                : withDefaults(response, request.response);
+          } catch (error) {
+            errors.push({
+              message: `${request.service}/${request.method} failed: ${String(error)}`,
+              responseKey: request.responseKey,
+            });
+            data[request.responseKey] = null;
+          }
+        }),
+    );
+
+    return errors.length === 0 ? { data } : { data, errors };
+  };
+}
+
+function withDefaults(value: unknown, defaults: ResponseDefaults): unknown {
+  if (Array.isArray(value)) {
+    return value.map((item) => withDefaults(item, defaults));
+  }
+  if (value == null || typeof value !== 'object') {
+    return value;
+  }
+  const object: { [responseKey: string]: unknown } = { ...value };
+  for (const [responseKey, fieldDefaults] of Object.entries(defaults)) {
+    const fieldValue = object[responseKey];
+    if (fieldValue === undefined) {
+      object[responseKey] = fieldDefaults.default;
+    } else if (fieldDefaults.fields != null) {
+      object[responseKey] = withDefaults(fieldValue, fieldDefaults.fields);
+    }
+  }
+  return object;
+}
@@ -0,0 +1,108 @@
+import type { Variables } from './FragmentReference';
+import type { IsographNetworkFunction } from './IsographEnvironment';
+
+/**
+ * The query text of entrypoints compiled with the mixed network protocol, whose
+ * root fields are fetched with more than one protocol.
+ */
+type MixedRequests = {
+  readonly kind: 'MixedRequests';
+  readonly graphql: string | null;
+  readonly rest: unknown;
+  readonly grpc: unknown;
+};
+
+type Protocol = 'graphql' | 'rest' | 'grpc';
+
+export type MixedNetworkFunctions = {
+  readonly [P in Protocol]?: IsographNetworkFunction;
+};
+
+type NetworkResponse = {
+  readonly data?: { readonly [responseKey: string]: unknown } | null;
+  readonly errors?: ReadonlyArray<unknown>;
+};
+
+/**
+ * Create a network function for entrypoints compiled with the mixed network
+ * protocol. Entrypoints whose root fields are all fetched with one protocol
+ * are passed to that protocol's network function (e.g. the function returned
+ * by createRestNetworkFunction.) Otherwise, each protocol's network function
+ * is called (in parallel), and the responses are combined.
+ */
+export function createMixedNetworkFunction(
+  networkFunctions: MixedNetworkFunctions,
+): IsographNetworkFunction {
+  return async (queryText: string, variables: Variables) => {
+    if (!queryText.startsWith('{')) {
+      return getNetworkFunction(networkFunctions, 'graphql')(
+        queryText,
+        variables,
+      );
+    }
+
+    const request: { readonly kind: string } = JSON.parse(queryText);
+    switch (request.kind) {
+      case 'RestRequests':
+        return getNetworkFunction(networkFunctions, 'rest')(
+          queryText,
+          variables,
+        );
+      case 'GrpcRequests':
+        return getNetworkFunction(networkFunctions, 'grpc')(
+          queryText,
+          variables,
+        );
+    }
+
+    const mixedRequests = request as MixedRequests;
+    const responses: NetworkResponse[] = await Promise.all([
+      ...(mixedRequests.graphql == null
+        ? []
+        : [
+            getNetworkFunction(networkFunctions, 'graphql')(
+              mixedRequests.graphql,
+              variables,
+            ),
+          ]),
+      ...(mixedRequests.rest == null
+        ? []
+        : [
+            getNetworkFunction(networkFunctions, 'rest')(
+              JSON.stringify(mixedRequests.rest),
+              variables,
+            ),
+          ]),
+      ...(mixedRequests.grpc == null
+        ? []
+        : [
+            getNetworkFunction(networkFunctions, 'grpc')(
+              JSON.stringify(mixedRequests.grpc),
+              variables,
+            ),
+          ]),
+    ]);
+
+    const data: { [responseKey: string]: unknown } = {};
+    const errors: unknown[] = [];
+    for (const response of responses) {
+      Object.assign(data, response.data);
+      errors.push(...(response.errors ?? []));
+    }
+    return errors.length === 0 ? { data } : { data, errors };
+  };
+}
+
+function getNetworkFunction(
+  networkFunctions: MixedNetworkFunctions,
+  protocol: Protocol,
+): IsographNetworkFunction {
+  const networkFunction = networkFunctions[protocol];
+  if (networkFunction == null) {
+    throw new Error(
+      `No network function was provided for ${protocol} requests. ` +
+        'This is indicative of a misconfigured mixed network function.',
+    );
+  }
+  return networkFunction;
+}
@@ -1,6 +1,7 @@
 import { CleanupFn } from '@isograph/disposable-types';
 import {
   getParentRecordKey,
+  getStoreKeyChunkForArgumentValue,
   insertIfNotExists,
   onNextChangeToRecord,
   type EncounteredIds,
@@ -39,6 +40,7 @@ import {
 import {
   ReaderAst,
   type LoadablySelectedField,
+  type ReaderCondition,
   type ReaderImperativelyLoadedField,
   type ReaderLinkedField,
   type ReaderNonLoadableResolverField,
@@ -265,6 +267,27 @@ function readData<TReadFromStore>(
         target[field.alias] = data.data;
         break;
       }
+      case 'Conditional': {
+        if (!areConditionsMet(field.conditions, root, variables)) {
+          target[field.alias] = null;
+          break;
+        }
+        const data = readData(
+          environment,
+          [field.selection],
+          root,
+          variables,
+          nestedRefetchQueries,
+          networkRequest,
+          networkRequestOptions,
+          mutableEncounteredRecords,
+        );
+        if (data.kind === 'MissingData') {
+          return data;
+        }
+        target[field.alias] = (data.data as any)[field.alias];
+        break;
+      }
 
       default: {
         // Ensure we have covered all variants
@@ -471,6 +494,8 @@ function generateChildVariableMap(
   for (const [name, value] of fieldArguments) {
     if (value.kind === 'Object') {
       childVars[name] = generateChildVariableMap(variables, value.value);
+    } else if (value.kind === 'List') {
+      childVars[name] = getStoreKeyChunkForArgumentValue(value, variables);
     } else if (value.kind === 'Variable') {
       const variable = variables[value.name];
       // Variable could be null if it was not provided but has a default case,
@@ -485,6 +510,32 @@ function generateChildVariableMap(
   return childVars;
 }
 
+function areConditionsMet(
+  conditions: ReadonlyArray<ReaderCondition>,
+  root: Link,
+  variables: Variables,
+): boolean {
+  return conditions.every((condition) => {
+    switch (condition.kind) {
+      case 'Include':
+        return (
+          getStoreKeyChunkForArgumentValue(condition.value, variables) === true
+        );
+      case 'Skip':
+        return (
+          getStoreKeyChunkForArgumentValue(condition.value, variables) !== true
+        );
+      case 'ConcreteType':
+        return condition.concreteTypes.includes(root.__typename);
+      default: {
+        const _: never = condition;
+        _;
+        throw new Error('Unexpected case');
+      }
+    }
+  });
+}
+
 function writeQueryArgsToVariables(
   targetVariables: any,
   queryArgs: Arguments | null,
@@ -503,6 +554,13 @@ function writeQueryArgsToVariables(
         );
         break;
       }
+      case 'List': {
+        targetVariables[name] = getStoreKeyChunkForArgumentValue(
+          argType,
+          variables,
+        );
+        break;
+      }
       case 'Variable': {
         targetVariables[name] = variables[argType.name];
         break;
@@ -538,7 +596,8 @@ export function readResolverFieldData(
   networkRequestOptions: NetworkRequestReaderOptions,
   mutableEncounteredRecords: EncounteredIds,
 ): ReadDataResult<unknown> {
-  const usedRefetchQueries = field.usedRefetchQueries;
+  const { readerArtifact, usedRefetchQueries } =
+    field.concreteOverrides?.[root.__typename] ?? field;
   const resolverRefetchQueries = usedRefetchQueries.map((index) => {
     const resolverRefetchQuery = nestedRefetchQueries[index];
     if (resolverRefetchQuery == null) {
@@ -549,9 +608,13 @@ export function readResolverFieldData(
     return resolverRefetchQuery;
   });
 
+  // The arguments passed to the client field (e.g. the $id in MyField(id: $id))
+  // are the only variables that are visible to it.
+  const childVariables = generateChildVariableMap(variables, field.arguments);
+
   const readerWithRefetchQueries = {
     kind: 'ReaderWithRefetchQueries',
-    readerArtifact: field.readerArtifact,
+    readerArtifact,
     nestedRefetchQueries: resolverRefetchQueries,
   } satisfies ReaderWithRefetchQueries<any, any>;
 
@@ -559,17 +622,17 @@ export function readResolverFieldData(
     kind: 'FragmentReference',
     readerWithRefetchQueries: wrapResolvedValue(readerWithRefetchQueries),
     root,
-    variables: generateChildVariableMap(variables, field.arguments),
+    variables: childVariables,
     networkRequest,
   } satisfies FragmentReference<any, any>;
 
-  switch (field.readerArtifact.kind) {
+  switch (readerArtifact.kind) {
     case 'EagerReaderArtifact': {
       const data = readData(
         environment,
-        field.readerArtifact.readerAst,
+        readerArtifact.readerAst,
         root,
-        generateChildVariableMap(variables, field.arguments),
+        childVariables,
         resolverRefetchQueries,
         networkRequest,
         networkRequestOptions,
@@ -585,8 +648,8 @@ export function readResolverFieldData(
       }
       const firstParameter = {
         data: data.data,
-        parameters: variables,
-        startUpdate: field.readerArtifact.hasUpdatable
+        parameters: childVariables,
+        startUpdate: readerArtifact.hasUpdatable
           ? getOrCreateCachedStartUpdate(
               environment,
               fragment,
@@ -596,7 +659,7 @@ export function readResolverFieldData(
       };
       return {
         kind: 'Success',
-        data: field.readerArtifact.resolver(firstParameter),
+        data: readerArtifact.resolver(firstParameter),
       };
     }
     case 'ComponentReaderArtifact': {
@@ -604,14 +667,14 @@ export function readResolverFieldData(
         kind: 'Success',
         data: getOrCreateCachedComponent(
           environment,
-          field.readerArtifact.fieldName,
+          readerArtifact.fieldName,
           fragment,
           networkRequestOptions,
         ),
       };
     }
     default: {
-      let _: never = field.readerArtifact;
+      let _: never = readerArtifact;
       _;
       throw new Error('Unexpected kind');
     }
@@ -719,23 +782,24 @@ export function readLinkedFieldData(
       nestedRefetchQueries: [],
     } satisfies ReaderWithRefetchQueries<any, any>;
 
+    // The arguments of the field are the parameters of the client pointer, e.g.
+    // the id of a node pointer.
+    const conditionVariables = generateChildVariableMap(
+      variables,
+      field.arguments,
+    );
+
     const fragment = {
       kind: 'FragmentReference',
       readerWithRefetchQueries: wrapResolvedValue(readerWithRefetchQueries),
       root,
-      variables: generateChildVariableMap(
-        variables,
-        // TODO this is wrong
-        // should use field.condition.variables
-        // but it doesn't exist
-        [],
-      ),
+      variables: conditionVariables,
       networkRequest,
     } satisfies FragmentReference<any, any>;
 
     const condition = field.condition.resolver({
       data: data.data,
-      parameters: {},
+      parameters: conditionVariables,
       ...(field.condition.hasUpdatable
         ? {
             startUpdate: getOrCreateCachedStartUpdate(
@@ -16,7 +16,7 @@ import {
   IsographEnvironment,
   type Link,
 } from './IsographEnvironment';
-import { Arguments } from './util';
+import { ArgumentValue, Arguments } from './util';
 
 export type TopLevelReaderArtifact<
   TReadFromStore extends UnknownTReadFromStore,
@@ -84,7 +84,8 @@ export type ReaderAstNode =
   | ReaderNonLoadableResolverField
   | ReaderImperativelyLoadedField
   | LoadablySelectedField
-  | ReaderLinkField;
+  | ReaderLinkField
+  | ReaderConditionalField;
 
 // @ts-ignore
 export type ReaderAst<TReadFromStore> = ReadonlyArray<ReaderAstNode>;
@@ -115,6 +116,27 @@ export type ReaderLinkedField = {
   readonly isUpdatable: boolean;
 };
 
+// A field selected with @include or @skip, or a field selected on an abstract
+// type that only some of its concrete types define. If the conditions are not
+// met, the field was not fetched, and is read as null.
+export type ReaderConditionalField = {
+  readonly kind: 'Conditional';
+  readonly alias: string;
+  readonly conditions: ReadonlyArray<ReaderCondition>;
+  readonly selection: ReaderAstNode;
+};
+
+export type ReaderCondition =
+  | {
+      readonly kind: 'Include' | 'Skip';
+      readonly value: ArgumentValue;
+    }
+  | {
+      // Met if the __typename of the record is one of the concrete types.
+      readonly kind: 'ConcreteType';
+      readonly concreteTypes: ReadonlyArray<string>;
+    };
+
 export type ReaderNonLoadableResolverField = {
   readonly kind: 'Resolver';
   readonly alias: string;
@@ -122,6 +144,18 @@ export type ReaderNonLoadableResolverField = {
   readonly readerArtifact: TopLevelReaderArtifact<any, any, any>;
   readonly arguments: Arguments | null;
   readonly usedRefetchQueries: number[];
+  // A client field defined on an abstract type can be overridden by its
+  // concrete types. If the record's __typename has an override, that override
+  // is read instead.
+  readonly concreteOverrides?: {
+    readonly [typename: string]: ReaderConcreteOverride;
+  };
+};
+
+export type ReaderConcreteOverride = {
+  // TODO don't type this as any
+  readonly readerArtifact: TopLevelReaderArtifact<any, any, any>;
+  readonly usedRefetchQueries: number[];
 };
 
 export type ReaderImperativelyLoadedField = {
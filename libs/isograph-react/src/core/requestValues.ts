@@ -0,0 +1,56 @@
+import type { Variables, VariableValue } from './FragmentReference';
+
+// The query text of entrypoints compiled with the rest and grpc network
+// protocols describes the arguments of each request with these types.
+
+export type RequestCondition = {
+  readonly kind: 'include' | 'skip';
+  readonly value: RequestValue;
+};
+
+export type RequestValue =
+  | { readonly kind: 'Variable'; readonly name: string }
+  | { readonly kind: 'Literal'; readonly value: VariableValue }
+  | { readonly kind: 'Enum'; readonly value: string }
+  | { readonly kind: 'List'; readonly value: ReadonlyArray<RequestValue> }
+  | {
+      readonly kind: 'Object';
+      readonly value: { readonly [name: string]: RequestValue };
+    };
+
+export function conditionsAreMet(
+  conditions: ReadonlyArray<RequestCondition>,
+  variables: Variables,
+): boolean {
+  return conditions.every((condition) => {
+    const value = evaluateRequestValue(condition.value, variables) === true;
+    return condition.kind === 'include' ? value : !value;
+  });
+}
+
+export function evaluateRequestValue(
+  value: RequestValue,
+  variables: Variables,
+): VariableValue | undefined {
+  switch (value.kind) {
+    case 'Variable':
+      return variables[value.name];
+    case 'Literal':
+    case 'Enum':
+      return value.value;
+    case 'List':
+      return value.value.map(
+        (item) => evaluateRequestValue(item, variables) ?? null,
+      );
+    case 'Object': {
+      const object: { [name: string]: VariableValue } = {};
+      for (const [name, item] of Object.entries(value.value)) {
+        const evaluatedItem = evaluateRequestValue(item, variables);
+        if (evaluatedItem !== undefined) {
+          object[name] = evaluatedItem;
+        }
+      }
+      return object;
+    }
+  }
+}
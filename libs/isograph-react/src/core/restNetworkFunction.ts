@@ -0,0 +1,113 @@
+import type { Variables } from './FragmentReference';
+import type { IsographNetworkFunction } from './IsographEnvironment';
+import {
+  conditionsAreMet,
+  evaluateRequestValue,
+  type RequestCondition,
+  type RequestValue,
+} from './requestValues';
+
+/**
+ * The query text of entrypoints compiled with the rest network protocol is a
+ * JSON description of the requests to make, one per root field.
+ */
+type RestRequests = {
+  readonly kind: 'RestRequests';
+  readonly requests: ReadonlyArray<RestRequest>;
+};
+
+type RestRequest = {
+  readonly responseKey: string;
+  readonly method: string;
+  /** e.g. /pets/{petId} */
+  readonly path: string;
+  readonly pathParameters: { readonly [name: string]: RequestValue };
+  readonly queryParameters: { readonly [name: string]: RequestValue };
+  readonly body: RequestValue | null;
+  readonly conditions: ReadonlyArray<RequestCondition>;
+};
+
+/**
+ * Create a network function for entrypoints compiled with the rest network
+ * protocol. Each root field is fetched with a separate request (in parallel),
+ * and the JSON response is stored under that field.
+ *
+ * Headers (e.g. for authentication) can be passed in init.
+ */
+export function createRestNetworkFunction(
+  baseUrl: string,
+  init?: RequestInit,
+): IsographNetworkFunction {
+  return async (queryText: string, variables: Variables) => {
+    const restRequests: RestRequests = JSON.parse(queryText);
+    const data: { [responseKey: string]: unknown } = {};
+    const errors: { message: string; responseKey: string }[] = [];
+
+    await Promise.all(
+      restRequests.requests
+        .filter((request) => conditionsAreMet(request.conditions, variables))
+        .map(async (request) => {
+          const headers = new Headers(init?.headers);
+          if (request.body != null && !headers.has('Content-Type')) {
+            headers.set('Content-Type', 'application/json');
+          }
+          const response = await fetch(getUrl(baseUrl, request, variables), {
+            ...init,
+            method: request.method,
+            headers,
+            body:
+              request.body == null
+                ? null
+                : JSON.stringify(
+                    evaluateRequestValue(request.body, variables),
+                  ),
+          });
+          if (!response.ok) {
+            errors.push({
+              message: `${request.method} ${request.path} failed with status ${response.status}`,
+              responseKey: request.responseKey,
+            });
+            data[request.responseKey] = null;
+            return;
+          }
+          data[request.responseKey] =
+            response.status === 204 ? null : await response.json();
+        }),
+    );
+
+    return errors.length === 0 ? { data } : { data, errors };
+  };
+}
+
+function getUrl(
+  baseUrl: string,
+  request: RestRequest,
+  variables: Variables,
+): string {
+  const path = request.path.replace(/\{([^}]+)\}/g, (_, name: string) => {
+    const value = request.pathParameters[name];
+    return encodeURIComponent(
+      String(value == null ? '' : evaluateRequestValue(value, variables)),
+    );
+  });
+
+  const searchParams = new URLSearchParams();
+  for (const [name, value] of Object.entries(request.queryParameters)) {
+    const evaluatedValue = evaluateRequestValue(value, variables);
+    if (evaluatedValue == null) {
+      continue;
+    }
+    if (Array.isArray(evaluatedValue)) {
+      for (const item of evaluatedValue) {
+        searchParams.append(name, String(item));
+      }
+    } else {
+      searchParams.append(name, String(evaluatedValue));
+    }
+  }
+
+  const search = searchParams.toString();
+  return (
+    baseUrl.replace(/\/$/, '') + path + (search === '' ? '' : `?${search}`)
+  );
+}
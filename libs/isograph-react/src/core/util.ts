@@ -28,4 +28,8 @@ export type ArgumentValue =
   | {
       readonly kind: 'Object';
       readonly value: Arguments;
+    }
+  | {
+      readonly kind: 'List';
+      readonly value: readonly ArgumentValue[];
     };
@@ -0,0 +1,8 @@
+// The version of this package. The generated iso.ts file fails to type check
+// if this is not compatible with the version of the compiler that generated it,
+// and artifacts generated with the generate_reader_ast_types option fail to type
+// check if this is not exactly that version.
+//
+// This must be kept in sync with the version in package.json, and with the
+// version of the compiler (which has a test that checks this).
+export type IsographRuntimeVersion = '0.3.1';
@@ -26,6 +26,15 @@ export {
   type EncounteredIds,
 } from './core/cache';
 export { makeNetworkRequest } from './core/makeNetworkRequest';
+export { createRestNetworkFunction } from './core/restNetworkFunction';
+export {
+  createGrpcNetworkFunction,
+  type GrpcTransport,
+} from './core/grpcNetworkFunction';
+export {
+  createMixedNetworkFunction,
+  type MixedNetworkFunctions,
+} from './core/mixedNetworkFunction';
 export {
   ROOT_ID,
   type DataId,
@@ -57,6 +66,7 @@ export {
   type ReaderAstNode,
   type ReaderLinkedField,
   type ReaderNonLoadableResolverField,
+  type ReaderConcreteOverride,
   type ReaderScalarField,
   type TopLevelReaderArtifact,
   type LoadableField,
@@ -65,19 +75,26 @@ export {
   type ReaderImperativelyLoadedField,
   type LoadablySelectedField as ReaderLoadableField,
   type ReaderLinkField,
+  type ReaderConditionalField,
+  type ReaderCondition,
   type StartUpdate,
 } from './core/reader';
 export {
   type NormalizationAst,
   type NormalizationAstNode,
   type NormalizationAstNodes,
+  type NormalizationTypeMetadata,
   type NormalizationAstLoader,
   type NormalizationLinkedField,
   type NormalizationScalarField,
   type IsographEntrypoint,
+  type EntrypointCacheHints,
   assertIsEntrypoint,
   type RefetchQueryNormalizationArtifact,
   type RefetchQueryNormalizationArtifactWrapper,
+  type RefetchQueryPathSegment,
+  type RefetchQueryIndexEntry,
+  type RefetchQueryIndex,
   type ExtractProps,
   type ExtractReadFromStore,
   type ExtractResolverResult,
@@ -170,3 +187,4 @@ export {
   useImperativeLoadableField,
   type UseImperativeLoadableFieldReturn,
 } from './loadable-hooks/useImperativeLoadableField';
+export { type IsographRuntimeVersion } from './core/version';
@@ -1,4 +1,4 @@
-import type { IsographEntrypoint } from '@isograph/react';
+import type { IsographEntrypoint, IsographRuntimeVersion } from '@isograph/react';
 import { type Query__meNameSuccessor__param } from './Query/meNameSuccessor/param_type';
 import { type Query__meName__param } from './Query/meName/param_type';
 import { type Query__nodeField__param } from './Query/nodeField/param_type';
@@ -8,6 +8,19 @@ import entrypoint_Query__meName from '../__isograph/Query/meName/entrypoint';
 import entrypoint_Query__nodeField from '../__isograph/Query/nodeField/entrypoint';
 import entrypoint_Query__subquery from '../__isograph/Query/subquery/entrypoint';
 
+// The version of the Isograph compiler that generated this file.
+export const ISOGRAPH_COMPILER_VERSION = '0.3.1';
+
+// If the following lines fail to type check, the installed version of
+// @isograph/react is not compatible with version 0.3.1 of the Isograph
+// compiler, which generated this file. Install a version of @isograph/react
+// that matches `0.3.${string}`, and recompile.
+type AssertRuntimeVersionIsCompatible<
+  TRuntimeVersion extends `0.3.${string}`,
+> = TRuntimeVersion;
+export type IsographRuntimeVersionCheck =
+  AssertRuntimeVersionIsCompatible<IsographRuntimeVersion>;
+
 // This is the type given to regular client fields.
 // This means that the type of the exported iso literal is exactly
 // the type of the passed-in function, which takes one parameter
@@ -35,8 +48,16 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
   ? Whitespace<In>
   : In;
 
+// Client fields and pointers can be preceded by a description.
+type WithoutDescription<In> = Whitespace<In> extends `"""${string}"""${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In> extends `"${string}"${infer In}`
+  ? Whitespace<In>
+  : Whitespace<In>;
+
 // This is a recursive TypeScript type that matches strings that
-// start with whitespace, followed by TString. So e.g. if we have
+// start with whitespace and an optional description, followed by
+// TString. So e.g. if we have
 // ```
 // export function iso<T>(
 //   isographLiteralText: T & MatchesWhitespaceAndString<'field Query.foo', T>
@@ -54,7 +75,7 @@ type Whitespace<In> = In extends `${WhitespaceCharacter}${infer In}`
 type MatchesWhitespaceAndString<
   TString extends string,
   T
-> = Whitespace<T> extends `${TString}${string}` ? T : never;
+> = WithoutDescription<T> extends `${TString}${string}` ? T : never;
 
 export function iso<T>(
   param: T & MatchesWhitespaceAndString<'field Query.meNameSuccessor', T>
@@ -0,0 +1,81 @@
+import { describe, expect, test, vi } from 'vitest';
+import type { FragmentReference } from '../core/FragmentReference';
+import {
+  createIsographEnvironment,
+  ROOT_ID,
+  type IsographStore,
+} from '../core/IsographEnvironment';
+import { wrapResolvedValue } from '../core/PromiseWrapper';
+import { readButDoNotEvaluate } from '../core/read';
+import type { EagerReaderArtifact, ReaderAst } from '../core/reader';
+
+describe('resolver parameters', () => {
+  test('contain exactly the arguments passed to the client field', () => {
+    const store: IsographStore = {
+      Query: {
+        [ROOT_ID]: {},
+      },
+    };
+    const networkFunction = vi
+      .fn()
+      .mockRejectedValue(new Error('Fetch failed'));
+    const environment = createIsographEnvironment(store, networkFunction);
+
+    const resolver = vi.fn(({ parameters }) => parameters);
+    const childArtifact: EagerReaderArtifact<any, unknown> = {
+      kind: 'EagerReaderArtifact',
+      fieldName: 'Query.Child',
+      readerAst: [],
+      resolver,
+      hasUpdatable: false,
+    };
+
+    // field Query.Parent($userId: ID!, $unrelated: String) {
+    //   Child(id: $userId, first: 10, name: "isograph")
+    // }
+    const parentReaderAst: ReaderAst<any> = [
+      {
+        kind: 'Resolver',
+        alias: 'Child',
+        readerArtifact: childArtifact,
+        arguments: [
+          ['id', { kind: 'Variable', name: 'userId' }],
+          ['first', { kind: 'Literal', value: 10 }],
+          ['name', { kind: 'String', value: 'isograph' }],
+        ],
+        usedRefetchQueries: [],
+      },
+    ];
+    const fragmentReference: FragmentReference<any, unknown> = {
+      kind: 'FragmentReference',
+      readerWithRefetchQueries: wrapResolvedValue({
+        kind: 'ReaderWithRefetchQueries',
+        readerArtifact: {
+          kind: 'EagerReaderArtifact',
+          fieldName: 'Query.Parent',
+          readerAst: parentReaderAst,
+          resolver: ({ data }) => data,
+          hasUpdatable: false,
+        },
+        nestedRefetchQueries: [],
+      }),
+      root: { __link: ROOT_ID, __typename: 'Query' },
+      variables: { userId: '4', unrelated: 'not passed to Child' },
+      networkRequest: wrapResolvedValue(undefined),
+    };
+
+    const data = readButDoNotEvaluate(environment, fragmentReference, {
+      suspendIfInFlight: true,
+      throwOnNetworkError: false,
+    });
+
+    expect(resolver).toHaveBeenCalledTimes(1);
+    expect(data.item).toStrictEqual({
+      Child: {
+        id: '4',
+        first: 10,
+        name: 'isograph',
+      },
+    });
+  });
+});